@@ -0,0 +1,120 @@
+use crate::BloodType;
+
+/// Check if red cells of the donor type can be transfused to the recipient
+///
+/// Based on compatibility rules:
+/// - O- can donate to all types (universal donor)
+/// - AB+ can receive from all types (universal recipient)
+/// - Rh- can donate to Rh+ and Rh-
+/// - Rh+ can only donate to Rh+
+pub fn can_transfuse(donor: BloodType, recipient: BloodType) -> bool {
+    use BloodType::*;
+
+    match (donor, recipient) {
+        // O- is universal donor
+        (ONegative, _) => true,
+
+        // O+ can donate to all positive types
+        (OPositive, APositive | BPositive | ABPositive | OPositive) => true,
+
+        // A- can donate to A and AB (both + and -)
+        (ANegative, APositive | ANegative | ABPositive | ABNegative) => true,
+
+        // A+ can donate to A+ and AB+
+        (APositive, APositive | ABPositive) => true,
+
+        // B- can donate to B and AB (both + and -)
+        (BNegative, BPositive | BNegative | ABPositive | ABNegative) => true,
+
+        // B+ can donate to B+ and AB+
+        (BPositive, BPositive | ABPositive) => true,
+
+        // AB- can donate to AB+ and AB-
+        (ABNegative, ABPositive | ABNegative) => true,
+
+        // AB+ can only donate to AB+
+        (ABPositive, ABPositive) => true,
+
+        // All other combinations are incompatible
+        _ => false,
+    }
+}
+
+/// Check if a unit of the given type may be matched to a recipient
+///
+/// Requires an exact type match unless `accept_compatible` is set, in which
+/// case any type that [`can_transfuse`] to the recipient is accepted.
+pub fn is_acceptable_match(unit: BloodType, recipient: BloodType, accept_compatible: bool) -> bool {
+    if accept_compatible {
+        can_transfuse(unit, recipient)
+    } else {
+        unit == recipient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BloodType::*;
+
+    /// Expected compatibility, one row per donor in `BloodType::ALL` order,
+    /// one column per recipient in the same order
+    const EXPECTED: [[bool; 8]; 8] = [
+        // A+     A-     B+     B-     AB+   AB-    O+     O-
+        [true, false, false, false, true, false, false, false], // A+
+        [true, true, false, false, true, true, false, false],   // A-
+        [false, false, true, false, true, false, false, false], // B+
+        [false, false, true, true, true, true, false, false],   // B-
+        [false, false, false, false, true, false, false, false], // AB+
+        [false, false, false, false, true, true, false, false], // AB-
+        [true, false, true, false, true, false, true, false],   // O+
+        [true, true, true, true, true, true, true, true],       // O-
+    ];
+
+    #[test]
+    fn test_can_transfuse_all_pairs() {
+        for (i, donor) in BloodType::ALL.iter().enumerate() {
+            for (j, recipient) in BloodType::ALL.iter().enumerate() {
+                assert_eq!(
+                    can_transfuse(*donor, *recipient),
+                    EXPECTED[i][j],
+                    "donor {:?} -> recipient {:?}",
+                    donor,
+                    recipient
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_can_transfuse_same_type() {
+        for blood_type in BloodType::ALL {
+            assert!(can_transfuse(blood_type, blood_type));
+        }
+    }
+
+    #[test]
+    fn test_universal_donor_and_recipient() {
+        for blood_type in BloodType::ALL {
+            assert!(can_transfuse(ONegative, blood_type));
+            assert!(can_transfuse(blood_type, ABPositive));
+        }
+    }
+
+    #[test]
+    fn test_rh_positive_never_donates_to_rh_negative() {
+        for donor in [APositive, BPositive, ABPositive, OPositive] {
+            for recipient in [ANegative, BNegative, ABNegative, ONegative] {
+                assert!(!can_transfuse(donor, recipient));
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_acceptable_match() {
+        assert!(is_acceptable_match(APositive, APositive, false));
+        assert!(!is_acceptable_match(ONegative, APositive, false));
+        assert!(is_acceptable_match(ONegative, APositive, true));
+        assert!(!is_acceptable_match(APositive, ONegative, true));
+    }
+}
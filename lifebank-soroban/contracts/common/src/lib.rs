@@ -0,0 +1,11 @@
+#![no_std]
+
+//! Types and rules shared by the LifeBank contracts
+
+pub mod compatibility;
+mod types;
+
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+pub use crate::types::{BloodType, ContractVersion};
@@ -0,0 +1,74 @@
+//! Helpers shared by the contract test suites
+//!
+//! Only compiled for this crate's own tests or when the `testutils` feature
+//! is enabled, which the contract crates do from their dev-dependencies.
+
+use soroban_sdk::{
+    testutils::{Events, Ledger},
+    Env, Symbol, TryFromVal, Val, Vec,
+};
+
+pub const SECONDS_PER_HOUR: u64 = 3600;
+pub const SECONDS_PER_DAY: u64 = 24 * SECONDS_PER_HOUR;
+
+/// Move the ledger clock forward by `hours`
+pub fn advance_hours(env: &Env, hours: u64) {
+    let now = env.ledger().timestamp();
+    env.ledger().set_timestamp(now + hours * SECONDS_PER_HOUR);
+}
+
+/// Move the ledger clock forward by `days`
+pub fn advance_days(env: &Env, days: u64) {
+    advance_hours(env, days * 24);
+}
+
+/// Topics and payload of the last event named `name` published by the
+/// most recent invocation
+pub fn find_event(env: &Env, name: &str) -> Option<(Vec<Val>, Val)> {
+    let name = Symbol::new(env, name);
+    let mut found = None;
+    for (_contract, topics, data) in env.events().all().iter() {
+        if event_name(env, &topics) == Some(name.clone()) {
+            found = Some((topics, data));
+        }
+    }
+    found
+}
+
+/// Decoded payload of the last event named `name` published by the most
+/// recent invocation
+///
+/// Panics when no such event was published.
+pub fn latest_event<T: TryFromVal<Env, Val>>(env: &Env, name: &str) -> T {
+    let (_topics, data) = find_event(env, name).unwrap_or_else(|| panic!("{} not emitted", name));
+    T::try_from_val(env, &data).unwrap_or_else(|_| panic!("{} payload has wrong type", name))
+}
+
+/// Number of events named `name` published by the most recent invocation
+pub fn count_events(env: &Env, name: &str) -> u32 {
+    let name = Symbol::new(env, name);
+    let mut count = 0;
+    for (_contract, topics, _data) in env.events().all().iter() {
+        if event_name(env, &topics) == Some(name.clone()) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Names of the events published by the most recent invocation, in order
+pub fn event_names(env: &Env) -> Vec<Symbol> {
+    let mut names = Vec::new(env);
+    for (_contract, topics, _data) in env.events().all().iter() {
+        if let Some(name) = event_name(env, &topics) {
+            names.push_back(name);
+        }
+    }
+    names
+}
+
+fn event_name(env: &Env, topics: &Vec<Val>) -> Option<Symbol> {
+    topics
+        .get(0)
+        .and_then(|t| Symbol::try_from_val(env, &t).ok())
+}
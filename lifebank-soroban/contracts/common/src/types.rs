@@ -0,0 +1,62 @@
+use crate::compatibility;
+use soroban_sdk::contracttype;
+
+/// Blood type enumeration supporting all major blood groups
+///
+/// Each variant represents a unique combination of ABO and Rh blood typing:
+/// - A+, A-: Type A with positive/negative Rh factor
+/// - B+, B-: Type B with positive/negative Rh factor
+/// - AB+, AB-: Type AB with positive/negative Rh factor
+/// - O+, O-: Type O with positive/negative Rh factor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum BloodType {
+    /// Type A positive (A+)
+    APositive,
+    /// Type A negative (A-)
+    ANegative,
+    /// Type B positive (B+)
+    BPositive,
+    /// Type B negative (B-)
+    BNegative,
+    /// Type AB positive (AB+)
+    ABPositive,
+    /// Type AB negative (AB-)
+    ABNegative,
+    /// Type O positive (O+)
+    OPositive,
+    /// Type O negative (O-)
+    ONegative,
+}
+
+impl BloodType {
+    /// Every blood type, in declaration order
+    pub const ALL: [BloodType; 8] = [
+        BloodType::APositive,
+        BloodType::ANegative,
+        BloodType::BPositive,
+        BloodType::BNegative,
+        BloodType::ABPositive,
+        BloodType::ABNegative,
+        BloodType::OPositive,
+        BloodType::ONegative,
+    ];
+
+    /// Check if this blood type can donate to the recipient blood type
+    ///
+    /// See [`compatibility::can_transfuse`].
+    pub fn can_donate_to(&self, recipient: &BloodType) -> bool {
+        compatibility::can_transfuse(*self, *recipient)
+    }
+}
+
+/// Semantic version of a deployed contract's interface and struct layouts
+///
+/// A new minor version only adds entrypoints or fields clients may ignore;
+/// a new major version changes layouts clients decode.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub struct ContractVersion {
+    pub major: u32,
+    pub minor: u32,
+}
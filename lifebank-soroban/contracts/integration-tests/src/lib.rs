@@ -0,0 +1,6 @@
+#![no_std]
+
+//! End-to-end tests that run the inventory and request contracts together
+//!
+//! The scenarios live in `tests/`; this crate has no library code of its
+//! own.
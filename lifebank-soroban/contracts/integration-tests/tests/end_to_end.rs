@@ -0,0 +1,413 @@
+use std::cell::RefCell;
+
+use inventory_contract::testutils::UnitFixture;
+use inventory_contract::{
+    BankRole, BloodStatus, CustodyChange, CustodyChangedEvent, InventoryContract,
+    InventoryContractClient,
+};
+use lifebank_common::testutils::advance_days;
+use lifebank_common::BloodType;
+use request_contract::testutils::{authorized_hospital, RequestFixture};
+use request_contract::{
+    ExpirySweepCompletedEvent, RequestContract, RequestContractClient, RequestStatus,
+    RequestStatusChangedEvent, UnitAssignment,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, Env, String, Symbol, TryFromVal, Val, Vec,
+};
+
+/// Both contracts registered in one `Env`, wired to each other, with a
+/// shared admin and one authorized hospital
+///
+/// `record` appends the events of the most recent invocation to a log
+/// that spans every step of a scenario.
+struct Harness<'a> {
+    env: Env,
+    admin: Address,
+    hospital: Address,
+    requests: RequestContractClient<'a>,
+    inventory: InventoryContractClient<'a>,
+    log: RefCell<std::vec::Vec<(Address, Symbol, Val)>>,
+}
+
+impl<'a> Harness<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1000);
+
+        let admin = Address::generate(&env);
+
+        let requests_id = env.register(RequestContract, ());
+        let requests = RequestContractClient::new(&env, &requests_id);
+        requests.initialize(&admin, &vec![&env], &0);
+
+        let inventory_id = env.register(InventoryContract, ());
+        let inventory = InventoryContractClient::new(&env, &inventory_id);
+        inventory.initialize(&admin, &vec![&env], &0);
+
+        inventory.set_request_contract(&requests_id);
+        requests.set_inventory_contract(&inventory_id);
+
+        let hospital = authorized_hospital(&env, &requests);
+
+        Harness {
+            env,
+            admin,
+            hospital,
+            requests,
+            inventory,
+            log: RefCell::new(std::vec::Vec::new()),
+        }
+    }
+
+    /// Append the events published by the most recent invocation
+    fn record(&self) {
+        let mut log = self.log.borrow_mut();
+        for (contract, topics, data) in self.env.events().all().iter() {
+            let name = Symbol::try_from_val(&self.env, &topics.get(0).unwrap()).unwrap();
+            log.push((contract, name, data));
+        }
+    }
+
+    /// Create an urgent A+ request due in one day, as the hospital
+    fn create_request(&self) -> u64 {
+        let id = RequestFixture::new(&self.env)
+            .by(&self.hospital)
+            .create(&self.requests);
+        self.record();
+        id
+    }
+
+    /// Register `count` A+ units and reserve them for `request_id`
+    fn reserve_units(&self, request_id: u64, count: u32) -> Vec<u64> {
+        let unit_ids =
+            UnitFixture::new(&self.env).register_many(&self.inventory, &self.admin, count);
+        for unit_id in unit_ids.iter() {
+            self.inventory.reserve_unit(
+                &unit_id,
+                &request_id,
+                &BloodType::APositive,
+                &false,
+                &self.admin,
+                &None,
+            );
+            self.record();
+        }
+        unit_ids
+    }
+
+    /// Logged events whose name is one of `names`, as `(contract, name)`
+    fn logged(&self, names: &[&str]) -> std::vec::Vec<(Address, Symbol)> {
+        let names: std::vec::Vec<Symbol> = names
+            .iter()
+            .map(|name| Symbol::new(&self.env, name))
+            .collect();
+        self.log
+            .borrow()
+            .iter()
+            .filter(|(_, name, _)| names.contains(name))
+            .map(|(contract, name, _)| (contract.clone(), name.clone()))
+            .collect()
+    }
+
+    /// Status changes logged by the request contract, as `(id, old, new)`
+    fn request_transitions(&self) -> std::vec::Vec<(u64, RequestStatus, RequestStatus)> {
+        let name = Symbol::new(&self.env, "request_status_changed");
+        self.log
+            .borrow()
+            .iter()
+            .filter(|(contract, event, _)| *contract == self.requests.address && *event == name)
+            .map(|(_, _, data)| {
+                let event = RequestStatusChangedEvent::try_from_val(&self.env, data).unwrap();
+                (event.request_id, event.old_status, event.new_status)
+            })
+            .collect()
+    }
+
+    fn requests_event(&self, name: &str) -> (Address, Symbol) {
+        (self.requests.address.clone(), Symbol::new(&self.env, name))
+    }
+
+    fn inventory_event(&self, name: &str) -> (Address, Symbol) {
+        (self.inventory.address.clone(), Symbol::new(&self.env, name))
+    }
+}
+
+#[test]
+fn test_request_delivered_end_to_end() {
+    let h = Harness::new();
+
+    let request_id = h.create_request();
+
+    h.requests.approve_request(&request_id);
+    h.record();
+
+    let unit_ids = h.reserve_units(request_id, 2);
+
+    let mut assignments = vec![&h.env];
+    for unit_id in unit_ids.iter() {
+        assignments.push_back(UnitAssignment {
+            unit_id,
+            blood_type: BloodType::APositive,
+            quantity_ml: 450,
+            bank_id: h.admin.clone(),
+        });
+    }
+    h.requests.assign_blood_units(&request_id, &assignments);
+    h.record();
+
+    h.requests
+        .update_request_status(&request_id, &RequestStatus::InDelivery);
+    h.record();
+    for unit_id in unit_ids.iter() {
+        h.inventory
+            .update_status(&unit_id, &BloodStatus::InTransit, &h.admin, &None);
+        h.record();
+    }
+
+    h.env.ledger().set_timestamp(5000);
+    h.requests.complete_request(&h.hospital, &request_id);
+    h.record();
+    for unit_id in unit_ids.iter() {
+        h.inventory
+            .mark_delivered(&unit_id, &h.admin, &String::from_str(&h.env, "Ward 3"));
+        h.record();
+    }
+
+    assert_eq!(
+        h.logged(&[
+            "request_created",
+            "request_status_changed",
+            "units_assigned",
+            "status_changed",
+            "blood_reserved",
+        ]),
+        std::vec![
+            h.requests_event("request_created"),
+            h.requests_event("request_status_changed"),
+            h.inventory_event("status_changed"),
+            h.inventory_event("blood_reserved"),
+            h.inventory_event("status_changed"),
+            h.inventory_event("blood_reserved"),
+            h.requests_event("units_assigned"),
+            h.requests_event("request_status_changed"),
+            h.inventory_event("status_changed"),
+            h.inventory_event("status_changed"),
+            h.requests_event("request_status_changed"),
+            h.inventory_event("status_changed"),
+            h.inventory_event("status_changed"),
+        ]
+    );
+    assert_eq!(
+        h.request_transitions(),
+        std::vec![
+            (request_id, RequestStatus::Pending, RequestStatus::Approved),
+            (
+                request_id,
+                RequestStatus::Approved,
+                RequestStatus::InDelivery
+            ),
+            (
+                request_id,
+                RequestStatus::InDelivery,
+                RequestStatus::Completed
+            ),
+        ]
+    );
+
+    let request = h.requests.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Completed);
+    assert_eq!(request.fulfilled_at, Some(5000));
+    assert_eq!(request.assigned_units, unit_ids);
+    for unit_id in unit_ids.iter() {
+        let unit = h.inventory.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Delivered);
+        assert_eq!(unit.reserved_for, Some(request_id));
+    }
+}
+
+#[test]
+fn test_custody_chain_end_to_end() {
+    let h = Harness::new();
+
+    let courier = Address::generate(&h.env);
+    h.inventory
+        .grant_role(&h.admin, &courier, &BankRole::Registrar);
+
+    let request_id = h.create_request();
+    h.requests.approve_request(&request_id);
+    let unit_ids = h.reserve_units(request_id, 1);
+    let unit_id = unit_ids.get(0).unwrap();
+    assert_eq!(h.inventory.get_blood_unit(&unit_id).custodian, h.admin);
+    h.log.borrow_mut().clear();
+
+    h.inventory
+        .update_status(&unit_id, &BloodStatus::InTransit, &h.admin, &None);
+    h.env.ledger().set_timestamp(2000);
+    h.inventory.record_pickup(&courier, &unit_ids);
+    h.record();
+    assert_eq!(h.inventory.get_blood_unit(&unit_id).custodian, courier);
+
+    h.env.ledger().set_timestamp(3000);
+    h.inventory.record_receipt(&h.hospital, &unit_ids);
+    h.record();
+
+    let unit = h.inventory.get_blood_unit(&unit_id);
+    assert_eq!(unit.custodian, h.hospital);
+    assert_eq!(unit.status, BloodStatus::Delivered);
+
+    let handoffs: std::vec::Vec<CustodyChangedEvent> = h
+        .log
+        .borrow()
+        .iter()
+        .filter(|(_, name, _)| *name == Symbol::new(&h.env, "custody_changed"))
+        .map(|(_, _, data)| CustodyChangedEvent::try_from_val(&h.env, data).unwrap())
+        .collect();
+    assert_eq!(
+        handoffs,
+        std::vec![
+            CustodyChangedEvent {
+                blood_unit_id: unit_id,
+                from_custodian: h.admin.clone(),
+                to_custodian: courier.clone(),
+                changed_at: 2000,
+            },
+            CustodyChangedEvent {
+                blood_unit_id: unit_id,
+                from_custodian: courier.clone(),
+                to_custodian: h.hospital.clone(),
+                changed_at: 3000,
+            },
+        ]
+    );
+    assert_eq!(
+        h.inventory.get_custody_history(&unit_id),
+        vec![
+            &h.env,
+            CustodyChange {
+                blood_unit_id: unit_id,
+                from_custodian: h.admin.clone(),
+                to_custodian: courier.clone(),
+                changed_at: 2000,
+            },
+            CustodyChange {
+                blood_unit_id: unit_id,
+                from_custodian: courier,
+                to_custodian: h.hospital.clone(),
+                changed_at: 3000,
+            },
+        ]
+    );
+
+    // Nobody can take custody of a delivered unit
+    assert!(h
+        .inventory
+        .try_record_pickup(&Address::generate(&h.env), &unit_ids)
+        .is_err());
+}
+
+#[test]
+fn test_cancellation_releases_units_end_to_end() {
+    let h = Harness::new();
+
+    let request_id = h.create_request();
+    h.requests.approve_request(&request_id);
+    h.record();
+    let unit_ids = h.reserve_units(request_id, 2);
+    h.log.borrow_mut().clear();
+
+    h.requests.cancel_request(&request_id, &h.hospital);
+    h.record();
+
+    // The inventory frees the units inside the cancellation call, before
+    // the request contract reports the cancellation
+    assert_eq!(
+        h.logged(&[
+            "reservation_released",
+            "status_changed",
+            "request_status_changed",
+        ]),
+        std::vec![
+            h.inventory_event("reservation_released"),
+            h.inventory_event("status_changed"),
+            h.inventory_event("reservation_released"),
+            h.inventory_event("status_changed"),
+            h.requests_event("request_status_changed"),
+        ]
+    );
+    assert_eq!(
+        h.request_transitions(),
+        std::vec![(
+            request_id,
+            RequestStatus::Approved,
+            RequestStatus::Cancelled
+        )]
+    );
+
+    for unit_id in unit_ids.iter() {
+        let unit = h.inventory.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Available);
+        assert_eq!(unit.reserved_for, None);
+    }
+    assert_eq!(
+        h.inventory.get_next_unit_fifo(&BloodType::APositive),
+        Some(unit_ids.get(0).unwrap())
+    );
+}
+
+#[test]
+fn test_expiry_releases_units_end_to_end() {
+    let h = Harness::new();
+
+    let approved = h.create_request();
+    h.requests.approve_request(&approved);
+    h.record();
+    let unit_ids = h.reserve_units(approved, 2);
+    let pending = h.create_request();
+    h.log.borrow_mut().clear();
+
+    // Both requests are due one day after creation
+    advance_days(&h.env, 2);
+    assert_eq!(h.requests.expire_overdue_requests(&None), 2);
+    h.record();
+
+    // Pending requests are swept before approved ones; only the approved
+    // request held units
+    assert_eq!(
+        h.logged(&[
+            "reservation_released",
+            "status_changed",
+            "request_status_changed",
+            "expiry_sweep_completed",
+        ]),
+        std::vec![
+            h.requests_event("request_status_changed"),
+            h.inventory_event("reservation_released"),
+            h.inventory_event("status_changed"),
+            h.inventory_event("reservation_released"),
+            h.inventory_event("status_changed"),
+            h.requests_event("request_status_changed"),
+            h.requests_event("expiry_sweep_completed"),
+        ]
+    );
+    assert_eq!(
+        h.request_transitions(),
+        std::vec![
+            (pending, RequestStatus::Pending, RequestStatus::Expired),
+            (approved, RequestStatus::Approved, RequestStatus::Expired),
+        ]
+    );
+
+    let (_, _, data) = h.log.borrow().last().unwrap().clone();
+    let sweep = ExpirySweepCompletedEvent::try_from_val(&h.env, &data).unwrap();
+    assert_eq!(sweep.expired_count, 2);
+    assert!(!sweep.more_remaining);
+
+    for unit_id in unit_ids.iter() {
+        let unit = h.inventory.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Available);
+        assert_eq!(unit.reserved_for, None);
+    }
+}
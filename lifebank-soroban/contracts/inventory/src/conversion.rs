@@ -0,0 +1,231 @@
+use crate::error::ContractError;
+use crate::types::{ComponentSplit, ProductType};
+use soroban_sdk::Vec;
+
+/// Volume fractions are expressed in basis points of the parent's volume
+pub const FRACTION_SCALE_BPS: u32 = 10_000;
+
+/// Lowest volume fraction the admin may configure for a derivation
+pub const MIN_FRACTION_BPS: u32 = 500;
+
+/// Highest volume fraction the admin may configure: a child never holds
+/// more than its parent
+pub const MAX_FRACTION_BPS: u32 = FRACTION_SCALE_BPS;
+
+/// Maximum number of units a single split may produce
+pub const MAX_SPLIT_CHILDREN: u32 = 4;
+
+/// Built-in share of a parent's volume a child product may take, in basis
+/// points, or None when the child cannot be derived from the parent
+///
+/// Based on processing rules:
+/// - Whole blood is separated into red cells, plasma and platelets
+/// - A separated component can only be divided into aliquots of itself
+/// - Components are never converted into one another
+pub fn default_max_fraction_bps(parent: ProductType, child: ProductType) -> Option<u32> {
+    use ProductType::*;
+
+    match (parent, child) {
+        // Separation of a whole blood donation
+        (WholeBlood, RedCells) => Some(6_500),
+        (WholeBlood, Plasma) => Some(6_000),
+        (WholeBlood, Platelets) => Some(2_500),
+
+        // Aliquots (e.g. pediatric doses) of a separated component
+        (RedCells, RedCells) | (Platelets, Platelets) | (Plasma, Plasma) => {
+            Some(FRACTION_SCALE_BPS)
+        }
+
+        // Everything else, including whole blood from a component
+        _ => None,
+    }
+}
+
+/// Check if units of `child` may be derived from a unit of `parent`
+pub fn is_allowed_derivation(parent: ProductType, child: ProductType) -> bool {
+    default_max_fraction_bps(parent, child).is_some()
+}
+
+/// Largest child volume a fraction of the parent's volume allows, rounded
+/// down
+pub fn max_child_ml(parent_ml: u32, fraction_bps: u32) -> u32 {
+    (parent_ml as u64 * fraction_bps as u64 / FRACTION_SCALE_BPS as u64) as u32
+}
+
+/// Check a split of a parent unit against the conversion table
+///
+/// `fraction_of` returns the fraction in force for a derivation, None for
+/// derivations the table does not allow. Each child must stay within its
+/// fraction of the parent's volume, and all children together within the
+/// parent's volume.
+///
+/// # Returns
+/// - Err(InvalidInput) if there are no children or more than
+///   `MAX_SPLIT_CHILDREN`
+/// - Err(InvalidComponentDerivation) if a child's product cannot be derived
+///   from the parent's
+/// - Err(ChildVolumeExceedsParent) if a child exceeds its fraction or the
+///   children exceed the parent's volume
+pub fn validate_split(
+    parent: ProductType,
+    parent_ml: u32,
+    children: &Vec<ComponentSplit>,
+    fraction_of: impl Fn(ProductType, ProductType) -> Option<u32>,
+) -> Result<(), ContractError> {
+    if children.is_empty() || children.len() > MAX_SPLIT_CHILDREN {
+        return Err(ContractError::InvalidInput);
+    }
+
+    let mut total_ml = 0u32;
+    for child in children.iter() {
+        let fraction_bps =
+            fraction_of(parent, child.product).ok_or(ContractError::InvalidComponentDerivation)?;
+        if child.quantity_ml > max_child_ml(parent_ml, fraction_bps) {
+            return Err(ContractError::ChildVolumeExceedsParent);
+        }
+        total_ml = total_ml.saturating_add(child.quantity_ml);
+    }
+
+    if total_ml > parent_ml {
+        return Err(ContractError::ChildVolumeExceedsParent);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{vec, Env};
+    use ProductType::*;
+
+    /// Expected default fractions, one row per parent in `ProductType::ALL`
+    /// order, one column per child in the same order
+    const EXPECTED: [[Option<u32>; 4]; 4] = [
+        // WholeBlood  RedCells     Platelets    Plasma
+        [None, Some(6_500), Some(2_500), Some(6_000)], // WholeBlood
+        [None, Some(10_000), None, None],              // RedCells
+        [None, None, Some(10_000), None],              // Platelets
+        [None, None, None, Some(10_000)],              // Plasma
+    ];
+
+    fn split(env: &Env, parts: &[(ProductType, u32)]) -> Vec<ComponentSplit> {
+        let mut children = Vec::new(env);
+        for (product, quantity_ml) in parts {
+            children.push_back(ComponentSplit {
+                product: *product,
+                quantity_ml: *quantity_ml,
+            });
+        }
+        children
+    }
+
+    #[test]
+    fn test_default_fractions_all_pairs() {
+        for (i, parent) in ProductType::ALL.iter().enumerate() {
+            for (j, child) in ProductType::ALL.iter().enumerate() {
+                assert_eq!(
+                    default_max_fraction_bps(*parent, *child),
+                    EXPECTED[i][j],
+                    "parent {:?} -> child {:?}",
+                    parent,
+                    child
+                );
+                assert_eq!(
+                    is_allowed_derivation(*parent, *child),
+                    EXPECTED[i][j].is_some()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_fractions_within_hard_bounds() {
+        for parent in ProductType::ALL {
+            for child in ProductType::ALL {
+                if let Some(fraction_bps) = default_max_fraction_bps(parent, child) {
+                    assert!((MIN_FRACTION_BPS..=MAX_FRACTION_BPS).contains(&fraction_bps));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_child_ml_rounds_down() {
+        assert_eq!(max_child_ml(450, 6_500), 292);
+        assert_eq!(max_child_ml(450, FRACTION_SCALE_BPS), 450);
+        assert_eq!(max_child_ml(0, 6_500), 0);
+        assert_eq!(max_child_ml(u32::MAX, FRACTION_SCALE_BPS), u32::MAX);
+    }
+
+    #[test]
+    fn test_validate_split_of_whole_blood() {
+        let env = Env::default();
+
+        let children = split(&env, &[(RedCells, 280), (Plasma, 150)]);
+        assert_eq!(
+            validate_split(WholeBlood, 450, &children, default_max_fraction_bps),
+            Ok(())
+        );
+
+        // More red cells than the donation can yield
+        let children = split(&env, &[(RedCells, 500)]);
+        assert_eq!(
+            validate_split(WholeBlood, 450, &children, default_max_fraction_bps),
+            Err(ContractError::ChildVolumeExceedsParent)
+        );
+
+        // Each child within its fraction, but not together
+        let children = split(&env, &[(RedCells, 290), (Plasma, 250)]);
+        assert_eq!(
+            validate_split(WholeBlood, 450, &children, default_max_fraction_bps),
+            Err(ContractError::ChildVolumeExceedsParent)
+        );
+    }
+
+    #[test]
+    fn test_validate_split_rejects_disallowed_derivations() {
+        let env = Env::default();
+
+        let children = split(&env, &[(Platelets, 100)]);
+        assert_eq!(
+            validate_split(Plasma, 250, &children, default_max_fraction_bps),
+            Err(ContractError::InvalidComponentDerivation)
+        );
+        let children = split(&env, &[(WholeBlood, 100)]);
+        assert_eq!(
+            validate_split(RedCells, 300, &children, default_max_fraction_bps),
+            Err(ContractError::InvalidComponentDerivation)
+        );
+    }
+
+    #[test]
+    fn test_validate_split_child_count() {
+        let env = Env::default();
+
+        assert_eq!(
+            validate_split(Plasma, 600, &vec![&env], default_max_fraction_bps),
+            Err(ContractError::InvalidInput)
+        );
+        let children = split(&env, &[(Plasma, 100); MAX_SPLIT_CHILDREN as usize + 1]);
+        assert_eq!(
+            validate_split(Plasma, 600, &children, default_max_fraction_bps),
+            Err(ContractError::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn test_validate_split_uses_configured_fraction() {
+        let env = Env::default();
+        let children = split(&env, &[(RedCells, 250)]);
+
+        let strict = |parent, child| default_max_fraction_bps(parent, child).map(|_| 5_000);
+        assert_eq!(
+            validate_split(WholeBlood, 450, &children, strict),
+            Err(ContractError::ChildVolumeExceedsParent)
+        );
+        assert_eq!(
+            validate_split(WholeBlood, 450, &children, default_max_fraction_bps),
+            Ok(())
+        );
+    }
+}
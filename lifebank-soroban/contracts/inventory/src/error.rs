@@ -18,6 +18,8 @@ pub enum ContractError {
     InvalidTimestamp = 15,
     InvalidQuantity = 16,
     InvalidExpiration = 17,
+    QuantityNotAligned = 18,
+    TooManyPhenotypeTags = 19,
 
     // State errors (20-29)
     AlreadyExists = 20,
@@ -25,13 +27,31 @@ pub enum ContractError {
     Expired = 22,
     BloodUnitExpired = 23,
     DuplicateBloodUnit = 24,
+    CapacityExceeded = 25,
+    NoPendingAdmin = 26,
+    CounterOverflow = 27,
+    RateLimitExceeded = 28,
+    VoidWindowClosed = 29,
 
     // Permission errors (30-39)
     InsufficientBalance = 30,
     InsufficientPermissions = 31,
     NotAuthorizedBloodBank = 32,
+    RegisterOnlyBank = 33,
 
     // Blood-specific errors (40-49)
     BloodUnitNotAvailable = 40,
     InvalidStatusTransition = 41,
+    IncompatibleBloodType = 42,
+    DonorDeferred = 43,
+    CampaignClosed = 44,
+    AutologousMismatch = 45,
+    TemperatureExcursion = 46,
+    InvalidComponentDerivation = 47,
+    ChildVolumeExceedsParent = 48,
+    UnitAlreadyModified = 49,
+
+    // Loan errors (50-59)
+    LoanAlreadySettled = 50,
+    ReplacementMismatch = 51,
 }
@@ -1,58 +1,949 @@
-use crate::types::{BloodRegisteredEvent, BloodType, StatusChangeEvent};
-use soroban_sdk::{Address, Env, String, Symbol};
+// Events keep their (topics, data) tuple layout, published through the
+// `Events::publish` API that `#[contractevent]` deprecates
+#![allow(deprecated)]
+
+use crate::topics;
+use crate::types::{
+    AdminTransferCancelledEvent, AdminTransferProposedEvent, AdminTransferredEvent,
+    AuthorizationTier, BankAuthorizationChangedEvent, BankCapacityChangedEvent, BankRole,
+    BankTierChangedEvent, BloodDiscardedEvent, BloodExpiredEvent, BloodRegisteredEvent,
+    BloodReservedEvent, BloodStatus, BloodType, BloodUnit, Campaign, CampaignCreatedEvent,
+    ConversionFractionChangedEvent, CustodyChangedEvent, DiscardReason, DonorDeferralChangedEvent,
+    DonorMilestoneEvent, DonorTemporarilyDeferredEvent, ExpirySweepCompletedEvent,
+    ImplicitAuthorizationChangedEvent, LoanOverdueEvent, LoanSettledEvent,
+    LowStockAlertChangedEvent, LowStockAlertConfig, LowStockEvent, MatchAvailableEvent,
+    PhenotypeTagsSetEvent, ProductType, QuantityStepChangedEvent,
+    RegistrationRateLimitChangedEvent, RegistrationVoidedEvent, RequestContractChangedEvent,
+    ReservationReleasedEvent, RoleChangedEvent, ShelfLife, ShelfLifeChangedEvent,
+    StatusChangeEvent, TemperatureExcursionEvent, TemperatureRange, TemperatureRangeChangedEvent,
+    TemperatureReading, UnitCorrectedEvent, UnitLentEvent, UnitLoan, UnitLocationChangedEvent,
+    UnitSplitEvent, UnitsPooledEvent, VoidWindowChangedEvent, WatchedTypeChangedEvent,
+};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
+
+/// Topics of an event about a single blood unit:
+/// `(name, bank_id, blood_type)`
+fn unit_topics(env: &Env, name: &str, blood_unit: &BloodUnit) -> (Symbol, Address, BloodType) {
+    (
+        Symbol::new(env, name),
+        blood_unit.bank_id.clone(),
+        blood_unit.blood_type,
+    )
+}
 
 /// Emit a BloodRegistered event
 ///
 /// # Arguments
 /// * `env` - Contract environment
-/// * `blood_unit_id` - Unique ID of the registered blood unit
-/// * `bank_id` - Blood bank that registered the unit
-/// * `blood_type` - Type of blood
-/// * `quantity_ml` - Quantity in milliliters
-/// * `expiration_timestamp` - When the unit expires
-pub fn emit_blood_registered(
+/// * `blood_unit` - The unit just registered
+pub fn emit_blood_registered(env: &Env, blood_unit: &BloodUnit) {
+    let event = BloodRegisteredEvent {
+        blood_unit_id: blood_unit.id,
+        bank_id: blood_unit.bank_id.clone(),
+        registered_by: blood_unit.registered_by.clone(),
+        blood_type: blood_unit.blood_type,
+        quantity_ml: blood_unit.quantity_ml,
+        expiration_timestamp: blood_unit.expiration_timestamp,
+        registered_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        unit_topics(env, topics::BLOOD_REGISTERED, blood_unit),
+        event,
+    );
+}
+
+/// Emit a UnitsPooled event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `pooled_unit` - The new pooled unit, listing its sources
+pub fn emit_units_pooled(env: &Env, pooled_unit: &BloodUnit) {
+    let event = UnitsPooledEvent {
+        pooled_unit_id: pooled_unit.id,
+        bank_id: pooled_unit.bank_id.clone(),
+        source_unit_ids: pooled_unit.pooled_from.clone(),
+        quantity_ml: pooled_unit.quantity_ml,
+        expiration_timestamp: pooled_unit.expiration_timestamp,
+        pooled_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish(unit_topics(env, topics::UNITS_POOLED, pooled_unit), event);
+}
+
+/// Emit a UnitSplit event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `parent` - The unit that was split, already Split
+/// * `child_unit_ids` - The new component units
+pub fn emit_unit_split(env: &Env, parent: &BloodUnit, child_unit_ids: &Vec<u64>) {
+    let event = UnitSplitEvent {
+        parent_unit_id: parent.id,
+        bank_id: parent.bank_id.clone(),
+        child_unit_ids: child_unit_ids.clone(),
+        split_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish(unit_topics(env, topics::UNIT_SPLIT, parent), event);
+}
+
+/// Emit a StatusChange event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit` - The unit, already in its new status
+/// * `from_status` - Status before the change
+/// * `authorized_by` - Who authorized the change
+/// * `reason` - Optional reason for the change
+pub fn emit_status_change(
+    env: &Env,
+    blood_unit: &BloodUnit,
+    from_status: BloodStatus,
+    authorized_by: &Address,
+    reason: Option<String>,
+) {
+    let event = StatusChangeEvent {
+        blood_unit_id: blood_unit.id,
+        from_status,
+        to_status: blood_unit.status,
+        authorized_by: authorized_by.clone(),
+        changed_at: env.ledger().timestamp(),
+        reason,
+    };
+
+    env.events()
+        .publish(unit_topics(env, topics::STATUS_CHANGED, blood_unit), event);
+}
+
+/// Emit a BloodDiscarded event
+///
+/// Wastage monitors can subscribe per type through the blood type topic.
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit` - The unit just discarded
+/// * `discard_reason` - Hemovigilance category of the discard
+/// * `reason` - Why the unit was discarded, in free text
+pub fn emit_blood_discarded(
+    env: &Env,
+    blood_unit: &BloodUnit,
+    discard_reason: DiscardReason,
+    reason: Option<String>,
+) {
+    let event = BloodDiscardedEvent {
+        blood_unit_id: blood_unit.id,
+        bank_id: blood_unit.bank_id.clone(),
+        blood_type: blood_unit.blood_type,
+        quantity_ml: blood_unit.quantity_ml,
+        discard_reason,
+        reason,
+        discarded_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish(unit_topics(env, topics::BLOOD_DISCARDED, blood_unit), event);
+}
+
+/// Emit a RegistrationVoided event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit` - The unit just voided
+pub fn emit_registration_voided(env: &Env, blood_unit: &BloodUnit) {
+    let event = RegistrationVoidedEvent {
+        blood_unit_id: blood_unit.id,
+        bank_id: blood_unit.bank_id.clone(),
+        blood_type: blood_unit.blood_type,
+        quantity_ml: blood_unit.quantity_ml,
+        registered_at: blood_unit.donation_timestamp,
+        voided_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        unit_topics(env, topics::REGISTRATION_VOIDED, blood_unit),
+        event,
+    );
+}
+
+/// Emit a BloodExpired event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit` - The unit just marked expired
+pub fn emit_blood_expired(env: &Env, blood_unit: &BloodUnit) {
+    let event = BloodExpiredEvent {
+        blood_unit_id: blood_unit.id,
+        bank_id: blood_unit.bank_id.clone(),
+        blood_type: blood_unit.blood_type,
+        quantity_ml: blood_unit.quantity_ml,
+        expired_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish(unit_topics(env, topics::BLOOD_EXPIRED, blood_unit), event);
+}
+
+/// Emit an ExpirySweepCompleted event at the end of an expiry sweep
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `expired_ids` - IDs expired by the sweep
+/// * `more_remaining` - Whether the limit left overdue units behind
+pub fn emit_expiry_sweep_completed(env: &Env, expired_ids: &Vec<u64>, more_remaining: bool) {
+    let event = ExpirySweepCompletedEvent {
+        expired_count: expired_ids.len(),
+        first_id: expired_ids.iter().min().unwrap_or(0),
+        last_id: expired_ids.iter().max().unwrap_or(0),
+        more_remaining,
+        swept_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::EXPIRY_SWEEP_COMPLETED),), event);
+}
+
+/// Emit a PhenotypeTagsSet event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit` - The unit, already carrying its new tags
+/// * `previous_tags` - Tags recorded before the change
+pub fn emit_phenotype_tags_set(env: &Env, blood_unit: &BloodUnit, previous_tags: Vec<Symbol>) {
+    let event = PhenotypeTagsSetEvent {
+        blood_unit_id: blood_unit.id,
+        bank_id: blood_unit.bank_id.clone(),
+        previous_tags,
+        phenotype_tags: blood_unit.phenotype_tags.clone(),
+        set_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        unit_topics(env, topics::PHENOTYPE_TAGS_SET, blood_unit),
+        event,
+    );
+}
+
+/// Emit a UnitLocationChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit_id` - ID of the blood unit that moved
+/// * `bank_id` - Bank that owns the unit
+/// * `from_location` - Previous location, if any
+/// * `to_location` - New location, or None when the unit left storage
+pub fn emit_unit_location_changed(
     env: &Env,
     blood_unit_id: u64,
     bank_id: &Address,
-    blood_type: BloodType,
-    quantity_ml: u32,
-    expiration_timestamp: u64,
+    from_location: Option<String>,
+    to_location: Option<String>,
 ) {
-    let registered_at = env.ledger().timestamp();
+    let changed_at = env.ledger().timestamp();
 
-    let event = BloodRegisteredEvent {
+    let event = UnitLocationChangedEvent {
         blood_unit_id,
         bank_id: bank_id.clone(),
-        blood_type,
-        quantity_ml,
-        expiration_timestamp,
-        registered_at,
+        from_location,
+        to_location,
+        changed_at,
     };
 
     env.events()
-        .publish((Symbol::new(env, "blood_registered"),), event);
+        .publish((Symbol::new(env, topics::UNIT_LOCATION_CHANGED),), event);
 }
 
+/// Emit a CustodyChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit` - The unit, already carrying its new custodian
+/// * `from_custodian` - Custodian that handed the unit over
+pub fn emit_custody_changed(env: &Env, blood_unit: &BloodUnit, from_custodian: &Address) {
+    let event = CustodyChangedEvent {
+        blood_unit_id: blood_unit.id,
+        from_custodian: from_custodian.clone(),
+        to_custodian: blood_unit.custodian.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
 
-pub fn emit_status_change(
+    env.events()
+        .publish(unit_topics(env, topics::CUSTODY_CHANGED, blood_unit), event);
+}
+
+/// Emit a DonorMilestone event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `donor_id` - Donor who reached the milestone
+/// * `milestone` - Donation count reached
+pub fn emit_donor_milestone(env: &Env, donor_id: &Address, milestone: u32) {
+    let event = DonorMilestoneEvent {
+        donor_id: donor_id.clone(),
+        milestone,
+        reached_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::DONOR_MILESTONE),), event);
+}
+
+/// Emit a BloodReserved event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit` - The unit just reserved
+/// * `request_id` - Request the unit is reserved for
+pub fn emit_blood_reserved(env: &Env, blood_unit: &BloodUnit, request_id: u64) {
+    let event = BloodReservedEvent {
+        unit_ids: Vec::from_array(env, [blood_unit.id]),
+        request_id,
+        bank_id: blood_unit.bank_id.clone(),
+        total_ml: blood_unit.quantity_ml,
+        reserved_until: blood_unit.expiration_timestamp,
+        reserved_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish(unit_topics(env, topics::BLOOD_RESERVED, blood_unit), event);
+}
+
+/// Emit a ReservationReleased event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit` - The unit, already in the status it moved to
+/// * `request_id` - Request the unit was reserved for
+pub fn emit_reservation_released(env: &Env, blood_unit: &BloodUnit, request_id: u64) {
+    let event = ReservationReleasedEvent {
+        blood_unit_id: blood_unit.id,
+        request_id,
+        status: blood_unit.status,
+        released_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        unit_topics(env, topics::RESERVATION_RELEASED, blood_unit),
+        event,
+    );
+}
+
+/// Emit an AdminTransferProposed event under the `admin_proposed` topic
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `current_admin` - Admin proposing the transfer
+/// * `proposed_admin` - Address that must accept the role
+pub fn emit_admin_transfer_proposed(env: &Env, current_admin: &Address, proposed_admin: &Address) {
+    let event = AdminTransferProposedEvent {
+        current_admin: current_admin.clone(),
+        proposed_admin: proposed_admin.clone(),
+        proposed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::ADMIN_PROPOSED),), event);
+}
+
+/// Emit an AdminTransferCancelled event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `admin` - Admin withdrawing the proposal
+/// * `cancelled_admin` - Address that was proposed
+pub fn emit_admin_transfer_cancelled(env: &Env, admin: &Address, cancelled_admin: &Address) {
+    let event = AdminTransferCancelledEvent {
+        admin: admin.clone(),
+        cancelled_admin: cancelled_admin.clone(),
+        cancelled_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::ADMIN_TRANSFER_CANCELLED),), event);
+}
+
+/// Emit an AdminTransferred event under the `admin_changed` topic
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `old_admin` - Previous admin
+/// * `new_admin` - Admin that accepted the role
+pub fn emit_admin_transferred(env: &Env, old_admin: &Address, new_admin: &Address) {
+    let event = AdminTransferredEvent {
+        old_admin: old_admin.clone(),
+        new_admin: new_admin.clone(),
+        transferred_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::ADMIN_CHANGED),), event);
+}
+
+/// Emit an ImplicitAuthorizationChanged event for a blood bank
+/// authorization gained or lost through the admin role
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `account` - Address whose authorization changed
+/// * `authorized` - Whether the address is now authorized
+pub fn emit_implicit_authorization_changed(env: &Env, account: &Address, authorized: bool) {
+    let event = ImplicitAuthorizationChangedEvent {
+        account: account.clone(),
+        authorized,
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        (Symbol::new(env, topics::IMPLICIT_BANK_AUTH_CHANGED),),
+        event,
+    );
+}
+
+/// Emit a RoleGranted event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `bank_id` - Bank granting the role
+/// * `account` - Operator receiving the role
+/// * `role` - Role granted
+pub fn emit_role_granted(env: &Env, bank_id: &Address, account: &Address, role: BankRole) {
+    let event = RoleChangedEvent {
+        bank_id: bank_id.clone(),
+        account: account.clone(),
+        role,
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::ROLE_GRANTED),), event);
+}
+
+/// Emit a RoleRevoked event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `bank_id` - Bank revoking the role
+/// * `account` - Operator losing the role
+/// * `role` - Role that was revoked
+pub fn emit_role_revoked(env: &Env, bank_id: &Address, account: &Address, role: BankRole) {
+    let event = RoleChangedEvent {
+        bank_id: bank_id.clone(),
+        account: account.clone(),
+        role,
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::ROLE_REVOKED),), event);
+}
+
+/// Emit a DonorDeferred or DonorReinstated event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `donor_id` - Donor whose deferral changed
+/// * `deferred` - Whether the donor is now deferred
+pub fn emit_donor_deferral_changed(env: &Env, donor_id: &Address, deferred: bool) {
+    let event = DonorDeferralChangedEvent {
+        donor_id: donor_id.clone(),
+        deferred,
+        changed_at: env.ledger().timestamp(),
+    };
+
+    let name = if deferred {
+        topics::DONOR_DEFERRED
+    } else {
+        topics::DONOR_REINSTATED
+    };
+    env.events().publish((Symbol::new(env, name),), event);
+}
+
+/// Emit a DonorTemporarilyDeferred event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `donor_id` - Donor who was deferred
+/// * `until` - End of the deferral
+/// * `deferred_by` - Bank or admin that set the deferral
+pub fn emit_donor_temporarily_deferred(
+    env: &Env,
+    donor_id: &Address,
+    until: u64,
+    deferred_by: &Address,
+) {
+    let event = DonorTemporarilyDeferredEvent {
+        donor_id: donor_id.clone(),
+        until,
+        deferred_by: deferred_by.clone(),
+        deferred_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        (Symbol::new(env, topics::DONOR_TEMPORARILY_DEFERRED),),
+        event,
+    );
+}
+
+/// Emit a UnitCorrected event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit_id` - Corrected blood unit
+/// * `old_quantity_ml` - Quantity before the correction
+/// * `new_quantity_ml` - Quantity after the correction
+/// * `justification` - Why the correction was made
+pub fn emit_unit_corrected(
     env: &Env,
     blood_unit_id: u64,
-    from_status: crate::types::BloodStatus,
-    to_status: crate::types::BloodStatus,
-    authorized_by: &Address,
-    reason: Option<String>,
+    old_quantity_ml: u32,
+    new_quantity_ml: u32,
+    justification: &String,
 ) {
-    let changed_at = env.ledger().timestamp();
+    let event = UnitCorrectedEvent {
+        blood_unit_id,
+        old_quantity_ml,
+        new_quantity_ml,
+        justification: justification.clone(),
+        corrected_at: env.ledger().timestamp(),
+    };
 
-    let event = StatusChangeEvent {
+    env.events()
+        .publish((Symbol::new(env, topics::UNIT_CORRECTED),), event);
+}
+
+/// Emit a BankAuthorized or BankRevoked event
+///
+/// Topics are `(name, bank)`.
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `bank` - Bank whose authorization changed
+/// * `admin` - Admin that made the change
+/// * `authorized` - Whether the bank is now authorized
+pub fn emit_bank_authorization_changed(
+    env: &Env,
+    bank: &Address,
+    admin: &Address,
+    authorized: bool,
+) {
+    let event = BankAuthorizationChangedEvent {
+        bank: bank.clone(),
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    let name = if authorized {
+        topics::BANK_AUTHORIZED
+    } else {
+        topics::BANK_REVOKED
+    };
+    env.events()
+        .publish((Symbol::new(env, name), bank.clone()), event);
+}
+
+/// Emit a BankCapacityChanged event
+///
+/// Topics are `("bank_capacity_set", bank)`.
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `bank` - Bank whose capacity changed
+/// * `max_units` - New capacity
+/// * `admin` - Admin that made the change
+pub fn emit_bank_capacity_changed(env: &Env, bank: &Address, max_units: u32, admin: &Address) {
+    let event = BankCapacityChangedEvent {
+        bank: bank.clone(),
+        max_units,
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        (Symbol::new(env, topics::BANK_CAPACITY_SET), bank.clone()),
+        event,
+    );
+}
+
+/// Emit a BankTierChanged event
+///
+/// Topics are `("bank_tier_changed", bank)`.
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `bank` - Bank whose tier changed
+/// * `tier` - New authorization tier
+/// * `admin` - Admin that made the change
+pub fn emit_bank_tier_changed(env: &Env, bank: &Address, tier: AuthorizationTier, admin: &Address) {
+    let event = BankTierChangedEvent {
+        bank: bank.clone(),
+        tier,
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        (Symbol::new(env, topics::BANK_TIER_CHANGED), bank.clone()),
+        event,
+    );
+}
+
+/// Emit a CampaignCreated event
+///
+/// Topics are `("campaign_created", bank)`.
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `campaign` - The new campaign
+pub fn emit_campaign_created(env: &Env, campaign: &Campaign) {
+    let event = CampaignCreatedEvent {
+        campaign_id: campaign.id,
+        bank_id: campaign.bank_id.clone(),
+        name: campaign.name.clone(),
+        starts_at: campaign.starts_at,
+        ends_at: campaign.ends_at,
+    };
+
+    env.events().publish(
+        (
+            Symbol::new(env, topics::CAMPAIGN_CREATED),
+            campaign.bank_id.clone(),
+        ),
+        event,
+    );
+}
+
+/// Topics of an event about a loan: `(name, lender, borrower)`
+fn loan_topics(env: &Env, name: &str, loan: &UnitLoan) -> (Symbol, Address, Address) {
+    (
+        Symbol::new(env, name),
+        loan.lender.clone(),
+        loan.borrower.clone(),
+    )
+}
+
+/// Emit a UnitLent event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `loan` - Newly recorded loan
+pub fn emit_unit_lent(env: &Env, loan: &UnitLoan) {
+    let event = UnitLentEvent {
+        loan_id: loan.id,
+        lender: loan.lender.clone(),
+        borrower: loan.borrower.clone(),
+        unit_id: loan.unit_id,
+        blood_type: loan.blood_type,
+        quantity_ml: loan.quantity_ml,
+        due_by: loan.due_by,
+    };
+
+    env.events()
+        .publish(loan_topics(env, topics::UNIT_LENT, loan), event);
+}
+
+/// Emit a LoanSettled event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `loan` - Settled loan
+/// * `replacement_unit_id` - Unit returned to the lender
+pub fn emit_loan_settled(env: &Env, loan: &UnitLoan, replacement_unit_id: u64) {
+    let event = LoanSettledEvent {
+        loan_id: loan.id,
+        lender: loan.lender.clone(),
+        borrower: loan.borrower.clone(),
+        replacement_unit_id,
+        settled_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish(loan_topics(env, topics::LOAN_SETTLED, loan), event);
+}
+
+/// Emit a LoanOverdue event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `loan` - Unsettled loan past its due date
+pub fn emit_loan_overdue(env: &Env, loan: &UnitLoan) {
+    let event = LoanOverdueEvent {
+        loan_id: loan.id,
+        lender: loan.lender.clone(),
+        borrower: loan.borrower.clone(),
+        due_by: loan.due_by,
+        checked_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish(loan_topics(env, topics::LOAN_OVERDUE, loan), event);
+}
+
+/// Emit a ShelfLifeChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `product` - Product the shelf life applies to
+/// * `shelf_life` - New shelf life
+/// * `admin` - Admin that made the change
+pub fn emit_shelf_life_changed(
+    env: &Env,
+    product: ProductType,
+    shelf_life: &ShelfLife,
+    admin: &Address,
+) {
+    let event = ShelfLifeChangedEvent {
+        product,
+        shelf_life: shelf_life.clone(),
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::SHELF_LIFE_SET),), event);
+}
+
+/// Emit a ConversionFractionChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `parent` - Product split
+/// * `child` - Product derived from it
+/// * `fraction_bps` - New volume fraction
+/// * `admin` - Admin that made the change
+pub fn emit_conversion_fraction_changed(
+    env: &Env,
+    parent: ProductType,
+    child: ProductType,
+    fraction_bps: u32,
+    admin: &Address,
+) {
+    let event = ConversionFractionChangedEvent {
+        parent,
+        child,
+        fraction_bps,
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::CONVERSION_FRACTION_SET),), event);
+}
+
+/// Emit a TemperatureRangeChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `product` - Product the range applies to
+/// * `range` - New temperature range
+/// * `admin` - Admin that made the change
+pub fn emit_temperature_range_changed(
+    env: &Env,
+    product: ProductType,
+    range: &TemperatureRange,
+    admin: &Address,
+) {
+    let event = TemperatureRangeChangedEvent {
+        product,
+        range: range.clone(),
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::TEMPERATURE_RANGE_SET),), event);
+}
+
+/// Emit a TemperatureExcursion event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit_id` - Unit the reading was taken for
+/// * `reading` - The out-of-range reading
+/// * `range` - Range the unit's product must be kept in
+pub fn emit_temperature_excursion(
+    env: &Env,
+    blood_unit_id: u64,
+    reading: &TemperatureReading,
+    range: &TemperatureRange,
+) {
+    let event = TemperatureExcursionEvent {
         blood_unit_id,
-        from_status,
-        to_status,
-        authorized_by: authorized_by.clone(),
-        changed_at,
-        reason,
+        temp_decicelsius: reading.temp_decicelsius,
+        range: range.clone(),
+        recorded_by: reading.recorded_by.clone(),
+        recorded_at: reading.recorded_at,
+    };
+
+    env.events().publish(
+        (
+            Symbol::new(env, topics::TEMPERATURE_EXCURSION),
+            blood_unit_id,
+        ),
+        event,
+    );
+}
+
+/// Emit a QuantityStepChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `step_ml` - New quantity step
+/// * `admin` - Admin that made the change
+pub fn emit_quantity_step_changed(env: &Env, step_ml: u32, admin: &Address) {
+    let event = QuantityStepChangedEvent {
+        step_ml,
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
     };
 
     env.events()
-        .publish((Symbol::new(env, "status_changed"),), event);
+        .publish((Symbol::new(env, topics::QUANTITY_STEP_SET),), event);
+}
+
+/// Emit a VoidWindowChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `window_seconds` - New void window
+/// * `admin` - Admin that made the change
+pub fn emit_void_window_changed(env: &Env, window_seconds: u64, admin: &Address) {
+    let event = VoidWindowChangedEvent {
+        window_seconds,
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::VOID_WINDOW_SET),), event);
+}
+
+/// Emit a RegistrationRateLimitChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `max_per_day` - New per-bank daily limit
+/// * `admin` - Admin that made the change
+pub fn emit_registration_rate_limit_changed(env: &Env, max_per_day: u32, admin: &Address) {
+    let event = RegistrationRateLimitChangedEvent {
+        max_per_day,
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        (Symbol::new(env, topics::REGISTRATION_RATE_LIMIT_SET),),
+        event,
+    );
+}
+
+/// Emit a RequestContractChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request_contract` - New request contract
+/// * `admin` - Admin that made the change
+pub fn emit_request_contract_changed(env: &Env, request_contract: &Address, admin: &Address) {
+    let event = RequestContractChangedEvent {
+        request_contract: request_contract.clone(),
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::REQUEST_CONTRACT_SET),), event);
+}
+
+/// Emit a LowStock event
+///
+/// Topics are `("low_stock", blood_type)`.
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_type` - Blood type running low
+/// * `available_ml` - Current Available volume
+/// * `threshold_ml` - Configured threshold
+pub fn emit_low_stock(env: &Env, blood_type: BloodType, available_ml: u64, threshold_ml: u64) {
+    let event = LowStockEvent {
+        blood_type,
+        available_ml,
+        threshold_ml,
+        emitted_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topics::LOW_STOCK), blood_type), event);
+}
+
+/// Emit a WatchedTypeChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_type` - Blood type whose Available volume changed
+/// * `available_ml` - Available volume after the change
+/// * `watchers` - Watchers subscribed to the type
+pub fn emit_watched_type_changed(
+    env: &Env,
+    blood_type: BloodType,
+    available_ml: u64,
+    watchers: Vec<Address>,
+) {
+    let event = WatchedTypeChangedEvent {
+        blood_type,
+        available_ml,
+        watchers,
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        (Symbol::new(env, topics::WATCHED_TYPE_CHANGED), blood_type),
+        event,
+    );
+}
+
+/// Emit a MatchAvailable event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_unit` - The unit just made available
+/// * `request_id` - Waitlisted request the unit could serve
+pub fn emit_match_available(env: &Env, blood_unit: &BloodUnit, request_id: u64) {
+    let event = MatchAvailableEvent {
+        blood_unit_id: blood_unit.id,
+        request_id,
+        blood_type: blood_unit.blood_type,
+        bank_id: blood_unit.bank_id.clone(),
+        quantity_ml: blood_unit.quantity_ml,
+        matched_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish(unit_topics(env, topics::MATCH_AVAILABLE, blood_unit), event);
+}
+
+/// Emit a LowStockAlertChanged event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `blood_type` - Blood type the settings apply to
+/// * `config` - New settings
+/// * `admin` - Admin that made the change
+pub fn emit_low_stock_alert_changed(
+    env: &Env,
+    blood_type: BloodType,
+    config: &LowStockAlertConfig,
+    admin: &Address,
+) {
+    let event = LowStockAlertChangedEvent {
+        blood_type,
+        config: config.clone(),
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        (Symbol::new(env, topics::LOW_STOCK_ALERT_SET), blood_type),
+        event,
+    );
 }
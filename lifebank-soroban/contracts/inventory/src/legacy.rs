@@ -0,0 +1,66 @@
+use crate::types::{BloodStatus, BloodType, BloodUnit, ProductType, BLOOD_UNIT_SCHEMA};
+use soroban_sdk::{contracttype, Address, Map, String, Symbol, Vec};
+
+/// `BloodUnit` as stored before records carried a `schema` tag (schema 0)
+///
+/// Soroban cannot decode a stored struct into a type with different
+/// fields, so units written under this layout are read through this type
+/// and rewritten by `migrate_units`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct LegacyBloodUnit {
+    pub id: u64,
+    pub blood_type: BloodType,
+    pub product: ProductType,
+    pub quantity_ml: u32,
+    pub bank_id: Address,
+    pub donor_id: Option<Address>,
+    pub donation_timestamp: u64,
+    pub expiration_timestamp: u64,
+    pub status: BloodStatus,
+    pub metadata: Map<Symbol, String>,
+    pub location: Option<String>,
+    pub reserved_for: Option<u64>,
+    pub directed_to: Option<u64>,
+    pub campaign_id: Option<u64>,
+    pub phenotype_tags: Vec<Symbol>,
+    pub autologous: bool,
+    pub pooled_from: Vec<u64>,
+    pub split_from: Option<u64>,
+    pub custodian: Address,
+}
+
+impl LegacyBloodUnit {
+    /// Number of fields in the legacy layout
+    pub const FIELD_COUNT: u32 = 19;
+
+    /// Convert to the current layout
+    ///
+    /// Fields the legacy layout lacks get their defaults: the unit is
+    /// attributed to its bank as `registered_by`.
+    pub fn into_current(self) -> BloodUnit {
+        BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
+            id: self.id,
+            blood_type: self.blood_type,
+            product: self.product,
+            quantity_ml: self.quantity_ml,
+            registered_by: self.bank_id.clone(),
+            bank_id: self.bank_id,
+            donor_id: self.donor_id,
+            donation_timestamp: self.donation_timestamp,
+            expiration_timestamp: self.expiration_timestamp,
+            status: self.status,
+            metadata: self.metadata,
+            location: self.location,
+            reserved_for: self.reserved_for,
+            directed_to: self.directed_to,
+            campaign_id: self.campaign_id,
+            phenotype_tags: self.phenotype_tags,
+            autologous: self.autologous,
+            pooled_from: self.pooled_from,
+            split_from: self.split_from,
+            custodian: self.custodian,
+        }
+    }
+}
@@ -1,15 +1,37 @@
 #![no_std]
 
+mod conversion;
 mod error;
 mod events;
+mod legacy;
 mod storage;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+/// Names of the first topic of every event this contract publishes
+///
+/// Events about a single blood unit (registered, status changed, reserved,
+/// reservation released, discarded, voided, expired, match available) are
+/// published with the topics `(name, bank_id, blood_type)`; loan events
+/// (unit lent, loan settled, loan overdue) with `(name, lender, borrower)`;
+/// all other events carry only the name.
+pub mod topics;
 mod types;
 mod validation;
 
-use crate::error::ContractError;
-use crate::types::{BloodStatus, BloodType, BloodUnit, DataKey};
+pub use crate::error::ContractError;
+pub use crate::types::{
+    AlertState, AuthorizationTier, BankRole, BloodStatus, BloodType, BloodUnit, BloodUnitSummary,
+    Campaign, CampaignCreatedEvent, CampaignStats, ComponentSplit, ContractVersion, CustodyChange,
+    CustodyChangedEvent, DiscardReason, ExpirySweepCompletedEvent, LowStockAlertConfig,
+    MatchAvailableEvent, OutstandingLoan, ProductType, RegistrationOptions, RegistrationRecord,
+    ReservationStats, ShelfLife, TemperatureExcursionEvent, TemperatureRange, TemperatureReading,
+    TemporaryDeferral, UnitLoan, UnitSplitEvent, WaitlistEntry, WatchedTypeChangedEvent,
+    BLOOD_UNIT_SCHEMA,
+};
+use crate::types::{DataKey, DonorProfile, QuantityCorrection, TypeBreakdown};
+use lifebank_common::compatibility;
 
-use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Vec};
+use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Symbol, Vec};
 #[contract]
 pub struct InventoryContract;
 
@@ -20,277 +42,3850 @@ impl InventoryContract {
     /// # Arguments
     /// * `env` - Contract environment
     /// * `admin` - Admin address who can authorize blood banks
+    /// * `banks` - Blood banks to authorize straight away (at most
+    ///   `MAX_INITIAL_BANKS`, may be empty)
+    /// * `id_offset` - Blood unit IDs start at `id_offset + 1` (0 for the
+    ///   default); lets fixtures and migrations use non-colliding ID spaces
     ///
     /// # Errors
     /// - `AlreadyInitialized`: Contract has already been initialized
-    pub fn initialize(env: Env, admin: Address) -> Result<(), ContractError> {
+    /// - `InvalidInput`: Too many initial banks
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        banks: Vec<Address>,
+        id_offset: u64,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        // Check if already initialized
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        if banks.len() > storage::MAX_INITIAL_BANKS {
+            return Err(ContractError::InvalidInput);
+        }
+
+        // Set admin
+        storage::set_admin(&env, &admin);
+        storage::set_version(&env, &storage::CONTRACT_VERSION);
+        storage::set_blood_unit_counter(&env, id_offset);
+
+        // Seed banks authorized at deploy time
+        for bank in banks.iter() {
+            storage::authorize_bank(&env, &bank);
+            events::emit_bank_authorization_changed(&env, &bank, &admin, true);
+        }
+
+        Ok(())
+    }
+
+    /// Authorize a blood bank to register blood (admin only)
+    ///
+    /// `tier` limits what the bank may do beyond registering donations;
+    /// authorizing an already authorized bank replaces its tier. Banks
+    /// seeded by `initialize` are Full. Emits `bank_authorized`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn authorize_bank(
+        env: Env,
+        bank: Address,
+        tier: AuthorizationTier,
+    ) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::authorize_bank(&env, &bank);
+        storage::set_bank_tier(&env, &bank, tier);
+        events::emit_bank_authorization_changed(&env, &bank, &admin, true);
+
+        Ok(())
+    }
+
+    /// Revoke a blood bank's authorization (admin only)
+    ///
+    /// Emits `bank_revoked`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn revoke_bank(env: Env, bank: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::revoke_bank(&env, &bank);
+        events::emit_bank_authorization_changed(&env, &bank, &admin, false);
+
+        Ok(())
+    }
+
+    /// Move an authorized blood bank to another tier (admin only)
+    ///
+    /// Emits `bank_tier_changed`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Bank was never authorized or was revoked
+    pub fn set_bank_tier(
+        env: Env,
+        bank: Address,
+        tier: AuthorizationTier,
+    ) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if !storage::is_explicitly_authorized_bank(&env, &bank) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        storage::set_bank_tier(&env, &bank, tier);
+        events::emit_bank_tier_changed(&env, &bank, tier, &admin);
+
+        Ok(())
+    }
+
+    /// Check if an address is an authorized blood bank
+    pub fn is_bank_authorized(env: Env, bank: Address) -> bool {
+        storage::is_authorized_bank(&env, &bank)
+    }
+
+    /// Get a blood bank's authorization tier, or None if it is not authorized
+    ///
+    /// The admin is always a Full bank.
+    pub fn get_bank_tier(env: Env, bank: Address) -> Option<AuthorizationTier> {
+        if !storage::is_authorized_bank(&env, &bank) {
+            return None;
+        }
+        Some(storage::get_bank_tier(&env, &bank))
+    }
+
+    /// Get a window of the authorized blood banks, in authorization order
+    ///
+    /// Lists banks authorized via `initialize` or `authorize_bank`; the
+    /// admin, which is always authorized, appears only if it was also
+    /// authorized explicitly.
+    ///
+    /// # Arguments
+    /// * `offset` - Number of banks to skip
+    /// * `limit` - Maximum number of banks (capped at `MAX_QUERY_LIMIT`)
+    pub fn list_authorized_banks(env: Env, offset: u32, limit: u32) -> Vec<Address> {
+        let banks = storage::get_authorized_banks(&env);
+        let start = offset.min(banks.len());
+        let end = start
+            .saturating_add(limit.min(storage::MAX_QUERY_LIMIT))
+            .min(banks.len());
+        banks.slice(start..end)
+    }
+
+    /// Get the number of banks `list_authorized_banks` walks over
+    pub fn count_authorized_banks(env: Env) -> u32 {
+        storage::get_authorized_banks(&env).len()
+    }
+
+    /// Propose a new admin (step one of the admin transfer)
+    ///
+    /// The proposal replaces any earlier one and takes effect only once the
+    /// proposed address calls `accept_admin`. Emits `admin_proposed`, after
+    /// `admin_transfer_cancelled` when a different proposal was replaced.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if let Some(pending) = storage::get_pending_admin(&env) {
+            if pending != new_admin {
+                events::emit_admin_transfer_cancelled(&env, &admin, &pending);
+            }
+        }
+
+        storage::set_pending_admin(&env, &new_admin);
+        events::emit_admin_transfer_proposed(&env, &admin, &new_admin);
+
+        Ok(())
+    }
+
+    /// Withdraw the pending admin proposal
+    ///
+    /// Emits `admin_transfer_cancelled`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NoPendingAdmin`: No transfer has been proposed
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let pending = storage::get_pending_admin(&env).ok_or(ContractError::NoPendingAdmin)?;
+        storage::clear_pending_admin(&env);
+        events::emit_admin_transfer_cancelled(&env, &admin, &pending);
+
+        Ok(())
+    }
+
+    /// Accept a pending admin proposal (step two of the admin transfer)
+    ///
+    /// The admin is implicitly an authorized blood bank. That authorization
+    /// follows the role: the old admin can no longer register blood unless
+    /// it was also authorized with `authorize_bank`, and the new admin can.
+    /// Emits `admin_changed`, then `implicit_bank_auth_changed` for each
+    /// implicit change.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NoPendingAdmin`: No transfer has been proposed
+    pub fn accept_admin(env: Env) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let new_admin = storage::get_pending_admin(&env).ok_or(ContractError::NoPendingAdmin)?;
+        new_admin.require_auth();
+
+        let old_admin = storage::get_admin(&env);
+        storage::set_admin(&env, &new_admin);
+        storage::clear_pending_admin(&env);
+
+        events::emit_admin_transferred(&env, &old_admin, &new_admin);
+
+        if old_admin != new_admin {
+            if !storage::is_explicitly_authorized_bank(&env, &old_admin) {
+                events::emit_implicit_authorization_changed(&env, &old_admin, false);
+            }
+            if !storage::is_explicitly_authorized_bank(&env, &new_admin) {
+                events::emit_implicit_authorization_changed(&env, &new_admin, true);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the admin proposed by the current admin, if any
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        storage::get_pending_admin(&env)
+    }
+
+    /// Get the version of the contract, so clients can tell which struct
+    /// layouts it serves
+    ///
+    /// None before `initialize`.
+    pub fn get_version(env: Env) -> Option<ContractVersion> {
+        storage::get_version(&env)
+    }
+
+    /// Register a new blood donation into the inventory
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank_id` - Blood bank's address (must be authorized)
+    /// * `blood_type` - Type of blood (A+, A-, B+, B-, AB+, AB-, O+, O-)
+    /// * `quantity_ml` - Quantity in milliliters (100-600ml)
+    /// * `expiration_timestamp` - Unix timestamp when blood expires
+    /// * `donor_id` - Optional donor address (None for anonymous). A named
+    ///   donor must also sign the transaction, consenting to the record
+    /// * `options` - Optional settings:
+    ///   - `directed_to_request`: Request (request contract) the donation is
+    ///     earmarked for. The unit starts out Directed instead of Available
+    ///     and can only be reserved for that request until it is released
+    ///     to the general pool
+    ///   - `campaign_id`: Blood drive the donation was collected under; it
+    ///     must belong to `bank_id` and be open
+    ///   - `phenotype_tags`: Extended phenotype antigens the unit was typed
+    ///     for (e.g. `kell_neg`), at most `MAX_PHENOTYPE_TAGS`
+    ///   - `autologous`: The donor gives blood for their own later
+    ///     transfusion. Needs a named donor and no directed request; the unit
+    ///     starts out Autologous and is never offered to anyone else
+    ///   - `external_ref`: The bank's own reference for the bag. Makes
+    ///     retries safe: if the bank already registered a unit under it with
+    ///     the same arguments, that unit's ID is returned and nothing is
+    ///     stored or emitted
+    ///   - `operator`: Operator of the bank registering on its behalf. Needs
+    ///     the Registrar role or above and signs instead of the bank; the
+    ///     unit records it as `registered_by`
+    ///
+    /// # Returns
+    /// Unique ID of the registered blood unit
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    /// - `Unauthorized`: `operator` holds no role at the bank
+    /// - `InsufficientPermissions`: `operator`'s role is below Registrar
+    /// - `InvalidQuantity`: Quantity outside acceptable range
+    /// - `QuantityNotAligned`: Quantity is not a multiple of the quantity step
+    /// - `InvalidExpiration`: Expiration date is invalid
+    /// - `TooManyPhenotypeTags`: More than `MAX_PHENOTYPE_TAGS` tags
+    /// - `InvalidInput`: A phenotype tag is listed twice, an autologous
+    ///   donation is anonymous or directed to a request, or the external
+    ///   reference is empty or longer than `MAX_EXTERNAL_REF_LEN`
+    /// - `DuplicateBloodUnit`: The bank already registered a unit under the
+    ///   external reference with different arguments
+    /// - `DonorDeferred`: Donor is permanently deferred or within a
+    ///   temporary deferral
+    /// - `NotFound`: Campaign doesn't exist
+    /// - `Unauthorized`: Campaign is run by another bank
+    /// - `CampaignClosed`: Campaign has not started or has ended
+    /// - `CapacityExceeded`: Bank is already holding its maximum number of units
+    /// - `RateLimitExceeded`: Bank already registered its daily limit of units
+    /// - `CounterOverflow`: No blood unit IDs are left
+    ///
+    /// # Events
+    /// Emits `BloodRegistered` event with all blood unit details, and
+    /// `DonorMilestone` when the donor reaches a donation milestone
+    pub fn register_blood(
+        env: Env,
+        bank_id: Address,
+        blood_type: BloodType,
+        quantity_ml: u32,
+        expiration_timestamp: u64,
+        donor_id: Option<Address>,
+        options: RegistrationOptions,
+    ) -> Result<u64, ContractError> {
+        Self::register(
+            &env,
+            bank_id,
+            blood_type,
+            ProductType::WholeBlood,
+            quantity_ml,
+            expiration_timestamp,
+            donor_id,
+            options,
+        )
+    }
+
+    /// Register a blood component (red cells, platelets, plasma, ...)
+    ///
+    /// Same as `register_blood`, except the expiration is checked against the
+    /// shelf-life bounds of `product` instead of those of whole blood, and
+    /// components always go to the general pool outside any campaign.
+    ///
+    /// # Errors
+    /// As `register_blood`; `InvalidExpiration` when the expiration is outside
+    /// the product's configured shelf life
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_component(
+        env: Env,
+        bank_id: Address,
+        blood_type: BloodType,
+        product: ProductType,
+        quantity_ml: u32,
+        expiration_timestamp: u64,
+        donor_id: Option<Address>,
+        phenotype_tags: Vec<Symbol>,
+    ) -> Result<u64, ContractError> {
+        Self::register(
+            &env,
+            bank_id,
+            blood_type,
+            product,
+            quantity_ml,
+            expiration_timestamp,
+            donor_id,
+            RegistrationOptions {
+                phenotype_tags,
+                ..RegistrationOptions::new(&env)
+            },
+        )
+    }
+
+    /// Combine platelet sources into one pooled platelet unit
+    ///
+    /// Every source must be an Available, unexpired whole blood or platelet
+    /// unit of the same blood type held by `bank_id`, and not a pool
+    /// itself. Sources become Pooled (terminal). The new Platelets unit
+    /// holds their total volume, expires with the earliest source (or at
+    /// the end of the platelet shelf life, if sooner), keeps only the
+    /// phenotype tags all sources share, and is linked to every
+    /// contributing donor.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank_id` - Bank holding the sources (must be authorized)
+    /// * `source_unit_ids` - `MIN_POOL_SOURCES` to `MAX_POOL_SOURCES` distinct units
+    ///
+    /// # Returns
+    /// Unique ID of the pooled unit
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    /// - `RegisterOnlyBank`: Bank is RegisterOnly
+    /// - `InvalidInput`: Too few or too many sources, a source listed twice,
+    ///   or a source that is a pool or not a platelet source
+    /// - `NotFound`: A source doesn't exist
+    /// - `Unauthorized`: A source belongs to another bank
+    /// - `BloodUnitNotAvailable`: A source is not Available
+    /// - `BloodUnitExpired`: A source is past its expiration time
+    /// - `IncompatibleBloodType`: Sources differ in blood type
+    /// - `CounterOverflow`: No blood unit IDs are left
+    ///
+    /// # Events
+    /// Emits `status_changed` for each source, then `blood_registered` and
+    /// `units_pooled` for the pool
+    pub fn pool_units(
+        env: Env,
+        bank_id: Address,
+        source_unit_ids: Vec<u64>,
+    ) -> Result<u64, ContractError> {
+        bank_id.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &bank_id) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+        Self::check_full_tier(&env, &bank_id, &bank_id)?;
+
+        let count = source_unit_ids.len();
+        if !(validation::MIN_POOL_SOURCES..=validation::MAX_POOL_SOURCES).contains(&count) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        // 1. Check every source before touching any of them
+        let current_time = env.ledger().timestamp();
+        let mut sources: Vec<BloodUnit> = Vec::new(&env);
+        for (i, unit_id) in source_unit_ids.iter().enumerate() {
+            if source_unit_ids.first_index_of(unit_id) != Some(i as u32) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let blood_unit =
+                storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+            if blood_unit.bank_id != bank_id {
+                return Err(ContractError::Unauthorized);
+            }
+            if !blood_unit.product.is_platelet_source() || !blood_unit.pooled_from.is_empty() {
+                return Err(ContractError::InvalidInput);
+            }
+            if blood_unit.status != BloodStatus::Available {
+                return Err(ContractError::BloodUnitNotAvailable);
+            }
+            if blood_unit.is_expired(current_time) {
+                return Err(ContractError::BloodUnitExpired);
+            }
+            if let Some(first) = sources.first() {
+                if blood_unit.blood_type != first.blood_type {
+                    return Err(ContractError::IncompatibleBloodType);
+                }
+            }
+            sources.push_back(blood_unit);
+        }
+
+        // 2. Derive the pool from its sources
+        let first = sources.get_unchecked(0);
+        let shelf_life = storage::get_shelf_life(&env, ProductType::Platelets);
+        let mut quantity_ml = 0u32;
+        let mut expiration_timestamp = current_time.saturating_add(shelf_life.max_seconds);
+        let mut phenotype_tags = first.phenotype_tags.clone();
+        for source in sources.iter() {
+            quantity_ml = quantity_ml.saturating_add(source.quantity_ml);
+            expiration_timestamp = expiration_timestamp.min(source.expiration_timestamp);
+            phenotype_tags = Self::shared_tags(&env, &phenotype_tags, &source.phenotype_tags);
+        }
+
+        // 3. Retire the sources, freeing their capacity slots
+        let reason = String::from_str(&env, "Pooled into platelet unit");
+        for mut source in sources.iter() {
+            Self::apply_status_change(
+                &env,
+                &mut source,
+                BloodStatus::Pooled,
+                &bank_id,
+                Some(reason.clone()),
+            );
+        }
+
+        // 4. Create and index the pooled unit
+        let pooled_unit = BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
+            id: storage::increment_blood_unit_id(&env)?,
+            blood_type: first.blood_type,
+            product: ProductType::Platelets,
+            quantity_ml,
+            bank_id: bank_id.clone(),
+            donor_id: None,
+            donation_timestamp: current_time,
+            expiration_timestamp,
+            status: BloodStatus::Available,
+            metadata: Map::new(&env),
+            location: None,
+            reserved_for: None,
+            directed_to: None,
+            campaign_id: None,
+            phenotype_tags,
+            autologous: false,
+            pooled_from: source_unit_ids,
+            split_from: None,
+            custodian: bank_id.clone(),
+            registered_by: bank_id.clone(),
+        };
+        pooled_unit.validate(current_time)?;
+
+        storage::set_blood_unit(&env, &pooled_unit);
+        storage::add_to_blood_type_index(&env, &pooled_unit);
+        storage::add_to_bank_index(&env, &pooled_unit);
+        storage::add_to_status_index(&env, &pooled_unit);
+        storage::add_to_available_by_expiry(&env, &pooled_unit);
+        storage::add_to_stock_totals(
+            &env,
+            pooled_unit.blood_type,
+            BloodStatus::Available,
+            quantity_ml,
+        );
+        storage::increment_bank_unit_count(&env, &bank_id);
+        for source in sources.iter() {
+            if let Some(donor) = &source.donor_id {
+                storage::link_unit_to_donor(&env, donor, pooled_unit.id);
+            }
+        }
+
+        events::emit_blood_registered(&env, &pooled_unit);
+        events::emit_units_pooled(&env, &pooled_unit);
+        Self::announce_match(&env, &pooled_unit);
+        Self::notify_watchers(&env, pooled_unit.blood_type);
+
+        Ok(pooled_unit.id)
+    }
+
+    /// Separate an Available unit into component units
+    ///
+    /// Every component must be derivable from the unit's product and stay
+    /// within its volume fraction of the unit (see the `conversion` table
+    /// and `set_conversion_fraction`); together they cannot exceed the
+    /// unit's volume. The unit becomes Split (terminal). Components keep
+    /// its blood type, donor, donation time and phenotype tags, and expire
+    /// at the end of their own product's shelf life counted from the
+    /// donation, never later than the unit for aliquots of the same product.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank_id` - Bank holding the unit (must be authorized)
+    /// * `unit_id` - Unit to split
+    /// * `children` - 1 to `MAX_SPLIT_CHILDREN` components to produce
+    ///
+    /// # Returns
+    /// IDs of the new units, in the order of `children`
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    /// - `RegisterOnlyBank`: Bank is RegisterOnly
+    /// - `NotFound`: Unit doesn't exist
+    /// - `Unauthorized`: Unit belongs to another bank
+    /// - `BloodUnitNotAvailable`: Unit is not Available
+    /// - `BloodUnitExpired`: Unit, or a component's shelf life, is over
+    /// - `InvalidInput`: No components or more than `MAX_SPLIT_CHILDREN`
+    /// - `InvalidComponentDerivation`: A component cannot be made from the
+    ///   unit's product
+    /// - `ChildVolumeExceedsParent`: A component exceeds its fraction, or
+    ///   the components exceed the unit's volume
+    /// - `InvalidQuantity`: A component is outside the unit volume range
+    /// - `CapacityExceeded`: The components don't fit the bank's capacity
+    /// - `CounterOverflow`: No blood unit IDs are left
+    ///
+    /// # Events
+    /// Emits `status_changed` for the unit, `blood_registered` for each
+    /// component, then `unit_split`
+    pub fn split_unit(
+        env: Env,
+        bank_id: Address,
+        unit_id: u64,
+        children: Vec<ComponentSplit>,
+    ) -> Result<Vec<u64>, ContractError> {
+        bank_id.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &bank_id) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+        Self::check_full_tier(&env, &bank_id, &bank_id)?;
+
+        // 1. Check the unit and the requested components
+        let current_time = env.ledger().timestamp();
+        let mut parent = storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+        if parent.bank_id != bank_id {
+            return Err(ContractError::Unauthorized);
+        }
+        if parent.status != BloodStatus::Available {
+            return Err(ContractError::BloodUnitNotAvailable);
+        }
+        if parent.is_expired(current_time) {
+            return Err(ContractError::BloodUnitExpired);
+        }
+        conversion::validate_split(
+            parent.product,
+            parent.quantity_ml,
+            &children,
+            |parent_product, child_product| {
+                storage::get_conversion_fraction(&env, parent_product, child_product)
+            },
+        )?;
+
+        // The parent's slot is freed, each component takes one
+        if let Some(max_units) = storage::get_bank_capacity(&env, &bank_id) {
+            let count = storage::get_bank_unit_count(&env, &bank_id);
+            if count.saturating_sub(1).saturating_add(children.len()) > max_units {
+                return Err(ContractError::CapacityExceeded);
+            }
+        }
+
+        // 2. Derive the components before touching the parent
+        let mut components = Vec::new(&env);
+        for child in children.iter() {
+            let shelf_life = storage::get_shelf_life(&env, child.product);
+            let mut expiration_timestamp = parent
+                .donation_timestamp
+                .saturating_add(shelf_life.max_seconds);
+            if child.product == parent.product {
+                expiration_timestamp = expiration_timestamp.min(parent.expiration_timestamp);
+            }
+            if current_time >= expiration_timestamp {
+                return Err(ContractError::BloodUnitExpired);
+            }
+
+            let component = BloodUnit {
+                schema: BLOOD_UNIT_SCHEMA,
+                id: 0,
+                blood_type: parent.blood_type,
+                product: child.product,
+                quantity_ml: child.quantity_ml,
+                bank_id: bank_id.clone(),
+                donor_id: parent.donor_id.clone(),
+                donation_timestamp: parent.donation_timestamp,
+                expiration_timestamp,
+                status: BloodStatus::Available,
+                metadata: Map::new(&env),
+                location: None,
+                reserved_for: None,
+                directed_to: None,
+                campaign_id: None,
+                phenotype_tags: parent.phenotype_tags.clone(),
+                autologous: false,
+                pooled_from: Vec::new(&env),
+                split_from: Some(unit_id),
+                custodian: bank_id.clone(),
+                registered_by: bank_id.clone(),
+            };
+            component.validate(current_time)?;
+            components.push_back(component);
+        }
+
+        // 3. Retire the parent, freeing its capacity slot
+        let reason = String::from_str(&env, "Split into components");
+        Self::apply_status_change(
+            &env,
+            &mut parent,
+            BloodStatus::Split,
+            &bank_id,
+            Some(reason),
+        );
+
+        // 4. Create and index the components
+        let mut child_unit_ids = Vec::new(&env);
+        for mut component in components.iter() {
+            component.id = storage::increment_blood_unit_id(&env)?;
+
+            storage::set_blood_unit(&env, &component);
+            storage::add_to_blood_type_index(&env, &component);
+            storage::add_to_bank_index(&env, &component);
+            storage::add_to_status_index(&env, &component);
+            storage::add_to_available_by_expiry(&env, &component);
+            storage::add_to_stock_totals(
+                &env,
+                component.blood_type,
+                BloodStatus::Available,
+                component.quantity_ml,
+            );
+            storage::increment_bank_unit_count(&env, &bank_id);
+            if let Some(donor) = &component.donor_id {
+                storage::link_unit_to_donor(&env, donor, component.id);
+            }
+
+            events::emit_blood_registered(&env, &component);
+            Self::announce_match(&env, &component);
+            child_unit_ids.push_back(component.id);
+        }
+
+        events::emit_unit_split(&env, &parent, &child_unit_ids);
+        Self::notify_watchers(&env, parent.blood_type);
+
+        Ok(child_unit_ids)
+    }
+
+    /// Start a named blood drive at a bank
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank` - Bank running the campaign (must be authorized)
+    /// * `name` - Campaign name
+    /// * `starts_at` - First moment units can be registered against it
+    /// * `ends_at` - Last moment units can be registered against it
+    ///
+    /// # Returns
+    /// Unique ID of the campaign
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    /// - `InvalidInput`: Name is empty or `ends_at` is before `starts_at`
+    /// - `CounterOverflow`: No campaign IDs are left
+    ///
+    /// # Events
+    /// Emits `campaign_created`
+    pub fn create_campaign(
+        env: Env,
+        bank: Address,
+        name: String,
+        starts_at: u64,
+        ends_at: u64,
+    ) -> Result<u64, ContractError> {
+        bank.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &bank) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        if name.is_empty() || ends_at < starts_at {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut campaign = Campaign {
+            id: 0,
+            bank_id: bank,
+            name,
+            starts_at,
+            ends_at,
+            created_at: env.ledger().timestamp(),
+        };
+        let campaign_id = storage::create_campaign(&env, &mut campaign)?;
+        events::emit_campaign_created(&env, &campaign);
+
+        Ok(campaign_id)
+    }
+
+    /// Get a campaign by ID
+    pub fn get_campaign(env: Env, campaign_id: u64) -> Result<Campaign, ContractError> {
+        storage::get_campaign(&env, campaign_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Totals of every unit registered against a campaign, whatever its
+    /// current status
+    ///
+    /// # Errors
+    /// - `NotFound`: Campaign doesn't exist
+    pub fn get_campaign_stats(env: Env, campaign_id: u64) -> Result<CampaignStats, ContractError> {
+        if storage::get_campaign(&env, campaign_id).is_none() {
+            return Err(ContractError::NotFound);
+        }
+
+        let mut stats = CampaignStats {
+            units: 0,
+            total_ml: 0,
+            by_blood_type: Map::new(&env),
+        };
+        for unit_id in storage::get_campaign_units(&env, campaign_id).iter() {
+            if let Some(blood_unit) = storage::get_blood_unit(&env, unit_id) {
+                stats.units += 1;
+                stats.total_ml = stats.total_ml.saturating_add(blood_unit.quantity_ml);
+                let type_ml = stats.by_blood_type.get(blood_unit.blood_type).unwrap_or(0);
+                stats.by_blood_type.set(
+                    blood_unit.blood_type,
+                    type_ml.saturating_add(blood_unit.quantity_ml),
+                );
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Lend an Available unit to another bank
+    ///
+    /// The unit moves to the borrower straight away, and a loan is recorded
+    /// that the borrower settles by returning a unit of the same blood type
+    /// and product, of at least the same volume (see `settle_loan`).
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `from_bank` - Lending bank, which holds the unit
+    /// * `to_bank` - Borrowing bank (must be authorized)
+    /// * `unit_id` - Blood unit to lend
+    /// * `due_by` - Time by which the loan should be settled
+    ///
+    /// # Returns
+    /// Unique ID of the loan
+    ///
+    /// # Errors
+    /// - `InvalidInput`: Both banks are the same
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Either bank is not authorized
+    /// - `RegisterOnlyBank`: Lending bank may only register units
+    /// - `InvalidTimestamp`: `due_by` is not in the future
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: Unit belongs to another bank
+    /// - `BloodUnitNotAvailable`: Unit is not Available
+    /// - `BloodUnitExpired`: Unit is past its expiration time
+    /// - `TemperatureExcursion`: Unit left its temperature range
+    /// - `CapacityExceeded`: Borrowing bank is at capacity
+    /// - `CounterOverflow`: No loan IDs are left
+    ///
+    /// # Events
+    /// Emits `custody_changed` and `unit_lent`
+    pub fn lend_unit(
+        env: Env,
+        from_bank: Address,
+        to_bank: Address,
+        unit_id: u64,
+        due_by: u64,
+    ) -> Result<u64, ContractError> {
+        from_bank.require_auth();
+        // Checked before the borrower signs: one address cannot authorize
+        // the same call twice
+        if from_bank == to_bank {
+            return Err(ContractError::InvalidInput);
+        }
+        to_bank.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &from_bank)
+            || !storage::is_authorized_bank(&env, &to_bank)
+        {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+        Self::check_full_tier(&env, &from_bank, &from_bank)?;
+
+        let now = env.ledger().timestamp();
+        if due_by <= now {
+            return Err(ContractError::InvalidTimestamp);
+        }
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+        Self::check_lendable(&env, &blood_unit, &from_bank)?;
+
+        Self::move_to_bank(&env, &mut blood_unit, &to_bank)?;
+
+        let mut loan = UnitLoan {
+            id: 0,
+            lender: from_bank,
+            borrower: to_bank,
+            unit_id,
+            blood_type: blood_unit.blood_type,
+            product: blood_unit.product,
+            quantity_ml: blood_unit.quantity_ml,
+            lent_at: now,
+            due_by,
+            replacement_unit_id: None,
+            settled_at: None,
+        };
+        let loan_id = storage::create_loan(&env, &mut loan)?;
+        events::emit_unit_lent(&env, &loan);
+
+        Ok(loan_id)
+    }
+
+    /// Settle a loan by returning a replacement unit to the lender
+    ///
+    /// The replacement must be an Available unit held by the borrower, of
+    /// the loan's blood type and product and holding at least the volume
+    /// lent. Late settlement is still accepted.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `borrower` - Bank that borrowed the unit
+    /// * `loan_id` - Loan to settle
+    /// * `replacement_unit_id` - Unit returned to the lender
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Borrowing bank is not authorized
+    /// - `RegisterOnlyBank`: Borrowing bank may only register units
+    /// - `NotFound`: Loan or replacement unit doesn't exist
+    /// - `Unauthorized`: Loan was taken out by another bank, or the
+    ///   replacement belongs to another bank
+    /// - `LoanAlreadySettled`: Loan was already settled
+    /// - `ReplacementMismatch`: Replacement differs in blood type or
+    ///   product, or holds less than the volume lent
+    /// - `BloodUnitNotAvailable`: Replacement is not Available
+    /// - `BloodUnitExpired`: Replacement is past its expiration time
+    /// - `TemperatureExcursion`: Replacement left its temperature range
+    /// - `CapacityExceeded`: Lending bank is at capacity
+    ///
+    /// # Events
+    /// Emits `custody_changed` and `loan_settled`
+    pub fn settle_loan(
+        env: Env,
+        borrower: Address,
+        loan_id: u64,
+        replacement_unit_id: u64,
+    ) -> Result<UnitLoan, ContractError> {
+        borrower.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &borrower) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+        Self::check_full_tier(&env, &borrower, &borrower)?;
+
+        let mut loan = storage::get_loan(&env, loan_id).ok_or(ContractError::NotFound)?;
+        if loan.borrower != borrower {
+            return Err(ContractError::Unauthorized);
+        }
+        if loan.settled_at.is_some() {
+            return Err(ContractError::LoanAlreadySettled);
+        }
+
+        let mut replacement =
+            storage::get_blood_unit(&env, replacement_unit_id).ok_or(ContractError::NotFound)?;
+        Self::check_lendable(&env, &replacement, &borrower)?;
+        if replacement.blood_type != loan.blood_type
+            || replacement.product != loan.product
+            || replacement.quantity_ml < loan.quantity_ml
+        {
+            return Err(ContractError::ReplacementMismatch);
+        }
+
+        Self::move_to_bank(&env, &mut replacement, &loan.lender)?;
+
+        loan.replacement_unit_id = Some(replacement_unit_id);
+        loan.settled_at = Some(env.ledger().timestamp());
+        storage::set_loan(&env, &loan);
+        storage::remove_outstanding_loan(&env, &loan);
+        events::emit_loan_settled(&env, &loan, replacement_unit_id);
+
+        Ok(loan)
+    }
+
+    /// Get a loan by ID, settled or not
+    pub fn get_loan(env: Env, loan_id: u64) -> Result<UnitLoan, ContractError> {
+        storage::get_loan(&env, loan_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the unsettled loans a bank lent or borrowed, oldest first, each
+    /// flagged as overdue once its due date has passed
+    ///
+    /// # Arguments
+    /// * `caller` - The bank itself, one of its Auditors or above, or the admin
+    /// * `bank` - Bank to list
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller holds no role at the bank
+    /// - `InsufficientPermissions`: Caller's role is below Auditor
+    pub fn get_outstanding_loans(
+        env: Env,
+        caller: Address,
+        bank: Address,
+    ) -> Result<Vec<OutstandingLoan>, ContractError> {
+        caller.require_auth();
+        Self::check_bank_role(&env, &caller, &bank, BankRole::Auditor)?;
+
+        let now = env.ledger().timestamp();
+        let mut loans = Vec::new(&env);
+        for loan_id in storage::get_outstanding_loans(&env, &bank).iter() {
+            if let Some(loan) = storage::get_loan(&env, loan_id) {
+                let overdue = now > loan.due_by;
+                loans.push_back(OutstandingLoan { loan, overdue });
+            }
+        }
+        Ok(loans)
+    }
+
+    /// Find a bank's overdue loans, emitting `loan_overdue` for each
+    ///
+    /// Anyone may call this, e.g. a keeper, so the parties are notified
+    /// without having to poll. A loan is reported on every check until it
+    /// is settled.
+    ///
+    /// # Returns
+    /// IDs of the overdue loans the bank lent or borrowed, oldest first
+    ///
+    /// # Events
+    /// Emits `loan_overdue` per overdue loan
+    pub fn check_overdue_loans(env: Env, bank: Address) -> Vec<u64> {
+        let now = env.ledger().timestamp();
+        let mut overdue = Vec::new(&env);
+        for loan_id in storage::get_outstanding_loans(&env, &bank).iter() {
+            if let Some(loan) = storage::get_loan(&env, loan_id) {
+                if now > loan.due_by {
+                    events::emit_loan_overdue(&env, &loan);
+                    overdue.push_back(loan_id);
+                }
+            }
+        }
+        overdue
+    }
+
+    /// Set the shelf-life bounds used to validate new units of a product
+    ///
+    /// # Arguments
+    /// * `product` - Blood product to configure
+    /// * `min_seconds` - Minimum shelf life left at registration
+    /// * `max_seconds` - Maximum shelf life at registration
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidInput`: `max_seconds` is zero or below `min_seconds`
+    pub fn set_shelf_life(
+        env: Env,
+        product: ProductType,
+        min_seconds: u64,
+        max_seconds: u64,
+    ) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if max_seconds == 0 || max_seconds < min_seconds {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let shelf_life = ShelfLife {
+            min_seconds,
+            max_seconds,
+        };
+        storage::set_shelf_life(&env, product, &shelf_life);
+        events::emit_shelf_life_changed(&env, product, &shelf_life, &admin);
+
+        Ok(())
+    }
+
+    /// Get the shelf-life bounds applied to a product
+    pub fn get_shelf_life(env: Env, product: ProductType) -> ShelfLife {
+        storage::get_shelf_life(&env, product)
+    }
+
+    /// Set the temperature range units of a product must be kept in
+    ///
+    /// Applies to readings logged from now on; earlier readings keep the
+    /// verdict they were logged with.
+    ///
+    /// # Arguments
+    /// * `product` - Blood product to configure
+    /// * `min_decicelsius` - Lowest allowed temperature, in tenths of a °C
+    /// * `max_decicelsius` - Highest allowed temperature, in tenths of a °C
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidInput`: `max_decicelsius` is below `min_decicelsius`
+    pub fn set_temperature_range(
+        env: Env,
+        product: ProductType,
+        min_decicelsius: i32,
+        max_decicelsius: i32,
+    ) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if max_decicelsius < min_decicelsius {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let range = TemperatureRange {
+            min_decicelsius,
+            max_decicelsius,
+        };
+        storage::set_temperature_range(&env, product, &range);
+        events::emit_temperature_range_changed(&env, product, &range, &admin);
+
+        Ok(())
+    }
+
+    /// Get the temperature range applied to a product
+    pub fn get_temperature_range(env: Env, product: ProductType) -> TemperatureRange {
+        storage::get_temperature_range(&env, product)
+    }
+
+    /// Set the largest share of a unit's volume one component of `child`
+    /// split from it may take
+    ///
+    /// Only derivations the conversion table allows can be configured, and
+    /// only within `MIN_FRACTION_BPS` to `MAX_FRACTION_BPS`.
+    ///
+    /// # Arguments
+    /// * `parent` - Product of the unit being split
+    /// * `child` - Product of the component
+    /// * `fraction_bps` - Share of the parent's volume, in basis points
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidComponentDerivation`: `child` cannot be made from `parent`
+    /// - `InvalidInput`: `fraction_bps` is outside the hard bounds
+    pub fn set_conversion_fraction(
+        env: Env,
+        parent: ProductType,
+        child: ProductType,
+        fraction_bps: u32,
+    ) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if !conversion::is_allowed_derivation(parent, child) {
+            return Err(ContractError::InvalidComponentDerivation);
+        }
+        if !(conversion::MIN_FRACTION_BPS..=conversion::MAX_FRACTION_BPS).contains(&fraction_bps) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        storage::set_conversion_fraction(&env, parent, child, fraction_bps);
+        events::emit_conversion_fraction_changed(&env, parent, child, fraction_bps, &admin);
+
+        Ok(())
+    }
+
+    /// Get the volume fraction in force for a derivation, None when the
+    /// conversion table does not allow it
+    pub fn get_conversion_fraction(
+        env: Env,
+        parent: ProductType,
+        child: ProductType,
+    ) -> Option<u32> {
+        storage::get_conversion_fraction(&env, parent, child)
+    }
+
+    /// Grant an operator a role at a bank, replacing any role it held
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank_id` - Bank granting the role (must be authorized)
+    /// * `account` - Operator receiving the role
+    /// * `role` - Role to grant
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    ///
+    /// # Events
+    /// Emits `role_granted`
+    pub fn grant_role(
+        env: Env,
+        bank_id: Address,
+        account: Address,
+        role: BankRole,
+    ) -> Result<(), ContractError> {
+        bank_id.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &bank_id) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        storage::set_bank_role(&env, &bank_id, &account, role);
+        events::emit_role_granted(&env, &bank_id, &account, role);
+
+        Ok(())
+    }
+
+    /// Revoke an operator's role at a bank
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    /// - `NotFound`: Operator holds no role at the bank
+    ///
+    /// # Events
+    /// Emits `role_revoked`
+    pub fn revoke_role(env: Env, bank_id: Address, account: Address) -> Result<(), ContractError> {
+        bank_id.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &bank_id) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        let role =
+            storage::get_bank_role(&env, &bank_id, &account).ok_or(ContractError::NotFound)?;
+        storage::remove_bank_role(&env, &bank_id, &account);
+        events::emit_role_revoked(&env, &bank_id, &account, role);
+
+        Ok(())
+    }
+
+    /// Get the role an operator holds at a bank, if any
+    pub fn get_role(env: Env, bank_id: Address, account: Address) -> Option<BankRole> {
+        storage::get_bank_role(&env, &bank_id, &account)
+    }
+
+    /// Get every operator holding a role at a bank
+    pub fn get_role_holders(env: Env, bank_id: Address) -> Vec<Address> {
+        storage::get_bank_role_holders(&env, &bank_id)
+    }
+
+    /// List every blood unit registered by a bank
+    ///
+    /// Restricted view: requires any role at the bank (Auditor is enough).
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller holds no role at the bank
+    pub fn get_bank_units(
+        env: Env,
+        caller: Address,
+        bank_id: Address,
+    ) -> Result<Vec<u64>, ContractError> {
+        caller.require_auth();
+
+        Self::check_bank_role(&env, &caller, &bank_id, BankRole::Auditor)?;
+
+        Ok(storage::get_bank_units(&env, &bank_id))
+    }
+
+    /// Get a window of the units one key registered for a bank, in
+    /// registration order, for internal audits
+    ///
+    /// `operator` may be the bank itself. Pooled and split units are not
+    /// listed. Restricted view: requires any role at the bank (Auditor is
+    /// enough).
+    ///
+    /// # Arguments
+    /// * `caller` - Admin, the bank or one of its operators
+    /// * `bank_id` - Bank the units were registered for
+    /// * `operator` - Key that performed the registrations
+    /// * `offset` - Number of units to skip
+    /// * `limit` - Maximum number of units (capped at `MAX_QUERY_LIMIT`)
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller holds no role at the bank
+    pub fn get_units_registered_by(
+        env: Env,
+        caller: Address,
+        bank_id: Address,
+        operator: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<u64>, ContractError> {
+        caller.require_auth();
+
+        Self::check_bank_role(&env, &caller, &bank_id, BankRole::Auditor)?;
+
+        let units = storage::get_operator_units(&env, &bank_id, &operator);
+        let start = offset.min(units.len());
+        let end = start
+            .saturating_add(limit.min(storage::MAX_QUERY_LIMIT))
+            .min(units.len());
+        Ok(units.slice(start..end))
+    }
+
+    /// Summaries of every blood unit registered by a bank
+    ///
+    /// Same access rule and order as `get_bank_units`.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller holds no role at the bank
+    pub fn get_bank_unit_summaries(
+        env: Env,
+        caller: Address,
+        bank_id: Address,
+    ) -> Result<Vec<BloodUnitSummary>, ContractError> {
+        let ids = Self::get_bank_units(env.clone(), caller, bank_id)?;
+        Ok(Self::load_summaries(&env, ids.iter()))
+    }
+
+    /// Summaries of the units of a blood type, in registration order
+    ///
+    /// Statuses are effective statuses, see `get_blood_unit`.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `blood_type` - Blood type to list
+    /// * `status` - Only include units whose effective status is this
+    /// * `limit` - Maximum number of results (capped at `MAX_QUERY_LIMIT`)
+    pub fn get_unit_summaries_by_type(
+        env: Env,
+        blood_type: BloodType,
+        status: Option<BloodStatus>,
+        limit: u32,
+    ) -> Vec<BloodUnitSummary> {
+        let max = limit.min(storage::MAX_QUERY_LIMIT);
+        let index = storage::PagedIndex::BloodType(blood_type);
+        let now = env.ledger().timestamp();
+
+        // Walk the index page by page so a full result stops the reads
+        let mut summaries = Vec::new(&env);
+        for page in 0..storage::get_index_page_count(&env, index) {
+            for id in storage::get_index_page(&env, index, page).iter() {
+                if summaries.len() >= max {
+                    return summaries;
+                }
+                if let Some(blood_unit) = storage::get_blood_unit(&env, id) {
+                    let blood_unit = blood_unit.with_effective_status(now);
+                    if status.is_none() || status == Some(blood_unit.status) {
+                        summaries.push_back(BloodUnitSummary::from(blood_unit));
+                    }
+                }
+            }
+        }
+        summaries
+    }
+
+    /// Summaries of several blood units at once
+    ///
+    /// Unknown IDs are skipped. At most `MAX_QUERY_LIMIT` IDs are read.
+    pub fn get_unit_summaries(env: Env, unit_ids: Vec<u64>) -> Vec<BloodUnitSummary> {
+        Self::load_summaries(
+            &env,
+            unit_ids.iter().take(storage::MAX_QUERY_LIMIT as usize),
+        )
+    }
+
+    /// Get blood unit details by ID
+    ///
+    /// The returned status is the unit's effective status: a unit past its
+    /// expiration reads as Expired even if storage still holds an earlier
+    /// status. Storage lags until the `expire_units` sweep, or `mark_expired`
+    /// for a single unit, reconciles it; reads never write.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `blood_unit_id` - ID of the blood unit to retrieve
+    ///
+    /// # Returns
+    /// Blood unit details
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit with given ID doesn't exist
+    pub fn get_blood_unit(env: Env, blood_unit_id: u64) -> Result<BloodUnit, ContractError> {
+        let now = env.ledger().timestamp();
+        storage::get_blood_unit(&env, blood_unit_id)
+            .map(|unit| unit.with_effective_status(now))
+            .ok_or(ContractError::NotFound)
+    }
+
+    /// Get the unit a bank registered under an external reference
+    pub fn get_unit_by_external_ref(
+        env: Env,
+        bank_id: Address,
+        external_ref: String,
+    ) -> Option<u64> {
+        storage::get_registration_by_ref(&env, &bank_id, &external_ref)
+            .map(|record| record.blood_unit_id)
+    }
+
+    /// Get the number of blood unit IDs issued so far
+    ///
+    /// IDs are assigned sequentially from 1, so this is also the highest ID.
+    pub fn get_blood_unit_count(env: Env) -> u64 {
+        storage::get_blood_unit_counter(&env)
+    }
+
+    /// Get the blood units with IDs in `start_id..start_id + limit`
+    ///
+    /// Intended for rebuilding the inventory off-chain by walking IDs from 1
+    /// to `get_blood_unit_count`. IDs with no stored unit are skipped, so the
+    /// result may hold fewer than `limit` entries. `limit` is capped at
+    /// `MAX_RANGE_LIMIT`. Statuses are effective statuses, as in
+    /// `get_blood_unit`.
+    pub fn get_blood_units_range(env: Env, start_id: u64, limit: u32) -> Vec<BloodUnit> {
+        let end = start_id
+            .saturating_add(limit.min(storage::MAX_RANGE_LIMIT) as u64)
+            .min(storage::get_blood_unit_counter(&env).saturating_add(1));
+
+        let now = env.ledger().timestamp();
+        let mut units = Vec::new(&env);
+        for id in start_id..end {
+            if let Some(unit) = storage::get_blood_unit(&env, id) {
+                units.push_back(unit.with_effective_status(now));
+            }
+        }
+        units
+    }
+
+    /// Rewrite blood units stored under the legacy layout (admin only)
+    ///
+    /// Visits IDs `start_id..start_id + limit` (`limit` capped at
+    /// `MAX_RANGE_LIMIT`), rewriting units written before records carried a
+    /// `schema` tag; new fields get defaults (`registered_by` is the unit's
+    /// bank). Units already on the current schema are skipped, so the sweep
+    /// can be rerun safely. Once the last ID has been visited, the current
+    /// contract version is stored.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `admin` - Current admin
+    /// * `start_id` - First ID to visit (1 to start from the beginning)
+    /// * `limit` - Maximum number of IDs to visit
+    ///
+    /// # Returns
+    /// The ID to continue from, or 0 once every unit has been visited
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is not the admin
+    pub fn migrate_units(
+        env: Env,
+        admin: Address,
+        start_id: u64,
+        limit: u32,
+    ) -> Result<u64, ContractError> {
+        admin.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if admin != storage::get_admin(&env) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let last_id = storage::get_blood_unit_counter(&env);
+        let start_id = start_id.max(1);
+        let end = start_id
+            .saturating_add(limit.min(storage::MAX_RANGE_LIMIT) as u64)
+            .min(last_id.saturating_add(1));
+
+        for id in start_id..end {
+            storage::migrate_blood_unit(&env, id);
+        }
+
+        if end > last_id {
+            storage::set_version(&env, &storage::CONTRACT_VERSION);
+            return Ok(0);
+        }
+        Ok(end)
+    }
+
+    /// Move a blood unit to a new status
+    ///
+    /// Requires the InventoryManager role at the unit's bank.
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: Caller holds no role at the unit's bank
+    /// - `InsufficientPermissions`: Caller's role is below InventoryManager
+    /// - `RegisterOnlyBank`: The unit's bank is RegisterOnly
+    /// - `BloodUnitExpired`: Unit is past its expiration time and the new
+    ///   status is not Expired
+    /// - `InvalidStatusTransition`: Transition is not allowed
+    pub fn update_status(
+        env: Env,
+        unit_id: u64,
+        new_status: BloodStatus,
+        authorized_by: Address,
+        reason: Option<String>,
+    ) -> Result<BloodUnit, ContractError> {
+        authorized_by.require_auth();
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        Self::check_bank_role(
+            &env,
+            &authorized_by,
+            &blood_unit.bank_id,
+            BankRole::InventoryManager,
+        )?;
+        Self::check_full_tier(&env, &authorized_by, &blood_unit.bank_id)?;
+
+        let current_time = env.ledger().timestamp();
+
+        if blood_unit.is_expired(current_time) && new_status != BloodStatus::Expired {
+            return Err(ContractError::BloodUnitExpired);
+        }
+
+        if blood_unit.status.is_terminal() {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        validation::validate_unit_transition(&blood_unit, new_status)?;
+
+        Self::apply_status_change(&env, &mut blood_unit, new_status, &authorized_by, reason);
+
+        Ok(blood_unit)
+    }
+
+    pub fn mark_delivered(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+        delivery_location: String,
+    ) -> Result<BloodUnit, ContractError> {
+        Self::update_status(
+            env,
+            unit_id,
+            BloodStatus::Delivered,
+            authorized_by,
+            Some(delivery_location),
+        )
+    }
+
+    pub fn mark_expired(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+    ) -> Result<BloodUnit, ContractError> {
+        let reason = String::from_str(&env, "Marked as expired");
+        Self::update_status(
+            env,
+            unit_id,
+            BloodStatus::Expired,
+            authorized_by,
+            Some(reason),
+        )
+    }
+
+    /// Mark Available units past their expiration as Expired (admin only)
+    ///
+    /// Reads each blood type's expiration-sorted availability index from
+    /// the front, so the longest-overdue units of a type go first. Besides
+    /// the usual status change events per unit, every call ends with one
+    /// `expiry_sweep_completed` event whose `more_remaining` flag tells
+    /// keepers to call again.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `limit` - Maximum number of units to expire (capped at `MAX_EXPIRY_BATCH`)
+    ///
+    /// # Returns
+    /// Number of units expired
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn expire_units(env: Env, limit: u32) -> Result<u32, ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let now = env.ledger().timestamp();
+        let max = limit.min(storage::MAX_EXPIRY_BATCH);
+
+        // Collect first: expiring a unit rewrites the availability index.
+        // One extra overdue unit tells whether the limit cut the sweep short.
+        let mut overdue = Vec::new(&env);
+        for blood_type in BloodType::ALL {
+            for entry in storage::get_available_by_expiry(&env, blood_type).iter() {
+                if overdue.len() > max || entry.expiration_timestamp > now {
+                    break;
+                }
+                overdue.push_back(entry.blood_unit_id);
+            }
+        }
+        let more_remaining = overdue.len() > max;
+        if more_remaining {
+            overdue.pop_back();
+        }
+
+        let reason = String::from_str(&env, "Expired by sweep");
+        for unit_id in overdue.iter() {
+            if let Some(mut blood_unit) = storage::get_blood_unit(&env, unit_id) {
+                Self::apply_status_change(
+                    &env,
+                    &mut blood_unit,
+                    BloodStatus::Expired,
+                    &admin,
+                    Some(reason.clone()),
+                );
+            }
+        }
+
+        events::emit_expiry_sweep_completed(&env, &overdue, more_remaining);
+
+        Ok(overdue.len())
+    }
+
+    /// Reserve an available blood unit for a request
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the blood unit to reserve
+    /// * `request_id` - Request (request contract) the unit is reserved for
+    /// * `recipient_type` - Blood type of the request
+    /// * `accept_compatible` - Allow compatible, non-identical blood types
+    /// * `authorized_by` - Admin performing the reservation
+    /// * `patient_ref` - Patient the request is for, as supplied by the
+    ///   requesting hospital; required to reserve an autologous unit, which
+    ///   only its donor may receive
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `BloodUnitNotAvailable`: Unit was donated for a different request
+    /// - `AutologousMismatch`: Unit is autologous and `patient_ref` is not its donor
+    /// - `TemperatureExcursion`: Unit left its temperature range
+    /// - `IncompatibleBloodType`: Unit cannot be given to the recipient type
+    /// - `InvalidStatusTransition`: Unit is not Available, Directed or Autologous
+    ///
+    /// # Events
+    /// Emits `status_changed` and `blood_reserved`; the matching
+    /// `reservation_released` follows when the hold ends
+    pub fn reserve_unit(
+        env: Env,
+        unit_id: u64,
+        request_id: u64,
+        recipient_type: BloodType,
+        accept_compatible: bool,
+        authorized_by: Address,
+        patient_ref: Option<Address>,
+    ) -> Result<BloodUnit, ContractError> {
+        let blood_unit = storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        if blood_unit
+            .directed_to
+            .is_some_and(|target| target != request_id)
+        {
+            return Err(ContractError::BloodUnitNotAvailable);
+        }
+
+        if blood_unit.autologous && patient_ref != blood_unit.donor_id {
+            return Err(ContractError::AutologousMismatch);
+        }
+
+        if storage::has_temperature_excursion(&env, unit_id) {
+            return Err(ContractError::TemperatureExcursion);
+        }
+
+        if !compatibility::is_acceptable_match(
+            blood_unit.blood_type,
+            recipient_type,
+            accept_compatible,
+        ) {
+            return Err(ContractError::IncompatibleBloodType);
+        }
+
+        let reason = String::from_str(&env, "Reserved for request");
+        let mut blood_unit = Self::update_status(
+            env.clone(),
+            unit_id,
+            BloodStatus::Reserved,
+            authorized_by,
+            Some(reason),
+        )?;
+
+        blood_unit.reserved_for = Some(request_id);
+        storage::set_blood_unit(&env, &blood_unit);
+        storage::add_to_reservation_index(&env, request_id, unit_id);
+
+        events::emit_blood_reserved(&env, &blood_unit, request_id);
+
+        Ok(blood_unit)
+    }
+
+    /// Return every unit still reserved or donated for a request to Available
+    ///
+    /// Called by the request contract when a request reaches a terminal
+    /// state, or by the admin. Units already in transit or delivered are
+    /// left alone; units past their expiration time are moved to Expired
+    /// instead. Directed units the request never used join the general pool.
+    /// Autologous units go back to being held for their donor, or are
+    /// discarded once past their expiration time.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Configured request contract or admin
+    /// * `request_id` - Request whose reservations are released
+    ///
+    /// # Returns
+    /// Number of units returned to Available
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller is neither the request contract nor admin
+    pub fn release_units_for_request(
+        env: Env,
+        caller: Address,
+        request_id: u64,
+    ) -> Result<u32, ContractError> {
+        Self::check_request_contract_or_admin(&env, &caller)?;
+
+        let current_time = env.ledger().timestamp();
+        let reason = String::from_str(&env, "Request closed");
+        let mut released = 0u32;
+
+        let mut unit_ids = storage::get_reserved_units(&env, request_id);
+        unit_ids.append(&storage::get_directed_units(&env, request_id));
+
+        for unit_id in unit_ids.iter() {
+            let Some(mut blood_unit) = storage::get_blood_unit(&env, unit_id) else {
+                continue;
+            };
+
+            if !matches!(
+                blood_unit.status,
+                BloodStatus::Reserved | BloodStatus::Directed
+            ) {
+                continue;
+            }
+
+            let expired = blood_unit.is_expired(current_time);
+            let (new_status, reason) = match (blood_unit.autologous, expired) {
+                (true, true) => {
+                    storage::set_discard_reason(&env, blood_unit.id, DiscardReason::Expired);
+                    (
+                        BloodStatus::Discarded,
+                        String::from_str(&env, "Autologous unit expired unused"),
+                    )
+                }
+                (true, false) => (BloodStatus::Autologous, reason.clone()),
+                (false, true) => (BloodStatus::Expired, reason.clone()),
+                (false, false) => {
+                    released += 1;
+                    (BloodStatus::Available, reason.clone())
+                }
+            };
+
+            Self::apply_status_change(&env, &mut blood_unit, new_status, &caller, Some(reason));
+        }
+
+        Ok(released)
+    }
+
+    /// Move a unit's reservation from its current request to another one
+    ///
+    /// Used by the request contract to let a more urgent request take over
+    /// a held unit. The unit stays Reserved; only the request holding it
+    /// changes. Directed and autologous units cannot be moved.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Configured request contract or admin
+    /// * `unit_id` - Reserved blood unit
+    /// * `request_id` - Request taking over the reservation
+    /// * `recipient_type` - Blood type of that request
+    /// * `accept_compatible` - Allow compatible, non-identical blood types
+    ///
+    /// # Returns
+    /// The request that held the unit before
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller is neither the request contract nor admin
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `InvalidStatus`: Unit is not Reserved
+    /// - `InvalidInput`: Unit is already reserved for `request_id`
+    /// - `BloodUnitNotAvailable`: Unit was donated for its current request
+    /// - `AutologousMismatch`: Unit is autologous
+    /// - `BloodUnitExpired`: Unit is past its expiration time
+    /// - `TemperatureExcursion`: Unit left its temperature range
+    /// - `IncompatibleBloodType`: Unit cannot be given to the recipient type
+    ///
+    /// # Events
+    /// Emits `reservation_released` for the previous request and
+    /// `blood_reserved` for the new one
+    pub fn transfer_reservation(
+        env: Env,
+        caller: Address,
+        unit_id: u64,
+        request_id: u64,
+        recipient_type: BloodType,
+        accept_compatible: bool,
+    ) -> Result<u64, ContractError> {
+        Self::check_request_contract_or_admin(&env, &caller)?;
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        let previous = match (blood_unit.status, blood_unit.reserved_for) {
+            (BloodStatus::Reserved, Some(previous)) => previous,
+            _ => return Err(ContractError::InvalidStatus),
+        };
+        if previous == request_id {
+            return Err(ContractError::InvalidInput);
+        }
+        if blood_unit.directed_to.is_some() {
+            return Err(ContractError::BloodUnitNotAvailable);
+        }
+        if blood_unit.autologous {
+            return Err(ContractError::AutologousMismatch);
+        }
+        if blood_unit.is_expired(env.ledger().timestamp()) {
+            return Err(ContractError::BloodUnitExpired);
+        }
+        if storage::has_temperature_excursion(&env, unit_id) {
+            return Err(ContractError::TemperatureExcursion);
+        }
+        if !compatibility::is_acceptable_match(
+            blood_unit.blood_type,
+            recipient_type,
+            accept_compatible,
+        ) {
+            return Err(ContractError::IncompatibleBloodType);
+        }
+
+        Self::end_reservation(&env, &mut blood_unit);
+        blood_unit.reserved_for = Some(request_id);
+        storage::set_blood_unit(&env, &blood_unit);
+        storage::add_to_reservation_index(&env, request_id, unit_id);
+
+        events::emit_blood_reserved(&env, &blood_unit, request_id);
+
+        Ok(previous)
+    }
+
+    /// Get how much of a bank's stock is held in reservations, and for how
+    /// long holds have lasted
+    ///
+    /// Units still Reserved past their expiration count until they are
+    /// released.
+    pub fn get_reservation_stats(env: Env, bank: Address) -> ReservationStats {
+        storage::get_reservation_stats(&env, &bank)
+    }
+
+    /// Get the request a blood unit is reserved for, if any
+    pub fn get_reserved_for(env: Env, unit_id: u64) -> Option<u64> {
+        storage::get_blood_unit(&env, unit_id).and_then(|unit| unit.reserved_for)
+    }
+
+    /// Get blood unit IDs currently reserved for a request
+    pub fn get_reserved_units(env: Env, request_id: u64) -> Vec<u64> {
+        storage::get_reserved_units(&env, request_id)
+    }
+
+    /// Get blood unit IDs donated for a request and not yet released
+    ///
+    /// Directed units are left out of every general availability query;
+    /// this is the only listing that returns them.
+    pub fn get_directed_units(env: Env, request_id: u64) -> Vec<u64> {
+        storage::get_directed_units(&env, request_id)
+    }
+
+    /// Put a request on the waitlist of a blood type
+    ///
+    /// Every Available unit of that type registered afterwards is announced
+    /// with `match_available` for the highest-priority waitlisted request,
+    /// until the request is removed. Nothing is reserved automatically.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Configured request contract or admin
+    /// * `request_id` - Request (request contract) waiting for units
+    /// * `blood_type` - Blood type the request needs
+    /// * `priority_score` - Request priority; higher scores are matched first
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller is neither the request contract nor admin
+    /// - `AlreadyExists`: Request is already waitlisted
+    pub fn waitlist_request(
+        env: Env,
+        caller: Address,
+        request_id: u64,
+        blood_type: BloodType,
+        priority_score: u64,
+    ) -> Result<(), ContractError> {
+        Self::check_request_contract_or_admin(&env, &caller)?;
+
+        if storage::is_waitlisted(&env, request_id) {
+            return Err(ContractError::AlreadyExists);
+        }
+
+        let entry = WaitlistEntry {
+            request_id,
+            priority_score,
+            added_at: env.ledger().timestamp(),
+        };
+        storage::add_to_waitlist(&env, blood_type, &entry);
+        Ok(())
+    }
+
+    /// Take a request off the waitlist it is on
+    ///
+    /// Called by the request contract once the request is fulfilled or
+    /// closed, or by the admin.
+    ///
+    /// # Returns
+    /// Whether the request was waitlisted
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller is neither the request contract nor admin
+    pub fn remove_from_waitlist(
+        env: Env,
+        caller: Address,
+        request_id: u64,
+    ) -> Result<bool, ContractError> {
+        Self::check_request_contract_or_admin(&env, &caller)?;
+
+        Ok(storage::remove_from_waitlist(&env, request_id))
+    }
+
+    /// Get the waitlist of a blood type, highest priority first
+    pub fn get_waitlist(env: Env, blood_type: BloodType) -> Vec<WaitlistEntry> {
+        storage::get_waitlist(&env, blood_type)
+    }
+
+    /// Configure the request contract allowed to release reservations (admin only)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_request_contract(env: Env, request_contract: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_request_contract(&env, &request_contract);
+        events::emit_request_contract_changed(&env, &request_contract, &admin);
+        Ok(())
+    }
+
+    /// Take a blood unit out of circulation
+    ///
+    /// Requires the InventoryManager role at the unit's bank. Unlike other
+    /// status changes this is allowed after the unit's expiration time.
+    /// The discard is counted under `discard_reason` in the current epoch
+    /// (see `get_discard_breakdown`).
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the blood unit to discard
+    /// * `authorized_by` - Bank or operator discarding the unit
+    /// * `discard_reason` - Hemovigilance category of the discard
+    /// * `reason` - Why the unit is discarded, in free text
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: Caller holds no role at the unit's bank
+    /// - `InsufficientPermissions`: Caller's role is below InventoryManager
+    /// - `RegisterOnlyBank`: The unit's bank is RegisterOnly
+    /// - `InvalidStatusTransition`: Unit is already delivered, expired or discarded
+    ///
+    /// # Events
+    /// Emits `status_changed` and `blood_discarded`
+    pub fn discard_blood_unit(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+        discard_reason: DiscardReason,
+        reason: String,
+    ) -> Result<BloodUnit, ContractError> {
+        authorized_by.require_auth();
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        Self::check_bank_role(
+            &env,
+            &authorized_by,
+            &blood_unit.bank_id,
+            BankRole::InventoryManager,
+        )?;
+        Self::check_full_tier(&env, &authorized_by, &blood_unit.bank_id)?;
+
+        validation::validate_unit_transition(&blood_unit, BloodStatus::Discarded)?;
+
+        storage::set_discard_reason(&env, unit_id, discard_reason);
+        Self::apply_status_change(
+            &env,
+            &mut blood_unit,
+            BloodStatus::Discarded,
+            &authorized_by,
+            Some(reason),
+        );
+
+        Ok(blood_unit)
+    }
+
+    /// Void a registration the bank got wholly wrong (duplicate bag, wrong
+    /// facility)
+    ///
+    /// Only allowed within the void window after registration (see
+    /// `set_void_window`) and while the unit is untouched: still in the
+    /// status it was registered in, never reserved, with no metadata,
+    /// location, custody handover, temperature reading or quantity
+    /// correction recorded. The unit becomes Voided and is taken out of
+    /// every index, stock total and counter, including the bank's unit
+    /// count, its daily registrations and the donor's donation count, so it
+    /// does not show up as wastage. Its external reference is released, so
+    /// the corrected bag can be registered under it. After the window, use
+    /// `discard_blood_unit`.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank_id` - Bank that registered the unit
+    /// * `unit_id` - ID of the blood unit to void
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: Unit belongs to another bank
+    /// - `VoidWindowClosed`: The void window has passed
+    /// - `UnitAlreadyModified`: Unit changed since it was registered, or was
+    ///   produced by pooling or splitting rather than registered
+    ///
+    /// # Events
+    /// Emits `registration_voided`
+    pub fn void_registration(
+        env: Env,
+        bank_id: Address,
+        unit_id: u64,
+    ) -> Result<BloodUnit, ContractError> {
+        bank_id.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        if blood_unit.bank_id != bank_id {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let window_ends = blood_unit
+            .donation_timestamp
+            .saturating_add(storage::get_void_window(&env));
+        if env.ledger().timestamp() > window_ends {
+            return Err(ContractError::VoidWindowClosed);
+        }
+
+        if !Self::is_untouched(&env, &blood_unit) {
+            return Err(ContractError::UnitAlreadyModified);
+        }
+
+        // Undo everything `register` indexed and counted
+        let registered_status = blood_unit.status;
+        storage::remove_from_registration_indexes(&env, &blood_unit);
+        storage::remove_from_stock_totals(
+            &env,
+            blood_unit.blood_type,
+            registered_status,
+            blood_unit.quantity_ml,
+        );
+        if registered_status == BloodStatus::Available {
+            storage::remove_from_available_by_expiry(&env, &blood_unit);
+        }
+        Self::end_direction(&env, &mut blood_unit);
+        storage::decrement_bank_unit_count(&env, &bank_id);
+        storage::decrement_daily_registrations(
+            &env,
+            &bank_id,
+            storage::day_of(blood_unit.donation_timestamp),
+        );
+        if let Some(donor) = &blood_unit.donor_id {
+            storage::retract_donation(&env, donor);
+        }
+
+        blood_unit.status = BloodStatus::Voided;
+        storage::set_blood_unit(&env, &blood_unit);
+
+        events::emit_registration_voided(&env, &blood_unit);
+        if registered_status == BloodStatus::Available {
+            Self::check_low_stock(&env, blood_unit.blood_type);
+            Self::notify_watchers(&env, blood_unit.blood_type);
+        }
+
+        Ok(blood_unit)
+    }
+
+    /// Get the number of discards per reason in a statistics epoch
+    ///
+    /// Epochs are 30-day windows (`timestamp / SECONDS_PER_EPOCH`); a
+    /// discard counts towards the epoch it happened in. Reasons with no
+    /// discards are left out. Units discarded before reasons were recorded
+    /// are not counted.
+    pub fn get_discard_breakdown(env: Env, epoch: u64) -> Map<DiscardReason, u32> {
+        storage::get_discard_stats(&env, epoch)
+    }
+
+    /// Get the category a blood unit was discarded under
+    ///
+    /// None until the unit is Discarded.
+    pub fn get_discard_reason(env: Env, blood_unit_id: u64) -> Option<DiscardReason> {
+        storage::get_discard_reason(&env, blood_unit_id)
+    }
+
+    /// Correct the registered quantity of a blood unit (admin only)
+    ///
+    /// Meant for data-entry mistakes. Only Available units can be corrected;
+    /// once a unit is reserved or has left storage its quantity is fixed.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is not the admin
+    /// - `InvalidInput`: Justification is empty
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `BloodUnitNotAvailable`: Unit is not Available
+    /// - `InvalidQuantity`: New quantity is out of range
+    ///
+    /// # Events
+    /// Emits `unit_corrected`
+    pub fn correct_unit_quantity(
+        env: Env,
+        admin: Address,
+        unit_id: u64,
+        new_quantity_ml: u32,
+        justification: String,
+    ) -> Result<BloodUnit, ContractError> {
+        admin.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if admin != storage::get_admin(&env) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if justification.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        if blood_unit.status != BloodStatus::Available {
+            return Err(ContractError::BloodUnitNotAvailable);
+        }
+
+        validation::validate_quantity(new_quantity_ml)?;
+
+        let old_quantity_ml = blood_unit.quantity_ml;
+        blood_unit.quantity_ml = new_quantity_ml;
+        storage::set_blood_unit(&env, &blood_unit);
+        storage::remove_from_stock_totals(
+            &env,
+            blood_unit.blood_type,
+            BloodStatus::Available,
+            old_quantity_ml,
+        );
+        storage::add_to_stock_totals(
+            &env,
+            blood_unit.blood_type,
+            BloodStatus::Available,
+            new_quantity_ml,
+        );
+        if new_quantity_ml < old_quantity_ml {
+            Self::check_low_stock(&env, blood_unit.blood_type);
+        }
+        if new_quantity_ml != old_quantity_ml {
+            Self::notify_watchers(&env, blood_unit.blood_type);
+        }
+
+        storage::record_quantity_correction(
+            &env,
+            &QuantityCorrection {
+                blood_unit_id: unit_id,
+                old_quantity_ml,
+                new_quantity_ml,
+                corrected_by: admin,
+                justification: justification.clone(),
+                corrected_at: env.ledger().timestamp(),
+            },
+        );
+        events::emit_unit_corrected(
+            &env,
+            unit_id,
+            old_quantity_ml,
+            new_quantity_ml,
+            &justification,
+        );
+
+        Ok(blood_unit)
+    }
+
+    /// Get the quantity corrections made to a blood unit
+    pub fn get_quantity_corrections(env: Env, unit_id: u64) -> Vec<QuantityCorrection> {
+        storage::get_quantity_corrections(&env, unit_id)
+    }
+
+    /// Move several blood units to the same status
+    ///
+    /// Requires the InventoryManager role at every unit's bank, and every
+    /// unit's bank to be Full; the whole batch fails if any unit is rejected.
+    pub fn batch_update_status(
+        env: Env,
+        unit_ids: Vec<u64>,
+        new_status: BloodStatus,
+        authorized_by: Address,
+        reason: Option<String>,
+    ) -> Result<u64, ContractError> {
+        authorized_by.require_auth();
+
+        let current_time = env.ledger().timestamp();
+        let mut updated_count = 0u64;
+
+        for i in 0..unit_ids.len() {
+            let unit_id = unit_ids.get(i).ok_or(ContractError::NotFound)?;
+            let mut blood_unit =
+                storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+            Self::check_bank_role(
+                &env,
+                &authorized_by,
+                &blood_unit.bank_id,
+                BankRole::InventoryManager,
+            )?;
+            Self::check_full_tier(&env, &authorized_by, &blood_unit.bank_id)?;
+
+            if blood_unit.is_expired(current_time) {
+                return Err(ContractError::BloodUnitExpired);
+            }
+
+            if blood_unit.status.is_terminal() {
+                return Err(ContractError::InvalidStatusTransition);
+            }
+
+            validation::validate_unit_transition(&blood_unit, new_status)?;
+
+            Self::apply_status_change(
+                &env,
+                &mut blood_unit,
+                new_status,
+                &authorized_by,
+                reason.clone(),
+            );
+
+            updated_count += 1;
+        }
+
+        Ok(updated_count)
+    }
+
+    pub fn get_status_history(env: Env, unit_id: u64) -> Vec<crate::types::StatusChangeHistory> {
+        storage::get_status_history(&env, unit_id)
+    }
+
+    pub fn get_status_change_count(env: Env, unit_id: u64) -> u64 {
+        storage::get_blood_unit_status_change_count(&env, unit_id)
+    }
+
+    /// Record where a blood unit is stored inside its bank
+    ///
+    /// Requires the Registrar role at the unit's bank.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Bank that owns the unit, or one of its operators
+    /// * `unit_id` - ID of the blood unit being moved
+    /// * `location` - Storage location label (fridge, freezer, shelf)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidInput`: Location is empty
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `NotAuthorizedBloodBank`: Caller is neither a bank nor an operator of the unit's bank
+    /// - `Unauthorized`: Caller is a bank, but the unit belongs to another bank
+    /// - `InsufficientPermissions`: Caller's role is below Registrar
+    /// - `RegisterOnlyBank`: The unit's bank is RegisterOnly
+    /// - `InvalidStatus`: Unit is no longer held in bank storage
+    ///
+    /// # Events
+    /// Emits `UnitLocationChanged` with the previous and new location
+    pub fn set_unit_location(
+        env: Env,
+        caller: Address,
+        unit_id: u64,
+        location: String,
+    ) -> Result<BloodUnit, ContractError> {
+        caller.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        validation::validate_location(&location)?;
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+        let bank_id = blood_unit.bank_id.clone();
+
+        match Self::check_bank_role(&env, &caller, &bank_id, BankRole::Registrar) {
+            Err(ContractError::Unauthorized) if !storage::is_authorized_bank(&env, &caller) => {
+                return Err(ContractError::NotAuthorizedBloodBank);
+            }
+            result => result?,
+        }
+        Self::check_full_tier(&env, &caller, &bank_id)?;
+
+        if !blood_unit.status.is_in_bank_storage() {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let from_location = blood_unit.location.clone();
+        if let Some(previous) = &from_location {
+            storage::remove_from_location_index(&env, &bank_id, previous, unit_id);
+        }
+        storage::add_to_location_index(&env, &bank_id, &location, unit_id);
+
+        blood_unit.location = Some(location.clone());
+        storage::set_blood_unit(&env, &blood_unit);
+
+        events::emit_unit_location_changed(&env, unit_id, &bank_id, from_location, Some(location));
+
+        Ok(blood_unit)
+    }
+
+    /// Record a courier picking units up for delivery
+    ///
+    /// The courier takes custody of every unit, from the bank or from the
+    /// courier holding it before. Requires the Registrar role at each
+    /// unit's bank.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `courier` - Courier taking the units
+    /// * `unit_ids` - Reserved or in-transit units being picked up
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidInput`: No units given, or the courier already holds one
+    /// - `NotFound`: A blood unit doesn't exist
+    /// - `Unauthorized`: Courier holds no role at a unit's bank
+    /// - `InsufficientPermissions`: Courier's role is below Registrar
+    /// - `RegisterOnlyBank`: A unit's bank is RegisterOnly
+    /// - `InvalidStatusTransition`: A unit is in a terminal state
+    /// - `InvalidStatus`: A unit is neither Reserved nor InTransit
+    ///
+    /// # Events
+    /// Emits `custody_changed` per unit
+    pub fn record_pickup(
+        env: Env,
+        courier: Address,
+        unit_ids: Vec<u64>,
+    ) -> Result<(), ContractError> {
+        courier.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if unit_ids.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        for unit_id in unit_ids.iter() {
+            let mut blood_unit =
+                storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+            Self::check_bank_role(&env, &courier, &blood_unit.bank_id, BankRole::Registrar)?;
+            Self::check_full_tier(&env, &courier, &blood_unit.bank_id)?;
+
+            if blood_unit.status.is_terminal() {
+                return Err(ContractError::InvalidStatusTransition);
+            }
+            if !matches!(
+                blood_unit.status,
+                BloodStatus::Reserved | BloodStatus::InTransit
+            ) {
+                return Err(ContractError::InvalidStatus);
+            }
+            if blood_unit.custodian == courier {
+                return Err(ContractError::InvalidInput);
+            }
+
+            Self::hand_over(&env, &mut blood_unit, &courier);
+            storage::set_blood_unit(&env, &blood_unit);
+        }
+
+        Ok(())
+    }
+
+    /// Record a hospital receiving units in transit
+    ///
+    /// The hospital takes custody of every unit and the units become
+    /// Delivered. The current custodian (courier or bank) must sign the
+    /// handoff together with the hospital.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital` - Hospital receiving the units
+    /// * `unit_ids` - In-transit units being received
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidInput`: No units given
+    /// - `NotFound`: A blood unit doesn't exist
+    /// - `BloodUnitExpired`: A unit is past its expiration time
+    /// - `InvalidStatusTransition`: A unit is in a terminal state
+    /// - `InvalidStatus`: A unit is not InTransit
+    ///
+    /// # Events
+    /// Emits `custody_changed` and `status_changed` per unit
+    pub fn record_receipt(
+        env: Env,
+        hospital: Address,
+        unit_ids: Vec<u64>,
+    ) -> Result<(), ContractError> {
+        hospital.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if unit_ids.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let mut handed_over_by: Vec<Address> = Vec::new(&env);
+        for unit_id in unit_ids.iter() {
+            let mut blood_unit =
+                storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+            if blood_unit.status.is_terminal() {
+                return Err(ContractError::InvalidStatusTransition);
+            }
+            if blood_unit.status != BloodStatus::InTransit {
+                return Err(ContractError::InvalidStatus);
+            }
+            if blood_unit.is_expired(current_time) {
+                return Err(ContractError::BloodUnitExpired);
+            }
+
+            if !handed_over_by.contains(&blood_unit.custodian) {
+                blood_unit.custodian.require_auth();
+                handed_over_by.push_back(blood_unit.custodian.clone());
+            }
+
+            Self::hand_over(&env, &mut blood_unit, &hospital);
+            Self::apply_status_change(
+                &env,
+                &mut blood_unit,
+                BloodStatus::Delivered,
+                &hospital,
+                None,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Get the custody handoffs of a blood unit, oldest first
+    ///
+    /// Units start in the custody of their bank, which is not recorded as
+    /// a handoff.
+    pub fn get_custody_history(env: Env, unit_id: u64) -> Vec<CustodyChange> {
+        storage::get_custody_history(&env, unit_id)
+    }
+
+    /// Log a cold-chain temperature reading for a unit
+    ///
+    /// Readings are checked against the temperature range of the unit's
+    /// product. A reading outside it flags the unit, which can no longer be
+    /// reserved. Only the last `MAX_TEMPERATURE_READINGS` readings are kept.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `custodian` - Current custodian of the unit
+    /// * `unit_id` - Blood unit the reading is for
+    /// * `temp_decicelsius` - Temperature in tenths of a degree Celsius
+    /// * `at` - When the reading was taken; not in the future nor before the
+    ///   unit's previous reading
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: Caller is not the unit's custodian
+    /// - `InvalidStatusTransition`: Unit is in a terminal state
+    /// - `InvalidTimestamp`: `at` is in the future or before the previous reading
+    ///
+    /// # Events
+    /// Emits `temperature_excursion` for an out-of-range reading
+    pub fn record_temperature(
+        env: Env,
+        custodian: Address,
+        unit_id: u64,
+        temp_decicelsius: i32,
+        at: u64,
+    ) -> Result<(), ContractError> {
+        custodian.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let blood_unit = storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        if blood_unit.custodian != custodian {
+            return Err(ContractError::Unauthorized);
+        }
+        if blood_unit.status.is_terminal() {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        let log = storage::get_temperature_log(&env, unit_id);
+        let previous_at = log.last().map(|reading| reading.recorded_at).unwrap_or(0);
+        if at > env.ledger().timestamp() || at < previous_at {
+            return Err(ContractError::InvalidTimestamp);
+        }
+
+        let range = storage::get_temperature_range(&env, blood_unit.product);
+        let reading = TemperatureReading {
+            temp_decicelsius,
+            recorded_at: at,
+            recorded_by: custodian,
+            in_range: range.contains(temp_decicelsius),
+        };
+        storage::record_temperature(&env, unit_id, &reading);
+
+        if !reading.in_range {
+            storage::flag_temperature_excursion(&env, unit_id);
+            events::emit_temperature_excursion(&env, unit_id, &reading, &range);
+        }
+
+        Ok(())
+    }
+
+    /// Get the temperature readings logged for a unit, oldest first
+    pub fn get_temperature_log(env: Env, unit_id: u64) -> Vec<TemperatureReading> {
+        storage::get_temperature_log(&env, unit_id)
+    }
+
+    /// Check if a unit ever had a reading outside its temperature range
+    pub fn has_temperature_excursion(env: Env, unit_id: u64) -> bool {
+        storage::has_temperature_excursion(&env, unit_id)
+    }
+
+    /// Record the extended phenotype antigens a unit was typed for
+    ///
+    /// Replaces any tags recorded at registration or by an earlier call;
+    /// an empty list clears them.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - The unit's bank, or an operator with at least Registrar role there
+    /// * `unit_id` - ID of the blood unit
+    /// * `phenotype_tags` - New tags (at most `MAX_PHENOTYPE_TAGS`, each once)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `TooManyPhenotypeTags`: More than `MAX_PHENOTYPE_TAGS` tags
+    /// - `InvalidInput`: A tag is listed twice
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `NotAuthorizedBloodBank`: Caller is neither a bank nor an operator of the unit's bank
+    /// - `Unauthorized`: Caller is a bank, but the unit belongs to another bank
+    /// - `InsufficientPermissions`: Caller's role is below Registrar
+    /// - `InvalidStatus`: Unit is no longer held in bank storage
+    ///
+    /// # Events
+    /// Emits `phenotype_tags_set` with the previous and new tags
+    pub fn set_phenotype_tags(
+        env: Env,
+        caller: Address,
+        unit_id: u64,
+        phenotype_tags: Vec<Symbol>,
+    ) -> Result<BloodUnit, ContractError> {
+        caller.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        validation::validate_phenotype_tags(&phenotype_tags)?;
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+        let bank_id = blood_unit.bank_id.clone();
+
+        match Self::check_bank_role(&env, &caller, &bank_id, BankRole::Registrar) {
+            Err(ContractError::Unauthorized) if !storage::is_authorized_bank(&env, &caller) => {
+                return Err(ContractError::NotAuthorizedBloodBank);
+            }
+            result => result?,
+        }
+
+        if !blood_unit.status.is_in_bank_storage() {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let previous_tags = blood_unit.phenotype_tags.clone();
+        blood_unit.phenotype_tags = phenotype_tags;
+        storage::set_blood_unit(&env, &blood_unit);
+
+        events::emit_phenotype_tags_set(&env, &blood_unit, previous_tags);
+
+        Ok(blood_unit)
+    }
+
+    /// Check if a unit carries every one of `required_tags`
+    ///
+    /// Used by the request contract to check units assigned to requests
+    /// that need phenotype-matched blood. Unknown units never match.
+    pub fn has_phenotype_tags(env: Env, unit_id: u64, required_tags: Vec<Symbol>) -> bool {
+        storage::get_blood_unit(&env, unit_id)
+            .is_some_and(|blood_unit| blood_unit.has_phenotype_tags(&required_tags))
+    }
+
+    /// Find Available units of a blood type carrying every required tag
+    ///
+    /// Walks the type's available units in FIFO order (earliest expiration
+    /// first), skipping units already past their expiration or flagged for
+    /// a temperature excursion.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `blood_type` - Blood type to search
+    /// * `required_tags` - Phenotype tags each unit must carry
+    /// * `limit` - Maximum number of results (capped at `MAX_QUERY_LIMIT`)
+    ///
+    /// # Returns
+    /// Unit IDs, soonest expiration first
+    pub fn find_units_with_phenotype(
+        env: Env,
+        blood_type: BloodType,
+        required_tags: Vec<Symbol>,
+        limit: u32,
+    ) -> Vec<u64> {
+        let current_time = env.ledger().timestamp();
+        let max = limit.min(storage::MAX_QUERY_LIMIT);
+        let mut found = Vec::new(&env);
+
+        for entry in storage::get_available_by_expiry(&env, blood_type).iter() {
+            if found.len() >= max {
+                break;
+            }
+            if entry.expiration_timestamp <= current_time {
+                continue;
+            }
+            if let Some(blood_unit) = storage::get_blood_unit(&env, entry.blood_unit_id) {
+                if blood_unit.status == BloodStatus::Available
+                    && blood_unit.has_phenotype_tags(&required_tags)
+                    && !storage::has_temperature_excursion(&env, blood_unit.id)
+                {
+                    found.push_back(blood_unit.id);
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Get IDs of the blood units stored at a location of a bank
+    pub fn get_units_by_location(env: Env, bank_id: Address, location: String) -> Vec<u64> {
+        storage::get_units_by_location(&env, &bank_id, &location)
+    }
+
+    /// Get the unit of a blood type that should be dispensed next (FIFO)
+    ///
+    /// Returns the Available, non-expired unit with the earliest expiration,
+    /// using donation time as a tiebreak. Units flagged for a temperature
+    /// excursion cannot be reserved and are skipped. Reads the expiration-sorted index
+    /// from the front and stops at the first usable unit.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `blood_type` - Blood type to dispense
+    ///
+    /// # Returns
+    /// The unit ID, or None if nothing is available
+    pub fn get_next_unit_fifo(env: Env, blood_type: BloodType) -> Option<u64> {
+        let current_time = env.ledger().timestamp();
+
+        for entry in storage::get_available_by_expiry(&env, blood_type).iter() {
+            if entry.expiration_timestamp <= current_time {
+                // Expired but not yet swept
+                continue;
+            }
+
+            if let Some(blood_unit) = storage::get_blood_unit(&env, entry.blood_unit_id) {
+                if blood_unit.status == BloodStatus::Available
+                    && !storage::has_temperature_excursion(&env, blood_unit.id)
+                {
+                    return Some(blood_unit.id);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Get a bank's Available units that expire within a time window
+    ///
+    /// Units already past their expiration are not included.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank_id` - Bank holding the units
+    /// * `within_seconds` - Window length from now
+    /// * `limit` - Maximum number of results (capped at `MAX_QUERY_LIMIT`)
+    ///
+    /// # Returns
+    /// Unit IDs, soonest expiration first
+    pub fn get_expiring_units_by_bank(
+        env: Env,
+        bank_id: Address,
+        within_seconds: u64,
+        limit: u32,
+    ) -> Vec<u64> {
+        let max = limit.min(storage::MAX_QUERY_LIMIT);
+
+        let mut expiring: Vec<(u64, u64)> = Vec::new(&env);
+        Self::for_each_expiring_unit(&env, within_seconds, |blood_unit| {
+            if blood_unit.bank_id != bank_id {
+                return;
+            }
+
+            let key = (blood_unit.expiration_timestamp, blood_unit.id);
+            let mut low = 0u32;
+            let mut high = expiring.len();
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if expiring.get_unchecked(mid) <= key {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            expiring.insert(low, key);
+        });
+
+        let mut ids = Vec::new(&env);
+        for (_, unit_id) in expiring.iter().take(max as usize) {
+            ids.push_back(unit_id);
+        }
+        ids
+    }
+
+    /// Get the Available volume expiring within a time window, per bank
+    ///
+    /// Units already past their expiration are not included. Banks with
+    /// nothing expiring are absent from the map.
+    pub fn get_expiring_ml_per_bank(env: Env, within_seconds: u64) -> Map<Address, u32> {
+        let mut per_bank: Map<Address, u32> = Map::new(&env);
+        Self::for_each_expiring_unit(&env, within_seconds, |blood_unit| {
+            let total = per_bank.get(blood_unit.bank_id.clone()).unwrap_or(0);
+            per_bank.set(
+                blood_unit.bank_id,
+                total.saturating_add(blood_unit.quantity_ml),
+            );
+        });
+        per_bank
+    }
+
+    /// Count Available units whose expiration has passed but which have not
+    /// been marked Expired yet
+    ///
+    /// A growing count means the keeper marking units expired is lagging.
+    /// Reads the expiration-sorted availability index of each blood type
+    /// from the front, examining at most `limit_scan` entries in total, so
+    /// when the limit is hit the result is a lower bound.
+    pub fn count_overdue_expirations(env: Env, limit_scan: u32) -> u32 {
+        let now = env.ledger().timestamp();
+        let mut scanned = 0u32;
+        let mut overdue = 0u32;
+
+        for blood_type in BloodType::ALL {
+            for entry in storage::get_available_by_expiry(&env, blood_type).iter() {
+                if scanned >= limit_scan {
+                    return overdue;
+                }
+                scanned += 1;
+
+                if entry.expiration_timestamp > now {
+                    break;
+                }
+                overdue += 1;
+            }
+        }
+
+        overdue
+    }
+
+    /// Get the Available unit that has been past its expiration the longest
+    ///
+    /// # Returns
+    /// The unit ID, or None if no Available unit is overdue
+    pub fn get_oldest_overdue_unit(env: Env) -> Option<u64> {
+        let now = env.ledger().timestamp();
+        BloodType::ALL
+            .iter()
+            .filter_map(|blood_type| storage::get_available_by_expiry(&env, *blood_type).first())
+            .filter(|entry| entry.expiration_timestamp <= now)
+            .min_by_key(|entry| (entry.expiration_timestamp, entry.blood_unit_id))
+            .map(|entry| entry.blood_unit_id)
+    }
+
+    /// Get the stock of a blood type broken down by state
+    ///
+    /// Available and reserved volumes are running totals, except that
+    /// Available units already past their expiration are left out of the
+    /// available volume even before they are marked Expired. The
+    /// expiring-soon volume covers Available units that have not yet expired
+    /// but will within the next 72 hours.
+    pub fn get_type_breakdown(env: Env, blood_type: BloodType) -> TypeBreakdown {
+        let totals = storage::get_stock_totals(&env, blood_type);
+        let now = env.ledger().timestamp();
+        let horizon = now.saturating_add(storage::EXPIRING_SOON_SECONDS);
+
+        let mut expired_ml = 0u64;
+        let mut expiring_72h_ml = 0u64;
+        for entry in storage::get_available_by_expiry(&env, blood_type).iter() {
+            if entry.expiration_timestamp > horizon {
+                break;
+            }
+            if let Some(blood_unit) = storage::get_blood_unit(&env, entry.blood_unit_id) {
+                if entry.expiration_timestamp <= now {
+                    // Expired but not yet swept
+                    expired_ml += blood_unit.quantity_ml as u64;
+                } else {
+                    expiring_72h_ml += blood_unit.quantity_ml as u64;
+                }
+            }
+        }
+
+        TypeBreakdown {
+            available_ml: totals.available_ml.saturating_sub(expired_ml),
+            reserved_ml: totals.reserved_ml,
+            expiring_72h_ml,
+        }
+    }
+
+    /// Get the stock breakdown of every blood type
+    pub fn get_all_type_breakdowns(env: Env) -> Map<BloodType, TypeBreakdown> {
+        let mut breakdowns = Map::new(&env);
+        for blood_type in BloodType::ALL {
+            breakdowns.set(
+                blood_type,
+                Self::get_type_breakdown(env.clone(), blood_type),
+            );
+        }
+        breakdowns
+    }
+
+    /// Configure low-stock alerts for a blood type (admin only)
+    ///
+    /// Once Available volume falls below `threshold_ml` a `low_stock` event
+    /// is emitted. Further alerts for the type wait `cooldown_seconds`
+    /// unless Available volume drops by `step_ml` more (0 disables that).
+    /// A threshold of 0 turns alerts off.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_low_stock_alert(
+        env: Env,
+        blood_type: BloodType,
+        threshold_ml: u64,
+        cooldown_seconds: u64,
+        step_ml: u64,
+    ) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let config = LowStockAlertConfig {
+            threshold_ml,
+            cooldown_seconds,
+            step_ml,
+        };
+        storage::set_low_stock_alert(&env, blood_type, &config);
+        events::emit_low_stock_alert_changed(&env, blood_type, &config, &admin);
+
+        Ok(())
+    }
+
+    /// Get the low-stock alert settings of a blood type, if configured
+    pub fn get_low_stock_alert(env: Env, blood_type: BloodType) -> Option<LowStockAlertConfig> {
+        storage::get_low_stock_alert(&env, blood_type)
+    }
+
+    /// Get the last low-stock alert emitted for a blood type
+    ///
+    /// None when the type has not alerted since it was last above its
+    /// threshold.
+    pub fn get_alert_state(env: Env, blood_type: BloodType) -> Option<AlertState> {
+        storage::get_alert_state(&env, blood_type)
+    }
+
+    /// Subscribe to availability changes of a blood type
+    ///
+    /// Whenever the type's Available volume changes (registration,
+    /// reservation, release, expiry, discard, correction, ...) a
+    /// `watched_type_changed` event listing its watchers is emitted, with
+    /// the blood type as a topic.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `AlreadyExists`: Watcher is already subscribed to the type
+    /// - `CapacityExceeded`: The type already has `MAX_WATCHERS_PER_TYPE`
+    ///   watchers
+    pub fn subscribe(
+        env: Env,
+        watcher: Address,
+        blood_type: BloodType,
+    ) -> Result<(), ContractError> {
+        watcher.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let mut watchers = storage::get_watchers(&env, blood_type);
+        if watchers.contains(&watcher) {
+            return Err(ContractError::AlreadyExists);
+        }
+        if watchers.len() >= storage::MAX_WATCHERS_PER_TYPE {
+            return Err(ContractError::CapacityExceeded);
+        }
+
+        watchers.push_back(watcher);
+        storage::set_watchers(&env, blood_type, &watchers);
+        Ok(())
+    }
+
+    /// Stop watching a blood type
+    ///
+    /// # Errors
+    /// - `NotFound`: Watcher is not subscribed to the type
+    pub fn unsubscribe(
+        env: Env,
+        watcher: Address,
+        blood_type: BloodType,
+    ) -> Result<(), ContractError> {
+        watcher.require_auth();
+
+        let mut watchers = storage::get_watchers(&env, blood_type);
+        let index = watchers
+            .first_index_of(&watcher)
+            .ok_or(ContractError::NotFound)?;
+
+        watchers.remove(index);
+        storage::set_watchers(&env, blood_type, &watchers);
+        Ok(())
+    }
+
+    /// Get the watchers subscribed to a blood type, oldest first
+    pub fn get_watchers(env: Env, blood_type: BloodType) -> Vec<Address> {
+        storage::get_watchers(&env, blood_type)
+    }
+
+    /// Set the maximum number of non-terminal units a bank may hold
+    ///
+    /// Banks without a configured capacity are unlimited.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank` - Blood bank address
+    /// * `max_units` - Maximum number of units held at once
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_bank_capacity(env: Env, bank: Address, max_units: u32) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_bank_capacity(&env, &bank, max_units);
+        events::emit_bank_capacity_changed(&env, &bank, max_units, &admin);
+
+        Ok(())
+    }
+
+    /// Require newly registered unit quantities to be a multiple of `step_ml`
+    ///
+    /// A step of 0 (the default) disables the check. Existing units and
+    /// quantity corrections are not affected.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_quantity_step(env: Env, step_ml: u32) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_quantity_step(&env, step_ml);
+        events::emit_quantity_step_changed(&env, step_ml, &admin);
+
+        Ok(())
+    }
+
+    /// Get the configured quantity step in ml (0 when disabled)
+    pub fn get_quantity_step(env: Env) -> u32 {
+        storage::get_quantity_step(&env)
+    }
+
+    /// Limit how many units each bank may register per day
+    ///
+    /// Days are counted from the ledger timestamp (UTC day boundaries). A
+    /// limit of 0 (the default) means unlimited. Pools and splits are not
+    /// registrations and are not counted.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_registration_rate_limit(env: Env, max_per_day: u32) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_registration_rate_limit(&env, max_per_day);
+        events::emit_registration_rate_limit_changed(&env, max_per_day, &admin);
+
+        Ok(())
+    }
+
+    /// Set how long after registration a bank may void a unit
+    ///
+    /// Defaults to `DEFAULT_VOID_WINDOW_SECONDS` (1 hour); 0 disables
+    /// voiding.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_void_window(env: Env, window_seconds: u64) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_void_window(&env, window_seconds);
+        events::emit_void_window_changed(&env, window_seconds, &admin);
+
+        Ok(())
+    }
+
+    /// Get how long after registration a bank may void a unit, in seconds
+    pub fn get_void_window(env: Env) -> u64 {
+        storage::get_void_window(&env)
+    }
+
+    /// Get the per-bank daily registration limit (0 when unlimited)
+    pub fn get_registration_rate_limit(env: Env) -> u32 {
+        storage::get_registration_rate_limit(&env)
+    }
+
+    /// Get how many units a bank has registered today
+    pub fn get_registrations_today(env: Env, bank: Address) -> u32 {
+        let day = storage::day_of(env.ledger().timestamp());
+        storage::get_daily_registrations(&env, &bank, day)
+    }
+
+    /// Get a bank's configured capacity (None if unlimited)
+    pub fn get_bank_capacity(env: Env, bank: Address) -> Option<u32> {
+        storage::get_bank_capacity(&env, &bank)
+    }
+
+    /// Get the number of non-terminal units currently held by a bank
+    pub fn get_bank_unit_count(env: Env, bank: Address) -> u32 {
+        storage::get_bank_unit_count(&env, &bank)
+    }
+
+    /// Get the donation profile of a donor
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `donor_id` - Donor address
+    ///
+    /// # Returns
+    /// The donor's profile, or None if no donation has been registered
+    /// The deferral reason code is redacted; see `get_deferral_reason`.
+    /// Temporary deferrals are kept apart, see `get_donor_deferral`.
+    pub fn get_donor_profile(env: Env, donor_id: Address) -> Option<DonorProfile> {
+        storage::get_donor_profile(&env, &donor_id).map(|mut profile| {
+            profile.deferral_reason = None;
+            profile
+        })
+    }
+
+    /// Temporarily defer a donor until a given time (authorized banks and
+    /// admin)
+    ///
+    /// Registrations naming this donor fail with `DonorDeferred` until
+    /// `until` passes. Overlapping deferrals collapse to the latest end
+    /// date: a deferral ending before the one in force is ignored.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Authorized bank or admin setting the deferral
+    /// * `donor_id` - Donor to defer
+    /// * `until` - End of the deferral
+    /// * `reason_code` - Confidential reason code
+    ///
+    /// # Returns
+    /// End of the deferral now in force
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Caller is neither an authorized bank nor
+    ///   the admin
+    /// - `InvalidTimestamp`: `until` is not in the future
+    ///
+    /// # Events
+    /// Emits `donor_temporarily_deferred` (without the reason code) when the
+    /// end date changes
+    pub fn defer_donor(
+        env: Env,
+        caller: Address,
+        donor_id: Address,
+        until: u64,
+        reason_code: u32,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &caller) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        let now = env.ledger().timestamp();
+        if until <= now {
+            return Err(ContractError::InvalidTimestamp);
+        }
+
+        if let Some(current) = storage::get_donor_deferral(&env, &donor_id)
+            .filter(|deferral| deferral.is_active(now) && deferral.until >= until)
+        {
+            return Ok(current.until);
+        }
+
+        storage::set_donor_deferral(
+            &env,
+            &donor_id,
+            &TemporaryDeferral {
+                until,
+                reason_code,
+                deferred_by: caller.clone(),
+            },
+        );
+
+        events::emit_donor_temporarily_deferred(&env, &donor_id, until, &caller);
+
+        Ok(until)
+    }
+
+    /// Get a donor's temporary deferral, if one is in force
+    ///
+    /// Only the admin and the donor themselves may read it.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is neither the admin nor the donor
+    pub fn get_donor_deferral(
+        env: Env,
+        caller: Address,
+        donor_id: Address,
+    ) -> Result<Option<TemporaryDeferral>, ContractError> {
+        caller.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if caller != donor_id && caller != storage::get_admin(&env) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let now = env.ledger().timestamp();
+        Ok(storage::get_donor_deferral(&env, &donor_id).filter(|deferral| deferral.is_active(now)))
+    }
+
+    /// Permanently defer a donor (admin only)
+    ///
+    /// Registrations naming this donor fail with `DonorDeferred` until the
+    /// donor is reinstated.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `donor_id` - Donor to defer
+    /// * `reason_code` - Confidential reason code
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    ///
+    /// # Events
+    /// Emits `donor_deferred` (without the reason code)
+    pub fn defer_donor_permanently(
+        env: Env,
+        donor_id: Address,
+        reason_code: u32,
+    ) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let mut profile = storage::get_or_new_donor_profile(&env, &donor_id);
+        profile.permanently_deferred = true;
+        profile.deferral_reason = Some(reason_code);
+        storage::set_donor_profile(&env, &profile);
+
+        events::emit_donor_deferral_changed(&env, &donor_id, true);
+
+        Ok(())
+    }
+
+    /// Lift a donor's permanent deferral (admin only)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidStatus`: Donor is not deferred
+    ///
+    /// # Events
+    /// Emits `donor_reinstated`
+    pub fn reinstate_donor(env: Env, donor_id: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
         admin.require_auth();
 
-        // Check if already initialized
-        if env.storage().instance().has(&DataKey::Admin) {
-            return Err(ContractError::AlreadyInitialized);
-        }
+        let mut profile = storage::get_donor_profile(&env, &donor_id)
+            .filter(|profile| profile.permanently_deferred)
+            .ok_or(ContractError::InvalidStatus)?;
+        profile.permanently_deferred = false;
+        profile.deferral_reason = None;
+        storage::set_donor_profile(&env, &profile);
 
-        // Set admin
-        storage::set_admin(&env, &admin);
+        events::emit_donor_deferral_changed(&env, &donor_id, false);
 
         Ok(())
     }
 
-    /// Register a new blood donation into the inventory
-    ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `bank_id` - Blood bank's address (must be authorized)
-    /// * `blood_type` - Type of blood (A+, A-, B+, B-, AB+, AB-, O+, O-)
-    /// * `quantity_ml` - Quantity in milliliters (100-600ml)
-    /// * `expiration_timestamp` - Unix timestamp when blood expires
-    /// * `donor_id` - Optional donor address (None for anonymous)
+    /// Get the confidential reason code of a donor's deferral
     ///
-    /// # Returns
-    /// Unique ID of the registered blood unit
+    /// Only the admin and the donor themselves may read it.
     ///
     /// # Errors
-    /// - `NotInitialized`: Contract not initialized
-    /// - `NotAuthorizedBloodBank`: Bank is not authorized
-    /// - `InvalidQuantity`: Quantity outside acceptable range
-    /// - `InvalidExpiration`: Expiration date is invalid
-    ///
-    /// # Events
-    /// Emits `BloodRegistered` event with all blood unit details
-    pub fn register_blood(
+    /// - `Unauthorized`: Caller is neither the admin nor the donor
+    pub fn get_deferral_reason(
         env: Env,
+        caller: Address,
+        donor_id: Address,
+    ) -> Result<Option<u32>, ContractError> {
+        caller.require_auth();
+
+        if caller != donor_id && caller != storage::get_admin(&env) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        Ok(storage::get_donor_profile(&env, &donor_id).and_then(|profile| profile.deferral_reason))
+    }
+
+    /// Shared implementation of `register_blood` and `register_component`
+    #[allow(clippy::too_many_arguments)]
+    fn register(
+        env: &Env,
         bank_id: Address,
         blood_type: BloodType,
+        product: ProductType,
         quantity_ml: u32,
         expiration_timestamp: u64,
         donor_id: Option<Address>,
+        options: RegistrationOptions,
     ) -> Result<u64, ContractError> {
-        // 1. Verify bank authentication
-        bank_id.require_auth();
+        let RegistrationOptions {
+            directed_to_request: directed_to,
+            campaign_id,
+            phenotype_tags,
+            autologous,
+            external_ref,
+            operator,
+        } = options;
+
+        // 1. Verify the registering key (bank or operator), and donor
+        // consent if named
+        let registered_by = operator.clone().unwrap_or(bank_id.clone());
+        registered_by.require_auth();
+        if let Some(donor) = &donor_id {
+            donor.require_auth();
+        }
 
         // 2. Check contract is initialized
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(ContractError::NotInitialized);
         }
 
-        // 3. Verify bank is authorized
-        if !storage::is_authorized_bank(&env, &bank_id) {
+        // 3. Verify bank is authorized, and the operator may register for it
+        if !storage::is_authorized_bank(env, &bank_id) {
             return Err(ContractError::NotAuthorizedBloodBank);
         }
+        if let Some(operator) = &operator {
+            Self::check_bank_role(env, operator, &bank_id, BankRole::Registrar)?;
+        }
+
+        // A retry of a registration that already went through
+        if let Some(external_ref) = &external_ref {
+            validation::validate_external_ref(external_ref)?;
+            if let Some(existing) = storage::get_registration_by_ref(env, &bank_id, external_ref) {
+                let retry = RegistrationRecord {
+                    blood_unit_id: existing.blood_unit_id,
+                    blood_type,
+                    product,
+                    quantity_ml,
+                    expiration_timestamp,
+                    donor_id: donor_id.clone(),
+                    directed_to_request: directed_to,
+                    campaign_id,
+                    phenotype_tags: phenotype_tags.clone(),
+                    autologous,
+                };
+                if retry != existing {
+                    return Err(ContractError::DuplicateBloodUnit);
+                }
+                return Ok(existing.blood_unit_id);
+            }
+        }
 
         // 4. Validate input parameters
-        validation::validate_blood_registration(&env, quantity_ml, expiration_timestamp)?;
-        validation::validate_minimum_shelf_life(&env, expiration_timestamp)?;
+        let shelf_life = storage::get_shelf_life(env, product);
+        validation::validate_blood_registration(
+            env,
+            quantity_ml,
+            expiration_timestamp,
+            &shelf_life,
+        )?;
+        validation::validate_quantity_step(quantity_ml, storage::get_quantity_step(env))?;
+        validation::validate_minimum_shelf_life(env, expiration_timestamp, &shelf_life)?;
+        validation::validate_phenotype_tags(&phenotype_tags)?;
+
+        // An autologous unit belongs to its (named) donor alone
+        if autologous && (donor_id.is_none() || directed_to.is_some()) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        if let Some(donor) = &donor_id {
+            if storage::is_donor_deferred(env, donor) {
+                return Err(ContractError::DonorDeferred);
+            }
+        }
+
+        if let Some(campaign_id) = campaign_id {
+            let campaign =
+                storage::get_campaign(env, campaign_id).ok_or(ContractError::NotFound)?;
+            if campaign.bank_id != bank_id {
+                return Err(ContractError::Unauthorized);
+            }
+            if !campaign.is_open(env.ledger().timestamp()) {
+                return Err(ContractError::CampaignClosed);
+            }
+        }
+
+        if !storage::has_bank_capacity(env, &bank_id) {
+            return Err(ContractError::CapacityExceeded);
+        }
+
+        let day = storage::day_of(env.ledger().timestamp());
+        let max_per_day = storage::get_registration_rate_limit(env);
+        if max_per_day > 0 && storage::get_daily_registrations(env, &bank_id, day) >= max_per_day {
+            return Err(ContractError::RateLimitExceeded);
+        }
+        storage::increment_daily_registrations(env, &bank_id, day);
 
         // 5. Generate unique blood unit ID
-        let blood_unit_id = storage::increment_blood_unit_id(&env);
+        let blood_unit_id = storage::increment_blood_unit_id(env)?;
 
         // 6. Create blood unit struct
         let current_time = env.ledger().timestamp();
+        let status = if autologous {
+            BloodStatus::Autologous
+        } else if directed_to.is_some() {
+            BloodStatus::Directed
+        } else {
+            BloodStatus::Available
+        };
         let blood_unit = BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
             id: blood_unit_id,
             blood_type,
+            product,
             quantity_ml,
             bank_id: bank_id.clone(),
             donor_id: donor_id.clone(),
             donation_timestamp: current_time,
             expiration_timestamp,
-            status: BloodStatus::Available,
-            metadata: Map::new(&env),
+            status,
+            metadata: Map::new(env),
+            location: None,
+            reserved_for: None,
+            directed_to,
+            campaign_id,
+            phenotype_tags,
+            autologous,
+            pooled_from: Vec::new(env),
+            split_from: None,
+            custodian: bank_id.clone(),
+            registered_by,
         };
 
         // 7. Validate the complete blood unit
         blood_unit.validate(current_time)?;
 
         // 8. Store blood unit
-        storage::set_blood_unit(&env, &blood_unit);
+        storage::set_blood_unit(env, &blood_unit);
+        if let Some(external_ref) = &external_ref {
+            storage::set_registration_by_ref(
+                env,
+                &bank_id,
+                external_ref,
+                &RegistrationRecord::from(&blood_unit),
+            );
+        }
 
         // 9. Update indexes for efficient querying
-        storage::add_to_blood_type_index(&env, &blood_unit);
-        storage::add_to_bank_index(&env, &blood_unit);
-        storage::add_to_status_index(&env, &blood_unit);
-        storage::add_to_donor_index(&env, &blood_unit);
+        storage::add_to_blood_type_index(env, &blood_unit);
+        storage::add_to_bank_index(env, &blood_unit);
+        storage::add_to_status_index(env, &blood_unit);
+        storage::add_to_donor_index(env, &blood_unit);
+        storage::add_to_operator_index(env, &blood_unit);
+        if let Some(request_id) = directed_to {
+            storage::add_to_directed_index(env, request_id, blood_unit_id);
+        } else if status == BloodStatus::Available {
+            storage::add_to_available_by_expiry(env, &blood_unit);
+        }
+        storage::add_to_stock_totals(env, blood_type, status, quantity_ml);
+        if let Some(campaign_id) = campaign_id {
+            storage::add_to_campaign_index(env, campaign_id, blood_unit_id);
+        }
+        storage::increment_bank_unit_count(env, &bank_id);
 
-        // 10. Emit event
-        events::emit_blood_registered(
-            &env,
-            blood_unit_id,
-            &bank_id,
-            blood_type,
-            quantity_ml,
-            expiration_timestamp,
-        );
+        // 10. Emit events, pointing a waitlisted request at the new unit
+        events::emit_blood_registered(env, &blood_unit);
+        if status == BloodStatus::Available {
+            Self::announce_match(env, &blood_unit);
+            Self::notify_watchers(env, blood_type);
+        }
+
+        // 11. Update donor profile and celebrate milestones
+        if let Some(donor) = &donor_id {
+            Self::record_donation(env, donor);
+        }
 
-        // 11. Return blood unit ID
+        // 12. Return blood unit ID
         Ok(blood_unit_id)
     }
 
-    /// Get blood unit details by ID
-    ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `blood_unit_id` - ID of the blood unit to retrieve
-    ///
-    /// # Returns
-    /// Blood unit details
+    /// Count a donation and emit an event for each newly reached milestone
     ///
-    /// # Errors
-    /// - `NotFound`: Blood unit with given ID doesn't exist
-    pub fn get_blood_unit(env: Env, blood_unit_id: u64) -> Result<BloodUnit, ContractError> {
-        storage::get_blood_unit(&env, blood_unit_id).ok_or(ContractError::NotFound)
+    /// Milestones are compared against the highest one already stored on the
+    /// profile, so each fires exactly once.
+    fn record_donation(env: &Env, donor: &Address) {
+        let mut profile = storage::record_donation(env, donor);
+
+        let mut reached = false;
+        for milestone in storage::DONATION_MILESTONES {
+            if profile.donation_count >= milestone && milestone > profile.highest_milestone {
+                profile.highest_milestone = milestone;
+                events::emit_donor_milestone(env, donor, milestone);
+                reached = true;
+            }
+        }
+
+        if reached {
+            storage::set_donor_profile(env, &profile);
+        }
     }
 
-    pub fn update_status(
-        env: Env,
-        unit_id: u64,
-        new_status: BloodStatus,
-        authorized_by: Address,
-        reason: Option<String>,
-    ) -> Result<BloodUnit, ContractError> {
-        authorized_by.require_auth();
+    /// Emit `match_available` pairing an Available unit with the
+    /// highest-priority request waitlisted for its blood type, if any
+    fn announce_match(env: &Env, blood_unit: &BloodUnit) {
+        if let Some(entry) = storage::get_waitlist(env, blood_unit.blood_type).first() {
+            events::emit_match_available(env, blood_unit, entry.request_id);
+        }
+    }
 
-        let admin = storage::get_admin(&env);
-        if authorized_by != admin {
+    /// Authenticate `caller` and check it is the configured request
+    /// contract or the admin
+    fn check_request_contract_or_admin(env: &Env, caller: &Address) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if caller != &storage::get_admin(env)
+            && Some(caller.clone()) != storage::get_request_contract(env)
+        {
             return Err(ContractError::Unauthorized);
         }
+        Ok(())
+    }
 
-        let mut blood_unit =
-            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
-
-        let current_time = env.ledger().timestamp();
+    /// Check that `caller` may act for `bank_id` with at least the `required` role
+    ///
+    /// The admin and the bank itself hold every role.
+    fn check_bank_role(
+        env: &Env,
+        caller: &Address,
+        bank_id: &Address,
+        required: BankRole,
+    ) -> Result<(), ContractError> {
+        if caller == &storage::get_admin(env)
+            || (caller == bank_id && storage::is_authorized_bank(env, bank_id))
+        {
+            return Ok(());
+        }
 
-        if blood_unit.is_expired(current_time) {
-            return Err(ContractError::BloodUnitExpired);
+        match storage::get_bank_role(env, bank_id, caller) {
+            Some(role) if role.satisfies(required) => Ok(()),
+            Some(_) => Err(ContractError::InsufficientPermissions),
+            None => Err(ContractError::Unauthorized),
         }
+    }
 
-        if blood_unit.status.is_terminal() {
-            return Err(ContractError::InvalidStatusTransition);
+    /// Check that `bank_id` may manage its stock beyond registering donations
+    ///
+    /// Fails for RegisterOnly banks and the operators acting for them; the
+    /// admin is never limited.
+    fn check_full_tier(
+        env: &Env,
+        caller: &Address,
+        bank_id: &Address,
+    ) -> Result<(), ContractError> {
+        if caller != &storage::get_admin(env)
+            && storage::get_bank_tier(env, bank_id) == AuthorizationTier::RegisterOnly
+        {
+            return Err(ContractError::RegisterOnlyBank);
         }
+        Ok(())
+    }
+
+    /// Pass a unit to a new custodian, recording the handoff in its custody
+    /// history and emitting `custody_changed`; the caller persists the unit
+    fn hand_over(env: &Env, blood_unit: &mut BloodUnit, to_custodian: &Address) {
+        let from_custodian = blood_unit.custodian.clone();
+        blood_unit.custodian = to_custodian.clone();
 
-        validation::validate_status_transition(blood_unit.status, new_status)?;
+        storage::record_custody_change(
+            env,
+            &CustodyChange {
+                blood_unit_id: blood_unit.id,
+                from_custodian: from_custodian.clone(),
+                to_custodian: to_custodian.clone(),
+                changed_at: env.ledger().timestamp(),
+            },
+        );
+        events::emit_custody_changed(env, blood_unit, &from_custodian);
+    }
 
+    /// Move a validated unit to a new status and keep all derived state in step
+    ///
+    /// Clears the location once the unit leaves storage, releases the
+    /// bank's capacity slot on terminal states, updates the availability
+    /// index, ends the reservation and directed-donation links when the unit
+    /// goes back to Available or is expired or discarded, then persists the unit and records history and events.
+    /// Discards and expirations also publish `blood_discarded` / `blood_expired`.
+    fn apply_status_change(
+        env: &Env,
+        blood_unit: &mut BloodUnit,
+        new_status: BloodStatus,
+        authorized_by: &Address,
+        reason: Option<String>,
+    ) {
         let old_status = blood_unit.status;
         blood_unit.status = new_status;
-        storage::set_blood_unit(&env, &blood_unit);
+        if !new_status.is_in_bank_storage() {
+            Self::clear_location(env, blood_unit);
+        }
+        if new_status.is_terminal() {
+            storage::decrement_bank_unit_count(env, &blood_unit.bank_id);
+        }
+        if matches!(
+            new_status,
+            BloodStatus::Available
+                | BloodStatus::Autologous
+                | BloodStatus::Expired
+                | BloodStatus::Discarded
+        ) {
+            Self::end_reservation(env, blood_unit);
+            Self::end_direction(env, blood_unit);
+        }
+        Self::sync_availability(env, blood_unit, old_status);
+        if old_status != BloodStatus::Reserved && new_status == BloodStatus::Reserved {
+            storage::start_hold(env, blood_unit);
+        } else if old_status == BloodStatus::Reserved && new_status != BloodStatus::Reserved {
+            storage::end_hold(env, blood_unit);
+        }
+        let discard_reason = (new_status == BloodStatus::Discarded).then(|| {
+            // Discards made through a plain status change have no category
+            let discard_reason =
+                storage::get_discard_reason(env, blood_unit.id).unwrap_or(DiscardReason::Other);
+            storage::set_discard_reason(env, blood_unit.id, discard_reason);
+            storage::record_discard(env, discard_reason);
+            discard_reason
+        });
+        storage::set_blood_unit(env, blood_unit);
 
         storage::record_status_change(
-            &env,
-            unit_id,
+            env,
+            blood_unit.id,
             old_status,
             new_status,
-            &authorized_by,
+            authorized_by,
             reason.clone(),
         );
 
-        events::emit_status_change(
-            &env,
-            unit_id,
-            old_status,
-            new_status,
-            &authorized_by,
-            reason,
-        );
+        events::emit_status_change(env, blood_unit, old_status, authorized_by, reason.clone());
 
-        Ok(blood_unit)
+        if let Some(discard_reason) = discard_reason {
+            events::emit_blood_discarded(env, blood_unit, discard_reason, reason);
+        } else if new_status == BloodStatus::Expired {
+            events::emit_blood_expired(env, blood_unit);
+        }
     }
 
-    pub fn mark_delivered(
-        env: Env,
-        unit_id: u64,
-        authorized_by: Address,
-        delivery_location: String,
-    ) -> Result<BloodUnit, ContractError> {
-        Self::update_status(
-            env,
-            unit_id,
-            BloodStatus::Delivered,
-            authorized_by,
-            Some(delivery_location),
-        )
+    /// Visit every Available unit that has not expired yet but will within
+    /// `within_seconds`, reading each type's expiration-sorted index from
+    /// the front
+    fn for_each_expiring_unit(env: &Env, within_seconds: u64, mut visit: impl FnMut(BloodUnit)) {
+        let now = env.ledger().timestamp();
+        let horizon = now.saturating_add(within_seconds);
+
+        for blood_type in BloodType::ALL {
+            for entry in storage::get_available_by_expiry(env, blood_type).iter() {
+                if entry.expiration_timestamp > horizon {
+                    break;
+                }
+                if entry.expiration_timestamp <= now {
+                    continue;
+                }
+                if let Some(blood_unit) = storage::get_blood_unit(env, entry.blood_unit_id) {
+                    visit(blood_unit);
+                }
+            }
+        }
     }
 
-    pub fn mark_expired(
-        env: Env,
-        unit_id: u64,
-        authorized_by: Address,
-    ) -> Result<BloodUnit, ContractError> {
-        let reason = String::from_str(&env, "Marked as expired");
-        Self::update_status(
-            env,
-            unit_id,
-            BloodStatus::Expired,
-            authorized_by,
-            Some(reason),
-        )
+    /// Load summaries for a list of IDs, skipping any that don't exist
+    ///
+    /// Summaries carry the units' effective status.
+    fn load_summaries(env: &Env, ids: impl Iterator<Item = u64>) -> Vec<BloodUnitSummary> {
+        let now = env.ledger().timestamp();
+        let mut summaries = Vec::new(env);
+        for id in ids {
+            if let Some(blood_unit) = storage::get_blood_unit(env, id) {
+                summaries.push_back(BloodUnitSummary::from(
+                    blood_unit.with_effective_status(now),
+                ));
+            }
+        }
+        summaries
     }
 
-    pub fn batch_update_status(
-        env: Env,
-        unit_ids: Vec<u64>,
-        new_status: BloodStatus,
-        authorized_by: Address,
-        reason: Option<String>,
-    ) -> Result<u64, ContractError> {
-        authorized_by.require_auth();
+    /// Tags present in both lists, in the order of `tags`
+    fn shared_tags(env: &Env, tags: &Vec<Symbol>, other: &Vec<Symbol>) -> Vec<Symbol> {
+        let mut shared = Vec::new(env);
+        for tag in tags.iter() {
+            if other.contains(&tag) {
+                shared.push_back(tag);
+            }
+        }
+        shared
+    }
 
-        let admin = storage::get_admin(&env);
-        if authorized_by != admin {
-            return Err(ContractError::Unauthorized);
+    /// Unlink a unit from the request it was reserved for
+    fn end_reservation(env: &Env, blood_unit: &mut BloodUnit) {
+        if let Some(request_id) = blood_unit.reserved_for.take() {
+            storage::remove_from_reservation_index(env, request_id, blood_unit.id);
+            events::emit_reservation_released(env, blood_unit, request_id);
         }
+    }
 
-        let current_time = env.ledger().timestamp();
-        let mut updated_count = 0u64;
+    /// Unlink a unit from the request it was donated for, returning it to
+    /// the general pool
+    fn end_direction(env: &Env, blood_unit: &mut BloodUnit) {
+        if let Some(request_id) = blood_unit.directed_to.take() {
+            storage::remove_from_directed_index(env, request_id, blood_unit.id);
+        }
+    }
 
-        for i in 0..unit_ids.len() {
-            let unit_id = unit_ids.get(i).ok_or(ContractError::NotFound)?;
-            let mut blood_unit =
-                storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+    /// Check a unit is exactly as `register` left it
+    fn is_untouched(env: &Env, blood_unit: &BloodUnit) -> bool {
+        matches!(
+            blood_unit.status,
+            BloodStatus::Available | BloodStatus::Directed | BloodStatus::Autologous
+        ) && blood_unit.reserved_for.is_none()
+            && blood_unit.metadata.is_empty()
+            && blood_unit.location.is_none()
+            && blood_unit.custodian == blood_unit.bank_id
+            && blood_unit.pooled_from.is_empty()
+            && blood_unit.split_from.is_none()
+            && storage::get_status_history(env, blood_unit.id).is_empty()
+            && storage::get_custody_history(env, blood_unit.id).is_empty()
+            && storage::get_temperature_log(env, blood_unit.id).is_empty()
+            && storage::get_quantity_corrections(env, blood_unit.id).is_empty()
+    }
 
-            if blood_unit.is_expired(current_time) {
-                return Err(ContractError::BloodUnitExpired);
-            }
+    /// Keep the expiration-sorted availability index and the per-type
+    /// volume totals in step with a status change
+    fn sync_availability(env: &Env, blood_unit: &BloodUnit, old_status: BloodStatus) {
+        storage::remove_from_stock_totals(
+            env,
+            blood_unit.blood_type,
+            old_status,
+            blood_unit.quantity_ml,
+        );
+        storage::add_to_stock_totals(
+            env,
+            blood_unit.blood_type,
+            blood_unit.status,
+            blood_unit.quantity_ml,
+        );
 
-            if blood_unit.status.is_terminal() {
-                return Err(ContractError::InvalidStatusTransition);
-            }
+        let was_available = old_status == BloodStatus::Available;
+        let is_available = blood_unit.status == BloodStatus::Available;
 
-            validation::validate_status_transition(blood_unit.status, new_status)?;
+        if was_available && !is_available {
+            storage::remove_from_available_by_expiry(env, blood_unit);
+            Self::check_low_stock(env, blood_unit.blood_type);
+        } else if !was_available && is_available {
+            storage::add_to_available_by_expiry(env, blood_unit);
+        }
+        if was_available != is_available {
+            Self::notify_watchers(env, blood_unit.blood_type);
+        }
+    }
 
-            let old_status = blood_unit.status;
-            blood_unit.status = new_status;
-            storage::set_blood_unit(&env, &blood_unit);
+    /// Emit `low_stock` if Available volume of a blood type is below its
+    /// threshold, unless an alert went out within the cooldown and volume
+    /// has not dropped another step since
+    fn check_low_stock(env: &Env, blood_type: BloodType) {
+        let config = match storage::get_low_stock_alert(env, blood_type) {
+            Some(config) if config.threshold_ml > 0 => config,
+            _ => return,
+        };
 
-            storage::record_status_change(
-                &env,
-                unit_id,
-                old_status,
-                new_status,
-                &authorized_by,
-                reason.clone(),
-            );
+        let available_ml = storage::get_stock_totals(env, blood_type).available_ml;
+        if available_ml >= config.threshold_ml {
+            storage::clear_alert_state(env, blood_type);
+            return;
+        }
 
-            events::emit_status_change(
-                &env,
-                unit_id,
-                old_status,
-                new_status,
-                &authorized_by,
-                reason.clone(),
-            );
+        let now = env.ledger().timestamp();
+        if let Some(state) = storage::get_alert_state(env, blood_type) {
+            let cooled_down = now
+                >= state
+                    .last_emitted_at
+                    .saturating_add(config.cooldown_seconds);
+            let dropped_a_step = config.step_ml > 0
+                && available_ml.saturating_add(config.step_ml) <= state.last_available_ml;
+            if !cooled_down && !dropped_a_step {
+                return;
+            }
+        }
 
-            updated_count += 1;
+        storage::set_alert_state(
+            env,
+            blood_type,
+            &AlertState {
+                last_emitted_at: now,
+                last_available_ml: available_ml,
+            },
+        );
+        events::emit_low_stock(env, blood_type, available_ml, config.threshold_ml);
+    }
+
+    /// Emit `watched_type_changed` if anyone is subscribed to the blood type
+    fn notify_watchers(env: &Env, blood_type: BloodType) {
+        let watchers = storage::get_watchers(env, blood_type);
+        if watchers.is_empty() {
+            return;
         }
 
-        Ok(updated_count)
+        let available_ml = storage::get_stock_totals(env, blood_type).available_ml;
+        events::emit_watched_type_changed(env, blood_type, available_ml, watchers);
     }
 
-    pub fn get_status_history(env: Env, unit_id: u64) -> Vec<crate::types::StatusChangeHistory> {
-        storage::get_status_history(&env, unit_id)
+    /// Check that `bank_id` holds a unit it can hand to another bank
+    fn check_lendable(
+        env: &Env,
+        blood_unit: &BloodUnit,
+        bank_id: &Address,
+    ) -> Result<(), ContractError> {
+        if &blood_unit.bank_id != bank_id {
+            return Err(ContractError::Unauthorized);
+        }
+        if blood_unit.status != BloodStatus::Available {
+            return Err(ContractError::BloodUnitNotAvailable);
+        }
+        if blood_unit.is_expired(env.ledger().timestamp()) {
+            return Err(ContractError::BloodUnitExpired);
+        }
+        if storage::has_temperature_excursion(env, blood_unit.id) {
+            return Err(ContractError::TemperatureExcursion);
+        }
+        Ok(())
     }
 
-    pub fn get_status_change_count(env: Env, unit_id: u64) -> u64 {
-        storage::get_blood_unit_status_change_count(&env, unit_id)
+    /// Move a unit into another bank's stock and custody, then persist it
+    ///
+    /// The unit leaves the old bank's location index and unit count; stock
+    /// totals are contract-wide and stay as they are.
+    fn move_to_bank(
+        env: &Env,
+        blood_unit: &mut BloodUnit,
+        to_bank: &Address,
+    ) -> Result<(), ContractError> {
+        if !storage::has_bank_capacity(env, to_bank) {
+            return Err(ContractError::CapacityExceeded);
+        }
+
+        let from_bank = blood_unit.bank_id.clone();
+        Self::clear_location(env, blood_unit);
+        storage::move_bank_index(env, blood_unit.id, &from_bank, to_bank);
+        storage::decrement_bank_unit_count(env, &from_bank);
+        storage::increment_bank_unit_count(env, to_bank);
+
+        blood_unit.bank_id = to_bank.clone();
+        Self::hand_over(env, blood_unit, to_bank);
+        storage::set_blood_unit(env, blood_unit);
+        Ok(())
+    }
+
+    /// Drop a unit from its bank's location index once it leaves storage
+    fn clear_location(env: &Env, blood_unit: &mut BloodUnit) {
+        if let Some(previous) = blood_unit.location.take() {
+            storage::remove_from_location_index(env, &blood_unit.bank_id, &previous, blood_unit.id);
+            events::emit_unit_location_changed(
+                env,
+                blood_unit.id,
+                &blood_unit.bank_id,
+                Some(previous),
+                None,
+            );
+        }
     }
 }
 
@@ -1,118 +1,1371 @@
-use crate::types::{BloodStatus, BloodUnit, DataKey, StatusChangeHistory};
-use soroban_sdk::{Address, Env, String, Vec};
+use crate::conversion;
+use crate::error::ContractError;
+use crate::legacy::LegacyBloodUnit;
+use crate::types::{
+    AlertState, AuthorizationTier, BankRole, BloodStatus, BloodType, BloodUnit, Campaign,
+    ContractVersion, CustodyChange, DataKey, DiscardReason, DonorProfile, ExpiryIndexEntry,
+    LowStockAlertConfig, ProductType, QuantityCorrection, RegistrationRecord, ReservationStats,
+    ShelfLife, StatusChangeHistory, StockTotals, TemperatureRange, TemperatureReading,
+    TemporaryDeferral, UnitLoan, WaitlistEntry,
+};
+use soroban_sdk::{Address, Env, Map, String, Symbol, TryFromVal, Val, Vec};
 
-/// Maximum expiration time (42 days for whole blood)
+/// Maximum expiration time (42 days for whole blood), the default upper
+/// bound of the whole blood and red cell shelf-life table entries
 pub const MAX_EXPIRATION_DAYS: u64 = 42;
 pub const SECONDS_PER_DAY: u64 = 86400;
 
+/// Window used for the expiring-soon part of a type breakdown
+pub const EXPIRING_SOON_SECONDS: u64 = 72 * 3600;
+
+/// Maximum number of results returned by list queries
+pub const MAX_QUERY_LIMIT: u32 = 200;
+
+/// Maximum number of units returned by one range scan
+pub const MAX_RANGE_LIMIT: u32 = 50;
+
+/// Maximum number of units marked Expired by one `expire_units` call
+pub const MAX_EXPIRY_BATCH: u32 = 20;
+
+/// Maximum number of banks that can be authorized by `initialize`
+pub const MAX_INITIAL_BANKS: u32 = 20;
+
+/// Version of this build of the contract, stored by `initialize`
+pub const CONTRACT_VERSION: ContractVersion = ContractVersion { major: 1, minor: 0 };
+
+/// Length of a discard statistics epoch, the same 30-day window the
+/// request contract uses for its statistics
+pub const SECONDS_PER_EPOCH: u64 = 30 * SECONDS_PER_DAY;
+
+/// Maximum number of watchers subscribed to one blood type
+pub const MAX_WATCHERS_PER_TYPE: u32 = 20;
+
+/// Temperature readings kept per unit; older readings are dropped
+pub const MAX_TEMPERATURE_READINGS: u32 = 48;
+
+/// Donation counts that trigger a donor milestone event
+pub const DONATION_MILESTONES: [u32; 3] = [5, 10, 25];
+
+/// Default time after registration during which a bank may void a unit
+pub const DEFAULT_VOID_WINDOW_SECONDS: u64 = 3600;
+
 /// Get the admin address
 pub fn get_admin(env: &Env) -> Address {
     env.storage()
-        .instance()
-        .get(&DataKey::Admin)
-        .expect("Admin not initialized")
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("Admin not initialized")
+}
+
+/// Set the admin address
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+/// Get the stored contract version (None before `initialize`)
+pub fn get_version(env: &Env) -> Option<ContractVersion> {
+    env.storage().instance().get(&DataKey::Version)
+}
+
+/// Store the contract version
+pub fn set_version(env: &Env, version: &ContractVersion) {
+    env.storage().instance().set(&DataKey::Version, version);
+}
+
+/// Get the admin proposed by the current admin, if any
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::PendingAdmin)
+}
+
+/// Record the admin proposed by the current admin
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::PendingAdmin, admin);
+}
+
+/// Clear the pending admin proposal
+pub fn clear_pending_admin(env: &Env) {
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+}
+
+/// Get the role an operator holds at a bank
+pub fn get_bank_role(env: &Env, bank_id: &Address, account: &Address) -> Option<BankRole> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BankRole(bank_id.clone(), account.clone()))
+}
+
+/// Grant (or replace) an operator's role at a bank
+pub fn set_bank_role(env: &Env, bank_id: &Address, account: &Address, role: BankRole) {
+    let key = DataKey::BankRole(bank_id.clone(), account.clone());
+    if !env.storage().persistent().has(&key) {
+        let holders_key = DataKey::BankRoleHolders(bank_id.clone());
+        let mut holders: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&holders_key)
+            .unwrap_or(Vec::new(env));
+        holders.push_back(account.clone());
+        env.storage().persistent().set(&holders_key, &holders);
+    }
+
+    env.storage().persistent().set(&key, &role);
+}
+
+/// Remove an operator's role at a bank
+pub fn remove_bank_role(env: &Env, bank_id: &Address, account: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::BankRole(bank_id.clone(), account.clone()));
+
+    let holders_key = DataKey::BankRoleHolders(bank_id.clone());
+    let mut holders: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&holders_key)
+        .unwrap_or(Vec::new(env));
+
+    if let Some(index) = holders.first_index_of(account) {
+        holders.remove(index);
+        if holders.is_empty() {
+            env.storage().persistent().remove(&holders_key);
+        } else {
+            env.storage().persistent().set(&holders_key, &holders);
+        }
+    }
+}
+
+/// Get every operator holding a role at a bank
+pub fn get_bank_role_holders(env: &Env, bank_id: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BankRoleHolders(bank_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Check if an address is authorized as a blood bank
+///
+/// The admin is always authorized.
+pub fn is_authorized_bank(env: &Env, bank: &Address) -> bool {
+    let admin = get_admin(env);
+    bank == &admin || is_explicitly_authorized_bank(env, bank)
+}
+
+/// Check if a bank was authorized via `authorize_bank`, ignoring the
+/// implicit authorization of the admin
+pub fn is_explicitly_authorized_bank(env: &Env, bank: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::AuthorizedBank(bank.clone()))
+}
+
+/// Authorize a blood bank, listing it unless it already is
+pub fn authorize_bank(env: &Env, bank: &Address) {
+    if is_explicitly_authorized_bank(env, bank) {
+        return;
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::AuthorizedBank(bank.clone()), &true);
+
+    let mut banks = get_authorized_banks(env);
+    banks.push_back(bank.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::AuthorizedBanks, &banks);
+}
+
+/// Revoke a blood bank's authorization and drop it from the list
+pub fn revoke_bank(env: &Env, bank: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::AuthorizedBank(bank.clone()));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::BankTier(bank.clone()));
+
+    let mut banks = get_authorized_banks(env);
+    if let Some(index) = banks.first_index_of(bank) {
+        banks.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::AuthorizedBanks, &banks);
+    }
+}
+
+/// Get a bank's authorization tier
+///
+/// Banks authorized before tiers existed, and the admin, are Full.
+pub fn get_bank_tier(env: &Env, bank: &Address) -> AuthorizationTier {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BankTier(bank.clone()))
+        .unwrap_or(AuthorizationTier::Full)
+}
+
+/// Set a bank's authorization tier
+pub fn set_bank_tier(env: &Env, bank: &Address, tier: AuthorizationTier) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::BankTier(bank.clone()), &tier);
+}
+
+/// Get the banks authorized via `authorize_bank`, in authorization order
+pub fn get_authorized_banks(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AuthorizedBanks)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get the request contract allowed to release reservations
+pub fn get_request_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::RequestContract)
+}
+
+/// Set the request contract allowed to release reservations
+pub fn set_request_contract(env: &Env, request_contract: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::RequestContract, request_contract);
+}
+
+/// Get the quantity step in ml (0 when alignment is not enforced)
+pub fn get_quantity_step(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::QuantityStep)
+        .unwrap_or(0)
+}
+
+/// Set the quantity step in ml (0 disables alignment)
+pub fn set_quantity_step(env: &Env, step_ml: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::QuantityStep, &step_ml);
+}
+
+/// Get the registrations allowed per bank per day (0 when unlimited)
+pub fn get_registration_rate_limit(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::RegistrationRateLimit)
+        .unwrap_or(0)
+}
+
+/// Set the registrations allowed per bank per day (0 removes the limit)
+pub fn set_registration_rate_limit(env: &Env, max_per_day: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::RegistrationRateLimit, &max_per_day);
+}
+
+/// Get the seconds after registration during which a unit may be voided
+pub fn get_void_window(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::VoidWindow)
+        .unwrap_or(DEFAULT_VOID_WINDOW_SECONDS)
+}
+
+/// Set the seconds after registration during which a unit may be voided
+pub fn set_void_window(env: &Env, window_seconds: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::VoidWindow, &window_seconds);
+}
+
+/// Day number containing a timestamp
+pub fn day_of(timestamp: u64) -> u64 {
+    timestamp / SECONDS_PER_DAY
+}
+
+/// Get the number of units a bank registered on a day
+pub fn get_daily_registrations(env: &Env, bank: &Address, day: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DailyRegistrations(bank.clone(), day))
+        .unwrap_or(0)
+}
+
+/// Take a voided registration back off a bank's day
+pub fn decrement_daily_registrations(env: &Env, bank: &Address, day: u64) {
+    let count = get_daily_registrations(env, bank, day);
+    env.storage().persistent().set(
+        &DataKey::DailyRegistrations(bank.clone(), day),
+        &count.saturating_sub(1),
+    );
+}
+
+/// Count a registration against a bank's day
+pub fn increment_daily_registrations(env: &Env, bank: &Address, day: u64) {
+    let count = get_daily_registrations(env, bank, day);
+    env.storage().persistent().set(
+        &DataKey::DailyRegistrations(bank.clone(), day),
+        &(count + 1),
+    );
+}
+
+/// Get the shelf-life bounds of a product (built-in defaults unless configured)
+pub fn get_shelf_life(env: &Env, product: ProductType) -> ShelfLife {
+    env.storage()
+        .instance()
+        .get(&DataKey::ShelfLife(product))
+        .unwrap_or_else(|| product.default_shelf_life())
+}
+
+/// Configure the shelf-life bounds of a product
+pub fn set_shelf_life(env: &Env, product: ProductType, shelf_life: &ShelfLife) {
+    env.storage()
+        .instance()
+        .set(&DataKey::ShelfLife(product), shelf_life);
+}
+
+/// Get the volume fraction in force for a product derivation, or None when
+/// the conversion table does not allow it
+pub fn get_conversion_fraction(env: &Env, parent: ProductType, child: ProductType) -> Option<u32> {
+    let default = conversion::default_max_fraction_bps(parent, child)?;
+    Some(
+        env.storage()
+            .instance()
+            .get(&DataKey::ConversionFraction(parent, child))
+            .unwrap_or(default),
+    )
+}
+
+/// Configure the volume fraction of a product derivation
+pub fn set_conversion_fraction(
+    env: &Env,
+    parent: ProductType,
+    child: ProductType,
+    fraction_bps: u32,
+) {
+    env.storage()
+        .instance()
+        .set(&DataKey::ConversionFraction(parent, child), &fraction_bps);
+}
+
+/// Get the temperature range of a product (built-in defaults unless configured)
+pub fn get_temperature_range(env: &Env, product: ProductType) -> TemperatureRange {
+    env.storage()
+        .instance()
+        .get(&DataKey::TemperatureRange(product))
+        .unwrap_or_else(|| product.default_temperature_range())
+}
+
+/// Configure the temperature range of a product
+pub fn set_temperature_range(env: &Env, product: ProductType, range: &TemperatureRange) {
+    env.storage()
+        .instance()
+        .set(&DataKey::TemperatureRange(product), range);
+}
+
+/// Get the low-stock alert settings of a blood type (None = alerts off)
+pub fn get_low_stock_alert(env: &Env, blood_type: BloodType) -> Option<LowStockAlertConfig> {
+    env.storage()
+        .instance()
+        .get(&DataKey::LowStockAlert(blood_type))
+}
+
+/// Configure low-stock alerts for a blood type
+pub fn set_low_stock_alert(env: &Env, blood_type: BloodType, config: &LowStockAlertConfig) {
+    env.storage()
+        .instance()
+        .set(&DataKey::LowStockAlert(blood_type), config);
+}
+
+/// Get the last low-stock alert emitted for a blood type
+pub fn get_alert_state(env: &Env, blood_type: BloodType) -> Option<AlertState> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AlertState(blood_type))
+}
+
+/// Record the low-stock alert just emitted for a blood type
+pub fn set_alert_state(env: &Env, blood_type: BloodType, state: &AlertState) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::AlertState(blood_type), state);
+}
+
+/// Forget the last low-stock alert of a blood type
+pub fn clear_alert_state(env: &Env, blood_type: BloodType) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::AlertState(blood_type));
+}
+
+/// Get a bank's storage capacity (None means unlimited)
+pub fn get_bank_capacity(env: &Env, bank: &Address) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BankCapacity(bank.clone()))
+}
+
+/// Set a bank's storage capacity
+pub fn set_bank_capacity(env: &Env, bank: &Address, max_units: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::BankCapacity(bank.clone()), &max_units);
+}
+
+/// Get the number of non-terminal units held by a bank
+pub fn get_bank_unit_count(env: &Env, bank: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BankUnitCount(bank.clone()))
+        .unwrap_or(0)
+}
+
+/// Check whether a bank can take in one more unit
+pub fn has_bank_capacity(env: &Env, bank: &Address) -> bool {
+    match get_bank_capacity(env, bank) {
+        Some(max_units) => get_bank_unit_count(env, bank) < max_units,
+        None => true,
+    }
+}
+
+/// Count a unit entering a bank
+pub fn increment_bank_unit_count(env: &Env, bank: &Address) {
+    let count = get_bank_unit_count(env, bank);
+    env.storage()
+        .persistent()
+        .set(&DataKey::BankUnitCount(bank.clone()), &(count + 1));
+}
+
+/// Count a unit leaving a bank (used, discarded, expired or transferred)
+pub fn decrement_bank_unit_count(env: &Env, bank: &Address) {
+    let count = get_bank_unit_count(env, bank);
+    env.storage().persistent().set(
+        &DataKey::BankUnitCount(bank.clone()),
+        &count.saturating_sub(1),
+    );
+}
+
+/// Get the current blood unit counter
+pub fn get_blood_unit_counter(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::BloodUnitCounter)
+        .unwrap_or(0)
+}
+
+/// Set the blood unit counter; the next unit gets `value + 1`
+pub fn set_blood_unit_counter(env: &Env, value: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::BloodUnitCounter, &value);
+}
+
+/// Increment and return the next blood unit ID
+pub fn increment_blood_unit_id(env: &Env) -> Result<u64, ContractError> {
+    let next_id = get_blood_unit_counter(env)
+        .checked_add(1)
+        .ok_or(ContractError::CounterOverflow)?;
+    set_blood_unit_counter(env, next_id);
+    Ok(next_id)
+}
+
+/// Store a blood unit
+pub fn set_blood_unit(env: &Env, blood_unit: &BloodUnit) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::BloodUnit(blood_unit.id), blood_unit);
+}
+
+/// Get a blood unit by ID
+pub fn get_blood_unit(env: &Env, id: u64) -> Option<BloodUnit> {
+    env.storage().persistent().get(&DataKey::BloodUnit(id))
+}
+
+/// Rewrite a unit stored under the legacy layout into the current one
+///
+/// Units already carrying the current schema, missing IDs and layouts this
+/// build does not know are left alone. Migrated units are added to their
+/// bank's own `OperatorIndex`.
+///
+/// # Returns
+/// true if the unit was rewritten
+pub fn migrate_blood_unit(env: &Env, id: u64) -> bool {
+    let key = DataKey::BloodUnit(id);
+    let Some(raw) = env.storage().persistent().get::<DataKey, Val>(&key) else {
+        return false;
+    };
+
+    // Decoding a struct from a map with a different field set traps rather
+    // than returning an error, so the layout is told apart by its keys first.
+    let Ok(fields) = Map::<Symbol, Val>::try_from_val(env, &raw) else {
+        return false;
+    };
+    if fields.contains_key(Symbol::new(env, "schema"))
+        || fields.len() != LegacyBloodUnit::FIELD_COUNT
+    {
+        return false;
+    }
+    let Ok(legacy) = LegacyBloodUnit::try_from_val(env, &raw) else {
+        return false;
+    };
+
+    let blood_unit = legacy.into_current();
+    set_blood_unit(env, &blood_unit);
+    add_to_operator_index(env, &blood_unit);
+    true
+}
+
+/// Append a blood unit ID to an index unless it is already present
+fn push_to_index(env: &Env, key: &DataKey, blood_unit_id: u64) {
+    let mut units: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+
+    if units.contains(blood_unit_id) {
+        return;
+    }
+    units.push_back(blood_unit_id);
+    env.storage().persistent().set(key, &units);
+}
+
+/// Drop a blood unit ID from an index
+fn remove_from_index(env: &Env, key: &DataKey, blood_unit_id: u64) {
+    let mut units: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+
+    if let Some(i) = units.first_index_of(blood_unit_id) {
+        units.remove(i);
+        env.storage().persistent().set(key, &units);
+    }
+}
+
+/// Number of blood unit IDs held by a full index page
+pub const INDEX_PAGE_SIZE: u32 = 100;
+
+/// Hot index stored as fixed-size pages instead of one growing Vec
+///
+/// Registering a unit touches only the last page, so its cost no longer
+/// grows with the size of the index. Each member also has a slot entry
+/// recording its page, so membership checks and removals read one page.
+#[derive(Clone, Copy)]
+pub enum PagedIndex {
+    BloodType(BloodType),
+    Status(BloodStatus),
+}
+
+impl PagedIndex {
+    fn page_key(self, page: u32) -> DataKey {
+        match self {
+            PagedIndex::BloodType(blood_type) => DataKey::BloodTypeIndexPage(blood_type, page),
+            PagedIndex::Status(status) => DataKey::StatusIndexPage(status, page),
+        }
+    }
+
+    fn page_count_key(self) -> DataKey {
+        match self {
+            PagedIndex::BloodType(blood_type) => DataKey::BloodTypeIndexPageCount(blood_type),
+            PagedIndex::Status(status) => DataKey::StatusIndexPageCount(status),
+        }
+    }
+
+    fn slot_key(self, blood_unit_id: u64) -> DataKey {
+        match self {
+            PagedIndex::BloodType(blood_type) => {
+                DataKey::BloodTypeIndexSlot(blood_type, blood_unit_id)
+            }
+            PagedIndex::Status(status) => DataKey::StatusIndexSlot(status, blood_unit_id),
+        }
+    }
+
+    fn legacy_key(self) -> DataKey {
+        match self {
+            PagedIndex::BloodType(blood_type) => DataKey::BloodTypeIndex(blood_type),
+            PagedIndex::Status(status) => DataKey::StatusIndex(status),
+        }
+    }
+}
+
+/// Split a legacy monolithic index into pages, once
+fn migrate_legacy_index(env: &Env, index: PagedIndex) {
+    let legacy_key = index.legacy_key();
+    let legacy: Option<Vec<u64>> = env.storage().persistent().get(&legacy_key);
+    let Some(units) = legacy else {
+        return;
+    };
+
+    let mut page_count = 0u32;
+    let mut start = 0u32;
+    while start < units.len() {
+        let end = (start + INDEX_PAGE_SIZE).min(units.len());
+        let page = units.slice(start..end);
+        for blood_unit_id in page.iter() {
+            env.storage()
+                .persistent()
+                .set(&index.slot_key(blood_unit_id), &page_count);
+        }
+        env.storage()
+            .persistent()
+            .set(&index.page_key(page_count), &page);
+        page_count += 1;
+        start = end;
+    }
+
+    env.storage()
+        .persistent()
+        .set(&index.page_count_key(), &page_count);
+    env.storage().persistent().remove(&legacy_key);
+}
+
+/// Number of pages of a paged index
+///
+/// Pages emptied by removals in the middle of the index still count; they
+/// read as empty.
+pub fn get_index_page_count(env: &Env, index: PagedIndex) -> u32 {
+    migrate_legacy_index(env, index);
+    env.storage()
+        .persistent()
+        .get(&index.page_count_key())
+        .unwrap_or(0)
+}
+
+/// Read one page of a paged index, defaulting to empty
+pub fn get_index_page(env: &Env, index: PagedIndex, page: u32) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&index.page_key(page))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Page of a paged index holding a blood unit ID, if the index holds it
+pub fn get_index_slot(env: &Env, index: PagedIndex, blood_unit_id: u64) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&index.slot_key(blood_unit_id))
+}
+
+/// Append a blood unit ID to the last page of a paged index, unless the
+/// index already holds it
+fn push_to_paged_index(env: &Env, index: PagedIndex, blood_unit_id: u64) {
+    let page_count = get_index_page_count(env, index);
+    if get_index_slot(env, index, blood_unit_id).is_some() {
+        return;
+    }
+
+    let last = page_count.saturating_sub(1);
+    let mut units = get_index_page(env, index, last);
+    let page = if page_count > 0 && units.len() < INDEX_PAGE_SIZE {
+        last
+    } else {
+        units = Vec::new(env);
+        env.storage()
+            .persistent()
+            .set(&index.page_count_key(), &(page_count + 1));
+        page_count
+    };
+
+    units.push_back(blood_unit_id);
+    env.storage()
+        .persistent()
+        .set(&index.page_key(page), &units);
+    env.storage()
+        .persistent()
+        .set(&index.slot_key(blood_unit_id), &page);
+}
+
+/// Drop a blood unit ID from the page of a paged index that holds it
+///
+/// Only that page is rewritten. A page left empty is deleted, and empty
+/// pages at the end of the index are dropped from its page count.
+fn remove_from_paged_index(env: &Env, index: PagedIndex, blood_unit_id: u64) {
+    let page_count = get_index_page_count(env, index);
+    let Some(page) = get_index_slot(env, index, blood_unit_id) else {
+        return;
+    };
+    env.storage()
+        .persistent()
+        .remove(&index.slot_key(blood_unit_id));
+
+    let mut units = get_index_page(env, index, page);
+    if let Some(i) = units.first_index_of(blood_unit_id) {
+        units.remove(i);
+    }
+    if !units.is_empty() {
+        env.storage()
+            .persistent()
+            .set(&index.page_key(page), &units);
+        return;
+    }
+
+    env.storage().persistent().remove(&index.page_key(page));
+    if page + 1 == page_count {
+        let mut remaining = page;
+        while remaining > 0
+            && !env
+                .storage()
+                .persistent()
+                .has(&index.page_key(remaining - 1))
+        {
+            remaining -= 1;
+        }
+        env.storage()
+            .persistent()
+            .set(&index.page_count_key(), &remaining);
+    }
+}
+
+/// Add blood unit to blood type index
+pub fn add_to_blood_type_index(env: &Env, blood_unit: &BloodUnit) {
+    push_to_paged_index(
+        env,
+        PagedIndex::BloodType(blood_unit.blood_type),
+        blood_unit.id,
+    );
+}
+
+/// Get blood unit IDs of a blood type, in registration order
+#[cfg(test)]
+pub fn get_blood_type_units(env: &Env, blood_type: BloodType) -> Vec<u64> {
+    let index = PagedIndex::BloodType(blood_type);
+    let mut units = Vec::new(env);
+    for page in 0..get_index_page_count(env, index) {
+        units.append(&get_index_page(env, index, page));
+    }
+    units
+}
+
+/// Add blood unit to bank index
+pub fn add_to_bank_index(env: &Env, blood_unit: &BloodUnit) {
+    push_to_index(
+        env,
+        &DataKey::BankIndex(blood_unit.bank_id.clone()),
+        blood_unit.id,
+    );
+}
+
+/// Get blood unit IDs registered by a bank
+pub fn get_bank_units(env: &Env, bank_id: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BankIndex(bank_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add a registered blood unit to the index of the key that registered it
+pub fn add_to_operator_index(env: &Env, blood_unit: &BloodUnit) {
+    push_to_index(
+        env,
+        &DataKey::OperatorIndex(blood_unit.bank_id.clone(), blood_unit.registered_by.clone()),
+        blood_unit.id,
+    );
+}
+
+/// Get blood unit IDs registered for a bank by one key, in registration order
+pub fn get_operator_units(env: &Env, bank_id: &Address, operator: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OperatorIndex(bank_id.clone(), operator.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add blood unit to status index
+pub fn add_to_status_index(env: &Env, blood_unit: &BloodUnit) {
+    push_to_paged_index(env, PagedIndex::Status(blood_unit.status), blood_unit.id);
+}
+
+/// Add blood unit to donor index (if donor_id exists)
+pub fn add_to_donor_index(env: &Env, blood_unit: &BloodUnit) {
+    if let Some(donor) = &blood_unit.donor_id {
+        link_unit_to_donor(env, donor, blood_unit.id);
+    }
+}
+
+/// Add a unit the donor contributed to (e.g. a pool) to their donor index
+pub fn link_unit_to_donor(env: &Env, donor: &Address, blood_unit_id: u64) {
+    push_to_index(env, &DataKey::DonorIndex(donor.clone()), blood_unit_id);
+}
+
+/// Get blood unit IDs a donor contributed to
+#[cfg(test)]
+pub fn get_donor_units(env: &Env, donor: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DonorIndex(donor.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Drop a voided unit from every index `register` added it to, and release
+/// its external reference
+///
+/// The directed and availability indexes are kept in step with status
+/// changes and are left to the caller.
+pub fn remove_from_registration_indexes(env: &Env, blood_unit: &BloodUnit) {
+    remove_from_paged_index(
+        env,
+        PagedIndex::BloodType(blood_unit.blood_type),
+        blood_unit.id,
+    );
+    remove_from_paged_index(env, PagedIndex::Status(blood_unit.status), blood_unit.id);
+    remove_from_index(
+        env,
+        &DataKey::BankIndex(blood_unit.bank_id.clone()),
+        blood_unit.id,
+    );
+    remove_from_index(
+        env,
+        &DataKey::OperatorIndex(blood_unit.bank_id.clone(), blood_unit.registered_by.clone()),
+        blood_unit.id,
+    );
+    if let Some(donor) = &blood_unit.donor_id {
+        remove_from_index(env, &DataKey::DonorIndex(donor.clone()), blood_unit.id);
+    }
+    if let Some(campaign_id) = blood_unit.campaign_id {
+        remove_from_index(env, &DataKey::CampaignIndex(campaign_id), blood_unit.id);
+    }
+    remove_registration_by_ref(env, blood_unit);
+}
+
+/// Get the Available units of a blood type, soonest expiration first
+pub fn get_available_by_expiry(env: &Env, blood_type: BloodType) -> Vec<ExpiryIndexEntry> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AvailableByExpiry(blood_type))
+        .unwrap_or(Vec::new(env))
 }
 
-/// Set the admin address
-pub fn set_admin(env: &Env, admin: &Address) {
-    env.storage().instance().set(&DataKey::Admin, admin);
+/// Get the running Available/Reserved volume totals for a blood type
+pub fn get_stock_totals(env: &Env, blood_type: BloodType) -> StockTotals {
+    env.storage()
+        .persistent()
+        .get(&DataKey::StockTotals(blood_type))
+        .unwrap_or_default()
 }
 
-/// Check if an address is authorized as a blood bank
-pub fn is_authorized_bank(env: &Env, bank: &Address) -> bool {
-    let admin = get_admin(env);
-    bank == &admin
+/// Count a unit's volume towards the running total for its status
+///
+/// Only Available and Reserved are tracked; other statuses are ignored.
+pub fn add_to_stock_totals(
+    env: &Env,
+    blood_type: BloodType,
+    status: BloodStatus,
+    quantity_ml: u32,
+) {
+    update_stock_totals(env, blood_type, status, |total| total + quantity_ml as u64);
 }
 
-/// Get the current blood unit counter
-pub fn get_blood_unit_counter(env: &Env) -> u64 {
+/// Take a unit's volume out of the running total for its status
+pub fn remove_from_stock_totals(
+    env: &Env,
+    blood_type: BloodType,
+    status: BloodStatus,
+    quantity_ml: u32,
+) {
+    update_stock_totals(env, blood_type, status, |total| {
+        total.saturating_sub(quantity_ml as u64)
+    });
+}
+
+fn update_stock_totals(
+    env: &Env,
+    blood_type: BloodType,
+    status: BloodStatus,
+    update: impl FnOnce(u64) -> u64,
+) {
+    let mut totals = get_stock_totals(env, blood_type);
+    let total = match status {
+        BloodStatus::Available => &mut totals.available_ml,
+        BloodStatus::Reserved => &mut totals.reserved_ml,
+        _ => return,
+    };
+    *total = update(*total);
+
     env.storage()
-        .instance()
-        .get(&DataKey::BloodUnitCounter)
-        .unwrap_or(0)
+        .persistent()
+        .set(&DataKey::StockTotals(blood_type), &totals);
 }
 
-/// Increment and return the next blood unit ID
-pub fn increment_blood_unit_id(env: &Env) -> u64 {
-    let current = get_blood_unit_counter(env);
-    let next_id = current + 1;
+/// Get the reservation aggregates of a bank
+pub fn get_reservation_stats(env: &Env, bank: &Address) -> ReservationStats {
     env.storage()
-        .instance()
-        .set(&DataKey::BloodUnitCounter, &next_id);
-    next_id
+        .persistent()
+        .get(&DataKey::ReservationStats(bank.clone()))
+        .unwrap_or_default()
 }
 
-/// Store a blood unit
-pub fn set_blood_unit(env: &Env, blood_unit: &BloodUnit) {
+fn set_reservation_stats(env: &Env, bank: &Address, stats: &ReservationStats) {
     env.storage()
         .persistent()
-        .set(&DataKey::BloodUnit(blood_unit.id), blood_unit);
+        .set(&DataKey::ReservationStats(bank.clone()), stats);
 }
 
-/// Get a blood unit by ID
-pub fn get_blood_unit(env: &Env, id: u64) -> Option<BloodUnit> {
-    env.storage().persistent().get(&DataKey::BloodUnit(id))
+/// Start timing a hold on a unit that just became Reserved
+pub fn start_hold(env: &Env, blood_unit: &BloodUnit) {
+    env.storage().persistent().set(
+        &DataKey::HoldStartedAt(blood_unit.id),
+        &env.ledger().timestamp(),
+    );
+
+    let mut stats = get_reservation_stats(env, &blood_unit.bank_id);
+    stats.reserved_ml += blood_unit.quantity_ml as u64;
+    stats.active_holds += 1;
+    set_reservation_stats(env, &blood_unit.bank_id, &stats);
 }
 
-/// Check if a blood unit exists
-pub fn blood_unit_exists(env: &Env, id: u64) -> bool {
-    env.storage().persistent().has(&DataKey::BloodUnit(id))
+/// Close the hold on a unit that is no longer Reserved, adding its
+/// duration to the bank's total
+///
+/// Holds started before hold timing existed were never counted and are
+/// skipped.
+pub fn end_hold(env: &Env, blood_unit: &BloodUnit) {
+    let key = DataKey::HoldStartedAt(blood_unit.id);
+    let Some(started_at) = env.storage().persistent().get::<_, u64>(&key) else {
+        return;
+    };
+    env.storage().persistent().remove(&key);
+
+    let mut stats = get_reservation_stats(env, &blood_unit.bank_id);
+    stats.reserved_ml = stats
+        .reserved_ml
+        .saturating_sub(blood_unit.quantity_ml as u64);
+    stats.active_holds = stats.active_holds.saturating_sub(1);
+    stats.ended_holds += 1;
+    stats.hold_seconds += env.ledger().timestamp().saturating_sub(started_at);
+    set_reservation_stats(env, &blood_unit.bank_id, &stats);
 }
 
-/// Add blood unit to blood type index
-pub fn add_to_blood_type_index(env: &Env, blood_unit: &BloodUnit) {
-    let key = DataKey::BloodTypeIndex(blood_unit.blood_type);
+/// Insert an Available unit into the expiration-sorted index
+///
+/// Ordered by expiration, then donation time, so the first entry is the
+/// unit to dispense next.
+pub fn add_to_available_by_expiry(env: &Env, blood_unit: &BloodUnit) {
+    let key = DataKey::AvailableByExpiry(blood_unit.blood_type);
+    let mut entries = get_available_by_expiry(env, blood_unit.blood_type);
+
+    let sort_key = (
+        blood_unit.expiration_timestamp,
+        blood_unit.donation_timestamp,
+    );
+    let mut low = 0u32;
+    let mut high = entries.len();
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let entry = entries.get_unchecked(mid);
+        if (entry.expiration_timestamp, entry.donation_timestamp) <= sort_key {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    entries.insert(
+        low,
+        ExpiryIndexEntry {
+            expiration_timestamp: blood_unit.expiration_timestamp,
+            donation_timestamp: blood_unit.donation_timestamp,
+            blood_unit_id: blood_unit.id,
+        },
+    );
+    env.storage().persistent().set(&key, &entries);
+}
+
+/// Remove a unit from the expiration-sorted index
+pub fn remove_from_available_by_expiry(env: &Env, blood_unit: &BloodUnit) {
+    let key = DataKey::AvailableByExpiry(blood_unit.blood_type);
+    let mut entries = get_available_by_expiry(env, blood_unit.blood_type);
+
+    for i in 0..entries.len() {
+        if entries.get_unchecked(i).blood_unit_id == blood_unit.id {
+            entries.remove(i);
+            env.storage().persistent().set(&key, &entries);
+            return;
+        }
+    }
+}
+
+/// Add blood unit to the location index of its bank
+pub fn add_to_location_index(env: &Env, bank_id: &Address, location: &String, blood_unit_id: u64) {
+    push_to_index(
+        env,
+        &DataKey::LocationIndex(bank_id.clone(), location.clone()),
+        blood_unit_id,
+    );
+}
+
+/// Remove blood unit from the location index of its bank
+pub fn remove_from_location_index(
+    env: &Env,
+    bank_id: &Address,
+    location: &String,
+    blood_unit_id: u64,
+) {
+    let key = DataKey::LocationIndex(bank_id.clone(), location.clone());
     let mut units: Vec<u64> = env
         .storage()
         .persistent()
         .get(&key)
         .unwrap_or(Vec::new(env));
 
-    units.push_back(blood_unit.id);
-    env.storage().persistent().set(&key, &units);
+    if let Some(index) = units.first_index_of(blood_unit_id) {
+        units.remove(index);
+        if units.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &units);
+        }
+    }
 }
 
-/// Add blood unit to bank index
-pub fn add_to_bank_index(env: &Env, blood_unit: &BloodUnit) {
-    let key = DataKey::BankIndex(blood_unit.bank_id.clone());
+/// Get blood unit IDs stored at a location of a bank
+pub fn get_units_by_location(env: &Env, bank_id: &Address, location: &String) -> Vec<u64> {
+    let key = DataKey::LocationIndex(bank_id.clone(), location.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add blood unit to the reservation index of a request
+pub fn add_to_reservation_index(env: &Env, request_id: u64, blood_unit_id: u64) {
+    push_to_index(env, &DataKey::ReservationIndex(request_id), blood_unit_id);
+}
+
+/// Remove blood unit from the reservation index of a request
+pub fn remove_from_reservation_index(env: &Env, request_id: u64, blood_unit_id: u64) {
+    let key = DataKey::ReservationIndex(request_id);
     let mut units: Vec<u64> = env
         .storage()
         .persistent()
         .get(&key)
         .unwrap_or(Vec::new(env));
 
-    units.push_back(blood_unit.id);
-    env.storage().persistent().set(&key, &units);
+    if let Some(index) = units.first_index_of(blood_unit_id) {
+        units.remove(index);
+        if units.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &units);
+        }
+    }
 }
 
-/// Add blood unit to status index
-pub fn add_to_status_index(env: &Env, blood_unit: &BloodUnit) {
-    let key = DataKey::StatusIndex(blood_unit.status);
+/// Get blood unit IDs reserved for a request
+pub fn get_reserved_units(env: &Env, request_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReservationIndex(request_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add blood unit to the directed-donation index of a request
+pub fn add_to_directed_index(env: &Env, request_id: u64, blood_unit_id: u64) {
+    push_to_index(env, &DataKey::DirectedIndex(request_id), blood_unit_id);
+}
+
+/// Remove blood unit from the directed-donation index of a request
+pub fn remove_from_directed_index(env: &Env, request_id: u64, blood_unit_id: u64) {
+    let key = DataKey::DirectedIndex(request_id);
     let mut units: Vec<u64> = env
         .storage()
         .persistent()
         .get(&key)
         .unwrap_or(Vec::new(env));
 
-    units.push_back(blood_unit.id);
-    env.storage().persistent().set(&key, &units);
+    if let Some(index) = units.first_index_of(blood_unit_id) {
+        units.remove(index);
+        if units.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &units);
+        }
+    }
 }
 
-/// Add blood unit to donor index (if donor_id exists)
-pub fn add_to_donor_index(env: &Env, blood_unit: &BloodUnit) {
-    if let Some(donor) = &blood_unit.donor_id {
-        let key = DataKey::DonorIndex(donor.clone());
-        let mut units: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
+/// Get blood unit IDs donated for a request
+pub fn get_directed_units(env: &Env, request_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DirectedIndex(request_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Read the waitlist of a blood type, highest priority first
+pub fn get_waitlist(env: &Env, blood_type: BloodType) -> Vec<WaitlistEntry> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Waitlist(blood_type))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get the watchers subscribed to a blood type, oldest first
+pub fn get_watchers(env: &Env, blood_type: BloodType) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Watchers(blood_type))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Replace the watchers subscribed to a blood type
+pub fn set_watchers(env: &Env, blood_type: BloodType, watchers: &Vec<Address>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Watchers(blood_type), watchers);
+}
+
+/// Check if a request is on any waitlist
+pub fn is_waitlisted(env: &Env, request_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::WaitlistedType(request_id))
+}
+
+/// Insert a request into the waitlist of a blood type at its priority position
+///
+/// Binary search for the first entry with a strictly lower score, so requests
+/// with equal scores keep their insertion order.
+pub fn add_to_waitlist(env: &Env, blood_type: BloodType, entry: &WaitlistEntry) {
+    let mut waitlist = get_waitlist(env, blood_type);
+
+    let mut low = 0u32;
+    let mut high = waitlist.len();
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if waitlist.get_unchecked(mid).priority_score >= entry.priority_score {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    waitlist.insert(low, entry.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Waitlist(blood_type), &waitlist);
+    env.storage()
+        .persistent()
+        .set(&DataKey::WaitlistedType(entry.request_id), &blood_type);
+}
+
+/// Remove a request from the waitlist it is on
+///
+/// Returns false if the request was not waitlisted.
+pub fn remove_from_waitlist(env: &Env, request_id: u64) -> bool {
+    let type_key = DataKey::WaitlistedType(request_id);
+    let Some(blood_type) = env.storage().persistent().get::<_, BloodType>(&type_key) else {
+        return false;
+    };
+    env.storage().persistent().remove(&type_key);
+
+    let key = DataKey::Waitlist(blood_type);
+    let mut waitlist = get_waitlist(env, blood_type);
+    for i in 0..waitlist.len() {
+        if waitlist.get_unchecked(i).request_id == request_id {
+            waitlist.remove(i);
+            if waitlist.is_empty() {
+                env.storage().persistent().remove(&key);
+            } else {
+                env.storage().persistent().set(&key, &waitlist);
+            }
+            break;
+        }
+    }
+    true
+}
 
-        units.push_back(blood_unit.id);
-        env.storage().persistent().set(&key, &units);
+/// Store a campaign, assigning it the next campaign ID
+pub fn create_campaign(env: &Env, campaign: &mut Campaign) -> Result<u64, ContractError> {
+    let id = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::CampaignCounter)
+        .unwrap_or(0)
+        .checked_add(1)
+        .ok_or(ContractError::CounterOverflow)?;
+    env.storage().instance().set(&DataKey::CampaignCounter, &id);
+
+    campaign.id = id;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Campaign(id), campaign);
+    Ok(id)
+}
+
+/// Get a campaign by ID
+pub fn get_campaign(env: &Env, campaign_id: u64) -> Option<Campaign> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Campaign(campaign_id))
+}
+
+/// Add blood unit to the index of the campaign it was collected under
+pub fn add_to_campaign_index(env: &Env, campaign_id: u64, blood_unit_id: u64) {
+    push_to_index(env, &DataKey::CampaignIndex(campaign_id), blood_unit_id);
+}
+
+/// Get blood unit IDs collected under a campaign
+pub fn get_campaign_units(env: &Env, campaign_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CampaignIndex(campaign_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Store a loan, assigning it the next loan ID, and list it as outstanding
+/// for both banks
+pub fn create_loan(env: &Env, loan: &mut UnitLoan) -> Result<u64, ContractError> {
+    let id = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::LoanCounter)
+        .unwrap_or(0)
+        .checked_add(1)
+        .ok_or(ContractError::CounterOverflow)?;
+    env.storage().instance().set(&DataKey::LoanCounter, &id);
+
+    loan.id = id;
+    set_loan(env, loan);
+    push_to_index(env, &DataKey::OutstandingLoans(loan.lender.clone()), id);
+    push_to_index(env, &DataKey::OutstandingLoans(loan.borrower.clone()), id);
+    Ok(id)
+}
+
+/// Get a loan by ID
+pub fn get_loan(env: &Env, loan_id: u64) -> Option<UnitLoan> {
+    env.storage().persistent().get(&DataKey::Loan(loan_id))
+}
+
+/// Store a loan
+pub fn set_loan(env: &Env, loan: &UnitLoan) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Loan(loan.id), loan);
+}
+
+/// Get IDs of the unsettled loans a bank lent or borrowed, oldest first
+pub fn get_outstanding_loans(env: &Env, bank: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OutstandingLoans(bank.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Drop a settled loan from both banks' outstanding loans
+pub fn remove_outstanding_loan(env: &Env, loan: &UnitLoan) {
+    remove_from_index(
+        env,
+        &DataKey::OutstandingLoans(loan.lender.clone()),
+        loan.id,
+    );
+    remove_from_index(
+        env,
+        &DataKey::OutstandingLoans(loan.borrower.clone()),
+        loan.id,
+    );
+}
+
+/// Move a blood unit from one bank's index to another's
+pub fn move_bank_index(env: &Env, blood_unit_id: u64, from_bank: &Address, to_bank: &Address) {
+    remove_from_index(env, &DataKey::BankIndex(from_bank.clone()), blood_unit_id);
+    push_to_index(env, &DataKey::BankIndex(to_bank.clone()), blood_unit_id);
+}
+
+/// Get a donor's profile
+pub fn get_donor_profile(env: &Env, donor: &Address) -> Option<DonorProfile> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DonorProfile(donor.clone()))
+}
+
+/// Store a donor's profile
+pub fn set_donor_profile(env: &Env, profile: &DonorProfile) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::DonorProfile(profile.donor_id.clone()), profile);
+}
+
+/// Get a donor's profile, or a blank one if the donor is unknown
+pub fn get_or_new_donor_profile(env: &Env, donor: &Address) -> DonorProfile {
+    get_donor_profile(env, donor).unwrap_or(DonorProfile {
+        donor_id: donor.clone(),
+        donation_count: 0,
+        last_donation_at: 0,
+        highest_milestone: 0,
+        permanently_deferred: false,
+        deferral_reason: None,
+    })
+}
+
+/// Record a donor's latest temporary deferral
+pub fn set_donor_deferral(env: &Env, donor: &Address, deferral: &TemporaryDeferral) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::DonorDeferral(donor.clone()), deferral);
+}
+
+/// Get a donor's latest temporary deferral, whether or not it has lapsed
+pub fn get_donor_deferral(env: &Env, donor: &Address) -> Option<TemporaryDeferral> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DonorDeferral(donor.clone()))
+}
+
+/// Check if a donor is permanently deferred or within a temporary deferral
+///
+/// Temporary deferrals lapse on their own once their end date passes.
+pub fn is_donor_deferred(env: &Env, donor: &Address) -> bool {
+    let now = env.ledger().timestamp();
+    get_donor_profile(env, donor).is_some_and(|profile| profile.permanently_deferred)
+        || get_donor_deferral(env, donor).is_some_and(|deferral| deferral.is_active(now))
+}
+
+/// Record a donation for a donor and return the updated profile
+pub fn record_donation(env: &Env, donor: &Address) -> DonorProfile {
+    let mut profile = get_or_new_donor_profile(env, donor);
+
+    profile.donation_count += 1;
+    profile.last_donation_at = env.ledger().timestamp();
+
+    set_donor_profile(env, &profile);
+    profile
+}
+
+/// Take a voided registration back out of a donor's donation count
+pub fn retract_donation(env: &Env, donor: &Address) {
+    if let Some(mut profile) = get_donor_profile(env, donor) {
+        profile.donation_count = profile.donation_count.saturating_sub(1);
+        set_donor_profile(env, &profile);
     }
 }
 
+/// Epoch containing a timestamp
+pub fn epoch_of(timestamp: u64) -> u64 {
+    timestamp / SECONDS_PER_EPOCH
+}
+
+/// Get the discard counts per reason for an epoch
+pub fn get_discard_stats(env: &Env, epoch: u64) -> Map<DiscardReason, u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DiscardStats(epoch))
+        .unwrap_or(Map::new(env))
+}
+
+/// Record the category a blood unit was discarded under
+pub fn set_discard_reason(env: &Env, blood_unit_id: u64, reason: DiscardReason) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::DiscardReason(blood_unit_id), &reason);
+}
+
+/// Get the category a blood unit was discarded under
+pub fn get_discard_reason(env: &Env, blood_unit_id: u64) -> Option<DiscardReason> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DiscardReason(blood_unit_id))
+}
+
+/// Count a discard under its reason in the current epoch
+pub fn record_discard(env: &Env, reason: DiscardReason) {
+    let epoch = epoch_of(env.ledger().timestamp());
+    let mut stats = get_discard_stats(env, epoch);
+    let count = stats.get(reason).unwrap_or(0);
+    stats.set(reason, count.saturating_add(1));
+    env.storage()
+        .persistent()
+        .set(&DataKey::DiscardStats(epoch), &stats);
+}
+
 /// Record a status change in history
 pub fn record_status_change(
     env: &Env,
@@ -152,6 +1405,116 @@ pub fn record_status_change(
     env.storage().persistent().set(&count_key, &(count + 1));
 }
 
+/// Record a quantity correction in a unit's correction history
+pub fn record_quantity_correction(env: &Env, correction: &QuantityCorrection) {
+    let key = DataKey::QuantityCorrections(correction.blood_unit_id);
+    let mut corrections = get_quantity_corrections(env, correction.blood_unit_id);
+    corrections.push_back(correction.clone());
+    env.storage().persistent().set(&key, &corrections);
+}
+
+/// Record a custody handoff in a unit's custody history
+pub fn record_custody_change(env: &Env, change: &CustodyChange) {
+    let key = DataKey::CustodyHistory(change.blood_unit_id);
+    let mut history = get_custody_history(env, change.blood_unit_id);
+    history.push_back(change.clone());
+    env.storage().persistent().set(&key, &history);
+}
+
+/// Get custody handoff history for a blood unit
+pub fn get_custody_history(env: &Env, blood_unit_id: u64) -> Vec<CustodyChange> {
+    let key = DataKey::CustodyHistory(blood_unit_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Append a reading to a unit's temperature log, dropping the oldest once
+/// `MAX_TEMPERATURE_READINGS` are kept
+pub fn record_temperature(env: &Env, blood_unit_id: u64, reading: &TemperatureReading) {
+    let mut log = get_temperature_log(env, blood_unit_id);
+    if log.len() >= MAX_TEMPERATURE_READINGS {
+        log.pop_front();
+    }
+    log.push_back(reading.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::TemperatureLog(blood_unit_id), &log);
+}
+
+/// Get the temperature readings of a blood unit, oldest first
+pub fn get_temperature_log(env: &Env, blood_unit_id: u64) -> Vec<TemperatureReading> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TemperatureLog(blood_unit_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Check if a blood unit ever had a reading outside its temperature range
+pub fn has_temperature_excursion(env: &Env, blood_unit_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::TemperatureExcursion(blood_unit_id))
+}
+
+/// Flag a blood unit as having left its temperature range
+pub fn flag_temperature_excursion(env: &Env, blood_unit_id: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::TemperatureExcursion(blood_unit_id), &true);
+}
+
+/// Get the registration a bank made under an external reference
+pub fn get_registration_by_ref(
+    env: &Env,
+    bank_id: &Address,
+    external_ref: &String,
+) -> Option<RegistrationRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ExternalRef(bank_id.clone(), external_ref.clone()))
+}
+
+/// Remember the registration a bank made under an external reference
+pub fn set_registration_by_ref(
+    env: &Env,
+    bank_id: &Address,
+    external_ref: &String,
+    record: &RegistrationRecord,
+) {
+    env.storage().persistent().set(
+        &DataKey::ExternalRef(bank_id.clone(), external_ref.clone()),
+        record,
+    );
+    env.storage().persistent().set(
+        &DataKey::UnitExternalRef(record.blood_unit_id),
+        external_ref,
+    );
+}
+
+/// Forget the external reference a blood unit was registered under, so the
+/// bank can register a new unit with it
+fn remove_registration_by_ref(env: &Env, blood_unit: &BloodUnit) {
+    let key = DataKey::UnitExternalRef(blood_unit.id);
+    if let Some(external_ref) = env.storage().persistent().get::<_, String>(&key) {
+        env.storage().persistent().remove(&DataKey::ExternalRef(
+            blood_unit.bank_id.clone(),
+            external_ref,
+        ));
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Get quantity correction history for a blood unit
+pub fn get_quantity_corrections(env: &Env, blood_unit_id: u64) -> Vec<QuantityCorrection> {
+    let key = DataKey::QuantityCorrections(blood_unit_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
 /// Get status change history for a blood unit
 pub fn get_status_history(env: &Env, blood_unit_id: u64) -> Vec<StatusChangeHistory> {
     let key = DataKey::StatusHistory(blood_unit_id);
@@ -1,47 +1,69 @@
+use crate::error::ContractError;
+use crate::legacy::LegacyBloodUnit;
 use crate::storage;
-use crate::types::{BloodStatus, BloodType};
+use crate::testutils::{setup, UnitFixture};
+use crate::topics;
+use crate::types::{
+    AdminTransferCancelledEvent, AdminTransferProposedEvent, AdminTransferredEvent, AlertState,
+    AuthorizationTier, BankAuthorizationChangedEvent, BankCapacityChangedEvent, BankRole,
+    BankTierChangedEvent, BloodDiscardedEvent, BloodExpiredEvent, BloodRegisteredEvent,
+    BloodReservedEvent, BloodStatus, BloodType, BloodUnit, BloodUnitSummary, CampaignCreatedEvent,
+    ComponentSplit, CustodyChange, CustodyChangedEvent, DataKey, DiscardReason,
+    DonorTemporarilyDeferredEvent, ExpirySweepCompletedEvent, LoanOverdueEvent, LoanSettledEvent,
+    LowStockEvent, MatchAvailableEvent, PhenotypeTagsSetEvent, ProductType,
+    QuantityStepChangedEvent, RegistrationOptions, RegistrationVoidedEvent,
+    RequestContractChangedEvent, ReservationReleasedEvent, ReservationStats, ShelfLife,
+    ShelfLifeChangedEvent, StatusChangeEvent, TemperatureExcursionEvent, TemperatureReading,
+    TemporaryDeferral, UnitLentEvent, UnitSplitEvent, UnitsPooledEvent, WatchedTypeChangedEvent,
+    BLOOD_UNIT_SCHEMA,
+};
+use crate::validation::MAX_PHENOTYPE_TAGS;
 use crate::{InventoryContract, InventoryContractClient};
+use lifebank_common::testutils::{
+    advance_days, advance_hours, count_events, event_names, find_event, latest_event,
+};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    vec, Address, Env, String,
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    vec, Address, Env, IntoVal, Map, String, Symbol, TryFromVal, Vec,
 };
 
-fn create_test_contract<'a>() -> (Env, Address, InventoryContractClient<'a>, Address) {
-    let env = Env::default();
-    env.mock_all_auths();
-
-    let contract_id = env.register(InventoryContract, ());
-    let client = InventoryContractClient::new(&env, &contract_id);
-
-    let admin = Address::generate(&env);
-
-    client.initialize(&admin);
-
-    (env, admin, client, contract_id)
-}
-
 #[test]
 fn test_initialize_success() {
-    let (env, admin, _client, contract_id) = create_test_contract();
+    let (env, admin, client, contract_id) = setup();
 
     // Verify admin is set
     let stored_admin = env.as_contract(&contract_id, || storage::get_admin(&env));
 
     assert_eq!(stored_admin, admin);
+    assert_eq!(client.get_version(), Some(storage::CONTRACT_VERSION));
+}
+
+#[test]
+fn test_new_units_carry_current_schema() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+    assert_eq!(client.get_blood_unit(&unit_id).schema, BLOOD_UNIT_SCHEMA);
+
+    let plasma = UnitFixture::new(&env)
+        .with_product(ProductType::Plasma)
+        .register(&client, &admin);
+    assert_eq!(client.get_blood_unit(&plasma).schema, BLOOD_UNIT_SCHEMA);
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #0)")]
 fn test_initialize_already_initialized() {
-    let (_env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     // Try to initialize again
-    client.initialize(&admin);
+    client.initialize(&admin, &vec![&env], &0);
 }
 
 #[test]
 fn test_register_blood_success() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone(); // Admin is authorized by default
     let blood_type = BloodType::APositive;
@@ -60,6 +82,7 @@ fn test_register_blood_success() {
         &quantity_ml,
         &expiration,
         &Some(donor.clone()),
+        &RegistrationOptions::new(&env),
     );
 
     assert_eq!(blood_unit_id, 1);
@@ -78,7 +101,7 @@ fn test_register_blood_success() {
 
 #[test]
 fn test_register_blood_anonymous_donor() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -90,6 +113,7 @@ fn test_register_blood_anonymous_donor() {
         &450u32,
         &(current_time + 30 * 86400),
         &None, // Anonymous donor
+        &RegistrationOptions::new(&env),
     );
 
     let stored_unit = client.get_blood_unit(&blood_unit_id);
@@ -98,7 +122,7 @@ fn test_register_blood_anonymous_donor() {
 
 #[test]
 fn test_register_blood_increments_id() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -106,22 +130,43 @@ fn test_register_blood_increments_id() {
     let expiration = current_time + (30 * 86400);
 
     // Register first unit
-    let id1 = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let id1 = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
     assert_eq!(id1, 1);
 
     // Register second unit
-    let id2 = client.register_blood(&bank, &BloodType::BPositive, &450u32, &expiration, &None);
+    let id2 = client.register_blood(
+        &bank,
+        &BloodType::BPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
     assert_eq!(id2, 2);
 
     // Register third unit
-    let id3 = client.register_blood(&bank, &BloodType::ONegative, &450u32, &expiration, &None);
+    let id3 = client.register_blood(
+        &bank,
+        &BloodType::ONegative,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
     assert_eq!(id3, 3);
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #16)")]
 fn test_register_blood_quantity_too_low() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -133,13 +178,14 @@ fn test_register_blood_quantity_too_low() {
         &50u32, // Too low
         &(current_time + 30 * 86400),
         &None,
+        &RegistrationOptions::new(&env),
     );
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #16)")]
 fn test_register_blood_quantity_too_high() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -151,13 +197,14 @@ fn test_register_blood_quantity_too_high() {
         &700u32, // Too high
         &(current_time + 30 * 86400),
         &None,
+        &RegistrationOptions::new(&env),
     );
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #17)")]
 fn test_register_blood_expiration_in_past() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -169,13 +216,14 @@ fn test_register_blood_expiration_in_past() {
         &450u32,
         &(current_time - 100), // In the past
         &None,
+        &RegistrationOptions::new(&env),
     );
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #17)")]
 fn test_register_blood_expiration_too_far_future() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -188,13 +236,14 @@ fn test_register_blood_expiration_too_far_future() {
         &450u32,
         &(current_time + 60 * 86400),
         &None,
+        &RegistrationOptions::new(&env),
     );
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #17)")]
 fn test_register_blood_insufficient_shelf_life() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -207,13 +256,14 @@ fn test_register_blood_insufficient_shelf_life() {
         &450u32,
         &(current_time + 43200),
         &None,
+        &RegistrationOptions::new(&env),
     );
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #32)")]
 fn test_register_blood_unauthorized_bank() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, _admin, client, _contract_id) = setup();
 
     let unauthorized_bank = Address::generate(&env);
     let current_time = 1000u64;
@@ -225,12 +275,13 @@ fn test_register_blood_unauthorized_bank() {
         &450u32,
         &(current_time + 30 * 86400),
         &None,
+        &RegistrationOptions::new(&env),
     );
 }
 
 #[test]
 fn test_register_all_blood_types() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -250,7 +301,14 @@ fn test_register_all_blood_types() {
     ];
 
     for (i, blood_type) in blood_types.iter().enumerate() {
-        let id = client.register_blood(&bank, &blood_type, &450u32, &expiration, &None);
+        let id = client.register_blood(
+            &bank,
+            &blood_type,
+            &450u32,
+            &expiration,
+            &None,
+            &RegistrationOptions::new(&env),
+        );
 
         assert_eq!(id, (i + 1) as u64);
 
@@ -262,14 +320,14 @@ fn test_register_all_blood_types() {
 #[test]
 #[should_panic(expected = "Error(Contract, #21)")]
 fn test_get_blood_unit_not_found() {
-    let (_env, _admin, client, _contract_id) = create_test_contract();
+    let (_env, _admin, client, _contract_id) = setup();
 
     client.get_blood_unit(&999);
 }
 
 #[test]
 fn test_register_blood_edge_case_quantities() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -277,19 +335,33 @@ fn test_register_blood_edge_case_quantities() {
     let expiration = current_time + (30 * 86400);
 
     // Minimum valid quantity
-    let id1 = client.register_blood(&bank, &BloodType::APositive, &100u32, &expiration, &None);
+    let id1 = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &100u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
     let unit1 = client.get_blood_unit(&id1);
     assert_eq!(unit1.quantity_ml, 100);
 
     // Maximum valid quantity
-    let id2 = client.register_blood(&bank, &BloodType::BPositive, &600u32, &expiration, &None);
+    let id2 = client.register_blood(
+        &bank,
+        &BloodType::BPositive,
+        &600u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
     let unit2 = client.get_blood_unit(&id2);
     assert_eq!(unit2.quantity_ml, 600);
 }
 
 #[test]
 fn test_register_blood_edge_case_expiration() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -303,6 +375,7 @@ fn test_register_blood_edge_case_expiration() {
         &450u32,
         &min_expiration,
         &None,
+        &RegistrationOptions::new(&env),
     );
     let unit1 = client.get_blood_unit(&id1);
     assert_eq!(unit1.expiration_timestamp, min_expiration);
@@ -315,21 +388,99 @@ fn test_register_blood_edge_case_expiration() {
         &450u32,
         &max_expiration,
         &None,
+        &RegistrationOptions::new(&env),
     );
     let unit2 = client.get_blood_unit(&id2);
     assert_eq!(unit2.expiration_timestamp, max_expiration);
 }
 
+#[test]
+fn test_register_blood_retry_with_external_ref() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let unit_id = UnitFixture::new(&env)
+        .with_external_ref("LIS-0001")
+        .register(&client, &admin);
+    assert_eq!(count_events(&env, topics::BLOOD_REGISTERED), 1);
+
+    // The retry returns the original unit and emits nothing
+    let retried = UnitFixture::new(&env)
+        .with_external_ref("LIS-0001")
+        .register(&client, &admin);
+    assert_eq!(retried, unit_id);
+    assert_eq!(count_events(&env, topics::BLOOD_REGISTERED), 0);
+    assert_eq!(client.get_blood_unit_count(), 1);
+    assert_eq!(client.get_bank_unit_count(&admin), 1);
+    assert_eq!(
+        client.get_unit_by_external_ref(&admin, &String::from_str(&env, "LIS-0001")),
+        Some(unit_id)
+    );
+
+    // References are namespaced per bank
+    let other_bank = Address::generate(&env);
+    client.authorize_bank(&other_bank, &AuthorizationTier::Full);
+    let other_unit = UnitFixture::new(&env)
+        .with_external_ref("LIS-0001")
+        .register(&client, &other_bank);
+    assert_ne!(other_unit, unit_id);
+}
+
+#[test]
+fn test_register_blood_conflicting_external_ref() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    UnitFixture::new(&env)
+        .with_external_ref("LIS-0001")
+        .register(&client, &admin);
+
+    // Same reference, different bag
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::ONegative,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &None,
+        &RegistrationOptions {
+            external_ref: Some(String::from_str(&env, "LIS-0001")),
+            ..RegistrationOptions::new(&env)
+        },
+    );
+    assert_eq!(result, Err(Ok(ContractError::DuplicateBloodUnit)));
+    assert_eq!(client.get_blood_unit_count(), 1);
+
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &None,
+        &RegistrationOptions {
+            external_ref: Some(String::from_str(&env, "")),
+            ..RegistrationOptions::new(&env)
+        },
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+}
+
 #[test]
 fn test_update_status_available_to_reserved() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Update to Reserved
     let updated_unit = client.update_status(
@@ -348,14 +499,21 @@ fn test_update_status_available_to_reserved() {
 
 #[test]
 fn test_update_status_complete_flow() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Available -> Reserved
     let unit = client.update_status(
@@ -388,14 +546,21 @@ fn test_update_status_complete_flow() {
 #[test]
 #[should_panic(expected = "Error(Contract, #41)")]
 fn test_update_status_invalid_transition() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Try to go directly from Available to Delivered (invalid)
     client.update_status(
@@ -409,14 +574,21 @@ fn test_update_status_invalid_transition() {
 #[test]
 #[should_panic(expected = "Error(Contract, #2)")]
 fn test_update_status_unauthorized() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     let unauthorized = Address::generate(&env);
 
@@ -427,7 +599,7 @@ fn test_update_status_unauthorized() {
 #[test]
 #[should_panic(expected = "Error(Contract, #21)")]
 fn test_update_status_nonexistent_unit() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (_env, admin, client, _contract_id) = setup();
 
     // Try to update unit that doesn't exist
     client.update_status(&999, &BloodStatus::Reserved, &admin, &None);
@@ -436,14 +608,21 @@ fn test_update_status_nonexistent_unit() {
 #[test]
 #[should_panic(expected = "Error(Contract, #23)")]
 fn test_update_status_expired_unit() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (5 * 86400); // 5 days
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Move time past expiration
     env.ledger().set_timestamp(expiration + 100);
@@ -455,14 +634,21 @@ fn test_update_status_expired_unit() {
 #[test]
 #[should_panic(expected = "Error(Contract, #41)")]
 fn test_update_status_from_terminal_delivered() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Move to Delivered
     client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
@@ -477,14 +663,21 @@ fn test_update_status_from_terminal_delivered() {
 
 #[test]
 fn test_mark_delivered_success() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Set to Reserved first (should be InTransit in real scenario, but for test)
     client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
@@ -499,14 +692,21 @@ fn test_mark_delivered_success() {
 #[test]
 #[should_panic(expected = "Error(Contract, #41)")]
 fn test_mark_delivered_from_available() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Try to mark as delivered when still Available (invalid transition)
     client.mark_delivered(&unit_id, &admin, &String::from_str(&env, "Hospital A"));
@@ -516,14 +716,21 @@ fn test_mark_delivered_from_available() {
 
 #[test]
 fn test_mark_expired_success() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Mark as expired from Available state (valid transition)
     let updated = client.mark_expired(&unit_id, &admin);
@@ -533,14 +740,21 @@ fn test_mark_expired_success() {
 
 #[test]
 fn test_mark_expired_from_reserved() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Move to Reserved
     client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
@@ -555,14 +769,21 @@ fn test_mark_expired_from_reserved() {
 
 #[test]
 fn test_status_history_tracking() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Perform status changes
     client.update_status(
@@ -611,14 +832,21 @@ fn test_status_history_tracking() {
 
 #[test]
 fn test_status_change_count() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Initial count should be 0 (no changes yet)
     assert_eq!(client.get_status_change_count(&unit_id), 0);
@@ -638,7 +866,7 @@ fn test_status_change_count() {
 
 #[test]
 fn test_batch_update_status_success() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
@@ -646,9 +874,30 @@ fn test_batch_update_status_success() {
     let expiration = current_time + (30 * 86400);
 
     // Create multiple blood units
-    let id1 = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
-    let id2 = client.register_blood(&bank, &BloodType::BPositive, &450u32, &expiration, &None);
-    let id3 = client.register_blood(&bank, &BloodType::ONegative, &450u32, &expiration, &None);
+    let id1 = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let id2 = client.register_blood(
+        &bank,
+        &BloodType::BPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let id3 = client.register_blood(
+        &bank,
+        &BloodType::ONegative,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Batch update to Reserved
     let unit_ids = vec![&env, id1, id2, id3];
@@ -669,14 +918,21 @@ fn test_batch_update_status_success() {
 
 #[test]
 fn test_batch_update_status_single_unit() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     let unit_ids = vec![&env, unit_id];
     let count = client.batch_update_status(&unit_ids, &BloodStatus::Reserved, &admin, &None);
@@ -690,7 +946,7 @@ fn test_batch_update_status_single_unit() {
 
 #[test]
 fn test_batch_update_status_empty_list() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let empty_list = vec![&env];
     let count = client.batch_update_status(&empty_list, &BloodStatus::Reserved, &admin, &None);
@@ -701,14 +957,21 @@ fn test_batch_update_status_empty_list() {
 #[test]
 #[should_panic(expected = "Error(Contract, #21)")]
 fn test_batch_update_status_nonexistent_unit() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Try batch update with one nonexistent unit
     let unit_ids = vec![&env, unit_id, 999];
@@ -718,14 +981,21 @@ fn test_batch_update_status_nonexistent_unit() {
 #[test]
 #[should_panic(expected = "Error(Contract, #2)")]
 fn test_batch_update_status_unauthorized() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     let unauthorized = Address::generate(&env);
 
@@ -736,15 +1006,29 @@ fn test_batch_update_status_unauthorized() {
 #[test]
 #[should_panic(expected = "Error(Contract, #41)")]
 fn test_batch_update_status_invalid_transition() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     let bank = admin.clone();
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let id1 = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
-    let id2 = client.register_blood(&bank, &BloodType::BPositive, &450u32, &expiration, &None);
+    let id1 = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let id2 = client.register_blood(
+        &bank,
+        &BloodType::BPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
 
     // Move id1 to Reserved
     client.update_status(&id1, &BloodStatus::Reserved, &admin, &None);
@@ -758,3 +1042,5042 @@ fn test_batch_update_status_invalid_transition() {
         &None,
     );
 }
+
+// ==================== Location Tests ====================
+
+#[test]
+fn test_set_unit_location_success() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let fridge = String::from_str(&env, "FRIDGE-1");
+
+    let unit = client.set_unit_location(&bank, &unit_id, &fridge);
+    assert_eq!(unit.location, Some(fridge.clone()));
+
+    let units = client.get_units_by_location(&bank, &fridge);
+    assert_eq!(units.len(), 1);
+    assert_eq!(units.get(0).unwrap(), unit_id);
+}
+
+#[test]
+fn test_set_unit_location_moves_between_locations() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    let id1 = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let id2 = client.register_blood(
+        &bank,
+        &BloodType::BPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let fridge = String::from_str(&env, "FRIDGE-1");
+    let freezer = String::from_str(&env, "FREEZER-2");
+
+    client.set_unit_location(&bank, &id1, &fridge);
+    client.set_unit_location(&bank, &id2, &fridge);
+
+    // Move the first unit to the freezer
+    client.set_unit_location(&bank, &id1, &freezer);
+
+    let in_fridge = client.get_units_by_location(&bank, &fridge);
+    assert_eq!(in_fridge.len(), 1);
+    assert_eq!(in_fridge.get(0).unwrap(), id2);
+
+    let in_freezer = client.get_units_by_location(&bank, &freezer);
+    assert_eq!(in_freezer.len(), 1);
+    assert_eq!(in_freezer.get(0).unwrap(), id1);
+}
+
+#[test]
+fn test_location_cleared_when_unit_leaves_storage() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    let id1 = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let id2 = client.register_blood(
+        &bank,
+        &BloodType::BPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let fridge = String::from_str(&env, "FRIDGE-1");
+
+    client.set_unit_location(&bank, &id1, &fridge);
+    client.set_unit_location(&bank, &id2, &fridge);
+
+    // Reserved units stay on the shelf
+    client.update_status(&id1, &BloodStatus::Reserved, &admin, &None);
+    assert_eq!(client.get_units_by_location(&bank, &fridge).len(), 2);
+
+    // Shipping and discarding both remove the unit from storage
+    client.update_status(&id1, &BloodStatus::InTransit, &admin, &None);
+    client.mark_expired(&id2, &admin);
+
+    assert_eq!(client.get_units_by_location(&bank, &fridge).len(), 0);
+    assert_eq!(client.get_blood_unit(&id1).location, None);
+    assert_eq!(client.get_blood_unit(&id2).location, None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_set_unit_location_empty() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    client.set_unit_location(&bank, &unit_id, &String::from_str(&env, ""));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_set_unit_location_after_delivery() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+    client.update_status(&unit_id, &BloodStatus::InTransit, &admin, &None);
+
+    client.set_unit_location(&bank, &unit_id, &String::from_str(&env, "FRIDGE-1"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_set_unit_location_unauthorized_bank() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let other_bank = Address::generate(&env);
+
+    client.set_unit_location(&other_bank, &unit_id, &String::from_str(&env, "FRIDGE-1"));
+}
+
+// ==================== Donor Milestone Tests ====================
+
+#[test]
+fn test_donor_profile_tracks_donations() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let donor = Address::generate(&env);
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    assert_eq!(client.get_donor_profile(&donor), None);
+
+    client.register_blood(
+        &bank,
+        &BloodType::OPositive,
+        &450u32,
+        &expiration,
+        &Some(donor.clone()),
+        &RegistrationOptions::new(&env),
+    );
+    client.register_blood(
+        &bank,
+        &BloodType::OPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    let profile = client.get_donor_profile(&donor).unwrap();
+    assert_eq!(profile.donation_count, 1);
+    assert_eq!(profile.last_donation_at, current_time);
+    assert_eq!(profile.highest_milestone, 0);
+}
+
+#[test]
+fn test_donor_milestone_reached_once() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let donor = Address::generate(&env);
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    for _ in 0..4 {
+        client.register_blood(
+            &bank,
+            &BloodType::ONegative,
+            &450u32,
+            &expiration,
+            &Some(donor.clone()),
+            &RegistrationOptions::new(&env),
+        );
+    }
+    assert_eq!(
+        client.get_donor_profile(&donor).unwrap().highest_milestone,
+        0
+    );
+
+    // 5th donation crosses the first milestone; the profile records it so
+    // the event cannot fire again
+    client.register_blood(
+        &bank,
+        &BloodType::ONegative,
+        &450u32,
+        &expiration,
+        &Some(donor.clone()),
+        &RegistrationOptions::new(&env),
+    );
+
+    let profile = client.get_donor_profile(&donor).unwrap();
+    assert_eq!(profile.donation_count, 5);
+    assert_eq!(profile.highest_milestone, 5);
+
+    // Further donations below the next milestone don't fire it again
+    for _ in 0..4 {
+        client.register_blood(
+            &bank,
+            &BloodType::ONegative,
+            &450u32,
+            &expiration,
+            &Some(donor.clone()),
+            &RegistrationOptions::new(&env),
+        );
+    }
+    assert_eq!(
+        client.get_donor_profile(&donor).unwrap().highest_milestone,
+        5
+    );
+
+    client.register_blood(
+        &bank,
+        &BloodType::ONegative,
+        &450u32,
+        &expiration,
+        &Some(donor.clone()),
+        &RegistrationOptions::new(&env),
+    );
+    let profile = client.get_donor_profile(&donor).unwrap();
+    assert_eq!(profile.donation_count, 10);
+    assert_eq!(profile.highest_milestone, 10);
+}
+
+// ==================== Capacity Tests ====================
+
+#[test]
+fn test_bank_without_capacity_is_unlimited() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    assert_eq!(client.get_bank_capacity(&bank), None);
+
+    for _ in 0..5 {
+        client.register_blood(
+            &bank,
+            &BloodType::APositive,
+            &450u32,
+            &expiration,
+            &None,
+            &RegistrationOptions::new(&env),
+        );
+    }
+    assert_eq!(client.get_bank_unit_count(&bank), 5);
+}
+
+#[test]
+fn test_bank_capacity_boundary() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    client.set_bank_capacity(&bank, &2u32);
+    assert_eq!(client.get_bank_capacity(&bank), Some(2));
+
+    client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(client.get_bank_unit_count(&bank), 2);
+
+    let result = client.try_register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::CapacityExceeded)));
+}
+
+#[test]
+fn test_bank_capacity_freed_by_terminal_units() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    client.set_bank_capacity(&bank, &2u32);
+
+    let id1 = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let id2 = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    // Reserved and in-transit units still count against capacity
+    client.update_status(&id1, &BloodStatus::Reserved, &admin, &None);
+    client.update_status(&id1, &BloodStatus::InTransit, &admin, &None);
+    assert_eq!(client.get_bank_unit_count(&bank), 2);
+
+    // Delivery and expiry free a slot each
+    client.mark_delivered(&id1, &admin, &String::from_str(&env, "Hospital A"));
+    assert_eq!(client.get_bank_unit_count(&bank), 1);
+    client.mark_expired(&id2, &admin);
+    assert_eq!(client.get_bank_unit_count(&bank), 0);
+
+    client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(client.get_bank_unit_count(&bank), 2);
+}
+
+// ==================== FIFO Dispensing Tests ====================
+
+#[test]
+fn test_get_next_unit_fifo_earliest_expiration() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let late = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .expiring_at(current_time + 30 * 86400)
+        .register(&client, &bank);
+    let early = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .expiring_at(current_time + 10 * 86400)
+        .register(&client, &bank);
+    UnitFixture::new(&env)
+        .expiring_at(current_time + 5 * 86400)
+        .register(&client, &bank);
+
+    assert_eq!(
+        client.get_next_unit_fifo(&BloodType::ONegative),
+        Some(early)
+    );
+
+    // Reserving the earliest unit moves on to the next one
+    client.update_status(&early, &BloodStatus::Reserved, &admin, &None);
+    assert_eq!(client.get_next_unit_fifo(&BloodType::ONegative), Some(late));
+
+    // Releasing the reservation puts it back at the front
+    client.update_status(&early, &BloodStatus::Available, &admin, &None);
+    assert_eq!(
+        client.get_next_unit_fifo(&BloodType::ONegative),
+        Some(early)
+    );
+}
+
+#[test]
+fn test_get_next_unit_fifo_donation_time_tiebreak() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let expiration = 1000u64 + 30 * 86400;
+
+    env.ledger().set_timestamp(2000u64);
+    let second = client.register_blood(
+        &bank,
+        &BloodType::BPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    env.ledger().set_timestamp(1000u64);
+    let first = client.register_blood(
+        &bank,
+        &BloodType::BPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    env.ledger().set_timestamp(3000u64);
+    assert_eq!(
+        client.get_next_unit_fifo(&BloodType::BPositive),
+        Some(first)
+    );
+
+    client.mark_expired(&first, &admin);
+    assert_eq!(
+        client.get_next_unit_fifo(&BloodType::BPositive),
+        Some(second)
+    );
+}
+
+#[test]
+fn test_get_next_unit_fifo_skips_expired() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    UnitFixture::new(&env)
+        .with_blood_type(BloodType::ABPositive)
+        .expiring_at(current_time + 8 * 86400)
+        .register(&client, &bank);
+    let fresh = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ABPositive)
+        .expiring_at(current_time + 20 * 86400)
+        .register(&client, &bank);
+
+    // First unit has expired on the ledger but hasn't been marked yet
+    advance_days(&env, 9);
+    assert_eq!(
+        client.get_next_unit_fifo(&BloodType::ABPositive),
+        Some(fresh)
+    );
+}
+
+#[test]
+fn test_selection_views_skip_temperature_excursions() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let oldest = UnitFixture::new(&env)
+        .with_phenotype(&["kell_neg"])
+        .expiring_in_days(10)
+        .register(&client, &admin);
+    let next = UnitFixture::new(&env)
+        .with_phenotype(&["kell_neg"])
+        .expiring_in_days(20)
+        .register(&client, &admin);
+    assert_eq!(
+        client.get_next_unit_fifo(&BloodType::APositive),
+        Some(oldest)
+    );
+
+    // The bank still holds the unit, so it logs the reading itself
+    client.record_temperature(&admin, &oldest, &85, &1000);
+    assert!(client.has_temperature_excursion(&oldest));
+
+    assert_eq!(client.get_next_unit_fifo(&BloodType::APositive), Some(next));
+    let required = vec![&env, Symbol::new(&env, "kell_neg")];
+    assert_eq!(
+        client.find_units_with_phenotype(&BloodType::APositive, &required, &10),
+        vec![&env, next]
+    );
+}
+
+#[test]
+fn test_get_next_unit_fifo_none_available() {
+    let (_env, _admin, client, _contract_id) = setup();
+
+    assert_eq!(client.get_next_unit_fifo(&BloodType::ANegative), None);
+}
+
+// ========== Reservation Tests ==========
+
+#[test]
+fn test_reserve_unit_exact_match() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let expiration = current_time + 30 * 86400;
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    let unit = client.reserve_unit(
+        &unit_id,
+        &1u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+    assert_eq!(unit.status, BloodStatus::Reserved);
+    assert_eq!(unit.reserved_for, Some(1));
+    assert_eq!(client.get_reserved_units(&1u64), vec![&env, unit_id]);
+    assert_eq!(client.get_next_unit_fifo(&BloodType::APositive), None);
+}
+
+#[test]
+fn test_reserve_unit_compatible_requires_flag() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let expiration = current_time + 30 * 86400;
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::ONegative,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    let result = client.try_reserve_unit(
+        &unit_id,
+        &1u64,
+        &BloodType::BPositive,
+        &false,
+        &admin,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(ContractError::IncompatibleBloodType)));
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Available
+    );
+
+    let unit = client.reserve_unit(&unit_id, &1u64, &BloodType::BPositive, &true, &admin, &None);
+    assert_eq!(unit.status, BloodStatus::Reserved);
+}
+
+#[test]
+fn test_reserve_unit_incompatible_with_flag() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let expiration = current_time + 30 * 86400;
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::ABPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    let result =
+        client.try_reserve_unit(&unit_id, &1u64, &BloodType::ONegative, &true, &admin, &None);
+    assert_eq!(result, Err(Ok(ContractError::IncompatibleBloodType)));
+}
+
+#[test]
+fn test_release_units_for_request() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let expiration = current_time + 30 * 86400;
+    let first = client.register_blood(
+        &bank,
+        &BloodType::OPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let second = client.register_blood(
+        &bank,
+        &BloodType::OPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let other = client.register_blood(
+        &bank,
+        &BloodType::OPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    client.reserve_unit(&first, &7u64, &BloodType::OPositive, &false, &admin, &None);
+    client.reserve_unit(&second, &7u64, &BloodType::OPositive, &false, &admin, &None);
+    client.reserve_unit(&other, &8u64, &BloodType::OPositive, &false, &admin, &None);
+
+    assert_eq!(client.release_units_for_request(&admin, &7u64), 2);
+
+    for unit_id in [first, second] {
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Available);
+        assert_eq!(unit.reserved_for, None);
+    }
+    assert!(client.get_reserved_units(&7u64).is_empty());
+
+    // Reservations for other requests are untouched
+    assert_eq!(client.get_blood_unit(&other).status, BloodStatus::Reserved);
+    assert_eq!(client.get_reserved_units(&8u64), vec![&env, other]);
+}
+
+#[test]
+fn test_release_units_for_request_unauthorized() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let stranger = Address::generate(&env);
+    let result = client.try_release_units_for_request(&stranger, &1u64);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_set_request_contract_not_initialized() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(InventoryContract, ());
+    let client = InventoryContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_set_request_contract(&Address::generate(&env)),
+        Err(Ok(ContractError::NotInitialized))
+    );
+}
+
+#[test]
+fn test_release_units_by_request_contract() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let request_contract = Address::generate(&env);
+    client.set_request_contract(&request_contract);
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env)
+        .expiring_at(1000 + 30 * 86400)
+        .register(&client, &bank);
+    client.reserve_unit(
+        &unit_id,
+        &3u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+
+    assert_eq!(
+        client.release_units_for_request(&request_contract, &3u64),
+        1
+    );
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Available
+    );
+}
+
+#[test]
+fn test_expiring_reserved_unit_ends_reservation() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env)
+        .expiring_at(1000 + 30 * 86400)
+        .register(&client, &bank);
+    client.reserve_unit(
+        &unit_id,
+        &3u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+
+    client.mark_expired(&unit_id, &admin);
+
+    assert_eq!(client.get_blood_unit(&unit_id).reserved_for, None);
+    assert!(client.get_reserved_units(&3u64).is_empty());
+}
+
+#[test]
+fn test_unit_events_topics_include_bank_and_blood_type() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::BPositive,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    let (event_topics, data) =
+        find_event(&env, topics::BLOOD_REGISTERED).expect("blood_registered not emitted");
+    assert_eq!(
+        event_topics,
+        (
+            Symbol::new(&env, topics::BLOOD_REGISTERED),
+            bank.clone(),
+            BloodType::BPositive,
+        )
+            .into_val(&env)
+    );
+    let registered = BloodRegisteredEvent::try_from_val(&env, &data).unwrap();
+    assert_eq!(registered.blood_unit_id, unit_id);
+    assert_eq!(registered.quantity_ml, 450);
+
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+
+    let (event_topics, data) =
+        find_event(&env, topics::STATUS_CHANGED).expect("status_changed not emitted");
+    assert_eq!(
+        event_topics,
+        (
+            Symbol::new(&env, topics::STATUS_CHANGED),
+            bank,
+            BloodType::BPositive,
+        )
+            .into_val(&env)
+    );
+    let changed = StatusChangeEvent::try_from_val(&env, &data).unwrap();
+    assert_eq!(changed.blood_unit_id, unit_id);
+    assert_eq!(changed.from_status, BloodStatus::Available);
+    assert_eq!(changed.to_status, BloodStatus::Reserved);
+}
+
+#[test]
+fn test_reserve_then_release_events() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let expiration = 1000 + 30 * 86400;
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    env.ledger().set_timestamp(2000);
+    client.reserve_unit(
+        &unit_id,
+        &9u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+
+    let (event_topics, data) =
+        find_event(&env, topics::BLOOD_RESERVED).expect("blood_reserved not emitted");
+    assert_eq!(
+        event_topics,
+        (
+            Symbol::new(&env, topics::BLOOD_RESERVED),
+            bank.clone(),
+            BloodType::APositive,
+        )
+            .into_val(&env)
+    );
+    let reserved = BloodReservedEvent::try_from_val(&env, &data).unwrap();
+    assert_eq!(
+        reserved,
+        BloodReservedEvent {
+            unit_ids: vec![&env, unit_id],
+            request_id: 9,
+            bank_id: bank.clone(),
+            total_ml: 450,
+            reserved_until: expiration,
+            reserved_at: 2000,
+        }
+    );
+
+    env.ledger().set_timestamp(3000);
+    assert_eq!(client.release_units_for_request(&admin, &9u64), 1);
+
+    let (event_topics, data) =
+        find_event(&env, topics::RESERVATION_RELEASED).expect("reservation_released not emitted");
+    assert_eq!(
+        event_topics,
+        (
+            Symbol::new(&env, topics::RESERVATION_RELEASED),
+            bank.clone(),
+            BloodType::APositive,
+        )
+            .into_val(&env)
+    );
+    let released = ReservationReleasedEvent::try_from_val(&env, &data).unwrap();
+    assert_eq!(
+        released,
+        ReservationReleasedEvent {
+            blood_unit_id: unit_id,
+            request_id: 9,
+            status: BloodStatus::Available,
+            released_at: 3000,
+        }
+    );
+}
+
+fn assert_discarded_event(
+    env: &Env,
+    unit_id: u64,
+    bank: &Address,
+    blood_type: BloodType,
+    discard_reason: DiscardReason,
+    reason: &str,
+) {
+    let (event_topics, data) =
+        find_event(env, topics::BLOOD_DISCARDED).expect("blood_discarded not emitted");
+    assert_eq!(
+        event_topics,
+        (
+            Symbol::new(env, topics::BLOOD_DISCARDED),
+            bank.clone(),
+            blood_type,
+        )
+            .into_val(env)
+    );
+    assert_eq!(
+        BloodDiscardedEvent::try_from_val(env, &data).unwrap(),
+        BloodDiscardedEvent {
+            blood_unit_id: unit_id,
+            bank_id: bank.clone(),
+            blood_type,
+            quantity_ml: 450,
+            discard_reason,
+            reason: Some(String::from_str(env, reason)),
+            discarded_at: env.ledger().timestamp(),
+        }
+    );
+}
+
+#[test]
+fn test_discard_events_carry_reason_and_quantity() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let expiration = 1000 + 30 * 86400;
+    let screened = client.register_blood(
+        &bank,
+        &BloodType::BNegative,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    let recalled = client.register_blood(
+        &bank,
+        &BloodType::OPositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    env.ledger().set_timestamp(2000);
+    client.discard_blood_unit(
+        &screened,
+        &admin,
+        &DiscardReason::PositiveScreening,
+        &String::from_str(&env, "Failed screening"),
+    );
+    assert_discarded_event(
+        &env,
+        screened,
+        &bank,
+        BloodType::BNegative,
+        DiscardReason::PositiveScreening,
+        "Failed screening",
+    );
+
+    env.ledger().set_timestamp(3000);
+    client.update_status(
+        &recalled,
+        &BloodStatus::Discarded,
+        &admin,
+        &Some(String::from_str(&env, "Recalled")),
+    );
+    assert_discarded_event(
+        &env,
+        recalled,
+        &bank,
+        BloodType::OPositive,
+        DiscardReason::Other,
+        "Recalled",
+    );
+}
+
+#[test]
+fn test_expire_emits_blood_expired_event() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let unit_id = client.register_blood(
+        &bank,
+        &BloodType::ABNegative,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    env.ledger().set_timestamp(5000);
+    client.mark_expired(&unit_id, &admin);
+
+    assert!(find_event(&env, topics::BLOOD_DISCARDED).is_none());
+    let (event_topics, data) =
+        find_event(&env, topics::BLOOD_EXPIRED).expect("blood_expired not emitted");
+    assert_eq!(
+        event_topics,
+        (
+            Symbol::new(&env, topics::BLOOD_EXPIRED),
+            bank.clone(),
+            BloodType::ABNegative,
+        )
+            .into_val(&env)
+    );
+    assert_eq!(
+        BloodExpiredEvent::try_from_val(&env, &data).unwrap(),
+        BloodExpiredEvent {
+            blood_unit_id: unit_id,
+            bank_id: bank,
+            blood_type: BloodType::ABNegative,
+            quantity_ml: 450,
+            expired_at: 5000,
+        }
+    );
+}
+
+#[test]
+fn test_transfer_reservation_moves_hold() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let unit_id = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .register(&client, &admin);
+    client.reserve_unit(&unit_id, &3, &BloodType::ONegative, &false, &admin, &None);
+    assert_eq!(client.get_reserved_for(&unit_id), Some(3));
+
+    let previous = client.transfer_reservation(&admin, &unit_id, &7, &BloodType::APositive, &true);
+    assert_eq!(previous, 3);
+    assert_eq!(
+        event_names(&env),
+        vec![
+            &env,
+            Symbol::new(&env, topics::RESERVATION_RELEASED),
+            Symbol::new(&env, topics::BLOOD_RESERVED),
+        ]
+    );
+    let released: ReservationReleasedEvent = latest_event(&env, topics::RESERVATION_RELEASED);
+    assert_eq!(released.request_id, 3);
+    let reserved: BloodReservedEvent = latest_event(&env, topics::BLOOD_RESERVED);
+    assert_eq!(reserved.request_id, 7);
+
+    let unit = client.get_blood_unit(&unit_id);
+    assert_eq!(unit.status, BloodStatus::Reserved);
+    assert_eq!(unit.reserved_for, Some(7));
+    assert_eq!(client.get_reserved_for(&unit_id), Some(7));
+    assert_eq!(client.get_reserved_units(&3).len(), 0);
+    assert_eq!(client.get_reserved_units(&7), vec![&env, unit_id]);
+}
+
+#[test]
+fn test_reservation_stats_through_preemption_and_expiry() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let expiring = UnitFixture::new(&env)
+        .expiring_at(1000 + 2 * 86400)
+        .register(&client, &admin);
+    let shipped = UnitFixture::new(&env)
+        .with_quantity(300)
+        .register(&client, &admin);
+    assert_eq!(
+        client.get_reservation_stats(&admin),
+        ReservationStats::default()
+    );
+
+    client.reserve_unit(&expiring, &3, &BloodType::APositive, &false, &admin, &None);
+    client.reserve_unit(&shipped, &3, &BloodType::APositive, &false, &admin, &None);
+    assert_eq!(
+        client.get_reservation_stats(&admin),
+        ReservationStats {
+            reserved_ml: 750,
+            active_holds: 2,
+            ended_holds: 0,
+            hold_seconds: 0,
+        }
+    );
+
+    // Preemption moves the hold to another request without ending it
+    advance_hours(&env, 1);
+    client.transfer_reservation(&admin, &expiring, &7, &BloodType::APositive, &false);
+    assert_eq!(client.get_reservation_stats(&admin).active_holds, 2);
+    assert_eq!(client.get_reservation_stats(&admin).ended_holds, 0);
+
+    // Shipping a unit ends its hold
+    advance_hours(&env, 1);
+    client.update_status(&shipped, &BloodStatus::InTransit, &admin, &None);
+    assert_eq!(
+        client.get_reservation_stats(&admin),
+        ReservationStats {
+            reserved_ml: 450,
+            active_holds: 1,
+            ended_holds: 1,
+            hold_seconds: 2 * 3600,
+        }
+    );
+
+    // The other hold runs until the unit expires and is released
+    env.ledger().set_timestamp(1000 + 3 * 86400);
+    client.release_units_for_request(&admin, &7);
+    assert_eq!(
+        client.get_blood_unit(&expiring).status,
+        BloodStatus::Expired
+    );
+    assert_eq!(
+        client.get_reservation_stats(&admin),
+        ReservationStats {
+            reserved_ml: 0,
+            active_holds: 0,
+            ended_holds: 2,
+            hold_seconds: 2 * 3600 + 3 * 86400,
+        }
+    );
+
+    // Other banks are tracked separately
+    let other_bank = Address::generate(&env);
+    assert_eq!(
+        client.get_reservation_stats(&other_bank),
+        ReservationStats::default()
+    );
+}
+
+#[test]
+fn test_transfer_reservation_rejections() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let available = UnitFixture::new(&env).register(&client, &admin);
+    let result =
+        client.try_transfer_reservation(&admin, &available, &7, &BloodType::APositive, &false);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatus)));
+    assert_eq!(client.get_reserved_for(&available), None);
+
+    let reserved = UnitFixture::new(&env).register(&client, &admin);
+    client.reserve_unit(&reserved, &3, &BloodType::APositive, &false, &admin, &None);
+
+    let result =
+        client.try_transfer_reservation(&admin, &reserved, &3, &BloodType::APositive, &false);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+    let result =
+        client.try_transfer_reservation(&admin, &reserved, &7, &BloodType::BPositive, &false);
+    assert_eq!(result, Err(Ok(ContractError::IncompatibleBloodType)));
+    let stranger = Address::generate(&env);
+    let result =
+        client.try_transfer_reservation(&stranger, &reserved, &7, &BloodType::APositive, &false);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    let donor = Address::generate(&env);
+    let autologous = UnitFixture::new(&env)
+        .with_donor(&donor)
+        .autologous()
+        .register(&client, &admin);
+    client.reserve_unit(
+        &autologous,
+        &3,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &Some(donor),
+    );
+    let result =
+        client.try_transfer_reservation(&admin, &autologous, &7, &BloodType::APositive, &false);
+    assert_eq!(result, Err(Ok(ContractError::AutologousMismatch)));
+    assert_eq!(client.get_reserved_for(&autologous), Some(3));
+}
+
+// ========== Directed Donation Tests ==========
+
+#[test]
+fn test_directed_unit_held_outside_general_pool() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let directed = UnitFixture::new(&env)
+        .directed_to(7)
+        .register(&client, &admin);
+
+    let unit = client.get_blood_unit(&directed);
+    assert_eq!(unit.status, BloodStatus::Directed);
+    assert_eq!(unit.directed_to, Some(7));
+    assert_eq!(client.get_directed_units(&7u64), vec![&env, directed]);
+
+    // Not offered to anyone else
+    assert_eq!(client.get_next_unit_fifo(&BloodType::APositive), None);
+    assert_eq!(
+        client
+            .get_type_breakdown(&BloodType::APositive)
+            .available_ml,
+        0
+    );
+    assert!(client
+        .get_unit_summaries_by_type(&BloodType::APositive, &Some(BloodStatus::Available), &10)
+        .is_empty());
+
+    let result = client.try_reserve_unit(
+        &directed,
+        &8u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(ContractError::BloodUnitNotAvailable)));
+
+    // Only the target request can take it
+    let unit = client.reserve_unit(
+        &directed,
+        &7u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+    assert_eq!(unit.status, BloodStatus::Reserved);
+    assert_eq!(unit.reserved_for, Some(7));
+}
+
+#[test]
+fn test_directed_unit_released_to_pool_when_request_closes() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let request_contract = Address::generate(&env);
+    client.set_request_contract(&request_contract);
+
+    // Expires first, so FIFO picks it once both units are back
+    let unused = UnitFixture::new(&env)
+        .directed_to(7)
+        .expiring_in_days(20)
+        .register(&client, &admin);
+    let reserved = UnitFixture::new(&env)
+        .directed_to(7)
+        .register(&client, &admin);
+    let elsewhere = UnitFixture::new(&env)
+        .directed_to(8)
+        .register(&client, &admin);
+    client.reserve_unit(
+        &reserved,
+        &7u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+
+    // The request closed without using either unit
+    assert_eq!(
+        client.release_units_for_request(&request_contract, &7u64),
+        2
+    );
+
+    for unit_id in [unused, reserved] {
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.status, BloodStatus::Available);
+        assert_eq!(unit.directed_to, None);
+        assert_eq!(unit.reserved_for, None);
+    }
+    assert!(client.get_directed_units(&7u64).is_empty());
+    assert_eq!(
+        client
+            .get_type_breakdown(&BloodType::APositive)
+            .available_ml,
+        900
+    );
+    assert_eq!(
+        client.get_next_unit_fifo(&BloodType::APositive),
+        Some(unused)
+    );
+
+    // Units donated for another request stay put, and released units can
+    // now serve anyone
+    assert_eq!(
+        client.get_blood_unit(&elsewhere).status,
+        BloodStatus::Directed
+    );
+    client.reserve_unit(&unused, &9u64, &BloodType::APositive, &false, &admin, &None);
+}
+
+// ========== Autologous Donation Tests ==========
+
+#[test]
+fn test_autologous_unit_held_outside_general_pool() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let donor = Address::generate(&env);
+    let unit_id = UnitFixture::new(&env)
+        .with_donor(&donor)
+        .with_phenotype(&["K_NEG"])
+        .autologous()
+        .register(&client, &admin);
+
+    let unit = client.get_blood_unit(&unit_id);
+    assert_eq!(unit.status, BloodStatus::Autologous);
+    assert!(unit.autologous);
+
+    assert_eq!(client.get_next_unit_fifo(&BloodType::APositive), None);
+    assert_eq!(
+        client
+            .get_type_breakdown(&BloodType::APositive)
+            .available_ml,
+        0
+    );
+    assert!(client
+        .find_units_with_phenotype(
+            &BloodType::APositive,
+            &vec![&env, Symbol::new(&env, "K_NEG")],
+            &10
+        )
+        .is_empty());
+
+    // It can never be made generally available
+    let result = client.try_update_status(&unit_id, &BloodStatus::Available, &admin, &None);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+}
+
+#[test]
+fn test_autologous_unit_reserved_only_for_donor() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let donor = Address::generate(&env);
+    let unit_id = UnitFixture::new(&env)
+        .with_donor(&donor)
+        .autologous()
+        .register(&client, &admin);
+
+    let result = client.try_reserve_unit(
+        &unit_id,
+        &1u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(ContractError::AutologousMismatch)));
+
+    let stranger = Some(Address::generate(&env));
+    let result = client.try_reserve_unit(
+        &unit_id,
+        &1u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &stranger,
+    );
+    assert_eq!(result, Err(Ok(ContractError::AutologousMismatch)));
+
+    let unit = client.reserve_unit(
+        &unit_id,
+        &1u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &Some(donor),
+    );
+    assert_eq!(unit.status, BloodStatus::Reserved);
+    assert_eq!(unit.reserved_for, Some(1));
+}
+
+#[test]
+fn test_autologous_unit_released_back_to_donor_or_discarded() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let request_contract = Address::generate(&env);
+    client.set_request_contract(&request_contract);
+
+    let donor = Address::generate(&env);
+    let kept = UnitFixture::new(&env)
+        .with_donor(&donor)
+        .expiring_in_days(30)
+        .autologous()
+        .register(&client, &admin);
+    let lapsing = UnitFixture::new(&env)
+        .with_donor(&donor)
+        .expiring_in_days(2)
+        .autologous()
+        .register(&client, &admin);
+    for unit_id in [kept, lapsing] {
+        client.reserve_unit(
+            &unit_id,
+            &1u64,
+            &BloodType::APositive,
+            &false,
+            &admin,
+            &Some(donor.clone()),
+        );
+    }
+
+    advance_days(&env, 3);
+
+    // Neither unit goes back to the general pool
+    assert_eq!(
+        client.release_units_for_request(&request_contract, &1u64),
+        0
+    );
+    assert_eq!(client.get_blood_unit(&kept).status, BloodStatus::Autologous);
+    let lapsed = client.get_blood_unit(&lapsing);
+    assert_eq!(lapsed.status, BloodStatus::Discarded);
+    assert_eq!(
+        client.get_discard_reason(&lapsing),
+        Some(DiscardReason::Expired)
+    );
+    assert_eq!(client.get_next_unit_fifo(&BloodType::APositive), None);
+}
+
+#[test]
+fn test_autologous_registration_requires_donor() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let options = RegistrationOptions {
+        autologous: true,
+        ..RegistrationOptions::new(&env)
+    };
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &None,
+        &options,
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+}
+
+// ========== Platelet Pooling Tests ==========
+
+#[test]
+fn test_pool_units_combines_sources() {
+    let (env, admin, client, contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let donors = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let mut sources = Vec::new(&env);
+    for (i, donor) in donors.iter().enumerate() {
+        let tags: &[&str] = if i == 0 {
+            &["K_NEG", "C_NEG"]
+        } else {
+            &["K_NEG"]
+        };
+        let unit_id = UnitFixture::new(&env)
+            .with_product(ProductType::Platelets)
+            .with_quantity(100)
+            .with_donor(donor)
+            .with_phenotype(tags)
+            .expiring_in_days(2 + i as u64)
+            .register(&client, &admin);
+        sources.push_back(unit_id);
+    }
+    assert_eq!(client.get_bank_unit_count(&admin), 4);
+
+    let pooled_id = client.pool_units(&admin, &sources);
+
+    let event: UnitsPooledEvent = latest_event(&env, topics::UNITS_POOLED);
+    assert_eq!(event.pooled_unit_id, pooled_id);
+    assert_eq!(event.source_unit_ids, sources);
+    assert_eq!(event.quantity_ml, 400);
+
+    let pooled = client.get_blood_unit(&pooled_id);
+    assert_eq!(pooled.status, BloodStatus::Available);
+    assert_eq!(pooled.product, ProductType::Platelets);
+    assert_eq!(pooled.quantity_ml, 400);
+    assert_eq!(pooled.expiration_timestamp, 1000 + 2 * 86400);
+    assert_eq!(pooled.donor_id, None);
+    assert_eq!(pooled.pooled_from, sources);
+    assert_eq!(
+        pooled.phenotype_tags,
+        vec![&env, Symbol::new(&env, "K_NEG")]
+    );
+
+    for unit_id in sources.iter() {
+        assert_eq!(client.get_blood_unit(&unit_id).status, BloodStatus::Pooled);
+    }
+    for donor in donors.iter() {
+        let units = env.as_contract(&contract_id, || storage::get_donor_units(&env, donor));
+        assert!(units.contains(pooled_id));
+    }
+
+    // Only the pool is left on the shelf
+    assert_eq!(client.get_bank_unit_count(&admin), 1);
+    assert_eq!(
+        client
+            .get_type_breakdown(&BloodType::APositive)
+            .available_ml,
+        400
+    );
+    assert_eq!(
+        client.get_next_unit_fifo(&BloodType::APositive),
+        Some(pooled_id)
+    );
+}
+
+#[test]
+fn test_pool_units_capped_at_platelet_shelf_life() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    // Whole blood keeps for weeks, the platelets pooled from it do not
+    let sources = UnitFixture::new(&env)
+        .expiring_in_days(30)
+        .register_many(&client, &admin, 4);
+
+    let pooled_id = client.pool_units(&admin, &sources);
+    let pooled = client.get_blood_unit(&pooled_id);
+    assert_eq!(pooled.quantity_ml, 1800);
+    assert_eq!(pooled.expiration_timestamp, 1000 + 5 * 86400);
+
+    // A pool is not a source for another pool
+    let more = UnitFixture::new(&env).register(&client, &admin);
+    let result = client.try_pool_units(&admin, &vec![&env, pooled_id, more]);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_pool_units_rejects_unsuitable_sources() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let a = UnitFixture::new(&env).register(&client, &admin);
+    let b = UnitFixture::new(&env).register(&client, &admin);
+
+    let cases = [
+        (vec![&env, a], ContractError::InvalidInput),
+        (vec![&env, a, a], ContractError::InvalidInput),
+        (vec![&env, a, 999], ContractError::NotFound),
+    ];
+    for (source_ids, expected) in cases {
+        let result = client.try_pool_units(&admin, &source_ids);
+        assert_eq!(result, Err(Ok(expected)));
+    }
+
+    let other_type = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .register(&client, &admin);
+    let result = client.try_pool_units(&admin, &vec![&env, a, other_type]);
+    assert_eq!(result, Err(Ok(ContractError::IncompatibleBloodType)));
+
+    let plasma = UnitFixture::new(&env)
+        .with_product(ProductType::Plasma)
+        .register(&client, &admin);
+    let result = client.try_pool_units(&admin, &vec![&env, a, plasma]);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let other_bank = Address::generate(&env);
+    client.authorize_bank(&other_bank, &AuthorizationTier::Full);
+    let foreign = UnitFixture::new(&env).register(&client, &other_bank);
+    let result = client.try_pool_units(&admin, &vec![&env, a, foreign]);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    client.reserve_unit(&b, &1u64, &BloodType::APositive, &false, &admin, &None);
+    let result = client.try_pool_units(&admin, &vec![&env, a, b]);
+    assert_eq!(result, Err(Ok(ContractError::BloodUnitNotAvailable)));
+
+    // Nothing was pooled along the way
+    assert_eq!(client.get_blood_unit(&a).status, BloodStatus::Available);
+
+    // Pooled is only reachable through pool_units
+    let result = client.try_update_status(&a, &BloodStatus::Pooled, &admin, &None);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+}
+
+// ========== Split Tests ==========
+
+fn component(product: ProductType, quantity_ml: u32) -> ComponentSplit {
+    ComponentSplit {
+        product,
+        quantity_ml,
+    }
+}
+
+#[test]
+fn test_split_unit_into_components() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let donor = Address::generate(&env);
+    let parent_id = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_donor(&donor)
+        .register(&client, &admin);
+
+    let children = vec![
+        &env,
+        component(ProductType::RedCells, 280),
+        component(ProductType::Plasma, 150),
+    ];
+    let child_ids = client.split_unit(&admin, &parent_id, &children);
+    assert_eq!(child_ids.len(), 2);
+
+    let event: UnitSplitEvent = latest_event(&env, topics::UNIT_SPLIT);
+    assert_eq!(
+        event,
+        UnitSplitEvent {
+            parent_unit_id: parent_id,
+            bank_id: admin.clone(),
+            child_unit_ids: child_ids.clone(),
+            split_at: 1000,
+        }
+    );
+
+    let parent = client.get_blood_unit(&parent_id);
+    assert_eq!(parent.status, BloodStatus::Split);
+
+    let red_cells = client.get_blood_unit(&child_ids.get(0).unwrap());
+    assert_eq!(red_cells.product, ProductType::RedCells);
+    assert_eq!(red_cells.quantity_ml, 280);
+    assert_eq!(red_cells.blood_type, BloodType::ONegative);
+    assert_eq!(red_cells.donor_id, Some(donor.clone()));
+    assert_eq!(red_cells.split_from, Some(parent_id));
+    assert_eq!(red_cells.status, BloodStatus::Available);
+    assert_eq!(
+        red_cells.expiration_timestamp,
+        parent.donation_timestamp + 42 * 86400
+    );
+
+    let plasma = client.get_blood_unit(&child_ids.get(1).unwrap());
+    assert_eq!(
+        plasma.expiration_timestamp,
+        parent.donation_timestamp + 365 * 86400
+    );
+
+    // A split unit cannot be split again
+    let result = client.try_split_unit(&admin, &parent_id, &children);
+    assert_eq!(result, Err(Ok(ContractError::BloodUnitNotAvailable)));
+}
+
+#[test]
+fn test_split_unit_enforces_conversion_rules() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let whole_blood = UnitFixture::new(&env).register(&client, &admin);
+    let plasma = UnitFixture::new(&env)
+        .with_product(ProductType::Plasma)
+        .with_quantity(250)
+        .register(&client, &admin);
+
+    // 450 ml of whole blood cannot yield 500 ml of red cells
+    let result = client.try_split_unit(
+        &admin,
+        &whole_blood,
+        &vec![&env, component(ProductType::RedCells, 500)],
+    );
+    assert_eq!(result, Err(Ok(ContractError::ChildVolumeExceedsParent)));
+
+    // Platelets are never derived from plasma
+    let result = client.try_split_unit(
+        &admin,
+        &plasma,
+        &vec![&env, component(ProductType::Platelets, 120)],
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidComponentDerivation)));
+
+    // Tightening the red cell fraction rejects a yield allowed before
+    client.set_conversion_fraction(&ProductType::WholeBlood, &ProductType::RedCells, &5_000);
+    assert_eq!(
+        client.get_conversion_fraction(&ProductType::WholeBlood, &ProductType::RedCells),
+        Some(5_000)
+    );
+    let result = client.try_split_unit(
+        &admin,
+        &whole_blood,
+        &vec![&env, component(ProductType::RedCells, 250)],
+    );
+    assert_eq!(result, Err(Ok(ContractError::ChildVolumeExceedsParent)));
+    assert_eq!(
+        client.get_blood_unit(&whole_blood).status,
+        BloodStatus::Available
+    );
+}
+
+#[test]
+fn test_set_conversion_fraction_bounds() {
+    let (_env, _admin, client, _contract_id) = setup();
+
+    assert_eq!(
+        client.get_conversion_fraction(&ProductType::Plasma, &ProductType::Platelets),
+        None
+    );
+    assert_eq!(
+        client.try_set_conversion_fraction(&ProductType::Plasma, &ProductType::Platelets, &5_000),
+        Err(Ok(ContractError::InvalidComponentDerivation))
+    );
+    assert_eq!(
+        client.try_set_conversion_fraction(&ProductType::WholeBlood, &ProductType::Plasma, &100),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_set_conversion_fraction(&ProductType::WholeBlood, &ProductType::Plasma, &10_001),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        client.get_conversion_fraction(&ProductType::WholeBlood, &ProductType::Plasma),
+        Some(6_000)
+    );
+}
+
+// ========== Custody Tests ==========
+
+/// Register a unit at the admin bank, reserve it and put it in transit
+fn unit_in_transit(env: &Env, client: &InventoryContractClient<'_>, admin: &Address) -> u64 {
+    let unit_id = UnitFixture::new(env).register(client, admin);
+    client.reserve_unit(&unit_id, &1, &BloodType::APositive, &false, admin, &None);
+    client.update_status(&unit_id, &BloodStatus::InTransit, admin, &None);
+    unit_id
+}
+
+#[test]
+fn test_unit_starts_in_bank_custody() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+
+    assert_eq!(client.get_blood_unit(&unit_id).custodian, admin);
+    assert_eq!(client.get_custody_history(&unit_id).len(), 0);
+}
+
+#[test]
+fn test_record_pickup_hands_units_to_courier() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let courier = Address::generate(&env);
+    client.grant_role(&admin, &courier, &BankRole::Registrar);
+    let first = unit_in_transit(&env, &client, &admin);
+    let second = UnitFixture::new(&env).register(&client, &admin);
+    client.reserve_unit(&second, &1, &BloodType::APositive, &false, &admin, &None);
+
+    env.ledger().set_timestamp(2000);
+    client.record_pickup(&courier, &vec![&env, first, second]);
+
+    assert_eq!(count_events(&env, topics::CUSTODY_CHANGED), 2);
+    let event: CustodyChangedEvent = latest_event(&env, topics::CUSTODY_CHANGED);
+    assert_eq!(
+        event,
+        CustodyChangedEvent {
+            blood_unit_id: second,
+            from_custodian: admin.clone(),
+            to_custodian: courier.clone(),
+            changed_at: 2000,
+        }
+    );
+
+    for unit_id in [first, second] {
+        let unit = client.get_blood_unit(&unit_id);
+        assert_eq!(unit.custodian, courier);
+        assert_eq!(
+            client.get_custody_history(&unit_id),
+            vec![
+                &env,
+                CustodyChange {
+                    blood_unit_id: unit_id,
+                    from_custodian: admin.clone(),
+                    to_custodian: courier.clone(),
+                    changed_at: 2000,
+                },
+            ]
+        );
+    }
+    // Custody does not change the status
+    assert_eq!(client.get_blood_unit(&second).status, BloodStatus::Reserved);
+}
+
+#[test]
+fn test_record_pickup_rejections() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let courier = Address::generate(&env);
+    client.grant_role(&admin, &courier, &BankRole::Registrar);
+    let in_stock = UnitFixture::new(&env).register(&client, &admin);
+    let in_transit = unit_in_transit(&env, &client, &admin);
+
+    // Units still in general stock are not handed to couriers
+    let result = client.try_record_pickup(&courier, &vec![&env, in_stock]);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatus)));
+
+    // Only operators of the unit's bank may pick it up
+    let stranger = Address::generate(&env);
+    let result = client.try_record_pickup(&stranger, &vec![&env, in_transit]);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    let result = client.try_record_pickup(&courier, &vec![&env]);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+    let result = client.try_record_pickup(&courier, &vec![&env, 999]);
+    assert_eq!(result, Err(Ok(ContractError::NotFound)));
+
+    client.record_pickup(&courier, &vec![&env, in_transit]);
+    let result = client.try_record_pickup(&courier, &vec![&env, in_transit]);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(client.get_custody_history(&in_transit).len(), 1);
+}
+
+#[test]
+fn test_record_receipt_delivers_to_hospital() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let courier = Address::generate(&env);
+    let hospital = Address::generate(&env);
+    client.grant_role(&admin, &courier, &BankRole::Registrar);
+    let unit_id = unit_in_transit(&env, &client, &admin);
+    client.record_pickup(&courier, &vec![&env, unit_id]);
+
+    env.ledger().set_timestamp(3000);
+    client.record_receipt(&hospital, &vec![&env, unit_id]);
+
+    assert_eq!(
+        event_names(&env),
+        vec![
+            &env,
+            Symbol::new(&env, topics::CUSTODY_CHANGED),
+            Symbol::new(&env, topics::STATUS_CHANGED),
+        ]
+    );
+    let event: CustodyChangedEvent = latest_event(&env, topics::CUSTODY_CHANGED);
+    assert_eq!(event.from_custodian, courier);
+    assert_eq!(event.to_custodian, hospital);
+    assert_eq!(event.changed_at, 3000);
+
+    let unit = client.get_blood_unit(&unit_id);
+    assert_eq!(unit.custodian, hospital);
+    assert_eq!(unit.status, BloodStatus::Delivered);
+
+    let history = client.get_custody_history(&unit_id);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().from_custodian, courier);
+    assert_eq!(history.get(1).unwrap().to_custodian, hospital);
+}
+
+#[test]
+fn test_custody_changes_fail_on_terminal_units() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let courier = Address::generate(&env);
+    let hospital = Address::generate(&env);
+    client.grant_role(&admin, &courier, &BankRole::Registrar);
+    let delivered = unit_in_transit(&env, &client, &admin);
+    client.record_receipt(&hospital, &vec![&env, delivered]);
+    let discarded = UnitFixture::new(&env).register(&client, &admin);
+    client.discard_blood_unit(
+        &discarded,
+        &admin,
+        &DiscardReason::BagDamage,
+        &String::from_str(&env, "Torn bag"),
+    );
+
+    for unit_id in [delivered, discarded] {
+        let result = client.try_record_pickup(&courier, &vec![&env, unit_id]);
+        assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+        let result = client.try_record_receipt(&hospital, &vec![&env, unit_id]);
+        assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+    }
+
+    // Receipt needs the unit to be on its way
+    let reserved = UnitFixture::new(&env).register(&client, &admin);
+    client.reserve_unit(&reserved, &1, &BloodType::APositive, &false, &admin, &None);
+    let result = client.try_record_receipt(&hospital, &vec![&env, reserved]);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatus)));
+    assert_eq!(client.get_blood_unit(&reserved).custodian, admin);
+}
+
+// ========== Cold Chain Tests ==========
+
+#[test]
+fn test_in_range_temperature_series() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let courier = Address::generate(&env);
+    client.grant_role(&admin, &courier, &BankRole::Registrar);
+    let unit_id = unit_in_transit(&env, &client, &admin);
+    client.record_pickup(&courier, &vec![&env, unit_id]);
+
+    env.ledger().set_timestamp(5000);
+    for (temp, at) in [(40, 2000u64), (45, 3000), (60, 4000)] {
+        client.record_temperature(&courier, &unit_id, &temp, &at);
+    }
+
+    let log = client.get_temperature_log(&unit_id);
+    assert_eq!(log.len(), 3);
+    assert_eq!(
+        log.get(2).unwrap(),
+        TemperatureReading {
+            temp_decicelsius: 60,
+            recorded_at: 4000,
+            recorded_by: courier.clone(),
+            in_range: true,
+        }
+    );
+    assert!(log.iter().all(|reading| reading.in_range));
+    assert!(!client.has_temperature_excursion(&unit_id));
+    assert_eq!(count_events(&env, topics::TEMPERATURE_EXCURSION), 0);
+
+    // Only the custodian logs, in order and not ahead of the ledger
+    assert_eq!(
+        client.try_record_temperature(&admin, &unit_id, &40, &4500),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_record_temperature(&courier, &unit_id, &40, &3500),
+        Err(Ok(ContractError::InvalidTimestamp))
+    );
+    assert_eq!(
+        client.try_record_temperature(&courier, &unit_id, &40, &6000),
+        Err(Ok(ContractError::InvalidTimestamp))
+    );
+}
+
+#[test]
+fn test_temperature_excursion_blocks_reservation() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let courier = Address::generate(&env);
+    client.grant_role(&admin, &courier, &BankRole::Registrar);
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+    client.reserve_unit(&unit_id, &1, &BloodType::APositive, &false, &admin, &None);
+    client.record_pickup(&courier, &vec![&env, unit_id]);
+
+    env.ledger().set_timestamp(3000);
+    client.record_temperature(&courier, &unit_id, &40, &2000);
+    client.record_temperature(&courier, &unit_id, &85, &3000);
+
+    let event: TemperatureExcursionEvent = latest_event(&env, topics::TEMPERATURE_EXCURSION);
+    assert_eq!(
+        event,
+        TemperatureExcursionEvent {
+            blood_unit_id: unit_id,
+            temp_decicelsius: 85,
+            range: ProductType::WholeBlood.default_temperature_range(),
+            recorded_by: courier.clone(),
+            recorded_at: 3000,
+        }
+    );
+    assert!(client.has_temperature_excursion(&unit_id));
+    assert!(
+        !client
+            .get_temperature_log(&unit_id)
+            .get(1)
+            .unwrap()
+            .in_range
+    );
+
+    // Back in range later does not clear the flag
+    client.record_temperature(&courier, &unit_id, &40, &3000);
+    assert!(client.has_temperature_excursion(&unit_id));
+
+    client.release_units_for_request(&admin, &1u64);
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Available
+    );
+    let result =
+        client.try_reserve_unit(&unit_id, &2, &BloodType::APositive, &false, &admin, &None);
+    assert_eq!(result, Err(Ok(ContractError::TemperatureExcursion)));
+}
+
+// ========== Waitlist Tests ==========
+
+#[test]
+fn test_match_available_names_top_priority_request() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    client.waitlist_request(&admin, &1u64, &BloodType::ONegative, &200u64);
+    client.waitlist_request(&admin, &2u64, &BloodType::ONegative, &500u64);
+    client.waitlist_request(&admin, &3u64, &BloodType::ONegative, &500u64);
+    client.waitlist_request(&admin, &4u64, &BloodType::APositive, &900u64);
+
+    let waitlist = client.get_waitlist(&BloodType::ONegative);
+    assert_eq!(waitlist.len(), 3);
+    assert_eq!(waitlist.get(0).unwrap().request_id, 2);
+    assert_eq!(waitlist.get(1).unwrap().request_id, 3);
+    assert_eq!(waitlist.get(2).unwrap().request_id, 1);
+
+    let unit_id = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .register(&client, &admin);
+
+    // One signal, for the highest score that joined first
+    assert_eq!(count_events(&env, topics::MATCH_AVAILABLE), 1);
+    let event: MatchAvailableEvent = latest_event(&env, topics::MATCH_AVAILABLE);
+    assert_eq!(
+        event,
+        MatchAvailableEvent {
+            blood_unit_id: unit_id,
+            request_id: 2,
+            blood_type: BloodType::ONegative,
+            bank_id: admin.clone(),
+            quantity_ml: 450,
+            matched_at: 1000,
+        }
+    );
+
+    // No reservation is made and the request stays waitlisted
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Available
+    );
+    assert_eq!(client.get_waitlist(&BloodType::ONegative).len(), 3);
+}
+
+#[test]
+fn test_match_available_skips_removed_requests() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let request_contract = Address::generate(&env);
+    client.set_request_contract(&request_contract);
+
+    client.waitlist_request(&request_contract, &1u64, &BloodType::BPositive, &900u64);
+    client.waitlist_request(&request_contract, &2u64, &BloodType::BPositive, &100u64);
+    assert_eq!(
+        client.try_waitlist_request(&request_contract, &1u64, &BloodType::BPositive, &900u64),
+        Err(Ok(ContractError::AlreadyExists))
+    );
+
+    assert!(client.remove_from_waitlist(&request_contract, &1u64));
+    assert!(!client.remove_from_waitlist(&request_contract, &1u64));
+
+    UnitFixture::new(&env)
+        .with_blood_type(BloodType::BPositive)
+        .register(&client, &admin);
+    let event: MatchAvailableEvent = latest_event(&env, topics::MATCH_AVAILABLE);
+    assert_eq!(event.request_id, 2);
+
+    // Other types and directed donations are never announced
+    assert!(client.remove_from_waitlist(&request_contract, &2u64));
+    assert!(client.get_waitlist(&BloodType::BPositive).is_empty());
+    UnitFixture::new(&env)
+        .with_blood_type(BloodType::BPositive)
+        .register(&client, &admin);
+    assert_eq!(count_events(&env, topics::MATCH_AVAILABLE), 0);
+
+    client.waitlist_request(&request_contract, &3u64, &BloodType::BPositive, &100u64);
+    UnitFixture::new(&env)
+        .with_blood_type(BloodType::BPositive)
+        .directed_to(4)
+        .register(&client, &admin);
+    assert_eq!(count_events(&env, topics::MATCH_AVAILABLE), 0);
+}
+
+#[test]
+fn test_waitlist_unauthorized() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_waitlist_request(&stranger, &1u64, &BloodType::APositive, &1u64),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_remove_from_waitlist(&stranger, &1u64),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+// ========== Campaign Tests ==========
+
+#[test]
+fn test_campaign_stats_across_two_campaigns() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let other_bank = Address::generate(&env);
+    client.authorize_bank(&other_bank, &AuthorizationTier::Full);
+
+    let spring = client.create_campaign(
+        &admin,
+        &String::from_str(&env, "Spring drive"),
+        &1000u64,
+        &(1000 + 7 * 86400),
+    );
+    let campus = client.create_campaign(
+        &other_bank,
+        &String::from_str(&env, "Campus drive"),
+        &1000u64,
+        &(1000 + 86400),
+    );
+    assert_eq!((spring, campus), (1, 2));
+    let created: CampaignCreatedEvent = latest_event(&env, topics::CAMPAIGN_CREATED);
+    assert_eq!(created.bank_id, other_bank);
+
+    UnitFixture::new(&env)
+        .in_campaign(spring)
+        .register_many(&client, &admin, 2);
+    let o_neg = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_quantity(300)
+        .in_campaign(spring)
+        .register(&client, &admin);
+    UnitFixture::new(&env)
+        .with_blood_type(BloodType::BPositive)
+        .in_campaign(campus)
+        .register(&client, &other_bank);
+    // Outside any campaign
+    UnitFixture::new(&env).register(&client, &admin);
+
+    assert_eq!(client.get_blood_unit(&o_neg).campaign_id, Some(spring));
+
+    let stats = client.get_campaign_stats(&spring);
+    assert_eq!(stats.units, 3);
+    assert_eq!(stats.total_ml, 1200);
+    assert_eq!(stats.by_blood_type.len(), 2);
+    assert_eq!(stats.by_blood_type.get(BloodType::APositive), Some(900));
+    assert_eq!(stats.by_blood_type.get(BloodType::ONegative), Some(300));
+
+    let stats = client.get_campaign_stats(&campus);
+    assert_eq!(stats.units, 1);
+    assert_eq!(stats.total_ml, 450);
+    assert_eq!(stats.by_blood_type.get(BloodType::BPositive), Some(450));
+
+    assert_eq!(
+        client.try_get_campaign_stats(&99),
+        Err(Ok(ContractError::NotFound))
+    );
+}
+
+#[test]
+fn test_register_against_closed_or_foreign_campaign_fails() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let other_bank = Address::generate(&env);
+    client.authorize_bank(&other_bank, &AuthorizationTier::Full);
+
+    let campaign = client.create_campaign(
+        &admin,
+        &String::from_str(&env, "Weekend drive"),
+        &(1000 + 86400),
+        &(1000 + 3 * 86400),
+    );
+    let expiration = 1000 + 30 * 86400u64;
+    let register = |bank: &Address| {
+        client.try_register_blood(
+            bank,
+            &BloodType::APositive,
+            &450u32,
+            &expiration,
+            &None,
+            &RegistrationOptions {
+                campaign_id: Some(campaign),
+                ..RegistrationOptions::new(&env)
+            },
+        )
+    };
+
+    // Not started yet
+    assert_eq!(register(&admin), Err(Ok(ContractError::CampaignClosed)));
+
+    advance_days(&env, 1);
+    assert!(register(&admin).is_ok());
+    assert_eq!(register(&other_bank), Err(Ok(ContractError::Unauthorized)));
+
+    // Ended
+    advance_days(&env, 3);
+    assert_eq!(register(&admin), Err(Ok(ContractError::CampaignClosed)));
+
+    assert_eq!(client.get_campaign_stats(&campaign).units, 1);
+}
+
+#[test]
+fn test_create_campaign_validation() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let result = client.try_create_campaign(&admin, &String::from_str(&env, ""), &0u64, &10u64);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let name = String::from_str(&env, "Drive");
+    let result = client.try_create_campaign(&admin, &name, &10u64, &5u64);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let stranger = Address::generate(&env);
+    let result = client.try_create_campaign(&stranger, &name, &0u64, &10u64);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedBloodBank)));
+}
+
+// ========== Phenotype Tests ==========
+
+#[test]
+fn test_find_units_with_phenotype() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let kell_neg = Symbol::new(&env, "kell_neg");
+    let c_neg = Symbol::new(&env, "c_neg");
+
+    let both = UnitFixture::new(&env)
+        .with_phenotype(&["kell_neg", "c_neg"])
+        .expiring_in_days(20)
+        .register(&client, &admin);
+    let kell_only = UnitFixture::new(&env)
+        .with_phenotype(&["kell_neg"])
+        .expiring_in_days(10)
+        .register(&client, &admin);
+    UnitFixture::new(&env)
+        .with_blood_type(BloodType::BPositive)
+        .with_phenotype(&["kell_neg", "c_neg"])
+        .register(&client, &admin);
+    let untyped = UnitFixture::new(&env).register(&client, &admin);
+
+    assert_eq!(
+        client.get_blood_unit(&both).phenotype_tags,
+        vec![&env, kell_neg.clone(), c_neg.clone()]
+    );
+
+    // A unit missing one required tag does not match
+    let required = vec![&env, kell_neg.clone(), c_neg.clone()];
+    assert_eq!(
+        client.find_units_with_phenotype(&BloodType::APositive, &required, &10),
+        vec![&env, both]
+    );
+    assert!(client.has_phenotype_tags(&both, &required));
+    assert!(!client.has_phenotype_tags(&kell_only, &required));
+    assert!(!client.has_phenotype_tags(&999, &required));
+
+    // Soonest expiration first
+    let required = vec![&env, kell_neg.clone()];
+    assert_eq!(
+        client.find_units_with_phenotype(&BloodType::APositive, &required, &10),
+        vec![&env, kell_only, both]
+    );
+    assert_eq!(
+        client.find_units_with_phenotype(&BloodType::APositive, &required, &1),
+        vec![&env, kell_only]
+    );
+
+    // No required tags matches every available unit
+    assert_eq!(
+        client
+            .find_units_with_phenotype(&BloodType::APositive, &vec![&env], &10)
+            .len(),
+        3
+    );
+
+    // Reserved units are no longer offered
+    client.reserve_unit(&kell_only, &1, &BloodType::APositive, &false, &admin, &None);
+    assert_eq!(
+        client.find_units_with_phenotype(&BloodType::APositive, &required, &10),
+        vec![&env, both]
+    );
+    assert!(client.get_blood_unit(&untyped).phenotype_tags.is_empty());
+}
+
+#[test]
+fn test_set_phenotype_tags() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let other_bank = Address::generate(&env);
+    client.authorize_bank(&other_bank, &AuthorizationTier::Full);
+    let unit_id = UnitFixture::new(&env)
+        .with_phenotype(&["kell_neg"])
+        .register(&client, &admin);
+
+    let tags = vec![
+        &env,
+        Symbol::new(&env, "kell_neg"),
+        Symbol::new(&env, "e_neg"),
+    ];
+    let unit = client.set_phenotype_tags(&admin, &unit_id, &tags);
+    assert_eq!(unit.phenotype_tags, tags);
+
+    let event: PhenotypeTagsSetEvent = latest_event(&env, topics::PHENOTYPE_TAGS_SET);
+    assert_eq!(event.blood_unit_id, unit_id);
+    assert_eq!(
+        event.previous_tags,
+        vec![&env, Symbol::new(&env, "kell_neg")]
+    );
+    assert_eq!(event.phenotype_tags, tags);
+    assert_eq!(client.get_blood_unit(&unit_id).phenotype_tags, tags);
+
+    // Only the unit's bank may tag it
+    let result = client.try_set_phenotype_tags(&other_bank, &unit_id, &tags);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    let duplicated = vec![
+        &env,
+        Symbol::new(&env, "kell_neg"),
+        Symbol::new(&env, "kell_neg"),
+    ];
+    let result = client.try_set_phenotype_tags(&admin, &unit_id, &duplicated);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let mut too_many = Vec::new(&env);
+    for tag in [
+        "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q",
+    ]
+    .iter()
+    .take(MAX_PHENOTYPE_TAGS as usize + 1)
+    {
+        too_many.push_back(Symbol::new(&env, tag));
+    }
+    assert_eq!(too_many.len(), MAX_PHENOTYPE_TAGS + 1);
+    let result = client.try_set_phenotype_tags(&admin, &unit_id, &too_many);
+    assert_eq!(result, Err(Ok(ContractError::TooManyPhenotypeTags)));
+
+    // Clearing is allowed
+    let unit = client.set_phenotype_tags(&admin, &unit_id, &vec![&env]);
+    assert!(unit.phenotype_tags.is_empty());
+}
+
+// ========== Admin Transfer Tests ==========
+
+#[test]
+fn test_admin_transfer_two_step() {
+    let (env, admin, client, contract_id) = setup();
+
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+    client.accept_admin();
+
+    let stored_admin = env.as_contract(&contract_id, || storage::get_admin(&env));
+    assert_eq!(stored_admin, new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+    assert_ne!(stored_admin, admin);
+}
+
+#[test]
+fn test_accept_admin_without_proposal() {
+    let (_env, _admin, client, _contract_id) = setup();
+
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(ContractError::NoPendingAdmin)));
+}
+
+#[test]
+fn test_admin_rotation_events() {
+    let (env, admin, client, contract_id) = setup();
+    env.ledger().set_timestamp(100);
+
+    let first = Address::generate(&env);
+    let successor = Address::generate(&env);
+
+    let result = client.try_cancel_admin_transfer();
+    assert_eq!(result, Err(Ok(ContractError::NoPendingAdmin)));
+
+    client.propose_admin(&first);
+    assert_eq!(
+        event_names(&env),
+        vec![&env, Symbol::new(&env, topics::ADMIN_PROPOSED)]
+    );
+    assert_eq!(
+        latest_event::<AdminTransferProposedEvent>(&env, topics::ADMIN_PROPOSED),
+        AdminTransferProposedEvent {
+            current_admin: admin.clone(),
+            proposed_admin: first.clone(),
+            proposed_at: 100,
+        }
+    );
+
+    client.cancel_admin_transfer();
+    assert_eq!(
+        latest_event::<AdminTransferCancelledEvent>(&env, topics::ADMIN_TRANSFER_CANCELLED),
+        AdminTransferCancelledEvent {
+            admin: admin.clone(),
+            cancelled_admin: first.clone(),
+            cancelled_at: 100,
+        }
+    );
+    assert_eq!(client.get_pending_admin(), None);
+
+    // Replacing a pending proposal cancels it first
+    client.propose_admin(&first);
+    client.propose_admin(&successor);
+    assert_eq!(
+        event_names(&env),
+        vec![
+            &env,
+            Symbol::new(&env, topics::ADMIN_TRANSFER_CANCELLED),
+            Symbol::new(&env, topics::ADMIN_PROPOSED),
+        ]
+    );
+
+    client.accept_admin();
+    assert_eq!(
+        event_names(&env),
+        vec![
+            &env,
+            Symbol::new(&env, topics::ADMIN_CHANGED),
+            Symbol::new(&env, topics::IMPLICIT_BANK_AUTH_CHANGED),
+            Symbol::new(&env, topics::IMPLICIT_BANK_AUTH_CHANGED),
+        ]
+    );
+    assert_eq!(
+        latest_event::<AdminTransferredEvent>(&env, topics::ADMIN_CHANGED),
+        AdminTransferredEvent {
+            old_admin: admin,
+            new_admin: successor.clone(),
+            transferred_at: 100,
+        }
+    );
+    let stored_admin = env.as_contract(&contract_id, || storage::get_admin(&env));
+    assert_eq!(stored_admin, successor);
+}
+
+#[test]
+fn test_old_admin_cannot_register_blood_after_rotation() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + 30 * 86400;
+
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
+    client.accept_admin();
+
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedBloodBank)));
+
+    UnitFixture::new(&env)
+        .expiring_at(expiration)
+        .register(&client, &new_admin);
+}
+
+// ========== Role Tests ==========
+
+#[test]
+fn test_grant_and_revoke_role() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    let courier = Address::generate(&env);
+    let manager = Address::generate(&env);
+
+    client.grant_role(&bank, &courier, &BankRole::Registrar);
+    client.grant_role(&bank, &manager, &BankRole::InventoryManager);
+
+    assert_eq!(client.get_role(&bank, &courier), Some(BankRole::Registrar));
+    assert_eq!(
+        client.get_role_holders(&bank),
+        vec![&env, courier.clone(), manager.clone()]
+    );
+
+    // Re-granting replaces the role without duplicating the holder
+    client.grant_role(&bank, &courier, &BankRole::Auditor);
+    assert_eq!(client.get_role(&bank, &courier), Some(BankRole::Auditor));
+    assert_eq!(client.get_role_holders(&bank).len(), 2);
+
+    client.revoke_role(&bank, &courier);
+    assert_eq!(client.get_role(&bank, &courier), None);
+    assert_eq!(client.get_role_holders(&bank), vec![&env, manager]);
+
+    let result = client.try_revoke_role(&bank, &courier);
+    assert_eq!(result, Err(Ok(ContractError::NotFound)));
+}
+
+#[test]
+fn test_grant_role_requires_authorized_bank() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let not_a_bank = Address::generate(&env);
+    let result = client.try_grant_role(&not_a_bank, &Address::generate(&env), &BankRole::Auditor);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedBloodBank)));
+}
+
+#[test]
+fn test_registrar_can_record_location() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env).register(&client, &bank);
+
+    let courier = Address::generate(&env);
+    client.grant_role(&bank, &courier, &BankRole::Registrar);
+
+    let fridge = String::from_str(&env, "FRIDGE-1");
+    let unit = client.set_unit_location(&courier, &unit_id, &fridge);
+    assert_eq!(unit.location, Some(fridge));
+}
+
+#[test]
+fn test_registrar_cannot_discard() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env).register(&client, &bank);
+
+    let courier = Address::generate(&env);
+    client.grant_role(&bank, &courier, &BankRole::Registrar);
+
+    let result = client.try_discard_blood_unit(
+        &unit_id,
+        &courier,
+        &DiscardReason::BagDamage,
+        &String::from_str(&env, "Torn bag"),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InsufficientPermissions)));
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Available
+    );
+}
+
+#[test]
+fn test_inventory_manager_can_discard() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env).register(&client, &bank);
+
+    let manager = Address::generate(&env);
+    client.grant_role(&bank, &manager, &BankRole::InventoryManager);
+
+    let unit = client.discard_blood_unit(
+        &unit_id,
+        &manager,
+        &DiscardReason::BagDamage,
+        &String::from_str(&env, "Torn bag"),
+    );
+    assert_eq!(unit.status, BloodStatus::Discarded);
+    assert_eq!(client.get_bank_unit_count(&bank), 0);
+    assert_eq!(client.get_next_unit_fifo(&BloodType::APositive), None);
+
+    // Discarded is terminal
+    let result = client.try_discard_blood_unit(
+        &unit_id,
+        &manager,
+        &DiscardReason::BagDamage,
+        &String::from_str(&env, "Again"),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+}
+
+#[test]
+fn test_discard_unauthorized() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+
+    let stranger = Address::generate(&env);
+    let result = client.try_discard_blood_unit(
+        &unit_id,
+        &stranger,
+        &DiscardReason::BagDamage,
+        &String::from_str(&env, "Torn bag"),
+    );
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_register_only_bank_cannot_discard() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let van = Address::generate(&env);
+    client.authorize_bank(&van, &AuthorizationTier::RegisterOnly);
+    assert_eq!(
+        client.get_bank_tier(&van),
+        Some(AuthorizationTier::RegisterOnly)
+    );
+
+    // Registering is allowed
+    let unit_id = UnitFixture::new(&env).register(&client, &van);
+
+    let result = client.try_discard_blood_unit(
+        &unit_id,
+        &van,
+        &DiscardReason::BagDamage,
+        &String::from_str(&env, "Torn bag"),
+    );
+    assert_eq!(result, Err(Ok(ContractError::RegisterOnlyBank)));
+
+    // Operators acting for the van are limited the same way
+    let manager = Address::generate(&env);
+    client.grant_role(&van, &manager, &BankRole::InventoryManager);
+    let result = client.try_update_status(&unit_id, &BloodStatus::Reserved, &manager, &None);
+    assert_eq!(result, Err(Ok(ContractError::RegisterOnlyBank)));
+    let result = client.try_set_unit_location(&van, &unit_id, &String::from_str(&env, "F1"));
+    assert_eq!(result, Err(Ok(ContractError::RegisterOnlyBank)));
+
+    // The admin is not limited
+    client.discard_blood_unit(
+        &unit_id,
+        &admin,
+        &DiscardReason::BagDamage,
+        &String::from_str(&env, "Torn bag"),
+    );
+}
+
+#[test]
+fn test_set_bank_tier() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let bank = Address::generate(&env);
+    let result = client.try_set_bank_tier(&bank, &AuthorizationTier::Full);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedBloodBank)));
+    assert_eq!(client.get_bank_tier(&bank), None);
+    assert_eq!(client.get_bank_tier(&admin), Some(AuthorizationTier::Full));
+
+    client.authorize_bank(&bank, &AuthorizationTier::RegisterOnly);
+    let first = UnitFixture::new(&env).register(&client, &bank);
+    let second = UnitFixture::new(&env).register(&client, &bank);
+    let result = client.try_discard_blood_unit(
+        &first,
+        &bank,
+        &DiscardReason::BagDamage,
+        &String::from_str(&env, "Torn bag"),
+    );
+    assert_eq!(result, Err(Ok(ContractError::RegisterOnlyBank)));
+
+    client.set_bank_tier(&bank, &AuthorizationTier::Full);
+    let (event_topics, data) =
+        find_event(&env, topics::BANK_TIER_CHANGED).expect("bank_tier_changed not emitted");
+    assert_eq!(
+        event_topics,
+        (Symbol::new(&env, topics::BANK_TIER_CHANGED), bank.clone()).into_val(&env)
+    );
+    assert_eq!(
+        BankTierChangedEvent::try_from_val(&env, &data).unwrap(),
+        BankTierChangedEvent {
+            bank: bank.clone(),
+            tier: AuthorizationTier::Full,
+            admin,
+            changed_at: 1000,
+        }
+    );
+
+    client.discard_blood_unit(
+        &first,
+        &bank,
+        &DiscardReason::BagDamage,
+        &String::from_str(&env, "Torn bag"),
+    );
+
+    // Downgrading takes effect straight away
+    client.set_bank_tier(&bank, &AuthorizationTier::RegisterOnly);
+    let result =
+        client.try_batch_update_status(&vec![&env, second], &BloodStatus::Reserved, &bank, &None);
+    assert_eq!(result, Err(Ok(ContractError::RegisterOnlyBank)));
+
+    // Revoking drops the tier
+    client.revoke_bank(&bank);
+    assert_eq!(client.get_bank_tier(&bank), None);
+}
+
+#[test]
+fn test_discard_breakdown_per_reason_and_epoch() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let units = UnitFixture::new(&env).register_many(&client, &admin, 5);
+    let notes = String::from_str(&env, "Logged by night shift");
+    let reasons = [
+        DiscardReason::BrokenColdChain,
+        DiscardReason::BrokenColdChain,
+        DiscardReason::PositiveScreening,
+        DiscardReason::ClericalError,
+    ];
+    for (i, discard_reason) in reasons.iter().enumerate() {
+        let unit = client.discard_blood_unit(
+            &units.get(i as u32).unwrap(),
+            &admin,
+            discard_reason,
+            &notes,
+        );
+        assert_eq!(client.get_discard_reason(&unit.id), Some(*discard_reason));
+    }
+
+    let breakdown = client.get_discard_breakdown(&0);
+    assert_eq!(breakdown.len(), 3);
+    assert_eq!(breakdown.get(DiscardReason::BrokenColdChain), Some(2));
+    assert_eq!(breakdown.get(DiscardReason::PositiveScreening), Some(1));
+    assert_eq!(breakdown.get(DiscardReason::ClericalError), Some(1));
+    assert_eq!(breakdown.get(DiscardReason::BagDamage), None);
+
+    // Later discards land in their own epoch; uncategorized ones count as Other
+    env.ledger().set_timestamp(storage::SECONDS_PER_EPOCH + 10);
+    client.update_status(
+        &units.get(4).unwrap(),
+        &BloodStatus::Discarded,
+        &admin,
+        &None,
+    );
+    let breakdown = client.get_discard_breakdown(&1);
+    assert_eq!(
+        breakdown,
+        Map::from_array(&env, [(DiscardReason::Other, 1)])
+    );
+    assert_eq!(client.get_discard_breakdown(&0).len(), 3);
+    assert!(client.get_discard_breakdown(&2).is_empty());
+}
+
+#[test]
+fn test_auditor_reads_restricted_views_only() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env).register(&client, &bank);
+
+    let auditor = Address::generate(&env);
+    client.grant_role(&bank, &auditor, &BankRole::Auditor);
+
+    assert_eq!(client.get_bank_units(&auditor, &bank), vec![&env, unit_id]);
+
+    let result = client.try_get_bank_units(&Address::generate(&env), &bank);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    let result = client.try_update_status(&unit_id, &BloodStatus::Reserved, &auditor, &None);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientPermissions)));
+
+    let result =
+        client.try_set_unit_location(&auditor, &unit_id, &String::from_str(&env, "FRIDGE-1"));
+    assert_eq!(result, Err(Ok(ContractError::InsufficientPermissions)));
+}
+
+#[test]
+fn test_units_registered_by_operator() {
+    let (env, _admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let bank = Address::generate(&env);
+    client.authorize_bank(&bank, &AuthorizationTier::Full);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    client.grant_role(&bank, &alice, &BankRole::Registrar);
+    client.grant_role(&bank, &bob, &BankRole::InventoryManager);
+    client.grant_role(&bank, &auditor, &BankRole::Auditor);
+
+    let first = UnitFixture::new(&env)
+        .by_operator(&alice)
+        .register(&client, &bank);
+    let event: BloodRegisteredEvent = latest_event(&env, topics::BLOOD_REGISTERED);
+    assert_eq!(event.bank_id, bank);
+    assert_eq!(event.registered_by, alice);
+    let second = UnitFixture::new(&env)
+        .by_operator(&bob)
+        .register(&client, &bank);
+    let third = UnitFixture::new(&env)
+        .by_operator(&alice)
+        .register(&client, &bank);
+    let own = UnitFixture::new(&env).register(&client, &bank);
+
+    assert_eq!(client.get_blood_unit(&first).registered_by, alice);
+    assert_eq!(client.get_blood_unit(&own).registered_by, bank);
+    assert_eq!(client.get_bank_units(&bank, &bank).len(), 4);
+
+    assert_eq!(
+        client.get_units_registered_by(&auditor, &bank, &alice, &0, &10),
+        vec![&env, first, third]
+    );
+    assert_eq!(
+        client.get_units_registered_by(&auditor, &bank, &alice, &1, &10),
+        vec![&env, third]
+    );
+    assert_eq!(
+        client.get_units_registered_by(&bank, &bank, &bob, &0, &10),
+        vec![&env, second]
+    );
+    assert_eq!(
+        client.get_units_registered_by(&bank, &bank, &bank, &0, &10),
+        vec![&env, own]
+    );
+
+    let result =
+        client.try_get_units_registered_by(&Address::generate(&env), &bank, &alice, &0, &10);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_auditor_cannot_register() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let auditor = Address::generate(&env);
+    client.grant_role(&admin, &auditor, &BankRole::Auditor);
+
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &None,
+        &RegistrationOptions {
+            operator: Some(auditor.clone()),
+            ..RegistrationOptions::new(&env)
+        },
+    );
+    assert_eq!(result, Err(Ok(ContractError::InsufficientPermissions)));
+
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &None,
+        &RegistrationOptions {
+            operator: Some(Address::generate(&env)),
+            ..RegistrationOptions::new(&env)
+        },
+    );
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+// ========== Donor Deferral Tests ==========
+
+#[test]
+fn test_deferred_donor_cannot_register() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let expiration = 1000 + 30 * 86400;
+    let donor = Address::generate(&env);
+
+    client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &Some(donor.clone()),
+        &RegistrationOptions::new(&env),
+    );
+    client.defer_donor_permanently(&donor, &7u32);
+
+    let result = client.try_register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &Some(donor.clone()),
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::DonorDeferred)));
+
+    let profile = client.get_donor_profile(&donor).unwrap();
+    assert!(profile.permanently_deferred);
+    assert_eq!(profile.donation_count, 1);
+}
+
+#[test]
+fn test_reinstated_donor_can_register() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = admin.clone();
+    env.ledger().set_timestamp(1000);
+    let expiration = 1000 + 30 * 86400;
+    let donor = Address::generate(&env);
+
+    client.defer_donor_permanently(&donor, &7u32);
+    client.reinstate_donor(&donor);
+
+    client.register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &Some(donor.clone()),
+        &RegistrationOptions::new(&env),
+    );
+
+    let profile = client.get_donor_profile(&donor).unwrap();
+    assert!(!profile.permanently_deferred);
+    assert_eq!(profile.donation_count, 1);
+    assert_eq!(client.get_deferral_reason(&donor, &donor), None);
+}
+
+#[test]
+fn test_reinstate_donor_not_deferred() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let result = client.try_reinstate_donor(&Address::generate(&env));
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_deferral_reason_is_confidential() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let donor = Address::generate(&env);
+    client.defer_donor_permanently(&donor, &7u32);
+
+    // Redacted from the public profile
+    assert_eq!(
+        client.get_donor_profile(&donor).unwrap().deferral_reason,
+        None
+    );
+
+    assert_eq!(client.get_deferral_reason(&admin, &donor), Some(7));
+    assert_eq!(client.get_deferral_reason(&donor, &donor), Some(7));
+
+    let result = client.try_get_deferral_reason(&Address::generate(&env), &donor);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_temporary_deferral_lapses() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let bank = Address::generate(&env);
+    client.authorize_bank(&bank, &AuthorizationTier::Full);
+    let donor = Address::generate(&env);
+    let until = 1000 + 7 * 86400;
+
+    assert_eq!(client.defer_donor(&bank, &donor, &until, &3u32), until);
+    let event: DonorTemporarilyDeferredEvent =
+        latest_event(&env, topics::DONOR_TEMPORARILY_DEFERRED);
+    assert_eq!(event.until, until);
+    assert_eq!(event.deferred_by, bank);
+
+    let register = || {
+        client.try_register_blood(
+            &bank,
+            &BloodType::APositive,
+            &450u32,
+            &(env.ledger().timestamp() + 30 * 86400),
+            &Some(donor.clone()),
+            &RegistrationOptions::new(&env),
+        )
+    };
+    assert_eq!(register(), Err(Ok(ContractError::DonorDeferred)));
+
+    advance_days(&env, 6);
+    assert_eq!(register(), Err(Ok(ContractError::DonorDeferred)));
+    assert_eq!(
+        client.get_donor_deferral(&admin, &donor),
+        Some(TemporaryDeferral {
+            until,
+            reason_code: 3,
+            deferred_by: bank.clone(),
+        })
+    );
+
+    // Lapses on its own once the end date passes
+    advance_days(&env, 1);
+    assert!(register().is_ok());
+    assert_eq!(client.get_donor_deferral(&donor, &donor), None);
+    assert_eq!(client.get_donor_profile(&donor).unwrap().donation_count, 1);
+}
+
+#[test]
+fn test_overlapping_deferrals_keep_latest_end() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let donor = Address::generate(&env);
+    let long = 1000 + 30 * 86400;
+    let short = 1000 + 7 * 86400;
+
+    client.defer_donor(&admin, &donor, &long, &1u32);
+    // A shorter deferral does not cut the longer one short
+    assert_eq!(client.defer_donor(&admin, &donor, &short, &2u32), long);
+    assert_eq!(count_events(&env, topics::DONOR_TEMPORARILY_DEFERRED), 0);
+    let deferral = client.get_donor_deferral(&donor, &donor).unwrap();
+    assert_eq!((deferral.until, deferral.reason_code), (long, 1));
+
+    // A later one extends it
+    assert_eq!(
+        client.defer_donor(&admin, &donor, &(long + 86400), &2u32),
+        long + 86400
+    );
+    let deferral = client.get_donor_deferral(&donor, &donor).unwrap();
+    assert_eq!((deferral.until, deferral.reason_code), (long + 86400, 2));
+}
+
+#[test]
+fn test_defer_donor_validation_and_confidentiality() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let donor = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(
+        client.try_defer_donor(&stranger, &donor, &2000, &1u32),
+        Err(Ok(ContractError::NotAuthorizedBloodBank))
+    );
+    assert_eq!(
+        client.try_defer_donor(&admin, &donor, &1000, &1u32),
+        Err(Ok(ContractError::InvalidTimestamp))
+    );
+
+    // The deferral is not part of the public donor profile
+    client.defer_donor(&admin, &donor, &2000, &1u32);
+    assert_eq!(client.get_donor_profile(&donor), None);
+    assert_eq!(
+        client.try_get_donor_deferral(&stranger, &donor),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_donor_deferral_not_initialized() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(InventoryContract, ());
+    let client = InventoryContractClient::new(&env, &contract_id);
+
+    let bank = Address::generate(&env);
+    let donor = Address::generate(&env);
+
+    assert_eq!(
+        client.try_defer_donor(&bank, &donor, &2000, &1u32),
+        Err(Ok(ContractError::NotInitialized))
+    );
+    assert_eq!(
+        client.try_get_donor_deferral(&bank, &donor),
+        Err(Ok(ContractError::NotInitialized))
+    );
+}
+
+// ========== Bank Authorization Tests ==========
+
+#[test]
+fn test_initialize_seeds_banks() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(InventoryContract, ());
+    let client = InventoryContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let bank = Address::generate(&env);
+    client.initialize(&admin, &vec![&env, bank.clone()], &0);
+
+    let (event_topics, data) =
+        find_event(&env, topics::BANK_AUTHORIZED).expect("bank_authorized not emitted");
+    assert_eq!(
+        event_topics,
+        (Symbol::new(&env, topics::BANK_AUTHORIZED), bank.clone()).into_val(&env)
+    );
+    assert_eq!(
+        BankAuthorizationChangedEvent::try_from_val(&env, &data)
+            .unwrap()
+            .admin,
+        admin
+    );
+
+    assert!(client.is_bank_authorized(&bank));
+    assert!(client.is_bank_authorized(&admin));
+    assert!(!client.is_bank_authorized(&Address::generate(&env)));
+    assert_eq!(
+        client.list_authorized_banks(&0, &10),
+        vec![&env, bank.clone()]
+    );
+
+    env.ledger().set_timestamp(1000);
+    UnitFixture::new(&env)
+        .expiring_at(1000 + 30 * 86400)
+        .register(&client, &bank);
+}
+
+#[test]
+fn test_list_authorized_banks() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    assert_eq!(client.count_authorized_banks(), 0);
+
+    client.authorize_bank(&first, &AuthorizationTier::Full);
+    client.authorize_bank(&second, &AuthorizationTier::Full);
+    client.authorize_bank(&third, &AuthorizationTier::Full);
+    assert_eq!(
+        client.list_authorized_banks(&0, &10),
+        vec![&env, first.clone(), second.clone(), third.clone()]
+    );
+    assert_eq!(
+        client.list_authorized_banks(&1, &1),
+        vec![&env, second.clone()]
+    );
+    assert_eq!(client.list_authorized_banks(&5, &10), vec![&env]);
+
+    // Revoking removes the bank; re-authorizing lists it once, at the end
+    client.revoke_bank(&second);
+    assert_eq!(
+        client.list_authorized_banks(&0, &10),
+        vec![&env, first.clone(), third.clone()]
+    );
+    client.authorize_bank(&second, &AuthorizationTier::Full);
+    client.authorize_bank(&second, &AuthorizationTier::Full);
+    assert_eq!(
+        client.list_authorized_banks(&0, &10),
+        vec![&env, first.clone(), third.clone(), second.clone()]
+    );
+    assert_eq!(client.count_authorized_banks(), 3);
+
+    // Revoking an unlisted address changes nothing
+    client.revoke_bank(&Address::generate(&env));
+    assert_eq!(client.count_authorized_banks(), 3);
+}
+
+#[test]
+fn test_initialize_too_many_banks() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(InventoryContract, ());
+    let client = InventoryContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let mut banks = vec![&env];
+    for _ in 0..=storage::MAX_INITIAL_BANKS {
+        banks.push_back(Address::generate(&env));
+    }
+
+    let result = client.try_initialize(&admin, &banks, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    // Nothing was stored, so a valid initialization still succeeds
+    client.initialize(&admin, &vec![&env], &0);
+}
+
+#[test]
+fn test_authorize_and_revoke_bank() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank = Address::generate(&env);
+    env.ledger().set_timestamp(500);
+    client.authorize_bank(&bank, &AuthorizationTier::Full);
+
+    let (event_topics, data) =
+        find_event(&env, topics::BANK_AUTHORIZED).expect("bank_authorized not emitted");
+    assert_eq!(
+        event_topics,
+        (Symbol::new(&env, topics::BANK_AUTHORIZED), bank.clone()).into_val(&env)
+    );
+    assert_eq!(
+        BankAuthorizationChangedEvent::try_from_val(&env, &data).unwrap(),
+        BankAuthorizationChangedEvent {
+            bank: bank.clone(),
+            admin: admin.clone(),
+            changed_at: 500,
+        }
+    );
+    assert!(client.is_bank_authorized(&bank));
+
+    env.ledger().set_timestamp(800);
+    client.revoke_bank(&bank);
+
+    let (event_topics, data) =
+        find_event(&env, topics::BANK_REVOKED).expect("bank_revoked not emitted");
+    assert_eq!(
+        event_topics,
+        (Symbol::new(&env, topics::BANK_REVOKED), bank.clone()).into_val(&env)
+    );
+    assert_eq!(
+        BankAuthorizationChangedEvent::try_from_val(&env, &data).unwrap(),
+        BankAuthorizationChangedEvent {
+            bank: bank.clone(),
+            admin,
+            changed_at: 800,
+        }
+    );
+    assert!(!client.is_bank_authorized(&bank));
+
+    env.ledger().set_timestamp(1000);
+    let result = client.try_register_blood(
+        &bank,
+        &BloodType::APositive,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedBloodBank)));
+}
+
+#[test]
+fn test_admin_configuration_changes_emit_events() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let bank = Address::generate(&env);
+
+    client.set_bank_capacity(&bank, &25);
+    let (event_topics, data) =
+        find_event(&env, topics::BANK_CAPACITY_SET).expect("bank_capacity_set not emitted");
+    assert_eq!(
+        event_topics,
+        (Symbol::new(&env, topics::BANK_CAPACITY_SET), bank.clone()).into_val(&env)
+    );
+    assert_eq!(
+        BankCapacityChangedEvent::try_from_val(&env, &data).unwrap(),
+        BankCapacityChangedEvent {
+            bank,
+            max_units: 25,
+            admin: admin.clone(),
+            changed_at: 1000,
+        }
+    );
+
+    client.set_shelf_life(&ProductType::Platelets, &86400, &(7 * 86400));
+    assert_eq!(
+        latest_event::<ShelfLifeChangedEvent>(&env, topics::SHELF_LIFE_SET),
+        ShelfLifeChangedEvent {
+            product: ProductType::Platelets,
+            shelf_life: ShelfLife {
+                min_seconds: 86400,
+                max_seconds: 7 * 86400,
+            },
+            admin: admin.clone(),
+            changed_at: 1000,
+        }
+    );
+
+    client.set_quantity_step(&450);
+    assert_eq!(
+        latest_event::<QuantityStepChangedEvent>(&env, topics::QUANTITY_STEP_SET),
+        QuantityStepChangedEvent {
+            step_ml: 450,
+            admin: admin.clone(),
+            changed_at: 1000,
+        }
+    );
+
+    let request_contract = Address::generate(&env);
+    client.set_request_contract(&request_contract);
+    assert_eq!(
+        latest_event::<RequestContractChangedEvent>(&env, topics::REQUEST_CONTRACT_SET),
+        RequestContractChangedEvent {
+            request_contract,
+            admin,
+            changed_at: 1000,
+        }
+    );
+}
+
+#[test]
+fn test_explicitly_authorized_old_admin_keeps_bank_role() {
+    let (env, admin, client, _contract_id) = setup();
+
+    client.authorize_bank(&admin, &AuthorizationTier::Full);
+    client.propose_admin(&Address::generate(&env));
+    client.accept_admin();
+
+    assert!(client.is_bank_authorized(&admin));
+}
+
+// ========== Quantity Correction Tests ==========
+
+#[test]
+fn test_correct_unit_quantity() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+
+    let justification = String::from_str(&env, "Entered 450 instead of 540");
+    let unit = client.correct_unit_quantity(&admin, &unit_id, &540u32, &justification);
+    assert_eq!(unit.quantity_ml, 540);
+    assert_eq!(client.get_blood_unit(&unit_id).quantity_ml, 540);
+
+    let corrections = client.get_quantity_corrections(&unit_id);
+    assert_eq!(corrections.len(), 1);
+    let correction = corrections.get(0).unwrap();
+    assert_eq!(correction.old_quantity_ml, 450);
+    assert_eq!(correction.new_quantity_ml, 540);
+    assert_eq!(correction.corrected_by, admin);
+    assert_eq!(correction.justification, justification);
+}
+
+#[test]
+fn test_correct_unit_quantity_rejects_reserved_unit() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+    client.reserve_unit(
+        &unit_id,
+        &1u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+
+    let result = client.try_correct_unit_quantity(
+        &admin,
+        &unit_id,
+        &540u32,
+        &String::from_str(&env, "Typo"),
+    );
+    assert_eq!(result, Err(Ok(ContractError::BloodUnitNotAvailable)));
+    assert_eq!(client.get_quantity_corrections(&unit_id).len(), 0);
+}
+
+#[test]
+fn test_correct_unit_quantity_validation() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+    let justification = String::from_str(&env, "Typo");
+
+    let result = client.try_correct_unit_quantity(&admin, &unit_id, &900u32, &justification);
+    assert_eq!(result, Err(Ok(ContractError::InvalidQuantity)));
+
+    let result =
+        client.try_correct_unit_quantity(&admin, &unit_id, &540u32, &String::from_str(&env, ""));
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let other = Address::generate(&env);
+    let result = client.try_correct_unit_quantity(&other, &unit_id, &540u32, &justification);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    assert_eq!(client.get_blood_unit(&unit_id).quantity_ml, 450);
+}
+
+// ========== Donor Consent Tests ==========
+
+#[test]
+fn test_register_named_donor_requires_donor_signature() {
+    let (env, admin, client, contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let expiration = 1000 + 30 * 86400u64;
+    let donor = Address::generate(&env);
+    let donor_id = Some(donor.clone());
+    let args = (
+        admin.clone(),
+        BloodType::APositive,
+        450u32,
+        expiration,
+        donor_id.clone(),
+        RegistrationOptions::new(&env),
+    );
+
+    // Only the bank signs
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "register_blood",
+                args: args.clone().into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_register_blood(
+            &admin,
+            &BloodType::APositive,
+            &450u32,
+            &expiration,
+            &donor_id,
+            &RegistrationOptions::new(&env),
+        );
+    assert!(result.is_err());
+    assert!(client.get_donor_profile(&donor).is_none());
+
+    // Bank and donor both sign
+    let invoke = MockAuthInvoke {
+        contract: &contract_id,
+        fn_name: "register_blood",
+        args: args.into_val(&env),
+        sub_invokes: &[],
+    };
+    let unit_id = client
+        .mock_auths(&[
+            MockAuth {
+                address: &admin,
+                invoke: &invoke,
+            },
+            MockAuth {
+                address: &donor,
+                invoke: &invoke,
+            },
+        ])
+        .register_blood(
+            &admin,
+            &BloodType::APositive,
+            &450u32,
+            &expiration,
+            &donor_id,
+            &RegistrationOptions::new(&env),
+        );
+    assert_eq!(client.get_blood_unit(&unit_id).donor_id, Some(donor));
+}
+
+#[test]
+fn test_register_anonymous_needs_only_bank_signature() {
+    let (env, admin, client, contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let expiration = 1000 + 30 * 86400u64;
+    let donor_id: Option<Address> = None;
+
+    let unit_id = client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "register_blood",
+                args: (
+                    admin.clone(),
+                    BloodType::APositive,
+                    450u32,
+                    expiration,
+                    donor_id.clone(),
+                    RegistrationOptions::new(&env),
+                )
+                    .into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .register_blood(
+            &admin,
+            &BloodType::APositive,
+            &450u32,
+            &expiration,
+            &donor_id,
+            &RegistrationOptions::new(&env),
+        );
+    assert_eq!(client.get_blood_unit(&unit_id).donor_id, None);
+}
+
+// ==================== Range Scan Tests ====================
+
+#[test]
+fn test_get_blood_units_range_skips_pruned_ids() {
+    let (env, admin, client, contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    assert_eq!(client.get_blood_unit_count(), 0);
+
+    for _ in 0..5 {
+        UnitFixture::new(&env).register(&client, &admin);
+    }
+    assert_eq!(client.get_blood_unit_count(), 5);
+
+    // Prune unit 3 to leave a hole in the ID space
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().remove(&DataKey::BloodUnit(3));
+    });
+
+    let mut ids = vec![&env];
+    for unit in client.get_blood_units_range(&1, &4).iter() {
+        ids.push_back(unit.id);
+    }
+    assert_eq!(ids, vec![&env, 1u64, 2, 4]);
+
+    assert_eq!(client.get_blood_units_range(&0, &100).len(), 4);
+    assert_eq!(client.get_blood_units_range(&6, &10).len(), 0);
+}
+
+#[test]
+fn test_get_blood_units_range_caps_limit() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    for _ in 0..(storage::MAX_RANGE_LIMIT + 5) {
+        UnitFixture::new(&env).register(&client, &admin);
+    }
+
+    let units = client.get_blood_units_range(&1, &u32::MAX);
+    assert_eq!(units.len(), storage::MAX_RANGE_LIMIT);
+    assert_eq!(units.get(0).unwrap().id, 1);
+}
+
+// ==================== Migration Tests ====================
+
+/// A unit as an older build stored it, before records carried a schema
+fn legacy_unit(env: &Env, id: u64, bank: &Address, donor: &Address) -> LegacyBloodUnit {
+    let mut metadata = Map::new(env);
+    metadata.set(
+        Symbol::new(env, "lot_number"),
+        String::from_str(env, "LOT-7"),
+    );
+    LegacyBloodUnit {
+        id,
+        blood_type: BloodType::ONegative,
+        product: ProductType::RedCells,
+        quantity_ml: 280,
+        bank_id: bank.clone(),
+        donor_id: Some(donor.clone()),
+        donation_timestamp: 900,
+        expiration_timestamp: 1000 + 30 * 86400,
+        status: BloodStatus::Reserved,
+        metadata,
+        location: Some(String::from_str(env, "FRIDGE-2")),
+        reserved_for: Some(12),
+        directed_to: None,
+        campaign_id: None,
+        phenotype_tags: vec![env, Symbol::new(env, "kell_neg")],
+        autologous: false,
+        pooled_from: Vec::new(env),
+        split_from: Some(1),
+        custodian: bank.clone(),
+    }
+}
+
+#[test]
+fn test_migrate_units_rewrites_legacy_layout() {
+    let (env, admin, client, contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let current = UnitFixture::new(&env).register(&client, &admin);
+    let donor = Address::generate(&env);
+
+    // An older build wrote unit 2 and stored no version
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DataKey::BloodUnit(2),
+            &legacy_unit(&env, 2, &admin, &donor),
+        );
+        storage::set_blood_unit_counter(&env, 2);
+        env.storage().instance().remove(&DataKey::Version);
+    });
+    assert!(client.try_get_blood_unit(&2).is_err());
+    assert_eq!(client.get_version(), None);
+
+    // One ID at a time: the current unit is skipped
+    assert_eq!(client.migrate_units(&admin, &1, &1), 2);
+    assert_eq!(client.get_version(), None);
+    assert_eq!(client.migrate_units(&admin, &2, &1), 0);
+    assert_eq!(client.get_version(), Some(storage::CONTRACT_VERSION));
+
+    let unit = client.get_blood_unit(&2);
+    assert_eq!(unit.schema, BLOOD_UNIT_SCHEMA);
+    assert_eq!(unit.registered_by, admin);
+    assert_eq!(unit.blood_type, BloodType::ONegative);
+    assert_eq!(unit.product, ProductType::RedCells);
+    assert_eq!(unit.quantity_ml, 280);
+    assert_eq!(unit.donor_id, Some(donor));
+    assert_eq!(unit.status, BloodStatus::Reserved);
+    assert_eq!(unit.reserved_for, Some(12));
+    assert_eq!(unit.split_from, Some(1));
+    assert_eq!(unit.location, Some(String::from_str(&env, "FRIDGE-2")));
+    assert_eq!(
+        unit.metadata.get(Symbol::new(&env, "lot_number")),
+        Some(String::from_str(&env, "LOT-7"))
+    );
+    assert_eq!(
+        unit.phenotype_tags,
+        vec![&env, Symbol::new(&env, "kell_neg")]
+    );
+    assert_eq!(client.get_blood_unit(&current).schema, BLOOD_UNIT_SCHEMA);
+
+    // Rerunning the sweep changes nothing
+    assert_eq!(client.migrate_units(&admin, &0, &10), 0);
+    assert_eq!(client.get_blood_unit(&2).quantity_ml, 280);
+    assert_eq!(
+        client.get_units_registered_by(&admin, &admin, &admin, &0, &10),
+        vec![&env, current, 2]
+    );
+}
+
+#[test]
+fn test_migrate_units_admin_only() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let result = client.try_migrate_units(&Address::generate(&env), &1, &10);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+// ==================== Type Breakdown Tests ====================
+
+#[test]
+fn test_type_breakdown_through_reserve_and_use() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let unit1 = UnitFixture::new(&env).register(&client, &admin);
+    let unit2 = UnitFixture::new(&env).register(&client, &admin);
+
+    let breakdown = client.get_type_breakdown(&BloodType::APositive);
+    assert_eq!(breakdown.available_ml, 900);
+    assert_eq!(breakdown.reserved_ml, 0);
+
+    client.reserve_unit(&unit1, &1u64, &BloodType::APositive, &false, &admin, &None);
+    let breakdown = client.get_type_breakdown(&BloodType::APositive);
+    assert_eq!(breakdown.available_ml, 450);
+    assert_eq!(breakdown.reserved_ml, 450);
+
+    client.update_status(&unit1, &BloodStatus::InTransit, &admin, &None);
+    client.mark_delivered(&unit1, &admin, &String::from_str(&env, "Ward 3"));
+    let breakdown = client.get_type_breakdown(&BloodType::APositive);
+    assert_eq!(breakdown.available_ml, 450);
+    assert_eq!(breakdown.reserved_ml, 0);
+
+    client.correct_unit_quantity(&admin, &unit2, &500u32, &String::from_str(&env, "Typo"));
+    let breakdown = client.get_type_breakdown(&BloodType::APositive);
+    assert_eq!(breakdown.available_ml, 500);
+    assert_eq!(breakdown.reserved_ml, 0);
+}
+
+#[test]
+fn test_type_breakdown_through_reserve_and_release() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+
+    client.reserve_unit(
+        &unit_id,
+        &7u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+    client.release_units_for_request(&admin, &7u64);
+
+    let breakdown = client.get_type_breakdown(&BloodType::APositive);
+    assert_eq!(breakdown.available_ml, 450);
+    assert_eq!(breakdown.reserved_ml, 0);
+
+    let all = client.get_all_type_breakdowns();
+    assert_eq!(all.len(), 8);
+    assert_eq!(all.get(BloodType::APositive).unwrap(), breakdown);
+    assert_eq!(all.get(BloodType::ONegative).unwrap().available_ml, 0);
+}
+
+#[test]
+fn test_type_breakdown_expiring_soon() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    UnitFixture::new(&env)
+        .with_quantity(300)
+        .expiring_at(1000 + 2 * 86400)
+        .register(&client, &admin);
+    UnitFixture::new(&env).register(&client, &admin);
+
+    let breakdown = client.get_type_breakdown(&BloodType::APositive);
+    assert_eq!(breakdown.available_ml, 750);
+    assert_eq!(breakdown.expiring_72h_ml, 300);
+
+    // Expired but not yet swept units no longer count as expiring soon
+    // or as available
+    env.ledger().set_timestamp(1000 + 2 * 86400);
+    let breakdown = client.get_type_breakdown(&BloodType::APositive);
+    assert_eq!(breakdown.expiring_72h_ml, 0);
+    assert_eq!(breakdown.available_ml, 450);
+}
+
+// ==================== Overdue Expiration Tests ====================
+
+#[test]
+fn test_count_overdue_expirations() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let first = UnitFixture::new(&env)
+        .expiring_at(1000 + 2 * 86400)
+        .register(&client, &admin);
+    let second = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .expiring_at(1000 + 3 * 86400)
+        .register(&client, &admin);
+    UnitFixture::new(&env).register(&client, &admin);
+
+    assert_eq!(client.count_overdue_expirations(&100), 0);
+    assert_eq!(client.get_oldest_overdue_unit(), None);
+
+    env.ledger().set_timestamp(1000 + 4 * 86400);
+    assert_eq!(client.count_overdue_expirations(&100), 2);
+    assert_eq!(client.get_oldest_overdue_unit(), Some(first));
+
+    // A small scan limit gives a lower bound
+    assert_eq!(client.count_overdue_expirations(&1), 1);
+
+    // Sweeping a unit removes it from the count
+    client.mark_expired(&first, &admin);
+    assert_eq!(client.count_overdue_expirations(&100), 1);
+    assert_eq!(client.get_oldest_overdue_unit(), Some(second));
+}
+
+#[test]
+fn test_expire_units_reports_more_remaining() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let first = UnitFixture::new(&env)
+        .expiring_at(1000 + 86400)
+        .register(&client, &admin);
+    let second = UnitFixture::new(&env)
+        .expiring_at(1000 + 2 * 86400)
+        .register(&client, &admin);
+    let third = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .expiring_at(1000 + 86400)
+        .register(&client, &admin);
+    let fresh = UnitFixture::new(&env).register(&client, &admin);
+
+    env.ledger().set_timestamp(1000 + 3 * 86400);
+
+    assert_eq!(client.expire_units(&2), 2);
+    assert_eq!(
+        latest_event::<ExpirySweepCompletedEvent>(&env, topics::EXPIRY_SWEEP_COMPLETED),
+        ExpirySweepCompletedEvent {
+            expired_count: 2,
+            first_id: first,
+            last_id: second,
+            more_remaining: true,
+            swept_at: 1000 + 3 * 86400,
+        }
+    );
+
+    assert_eq!(client.expire_units(&2), 1);
+    assert_eq!(
+        latest_event::<ExpirySweepCompletedEvent>(&env, topics::EXPIRY_SWEEP_COMPLETED),
+        ExpirySweepCompletedEvent {
+            expired_count: 1,
+            first_id: third,
+            last_id: third,
+            more_remaining: false,
+            swept_at: 1000 + 3 * 86400,
+        }
+    );
+
+    assert_eq!(client.count_overdue_expirations(&100), 0);
+    assert_eq!(client.get_blood_unit(&third).status, BloodStatus::Expired);
+    assert_eq!(client.get_blood_unit(&fresh).status, BloodStatus::Available);
+}
+
+// ==================== Lazy Expiration Tests ====================
+
+#[test]
+fn test_reads_report_expired_before_sweep() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let lapsing = UnitFixture::new(&env)
+        .expiring_at(1000 + 86400)
+        .register(&client, &admin);
+    let fresh = UnitFixture::new(&env).register(&client, &admin);
+
+    env.ledger().set_timestamp(1000 + 86400);
+
+    assert_eq!(client.get_blood_unit(&lapsing).status, BloodStatus::Expired);
+    assert_eq!(client.get_blood_unit(&fresh).status, BloodStatus::Available);
+    let range = client.get_blood_units_range(&1, &10);
+    assert_eq!(range.get(0).unwrap().status, BloodStatus::Expired);
+    assert_eq!(range.get(1).unwrap().status, BloodStatus::Available);
+    let summaries = client.get_unit_summaries(&vec![&env, lapsing, fresh]);
+    assert_eq!(summaries.get(0).unwrap().status, BloodStatus::Expired);
+    assert_eq!(
+        client
+            .get_bank_unit_summaries(&admin, &admin)
+            .get(0)
+            .unwrap()
+            .status,
+        BloodStatus::Expired
+    );
+
+    // Availability queries leave the unit out
+    let available = client.get_unit_summaries_by_type(
+        &BloodType::APositive,
+        &Some(BloodStatus::Available),
+        &10,
+    );
+    assert_eq!(available.len(), 1);
+    assert_eq!(available.get(0).unwrap().id, fresh);
+    assert_eq!(
+        client.get_next_unit_fifo(&BloodType::APositive),
+        Some(fresh)
+    );
+
+    // Storage is untouched until the sweep catches up
+    assert_eq!(client.count_overdue_expirations(&100), 1);
+    assert_eq!(client.get_status_change_count(&lapsing), 0);
+    assert_eq!(client.expire_units(&10), 1);
+    assert_eq!(client.count_overdue_expirations(&100), 0);
+    assert_eq!(client.get_status_change_count(&lapsing), 1);
+    assert_eq!(client.get_blood_unit(&lapsing).status, BloodStatus::Expired);
+}
+
+#[test]
+fn test_terminal_status_not_overridden_on_read() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let unit_id = UnitFixture::new(&env)
+        .expiring_at(1000 + 86400)
+        .register(&client, &admin);
+    client.discard_blood_unit(
+        &unit_id,
+        &admin,
+        &DiscardReason::BagDamage,
+        &String::from_str(&env, "Bag seal broken"),
+    );
+
+    env.ledger().set_timestamp(1000 + 2 * 86400);
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Discarded
+    );
+}
+
+// ==================== Expiring Stock Tests ====================
+
+#[test]
+fn test_expiring_units_across_banks() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let bank_a = Address::generate(&env);
+    let bank_b = Address::generate(&env);
+    client.authorize_bank(&bank_a, &AuthorizationTier::Full);
+    client.authorize_bank(&bank_b, &AuthorizationTier::Full);
+
+    env.ledger().set_timestamp(1000);
+    let day = 86400u64;
+    let a_late = UnitFixture::new(&env)
+        .expiring_at(1000 + 3 * day)
+        .register(&client, &bank_a);
+    let a_soon = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_quantity(300)
+        .expiring_at(1000 + 2 * day)
+        .register(&client, &bank_a);
+    let b_soon = UnitFixture::new(&env)
+        .with_quantity(400)
+        .expiring_at(1000 + 2 * day)
+        .register(&client, &bank_b);
+    UnitFixture::new(&env)
+        .expiring_at(1000 + 20 * day)
+        .register(&client, &bank_b);
+    UnitFixture::new(&env).register(&client, &admin);
+
+    // Soonest expiration first, across blood types
+    assert_eq!(
+        client.get_expiring_units_by_bank(&bank_a, &(4 * day), &10),
+        vec![&env, a_soon, a_late]
+    );
+    assert_eq!(
+        client.get_expiring_units_by_bank(&bank_a, &(4 * day), &1),
+        vec![&env, a_soon]
+    );
+    assert_eq!(
+        client.get_expiring_units_by_bank(&bank_b, &(4 * day), &10),
+        vec![&env, b_soon]
+    );
+
+    let per_bank = client.get_expiring_ml_per_bank(&(4 * day));
+    assert_eq!(per_bank.len(), 2);
+    assert_eq!(per_bank.get(bank_a.clone()), Some(750));
+    assert_eq!(per_bank.get(bank_b.clone()), Some(400));
+    assert_eq!(per_bank.get(admin), None);
+
+    // Reserved and already expired units drop out
+    client.reserve_unit(
+        &a_soon,
+        &1u64,
+        &BloodType::ONegative,
+        &false,
+        &bank_a,
+        &None,
+    );
+    env.ledger().set_timestamp(1000 + 2 * day);
+    let per_bank = client.get_expiring_ml_per_bank(&(4 * day));
+    assert_eq!(per_bank.get(bank_a), Some(450));
+    assert_eq!(per_bank.get(bank_b), None);
+}
+
+// ==================== Summary View Tests ====================
+
+fn assert_summary_matches(summary: &BloodUnitSummary, blood_unit: &BloodUnit) {
+    assert_eq!(summary.id, blood_unit.id);
+    assert_eq!(summary.blood_type, blood_unit.blood_type);
+    assert_eq!(summary.quantity_ml, blood_unit.quantity_ml);
+    assert_eq!(summary.bank_id, blood_unit.bank_id);
+    assert_eq!(summary.status, blood_unit.status);
+    assert_eq!(
+        summary.expiration_timestamp,
+        blood_unit.expiration_timestamp
+    );
+}
+
+#[test]
+fn test_unit_summaries_match_full_units() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+    let donated = client.register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &Some(donor),
+        &RegistrationOptions::new(&env),
+    );
+    let reserved = UnitFixture::new(&env).register(&client, &admin);
+    let other_type = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_quantity(300)
+        .expiring_at(1000 + 10 * 86400)
+        .register(&client, &admin);
+    client.reserve_unit(
+        &reserved,
+        &1u64,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+
+    let summaries = client.get_unit_summaries(&vec![&env, donated, other_type, 99]);
+    assert_eq!(summaries.len(), 2);
+    for summary in summaries.iter() {
+        assert_summary_matches(&summary, &client.get_blood_unit(&summary.id));
+    }
+
+    let bank_summaries = client.get_bank_unit_summaries(&admin, &admin);
+    assert_eq!(bank_summaries.len(), 3);
+    for summary in bank_summaries.iter() {
+        assert_summary_matches(&summary, &client.get_blood_unit(&summary.id));
+    }
+
+    let a_positive = client.get_unit_summaries_by_type(&BloodType::APositive, &None, &10);
+    assert_eq!(a_positive.len(), 2);
+    let available = client.get_unit_summaries_by_type(
+        &BloodType::APositive,
+        &Some(BloodStatus::Available),
+        &10,
+    );
+    assert_eq!(available.len(), 1);
+    assert_eq!(available.get(0).unwrap().id, donated);
+    assert_eq!(
+        client
+            .get_unit_summaries_by_type(&BloodType::APositive, &None, &1)
+            .len(),
+        1
+    );
+}
+
+// ==================== Timestamp Overflow Tests ====================
+
+#[test]
+fn test_register_blood_extreme_expiration() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000);
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &u64::MAX,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidExpiration)));
+
+    // Near the end of time the shelf-life bounds would overflow
+    env.ledger().set_timestamp(u64::MAX - 3600);
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &u64::MAX,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidTimestamp)));
+}
+
+// ==================== ID Counter Tests ====================
+
+#[test]
+fn test_initialize_with_id_offset() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(InventoryContract, ());
+    let client = InventoryContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &vec![&env], &1000);
+
+    assert_eq!(UnitFixture::new(&env).register(&client, &admin), 1001);
+    assert_eq!(UnitFixture::new(&env).register(&client, &admin), 1002);
+    assert_eq!(client.get_blood_unit_count(), 1002);
+    assert_eq!(client.get_blood_units_range(&1001, &10).len(), 2);
+}
+
+#[test]
+fn test_register_blood_counter_overflow() {
+    let (env, admin, client, contract_id) = setup();
+
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::BloodUnitCounter, &(u64::MAX - 1));
+    });
+
+    assert_eq!(UnitFixture::new(&env).register(&client, &admin), u64::MAX);
+
+    let current_time = env.ledger().timestamp();
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &(current_time + 30 * 86400),
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::CounterOverflow)));
+    assert_eq!(client.get_blood_unit_count(), u64::MAX);
+}
+
+// ==================== Registration Rate Limit Tests ====================
+
+#[test]
+fn test_registration_rate_limit_resets_each_day() {
+    let (env, admin, client, _contract_id) = setup();
+    let day = 86400u64;
+    env.ledger().set_timestamp(10 * day + 1000);
+
+    let other_bank = Address::generate(&env);
+    client.authorize_bank(&other_bank, &AuthorizationTier::Full);
+
+    assert_eq!(client.get_registration_rate_limit(), 0);
+    client.set_registration_rate_limit(&2);
+
+    UnitFixture::new(&env).register_many(&client, &admin, 2);
+    assert_eq!(client.get_registrations_today(&admin), 2);
+
+    let expiration = env.ledger().timestamp() + 30 * day;
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::RateLimitExceeded)));
+
+    // Other banks have their own allowance
+    UnitFixture::new(&env).register(&client, &other_bank);
+
+    // Last second of the day, then the first of the next
+    env.ledger().set_timestamp(11 * day - 1);
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::RateLimitExceeded)));
+
+    env.ledger().set_timestamp(11 * day);
+    assert_eq!(client.get_registrations_today(&admin), 0);
+    UnitFixture::new(&env).register_many(&client, &admin, 2);
+
+    // Removing the limit lifts the cap
+    client.set_registration_rate_limit(&0);
+    UnitFixture::new(&env).register(&client, &admin);
+    assert_eq!(client.get_registrations_today(&admin), 3);
+}
+
+// ==================== Void Registration Tests ====================
+
+#[test]
+fn test_void_registration_within_window() {
+    let (env, admin, client, contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let donor = Address::generate(&env);
+    let kept = UnitFixture::new(&env).register(&client, &admin);
+    let unit_id = UnitFixture::new(&env)
+        .with_donor(&donor)
+        .register(&client, &admin);
+    assert_eq!(client.get_bank_unit_count(&admin), 2);
+
+    env.ledger().set_timestamp(1000 + 30 * 60);
+    let voided = client.void_registration(&admin, &unit_id);
+    assert_eq!(voided.status, BloodStatus::Voided);
+
+    let event: RegistrationVoidedEvent = latest_event(&env, topics::REGISTRATION_VOIDED);
+    assert_eq!(event.blood_unit_id, unit_id);
+    assert_eq!(event.registered_at, 1000);
+    assert_eq!(event.voided_at, 1000 + 30 * 60);
+
+    // Gone from every index, total and counter
+    env.as_contract(&contract_id, || {
+        let remaining = Vec::from_array(&env, [kept]);
+        assert_eq!(storage::get_bank_units(&env, &admin), remaining);
+        assert_eq!(
+            storage::get_blood_type_units(&env, BloodType::APositive),
+            remaining
+        );
+        assert_eq!(storage::get_operator_units(&env, &admin, &admin), remaining);
+        assert!(storage::get_donor_units(&env, &donor).is_empty());
+        assert_eq!(
+            storage::get_available_by_expiry(&env, BloodType::APositive).len(),
+            1
+        );
+    });
+    assert_eq!(
+        client
+            .get_type_breakdown(&BloodType::APositive)
+            .available_ml,
+        450
+    );
+    assert_eq!(client.get_bank_unit_count(&admin), 1);
+    assert_eq!(client.get_registrations_today(&admin), 1);
+    assert_eq!(client.get_donor_profile(&donor).unwrap().donation_count, 0);
+    assert!(client.get_discard_breakdown(&0).is_empty());
+    assert_eq!(client.get_status_change_count(&unit_id), 0);
+
+    // A voided unit is terminal
+    assert_eq!(
+        client.try_void_registration(&admin, &unit_id),
+        Err(Ok(ContractError::UnitAlreadyModified))
+    );
+}
+
+#[test]
+fn test_void_registration_releases_external_ref() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let external_ref = String::from_str(&env, "LIS-0001");
+    let wrong = UnitFixture::new(&env)
+        .with_external_ref("LIS-0001")
+        .register(&client, &admin);
+    client.void_registration(&admin, &wrong);
+    assert_eq!(client.get_unit_by_external_ref(&admin, &external_ref), None);
+
+    // Re-registering the bag creates a new unit rather than a retry
+    let corrected = UnitFixture::new(&env)
+        .with_quantity(400)
+        .with_external_ref("LIS-0001")
+        .register(&client, &admin);
+    assert_ne!(corrected, wrong);
+    assert_eq!(
+        client.get_blood_unit(&corrected).status,
+        BloodStatus::Available
+    );
+    assert_eq!(
+        client.get_unit_by_external_ref(&admin, &external_ref),
+        Some(corrected)
+    );
+}
+
+#[test]
+fn test_void_registration_outside_window() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+    assert_eq!(
+        client.get_void_window(),
+        storage::DEFAULT_VOID_WINDOW_SECONDS
+    );
+
+    env.ledger()
+        .set_timestamp(1000 + storage::DEFAULT_VOID_WINDOW_SECONDS + 1);
+    assert_eq!(
+        client.try_void_registration(&admin, &unit_id),
+        Err(Ok(ContractError::VoidWindowClosed))
+    );
+
+    // The normal discard path still applies
+    client.discard_blood_unit(
+        &unit_id,
+        &admin,
+        &DiscardReason::ClericalError,
+        &String::from_str(&env, "Duplicate bag"),
+    );
+    assert_eq!(
+        client
+            .get_discard_breakdown(&0)
+            .get(DiscardReason::ClericalError),
+        Some(1)
+    );
+
+    // A wider window lets later mistakes be voided
+    client.set_void_window(&(4 * 3600));
+    let later = UnitFixture::new(&env).register(&client, &admin);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 2 * 3600);
+    assert_eq!(
+        client.void_registration(&admin, &later).status,
+        BloodStatus::Voided
+    );
+}
+
+#[test]
+fn test_void_registration_requires_untouched_unit() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let located = UnitFixture::new(&env).register(&client, &admin);
+    client.set_unit_location(&admin, &located, &String::from_str(&env, "Fridge 2"));
+    assert_eq!(
+        client.try_void_registration(&admin, &located),
+        Err(Ok(ContractError::UnitAlreadyModified))
+    );
+
+    let reserved = UnitFixture::new(&env).register(&client, &admin);
+    client.update_status(&reserved, &BloodStatus::Reserved, &admin, &None);
+    assert_eq!(
+        client.try_void_registration(&admin, &reserved),
+        Err(Ok(ContractError::UnitAlreadyModified))
+    );
+
+    let other_bank = Address::generate(&env);
+    client.authorize_bank(&other_bank, &AuthorizationTier::Full);
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+    assert_eq!(
+        client.try_void_registration(&other_bank, &unit_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+// ==================== Loan Tests ====================
+
+#[test]
+fn test_lend_unit_moves_unit_and_records_loan() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let borrower = Address::generate(&env);
+    client.authorize_bank(&borrower, &AuthorizationTier::Full);
+    let unit_id = UnitFixture::new(&env)
+        .expiring_in_days(30)
+        .register(&client, &admin);
+
+    assert_eq!(
+        client.try_lend_unit(&admin, &admin, &unit_id, &5000),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_lend_unit(&admin, &borrower, &unit_id, &1000),
+        Err(Ok(ContractError::InvalidTimestamp))
+    );
+
+    let loan_id = client.lend_unit(&admin, &borrower, &unit_id, &5000);
+    let event: UnitLentEvent = latest_event(&env, topics::UNIT_LENT);
+    assert_eq!(event.loan_id, loan_id);
+    assert_eq!(event.quantity_ml, 450);
+
+    // The unit now sits with the borrower
+    let blood_unit = client.get_blood_unit(&unit_id);
+    assert_eq!(blood_unit.bank_id, borrower);
+    assert_eq!(blood_unit.custodian, borrower);
+    assert_eq!(blood_unit.status, BloodStatus::Available);
+    assert!(client.get_bank_units(&admin, &admin).is_empty());
+    assert_eq!(
+        client.get_bank_units(&admin, &borrower),
+        vec![&env, unit_id]
+    );
+    assert_eq!(client.get_bank_unit_count(&admin), 0);
+    assert_eq!(client.get_bank_unit_count(&borrower), 1);
+
+    let loan = client.get_loan(&loan_id);
+    assert_eq!(loan.lender, admin);
+    assert_eq!(loan.borrower, borrower);
+    assert_eq!(loan.blood_type, BloodType::APositive);
+    assert_eq!(loan.due_by, 5000);
+    assert_eq!(loan.settled_at, None);
+    assert_eq!(client.get_outstanding_loans(&admin, &admin).len(), 1);
+    assert_eq!(client.get_outstanding_loans(&admin, &borrower).len(), 1);
+
+    // The lender no longer holds it
+    assert_eq!(
+        client.try_lend_unit(&admin, &borrower, &unit_id, &5000),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_settle_loan_with_matching_replacement() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let borrower = Address::generate(&env);
+    client.authorize_bank(&borrower, &AuthorizationTier::Full);
+    let unit_id = UnitFixture::new(&env)
+        .expiring_in_days(30)
+        .register(&client, &admin);
+    let loan_id = client.lend_unit(&admin, &borrower, &unit_id, &5000);
+
+    let wrong_type = UnitFixture::new(&env)
+        .with_blood_type(BloodType::BPositive)
+        .expiring_in_days(30)
+        .register(&client, &borrower);
+    let too_small = UnitFixture::new(&env)
+        .with_quantity(300)
+        .expiring_in_days(30)
+        .register(&client, &borrower);
+    let replacement = UnitFixture::new(&env)
+        .expiring_in_days(30)
+        .register(&client, &borrower);
+
+    assert_eq!(
+        client.try_settle_loan(&admin, &loan_id, &replacement),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_settle_loan(&borrower, &loan_id, &wrong_type),
+        Err(Ok(ContractError::ReplacementMismatch))
+    );
+    assert_eq!(
+        client.try_settle_loan(&borrower, &loan_id, &too_small),
+        Err(Ok(ContractError::ReplacementMismatch))
+    );
+
+    // A borrower limited to registering cannot move stock out
+    client.set_bank_tier(&borrower, &AuthorizationTier::RegisterOnly);
+    assert_eq!(
+        client.try_settle_loan(&borrower, &loan_id, &replacement),
+        Err(Ok(ContractError::RegisterOnlyBank))
+    );
+    client.set_bank_tier(&borrower, &AuthorizationTier::Full);
+
+    env.ledger().set_timestamp(2000);
+    let loan = client.settle_loan(&borrower, &loan_id, &replacement);
+    assert_eq!(loan.settled_at, Some(2000));
+    assert_eq!(loan.replacement_unit_id, Some(replacement));
+
+    let event: LoanSettledEvent = latest_event(&env, topics::LOAN_SETTLED);
+    assert_eq!(event.loan_id, loan_id);
+    assert_eq!(event.replacement_unit_id, replacement);
+
+    assert_eq!(client.get_blood_unit(&replacement).bank_id, admin);
+    assert_eq!(client.get_bank_unit_count(&admin), 1);
+    assert!(client.get_outstanding_loans(&admin, &admin).is_empty());
+    assert!(client.get_outstanding_loans(&admin, &borrower).is_empty());
+
+    assert_eq!(
+        client.try_settle_loan(&borrower, &loan_id, &replacement),
+        Err(Ok(ContractError::LoanAlreadySettled))
+    );
+}
+
+#[test]
+fn test_overdue_loans_flagged_and_reported() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let borrower = Address::generate(&env);
+    client.authorize_bank(&borrower, &AuthorizationTier::Full);
+    let first = UnitFixture::new(&env)
+        .expiring_in_days(30)
+        .register(&client, &admin);
+    let second = UnitFixture::new(&env)
+        .expiring_in_days(30)
+        .register(&client, &admin);
+    let early = client.lend_unit(&admin, &borrower, &first, &5000);
+    let late = client.lend_unit(&admin, &borrower, &second, &50_000);
+
+    assert!(client.check_overdue_loans(&borrower).is_empty());
+    assert_eq!(count_events(&env, topics::LOAN_OVERDUE), 0);
+
+    env.ledger().set_timestamp(6000);
+    let loans = client.get_outstanding_loans(&borrower, &borrower);
+    assert_eq!(loans.len(), 2);
+    assert_eq!(loans.get(0).unwrap().loan.id, early);
+    assert!(loans.get(0).unwrap().overdue);
+    assert_eq!(loans.get(1).unwrap().loan.id, late);
+    assert!(!loans.get(1).unwrap().overdue);
+
+    assert_eq!(client.check_overdue_loans(&admin), vec![&env, early]);
+    let event: LoanOverdueEvent = latest_event(&env, topics::LOAN_OVERDUE);
+    assert_eq!(event.loan_id, early);
+    assert_eq!(event.due_by, 5000);
+    assert_eq!(event.checked_at, 6000);
+
+    // Outsiders cannot list a bank's loans
+    assert_eq!(
+        client.try_get_outstanding_loans(&Address::generate(&env), &borrower),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+// ==================== Quantity Step Tests ====================
+
+#[test]
+fn test_quantity_step_disabled_by_default() {
+    let (env, admin, client, _contract_id) = setup();
+    let expiration = env.ledger().timestamp() + 30 * 86400;
+
+    assert_eq!(client.get_quantity_step(), 0);
+    client.register_blood(
+        &admin,
+        &BloodType::APositive,
+        &437u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+}
+
+#[test]
+fn test_quantity_step_enforced_on_registration() {
+    let (env, admin, client, _contract_id) = setup();
+    let expiration = env.ledger().timestamp() + 30 * 86400;
+
+    client.set_quantity_step(&150);
+    assert_eq!(client.get_quantity_step(), 150);
+
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &449u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::QuantityNotAligned)));
+    let result = client.try_register_blood(
+        &admin,
+        &BloodType::APositive,
+        &451u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::QuantityNotAligned)));
+
+    client.register_blood(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+    client.register_blood(
+        &admin,
+        &BloodType::APositive,
+        &600u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+
+    // Disabling the step lets odd volumes through again
+    client.set_quantity_step(&0);
+    client.register_blood(
+        &admin,
+        &BloodType::APositive,
+        &437u32,
+        &expiration,
+        &None,
+        &RegistrationOptions::new(&env),
+    );
+}
+
+// ==================== Index Invariant Tests ====================
+
+fn assert_no_duplicates(ids: &soroban_sdk::Vec<u64>) {
+    for (i, id) in ids.iter().enumerate() {
+        assert_eq!(
+            ids.first_index_of(id),
+            Some(i as u32),
+            "duplicate id {}",
+            id
+        );
+    }
+}
+
+#[test]
+fn test_index_insertion_is_idempotent() {
+    let (env, admin, client, contract_id) = setup();
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+    let location = String::from_str(&env, "Fridge A");
+
+    env.as_contract(&contract_id, || {
+        let blood_unit = storage::get_blood_unit(&env, unit_id).unwrap();
+        storage::add_to_blood_type_index(&env, &blood_unit);
+        storage::add_to_bank_index(&env, &blood_unit);
+        storage::add_to_status_index(&env, &blood_unit);
+        storage::add_to_location_index(&env, &admin, &location, unit_id);
+        storage::add_to_location_index(&env, &admin, &location, unit_id);
+        storage::add_to_reservation_index(&env, 7, unit_id);
+        storage::add_to_reservation_index(&env, 7, unit_id);
+
+        assert_eq!(
+            storage::get_blood_type_units(&env, BloodType::APositive),
+            vec![&env, unit_id]
+        );
+        assert_eq!(storage::get_bank_units(&env, &admin), vec![&env, unit_id]);
+    });
+
+    assert_eq!(
+        client.get_units_by_location(&admin, &location),
+        vec![&env, unit_id]
+    );
+    assert_eq!(client.get_reserved_units(&7), vec![&env, unit_id]);
+}
+
+#[test]
+fn test_indexes_stay_duplicate_free_after_random_operations() {
+    let (env, admin, client, _contract_id) = setup();
+    let locations = [
+        String::from_str(&env, "Fridge A"),
+        String::from_str(&env, "Fridge B"),
+    ];
+
+    // Deterministic pseudo-random sequence (LCG)
+    let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut next = |bound: u64| {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) % bound
+    };
+
+    let mut registered: u64 = 0;
+    for _ in 0..60 {
+        let op = next(5);
+        let target = if registered == 0 {
+            0
+        } else {
+            next(registered) + 1
+        };
+        match op {
+            0 | 1 => {
+                UnitFixture::new(&env).register(&client, &admin);
+                registered += 1;
+            }
+            2 => {
+                let request_id = next(4) + 1;
+                let _ = client.try_reserve_unit(
+                    &target,
+                    &request_id,
+                    &BloodType::APositive,
+                    &false,
+                    &admin,
+                    &None,
+                );
+            }
+            3 => {
+                let _ = client.try_release_units_for_request(&admin, &(next(4) + 1));
+            }
+            _ => {
+                let location = &locations[next(2) as usize];
+                let _ = client.try_set_unit_location(&admin, &target, location);
+            }
+        }
+    }
+
+    assert_no_duplicates(&client.get_bank_units(&admin, &admin));
+    for request_id in 1..=4u64 {
+        let reserved = client.get_reserved_units(&request_id);
+        assert_no_duplicates(&reserved);
+        for id in reserved.iter() {
+            assert_eq!(client.get_blood_unit(&id).status, BloodStatus::Reserved);
+        }
+    }
+    for location in locations.iter() {
+        assert_no_duplicates(&client.get_units_by_location(&admin, location));
+    }
+}
+
+// ==================== Paged Index Tests ====================
+
+/// Sequential IDs `from..to`
+fn id_range(env: &Env, from: u64, to: u64) -> soroban_sdk::Vec<u64> {
+    let mut ids = vec![env];
+    for id in from..to {
+        ids.push_back(id);
+    }
+    ids
+}
+
+#[test]
+fn test_blood_type_index_rolls_over_pages() {
+    let (env, admin, client, contract_id) = setup();
+    let first = UnitFixture::new(&env).register(&client, &admin);
+    let index = storage::PagedIndex::BloodType(BloodType::APositive);
+
+    env.as_contract(&contract_id, || {
+        // Synthetic IDs well above the counter, so later registrations don't collide
+        let mut blood_unit = storage::get_blood_unit(&env, first).unwrap();
+        for id in 1001..1250u64 {
+            blood_unit.id = id;
+            storage::add_to_blood_type_index(&env, &blood_unit);
+        }
+
+        assert_eq!(storage::get_index_page_count(&env, index), 3);
+        assert_eq!(storage::get_index_page(&env, index, 0).len(), 100);
+        assert_eq!(storage::get_index_page(&env, index, 2).len(), 50);
+        assert_eq!(storage::get_index_slot(&env, index, first), Some(0));
+        assert_eq!(storage::get_index_slot(&env, index, 1200), Some(2));
+
+        let mut expected = vec![&env, first];
+        expected.append(&id_range(&env, 1001, 1250));
+        assert_eq!(
+            storage::get_blood_type_units(&env, BloodType::APositive),
+            expected
+        );
+    });
+
+    // Units registered after the synthetic entries land on the last page
+    let next = UnitFixture::new(&env).register(&client, &admin);
+    env.as_contract(&contract_id, || {
+        assert_eq!(storage::get_index_slot(&env, index, next), Some(2));
+        assert_eq!(storage::get_index_page(&env, index, 2).last(), Some(next));
+    });
+}
+
+#[test]
+fn test_blood_type_index_removes_from_one_page() {
+    let (env, admin, client, contract_id) = setup();
+    let first = UnitFixture::new(&env).register(&client, &admin);
+    let index = storage::PagedIndex::BloodType(BloodType::APositive);
+
+    env.as_contract(&contract_id, || {
+        // Hundreds of index writes in one frame
+        env.cost_estimate().budget().reset_unlimited();
+        let mut blood_unit = storage::get_blood_unit(&env, first).unwrap();
+        for id in 1001..1250u64 {
+            blood_unit.id = id;
+            storage::add_to_blood_type_index(&env, &blood_unit);
+        }
+        let mut remove = |id: u64| {
+            blood_unit.id = id;
+            storage::remove_from_registration_indexes(&env, &blood_unit);
+        };
+
+        // Only the page holding the ID changes
+        remove(1150);
+        assert_eq!(storage::get_index_slot(&env, index, 1150), None);
+        assert_eq!(storage::get_index_page(&env, index, 0).len(), 100);
+        assert_eq!(storage::get_index_page(&env, index, 1).len(), 99);
+        assert_eq!(storage::get_index_page(&env, index, 2).len(), 50);
+        assert_eq!(storage::get_index_slot(&env, index, 1200), Some(2));
+
+        // An emptied middle page reads as empty; later pages keep their place
+        (1100..1200u64).for_each(&mut remove);
+        assert_eq!(storage::get_index_page_count(&env, index), 3);
+        assert!(storage::get_index_page(&env, index, 1).is_empty());
+        assert_eq!(storage::get_index_slot(&env, index, 1249), Some(2));
+
+        // Emptying the last page drops it and the empty page before it
+        (1200..1250u64).for_each(&mut remove);
+        assert_eq!(storage::get_index_page_count(&env, index), 1);
+
+        // A repeated insert is skipped wherever the ID sits
+        for id in [1300, 1300, 1050] {
+            blood_unit.id = id;
+            storage::add_to_blood_type_index(&env, &blood_unit);
+        }
+        assert_eq!(storage::get_index_page(&env, index, 1), vec![&env, 1300]);
+
+        let mut expected = vec![&env, first];
+        expected.append(&id_range(&env, 1001, 1100));
+        expected.push_back(1300);
+        assert_eq!(
+            storage::get_blood_type_units(&env, BloodType::APositive),
+            expected
+        );
+    });
+}
+
+#[test]
+fn test_legacy_blood_type_index_migrates_lazily() {
+    let (env, admin, client, contract_id) = setup();
+    let legacy_key = DataKey::BloodTypeIndex(BloodType::APositive);
+
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&legacy_key, &id_range(&env, 1001, 1151));
+    });
+
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+
+    env.as_contract(&contract_id, || {
+        let index = storage::PagedIndex::BloodType(BloodType::APositive);
+        assert!(!env.storage().persistent().has(&legacy_key));
+        assert_eq!(storage::get_index_page_count(&env, index), 2);
+
+        let units = storage::get_blood_type_units(&env, BloodType::APositive);
+        assert_eq!(units.len(), 151);
+        assert_eq!(units.get(0), Some(1001));
+        assert_eq!(units.get(150), Some(unit_id));
+    });
+}
+
+#[test]
+fn test_blood_type_index_insert_cost_independent_of_size() {
+    let (env, admin, client, contract_id) = setup();
+    let first = UnitFixture::new(&env).register(&client, &admin);
+
+    env.as_contract(&contract_id, || {
+        // Small index: one page of 50; large index: 20 full pages plus 50
+        env.storage().persistent().set(
+            &DataKey::BloodTypeIndex(BloodType::BPositive),
+            &id_range(&env, 1, 51),
+        );
+        env.storage().persistent().set(
+            &DataKey::BloodTypeIndex(BloodType::ONegative),
+            &id_range(&env, 1, 2051),
+        );
+
+        let template = storage::get_blood_unit(&env, first).unwrap();
+        let insert_cost = |blood_type: BloodType, id: u64| {
+            let mut blood_unit = template.clone();
+            blood_unit.blood_type = blood_type;
+            blood_unit.id = id;
+            let mut budget = env.cost_estimate().budget();
+            budget.reset_unlimited();
+            storage::add_to_blood_type_index(&env, &blood_unit);
+            budget.cpu_instruction_cost()
+        };
+
+        // The first insert pays for the one-off migration
+        insert_cost(BloodType::BPositive, 100_000);
+        insert_cost(BloodType::ONegative, 100_000);
+
+        let small = insert_cost(BloodType::BPositive, 100_001);
+        let large = insert_cost(BloodType::ONegative, 100_001);
+        assert!(large < small * 2, "small: {}, large: {}", small, large);
+    });
+}
+
+// ==================== Product Shelf Life Tests ====================
+
+#[test]
+fn test_component_shelf_life_boundaries() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1_000_000);
+    let now = env.ledger().timestamp();
+    let day = 86400u64;
+
+    for (product, max_days) in [
+        (ProductType::WholeBlood, 42u64),
+        (ProductType::RedCells, 42),
+        (ProductType::Platelets, 5),
+        (ProductType::Plasma, 365),
+    ] {
+        let shelf_life = client.get_shelf_life(&product);
+        assert_eq!(shelf_life.min_seconds, day);
+        assert_eq!(shelf_life.max_seconds, max_days * day);
+
+        let register = |expiration: u64| {
+            client.try_register_component(
+                &admin,
+                &BloodType::OPositive,
+                &product,
+                &250u32,
+                &expiration,
+                &None,
+                &vec![&env],
+            )
+        };
+
+        // Both bounds are inclusive
+        let unit_id = register(now + day).unwrap().unwrap();
+        assert_eq!(client.get_blood_unit(&unit_id).product, product);
+        assert!(register(now + max_days * day).is_ok());
+
+        assert_eq!(
+            register(now + day - 1),
+            Err(Ok(ContractError::InvalidExpiration))
+        );
+        assert_eq!(
+            register(now + max_days * day + 1),
+            Err(Ok(ContractError::InvalidExpiration))
+        );
+    }
+
+    // register_blood keeps whole blood bounds
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+    assert_eq!(
+        client.get_blood_unit(&unit_id).product,
+        ProductType::WholeBlood
+    );
+}
+
+#[test]
+fn test_set_shelf_life() {
+    let (env, admin, client, _contract_id) = setup();
+    let now = env.ledger().timestamp();
+    let hour = 3600u64;
+
+    // Pathogen-reduced platelets keep 7 days, but need 12 hours left
+    client.set_shelf_life(&ProductType::Platelets, &(12 * hour), &(7 * 24 * hour));
+
+    let register = |expiration: u64| {
+        client.try_register_component(
+            &admin,
+            &BloodType::APositive,
+            &ProductType::Platelets,
+            &250u32,
+            &expiration,
+            &None,
+            &vec![&env],
+        )
+    };
+    assert!(register(now + 12 * hour).is_ok());
+    assert!(register(now + 7 * 24 * hour).is_ok());
+    assert_eq!(
+        register(now + 12 * hour - 1),
+        Err(Ok(ContractError::InvalidExpiration))
+    );
+    assert_eq!(
+        register(now + 7 * 24 * hour + 1),
+        Err(Ok(ContractError::InvalidExpiration))
+    );
+
+    // Other products are unaffected
+    assert_eq!(
+        client.get_shelf_life(&ProductType::Plasma).max_seconds,
+        365 * 24 * hour
+    );
+
+    let result = client.try_set_shelf_life(&ProductType::Plasma, &(2 * hour), &hour);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+    let result = client.try_set_shelf_life(&ProductType::Plasma, &0, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+}
+
+// ==================== Low Stock Alert Tests ====================
+
+#[test]
+fn test_low_stock_alert_cooldown() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    // Below 1000 ml is low; no step override
+    client.set_low_stock_alert(&BloodType::APositive, &1000, &3600, &0);
+    let ids = UnitFixture::new(&env).register_many(&client, &admin, 4);
+
+    let mut alerts = 0;
+    for (request_id, unit_id) in ids.iter().enumerate() {
+        client.reserve_unit(
+            &unit_id,
+            &(request_id as u64 + 1),
+            &BloodType::APositive,
+            &false,
+            &admin,
+            &None,
+        );
+        alerts += count_events(&env, topics::LOW_STOCK);
+    }
+    // 1350 ml is fine, 900 ml alerts, 450 and 0 ml fall in the cooldown
+    assert_eq!(alerts, 1);
+    assert_eq!(
+        client.get_alert_state(&BloodType::APositive),
+        Some(AlertState {
+            last_emitted_at: 1000,
+            last_available_ml: 900,
+        })
+    );
+
+    // Once the cooldown has passed the next drop alerts again
+    advance_hours(&env, 1);
+    let unit_id = UnitFixture::new(&env).register(&client, &admin);
+    client.reserve_unit(&unit_id, &9, &BloodType::APositive, &false, &admin, &None);
+    assert_eq!(
+        latest_event::<LowStockEvent>(&env, topics::LOW_STOCK),
+        LowStockEvent {
+            blood_type: BloodType::APositive,
+            available_ml: 0,
+            threshold_ml: 1000,
+            emitted_at: 1000 + 3600,
+        }
+    );
+}
+
+#[test]
+fn test_low_stock_alert_step_overrides_cooldown() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    // Re-alert within the cooldown after another 900 ml drop
+    client.set_low_stock_alert(&BloodType::APositive, &2000, &86400, &900);
+    let ids = UnitFixture::new(&env).register_many(&client, &admin, 5);
+
+    let mut alerts = Vec::new(&env);
+    for (request_id, unit_id) in ids.iter().enumerate() {
+        client.reserve_unit(
+            &unit_id,
+            &(request_id as u64 + 1),
+            &BloodType::APositive,
+            &false,
+            &admin,
+            &None,
+        );
+        alerts.push_back(count_events(&env, topics::LOW_STOCK));
+    }
+    // 1800 ml alerts, 1350 is suppressed, 900 is a full step lower,
+    // 450 is suppressed, 0 is a full step below 900
+    assert_eq!(alerts, vec![&env, 1, 0, 1, 0, 1]);
+}
+
+#[test]
+fn test_low_stock_alert_resets_above_threshold() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    client.set_low_stock_alert(&BloodType::APositive, &1000, &86400, &0);
+    let ids = UnitFixture::new(&env).register_many(&client, &admin, 2);
+
+    client.reserve_unit(
+        &ids.get(0).unwrap(),
+        &1,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+    assert_eq!(count_events(&env, topics::LOW_STOCK), 1);
+    assert!(client.get_alert_state(&BloodType::APositive).is_some());
+
+    // Restocking above the threshold clears the alert state on the next check
+    UnitFixture::new(&env).register_many(&client, &admin, 3);
+    client.reserve_unit(
+        &ids.get(1).unwrap(),
+        &2,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+    assert_eq!(count_events(&env, topics::LOW_STOCK), 0);
+    assert_eq!(client.get_alert_state(&BloodType::APositive), None);
+
+    // Other blood types are never alerted without settings
+    assert_eq!(client.get_low_stock_alert(&BloodType::ONegative), None);
+}
+
+// ==================== Watcher Tests ====================
+
+#[test]
+fn test_subscribe_and_watched_type_event() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let courier = Address::generate(&env);
+    let coordinator = Address::generate(&env);
+    client.subscribe(&courier, &BloodType::ONegative);
+    client.subscribe(&coordinator, &BloodType::ONegative);
+    assert_eq!(
+        client.get_watchers(&BloodType::ONegative),
+        vec![&env, courier.clone(), coordinator.clone()]
+    );
+    assert_eq!(
+        client.try_subscribe(&courier, &BloodType::ONegative),
+        Err(Ok(ContractError::AlreadyExists))
+    );
+
+    let unit_id = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .register(&client, &admin);
+    let (event_topics, data) =
+        find_event(&env, topics::WATCHED_TYPE_CHANGED).expect("watched_type_changed not emitted");
+    assert_eq!(
+        event_topics,
+        (
+            Symbol::new(&env, topics::WATCHED_TYPE_CHANGED),
+            BloodType::ONegative,
+        )
+            .into_val(&env)
+    );
+    let event = WatchedTypeChangedEvent::try_from_val(&env, &data).unwrap();
+    assert_eq!(event.blood_type, BloodType::ONegative);
+    assert_eq!(event.available_ml, 450);
+    assert_eq!(
+        event.watchers,
+        vec![&env, courier.clone(), coordinator.clone()]
+    );
+
+    // Unwatched types stay quiet
+    UnitFixture::new(&env).register(&client, &admin);
+    assert_eq!(count_events(&env, topics::WATCHED_TYPE_CHANGED), 0);
+
+    // Unsubscribing shrinks the list; the event keeps firing for the rest
+    client.unsubscribe(&courier, &BloodType::ONegative);
+    assert_eq!(
+        client.get_watchers(&BloodType::ONegative),
+        vec![&env, coordinator.clone()]
+    );
+    client.reserve_unit(&unit_id, &1, &BloodType::ONegative, &false, &admin, &None);
+    let event: WatchedTypeChangedEvent = latest_event(&env, topics::WATCHED_TYPE_CHANGED);
+    assert_eq!(event.available_ml, 0);
+    assert_eq!(event.watchers, vec![&env, coordinator]);
+
+    assert_eq!(
+        client.try_unsubscribe(&courier, &BloodType::ONegative),
+        Err(Ok(ContractError::NotFound))
+    );
+}
+
+#[test]
+fn test_watchers_capped_per_type() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    for _ in 0..storage::MAX_WATCHERS_PER_TYPE {
+        client.subscribe(&Address::generate(&env), &BloodType::APositive);
+    }
+    assert_eq!(
+        client.try_subscribe(&Address::generate(&env), &BloodType::APositive),
+        Err(Ok(ContractError::CapacityExceeded))
+    );
+
+    // The cap is per blood type
+    client.subscribe(&Address::generate(&env), &BloodType::BPositive);
+}
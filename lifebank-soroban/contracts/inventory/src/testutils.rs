@@ -0,0 +1,179 @@
+//! Fixtures for tests that need a live inventory contract
+//!
+//! Compiled for this crate's tests and, through the `testutils` feature, for
+//! the request contract's tests.
+//!
+//! ```ignore
+//! let (env, admin, client, _contract_id) = testutils::setup();
+//! let unit_id = UnitFixture::new(&env)
+//!     .with_blood_type(BloodType::ONegative)
+//!     .expiring_in_days(10)
+//!     .register(&client, &admin);
+//! ```
+
+use crate::types::{BloodType, ProductType, RegistrationOptions};
+use crate::{InventoryContract, InventoryContractClient};
+use lifebank_common::testutils::SECONDS_PER_DAY;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String, Symbol, Vec};
+
+/// Register and initialize an inventory contract with auth mocked
+///
+/// Returns `(env, admin, client, contract_id)`. The admin is also an
+/// authorized bank.
+pub fn setup<'a>() -> (Env, Address, InventoryContractClient<'a>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(InventoryContract, ());
+    let client = InventoryContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin, &vec![&env], &0);
+
+    (env, admin, client, contract_id)
+}
+
+/// Builder for a blood unit registration
+///
+/// Defaults to an anonymous 450 ml A+ whole blood unit expiring 30 days
+/// after the ledger time at which it is registered.
+#[derive(Clone)]
+pub struct UnitFixture {
+    env: Env,
+    blood_type: BloodType,
+    product: ProductType,
+    quantity_ml: u32,
+    expiration: Option<u64>,
+    donor: Option<Address>,
+    options: RegistrationOptions,
+}
+
+impl UnitFixture {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            env: env.clone(),
+            blood_type: BloodType::APositive,
+            product: ProductType::WholeBlood,
+            quantity_ml: 450,
+            expiration: None,
+            donor: None,
+            options: RegistrationOptions::new(env),
+        }
+    }
+
+    pub fn with_blood_type(mut self, blood_type: BloodType) -> Self {
+        self.blood_type = blood_type;
+        self
+    }
+
+    /// Register through `register_component` instead of `register_blood`
+    pub fn with_product(mut self, product: ProductType) -> Self {
+        self.product = product;
+        self
+    }
+
+    pub fn with_quantity(mut self, quantity_ml: u32) -> Self {
+        self.quantity_ml = quantity_ml;
+        self
+    }
+
+    /// Absolute expiration timestamp
+    pub fn expiring_at(mut self, expiration: u64) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+
+    /// Expiration relative to the current ledger time
+    pub fn expiring_in_days(self, days: u64) -> Self {
+        let now = self.env.ledger().timestamp();
+        self.expiring_at(now + days * SECONDS_PER_DAY)
+    }
+
+    pub fn with_donor(mut self, donor: &Address) -> Self {
+        self.donor = Some(donor.clone());
+        self
+    }
+
+    /// Earmark the donation for a request (whole blood only)
+    pub fn directed_to(mut self, request_id: u64) -> Self {
+        self.options.directed_to_request = Some(request_id);
+        self
+    }
+
+    /// Register against a blood drive campaign (whole blood only)
+    pub fn in_campaign(mut self, campaign_id: u64) -> Self {
+        self.options.campaign_id = Some(campaign_id);
+        self
+    }
+
+    /// Record extended phenotype tags at registration
+    pub fn with_phenotype(mut self, tags: &[&str]) -> Self {
+        for tag in tags {
+            self.options
+                .phenotype_tags
+                .push_back(Symbol::new(&self.env, tag));
+        }
+        self
+    }
+
+    /// Pre-donated by the donor for their own transfusion (whole blood
+    /// only, needs `with_donor`)
+    pub fn autologous(mut self) -> Self {
+        self.options.autologous = true;
+        self
+    }
+
+    /// Tag the registration with the bank's own reference (whole blood only)
+    pub fn with_external_ref(mut self, external_ref: &str) -> Self {
+        self.options.external_ref = Some(String::from_str(&self.env, external_ref));
+        self
+    }
+
+    /// Register through one of the bank's operators instead of the bank
+    /// itself (whole blood only)
+    pub fn by_operator(mut self, operator: &Address) -> Self {
+        self.options.operator = Some(operator.clone());
+        self
+    }
+
+    /// Register the unit at `bank` and return its id
+    pub fn register(self, client: &InventoryContractClient<'_>, bank: &Address) -> u64 {
+        let expiration = self
+            .expiration
+            .unwrap_or(self.env.ledger().timestamp() + 30 * SECONDS_PER_DAY);
+        match self.product {
+            ProductType::WholeBlood => client.register_blood(
+                bank,
+                &self.blood_type,
+                &self.quantity_ml,
+                &expiration,
+                &self.donor,
+                &self.options,
+            ),
+            product => client.register_component(
+                bank,
+                &self.blood_type,
+                &product,
+                &self.quantity_ml,
+                &expiration,
+                &self.donor,
+                &self.options.phenotype_tags,
+            ),
+        }
+    }
+
+    /// Register `count` identical units at `bank` and return their ids
+    pub fn register_many(
+        self,
+        client: &InventoryContractClient<'_>,
+        bank: &Address,
+        count: u32,
+    ) -> Vec<u64> {
+        let mut ids = Vec::new(&self.env);
+        for _ in 0..count {
+            ids.push_back(self.clone().register(client, bank));
+        }
+        ids
+    }
+}
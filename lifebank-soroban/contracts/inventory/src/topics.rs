@@ -0,0 +1,44 @@
+pub const BLOOD_REGISTERED: &str = "blood_registered";
+pub const STATUS_CHANGED: &str = "status_changed";
+pub const BLOOD_RESERVED: &str = "blood_reserved";
+pub const RESERVATION_RELEASED: &str = "reservation_released";
+pub const BLOOD_DISCARDED: &str = "blood_discarded";
+pub const REGISTRATION_VOIDED: &str = "registration_voided";
+pub const BLOOD_EXPIRED: &str = "blood_expired";
+pub const EXPIRY_SWEEP_COMPLETED: &str = "expiry_sweep_completed";
+pub const UNIT_LOCATION_CHANGED: &str = "unit_location_changed";
+pub const CUSTODY_CHANGED: &str = "custody_changed";
+pub const PHENOTYPE_TAGS_SET: &str = "phenotype_tags_set";
+pub const UNITS_POOLED: &str = "units_pooled";
+pub const UNIT_SPLIT: &str = "unit_split";
+pub const UNIT_CORRECTED: &str = "unit_corrected";
+pub const DONOR_MILESTONE: &str = "donor_milestone";
+pub const DONOR_DEFERRED: &str = "donor_deferred";
+pub const DONOR_REINSTATED: &str = "donor_reinstated";
+pub const DONOR_TEMPORARILY_DEFERRED: &str = "donor_temporarily_deferred";
+pub const ADMIN_PROPOSED: &str = "admin_proposed";
+pub const ADMIN_CHANGED: &str = "admin_changed";
+pub const ADMIN_TRANSFER_CANCELLED: &str = "admin_transfer_cancelled";
+pub const IMPLICIT_BANK_AUTH_CHANGED: &str = "implicit_bank_auth_changed";
+pub const ROLE_GRANTED: &str = "role_granted";
+pub const ROLE_REVOKED: &str = "role_revoked";
+pub const BANK_AUTHORIZED: &str = "bank_authorized";
+pub const BANK_REVOKED: &str = "bank_revoked";
+pub const BANK_CAPACITY_SET: &str = "bank_capacity_set";
+pub const BANK_TIER_CHANGED: &str = "bank_tier_changed";
+pub const SHELF_LIFE_SET: &str = "shelf_life_set";
+pub const CONVERSION_FRACTION_SET: &str = "conversion_fraction_set";
+pub const TEMPERATURE_RANGE_SET: &str = "temperature_range_set";
+pub const TEMPERATURE_EXCURSION: &str = "temperature_excursion";
+pub const QUANTITY_STEP_SET: &str = "quantity_step_set";
+pub const REGISTRATION_RATE_LIMIT_SET: &str = "registration_rate_limit_set";
+pub const VOID_WINDOW_SET: &str = "void_window_set";
+pub const REQUEST_CONTRACT_SET: &str = "request_contract_set";
+pub const LOW_STOCK: &str = "low_stock";
+pub const LOW_STOCK_ALERT_SET: &str = "low_stock_alert_set";
+pub const MATCH_AVAILABLE: &str = "match_available";
+pub const WATCHED_TYPE_CHANGED: &str = "watched_type_changed";
+pub const CAMPAIGN_CREATED: &str = "campaign_created";
+pub const UNIT_LENT: &str = "unit_lent";
+pub const LOAN_SETTLED: &str = "loan_settled";
+pub const LOAN_OVERDUE: &str = "loan_overdue";
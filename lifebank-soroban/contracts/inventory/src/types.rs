@@ -1,297 +1,1755 @@
 use crate::error::ContractError;
-use soroban_sdk::{contracttype, Address, Map, String, Symbol, Vec};
+use crate::storage::{MAX_EXPIRATION_DAYS, SECONDS_PER_DAY};
+use crate::validation::MAX_POOLED_QUANTITY_ML;
+use soroban_sdk::{contracttype, Address, Env, Map, String, Symbol, Vec};
 
-/// Blood type enumeration supporting all major blood groups
+/// Contract version type shared with the request contract
+pub use lifebank_common::ContractVersion;
+
+/// Blood type shared with the request contract
+pub use lifebank_common::BloodType;
+
+/// Blood unit status representing its current state in the supply chain
+///
+/// Status transitions follow this flow:
+/// Available -> Reserved -> InTransit -> Delivered
+///           \-> Expired or Discarded (can happen at any stage)
+/// Directed  -> Reserved (for its request only), or Available once released
+/// Autologous -> Reserved (for its donor only) -> back to Autologous, never
+///               Available; discarded if unused by expiration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum BloodStatus {
+    /// Available for reservation - initial state after donation processing
+    Available,
+    /// Reserved for a specific request but not yet shipped
+    Reserved,
+    /// Currently being transported to destination
+    InTransit,
+    /// Successfully delivered to recipient/hospital
+    Delivered,
+    /// Expired and no longer usable (typically after 42 days for whole blood)
+    Expired,
+    /// Taken out of circulation by the bank (damaged, failed screening, recalled)
+    Discarded,
+    /// Donated for a specific request and held outside the general pool
+    Directed,
+    /// Pre-donated by a patient for their own transfusion, held for them only
+    Autologous,
+    /// Combined into a pooled platelet unit (see `BloodUnit::pooled_from`)
+    Pooled,
+    /// Separated into component units (see `BloodUnit::split_from`)
+    Split,
+    /// Registration withdrawn by the bank shortly after it was made (see
+    /// `void_registration`); left out of every index and statistic
+    Voided,
+}
+
+/// Hemovigilance category of a discarded unit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum DiscardReason {
+    /// Past its expiration time before it could be used
+    Expired,
+    /// Storage or transport temperature went out of range
+    BrokenColdChain,
+    /// Donation screening came back positive
+    PositiveScreening,
+    /// Bag punctured, leaking or otherwise damaged
+    BagDamage,
+    /// Labelling or record-keeping mistake
+    ClericalError,
+    /// Anything else, including discards recorded without a category
+    Other,
+}
+
+/// Blood product a unit contains
+///
+/// Each product has its own shelf-life bounds, see `ShelfLife`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum ProductType {
+    /// Whole blood, 42 days
+    WholeBlood,
+    /// Packed red cells, 42 days
+    RedCells,
+    /// Platelet concentrate (pooled or apheresis), 5 days
+    Platelets,
+    /// Frozen plasma, 1 year
+    Plasma,
+}
+
+/// Storage temperature range of a blood product, in tenths of a degree
+/// Celsius (both bounds inclusive)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemperatureRange {
+    pub min_decicelsius: i32,
+    pub max_decicelsius: i32,
+}
+
+impl TemperatureRange {
+    /// Check if a reading lies within the range
+    pub fn contains(&self, temp_decicelsius: i32) -> bool {
+        temp_decicelsius >= self.min_decicelsius && temp_decicelsius <= self.max_decicelsius
+    }
+}
+
+/// Shelf-life bounds of a blood product, in seconds after registration
+///
+/// A unit's expiration must leave at least `min_seconds` of shelf life and
+/// no more than `max_seconds`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShelfLife {
+    pub min_seconds: u64,
+    pub max_seconds: u64,
+}
+
+impl ProductType {
+    /// All products, in declaration order
+    pub const ALL: [ProductType; 4] = [
+        ProductType::WholeBlood,
+        ProductType::RedCells,
+        ProductType::Platelets,
+        ProductType::Plasma,
+    ];
+
+    /// Built-in shelf-life bounds, used until the admin configures others
+    pub fn default_shelf_life(&self) -> ShelfLife {
+        let max_days = match self {
+            ProductType::WholeBlood | ProductType::RedCells => MAX_EXPIRATION_DAYS,
+            ProductType::Platelets => 5,
+            ProductType::Plasma => 365,
+        };
+        ShelfLife {
+            min_seconds: SECONDS_PER_DAY,
+            max_seconds: max_days * SECONDS_PER_DAY,
+        }
+    }
+
+    /// Built-in storage temperature range, used until the admin configures
+    /// another
+    pub fn default_temperature_range(&self) -> TemperatureRange {
+        let (min_decicelsius, max_decicelsius) = match self {
+            // 1-6 °C
+            ProductType::WholeBlood | ProductType::RedCells => (10, 60),
+            // 20-24 °C, agitated
+            ProductType::Platelets => (200, 240),
+            // Frozen at -18 °C or colder
+            ProductType::Plasma => (-800, -180),
+        };
+        TemperatureRange {
+            min_decicelsius,
+            max_decicelsius,
+        }
+    }
+
+    /// Check if platelets can be pooled from units of this product
+    pub fn is_platelet_source(&self) -> bool {
+        matches!(self, ProductType::WholeBlood | ProductType::Platelets)
+    }
+}
+
+/// Layout version of newly written `BloodUnit` records
+pub const BLOOD_UNIT_SCHEMA: u32 = 1;
+
+/// Complete blood unit record stored in the inventory contract
+///
+/// Represents a single unit of donated blood with full tracking information
+/// from donation through delivery or expiration.
+///
+/// # Storage Keys
+/// - Primary key: `id` (u64)
+/// - Secondary indexes: `blood_type`, `bank_id`, `status`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BloodUnit {
+    /// Layout version the record was written with (`BLOOD_UNIT_SCHEMA` for
+    /// new records)
+    pub schema: u32,
+
+    /// Unique identifier for this blood unit
+    pub id: u64,
+
+    /// Blood type (A+, A-, B+, B-, AB+, AB-, O+, O-)
+    pub blood_type: BloodType,
+
+    /// Blood product (whole blood or a component)
+    pub product: ProductType,
+
+    /// Volume in milliliters (ml)
+    /// Standard unit: 450ml ± 10% for whole blood
+    /// Typical range: 400-500ml
+    pub quantity_ml: u32,
+
+    /// Blood bank address that manages this unit
+    pub bank_id: Address,
+
+    /// Optional donor address (may be anonymous)
+    /// None indicates anonymous donation
+    pub donor_id: Option<Address>,
+
+    /// Unix timestamp (seconds) when donation was collected
+    pub donation_timestamp: u64,
+
+    /// Unix timestamp (seconds) when unit expires
+    /// Typically 42 days from donation for whole blood
+    /// 5 days for platelets, 1 year for frozen plasma
+    pub expiration_timestamp: u64,
+
+    /// Current status in supply chain
+    pub status: BloodStatus,
+
+    /// Extensible metadata for additional attributes
+    /// Examples: test_results, lot_number, processing_notes
+    pub metadata: Map<Symbol, String>,
+
+    /// Storage location inside the owning bank (fridge, freezer, shelf)
+    /// None until the bank records one, cleared once the unit leaves storage
+    pub location: Option<String>,
+
+    /// Request (request contract) this unit is reserved for
+    /// Set by `reserve_unit`, cleared when the reservation is released
+    pub reserved_for: Option<u64>,
+
+    /// Request (request contract) this unit was donated for
+    /// Cleared when the unit is released to the general pool
+    pub directed_to: Option<u64>,
+
+    /// Blood drive campaign the donation was collected under
+    pub campaign_id: Option<u64>,
+
+    /// Extended phenotype antigens the unit was typed for
+    /// Examples: `kell_neg`, `c_neg`, `e_neg`; empty when not typed
+    pub phenotype_tags: Vec<Symbol>,
+
+    /// Pre-donated by the donor for their own transfusion
+    /// Never joins the general pool; only reservable for the donor
+    pub autologous: bool,
+
+    /// Units combined into this pooled platelet unit, empty for single
+    /// donations
+    pub pooled_from: Vec<u64>,
+
+    /// Unit this component was separated from by `split_unit`
+    pub split_from: Option<u64>,
+
+    /// Who physically holds the unit: the bank until a courier picks it
+    /// up, then the courier, then the receiving hospital
+    pub custodian: Address,
+
+    /// Key that performed the registration: the bank itself or one of its
+    /// operators
+    pub registered_by: Address,
+}
+
+/// Optional settings of a whole blood registration, passed to `register_blood`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistrationOptions {
+    /// Request (request contract) the donation is earmarked for
+    pub directed_to_request: Option<u64>,
+
+    /// Blood drive the donation was collected under
+    pub campaign_id: Option<u64>,
+
+    /// Extended phenotype antigens the unit was typed for
+    pub phenotype_tags: Vec<Symbol>,
+
+    /// Donor is giving blood for their own later transfusion
+    pub autologous: bool,
+
+    /// The bank's own reference for the bag (e.g. its LIS record), unique
+    /// per bank; a retried registration under the same reference returns
+    /// the unit already registered
+    pub external_ref: Option<String>,
+
+    /// Bank operator (Registrar or above) registering on the bank's behalf;
+    /// None when the bank signs itself
+    pub operator: Option<Address>,
+}
+
+impl RegistrationOptions {
+    /// General-pool donation outside any campaign, without phenotype tags
+    pub fn new(env: &Env) -> Self {
+        Self {
+            directed_to_request: None,
+            campaign_id: None,
+            phenotype_tags: Vec::new(env),
+            autologous: false,
+            external_ref: None,
+            operator: None,
+        }
+    }
+}
+
+impl BloodStatus {
+    /// Check if transition from current status to new status is valid
+    ///
+    /// Valid transitions:
+    /// - Available -> Reserved, Expired, Discarded
+    /// - Reserved -> InTransit, Available (if cancelled), Expired, Discarded
+    /// - InTransit -> Delivered, Expired, Discarded
+    /// - Directed -> Reserved, Available (released to the pool), Expired, Discarded
+    /// - Autologous -> Reserved, Discarded
+    /// - Reserved -> Autologous (reservation of an autologous unit released)
+    /// - Available -> Pooled (combined into a platelet pool)
+    /// - Delivered, Expired, Discarded, Pooled, Split, Voided -> (terminal states)
+    pub fn can_transition_to(&self, new_status: &BloodStatus) -> bool {
+        use BloodStatus::*;
+
+        match (self, new_status) {
+            // Available can go to Reserved or Expired
+            (Available, Reserved) => true,
+            (Available, Expired) => true,
+
+            // Reserved can go to InTransit, back to Available, or Expired
+            (Reserved, InTransit) => true,
+            (Reserved, Available) => true,
+            (Reserved, Expired) => true,
+
+            // InTransit can go to Delivered or Expired
+            (InTransit, Delivered) => true,
+            (InTransit, Expired) => true,
+
+            // Directed units are reserved for their request or join the pool
+            (Directed, Reserved) => true,
+            (Directed, Available) => true,
+            (Directed, Expired) => true,
+
+            // Autologous units only ever serve their donor, and are
+            // discarded rather than expired or pooled
+            (Autologous, Reserved) => true,
+            (Reserved, Autologous) => true,
+
+            // Only units on the shelf can be combined into a pool or split
+            (Available, Pooled) => true,
+            (Available, Split) => true,
+
+            // Any non-terminal unit can be discarded
+            (Available | Reserved | InTransit | Directed | Autologous, Discarded) => true,
+
+            // Delivered, Expired and Discarded are terminal states
+            (Delivered, _) => false,
+            (Expired, _) => false,
+            (Discarded, _) => false,
+            (Pooled, _) => false,
+            (Split, _) => false,
+            (Voided, _) => false,
+
+            // No other transitions allowed
+            _ => false,
+        }
+    }
+
+    /// Check if this status is a terminal state
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            BloodStatus::Delivered
+                | BloodStatus::Expired
+                | BloodStatus::Discarded
+                | BloodStatus::Pooled
+                | BloodStatus::Split
+                | BloodStatus::Voided
+        )
+    }
+
+    /// Check if a unit in this status is still physically held in bank storage
+    pub fn is_in_bank_storage(&self) -> bool {
+        matches!(
+            self,
+            BloodStatus::Available
+                | BloodStatus::Reserved
+                | BloodStatus::Directed
+                | BloodStatus::Autologous
+        )
+    }
+}
+
+/// What an authorized blood bank may do
+///
+/// RegisterOnly banks (e.g. mobile collection vans) can register donations
+/// but cannot move, hand over, discard or otherwise manage stock.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum AuthorizationTier {
+    Full,
+    RegisterOnly,
+}
+
+/// Role an operator can hold at a blood bank
+///
+/// The bank itself and the contract admin hold every role.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum BankRole {
+    /// Records checkpoints and storage locations (e.g. couriers scanning bags)
+    Registrar,
+    /// Manages stock: status changes, reservations and discards
+    InventoryManager,
+    /// Read-only access to the bank's restricted views
+    Auditor,
+}
+
+impl BankRole {
+    /// Check if this role covers the minimum role an operation requires
+    ///
+    /// InventoryManager covers Registrar; every role covers Auditor.
+    pub fn satisfies(&self, required: BankRole) -> bool {
+        match required {
+            BankRole::Registrar => {
+                matches!(self, BankRole::Registrar | BankRole::InventoryManager)
+            }
+            BankRole::InventoryManager => *self == BankRole::InventoryManager,
+            BankRole::Auditor => true,
+        }
+    }
+}
+
+impl BloodUnit {
+    /// Validate that the blood unit data is consistent and valid
+    ///
+    /// Checks:
+    /// - Quantity is within acceptable range (100-600ml)
+    /// - Expiration is after donation
+    /// - Timestamps are reasonable (not in far future)
+    pub fn validate(&self, current_time: u64) -> Result<(), ContractError> {
+        // Validate quantity (typical range: 100-600ml; a pool holds the
+        // sum of its sources)
+        let max_quantity_ml = if self.pooled_from.is_empty() {
+            600
+        } else {
+            MAX_POOLED_QUANTITY_ML
+        };
+        if self.quantity_ml < 100 || self.quantity_ml > max_quantity_ml {
+            return Err(ContractError::InvalidQuantity);
+        }
+
+        // Validate timestamps
+        if self.expiration_timestamp <= self.donation_timestamp {
+            return Err(ContractError::InvalidTimestamp);
+        }
+
+        // Donation shouldn't be from far future (allow up to 1 hour ahead for clock skew)
+        if self.donation_timestamp > current_time.saturating_add(3600) {
+            return Err(ContractError::InvalidTimestamp);
+        }
+
+        Ok(())
+    }
+
+    /// Check if blood unit is currently expired
+    pub fn is_expired(&self, current_time: u64) -> bool {
+        current_time >= self.expiration_timestamp
+    }
+
+    /// Status of the unit as of `current_time`
+    ///
+    /// A non-terminal unit past its expiration reads as Expired, even if the
+    /// stored status has not been updated yet.
+    pub fn effective_status(&self, current_time: u64) -> BloodStatus {
+        if !self.status.is_terminal() && self.is_expired(current_time) {
+            BloodStatus::Expired
+        } else {
+            self.status
+        }
+    }
+
+    /// The unit with its status replaced by `effective_status`
+    pub fn with_effective_status(mut self, current_time: u64) -> Self {
+        self.status = self.effective_status(current_time);
+        self
+    }
+
+    /// Check if the unit carries every one of `required_tags`
+    pub fn has_phenotype_tags(&self, required_tags: &Vec<Symbol>) -> bool {
+        required_tags
+            .iter()
+            .all(|tag| self.phenotype_tags.contains(&tag))
+    }
+
+    /// Calculate shelf life remaining in seconds
+    ///
+    /// Clamped to the `i64` range instead of wrapping.
+    pub fn shelf_life_remaining(&self, current_time: u64) -> i64 {
+        (self.expiration_timestamp as i128 - current_time as i128)
+            .clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+}
+
+/// Compact view of a blood unit for list screens
+///
+/// Leaves out the metadata and location, and deliberately the donor, so list
+/// views never expose donor identity.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BloodUnitSummary {
+    pub id: u64,
+    pub blood_type: BloodType,
+    pub quantity_ml: u32,
+    pub bank_id: Address,
+    pub status: BloodStatus,
+    pub expiration_timestamp: u64,
+}
+
+impl From<BloodUnit> for BloodUnitSummary {
+    fn from(blood_unit: BloodUnit) -> Self {
+        BloodUnitSummary {
+            id: blood_unit.id,
+            blood_type: blood_unit.blood_type,
+            quantity_ml: blood_unit.quantity_ml,
+            bank_id: blood_unit.bank_id,
+            status: blood_unit.status,
+            expiration_timestamp: blood_unit.expiration_timestamp,
+        }
+    }
+}
+
+/// Inputs of a registration made under a bank's external reference
+///
+/// A retry under the same reference must repeat them exactly.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistrationRecord {
+    pub blood_unit_id: u64,
+    pub blood_type: BloodType,
+    pub product: ProductType,
+    pub quantity_ml: u32,
+    pub expiration_timestamp: u64,
+    pub donor_id: Option<Address>,
+    pub directed_to_request: Option<u64>,
+    pub campaign_id: Option<u64>,
+    pub phenotype_tags: Vec<Symbol>,
+    pub autologous: bool,
+}
+
+impl From<&BloodUnit> for RegistrationRecord {
+    fn from(blood_unit: &BloodUnit) -> Self {
+        RegistrationRecord {
+            blood_unit_id: blood_unit.id,
+            blood_type: blood_unit.blood_type,
+            product: blood_unit.product,
+            quantity_ml: blood_unit.quantity_ml,
+            expiration_timestamp: blood_unit.expiration_timestamp,
+            donor_id: blood_unit.donor_id.clone(),
+            directed_to_request: blood_unit.directed_to,
+            campaign_id: blood_unit.campaign_id,
+            phenotype_tags: blood_unit.phenotype_tags.clone(),
+            autologous: blood_unit.autologous,
+        }
+    }
+}
+
+/// Storage key types for efficient querying
+///
+/// Internal to the contract and left out of its spec, which caps a union
+/// at 50 cases.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub enum DataKey {
+    /// Individual blood unit by ID
+    BloodUnit(u64),
+
+    /// Counter for generating new blood unit IDs
+    BloodUnitCounter,
+
+    /// Legacy monolithic blood type index -> Vec<u64>, moved into
+    /// `BloodTypeIndexPage` on first access
+    BloodTypeIndex(BloodType),
+
+    /// Index: Bank ID -> Vec<u64> (blood unit IDs)
+    BankIndex(Address),
+
+    /// Legacy monolithic status index -> Vec<u64>, moved into
+    /// `StatusIndexPage` on first access
+    StatusIndex(BloodStatus),
+
+    /// Index: Donor ID -> Vec<u64> (blood unit IDs)
+    DonorIndex(Address),
+
+    /// Index: (Bank ID, registering key) -> Vec<u64> (blood unit IDs)
+    OperatorIndex(Address, Address),
+
+    /// Admin address
+    Admin,
+
+    /// Version of the code that initialized the contract -> ContractVersion
+    Version,
+
+    /// Authorization flag for a blood bank
+    AuthorizedBank(Address),
+
+    /// Banks authorized via `authorize_bank`, in authorization order -> Vec<Address>
+    AuthorizedBanks,
+
+    /// Authorization tier of a blood bank (Full when unset)
+    BankTier(Address),
+
+    /// Status change history for a blood unit
+    StatusHistory(u64), // u64 is blood_unit_id -> Vec<StatusChangeHistory>
+
+    /// Counter for status change history records
+    StatusHistoryCounter,
+
+    /// Counter for status changes on specific blood unit
+    BloodUnitStatusChangeCount(u64), // u64 is blood_unit_id
+
+    /// Quantity corrections made to a blood unit -> Vec<QuantityCorrection>
+    QuantityCorrections(u64),
+
+    /// Index: (Bank ID, location) -> Vec<u64> (blood unit IDs)
+    LocationIndex(Address, String),
+
+    /// Donation profile of a donor
+    DonorProfile(Address),
+
+    /// Latest temporary deferral of a donor, kept after it lapses
+    /// -> TemporaryDeferral
+    DonorDeferral(Address),
+
+    /// Maximum number of non-terminal units a bank may hold
+    BankCapacity(Address),
+
+    /// Number of non-terminal units currently held by a bank
+    BankUnitCount(Address),
+
+    /// Index: Blood type -> Vec<ExpiryIndexEntry> of Available units,
+    /// soonest expiration first
+    AvailableByExpiry(BloodType),
+
+    /// Running Available/Reserved volume totals for a blood type
+    StockTotals(BloodType),
+
+    /// Running reservation aggregates for a bank -> ReservationStats
+    ReservationStats(Address),
+
+    /// When a Reserved unit's current hold started
+    HoldStartedAt(u64),
+
+    /// Request contract allowed to release reservations
+    RequestContract,
+
+    /// Index: Request ID -> Vec<u64> (blood unit IDs reserved for it)
+    ReservationIndex(u64),
+
+    /// Index: Request ID -> Vec<u64> (blood unit IDs donated for it)
+    DirectedIndex(u64),
+
+    /// Requests waiting for stock of a blood type -> Vec<WaitlistEntry>,
+    /// highest priority first
+    Waitlist(BloodType),
+
+    /// Blood type whose waitlist holds a request
+    WaitlistedType(u64),
+
+    /// Blood drive campaign by ID
+    Campaign(u64),
+
+    /// Counter for generating new campaign IDs
+    CampaignCounter,
+
+    /// Index: Campaign ID -> Vec<u64> (blood unit IDs collected under it)
+    CampaignIndex(u64),
+
+    /// Inter-bank unit loan by ID
+    Loan(u64),
+
+    /// Counter for generating new loan IDs
+    LoanCounter,
+
+    /// Index: Bank ID -> Vec<u64> (unsettled loans it lent or borrowed)
+    OutstandingLoans(Address),
+
+    /// Admin proposed by the current admin, awaiting acceptance
+    PendingAdmin,
+
+    /// Role held by an operator at a bank: (bank, operator) -> BankRole
+    BankRole(Address, Address),
+
+    /// Index: Bank ID -> Vec<Address> (operators holding a role)
+    BankRoleHolders(Address),
+
+    /// Unit quantities must be a multiple of this many ml (0 = disabled)
+    QuantityStep,
+
+    /// Registrations allowed per bank per day (0 = unlimited)
+    RegistrationRateLimit,
+
+    /// Registrations a bank made on a day: (bank, day number) -> u32
+    DailyRegistrations(Address, u64),
+
+    /// Seconds after registration during which a bank may void a unit
+    VoidWindow,
+
+    /// Shelf-life bounds configured for a blood product
+    ShelfLife(ProductType),
+
+    /// Index: (Blood type, page) -> Vec<u64> (at most `INDEX_PAGE_SIZE` unit IDs)
+    BloodTypeIndexPage(BloodType, u32),
+
+    /// Number of pages of a blood type index
+    BloodTypeIndexPageCount(BloodType),
+
+    /// Page of a blood type index holding a unit: (Blood type, unit ID) -> u32
+    BloodTypeIndexSlot(BloodType, u64),
+
+    /// Index: (Status, page) -> Vec<u64> (at most `INDEX_PAGE_SIZE` unit IDs)
+    StatusIndexPage(BloodStatus, u32),
+
+    /// Number of pages of a status index
+    StatusIndexPageCount(BloodStatus),
+
+    /// Page of a status index holding a unit: (Status, unit ID) -> u32
+    StatusIndexSlot(BloodStatus, u64),
+
+    /// Low-stock alert settings for a blood type
+    LowStockAlert(BloodType),
+
+    /// Last low-stock alert emitted for a blood type
+    AlertState(BloodType),
+
+    /// Discards per `DiscardReason` in an epoch -> Map<DiscardReason, u32>
+    DiscardStats(u64),
+
+    /// Category a blood unit was discarded under -> DiscardReason
+    DiscardReason(u64),
+
+    /// Custody handoffs of a blood unit -> Vec<CustodyChange>
+    CustodyHistory(u64),
+
+    /// Storage temperature range configured for a blood product
+    TemperatureRange(ProductType),
+
+    /// Volume fraction configured for a (parent, child) product derivation
+    ConversionFraction(ProductType, ProductType),
+
+    /// Latest temperature readings of a blood unit -> Vec<TemperatureReading>
+    TemperatureLog(u64),
+
+    /// Set once a blood unit had a reading outside its temperature range
+    TemperatureExcursion(u64),
+
+    /// (Bank, external reference) -> RegistrationRecord
+    ExternalRef(Address, String),
+
+    /// Blood unit ID -> external reference it was registered under
+    UnitExternalRef(u64),
+
+    /// Watchers subscribed to a blood type -> Vec<Address>
+    Watchers(BloodType),
+}
+
+/// Entry of the expiration-sorted availability index
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiryIndexEntry {
+    /// When the unit expires
+    pub expiration_timestamp: u64,
+
+    /// When the unit was donated (tiebreak for equal expirations)
+    pub donation_timestamp: u64,
+
+    /// Blood unit ID
+    pub blood_unit_id: u64,
+}
+
+/// Running donation record for a donor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonorProfile {
+    /// Donor address
+    pub donor_id: Address,
+
+    /// Number of donations registered for this donor
+    pub donation_count: u32,
+
+    /// When the most recent donation was registered
+    pub last_donation_at: u64,
+
+    /// Highest donation milestone reached (0 if none yet)
+    pub highest_milestone: u32,
+
+    /// Whether the donor is permanently deferred from donating
+    pub permanently_deferred: bool,
+
+    /// Confidential deferral reason code, only visible to the admin and the
+    /// donor (redacted from the public profile view)
+    pub deferral_reason: Option<u32>,
+}
+
+/// Temporary deferral of a donor (e.g. recent travel, low hemoglobin)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemporaryDeferral {
+    /// Registrations naming the donor are refused until this time
+    pub until: u64,
+
+    /// Confidential reason code
+    pub reason_code: u32,
+
+    /// Bank or admin that set the deferral
+    pub deferred_by: Address,
+}
+
+impl TemporaryDeferral {
+    /// Check if the deferral is still in force at `now`
+    pub fn is_active(&self, now: u64) -> bool {
+        now < self.until
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BloodRegisteredEvent {
+    /// Unique ID of the registered blood unit
+    pub blood_unit_id: u64,
+
+    /// Blood bank that registered the unit
+    pub bank_id: Address,
+
+    /// Key that performed the registration (the bank or an operator)
+    pub registered_by: Address,
+
+    /// Blood type
+    pub blood_type: BloodType,
+
+    /// Quantity in milliliters
+    pub quantity_ml: u32,
+
+    /// When the unit expires
+    pub expiration_timestamp: u64,
+
+    /// When the unit was registered
+    pub registered_at: u64,
+}
+
+/// Event emitted when a bank combines units into a pooled platelet unit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitsPooledEvent {
+    /// Unique ID of the new pooled unit
+    pub pooled_unit_id: u64,
+
+    /// Bank holding the pool and its sources
+    pub bank_id: Address,
+
+    /// Units combined into the pool, now Pooled
+    pub source_unit_ids: Vec<u64>,
+
+    /// Total volume of the pool in milliliters
+    pub quantity_ml: u32,
+
+    /// When the pool expires
+    pub expiration_timestamp: u64,
+
+    /// When the units were pooled
+    pub pooled_at: u64,
+}
+
+/// Event emitted when blood unit status changes
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StatusChangeEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Previous status
+    pub from_status: BloodStatus,
+
+    /// New status
+    pub to_status: BloodStatus,
+
+    /// Who authorized this change
+    pub authorized_by: Address,
+
+    /// When the status change occurred
+    pub changed_at: u64,
+
+    /// Optional reason for status change (e.g., "Delivered to Hospital A")
+    pub reason: Option<String>,
+}
+
+/// Event emitted when a bank voids a mistaken registration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistrationVoidedEvent {
+    /// ID of the voided blood unit
+    pub blood_unit_id: u64,
+
+    /// Bank that registered the unit
+    pub bank_id: Address,
+
+    /// Blood type of the unit
+    pub blood_type: BloodType,
+
+    /// Volume taken back out of stock
+    pub quantity_ml: u32,
+
+    /// When the unit was registered
+    pub registered_at: u64,
+
+    /// When the registration was voided
+    pub voided_at: u64,
+}
+
+/// Event emitted when a blood unit is discarded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BloodDiscardedEvent {
+    /// ID of the discarded blood unit
+    pub blood_unit_id: u64,
+
+    /// Bank that held the unit
+    pub bank_id: Address,
+
+    /// Blood type of the unit
+    pub blood_type: BloodType,
+
+    /// Volume lost
+    pub quantity_ml: u32,
+
+    /// Category the discard is reported under
+    pub discard_reason: DiscardReason,
+
+    /// Why the unit was discarded (e.g., "Recalled", "Failed screening")
+    pub reason: Option<String>,
+
+    /// When the unit was discarded
+    pub discarded_at: u64,
+}
+
+/// Event emitted when a blood unit is marked expired
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BloodExpiredEvent {
+    /// ID of the expired blood unit
+    pub blood_unit_id: u64,
+
+    /// Bank that held the unit
+    pub bank_id: Address,
+
+    /// Blood type of the unit
+    pub blood_type: BloodType,
+
+    /// Volume lost
+    pub quantity_ml: u32,
+
+    /// When the unit was marked expired
+    pub expired_at: u64,
+}
+
+/// Summary published once per `expire_units` call
+///
+/// `first_id` and `last_id` bound the IDs expired by the call (both 0 when
+/// nothing expired).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpirySweepCompletedEvent {
+    /// Number of units marked Expired
+    pub expired_count: u32,
+
+    /// Lowest ID expired
+    pub first_id: u64,
+
+    /// Highest ID expired
+    pub last_id: u64,
+
+    /// Whether the limit left overdue units behind
+    pub more_remaining: bool,
+
+    /// When the sweep ran
+    pub swept_at: u64,
+}
+
+/// Event emitted when a blood unit moves between storage locations
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct UnitLocationChangedEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Bank that owns the unit
+    pub bank_id: Address,
+
+    /// Previous location (None if the unit had no recorded location)
+    pub from_location: Option<String>,
+
+    /// New location (None when the unit left bank storage)
+    pub to_location: Option<String>,
+
+    /// When the move was recorded
+    pub changed_at: u64,
+}
+
+/// Event emitted when a bank records a unit's phenotype tags
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PhenotypeTagsSetEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Bank that owns the unit
+    pub bank_id: Address,
+
+    /// Tags replaced by this change
+    pub previous_tags: Vec<Symbol>,
+
+    /// Tags now recorded on the unit
+    pub phenotype_tags: Vec<Symbol>,
+
+    /// When the tags were set
+    pub set_at: u64,
+}
+
+/// Event emitted when a donor reaches a donation milestone
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DonorMilestoneEvent {
+    /// Donor address
+    pub donor_id: Address,
+
+    /// Milestone reached (e.g. 5th, 10th, 25th donation)
+    pub milestone: u32,
+
+    /// When the milestone was reached
+    pub reached_at: u64,
+}
+
+/// Event emitted when blood units are reserved for a request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BloodReservedEvent {
+    /// IDs of the reserved blood units
+    pub unit_ids: Vec<u64>,
+
+    /// Request the units are reserved for
+    pub request_id: u64,
+
+    /// Bank holding the units
+    pub bank_id: Address,
+
+    /// Combined volume of the reserved units
+    pub total_ml: u32,
+
+    /// Latest time the hold can last (the earliest unit expiration); the
+    /// units are released as Expired after that
+    pub reserved_until: u64,
+
+    /// When the reservation was made
+    pub reserved_at: u64,
+}
+
+/// Event emitted when a unit's reservation for a request ends
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReservationReleasedEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Request the unit was reserved for
+    pub request_id: u64,
+
+    /// Status the unit moved to
+    pub status: BloodStatus,
+
+    /// When the reservation ended
+    pub released_at: u64,
+}
+
+/// Event emitted when the admin proposes a successor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferProposedEvent {
+    /// Admin proposing the transfer
+    pub current_admin: Address,
+
+    /// Address that must accept the role
+    pub proposed_admin: Address,
+
+    /// When the transfer was proposed
+    pub proposed_at: u64,
+}
+
+/// Event emitted when the proposed admin accepts the role
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferredEvent {
+    /// Previous admin
+    pub old_admin: Address,
+
+    /// New admin
+    pub new_admin: Address,
+
+    /// When the transfer completed
+    pub transferred_at: u64,
+}
+
+/// Event emitted when a pending admin proposal is withdrawn or replaced
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferCancelledEvent {
+    /// Admin withdrawing the proposal
+    pub admin: Address,
+
+    /// Address that was proposed
+    pub cancelled_admin: Address,
+
+    /// When the proposal was withdrawn
+    pub cancelled_at: u64,
+}
+
+/// Event emitted when a blood bank authorization held only through the
+/// admin role is gained or lost
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ImplicitAuthorizationChangedEvent {
+    /// Address whose authorization changed
+    pub account: Address,
+
+    /// Whether the address is now authorized
+    pub authorized: bool,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when a bank grants or revokes an operator role
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RoleChangedEvent {
+    /// Bank the role applies to
+    pub bank_id: Address,
+
+    /// Operator receiving or losing the role
+    pub account: Address,
+
+    /// Role granted, or the role that was revoked
+    pub role: BankRole,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when a donor is permanently deferred or reinstated
+///
+/// Deliberately carries no reason code, which is confidential.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DonorDeferralChangedEvent {
+    /// Donor whose deferral changed
+    pub donor_id: Address,
+
+    /// Whether the donor is now deferred
+    pub deferred: bool,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when a donor's temporary deferral is set or extended
+///
+/// Deliberately carries no reason code, which is confidential.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DonorTemporarilyDeferredEvent {
+    /// Donor who was deferred
+    pub donor_id: Address,
+
+    /// End of the deferral
+    pub until: u64,
+
+    /// Bank or admin that set the deferral
+    pub deferred_by: Address,
+
+    /// When the deferral was set
+    pub deferred_at: u64,
+}
+
+/// Event emitted when the admin authorizes or revokes a blood bank
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BankAuthorizationChangedEvent {
+    /// Bank whose authorization changed
+    pub bank: Address,
+
+    /// Admin that made the change
+    pub admin: Address,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when the admin changes a bank's authorization tier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BankTierChangedEvent {
+    /// Bank whose tier changed
+    pub bank: Address,
+
+    /// New authorization tier
+    pub tier: AuthorizationTier,
+
+    /// Admin that made the change
+    pub admin: Address,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when the admin sets a bank's storage capacity
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BankCapacityChangedEvent {
+    /// Bank whose capacity changed
+    pub bank: Address,
+
+    /// New maximum number of units in storage
+    pub max_units: u32,
+
+    /// Admin that made the change
+    pub admin: Address,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when the admin sets the shelf life of a product
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShelfLifeChangedEvent {
+    /// Product the shelf life applies to
+    pub product: ProductType,
+
+    /// New shelf life
+    pub shelf_life: ShelfLife,
+
+    /// Admin that made the change
+    pub admin: Address,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when the admin sets the temperature range of a product
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemperatureRangeChangedEvent {
+    /// Product the range applies to
+    pub product: ProductType,
+
+    /// New temperature range
+    pub range: TemperatureRange,
+
+    /// Admin that made the change
+    pub admin: Address,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when the admin sets the registration quantity step
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuantityStepChangedEvent {
+    /// New step in ml (0 disables the check)
+    pub step_ml: u32,
+
+    /// Admin that made the change
+    pub admin: Address,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when the admin sets the window for voiding registrations
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoidWindowChangedEvent {
+    /// New window in seconds (0 disables voiding)
+    pub window_seconds: u64,
+
+    /// Admin that made the change
+    pub admin: Address,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when the admin sets the per-bank daily registration limit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistrationRateLimitChangedEvent {
+    /// New limit per bank per day (0 = unlimited)
+    pub max_per_day: u32,
+
+    /// Admin that made the change
+    pub admin: Address,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Event emitted when the admin sets the request contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestContractChangedEvent {
+    /// New request contract
+    pub request_contract: Address,
+
+    /// Admin that made the change
+    pub admin: Address,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Running volume totals for one blood type, kept up to date on every
+/// status change
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StockTotals {
+    /// Volume of Available units
+    pub available_ml: u64,
+
+    /// Volume of Reserved units
+    pub reserved_ml: u64,
+}
+
+/// Running reservation aggregates for one bank, kept up to date on every
+/// status change
+///
+/// A hold lasts while a unit is Reserved; it ends when the unit ships, is
+/// released back to stock, expires or is discarded. Moving a reservation
+/// to another request does not end the hold.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReservationStats {
+    /// Volume of the bank's units currently Reserved
+    pub reserved_ml: u64,
+
+    /// Number of the bank's units currently Reserved
+    pub active_holds: u32,
+
+    /// Number of holds that have ended
+    pub ended_holds: u32,
+
+    /// Combined duration of the ended holds, in seconds
+    pub hold_seconds: u64,
+}
+
+/// Low-stock alert settings for one blood type
 ///
-/// Each variant represents a unique combination of ABO and Rh blood typing:
-/// - A+, A-: Type A with positive/negative Rh factor
-/// - B+, B-: Type B with positive/negative Rh factor  
-/// - AB+, AB-: Type AB with positive/negative Rh factor
-/// - O+, O-: Type O with positive/negative Rh factor
+/// A `low_stock` event fires when Available volume falls below
+/// `threshold_ml`. After that, further alerts for the type are suppressed
+/// for `cooldown_seconds` unless Available volume drops by at least
+/// `step_ml` more (a step of 0 disables that override).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LowStockAlertConfig {
+    /// Available volume below which the type counts as low
+    pub threshold_ml: u64,
+
+    /// Minimum time between two alerts for the type
+    pub cooldown_seconds: u64,
+
+    /// Further drop that re-alerts during the cooldown (0 = never)
+    pub step_ml: u64,
+}
+
+/// Last low-stock alert emitted for a blood type
+///
+/// Cleared once Available volume is back at or above the threshold.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AlertState {
+    /// When the last alert was emitted
+    pub last_emitted_at: u64,
+
+    /// Available volume reported by the last alert
+    pub last_available_ml: u64,
+}
+
+/// Event emitted when a blood type's Available volume is low
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LowStockEvent {
+    /// Blood type running low
+    pub blood_type: BloodType,
+
+    /// Available volume after the change that triggered the alert
+    pub available_ml: u64,
+
+    /// Configured threshold
+    pub threshold_ml: u64,
+
+    /// When the alert was emitted
+    pub emitted_at: u64,
+}
+
+/// Event emitted when the Available volume of a watched blood type changes
 #[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq, Copy)]
-pub enum BloodType {
-    /// Type A positive (A+)
-    APositive,
-    /// Type A negative (A-)
-    ANegative,
-    /// Type B positive (B+)
-    BPositive,
-    /// Type B negative (B-)
-    BNegative,
-    /// Type AB positive (AB+) - Universal plasma donor
-    ABPositive,
-    /// Type AB negative (AB-)
-    ABNegative,
-    /// Type O positive (O+)
-    OPositive,
-    /// Type O negative (O-) - Universal blood donor
-    ONegative,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WatchedTypeChangedEvent {
+    /// Blood type whose availability changed
+    pub blood_type: BloodType,
+
+    /// Available volume after the change
+    pub available_ml: u64,
+
+    /// Watchers subscribed to the type (at most `MAX_WATCHERS_PER_TYPE`)
+    pub watchers: Vec<Address>,
+
+    /// When the change happened
+    pub changed_at: u64,
 }
 
-/// Blood unit status representing its current state in the supply chain
-///
-/// Status transitions follow this flow:
-/// Available -> Reserved -> InTransit -> Delivered
-///           \-> Expired (can happen at any stage)
+/// Request waiting for stock of a blood type
 #[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq, Copy)]
-pub enum BloodStatus {
-    /// Available for reservation - initial state after donation processing
-    Available,
-    /// Reserved for a specific request but not yet shipped
-    Reserved,
-    /// Currently being transported to destination
-    InTransit,
-    /// Successfully delivered to recipient/hospital
-    Delivered,
-    /// Expired and no longer usable (typically after 42 days for whole blood)
-    Expired,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistEntry {
+    /// Request (request contract) waiting for units
+    pub request_id: u64,
+
+    /// Priority score of the request; higher scores are matched first
+    pub priority_score: u64,
+
+    /// When the request joined the waitlist
+    pub added_at: u64,
 }
 
-/// Complete blood unit record stored in the inventory contract
-///
-/// Represents a single unit of donated blood with full tracking information
-/// from donation through delivery or expiration.
+/// Event emitted when a newly registered unit could serve the
+/// highest-priority waitlisted request of its blood type
 ///
-/// # Storage Keys
-/// - Primary key: `id` (u64)
-/// - Secondary indexes: `blood_type`, `bank_id`, `status`
+/// Only a signal: the unit is not reserved.
 #[contracttype]
-#[derive(Clone, Debug)]
-pub struct BloodUnit {
-    /// Unique identifier for this blood unit
-    pub id: u64,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchAvailableEvent {
+    /// Unique ID of the new blood unit
+    pub blood_unit_id: u64,
 
-    /// Blood type (A+, A-, B+, B-, AB+, AB-, O+, O-)
+    /// Waitlisted request the unit could serve
+    pub request_id: u64,
+
+    /// Blood type of the unit and the request
     pub blood_type: BloodType,
 
-    /// Volume in milliliters (ml)
-    /// Standard unit: 450ml ± 10% for whole blood
-    /// Typical range: 400-500ml
+    /// Bank holding the unit
+    pub bank_id: Address,
+
+    /// Volume of the unit
     pub quantity_ml: u32,
 
-    /// Blood bank address that manages this unit
+    /// When the match was found
+    pub matched_at: u64,
+}
+
+/// Event emitted when the admin configures low-stock alerts for a blood type
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LowStockAlertChangedEvent {
+    /// Blood type the settings apply to
+    pub blood_type: BloodType,
+
+    /// New settings
+    pub config: LowStockAlertConfig,
+
+    /// Admin that made the change
+    pub admin: Address,
+
+    /// When the change happened
+    pub changed_at: u64,
+}
+
+/// Named blood drive run by a bank
+///
+/// Units can be registered against the campaign while it is open, from
+/// `starts_at` to `ends_at` inclusive.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Campaign {
+    pub id: u64,
     pub bank_id: Address,
+    pub name: String,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub created_at: u64,
+}
 
-    /// Optional donor address (may be anonymous)
-    /// None indicates anonymous donation
-    pub donor_id: Option<Address>,
+impl Campaign {
+    /// Check if units can be registered against the campaign
+    pub fn is_open(&self, current_time: u64) -> bool {
+        current_time >= self.starts_at && current_time <= self.ends_at
+    }
+}
 
-    /// Unix timestamp (seconds) when donation was collected
-    pub donation_timestamp: u64,
+/// What a campaign collected, counting every unit registered against it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CampaignStats {
+    /// Number of units
+    pub units: u32,
 
-    /// Unix timestamp (seconds) when unit expires
-    /// Typically 42 days from donation for whole blood
-    /// 5 days for platelets, 1 year for frozen plasma
-    pub expiration_timestamp: u64,
+    /// Combined volume in ml
+    pub total_ml: u32,
 
-    /// Current status in supply chain
-    pub status: BloodStatus,
+    /// Volume in ml per blood type; types with no units are left out
+    pub by_blood_type: Map<BloodType, u32>,
+}
 
-    /// Extensible metadata for additional attributes
-    /// Examples: test_results, storage_location, lot_number, processing_notes
-    pub metadata: Map<Symbol, String>,
+/// Event emitted when a bank starts a campaign
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CampaignCreatedEvent {
+    pub campaign_id: u64,
+    pub bank_id: Address,
+    pub name: String,
+    pub starts_at: u64,
+    pub ends_at: u64,
 }
 
-impl BloodType {
-    /// Check if this blood type can donate to the recipient blood type
-    ///
-    /// Based on compatibility rules:
-    /// - O- can donate to all types (universal donor)
-    /// - AB+ can receive from all types (universal recipient)
-    /// - Rh- can donate to Rh+ and Rh-
-    /// - Rh+ can only donate to Rh+
-    pub fn can_donate_to(&self, recipient: &BloodType) -> bool {
-        use BloodType::*;
+/// Unit lent by one bank to another, to be replaced in kind
+///
+/// The replacement must be a unit of the same blood type and product
+/// holding at least `quantity_ml`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitLoan {
+    pub id: u64,
+    pub lender: Address,
+    pub borrower: Address,
+    pub unit_id: u64,
+    pub blood_type: BloodType,
+    pub product: ProductType,
+    pub quantity_ml: u32,
+    pub lent_at: u64,
+    pub due_by: u64,
 
-        match (self, recipient) {
-            // O- is universal donor
-            (ONegative, _) => true,
+    /// Unit returned in replacement, once settled
+    pub replacement_unit_id: Option<u64>,
 
-            // O+ can donate to all positive types
-            (OPositive, APositive | BPositive | ABPositive | OPositive) => true,
+    /// When the replacement was returned
+    pub settled_at: Option<u64>,
+}
 
-            // A- can donate to A and AB (both + and -)
-            (ANegative, APositive | ANegative | ABPositive | ABNegative) => true,
+/// An unsettled loan and whether its due date has passed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutstandingLoan {
+    pub loan: UnitLoan,
+    pub overdue: bool,
+}
 
-            // A+ can donate to A+ and AB+
-            (APositive, APositive | ABPositive) => true,
+/// Event emitted when a bank lends a unit to another
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitLentEvent {
+    pub loan_id: u64,
+    pub lender: Address,
+    pub borrower: Address,
+    pub unit_id: u64,
+    pub blood_type: BloodType,
+    pub quantity_ml: u32,
+    pub due_by: u64,
+}
 
-            // B- can donate to B and AB (both + and -)
-            (BNegative, BPositive | BNegative | ABPositive | ABNegative) => true,
+/// Event emitted when a borrower returns a replacement unit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanSettledEvent {
+    pub loan_id: u64,
+    pub lender: Address,
+    pub borrower: Address,
+    pub replacement_unit_id: u64,
+    pub settled_at: u64,
+}
 
-            // B+ can donate to B+ and AB+
-            (BPositive, BPositive | ABPositive) => true,
+/// Event emitted for each overdue loan found by `check_overdue_loans`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanOverdueEvent {
+    pub loan_id: u64,
+    pub lender: Address,
+    pub borrower: Address,
+    pub due_by: u64,
+    pub checked_at: u64,
+}
 
-            // AB- can donate to AB+ and AB-
-            (ABNegative, ABPositive | ABNegative) => true,
+/// Stock of one blood type broken down by state
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TypeBreakdown {
+    /// Volume of Available units
+    pub available_ml: u64,
 
-            // AB+ can only donate to AB+
-            (ABPositive, ABPositive) => true,
+    /// Volume of Reserved units
+    pub reserved_ml: u64,
 
-            // All other combinations are incompatible
-            _ => false,
-        }
-    }
+    /// Volume of Available units expiring within the next 72 hours
+    pub expiring_72h_ml: u64,
 }
 
-impl BloodStatus {
-    /// Check if transition from current status to new status is valid
-    ///
-    /// Valid transitions:
-    /// - Available -> Reserved, Expired
-    /// - Reserved -> InTransit, Available (if cancelled), Expired
-    /// - InTransit -> Delivered, Expired
-    /// - Delivered -> (terminal state)
-    /// - Expired -> (terminal state)
-    pub fn can_transition_to(&self, new_status: &BloodStatus) -> bool {
-        use BloodStatus::*;
+/// Event emitted when an admin corrects a unit's registered quantity
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct UnitCorrectedEvent {
+    /// Corrected blood unit
+    pub blood_unit_id: u64,
 
-        match (self, new_status) {
-            // Available can go to Reserved or Expired
-            (Available, Reserved) => true,
-            (Available, Expired) => true,
+    /// Quantity before the correction
+    pub old_quantity_ml: u32,
 
-            // Reserved can go to InTransit, back to Available, or Expired
-            (Reserved, InTransit) => true,
-            (Reserved, Available) => true,
-            (Reserved, Expired) => true,
+    /// Quantity after the correction
+    pub new_quantity_ml: u32,
 
-            // InTransit can go to Delivered or Expired
-            (InTransit, Delivered) => true,
-            (InTransit, Expired) => true,
+    /// Why the correction was made
+    pub justification: String,
 
-            // Delivered and Expired are terminal states
-            (Delivered, _) => false,
-            (Expired, _) => false,
+    /// When the correction was made
+    pub corrected_at: u64,
+}
 
-            // No other transitions allowed
-            _ => false,
-        }
-    }
+/// Event emitted when a blood unit changes hands
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustodyChangedEvent {
+    /// Blood unit handed over
+    pub blood_unit_id: u64,
 
-    /// Check if this status is a terminal state
-    pub fn is_terminal(&self) -> bool {
-        matches!(self, BloodStatus::Delivered | BloodStatus::Expired)
-    }
+    /// Custodian handing the unit over
+    pub from_custodian: Address,
+
+    /// Custodian taking the unit
+    pub to_custodian: Address,
+
+    /// When the handoff happened
+    pub changed_at: u64,
 }
 
-impl BloodUnit {
-    /// Validate that the blood unit data is consistent and valid
-    ///
-    /// Checks:
-    /// - Quantity is within acceptable range (100-600ml)
-    /// - Expiration is after donation
-    /// - Timestamps are reasonable (not in far future)
-    pub fn validate(&self, current_time: u64) -> Result<(), ContractError> {
-        // Validate quantity (typical range: 100-600ml)
-        if self.quantity_ml < 100 || self.quantity_ml > 600 {
-            return Err(ContractError::InvalidQuantity);
-        }
+/// Historical record of a custody handoff
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustodyChange {
+    /// Blood unit ID
+    pub blood_unit_id: u64,
 
-        // Validate timestamps
-        if self.expiration_timestamp <= self.donation_timestamp {
-            return Err(ContractError::InvalidTimestamp);
-        }
+    /// Custodian handing the unit over
+    pub from_custodian: Address,
 
-        // Donation shouldn't be from far future (allow up to 1 hour ahead for clock skew)
-        if self.donation_timestamp > current_time + 3600 {
-            return Err(ContractError::InvalidTimestamp);
-        }
+    /// Custodian taking the unit
+    pub to_custodian: Address,
 
-        Ok(())
-    }
+    /// When the handoff happened
+    pub changed_at: u64,
+}
 
-    /// Check if blood unit is currently expired
-    pub fn is_expired(&self, current_time: u64) -> bool {
-        current_time >= self.expiration_timestamp
-    }
+/// A component to separate from a unit with `split_unit`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComponentSplit {
+    /// Product of the new unit
+    pub product: ProductType,
 
-    /// Calculate shelf life remaining in seconds
-    pub fn shelf_life_remaining(&self, current_time: u64) -> i64 {
-        (self.expiration_timestamp as i64) - (current_time as i64)
-    }
+    /// Volume of the new unit in milliliters
+    pub quantity_ml: u32,
 }
 
-/// Storage key types for efficient querying
+/// Event emitted when a unit is separated into components
 #[contracttype]
-#[derive(Clone, Debug)]
-pub enum DataKey {
-    /// Individual blood unit by ID
-    BloodUnit(u64),
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitSplitEvent {
+    /// Unit that was split, now Split
+    pub parent_unit_id: u64,
 
-    /// Counter for generating new blood unit IDs
-    BloodUnitCounter,
+    /// Bank holding the parent and its components
+    pub bank_id: Address,
 
-    /// Index: Blood type -> Vec<u64> (blood unit IDs)
-    BloodTypeIndex(BloodType),
+    /// New component units, in the order they were requested
+    pub child_unit_ids: Vec<u64>,
 
-    /// Index: Bank ID -> Vec<u64> (blood unit IDs)
-    BankIndex(Address),
+    /// When the unit was split
+    pub split_at: u64,
+}
 
-    /// Index: Status -> Vec<u64> (blood unit IDs)
-    StatusIndex(BloodStatus),
+/// Event emitted when the admin sets the volume fraction of a derivation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConversionFractionChangedEvent {
+    /// Product split
+    pub parent: ProductType,
 
-    /// Index: Donor ID -> Vec<u64> (blood unit IDs)
-    DonorIndex(Address),
+    /// Product derived from it
+    pub child: ProductType,
 
-    /// Admin address
-    Admin,
+    /// New maximum share of the parent's volume, in basis points
+    pub fraction_bps: u32,
 
-    /// Status change history for a blood unit
-    StatusHistory(u64), // u64 is blood_unit_id -> Vec<StatusChangeHistory>
+    /// Admin that made the change
+    pub admin: Address,
 
-    /// Counter for status change history records
-    StatusHistoryCounter,
+    /// When the change happened
+    pub changed_at: u64,
+}
 
-    /// Counter for status changes on specific blood unit
-    BloodUnitStatusChangeCount(u64), // u64 is blood_unit_id
+/// Temperature reading logged by a unit's custodian
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemperatureReading {
+    /// Temperature in tenths of a degree Celsius
+    pub temp_decicelsius: i32,
+
+    /// When the reading was taken
+    pub recorded_at: u64,
+
+    /// Custodian that logged the reading
+    pub recorded_by: Address,
+
+    /// Whether the reading was within the product's temperature range
+    pub in_range: bool,
 }
 
+/// Event emitted when a temperature reading falls outside a unit's range
 #[contracttype]
-#[derive(Clone, Debug)]
-pub struct BloodRegisteredEvent {
-    /// Unique ID of the registered blood unit
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemperatureExcursionEvent {
+    /// Blood unit the reading was taken for
     pub blood_unit_id: u64,
 
-    /// Blood bank that registered the unit
-    pub bank_id: Address,
-
-    /// Blood type
-    pub blood_type: BloodType,
+    /// Out-of-range temperature in tenths of a degree Celsius
+    pub temp_decicelsius: i32,
 
-    /// Quantity in milliliters
-    pub quantity_ml: u32,
+    /// Range the unit's product must be kept in
+    pub range: TemperatureRange,
 
-    /// When the unit expires
-    pub expiration_timestamp: u64,
+    /// Custodian that logged the reading
+    pub recorded_by: Address,
 
-    /// When the unit was registered
-    pub registered_at: u64,
+    /// When the reading was taken
+    pub recorded_at: u64,
 }
 
-/// Event emitted when blood unit status changes
+/// Historical record of a quantity correction
 #[contracttype]
 #[derive(Clone, Debug)]
-pub struct StatusChangeEvent {
-    /// Unique ID of the blood unit
+pub struct QuantityCorrection {
+    /// Blood unit ID
     pub blood_unit_id: u64,
 
-    /// Previous status
-    pub from_status: BloodStatus,
+    /// Quantity before the correction
+    pub old_quantity_ml: u32,
 
-    /// New status
-    pub to_status: BloodStatus,
+    /// Quantity after the correction
+    pub new_quantity_ml: u32,
 
-    /// Who authorized this change
-    pub authorized_by: Address,
+    /// Admin who made the correction
+    pub corrected_by: Address,
 
-    /// When the status change occurred
-    pub changed_at: u64,
+    /// Why the correction was made
+    pub justification: String,
 
-    /// Optional reason for status change (e.g., "Delivered to Hospital A")
-    pub reason: Option<String>,
+    /// When the correction was made
+    pub corrected_at: u64,
 }
 
 /// Historical record of a status change
@@ -407,6 +1865,26 @@ mod tests {
         assert!(!InTransit.can_transition_to(&Available));
         assert!(!InTransit.can_transition_to(&Reserved));
 
+        // Directed transitions
+        assert!(Directed.can_transition_to(&Reserved));
+        assert!(Directed.can_transition_to(&Available));
+        assert!(Directed.can_transition_to(&Discarded));
+        assert!(!Directed.can_transition_to(&InTransit));
+        assert!(!Available.can_transition_to(&Directed));
+
+        // Autologous transitions
+        assert!(Autologous.can_transition_to(&Reserved));
+        assert!(Autologous.can_transition_to(&Discarded));
+        assert!(Reserved.can_transition_to(&Autologous));
+        assert!(!Autologous.can_transition_to(&Available));
+        assert!(!Autologous.can_transition_to(&Expired));
+        assert!(!Available.can_transition_to(&Autologous));
+
+        // Pooled transitions
+        assert!(Available.can_transition_to(&Pooled));
+        assert!(!Reserved.can_transition_to(&Pooled));
+        assert!(!Pooled.can_transition_to(&Available));
+
         // Terminal states
         assert!(!Delivered.can_transition_to(&Expired));
         assert!(!Expired.can_transition_to(&Delivered));
@@ -416,9 +1894,42 @@ mod tests {
     fn test_status_terminal_states() {
         assert!(BloodStatus::Delivered.is_terminal());
         assert!(BloodStatus::Expired.is_terminal());
+        assert!(BloodStatus::Discarded.is_terminal());
+        assert!(BloodStatus::Pooled.is_terminal());
         assert!(!BloodStatus::Available.is_terminal());
         assert!(!BloodStatus::Reserved.is_terminal());
         assert!(!BloodStatus::InTransit.is_terminal());
+        assert!(!BloodStatus::Directed.is_terminal());
+        assert!(!BloodStatus::Autologous.is_terminal());
+    }
+
+    #[test]
+    fn test_status_in_bank_storage() {
+        assert!(BloodStatus::Available.is_in_bank_storage());
+        assert!(BloodStatus::Reserved.is_in_bank_storage());
+        assert!(BloodStatus::Directed.is_in_bank_storage());
+        assert!(BloodStatus::Autologous.is_in_bank_storage());
+        assert!(!BloodStatus::InTransit.is_in_bank_storage());
+        assert!(!BloodStatus::Delivered.is_in_bank_storage());
+        assert!(!BloodStatus::Expired.is_in_bank_storage());
+        assert!(!BloodStatus::Discarded.is_in_bank_storage());
+        assert!(!BloodStatus::Pooled.is_in_bank_storage());
+    }
+
+    #[test]
+    fn test_bank_role_satisfies() {
+        use BankRole::*;
+
+        assert!(Registrar.satisfies(Registrar));
+        assert!(!Registrar.satisfies(InventoryManager));
+        assert!(InventoryManager.satisfies(Registrar));
+        assert!(InventoryManager.satisfies(InventoryManager));
+        assert!(!Auditor.satisfies(Registrar));
+        assert!(!Auditor.satisfies(InventoryManager));
+
+        for role in [Registrar, InventoryManager, Auditor] {
+            assert!(role.satisfies(Auditor));
+        }
     }
 
     #[test]
@@ -428,15 +1939,27 @@ mod tests {
         let current_time = 1000u64;
 
         let unit = BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
             id: 1,
             blood_type: BloodType::APositive,
+            product: ProductType::WholeBlood,
             quantity_ml: 450,
-            bank_id: bank,
+            bank_id: bank.clone(),
             donor_id: None,
             donation_timestamp: current_time,
             expiration_timestamp: current_time + (42 * 24 * 60 * 60), // 42 days
             status: BloodStatus::Available,
             metadata: Map::new(&env),
+            location: None,
+            reserved_for: None,
+            directed_to: None,
+            campaign_id: None,
+            phenotype_tags: Vec::new(&env),
+            autologous: false,
+            pooled_from: Vec::new(&env),
+            split_from: None,
+            registered_by: bank.clone(),
+            custodian: bank,
         };
 
         assert!(unit.validate(current_time).is_ok());
@@ -449,15 +1972,27 @@ mod tests {
         let current_time = 1000u64;
 
         let unit = BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
             id: 1,
             blood_type: BloodType::APositive,
+            product: ProductType::WholeBlood,
             quantity_ml: 50, // Too low
-            bank_id: bank,
+            bank_id: bank.clone(),
             donor_id: None,
             donation_timestamp: current_time,
             expiration_timestamp: current_time + (42 * 24 * 60 * 60),
             status: BloodStatus::Available,
             metadata: Map::new(&env),
+            location: None,
+            reserved_for: None,
+            directed_to: None,
+            campaign_id: None,
+            phenotype_tags: Vec::new(&env),
+            autologous: false,
+            pooled_from: Vec::new(&env),
+            split_from: None,
+            registered_by: bank.clone(),
+            custodian: bank,
         };
 
         assert_eq!(
@@ -473,15 +2008,27 @@ mod tests {
         let current_time = 1000u64;
 
         let unit = BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
             id: 1,
             blood_type: BloodType::APositive,
+            product: ProductType::WholeBlood,
             quantity_ml: 700, // Too high
-            bank_id: bank,
+            bank_id: bank.clone(),
             donor_id: None,
             donation_timestamp: current_time,
             expiration_timestamp: current_time + (42 * 24 * 60 * 60),
             status: BloodStatus::Available,
             metadata: Map::new(&env),
+            location: None,
+            reserved_for: None,
+            directed_to: None,
+            campaign_id: None,
+            phenotype_tags: Vec::new(&env),
+            autologous: false,
+            pooled_from: Vec::new(&env),
+            split_from: None,
+            registered_by: bank.clone(),
+            custodian: bank,
         };
 
         assert_eq!(
@@ -497,15 +2044,27 @@ mod tests {
         let current_time = 1000u64;
 
         let unit = BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
             id: 1,
             blood_type: BloodType::APositive,
+            product: ProductType::WholeBlood,
             quantity_ml: 450,
-            bank_id: bank,
+            bank_id: bank.clone(),
             donor_id: None,
             donation_timestamp: current_time,
             expiration_timestamp: current_time - 100, // Before donation
             status: BloodStatus::Available,
             metadata: Map::new(&env),
+            location: None,
+            reserved_for: None,
+            directed_to: None,
+            campaign_id: None,
+            phenotype_tags: Vec::new(&env),
+            autologous: false,
+            pooled_from: Vec::new(&env),
+            split_from: None,
+            registered_by: bank.clone(),
+            custodian: bank,
         };
 
         assert_eq!(
@@ -521,15 +2080,27 @@ mod tests {
         let current_time = 1000u64;
 
         let unit = BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
             id: 1,
             blood_type: BloodType::APositive,
+            product: ProductType::WholeBlood,
             quantity_ml: 450,
-            bank_id: bank,
+            bank_id: bank.clone(),
             donor_id: None,
             donation_timestamp: current_time + 7200, // 2 hours in future
             expiration_timestamp: current_time + (42 * 24 * 60 * 60),
             status: BloodStatus::Available,
             metadata: Map::new(&env),
+            location: None,
+            reserved_for: None,
+            directed_to: None,
+            campaign_id: None,
+            phenotype_tags: Vec::new(&env),
+            autologous: false,
+            pooled_from: Vec::new(&env),
+            split_from: None,
+            registered_by: bank.clone(),
+            custodian: bank,
         };
 
         assert_eq!(
@@ -546,15 +2117,27 @@ mod tests {
         let expiration_time = donation_time + (42 * 24 * 60 * 60);
 
         let unit = BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
             id: 1,
             blood_type: BloodType::APositive,
+            product: ProductType::WholeBlood,
             quantity_ml: 450,
-            bank_id: bank,
+            bank_id: bank.clone(),
             donor_id: None,
             donation_timestamp: donation_time,
             expiration_timestamp: expiration_time,
             status: BloodStatus::Available,
             metadata: Map::new(&env),
+            location: None,
+            reserved_for: None,
+            directed_to: None,
+            campaign_id: None,
+            phenotype_tags: Vec::new(&env),
+            autologous: false,
+            pooled_from: Vec::new(&env),
+            split_from: None,
+            registered_by: bank.clone(),
+            custodian: bank,
         };
 
         // Not expired before expiration time
@@ -567,6 +2150,56 @@ mod tests {
         assert!(unit.is_expired(expiration_time + 100));
     }
 
+    #[test]
+    fn test_blood_unit_effective_status() {
+        let env = Env::default();
+        let bank = Address::generate(&env);
+        let donation_time = 1000u64;
+        let expiration_time = donation_time + 3600;
+
+        let mut unit = BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
+            id: 1,
+            blood_type: BloodType::APositive,
+            product: ProductType::WholeBlood,
+            quantity_ml: 450,
+            bank_id: bank.clone(),
+            donor_id: None,
+            donation_timestamp: donation_time,
+            expiration_timestamp: expiration_time,
+            status: BloodStatus::Reserved,
+            metadata: Map::new(&env),
+            location: None,
+            reserved_for: Some(7),
+            directed_to: None,
+            campaign_id: None,
+            phenotype_tags: Vec::new(&env),
+            autologous: false,
+            pooled_from: Vec::new(&env),
+            split_from: None,
+            registered_by: bank.clone(),
+            custodian: bank,
+        };
+
+        // Stored status until expiration, Expired from then on
+        assert_eq!(
+            unit.effective_status(expiration_time - 1),
+            BloodStatus::Reserved
+        );
+        assert_eq!(unit.effective_status(expiration_time), BloodStatus::Expired);
+        assert_eq!(
+            unit.clone().with_effective_status(expiration_time).status,
+            BloodStatus::Expired
+        );
+
+        // Terminal states are never overridden
+        unit.status = BloodStatus::Delivered;
+        assert_eq!(
+            unit.effective_status(expiration_time + 100),
+            BloodStatus::Delivered
+        );
+    }
+
     #[test]
     fn test_blood_unit_shelf_life_remaining() {
         let env = Env::default();
@@ -575,15 +2208,27 @@ mod tests {
         let expiration_time = donation_time + 3600; // 1 hour
 
         let unit = BloodUnit {
+            schema: BLOOD_UNIT_SCHEMA,
             id: 1,
             blood_type: BloodType::APositive,
+            product: ProductType::WholeBlood,
             quantity_ml: 450,
-            bank_id: bank,
+            bank_id: bank.clone(),
             donor_id: None,
             donation_timestamp: donation_time,
             expiration_timestamp: expiration_time,
             status: BloodStatus::Available,
             metadata: Map::new(&env),
+            location: None,
+            reserved_for: None,
+            directed_to: None,
+            campaign_id: None,
+            phenotype_tags: Vec::new(&env),
+            autologous: false,
+            pooled_from: Vec::new(&env),
+            split_from: None,
+            registered_by: bank.clone(),
+            custodian: bank,
         };
 
         // 30 minutes before expiration
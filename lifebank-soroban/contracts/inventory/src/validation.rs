@@ -1,23 +1,34 @@
 use crate::error::ContractError;
-use crate::storage::{MAX_EXPIRATION_DAYS, SECONDS_PER_DAY};
-use crate::types::BloodStatus;
-use soroban_sdk::Env;
+use crate::types::{BloodStatus, BloodUnit, ShelfLife};
+use soroban_sdk::{Env, String, Symbol, Vec};
+
+/// Maximum number of phenotype tags recorded on one unit
+pub const MAX_PHENOTYPE_TAGS: u32 = 16;
+
+/// Longest external reference a bank may attach to a registration, in bytes
+pub const MAX_EXTERNAL_REF_LEN: u32 = 64;
+
+/// Fewest units `pool_units` combines
+pub const MIN_POOL_SOURCES: u32 = 2;
+
+/// Most units `pool_units` combines (a typical dose pools 4-6 donations)
+pub const MAX_POOL_SOURCES: u32 = 6;
+
+/// Largest volume of a pooled unit: `MAX_POOL_SOURCES` full-size units
+pub const MAX_POOLED_QUANTITY_ML: u32 = MAX_POOL_SOURCES * 600;
 
 /// Validate blood registration parameters
 ///
 /// Checks:
 /// - Quantity is within acceptable range (100-600ml)
-/// - Expiration is in the future but not too far (max 42 days for whole blood)
-/// - Expiration is reasonable relative to current time
+/// - Expiration is in the future but within the product's maximum shelf life
 pub fn validate_blood_registration(
     env: &Env,
     quantity_ml: u32,
     expiration_timestamp: u64,
+    shelf_life: &ShelfLife,
 ) -> Result<(), ContractError> {
-    // Validate quantity (typical range: 100-600ml)
-    if quantity_ml < 100 || quantity_ml > 600 {
-        return Err(ContractError::InvalidQuantity);
-    }
+    validate_quantity(quantity_ml)?;
 
     let current_time = env.ledger().timestamp();
 
@@ -26,9 +37,10 @@ pub fn validate_blood_registration(
         return Err(ContractError::InvalidExpiration);
     }
 
-    // Expiration shouldn't be too far in the future
-    // Standard whole blood shelf life is 42 days
-    let max_expiration = current_time + (MAX_EXPIRATION_DAYS * SECONDS_PER_DAY);
+    // Expiration shouldn't be further out than the product keeps
+    let max_expiration = current_time
+        .checked_add(shelf_life.max_seconds)
+        .ok_or(ContractError::InvalidTimestamp)?;
     if expiration_timestamp > max_expiration {
         return Err(ContractError::InvalidExpiration);
     }
@@ -36,14 +48,36 @@ pub fn validate_blood_registration(
     Ok(())
 }
 
-/// Validate that expiration timestamp is reasonable
-/// Should be at least 1 day in the future for practical use
+/// Validate a unit quantity (typical range: 100-600ml)
+pub fn validate_quantity(quantity_ml: u32) -> Result<(), ContractError> {
+    if !(100..=600).contains(&quantity_ml) {
+        return Err(ContractError::InvalidQuantity);
+    }
+
+    Ok(())
+}
+
+/// Validate a unit quantity is a multiple of the configured step
+/// (a step of 0 disables the check)
+pub fn validate_quantity_step(quantity_ml: u32, step_ml: u32) -> Result<(), ContractError> {
+    if step_ml != 0 && !quantity_ml.is_multiple_of(step_ml) {
+        return Err(ContractError::QuantityNotAligned);
+    }
+
+    Ok(())
+}
+
+/// Validate that expiration leaves the product's minimum shelf life
+/// (1 day by default) for practical use
 pub fn validate_minimum_shelf_life(
     env: &Env,
     expiration_timestamp: u64,
+    shelf_life: &ShelfLife,
 ) -> Result<(), ContractError> {
     let current_time = env.ledger().timestamp();
-    let min_shelf_life = current_time + SECONDS_PER_DAY; // At least 1 day
+    let min_shelf_life = current_time
+        .checked_add(shelf_life.min_seconds)
+        .ok_or(ContractError::InvalidTimestamp)?;
 
     if expiration_timestamp < min_shelf_life {
         return Err(ContractError::InvalidExpiration);
@@ -62,3 +96,63 @@ pub fn validate_status_transition(
     }
     Ok(())
 }
+
+/// Validate a status change of a specific unit
+///
+/// On top of the state machine, autologous units never become Available
+/// or Expired (they are discarded instead), only they may return to
+/// Autologous, and units only become Pooled or Split through `pool_units`
+/// and `split_unit`
+pub fn validate_unit_transition(
+    blood_unit: &BloodUnit,
+    new_status: BloodStatus,
+) -> Result<(), ContractError> {
+    validate_status_transition(blood_unit.status, new_status)?;
+
+    let allowed = match new_status {
+        BloodStatus::Available | BloodStatus::Expired => !blood_unit.autologous,
+        BloodStatus::Autologous => blood_unit.autologous,
+        BloodStatus::Pooled | BloodStatus::Split => false,
+        _ => true,
+    };
+    if !allowed {
+        return Err(ContractError::InvalidStatusTransition);
+    }
+    Ok(())
+}
+
+/// Validate a unit's phenotype tags
+///
+/// At most `MAX_PHENOTYPE_TAGS` tags, each listed once
+pub fn validate_phenotype_tags(tags: &Vec<Symbol>) -> Result<(), ContractError> {
+    if tags.len() > MAX_PHENOTYPE_TAGS {
+        return Err(ContractError::TooManyPhenotypeTags);
+    }
+    for (i, tag) in tags.iter().enumerate() {
+        if tags.last_index_of(&tag) != Some(i as u32) {
+            return Err(ContractError::InvalidInput);
+        }
+    }
+    Ok(())
+}
+
+/// Validate a bank's external reference for a registration
+///
+/// Must not be empty or longer than `MAX_EXTERNAL_REF_LEN`
+pub fn validate_external_ref(external_ref: &String) -> Result<(), ContractError> {
+    if external_ref.is_empty() || external_ref.len() > MAX_EXTERNAL_REF_LEN {
+        return Err(ContractError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Validate a storage location label
+///
+/// Locations are free-form labels chosen by the bank (e.g. "FRIDGE-2/SHELF-A")
+/// but must not be empty
+pub fn validate_location(location: &String) -> Result<(), ContractError> {
+    if location.is_empty() {
+        return Err(ContractError::InvalidInput);
+    }
+    Ok(())
+}
@@ -22,13 +22,10 @@ pub enum ContractError {
     /// Caller is not authorized to perform this action
     Unauthorized = 2,
 
-    // ========== Validation errors (10-19) ==========
-    /// Invalid amount specified
-    InvalidAmount = 10,
-
-    /// Invalid address provided
-    InvalidAddress = 11,
+    /// Operation is paused by the admin
+    ContractPaused = 3,
 
+    // ========== Validation errors (10-19) ==========
     /// Invalid input parameter
     InvalidInput = 12,
 
@@ -50,7 +47,7 @@ pub enum ContractError {
     /// Invalid urgency level
     InvalidUrgency = 18,
 
-    /// Delivery address is empty or invalid
+    /// Delivery address is empty or only whitespace
     InvalidDeliveryAddress = 19,
 
     // ========== State errors (20-29) ==========
@@ -69,19 +66,34 @@ pub enum ContractError {
     /// Duplicate request detected
     DuplicateRequest = 24,
 
-    // ========== Permission errors (30-39) ==========
-    /// Insufficient balance for operation
-    InsufficientBalance = 30,
+    /// No admin transfer has been proposed
+    NoPendingAdmin = 25,
+
+    /// ID counter cannot be incremented any further
+    CounterOverflow = 26,
 
-    /// Insufficient permissions for this action
-    InsufficientPermissions = 31,
+    /// No inventory contract is configured
+    InventoryNotConfigured = 27,
 
+    // ========== Permission errors (30-39) ==========
     /// Hospital is not authorized to make requests
     NotAuthorizedHospital = 32,
 
     /// Only the request creator can perform this action
     NotRequestCreator = 33,
 
+    /// Hospital is temporarily suspended
+    HospitalSuspended = 34,
+
+    /// Critical request needs a co-signer registered by the hospital
+    CoSignerRequired = 35,
+
+    /// Co-signer is not registered as a signer for the hospital
+    NotRequestSigner = 36,
+
+    /// Hospital's grace period for cancelling an approved request has passed
+    CancellationWindowClosed = 37,
+
     // ========== Request-specific errors (40-49) ==========
     /// Request is not in a valid state for this operation
     InvalidRequestState = 40,
@@ -98,45 +110,65 @@ pub enum ContractError {
     /// Insufficient quantity to fulfill request
     InsufficientQuantity = 44,
 
-    /// Request has already been fulfilled
-    AlreadyFulfilled = 45,
-
     /// Units have already been assigned to this request
     UnitsAlreadyAssigned = 46,
 
     /// Request is not yet approved
     RequestNotApproved = 47,
-    // General errors (0-9)
-    AlreadyInitialized = 0,
-    NotInitialized = 1,
-    Unauthorized = 2,
 
-    // Validation errors (10-19)
-    InvalidAmount = 10,
-    InvalidAddress = 11,
-    InvalidInput = 12,
-    InvalidBloodType = 13,
-    InvalidStatus = 14,
-    InvalidTimestamp = 15,
-    InvalidQuantity = 16,
-    InvalidExpiration = 17,
+    /// Request with the given ID does not exist
+    RequestNotFound = 48,
 
-    // State errors (20-29)
-    AlreadyExists = 20,
-    NotFound = 21,
-    Expired = 22,
-    RequestExpired = 23,
-    DuplicateRequest = 24,
+    /// Blood unit type cannot be matched to the request's blood type
+    IncompatibleBloodType = 49,
 
-    // Permission errors (30-39)
-    InsufficientPermissions = 31,
-    NotAuthorizedHospital = 32,
-    NotAuthorizedBloodBank = 33,
+    // ========== Extended validation errors (50-59) ==========
+    /// Delivery address is longer than `MAX_DELIVERY_ADDRESS_LEN`
+    DeliveryAddressTooLong = 50,
 
-    // Request-specific errors (40-49)
-    RequestNotFound = 40,
-    InvalidStatusTransition = 41,
-    RequestAlreadyFulfilled = 42,
-    InsufficientBloodUnits = 43,
-    RequestOverdue = 44,
+    /// Quantity is not a multiple of the configured quantity step
+    QuantityNotAligned = 51,
+
+    /// Blood units are assigned to a request that was never approved
+    UnitsAssignedBeforeApproval = 52,
+
+    /// fulfilled_at is set on an unfulfilled request or precedes created_at
+    InvalidFulfilledAt = 53,
+
+    /// Department is longer than `MAX_DEPARTMENT_LEN`
+    DepartmentTooLong = 54,
+
+    /// Assigned unit lacks a phenotype tag the request requires
+    PhenotypeMismatch = 55,
+
+    /// The same authority already approved this cross-region request
+    ApprovalAlreadyRecorded = 56,
+
+    /// Cross-region approval requested but no regional authority is set
+    RegionalAuthorityNotConfigured = 57,
+
+    /// Cross-region request is still missing the regional authority's approval
+    SecondApprovalRequired = 58,
+
+    /// Unit is held by a request at least as urgent as the preempting one
+    PreemptionNotAllowed = 59,
+
+    // ========== Extended validation errors (60-69) ==========
+    /// Assigned volume exceeds the request's quantity plus the configured
+    /// assignment tolerance
+    OverAssignment = 60,
+
+    /// Physician identifier is longer than `MAX_PHYSICIAN_ID_LEN`
+    PhysicianIdTooLong = 61,
+
+    /// Delivery contact is longer than `MAX_CONTACT_LEN`
+    ContactTooLong = 62,
+
+    // ========== State import errors (70-79) ==========
+    /// State can only be imported into a freshly initialized contract
+    /// before any request is created
+    ImportNotAllowed = 70,
+
+    /// A state import has started and not received its final page yet
+    ImportInProgress = 71,
 }
@@ -1,78 +1,56 @@
+// Events keep their (topics, data) tuple layout, published through the
+// `Events::publish` API that `#[contractevent]` deprecates
+#![allow(deprecated)]
+
 use crate::types::{
-    BloodType, RequestCreatedEvent, RequestStatus, RequestStatusChangedEvent, UnitsAssignedEvent,
-    UrgencyLevel,
+    AdminTransferCancelledEvent, AdminTransferProposedEvent, AdminTransferredEvent,
+    ApprovalAuthority, ApprovalRecordedEvent, ApprovalRevertedEvent, AssignedUnit,
+    AssignedUnitsReleasedEvent, BloodRequest, BloodTypeCount, CompletionOverride,
+    ContactUpdatedEvent, CourierAssignedEvent, DeliveryAddressUpdatedEvent, DeliveryStartedEvent,
+    EtaUpdatedEvent, ExpirySweepCompletedEvent, HospitalAuthorizationChangedEvent,
+    HospitalRenewedEvent, HospitalSuspensionChangedEvent, ImplicitAuthorizationChangedEvent,
+    OrphanedAssignmentsReleasedEvent, RejectionReason, RequestApprovedEvent, RequestCreatedEvent,
+    RequestRejectedEvent, RequestReprioritizedEvent, RequestStatus, RequestStatusChangedEvent,
+    RequiredPhenotypeSetEvent, ReservationPreemptedEvent, UnfulfilledStatsRolloverEvent,
+    UnitsAssignedEvent,
 };
-use soroban_sdk::{Address, Env, Symbol, Vec};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
 /// Emit a RequestCreated event when a new blood request is submitted
 ///
+/// Topics are `("request_created", hospital_id, blood_type)` so subscribers
+/// can filter on one hospital or one blood type; the data is the full
+/// `RequestCreatedEvent`.
+///
 /// # Arguments
 /// * `env` - Contract environment
-/// * `request_id` - Unique ID of the created request
-/// * `hospital_id` - Hospital that created the request
-/// * `blood_type` - Requested blood type
-/// * `quantity_ml` - Quantity requested in milliliters
-/// * `urgency` - Urgency level of the request
-/// * `required_by` - Timestamp when blood is required
-use crate::types::{BloodType, RequestStatus, UrgencyLevel};
-use soroban_sdk::{Address, Env, Symbol};
-
-/// Event emitted when a blood request is created
-#[soroban_sdk::contracttype]
-#[derive(Clone)]
-pub struct RequestCreatedEvent {
-    pub request_id: u64,
-    pub hospital_id: Address,
-    pub blood_type: BloodType,
-    pub quantity_ml: u32,
-    pub urgency: UrgencyLevel,
-    pub required_by: u64,
-    pub created_at: u64,
-}
-
-/// Event emitted when a request status changes
-#[soroban_sdk::contracttype]
-#[derive(Clone)]
-pub struct RequestStatusChangedEvent {
-    pub request_id: u64,
-    pub old_status: RequestStatus,
-    pub new_status: RequestStatus,
-    pub changed_at: u64,
-}
-
-/// Event emitted when blood units are assigned to a request
-#[soroban_sdk::contracttype]
-#[derive(Clone)]
-pub struct UnitsAssignedEvent {
-    pub request_id: u64,
-    pub assigned_units: soroban_sdk::Vec<u64>,
-    pub assigned_at: u64,
-}
-
-/// Emit a RequestCreated event
-pub fn emit_request_created(
-    env: &Env,
-    request_id: u64,
-    hospital_id: &Address,
-    blood_type: BloodType,
-    quantity_ml: u32,
-    urgency: UrgencyLevel,
-    required_by: u64,
-) {
-    let created_at = env.ledger().timestamp();
-
+/// * `request` - The newly created request
+/// * `contact` - Contact at the delivery point, if given
+pub fn emit_request_created(env: &Env, request: &BloodRequest, contact: &Option<String>) {
     let event = RequestCreatedEvent {
-        request_id,
-        hospital_id: hospital_id.clone(),
-        blood_type,
-        quantity_ml,
-        urgency,
-        required_by,
-        created_at,
+        request_id: request.id,
+        hospital_id: request.hospital_id.clone(),
+        blood_type: request.blood_type,
+        quantity_ml: request.quantity_ml,
+        urgency: request.urgency,
+        required_by: request.required_by,
+        co_signer: request.co_signer.clone(),
+        department: request.department.clone(),
+        physician_id: request.physician_id.clone(),
+        pediatric: request.pediatric,
+        cross_region: request.cross_region,
+        contact: contact.clone(),
+        created_at: request.created_at,
     };
 
-    env.events()
-        .publish((Symbol::new(env, "request_created"),), event);
+    env.events().publish(
+        (
+            Symbol::new(env, "request_created"),
+            request.hospital_id.clone(),
+            request.blood_type,
+        ),
+        event,
+    );
 }
 
 /// Emit a RequestStatusChanged event when request status is updated
@@ -82,8 +60,6 @@ pub fn emit_request_created(
 /// * `request_id` - ID of the request
 /// * `old_status` - Previous status
 /// * `new_status` - New status
-pub fn emit_status_changed(
-/// Emit a RequestStatusChanged event
 pub fn emit_request_status_changed(
     env: &Env,
     request_id: u64,
@@ -100,50 +76,285 @@ pub fn emit_request_status_changed(
     };
 
     env.events()
-        .publish((Symbol::new(env, "status_changed"),), event);
+        .publish((Symbol::new(env, "request_status_changed"),), event);
 }
 
-/// Emit an UnitsAssigned event when blood units are assigned to a request
+/// Emit one UnitsAssigned event per source bank when blood units are
+/// assigned to a request
+///
+/// Banks are reported in the order their first unit appears in `units`.
 ///
 /// # Arguments
 /// * `env` - Contract environment
 /// * `request_id` - ID of the request
-/// * `unit_ids` - IDs of the assigned blood units
-/// * `total_quantity_ml` - Total quantity assigned in milliliters
-#[allow(dead_code)]
-pub fn emit_units_assigned(
+/// * `units` - The assigned blood units with their volumes and banks
+pub fn emit_units_assigned(env: &Env, request_id: u64, units: &Vec<AssignedUnit>) {
+    let assigned_at = env.ledger().timestamp();
+
+    let mut banks: Vec<Address> = Vec::new(env);
+    for unit in units.iter() {
+        if !banks.contains(&unit.bank_id) {
+            banks.push_back(unit.bank_id);
+        }
+    }
+
+    for bank_id in banks.iter() {
+        let mut assigned_units = Vec::new(env);
+        let mut bank_units = Vec::new(env);
+        let mut total_ml = 0u32;
+        for unit in units.iter() {
+            if unit.bank_id == bank_id {
+                assigned_units.push_back(unit.unit_id);
+                total_ml += unit.quantity_ml;
+                bank_units.push_back(unit);
+            }
+        }
+
+        let event = UnitsAssignedEvent {
+            request_id,
+            bank_id: bank_id.clone(),
+            assigned_units,
+            units: bank_units,
+            total_ml,
+            assigned_at,
+        };
+
+        env.events()
+            .publish((Symbol::new(env, "units_assigned"), bank_id), event);
+    }
+}
+
+/// Emit a DeliveryAddressUpdated event when a hospital changes the address
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request_id` - ID of the request
+/// * `hospital_id` - Hospital that made the change
+/// * `old_address` - Previous delivery address
+/// * `new_address` - New delivery address
+pub fn emit_delivery_address_updated(
     env: &Env,
     request_id: u64,
-    unit_ids: Vec<u64>,
-    total_quantity_ml: u32,
-        .publish((Symbol::new(env, "request_status_changed"),), event);
+    hospital_id: &Address,
+    old_address: String,
+    new_address: String,
+) {
+    let event = DeliveryAddressUpdatedEvent {
+        request_id,
+        hospital_id: hospital_id.clone(),
+        old_address,
+        new_address,
+        updated_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "delivery_address_updated"),), event);
+}
+
+/// Emit a DeliveryStarted event when a request goes InDelivery
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - The request, already InDelivery
+/// * `contact` - Contact at the delivery point, if any
+pub fn emit_delivery_started(env: &Env, request: &BloodRequest, contact: Option<String>) {
+    let event = DeliveryStartedEvent {
+        request_id: request.id,
+        courier: request.courier.clone(),
+        delivery_address: request.delivery_address.clone(),
+        contact,
+        started_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "delivery_started"), request.id), event);
+}
+
+/// Emit a ContactUpdated event when a hospital changes the contact
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request_id` - ID of the request
+/// * `hospital_id` - Hospital that made the change
+pub fn emit_contact_updated(env: &Env, request_id: u64, hospital_id: &Address) {
+    let event = ContactUpdatedEvent {
+        request_id,
+        hospital_id: hospital_id.clone(),
+        updated_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "contact_updated"),), event);
+}
+
+/// Emit a CourierAssigned event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request_id` - ID of the request
+/// * `courier` - Courier now delivering the request
+pub fn emit_courier_assigned(env: &Env, request_id: u64, courier: &Address) {
+    let event = CourierAssignedEvent {
+        request_id,
+        courier: courier.clone(),
+        assigned_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "courier_assigned"),), event);
 }
 
-/// Emit an UnitsAssigned event
-pub fn emit_units_assigned(
+/// Emit an EtaUpdated event with the previous and new ETA
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request_id` - ID of the request
+/// * `updated_by` - Admin or courier that set the ETA
+/// * `old_eta` - ETA before the update, if one was set
+/// * `new_eta` - New expected arrival
+pub fn emit_eta_updated(
     env: &Env,
     request_id: u64,
-    assigned_units: soroban_sdk::Vec<u64>,
+    updated_by: &Address,
+    old_eta: Option<u64>,
+    new_eta: u64,
 ) {
-    let assigned_at = env.ledger().timestamp();
-
-    let event = UnitsAssignedEvent {
+    let event = EtaUpdatedEvent {
         request_id,
-        unit_ids,
-        total_quantity_ml,
-        assigned_units,
-        assigned_at,
+        old_eta,
+        new_eta,
+        updated_by: updated_by.clone(),
+        updated_at: env.ledger().timestamp(),
     };
 
     env.events()
-        .publish((Symbol::new(env, "units_assigned"),), event);
+        .publish((Symbol::new(env, "eta_updated"),), event);
 }
 
-/// Emit a request approved event
+/// Emit a RequiredPhenotypeSet event when a hospital changes the tags
 ///
-/// This is a simplified event using just the request ID
-pub fn emit_request_approved(env: &Env, request_id: u64) {
-    emit_status_changed(env, request_id, RequestStatus::Pending, RequestStatus::Approved);
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - Request carrying its new required phenotype
+pub fn emit_required_phenotype_set(env: &Env, request: &BloodRequest) {
+    let event = RequiredPhenotypeSetEvent {
+        request_id: request.id,
+        hospital_id: request.hospital_id.clone(),
+        required_phenotype: request.required_phenotype.clone(),
+        updated_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "required_phenotype_set"),), event);
+}
+
+/// Emit a RequestReprioritized event after an escalation or deadline change
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - Request with its updated urgency, deadline and score
+pub fn emit_request_reprioritized(env: &Env, request: &BloodRequest) {
+    let event = RequestReprioritizedEvent {
+        request_id: request.id,
+        urgency: request.urgency,
+        required_by: request.required_by,
+        priority_score: request.priority_score,
+        updated_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "request_reprioritized"),), event);
+}
+
+/// Emit a RequestApproved event naming the approver of a request
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - The approved request, carrying its approver
+pub fn emit_request_approved(env: &Env, request: &BloodRequest) {
+    let Some(approved_by) = request.approved_by.clone() else {
+        return;
+    };
+
+    let event = RequestApprovedEvent {
+        request_id: request.id,
+        hospital_id: request.hospital_id.clone(),
+        approved_by,
+        approved_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "request_approved"),), event);
+}
+
+/// Emit an ApprovalRecorded event for one approval of a cross-region request
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - Request carrying its updated approvals
+/// * `authority` - Authority that approved
+/// * `approver` - Address that signed the approval
+pub fn emit_approval_recorded(
+    env: &Env,
+    request: &BloodRequest,
+    authority: ApprovalAuthority,
+    approver: &Address,
+) {
+    let event = ApprovalRecordedEvent {
+        request_id: request.id,
+        authority,
+        approver: approver.clone(),
+        approvals: request.approvals,
+        recorded_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "approval_recorded"),), event);
+}
+
+/// Emit a RequestRejected event with the reason of a rejection
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - The rejected request
+/// * `reason` - Why the request was rejected
+pub fn emit_request_rejected(env: &Env, request: &BloodRequest, reason: RejectionReason) {
+    let event = RequestRejectedEvent {
+        request_id: request.id,
+        hospital_id: request.hospital_id.clone(),
+        reason,
+        rejected_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "request_rejected"),), event);
+}
+
+/// Emit a ReservationPreempted event naming the displaced and the new holder
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `unit_id` - Blood unit that changed hands
+/// * `from_request_id` - Request that lost the unit
+/// * `to_request_id` - Critical request that took it over
+/// * `waitlisted` - Whether the displaced request was waitlisted
+pub fn emit_reservation_preempted(
+    env: &Env,
+    unit_id: u64,
+    from_request_id: u64,
+    to_request_id: u64,
+    waitlisted: bool,
+) {
+    let event = ReservationPreemptedEvent {
+        unit_id,
+        from_request_id,
+        to_request_id,
+        waitlisted,
+        preempted_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "reservation_preempted"),), event);
 }
 
 /// Emit a request cancelled event
@@ -153,7 +364,51 @@ pub fn emit_request_approved(env: &Env, request_id: u64) {
 /// * `request_id` - ID of the cancelled request
 /// * `previous_status` - Status before cancellation
 pub fn emit_request_cancelled(env: &Env, request_id: u64, previous_status: RequestStatus) {
-    emit_status_changed(env, request_id, previous_status, RequestStatus::Cancelled);
+    emit_request_status_changed(env, request_id, previous_status, RequestStatus::Cancelled);
+}
+
+/// Emit an AssignedUnitsReleased event when a cancelled request gives up
+/// the units assigned to it
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - The cancelled request
+/// * `cancelled_by` - Hospital or admin that cancelled it
+pub fn emit_assigned_units_released(env: &Env, request: &BloodRequest, cancelled_by: &Address) {
+    let event = AssignedUnitsReleasedEvent {
+        request_id: request.id,
+        unit_ids: request.assigned_units.clone(),
+        cancelled_by: cancelled_by.clone(),
+        released_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "assigned_units_released"),), event);
+}
+
+/// Emit an OrphanedAssignmentsReleased event once the admin has cleared
+/// the assignment of a closed request
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request_id` - ID of the cancelled or expired request
+/// * `unit_ids` - Units that were still assigned to it
+/// * `released_by` - Admin that cleared the assignment
+pub fn emit_orphaned_assignments_released(
+    env: &Env,
+    request_id: u64,
+    unit_ids: Vec<u64>,
+    released_by: &Address,
+) {
+    let event = OrphanedAssignmentsReleasedEvent {
+        request_id,
+        unit_ids,
+        released_by: released_by.clone(),
+        released_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "orphaned_assignments_released"),), event);
 }
 
 /// Emit a request expired event
@@ -162,18 +417,256 @@ pub fn emit_request_cancelled(env: &Env, request_id: u64, previous_status: Reque
 /// * `env` - Contract environment
 /// * `request_id` - ID of the expired request
 /// * `previous_status` - Status before expiration
-#[allow(dead_code)]
 pub fn emit_request_expired(env: &Env, request_id: u64, previous_status: RequestStatus) {
-    emit_status_changed(env, request_id, previous_status, RequestStatus::Expired);
+    emit_request_status_changed(env, request_id, previous_status, RequestStatus::Expired);
 }
 
 /// Emit a request completed event
-#[allow(dead_code)]
 pub fn emit_request_completed(env: &Env, request_id: u64) {
-    emit_status_changed(
+    emit_request_status_changed(
         env,
         request_id,
         RequestStatus::InDelivery,
         RequestStatus::Completed,
     );
 }
+
+/// Emit a ForcedCompletion event when the admin overrides the normal flow
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `record` - Audit record of the override
+pub fn emit_forced_completion(env: &Env, record: &CompletionOverride) {
+    env.events()
+        .publish((Symbol::new(env, "forced_completion"),), record.clone());
+}
+
+/// Emit an ApprovalReverted event when an approval is undone
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request_id` - ID of the request sent back to Pending
+/// * `admin` - Admin who reverted the approval
+/// * `justification` - Why the approval was reverted
+pub fn emit_approval_reverted(env: &Env, request_id: u64, admin: &Address, justification: &String) {
+    emit_request_status_changed(
+        env,
+        request_id,
+        RequestStatus::Approved,
+        RequestStatus::Pending,
+    );
+
+    let event = ApprovalRevertedEvent {
+        request_id,
+        admin: admin.clone(),
+        justification: justification.clone(),
+        reverted_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "approval_reverted"),), event);
+}
+
+/// Emit an UnfulfilledStatsRollover event when a statistics epoch closes
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `epoch` - Epoch that just closed
+/// * `counts` - Unfulfilled expiries per blood type in that epoch
+/// * `total` - Sum of all counts
+pub fn emit_unfulfilled_stats_rollover(
+    env: &Env,
+    epoch: u64,
+    counts: Vec<BloodTypeCount>,
+    total: u32,
+) {
+    let event = UnfulfilledStatsRolloverEvent {
+        epoch,
+        counts,
+        total,
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "unfulfilled_stats_rollover"),), event);
+}
+
+/// Emit an ExpirySweepCompleted event at the end of an expiry sweep
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `expired_ids` - IDs expired by the sweep
+/// * `more_remaining` - Whether the limit left overdue requests behind
+pub fn emit_expiry_sweep_completed(env: &Env, expired_ids: &Vec<u64>, more_remaining: bool) {
+    let mut first_id = 0;
+    let mut last_id = 0;
+    for id in expired_ids.iter() {
+        if first_id == 0 || id < first_id {
+            first_id = id;
+        }
+        last_id = last_id.max(id);
+    }
+
+    let event = ExpirySweepCompletedEvent {
+        expired_count: expired_ids.len(),
+        first_id,
+        last_id,
+        more_remaining,
+        swept_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "expiry_sweep_completed"),), event);
+}
+
+/// Emit an AdminTransferProposed event under the `admin_proposed` topic
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `current_admin` - Admin proposing the transfer
+/// * `proposed_admin` - Address that must accept the role
+pub fn emit_admin_transfer_proposed(env: &Env, current_admin: &Address, proposed_admin: &Address) {
+    let event = AdminTransferProposedEvent {
+        current_admin: current_admin.clone(),
+        proposed_admin: proposed_admin.clone(),
+        proposed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "admin_proposed"),), event);
+}
+
+/// Emit an AdminTransferCancelled event
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `admin` - Admin withdrawing the proposal
+/// * `cancelled_admin` - Address that was proposed
+pub fn emit_admin_transfer_cancelled(env: &Env, admin: &Address, cancelled_admin: &Address) {
+    let event = AdminTransferCancelledEvent {
+        admin: admin.clone(),
+        cancelled_admin: cancelled_admin.clone(),
+        cancelled_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "admin_transfer_cancelled"),), event);
+}
+
+/// Emit an AdminTransferred event under the `admin_changed` topic
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `old_admin` - Previous admin
+/// * `new_admin` - Admin that accepted the role
+pub fn emit_admin_transferred(env: &Env, old_admin: &Address, new_admin: &Address) {
+    let event = AdminTransferredEvent {
+        old_admin: old_admin.clone(),
+        new_admin: new_admin.clone(),
+        transferred_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "admin_changed"),), event);
+}
+
+/// Emit a HospitalAuthorized or HospitalRevoked event
+///
+/// Topics are `(name, hospital)`.
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `hospital` - Hospital whose authorization changed
+/// * `admin` - Admin that made the change
+/// * `authorized` - Whether the hospital is now authorized
+pub fn emit_hospital_authorization_changed(
+    env: &Env,
+    hospital: &Address,
+    admin: &Address,
+    authorized: bool,
+) {
+    let event = HospitalAuthorizationChangedEvent {
+        hospital: hospital.clone(),
+        admin: admin.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    let name = if authorized {
+        "hospital_authorized"
+    } else {
+        "hospital_revoked"
+    };
+    env.events()
+        .publish((Symbol::new(env, name), hospital.clone()), event);
+}
+
+/// Emit a HospitalRenewed event when a hospital's authorization is extended
+///
+/// Topics are `("hospital_renewed", hospital)`.
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `hospital` - Hospital whose authorization was extended
+/// * `admin` - Admin that made the change
+/// * `valid_until` - When the authorization now lapses
+pub fn emit_hospital_renewed(env: &Env, hospital: &Address, admin: &Address, valid_until: u64) {
+    let event = HospitalRenewedEvent {
+        hospital: hospital.clone(),
+        admin: admin.clone(),
+        valid_until,
+        renewed_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        (Symbol::new(env, "hospital_renewed"), hospital.clone()),
+        event,
+    );
+}
+
+/// Emit a HospitalSuspended or HospitalSuspensionLifted event
+///
+/// Topics are `(name, hospital)`.
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `hospital` - Hospital whose suspension changed
+/// * `admin` - Admin that made the change
+/// * `suspended_until` - End of the new suspension, or None when lifted
+pub fn emit_hospital_suspension_changed(
+    env: &Env,
+    hospital: &Address,
+    admin: &Address,
+    suspended_until: Option<u64>,
+) {
+    let event = HospitalSuspensionChangedEvent {
+        hospital: hospital.clone(),
+        admin: admin.clone(),
+        suspended_until,
+        changed_at: env.ledger().timestamp(),
+    };
+
+    let name = if suspended_until.is_some() {
+        "hospital_suspended"
+    } else {
+        "hospital_suspension_lifted"
+    };
+    env.events()
+        .publish((Symbol::new(env, name), hospital.clone()), event);
+}
+
+/// Emit an ImplicitAuthorizationChanged event for a hospital authorization
+/// gained or lost through the admin role
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `account` - Address whose authorization changed
+/// * `authorized` - Whether the address is now authorized
+pub fn emit_implicit_authorization_changed(env: &Env, account: &Address, authorized: bool) {
+    let event = ImplicitAuthorizationChangedEvent {
+        account: account.clone(),
+        authorized,
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "implicit_hospital_auth_changed"),), event);
+}
@@ -1,24 +1,49 @@
 #![no_std]
+// `create_request` takes more arguments than clippy allows, and the argument
+// builder `#[contractimpl]` generates for it does not carry a function-level allow
+#![allow(clippy::too_many_arguments)]
 
 mod error;
 mod events;
 mod storage;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 mod types;
 mod validation;
 
 pub use crate::error::ContractError;
 pub use crate::types::{
-    BloodRequest, BloodType, DataKey, RequestCreatedEvent, RequestStatus,
-    RequestStatusChangedEvent, UnitsAssignedEvent, UrgencyLevel,
+    AdminTransferCancelledEvent, AdminTransferProposedEvent, AdminTransferredEvent,
+    ApprovalAuthority, ApprovalRecordedEvent, ApprovalRevertedEvent, AssignedUnit,
+    AssignmentTolerance, BloodRequest, BloodType, BloodTypeCount, CandidateUnit,
+    CompletionOverride, ContractVersion, CourierAssignedEvent, DataKey,
+    DeliveryAddressUpdatedEvent, DemandStats, EtaUpdatedEvent, ExpiryReason,
+    ExpirySweepCompletedEvent, ExportedConfig, ExportedHospital, ExportedRequest,
+    HospitalAuthorizationChangedEvent, HospitalEntry, HospitalRenewedEvent, HospitalReport,
+    HospitalScore, HospitalSuspensionChangedEvent, IdempotencyRecord,
+    ImplicitAuthorizationChangedEvent, ImportState, OrphanedAssignmentsReleasedEvent, PauseFlags,
+    PendingQueueEntry, RejectionReason, RequestCreatedEvent, RequestMetadata, RequestOptions,
+    RequestReprioritizedEvent, RequestStatus, RequestStatusChangedEvent, RequestSummary,
+    ReservationPreemptedEvent, StateExportPage, UnfulfilledStatsRolloverEvent, UnitAssignment,
+    UnitsAssignedEvent, UrgencyLevel, BLOOD_REQUEST_SCHEMA,
 };
 
-use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Vec};
-use crate::error::ContractError;
-use crate::types::{BloodRequest, BloodType, RequestMetadata, RequestStatus, UrgencyLevel};
+use soroban_sdk::{
+    contract, contractimpl, vec, Address, BytesN, Env, IntoVal, Map, String, Symbol, Vec,
+};
 
 // Pagination constants
 const DEFAULT_QUERY_LIMIT: u32 = 50;
 const MAX_QUERY_LIMIT: u32 = 200;
+const MAX_RANGE_LIMIT: u32 = 50;
+const MAX_RECENT_REQUESTS: u32 = 25;
+const MAX_HOSPITAL_PAGE_LIMIT: u32 = 50;
+
+/// Maximum number of units `propose_allocation` considers
+pub const MAX_CANDIDATE_UNITS: u32 = 100;
+
+/// Maximum number of hospitals that can be authorized by `initialize`
+pub const MAX_INITIAL_HOSPITALS: u32 = 20;
 
 #[contract]
 pub struct RequestContract;
@@ -30,10 +55,20 @@ impl RequestContract {
     /// # Arguments
     /// * `env` - Contract environment
     /// * `admin` - Admin address who can manage hospitals and approve requests
+    /// * `hospitals` - Hospitals to authorize straight away (at most
+    ///   `MAX_INITIAL_HOSPITALS`, may be empty)
+    /// * `id_offset` - Request IDs start at `id_offset + 1` (0 for the
+    ///   default); lets fixtures and migrations use non-colliding ID spaces
     ///
     /// # Errors
     /// - `AlreadyInitialized`: Contract has already been initialized
-    pub fn initialize(env: Env, admin: Address) -> Result<(), ContractError> {
+    /// - `InvalidInput`: Too many initial hospitals
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        hospitals: Vec<Address>,
+        id_offset: u64,
+    ) -> Result<(), ContractError> {
         admin.require_auth();
 
         // Check if already initialized
@@ -41,46 +76,73 @@ impl RequestContract {
             return Err(ContractError::AlreadyInitialized);
         }
 
+        if hospitals.len() > MAX_INITIAL_HOSPITALS {
+            return Err(ContractError::InvalidInput);
+        }
+
         // Set admin
         storage::set_admin(&env, &admin);
+        storage::set_version(&env, &storage::CONTRACT_VERSION);
+        storage::set_request_counter(&env, id_offset);
+        storage::set_import_state(&env, ImportState::Open);
+
+        // Seed hospitals authorized at deploy time
+        for hospital in hospitals.iter() {
+            storage::authorize_hospital(&env, &hospital);
+            events::emit_hospital_authorization_changed(&env, &hospital, &admin, true);
+        }
 
         Ok(())
     }
 
-    /// Authorize a hospital to create blood requests
+    /// Pause or resume groups of operations independently
     ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `hospital` - Hospital address to authorize
+    /// `create_paused` blocks new requests, `approve_paused` blocks
+    /// approvals, and `fulfill_paused` blocks unit assignment and moves to
+    /// Fulfilled, InDelivery or Completed. Cancellation, expiry and the
+    /// admin's `force_complete` are never paused.
     ///
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
-    /// - `Unauthorized`: Caller is not admin
-    pub fn authorize_hospital(env: Env, hospital: Address) -> Result<(), ContractError> {
-        // Check contract is initialized
+    pub fn set_pause_flags(
+        env: Env,
+        create_paused: bool,
+        approve_paused: bool,
+        fulfill_paused: bool,
+    ) -> Result<(), ContractError> {
         if !storage::is_initialized(&env) {
             return Err(ContractError::NotInitialized);
         }
 
-        // Only admin can authorize hospitals
         let admin = storage::get_admin(&env);
         admin.require_auth();
 
-        storage::authorize_hospital(&env, &hospital);
+        storage::set_pause_flags(
+            &env,
+            &PauseFlags {
+                create_paused,
+                approve_paused,
+                fulfill_paused,
+            },
+        );
 
         Ok(())
     }
 
-    /// Revoke hospital authorization
+    /// Get the current pause switches
+    pub fn get_pause_flags(env: Env) -> PauseFlags {
+        storage::get_pause_flags(&env)
+    }
+
+    /// Require new request quantities to be a multiple of `step_ml`
     ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `hospital` - Hospital address to revoke
+    /// Whole blood comes in ~450 ml bags, so odd volumes are usually typos.
+    /// A step of 0 (the default) disables the check. Existing requests are
+    /// not affected.
     ///
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
-    /// - `Unauthorized`: Caller is not admin
-    pub fn revoke_hospital(env: Env, hospital: Address) -> Result<(), ContractError> {
+    pub fn set_quantity_step(env: Env, step_ml: u32) -> Result<(), ContractError> {
         if !storage::is_initialized(&env) {
             return Err(ContractError::NotInitialized);
         }
@@ -88,312 +150,2411 @@ impl RequestContract {
         let admin = storage::get_admin(&env);
         admin.require_auth();
 
-        storage::revoke_hospital(&env, &hospital);
+        storage::set_quantity_step(&env, step_ml);
 
         Ok(())
     }
 
-    /// Create a new blood request
-    ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `hospital_id` - Hospital requesting blood (must be authorized)
-    /// * `blood_type` - Type of blood requested
-    /// * `quantity_ml` - Quantity in milliliters (50-5000ml)
-    /// * `urgency` - Urgency level (Critical, Urgent, Normal)
-    /// * `required_by` - Unix timestamp when blood is required
-    /// * `delivery_address` - Address where blood should be delivered
-    /// * `patient_id` - Patient address/identifier
-    /// * `procedure` - Medical procedure requiring blood
-    /// * `notes` - Additional notes or requirements
+    /// Get the configured quantity step in ml (0 when disabled)
+    pub fn get_quantity_step(env: Env) -> u32 {
+        storage::get_quantity_step(&env)
+    }
+
+    /// Set the smallest quantity a pediatric request may ask for
     ///
-    /// # Returns
-    /// Unique ID of the created request
+    /// Neonatal transfusions use 10-50 ml aliquots, below the adult minimum.
+    /// Defaults to `DEFAULT_PEDIATRIC_MIN_QUANTITY_ML`. Existing requests are
+    /// not affected.
     ///
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
-    /// - `NotAuthorizedHospital`: Hospital is not authorized
-    /// - `InvalidQuantity`: Quantity outside acceptable range
-    /// - `InvalidTimestamp`: Required_by timestamp is invalid
-    /// - `InvalidInput`: Delivery address is empty
-    ///
-    /// # Events
-    /// Emits `RequestCreated` event with all request details
-    pub fn create_request(
-        env: Env,
-        hospital_id: Address,
-        blood_type: BloodType,
-        quantity_ml: u32,
-        urgency: UrgencyLevel,
-        required_by: u64,
-        delivery_address: String,
-        patient_id: Address,
-        procedure: String,
-        notes: String,
-    ) -> Result<u64, ContractError> {
-        // 1. Verify hospital authentication
-        hospital_id.require_auth();
-
-        // 2. Check contract is initialized
+    /// - `InvalidQuantity`: Minimum is below `MIN_PEDIATRIC_QUANTITY_ML` or
+    ///   above the adult minimum
+    pub fn set_pediatric_min_quantity(env: Env, min_ml: u32) -> Result<(), ContractError> {
         if !storage::is_initialized(&env) {
             return Err(ContractError::NotInitialized);
         }
 
-        // 3. Verify hospital is authorized
-        if !storage::is_authorized_hospital(&env, &hospital_id) {
-            return Err(ContractError::NotAuthorizedHospital);
-        }
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
 
-        // 4. Validate request parameters
-        validation::validate_request_creation(&env, quantity_ml, required_by)?;
-        validation::validate_delivery_address(&delivery_address)?;
-        validation::validate_blood_type(&blood_type)?;
+        if !(validation::MIN_PEDIATRIC_QUANTITY_ML..=validation::MIN_REQUEST_QUANTITY_ML)
+            .contains(&min_ml)
+        {
+            return Err(ContractError::InvalidQuantity);
+        }
 
-        // 5. Validate urgency-specific time window
-        validation::validate_urgency_time_window(&env, required_by, urgency.priority_weight())?;
+        storage::set_pediatric_min_quantity(&env, min_ml);
 
-        // 6. Generate request ID
-        let request_id = storage::increment_request_id(&env);
-        let current_time = env.ledger().timestamp();
+        Ok(())
+    }
 
-        // 7. Create request metadata
-        let metadata = RequestMetadata {
-            patient_id,
-            procedure,
-            notes,
-        };
+    /// Get the pediatric minimum quantity in ml
+    pub fn get_pediatric_min_quantity(env: Env) -> u32 {
+        storage::get_pediatric_min_quantity(&env)
+    }
 
-        // 8. Create blood request
-        let request = BloodRequest {
-            id: request_id,
-            hospital_id: hospital_id.clone(),
-            blood_type,
-            quantity_ml,
-            urgency,
-            status: RequestStatus::Pending,
-            created_at: current_time,
-            required_by,
-            fulfilled_at: None,
-            assigned_units: Vec::new(&env),
-            delivery_address,
-            metadata: Map::new(&env),
-        };
+    /// Set how long after approval a hospital may still cancel its request
+    ///
+    /// Defaults to `DEFAULT_CANCELLATION_GRACE_SECONDS` (1 hour); 0 leaves
+    /// cancelling approved requests to the admin alone.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidInput`: Period exceeds `MAX_CANCELLATION_GRACE_SECONDS`
+    pub fn set_cancellation_grace_period(env: Env, seconds: u64) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
 
-        // 9. Validate the complete request
-        request.validate(current_time)?;
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
 
-        // 10. Store request
-        storage::set_blood_request(&env, &request);
+        if seconds > validation::MAX_CANCELLATION_GRACE_SECONDS {
+            return Err(ContractError::InvalidInput);
+        }
 
-        // 11. Update indexes for efficient querying
-        storage::add_to_hospital_index(&env, &request);
-        storage::add_to_blood_type_index(&env, &request);
-        storage::add_to_status_index(&env, &request);
-        storage::add_to_urgency_index(&env, &request);
+        storage::set_cancellation_grace_period(&env, seconds);
 
-        // 12. Emit event
-        events::emit_request_created(
-            &env,
-            request_id,
-            &hospital_id,
-            blood_type,
-            quantity_ml,
-            urgency,
-            required_by,
-        );
+        Ok(())
+    }
 
-        // 13. Return request ID
-        Ok(request_id)
+    /// Get the cancellation grace period in seconds
+    pub fn get_cancellation_grace_period(env: Env) -> u64 {
+        storage::get_cancellation_grace_period(&env)
     }
 
-    /// Get blood request details by ID
-    ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `request_id` - ID of the request to retrieve
+    /// Choose whether fully assigned requests complete on their own
     ///
-    /// # Returns
-    /// Blood request details
+    /// When enabled, an assignment that brings an InDelivery request's
+    /// assigned volume to its requested quantity completes the request in
+    /// the same call. Disabled by default, leaving completion to
+    /// `complete_request`.
     ///
     /// # Errors
-    /// - `RequestNotFound`: Request with given ID doesn't exist
-    pub fn get_request(env: Env, request_id: u64) -> Result<BloodRequest, ContractError> {
-        storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_auto_complete(env: Env, enabled: bool) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_auto_complete(&env, enabled);
+
+        Ok(())
     }
 
-    /// Approve a pending blood request
+    /// Check whether fully assigned requests complete on their own
+    pub fn is_auto_complete(env: Env) -> bool {
+        storage::is_auto_complete(&env)
+    }
+
+    /// Cap how much more than a request's quantity may be assigned to it
     ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `request_id` - ID of the request to approve
+    /// Checked by `assign_blood_units` when units are assigned; requests
+    /// assigned earlier keep their units. Unlimited by default.
     ///
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
-    /// - `NotFound`: Request doesn't exist
-    /// - `InvalidStatusTransition`: Request is not in Pending status
-    pub fn approve_request(env: Env, request_id: u64) -> Result<(), ContractError> {
+    /// - `InvalidInput`: Percentage above 100
+    pub fn set_assignment_tolerance(
+        env: Env,
+        tolerance: AssignmentTolerance,
+    ) -> Result<(), ContractError> {
         if !storage::is_initialized(&env) {
             return Err(ContractError::NotInitialized);
         }
 
-        // Only admin can approve
         let admin = storage::get_admin(&env);
         admin.require_auth();
 
-        // Get request
-        let mut request =
-            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
-
-        // Check valid transition
-        if !request.status.can_transition_to(&RequestStatus::Approved) {
-            return Err(ContractError::InvalidStatusTransition);
+        if let AssignmentTolerance::Percent(percent) = tolerance {
+            if percent > 100 {
+                return Err(ContractError::InvalidInput);
+            }
         }
 
-        // Check not expired
-        validation::validate_not_expired(&env, request.required_by)?;
-
-        // Update status
-        let old_status = request.status;
-        request.status = RequestStatus::Approved;
-
-        // Save and update index
-        storage::set_blood_request(&env, &request);
-        storage::update_status_index(&env, request_id, old_status, RequestStatus::Approved);
+        storage::set_assignment_tolerance(&env, tolerance);
 
-        // Emit event
-        events::emit_request_approved(&env, request_id);
-        
         Ok(())
     }
 
-    /// Update request status
+    /// Get the assignment tolerance, for clients to check assignments
+    /// up front
+    pub fn get_assignment_tolerance(env: Env) -> AssignmentTolerance {
+        storage::get_assignment_tolerance(&env)
+    }
+
+    /// Propose a new admin (step one of the admin transfer)
     ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `request_id` - ID of request to update
-    /// * `new_status` - New status for the request
+    /// The proposal replaces any earlier one and takes effect only once the
+    /// proposed address calls `accept_admin`. Emits `admin_proposed`, after
+    /// `admin_transfer_cancelled` when a different proposal was replaced.
     ///
     /// # Errors
-    /// - `RequestNotFound`: Request does not exist
-    /// - `InvalidStatusTransition`: Status transition is not allowed
-    /// - `Unauthorized`: Caller is not authorized
-    pub fn update_request_status(
-        env: Env,
-        request_id: u64,
-        new_status: RequestStatus,
-    ) -> Result<(), ContractError> {
+    /// - `NotInitialized`: Contract not initialized
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
         let admin = storage::get_admin(&env);
         admin.require_auth();
 
-        // Get existing request
-        let mut request = storage::get_blood_request(&env, request_id)
-            .ok_or(ContractError::RequestNotFound)?;
-
-        // Validate status transition
-        if !request.status.can_transition_to(&new_status) {
-            return Err(ContractError::InvalidStatusTransition);
+        if let Some(pending) = storage::get_pending_admin(&env) {
+            if pending != new_admin {
+                events::emit_admin_transfer_cancelled(&env, &admin, &pending);
+            }
         }
 
-        let old_status = request.status;
-        request.status = new_status;
+        storage::set_pending_admin(&env, &new_admin);
+        events::emit_admin_transfer_proposed(&env, &admin, &new_admin);
 
-        // Set fulfilled_at if transitioning to Fulfilled
-        if new_status == RequestStatus::Fulfilled {
-            request.fulfilled_at = Some(env.ledger().timestamp());
+        Ok(())
+    }
+
+    /// Withdraw the pending admin proposal
+    ///
+    /// Emits `admin_transfer_cancelled`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NoPendingAdmin`: No transfer has been proposed
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
         }
 
-        // Store updated request
-        storage::set_blood_request(&env, &request);
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
 
-        // Emit event
-        events::emit_request_status_changed(&env, request_id, old_status, new_status);
+        let pending = storage::get_pending_admin(&env).ok_or(ContractError::NoPendingAdmin)?;
+        storage::clear_pending_admin(&env);
+        events::emit_admin_transfer_cancelled(&env, &admin, &pending);
 
         Ok(())
     }
 
-    /// Cancel a blood request
+    /// Accept a pending admin proposal (step two of the admin transfer)
     ///
-    /// Can be called by the hospital that created the request or by admin
-    ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `request_id` - ID of the request to cancel
-    /// * `caller` - Address of the caller (hospital or admin)
+    /// The admin is implicitly an authorized hospital. That authorization
+    /// follows the role: the old admin loses it unless it was also
+    /// authorized explicitly with `authorize_hospital`, and the new admin
+    /// gains it. Emits `admin_changed`, then an
+    /// `implicit_hospital_auth_changed` event for each implicit change.
     ///
     /// # Errors
-    /// - `NotFound`: Request doesn't exist
-    /// - `CannotCancelRequest`: Request cannot be cancelled in current state
-    /// - `Unauthorized`: Caller is not hospital owner or admin
-    pub fn cancel_request(env: Env, request_id: u64, caller: Address) -> Result<(), ContractError> {
-        caller.require_auth();
-
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NoPendingAdmin`: No transfer has been proposed
+    pub fn accept_admin(env: Env) -> Result<(), ContractError> {
         if !storage::is_initialized(&env) {
             return Err(ContractError::NotInitialized);
         }
 
-        // Get request
-        let mut request =
-            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
+        let new_admin = storage::get_pending_admin(&env).ok_or(ContractError::NoPendingAdmin)?;
+        new_admin.require_auth();
 
-        // Check caller is authorized (hospital owner or admin)
-        let admin = storage::get_admin(&env);
-        if caller != request.hospital_id && caller != admin {
-            return Err(ContractError::Unauthorized);
-        }
+        let old_admin = storage::get_admin(&env);
+        storage::set_admin(&env, &new_admin);
+        storage::clear_pending_admin(&env);
 
-        // Check can cancel
-        if !request.status.can_cancel() {
-            return Err(ContractError::CannotCancelRequest);
+        events::emit_admin_transferred(&env, &old_admin, &new_admin);
+
+        if old_admin != new_admin {
+            if !storage::is_explicitly_authorized_hospital(&env, &old_admin) {
+                events::emit_implicit_authorization_changed(&env, &old_admin, false);
+            }
+            if !storage::is_explicitly_authorized_hospital(&env, &new_admin) {
+                events::emit_implicit_authorization_changed(&env, &new_admin, true);
+            }
         }
 
-        // Update status
-        let old_status = request.status;
-        request.status = RequestStatus::Cancelled;
+        Ok(())
+    }
 
-        // Save and update index
-        storage::set_blood_request(&env, &request);
-        storage::update_status_index(&env, request_id, old_status, RequestStatus::Cancelled);
+    /// Get the current admin
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn get_admin(env: Env) -> Result<Address, ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+        Ok(storage::get_admin(&env))
+    }
 
-        // Emit event
-        events::emit_request_cancelled(&env, request_id, old_status);
+    /// Get the version of the contract, so clients can tell which struct
+    /// layouts it serves
+    ///
+    /// None before `initialize`.
+    pub fn get_version(env: Env) -> Option<ContractVersion> {
+        storage::get_version(&env)
+    }
 
-        Ok(())
+    /// Get the admin proposed by the current admin, if any
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        storage::get_pending_admin(&env)
     }
 
-    /// Assign blood units to a request
+    /// Authorize a hospital to create blood requests
     ///
     /// # Arguments
     /// * `env` - Contract environment
-    /// * `request_id` - ID of request
-    /// * `unit_ids` - Vector of blood unit IDs to assign
+    /// * `hospital` - Hospital address to authorize
+    /// * `valid_until` - When the authorization lapses, or None for no
+    ///   limit; re-authorizing replaces any earlier limit
+    ///
+    /// # Events
+    /// Emits `hospital_authorized`
     ///
     /// # Errors
-    /// - `RequestNotFound`: Request does not exist
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is not admin
+    /// - `InvalidTimestamp`: `valid_until` is not in the future
+    pub fn authorize_hospital(
+        env: Env,
+        hospital: Address,
+        valid_until: Option<u64>,
+    ) -> Result<(), ContractError> {
+        // Check contract is initialized
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        // Only admin can authorize hospitals
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if valid_until.is_some_and(|valid_until| valid_until <= env.ledger().timestamp()) {
+            return Err(ContractError::InvalidTimestamp);
+        }
+
+        storage::authorize_hospital(&env, &hospital);
+        storage::set_hospital_valid_until(&env, &hospital, valid_until);
+        events::emit_hospital_authorization_changed(&env, &hospital, &admin, true);
+
+        Ok(())
+    }
+
+    /// Revoke hospital authorization
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital` - Hospital address to revoke
+    ///
+    /// # Events
+    /// Emits `hospital_revoked`
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is not admin
+    pub fn revoke_hospital(env: Env, hospital: Address) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::revoke_hospital(&env, &hospital);
+        events::emit_hospital_authorization_changed(&env, &hospital, &admin, false);
+
+        Ok(())
+    }
+
+    /// Extend a hospital's authorization to a new lapse time
+    ///
+    /// Works whether or not the previous validity has already elapsed, so a
+    /// lapsed hospital can be brought back without re-authorizing it.
+    /// Emits `hospital_renewed`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedHospital`: Hospital was never authorized or was revoked
+    /// - `InvalidTimestamp`: `new_valid_until` is not in the future
+    pub fn renew_hospital(
+        env: Env,
+        hospital: Address,
+        new_valid_until: u64,
+    ) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if !storage::is_explicitly_authorized_hospital(&env, &hospital) {
+            return Err(ContractError::NotAuthorizedHospital);
+        }
+
+        if new_valid_until <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidTimestamp);
+        }
+
+        storage::set_hospital_valid_until(&env, &hospital, Some(new_valid_until));
+        events::emit_hospital_renewed(&env, &hospital, &admin, new_valid_until);
+
+        Ok(())
+    }
+
+    /// Suspend a hospital until a given time without revoking it
+    ///
+    /// While suspended the hospital cannot create requests. The suspension
+    /// lifts itself once `until` has passed; calling again replaces the end
+    /// time. Emits `hospital_suspended`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidTimestamp`: `until` is not in the future
+    pub fn suspend_hospital(env: Env, hospital: Address, until: u64) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if until <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidTimestamp);
+        }
+
+        storage::set_suspension(&env, &hospital, until);
+        events::emit_hospital_suspension_changed(&env, &hospital, &admin, Some(until));
+
+        Ok(())
+    }
+
+    /// Lift a hospital's suspension early
+    ///
+    /// Emits `hospital_suspension_lifted`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn lift_suspension(env: Env, hospital: Address) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::clear_suspension(&env, &hospital);
+        events::emit_hospital_suspension_changed(&env, &hospital, &admin, None);
+
+        Ok(())
+    }
+
+    /// Configure the inventory contract that holds units reserved for requests
+    ///
+    /// Once set, cancelling or expiring a request releases its reserved
+    /// units there. The inventory contract must in turn list this contract
+    /// as its request contract.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_inventory_contract(env: Env, inventory: Address) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_inventory_contract(&env, &inventory);
+
+        Ok(())
+    }
+
+    /// Get the configured inventory contract, if any
+    pub fn get_inventory_contract(env: Env) -> Option<Address> {
+        storage::get_inventory_contract(&env)
+    }
+
+    /// Configure the regional authority that co-approves cross-region requests
+    ///
+    /// Replaces any previous authority; approvals it already recorded on
+    /// pending requests stay valid.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_regional_authority(env: Env, authority: Address) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_regional_authority(&env, &authority);
+
+        Ok(())
+    }
+
+    /// Get the configured regional authority, if any
+    pub fn get_regional_authority(env: Env) -> Option<Address> {
+        storage::get_regional_authority(&env)
+    }
+
+    /// Get every account that can currently approve requests
+    ///
+    /// The admin, which approves every request, followed by the regional
+    /// authority that co-approves cross-region requests, if one is set and
+    /// is not the admin itself. Empty before initialization.
+    pub fn get_approvers(env: Env) -> Vec<Address> {
+        let mut approvers = Vec::new(&env);
+        if !storage::is_initialized(&env) {
+            return approvers;
+        }
+
+        let admin = storage::get_admin(&env);
+        approvers.push_back(admin.clone());
+        if let Some(authority) = storage::get_regional_authority(&env) {
+            if authority != admin {
+                approvers.push_back(authority);
+            }
+        }
+        approvers
+    }
+
+    /// Create a new blood request
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital_id` - Hospital requesting blood (must be authorized)
+    /// * `blood_type` - Type of blood requested
+    /// * `quantity_ml` - Quantity in milliliters (100-10000ml, or from the
+    ///   pediatric minimum for pediatric requests)
+    /// * `urgency` - Urgency level (Critical, Urgent, Normal)
+    /// * `required_by` - Unix timestamp when blood is required
+    /// * `delivery_address` - Address where blood should be delivered
+    /// * `metadata` - Patient identifier, procedure and notes
+    /// * `options` - Compatible-type matching, co-signer, department,
+    ///   ordering physician, pediatric mode, cross-region shipping and
+    ///   idempotency key (see `RequestOptions`)
+    ///
+    /// # Returns
+    /// Unique ID of the created request. If the hospital already created a
+    /// request under `options.idempotency_key` within the last
+    /// `IDEMPOTENCY_WINDOW_SECONDS`, that request's ID is returned instead
+    /// and nothing is created or emitted; the other arguments are not
+    /// compared with the original call.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedHospital`: Hospital is not authorized
+    /// - `HospitalSuspended`: Hospital is temporarily suspended
+    /// - `InvalidQuantity`: Quantity outside acceptable range
+    /// - `InvalidRequiredBy`: Required_by timestamp is invalid
+    /// - `InvalidDeliveryAddress`: Delivery address is empty or only whitespace
+    /// - `DeliveryAddressTooLong`: Delivery address exceeds `MAX_DELIVERY_ADDRESS_LEN`
+    /// - `DepartmentTooLong`: Department exceeds `MAX_DEPARTMENT_LEN`
+    /// - `PhysicianIdTooLong`: Physician identifier exceeds `MAX_PHYSICIAN_ID_LEN`
+    /// - `InvalidInput`: Contact is given but empty or only whitespace
+    /// - `ContactTooLong`: Contact exceeds `MAX_CONTACT_LEN`
+    /// - `QuantityNotAligned`: Quantity is not a multiple of the quantity step
+    ///   (not checked for pediatric requests, which are split into aliquots)
+    /// - `ContractPaused`: Request creation is paused
+    /// - `ImportInProgress`: A state import has not received its config yet
+    /// - `CounterOverflow`: No request IDs are left
+    /// - `CoSignerRequired`: Critical request without a co-signer
+    /// - `NotRequestSigner`: Co-signer is not registered by the hospital
+    ///
+    /// # Events
+    /// Emits `RequestCreated` event with all request details, under topics
+    /// `("request_created", hospital_id, blood_type)`
+    pub fn create_request(
+        env: Env,
+        hospital_id: Address,
+        blood_type: BloodType,
+        quantity_ml: u32,
+        urgency: UrgencyLevel,
+        required_by: u64,
+        delivery_address: String,
+        metadata: RequestMetadata,
+        options: RequestOptions,
+    ) -> Result<u64, ContractError> {
+        // 1. Verify hospital authentication
+        hospital_id.require_auth();
+
+        // 2. Check contract is initialized
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if storage::get_pause_flags(&env).create_paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        // The first organic request closes importing for good
+        match storage::get_import_state(&env) {
+            Some(ImportState::InProgress) => return Err(ContractError::ImportInProgress),
+            Some(ImportState::Open) => storage::close_import(&env),
+            None => {}
+        }
+
+        // 3. Verify hospital is authorized
+        if !storage::is_authorized_hospital(&env, &hospital_id) {
+            return Err(ContractError::NotAuthorizedHospital);
+        }
+
+        if storage::get_active_suspension(&env, &hospital_id).is_some() {
+            return Err(ContractError::HospitalSuspended);
+        }
+
+        // A retry of a create that already went through
+        if let Some(key) = &options.idempotency_key {
+            if let Some(record) = storage::get_idempotency_record(&env, &hospital_id, key) {
+                if record.expires_at > env.ledger().timestamp() {
+                    return Ok(record.request_id);
+                }
+            }
+        }
+
+        Self::check_co_signer(&env, &hospital_id, urgency, &options.co_signer)?;
+
+        // 4. Validate request parameters; pediatric requests only lower the
+        // quantity floor
+        let min_quantity_ml = if options.pediatric {
+            storage::get_pediatric_min_quantity(&env)
+        } else {
+            validation::MIN_REQUEST_QUANTITY_ML
+        };
+        validation::validate_request_creation(
+            &env,
+            quantity_ml,
+            min_quantity_ml,
+            required_by,
+            &delivery_address,
+        )?;
+        validation::validate_department(&options.department)?;
+        validation::validate_physician_id(&options.physician_id)?;
+        validation::validate_contact(&options.contact)?;
+        if !options.pediatric {
+            validation::validate_quantity_step(quantity_ml, storage::get_quantity_step(&env))?;
+        }
+        validation::validate_blood_type(&blood_type)?;
+
+        // 5. Validate urgency-specific time window
+        validation::validate_urgency_time_window(&env, required_by, urgency.priority_weight())?;
+
+        // 6. Generate request ID
+        let request_id = storage::increment_request_id(&env)?;
+        let current_time = env.ledger().timestamp();
+
+        // 7. Create blood request
+        let request = BloodRequest {
+            schema: BLOOD_REQUEST_SCHEMA,
+            id: request_id,
+            hospital_id: hospital_id.clone(),
+            blood_type,
+            quantity_ml,
+            urgency,
+            status: RequestStatus::Pending,
+            created_at: current_time,
+            required_by,
+            fulfilled_at: None,
+            assigned_units: Vec::new(&env),
+            assignments: Vec::new(&env),
+            delivery_address,
+            metadata,
+            priority_score: BloodRequest::compute_priority_score(urgency, required_by),
+            accept_compatible: options.accept_compatible,
+            completion_override: false,
+            co_signer: options.co_signer,
+            department: options.department,
+            physician_id: options.physician_id,
+            pediatric: options.pediatric,
+            required_phenotype: Vec::new(&env),
+            cross_region: options.cross_region,
+            approvals: 0,
+            approved_at: None,
+            approved_by: None,
+            courier: None,
+            delivery_eta: None,
+            sla_breached: false,
+        };
+
+        // 8. Validate the complete request
+        request.validate(current_time)?;
+
+        // 9. Store request
+        storage::set_blood_request(&env, &request);
+        storage::set_request_contact(&env, request_id, &options.contact);
+        if let Some(key) = &options.idempotency_key {
+            storage::set_idempotency_record(
+                &env,
+                &hospital_id,
+                key,
+                &IdempotencyRecord {
+                    request_id,
+                    expires_at: current_time.saturating_add(storage::IDEMPOTENCY_WINDOW_SECONDS),
+                },
+            );
+        }
+
+        // 10. Update indexes for efficient querying
+        storage::add_to_hospital_index(&env, &request);
+        storage::add_to_department_index(&env, &request);
+        storage::add_to_physician_index(&env, &request);
+        storage::add_to_blood_type_index(&env, &request);
+        storage::add_to_status_index(&env, &request);
+        storage::add_to_urgency_index(&env, &request);
+        storage::add_to_deadline_bucket(&env, &request);
+        storage::insert_into_pending_queue(&env, &request);
+        storage::record_demand(
+            &env,
+            blood_type,
+            storage::epoch_of(current_time),
+            quantity_ml,
+        );
+        storage::record_report_created(
+            &env,
+            &hospital_id,
+            storage::epoch_of(current_time),
+            quantity_ml,
+        );
+
+        // 11. Emit event
+        events::emit_request_created(&env, &request, &options.contact);
+
+        // 12. Return request ID
+        Ok(request_id)
+    }
+
+    /// Get the request a hospital created under an idempotency key
+    ///
+    /// Returns None once the key's window has passed, even before it is
+    /// pruned.
+    pub fn get_request_by_idempotency_key(
+        env: Env,
+        hospital_id: Address,
+        key: BytesN<32>,
+    ) -> Option<u64> {
+        storage::get_idempotency_record(&env, &hospital_id, &key)
+            .filter(|record| record.expires_at > env.ledger().timestamp())
+            .map(|record| record.request_id)
+    }
+
+    /// Delete an idempotency key whose window has passed
+    ///
+    /// Anyone may prune a lapsed key; live keys are left alone.
+    ///
+    /// # Returns
+    /// Whether a key was removed
+    pub fn prune_idempotency_key(env: Env, hospital_id: Address, key: BytesN<32>) -> bool {
+        match storage::get_idempotency_record(&env, &hospital_id, &key) {
+            Some(record) if record.expires_at <= env.ledger().timestamp() => {
+                storage::remove_idempotency_record(&env, &hospital_id, &key);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Get blood request details by ID
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of the request to retrieve
+    ///
+    /// # Returns
+    /// Blood request details
+    ///
+    /// # Errors
+    /// - `RequestNotFound`: Request with given ID doesn't exist
+    pub fn get_request(env: Env, request_id: u64) -> Result<BloodRequest, ContractError> {
+        storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)
+    }
+
+    /// Get the number of request IDs issued so far
+    ///
+    /// IDs are assigned sequentially from 1, so this is also the highest ID.
+    pub fn get_request_count(env: Env) -> u64 {
+        storage::get_request_counter(&env)
+    }
+
+    /// Get the requests with IDs in `start_id..start_id + limit`
+    ///
+    /// Intended for walking every request from 1 to `get_request_count`.
+    /// IDs with no stored request are skipped, so the result may hold fewer
+    /// than `limit` entries. `limit` is capped at 50.
+    pub fn get_requests_range(env: Env, start_id: u64, limit: u32) -> Vec<BloodRequest> {
+        let end = start_id
+            .saturating_add(limit.min(MAX_RANGE_LIMIT) as u64)
+            .min(storage::get_request_counter(&env).saturating_add(1));
+
+        let mut requests = Vec::new(&env);
+        for id in start_id..end {
+            if let Some(request) = storage::get_blood_request(&env, id) {
+                requests.push_back(request);
+            }
+        }
+        requests
+    }
+
+    /// Export the contract's requests one page at a time (admin only)
+    ///
+    /// Start with `cursor` 0 and pass each page's `next_cursor` to get the
+    /// next one. A page holds the requests with IDs in
+    /// `cursor..cursor + limit` (`limit` capped at 50) along with their
+    /// contact, waitlist flag, expiry or rejection reason and completion
+    /// override. The page reaching the last ID has a `next_cursor` of 0.
+    /// The cursor is a request ID, so an interrupted export resumes from
+    /// the last `next_cursor` received. The request counter, configuration,
+    /// authorized hospitals and orphaned assignments are exported
+    /// separately by `export_state_config`.
+    ///
+    /// Indexes are left out and rebuilt by `import_state_page`; statistics
+    /// and idempotency keys are not exported.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn export_state_page(
+        env: Env,
+        cursor: u64,
+        limit: u32,
+    ) -> Result<StateExportPage, ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+        storage::get_admin(&env).require_auth();
+
+        let start = cursor.max(1);
+        let counter = storage::get_request_counter(&env);
+        let end = start
+            .saturating_add(limit.clamp(1, MAX_RANGE_LIMIT) as u64)
+            .min(counter.saturating_add(1));
+
+        let mut page = StateExportPage {
+            requests: Vec::new(&env),
+            expiry_reasons: Map::new(&env),
+            rejection_reasons: Map::new(&env),
+            completion_overrides: Vec::new(&env),
+            next_cursor: if end > counter { 0 } else { end },
+        };
+        for id in start..end {
+            let Some(request) = storage::get_blood_request(&env, id) else {
+                continue;
+            };
+            if let Some(reason) = storage::get_expiry_reason(&env, id) {
+                page.expiry_reasons.set(id, reason);
+            }
+            if let Some(reason) = storage::get_rejection_reason(&env, id) {
+                page.rejection_reasons.set(id, reason);
+            }
+            if let Some(record) = storage::get_completion_override(&env, id) {
+                page.completion_overrides.push_back(record);
+            }
+            page.requests.push_back(ExportedRequest {
+                contact: storage::get_request_contact(&env, id),
+                waitlisted: storage::is_waitlisted(&env, id),
+                request,
+            });
+        }
+
+        Ok(page)
+    }
+
+    /// Export the request counter, configuration, authorized hospitals and
+    /// orphaned assignments (admin only)
+    ///
+    /// The counterpart of `export_state_page` for everything that is not
+    /// kept per request.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn export_state_config(env: Env) -> Result<ExportedConfig, ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+        storage::get_admin(&env).require_auth();
+
+        let mut hospitals = Vec::new(&env);
+        for hospital in storage::get_authorized_hospitals(&env).iter() {
+            hospitals.push_back(ExportedHospital {
+                valid_until: storage::get_hospital_valid_until(&env, &hospital),
+                suspended_until: storage::get_active_suspension(&env, &hospital),
+                signers: storage::get_request_signers(&env, &hospital),
+                hospital,
+            });
+        }
+
+        Ok(ExportedConfig {
+            request_counter: storage::get_request_counter(&env),
+            hospitals,
+            pause_flags: storage::get_pause_flags(&env),
+            inventory_contract: storage::get_inventory_contract(&env),
+            regional_authority: storage::get_regional_authority(&env),
+            quantity_step: storage::get_quantity_step(&env),
+            pediatric_min_quantity: storage::get_pediatric_min_quantity(&env),
+            cancellation_grace_period: storage::get_cancellation_grace_period(&env),
+            auto_complete: storage::is_auto_complete(&env),
+            assignment_tolerance: storage::get_assignment_tolerance(&env),
+            orphaned_assignments: storage::get_orphaned_assignments(&env),
+            stats_epoch: storage::get_stats_epoch(&env),
+        })
+    }
+
+    /// Import a page produced by `export_state_page` (admin only)
+    ///
+    /// Only a freshly initialized contract accepts imports, and only until
+    /// `import_state_config` completes the import or a request is created,
+    /// whichever comes first. Request creation is refused while an import
+    /// is in progress. Pages may be imported in any order.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ImportNotAllowed`: Requests were created or an import completed
+    /// - `AlreadyExists`: A request on the page is already stored
+    pub fn import_state_page(env: Env, page: StateExportPage) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+        storage::get_admin(&env).require_auth();
+
+        if storage::get_import_state(&env).is_none() {
+            return Err(ContractError::ImportNotAllowed);
+        }
+        storage::set_import_state(&env, ImportState::InProgress);
+
+        for exported in page.requests.iter() {
+            let request = exported.request;
+            if storage::get_blood_request(&env, request.id).is_some() {
+                return Err(ContractError::AlreadyExists);
+            }
+
+            storage::set_blood_request(&env, &request);
+            storage::set_request_contact(&env, request.id, &exported.contact);
+            if exported.waitlisted {
+                storage::set_waitlisted(&env, request.id);
+            }
+
+            storage::add_to_hospital_index(&env, &request);
+            storage::add_to_department_index(&env, &request);
+            storage::add_to_physician_index(&env, &request);
+            storage::add_to_blood_type_index(&env, &request);
+            storage::add_to_status_index(&env, &request);
+            storage::add_to_urgency_index(&env, &request);
+            storage::add_to_deadline_bucket(&env, &request);
+            if request.status == RequestStatus::Pending {
+                storage::insert_into_pending_queue(&env, &request);
+            }
+        }
+
+        for (request_id, reason) in page.expiry_reasons.iter() {
+            storage::set_expiry_reason(&env, request_id, reason);
+        }
+        for (request_id, reason) in page.rejection_reasons.iter() {
+            storage::set_rejection_reason(&env, request_id, reason);
+        }
+        for record in page.completion_overrides.iter() {
+            storage::set_completion_override(&env, &record);
+        }
+
+        Ok(())
+    }
+
+    /// Import the output of `export_state_config` and complete the import
+    /// (admin only)
+    ///
+    /// Import every page first: once this succeeds, neither pages nor
+    /// another configuration are accepted.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ImportNotAllowed`: Requests were created or an import completed
+    pub fn import_state_config(env: Env, config: ExportedConfig) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+        storage::get_admin(&env).require_auth();
+
+        if storage::get_import_state(&env).is_none() {
+            return Err(ContractError::ImportNotAllowed);
+        }
+
+        for exported in config.hospitals.iter() {
+            storage::authorize_hospital(&env, &exported.hospital);
+            storage::set_hospital_valid_until(&env, &exported.hospital, exported.valid_until);
+            if let Some(until) = exported.suspended_until {
+                storage::set_suspension(&env, &exported.hospital, until);
+            }
+            storage::set_request_signers(&env, &exported.hospital, &exported.signers);
+        }
+
+        storage::set_pause_flags(&env, &config.pause_flags);
+        if let Some(inventory) = &config.inventory_contract {
+            storage::set_inventory_contract(&env, inventory);
+        }
+        if let Some(authority) = &config.regional_authority {
+            storage::set_regional_authority(&env, authority);
+        }
+        storage::set_quantity_step(&env, config.quantity_step);
+        storage::set_pediatric_min_quantity(&env, config.pediatric_min_quantity);
+        storage::set_cancellation_grace_period(&env, config.cancellation_grace_period);
+        storage::set_auto_complete(&env, config.auto_complete);
+        storage::set_assignment_tolerance(&env, config.assignment_tolerance);
+        for request_id in config.orphaned_assignments.iter() {
+            storage::add_orphaned_assignment(&env, request_id);
+        }
+        if let Some(epoch) = config.stats_epoch {
+            storage::set_stats_epoch(&env, epoch);
+        }
+        storage::set_request_counter(&env, config.request_counter);
+        storage::close_import(&env);
+
+        Ok(())
+    }
+
+    /// Approve a pending blood request
+    ///
+    /// A cross-region request only becomes Approved once the regional
+    /// authority has approved it too (`approve_cross_region`), in either
+    /// order; until then the admin's approval is recorded and the request
+    /// stays Pending.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of the request to approve
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotFound`: Request doesn't exist
+    /// - `InvalidStatusTransition`: Request is not in Pending status
+    /// - `ContractPaused`: Approvals are paused
+    /// - `ApprovalAlreadyRecorded`: Admin already approved this cross-region request
+    ///
+    /// # Events
+    /// Emits `approval_recorded` for a cross-region request, and
+    /// `request_status_changed` once the request is Approved
+    pub fn approve_request(env: Env, request_id: u64) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if storage::get_pause_flags(&env).approve_paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        // Only admin can approve
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        // Get request
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
+
+        Self::record_approval(&env, &mut request, ApprovalAuthority::Admin, &admin)
+    }
+
+    /// Record the regional authority's approval of a cross-region request
+    ///
+    /// The request becomes Approved once the admin has approved it too
+    /// (`approve_request`), in either order.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of the cross-region request to approve
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ContractPaused`: Approvals are paused
+    /// - `RegionalAuthorityNotConfigured`: No regional authority is set
+    /// - `NotFound`: Request doesn't exist
+    /// - `InvalidInput`: Request is not cross-region
+    /// - `InvalidStatusTransition`: Request is not in Pending status
+    /// - `ApprovalAlreadyRecorded`: Regional authority already approved it
+    ///
+    /// # Events
+    /// Emits `approval_recorded`, and `request_status_changed` once the
+    /// request is Approved
+    pub fn approve_cross_region(env: Env, request_id: u64) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if storage::get_pause_flags(&env).approve_paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let authority = storage::get_regional_authority(&env)
+            .ok_or(ContractError::RegionalAuthorityNotConfigured)?;
+        authority.require_auth();
+
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
+
+        if !request.cross_region {
+            return Err(ContractError::InvalidInput);
+        }
+
+        Self::record_approval(&env, &mut request, ApprovalAuthority::Regional, &authority)
+    }
+
+    /// Update request status
+    ///
+    /// Rejections made here are recorded with `RejectionReason::Other`;
+    /// use `reject_request` to give a reason.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of request to update
+    /// * `new_status` - New status for the request
+    ///
+    /// # Errors
+    /// - `RequestNotFound`: Request does not exist
+    /// - `InvalidStatusTransition`: Status transition is not allowed
+    /// - `Unauthorized`: Caller is not authorized
+    /// - `ContractPaused`: The target status belongs to a paused group
+    pub fn update_request_status(
+        env: Env,
+        request_id: u64,
+        new_status: RequestStatus,
+    ) -> Result<(), ContractError> {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let flags = storage::get_pause_flags(&env);
+        let paused = match new_status {
+            RequestStatus::Approved => flags.approve_paused,
+            RequestStatus::Fulfilled | RequestStatus::InDelivery | RequestStatus::Completed => {
+                flags.fulfill_paused
+            }
+            _ => false,
+        };
+        if paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        // Get existing request
+        let request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        Self::change_status(&env, request, new_status)
+    }
+
+    /// Reject a pending request with a machine-readable reason
+    ///
+    /// The reason is stored with the request (see `get_rejection_reason`)
+    /// and counted per epoch (see `get_rejection_breakdown`).
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of the request to reject
+    /// * `reason` - Why the request is rejected
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RequestNotFound`: Request does not exist
+    /// - `InvalidStatusTransition`: Request is not Pending
+    ///
+    /// # Events
+    /// Emits `request_status_changed` and `request_rejected`
+    pub fn reject_request(
+        env: Env,
+        request_id: u64,
+        reason: RejectionReason,
+    ) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+        if !request.status.can_transition_to(&RequestStatus::Rejected) {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        storage::set_rejection_reason(&env, request_id, reason);
+        Self::change_status(&env, request, RequestStatus::Rejected)
+    }
+
+    /// Get the reason a request was rejected
+    ///
+    /// None until the request is Rejected.
+    pub fn get_rejection_reason(env: Env, request_id: u64) -> Option<RejectionReason> {
+        storage::get_rejection_reason(&env, request_id)
+    }
+
+    /// Get the number of rejections per reason in a statistics epoch
+    ///
+    /// Epochs are the same 30-day windows used by the unfulfilled stats; a
+    /// rejection counts towards the epoch it happened in. Reasons with no
+    /// rejections are left out.
+    pub fn get_rejection_breakdown(env: Env, epoch: u64) -> Map<RejectionReason, u32> {
+        storage::get_rejection_stats(&env, epoch)
+    }
+
+    /// Move a request to a new status on the admin's behalf, keeping the
+    /// indexes, inventory links and statistics in step
+    fn change_status(
+        env: &Env,
+        mut request: BloodRequest,
+        new_status: RequestStatus,
+    ) -> Result<(), ContractError> {
+        let request_id = request.id;
+
+        // Validate status transition
+        if !request.status.can_transition_to(&new_status) {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        // The admin cannot approve a cross-region request on their own
+        if new_status == RequestStatus::Approved
+            && request.awaits_approval(ApprovalAuthority::Regional)
+        {
+            return Err(ContractError::SecondApprovalRequired);
+        }
+
+        let old_status = request.status;
+        request.status = new_status;
+
+        // Set approved_at / approved_by / fulfilled_at on reaching those states
+        if new_status == RequestStatus::Approved {
+            request.approved_at = Some(env.ledger().timestamp());
+            request.approved_by = Some(storage::get_admin(env));
+        }
+        if new_status == RequestStatus::Fulfilled {
+            request.fulfilled_at = Some(env.ledger().timestamp());
+        }
+        if new_status == RequestStatus::Completed {
+            request.delivery_eta = None;
+        }
+
+        // Every rejection is counted, under Other when no reason was given
+        let rejection_reason = (new_status == RequestStatus::Rejected).then(|| {
+            let reason =
+                storage::get_rejection_reason(env, request_id).unwrap_or(RejectionReason::Other);
+            storage::set_rejection_reason(env, request_id, reason);
+            storage::record_rejection(env, reason);
+            reason
+        });
+
+        // Store updated request and move it between status indexes
+        Self::save_request(env, &request)?;
+        storage::update_status_index(env, request_id, old_status, new_status);
+        match new_status {
+            RequestStatus::Completed => {
+                storage::record_report_completed(
+                    env,
+                    &request.hospital_id,
+                    storage::current_epoch(env),
+                    request.assigned_ml(),
+                );
+                storage::record_score_completed(env, &request.hospital_id);
+            }
+            RequestStatus::Cancelled => {
+                storage::record_report_cancelled(
+                    env,
+                    &request.hospital_id,
+                    storage::current_epoch(env),
+                );
+                if old_status != RequestStatus::Pending {
+                    storage::record_score_late_cancellation(env, &request.hospital_id);
+                }
+            }
+            _ => {}
+        }
+
+        // A closed request frees whatever it still holds in inventory, and
+        // a served or closed one stops waiting for stock
+        if new_status.is_terminal() {
+            Self::release_reserved_units(env, request_id);
+            Self::track_orphaned_assignment(env, &request);
+        }
+        if new_status.is_terminal()
+            || matches!(
+                new_status,
+                RequestStatus::Fulfilled | RequestStatus::InDelivery
+            )
+        {
+            Self::leave_waitlist(env, request_id);
+        }
+
+        // Emit event
+        events::emit_request_status_changed(env, request_id, old_status, new_status);
+        if new_status == RequestStatus::Approved {
+            events::emit_request_approved(env, &request);
+        }
+        if let Some(reason) = rejection_reason {
+            events::emit_request_rejected(env, &request, reason);
+        }
+        if new_status == RequestStatus::InDelivery {
+            let contact = storage::get_request_contact(env, request_id);
+            events::emit_delivery_started(env, &request, contact);
+        }
+
+        Ok(())
+    }
+
+    /// Cancel a blood request
+    ///
+    /// Can be called by the hospital that created the request or by admin.
+    /// Once a request is approved, the hospital may only cancel it within
+    /// the cancellation grace period after approval; later cancellations
+    /// need the admin. Units reserved for the request are released in the
+    /// inventory contract, when configured.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of the request to cancel
+    /// * `caller` - Address of the caller (hospital or admin)
+    ///
+    /// # Errors
+    /// - `NotFound`: Request doesn't exist
+    /// - `CannotCancelRequest`: Request cannot be cancelled in current state
+    /// - `Unauthorized`: Caller is not hospital owner or admin
+    /// - `CancellationWindowClosed`: Hospital cancelling an approved request
+    ///   after the grace period
+    ///
+    /// # Events
+    /// Emits `request_status_changed`, and `assigned_units_released` when
+    /// units were assigned; the inventory contract reports each released
+    /// reservation
+    pub fn cancel_request(env: Env, request_id: u64, caller: Address) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        // Get request
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
+
+        // Check caller is authorized (hospital owner or admin)
+        let admin = storage::get_admin(&env);
+        if caller != request.hospital_id && caller != admin {
+            return Err(ContractError::Unauthorized);
+        }
+
+        // Check can cancel
+        if !request.status.can_cancel() {
+            return Err(ContractError::CannotCancelRequest);
+        }
+
+        // Hospitals only get a short window to change their mind after approval
+        if caller != admin && request.status == RequestStatus::Approved {
+            let deadline = request
+                .approved_at
+                .unwrap_or(request.created_at)
+                .saturating_add(storage::get_cancellation_grace_period(&env));
+            if env.ledger().timestamp() > deadline {
+                return Err(ContractError::CancellationWindowClosed);
+            }
+        }
+
+        Self::cancel(&env, &mut request, &caller)
+    }
+
+    /// Cancel a hospital's Pending requests, oldest first
+    ///
+    /// Approved and later requests are skipped. Each cancellation has the
+    /// same effects and events as `cancel_request`. A return value equal to
+    /// `limit` means more Pending requests may be left; call again until it
+    /// returns less.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital` - Hospital whose requests are withdrawn
+    /// * `limit` - Maximum number of requests to cancel (capped at
+    ///   `MAX_QUERY_LIMIT`)
+    ///
+    /// # Returns
+    /// Number of requests cancelled
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn cancel_my_pending_requests(
+        env: Env,
+        hospital: Address,
+        limit: u32,
+    ) -> Result<u32, ContractError> {
+        hospital.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let max = limit.min(MAX_QUERY_LIMIT);
+        let mut cancelled = 0u32;
+        for id in storage::get_requests_by_hospital(&env, &hospital).iter() {
+            if cancelled >= max {
+                break;
+            }
+            if let Some(mut request) = storage::get_blood_request(&env, id) {
+                if request.status == RequestStatus::Pending {
+                    Self::cancel(&env, &mut request, &hospital)?;
+                    cancelled += 1;
+                }
+            }
+        }
+
+        Ok(cancelled)
+    }
+
+    /// Assign the courier delivering a request (admin only)
+    ///
+    /// Reassigning replaces the previous courier.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of the request
+    /// * `courier` - Courier taking the delivery
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RequestNotFound`: Request does not exist
+    /// - `InvalidStatusTransition`: Request is not Approved, Fulfilled or InDelivery
+    ///
+    /// # Events
+    /// Emits `courier_assigned`
+    pub fn assign_courier(
+        env: Env,
+        request_id: u64,
+        courier: Address,
+    ) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        if !matches!(
+            request.status,
+            RequestStatus::Approved | RequestStatus::Fulfilled | RequestStatus::InDelivery
+        ) {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        request.courier = Some(courier.clone());
+        Self::save_request(&env, &request)?;
+
+        events::emit_courier_assigned(&env, request_id, &courier);
+
+        Ok(())
+    }
+
+    /// Set the expected arrival of a request that is out for delivery
+    ///
+    /// May be updated any number of times while the request is InDelivery;
+    /// completion clears it.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Admin or the courier assigned to the request
+    /// * `request_id` - ID of the request
+    /// * `eta` - Expected arrival (Unix seconds), at most
+    ///   `MAX_DELIVERY_ETA_SECONDS` ahead
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RequestNotFound`: Request does not exist
+    /// - `Unauthorized`: Caller is neither the admin nor the assigned courier
+    /// - `InvalidStatusTransition`: Request is not InDelivery
+    /// - `InvalidTimestamp`: ETA is not in the future or too far ahead
+    ///
+    /// # Events
+    /// Emits `eta_updated` with the old and new ETA
+    pub fn set_delivery_eta(
+        env: Env,
+        caller: Address,
+        request_id: u64,
+        eta: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        if caller != storage::get_admin(&env) && request.courier.as_ref() != Some(&caller) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if request.status != RequestStatus::InDelivery {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        validation::validate_delivery_eta(&env, eta)?;
+
+        let old_eta = request.delivery_eta.replace(eta);
+        Self::save_request(&env, &request)?;
+
+        events::emit_eta_updated(&env, request_id, &caller, old_eta, eta);
+
+        Ok(())
+    }
+
+    /// Confirm receipt of a delivered request
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital_id` - Hospital that created the request
+    /// * `request_id` - ID of the request to complete
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RequestNotFound`: Request does not exist
+    /// - `Unauthorized`: Caller is not the hospital that created the request
+    /// - `InvalidStatusTransition`: Request is not InDelivery
+    /// - `ContractPaused`: Fulfillment is paused
+    pub fn complete_request(
+        env: Env,
+        hospital_id: Address,
+        request_id: u64,
+    ) -> Result<(), ContractError> {
+        hospital_id.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if storage::get_pause_flags(&env).fulfill_paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        if request.hospital_id != hospital_id {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if request.status != RequestStatus::InDelivery {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        Self::mark_completed(&env, &mut request)?;
+        events::emit_request_completed(&env, request_id);
+
+        Ok(())
+    }
+
+    /// Close out a request without the normal delivery flow
+    ///
+    /// Admin-only escape hatch for when the hospital cannot confirm (e.g. a
+    /// lost key mid-delivery). Skips the InDelivery requirement and the
+    /// hospital confirmation, marks the request with `completion_override`
+    /// and keeps an audit record of the justification.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `admin` - Contract admin
+    /// * `request_id` - ID of the request to complete
+    /// * `justification` - Why the override was necessary
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller is not the admin
+    /// - `InvalidInput`: Justification is empty
+    /// - `RequestNotFound`: Request does not exist
+    /// - `RequestNotApproved`: Request is still Pending
+    /// - `RequestAlreadyTerminal`: Request is already closed
+    ///
+    /// # Events
+    /// Emits `forced_completion` with the audit record
+    pub fn force_complete(
+        env: Env,
+        admin: Address,
+        request_id: u64,
+        justification: String,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if admin != storage::get_admin(&env) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if justification.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        if request.status.is_terminal() {
+            return Err(ContractError::RequestAlreadyTerminal);
+        }
+
+        if request.status == RequestStatus::Pending {
+            return Err(ContractError::RequestNotApproved);
+        }
+
+        let record = CompletionOverride {
+            request_id,
+            admin,
+            previous_status: request.status,
+            justification,
+            completed_at: env.ledger().timestamp(),
+        };
+
+        request.completion_override = true;
+        Self::mark_completed(&env, &mut request)?;
+        storage::set_completion_override(&env, &record);
+
+        events::emit_request_status_changed(
+            &env,
+            request_id,
+            record.previous_status,
+            RequestStatus::Completed,
+        );
+        events::emit_forced_completion(&env, &record);
+
+        Ok(())
+    }
+
+    /// Get the audit record of a forced completion, if any
+    pub fn get_completion_override(env: Env, request_id: u64) -> Option<CompletionOverride> {
+        storage::get_completion_override(&env, request_id)
+    }
+
+    /// Send an approved request back to Pending
+    ///
+    /// Admin-only correction for an approval made in error. The request
+    /// returns to the pending queue at its current priority. Units must be
+    /// unassigned first (assign an empty list with `assign_blood_units`).
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `admin` - Contract admin
+    /// * `request_id` - ID of the request to revert
+    /// * `justification` - Why the approval is reverted
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller is not the admin
+    /// - `InvalidInput`: Justification is empty
+    /// - `RequestNotFound`: Request does not exist
+    /// - `RequestNotApproved`: Request is not Approved
+    /// - `UnitsAlreadyAssigned`: Units are still assigned to the request
+    ///
+    /// # Events
+    /// Emits `request_status_changed` and `approval_reverted`
+    pub fn revert_approval(
+        env: Env,
+        admin: Address,
+        request_id: u64,
+        justification: String,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if admin != storage::get_admin(&env) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if justification.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        if request.status != RequestStatus::Approved {
+            return Err(ContractError::RequestNotApproved);
+        }
+
+        if !request.assigned_units.is_empty() {
+            return Err(ContractError::UnitsAlreadyAssigned);
+        }
+
+        // A cross-region request has to collect both approvals again
+        request.status = RequestStatus::Pending;
+        request.approvals = 0;
+        request.approved_at = None;
+        request.approved_by = None;
+        Self::save_request(&env, &request)?;
+        storage::update_status_index(
+            &env,
+            request_id,
+            RequestStatus::Approved,
+            RequestStatus::Pending,
+        );
+        storage::insert_into_pending_queue(&env, &request);
+
+        events::emit_approval_reverted(&env, request_id, &admin, &justification);
+
+        Ok(())
+    }
+
+    /// Update the delivery address of a request before delivery starts
+    ///
+    /// Only the hospital that created the request can change the address, and
+    /// only while the request is still Pending or Approved.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital_id` - Hospital that created the request
+    /// * `request_id` - ID of the request to update
+    /// * `new_address` - New delivery address
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RequestNotFound`: Request does not exist
+    /// - `NotRequestCreator`: Caller is not the hospital that created the request
+    /// - `InvalidStatusTransition`: Request is no longer Pending or Approved
+    /// - `InvalidDeliveryAddress`: New address is empty or only whitespace
+    /// - `DeliveryAddressTooLong`: New address exceeds `MAX_DELIVERY_ADDRESS_LEN`
+    ///
+    /// # Events
+    /// Emits `DeliveryAddressUpdated` event with the old and new address
+    pub fn update_delivery_address(
+        env: Env,
+        hospital_id: Address,
+        request_id: u64,
+        new_address: String,
+    ) -> Result<(), ContractError> {
+        // Address is locked once units are prepared or on their way
+        let mut request = Self::load_owned_open_request(&env, &hospital_id, request_id)?;
+
+        validation::validate_delivery_address(&new_address)?;
+
+        let old_address = request.delivery_address.clone();
+        request.delivery_address = new_address.clone();
+
+        Self::save_request(&env, &request)?;
+
+        events::emit_delivery_address_updated(
+            &env,
+            request_id,
+            &hospital_id,
+            old_address,
+            new_address,
+        );
+
+        Ok(())
+    }
+
+    /// Set or clear the contact at a request's delivery point
+    ///
+    /// Only the hospital that created the request can change it, until the
+    /// request is closed.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital_id` - Hospital that created the request
+    /// * `request_id` - ID of the request to update
+    /// * `contact` - New contact, or None to remove it
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RequestNotFound`: Request does not exist
+    /// - `Unauthorized`: Caller is not the hospital that created the request
+    /// - `RequestAlreadyTerminal`: Request is already closed
+    /// - `InvalidInput`: Contact is empty or only whitespace
+    /// - `ContactTooLong`: Contact exceeds `MAX_CONTACT_LEN`
+    ///
+    /// # Events
+    /// Emits `contact_updated`, without the contact
+    pub fn update_contact(
+        env: Env,
+        hospital_id: Address,
+        request_id: u64,
+        contact: Option<String>,
+    ) -> Result<(), ContractError> {
+        hospital_id.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        if request.hospital_id != hospital_id {
+            return Err(ContractError::Unauthorized);
+        }
+        if request.status.is_terminal() {
+            return Err(ContractError::RequestAlreadyTerminal);
+        }
+
+        validation::validate_contact(&contact)?;
+
+        storage::set_request_contact(&env, request_id, &contact);
+        events::emit_contact_updated(&env, request_id, &hospital_id);
+
+        Ok(())
+    }
+
+    /// Get the contact at a request's delivery point
+    ///
+    /// Not part of the request record or its summaries; only the hospital
+    /// that created the request, the admin and the assigned courier may
+    /// read it.
+    ///
+    /// # Errors
+    /// - `RequestNotFound`: Request does not exist
+    /// - `Unauthorized`: Caller is none of the allowed readers
+    pub fn get_contact(
+        env: Env,
+        caller: Address,
+        request_id: u64,
+    ) -> Result<Option<String>, ContractError> {
+        caller.require_auth();
+
+        let request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        let allowed = caller == request.hospital_id
+            || caller == storage::get_admin(&env)
+            || request.courier.as_ref() == Some(&caller);
+        if !allowed {
+            return Err(ContractError::Unauthorized);
+        }
+
+        Ok(storage::get_request_contact(&env, request_id))
+    }
+
+    /// Set the extended phenotype every unit assigned to a request must carry
+    ///
+    /// Only the hospital that created the request can set it, and only while
+    /// the request is still Pending or Approved. An empty list drops the
+    /// requirement. Assignments are checked against the inventory contract's
+    /// unit tags.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital_id` - Hospital that created the request
+    /// * `request_id` - ID of the request to update
+    /// * `required_phenotype` - Tags such as `kell_neg` (at most
+    ///   `MAX_REQUIRED_PHENOTYPE_TAGS`, each once)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RequestNotFound`: Request does not exist
+    /// - `NotRequestCreator`: Caller is not the hospital that created the request
+    /// - `InvalidStatusTransition`: Request is no longer Pending or Approved
+    /// - `InvalidInput`: Too many tags, or a tag is listed twice
+    ///
+    /// # Events
+    /// Emits `required_phenotype_set` with the new tags
+    pub fn set_required_phenotype(
+        env: Env,
+        hospital_id: Address,
+        request_id: u64,
+        required_phenotype: Vec<Symbol>,
+    ) -> Result<(), ContractError> {
+        let mut request = Self::load_owned_open_request(&env, &hospital_id, request_id)?;
+
+        validation::validate_required_phenotype(&required_phenotype)?;
+
+        request.required_phenotype = required_phenotype;
+        Self::save_request(&env, &request)?;
+
+        events::emit_required_phenotype_set(&env, &request);
+
+        Ok(())
+    }
+
+    /// Raise the urgency of a request
+    ///
+    /// Only the hospital that created the request can escalate it, and only
+    /// while it is Pending or Approved. A pending request moves to its new
+    /// position in the pending queue.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital_id` - Hospital that created the request
+    /// * `request_id` - ID of the request to escalate
+    /// * `new_urgency` - New urgency level (must be higher than the current one)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RequestNotFound`: Request does not exist
+    /// - `NotRequestCreator`: Caller is not the hospital that created the request
+    /// - `InvalidStatusTransition`: Request is no longer Pending or Approved
+    /// - `InvalidUrgency`: New urgency is not higher than the current one
+    pub fn escalate_request(
+        env: Env,
+        hospital_id: Address,
+        request_id: u64,
+        new_urgency: UrgencyLevel,
+    ) -> Result<(), ContractError> {
+        let mut request = Self::load_owned_open_request(&env, &hospital_id, request_id)?;
+
+        if new_urgency.priority_weight() <= request.urgency.priority_weight() {
+            return Err(ContractError::InvalidUrgency);
+        }
+
+        let old_urgency = request.urgency;
+        request.urgency = new_urgency;
+        storage::update_urgency_index(&env, request_id, old_urgency, new_urgency);
+
+        Self::reprioritize(&env, &mut request)?;
+
+        Ok(())
+    }
+
+    /// Push back the deadline of a request
+    ///
+    /// Only the hospital that created the request can extend it, and only
+    /// while it is Pending or Approved. A pending request moves to its new
+    /// position in the pending queue.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital_id` - Hospital that created the request
+    /// * `request_id` - ID of the request to extend
+    /// * `new_required_by` - New deadline (must be later than the current one)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RequestNotFound`: Request does not exist
+    /// - `NotRequestCreator`: Caller is not the hospital that created the request
+    /// - `InvalidStatusTransition`: Request is no longer Pending or Approved
+    /// - `InvalidRequiredBy`: New deadline is not later or is out of range
+    pub fn extend_deadline(
+        env: Env,
+        hospital_id: Address,
+        request_id: u64,
+        new_required_by: u64,
+    ) -> Result<(), ContractError> {
+        let mut request = Self::load_owned_open_request(&env, &hospital_id, request_id)?;
+
+        if new_required_by <= request.required_by {
+            return Err(ContractError::InvalidRequiredBy);
+        }
+        validation::validate_required_by(&env, new_required_by)?;
+
+        storage::update_deadline_bucket(&env, request_id, request.required_by, new_required_by);
+        request.required_by = new_required_by;
+
+        Self::reprioritize(&env, &mut request)?;
+
+        Ok(())
+    }
+
+    /// Expire Pending and Approved requests whose deadline has passed
+    ///
+    /// Records an expiry reason per request and counts each expiry against
+    /// its blood type in the current epoch. Approved requests are also
+    /// flagged `sla_breached` and counted as SLA breaches (see
+    /// `get_sla_breaches`). The first sweep of a new epoch emits the closed
+    /// epoch's totals.
+    ///
+    /// Besides a status change event per request, every call ends with one
+    /// `expiry_sweep_completed` event whose `more_remaining` flag tells
+    /// keepers to call again.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `limit` - Maximum number of requests to expire (defaults to 50, max 200)
+    ///
+    /// # Returns
+    /// Number of requests expired
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn expire_overdue_requests(env: Env, limit: Option<u32>) -> Result<u32, ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let epoch = storage::current_epoch(&env);
+        Self::roll_stats_epoch(&env, epoch);
+
+        let current_time = env.ledger().timestamp();
+        let max = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT);
+
+        // Collect first: expiring a request mutates the status indexes. One
+        // extra overdue request tells whether the limit cut the sweep short.
+        let mut overdue = Vec::new(&env);
+        for status in [RequestStatus::Pending, RequestStatus::Approved] {
+            for id in storage::get_requests_by_status(&env, status).iter() {
+                if overdue.len() > max {
+                    break;
+                }
+                if let Some(request) = storage::get_blood_request(&env, id) {
+                    if request.is_overdue(current_time) {
+                        overdue.push_back(request);
+                    }
+                }
+            }
+        }
+        let more_remaining = overdue.len() > max;
+        if more_remaining {
+            overdue.pop_back();
+        }
+
+        let mut expired_ids = Vec::new(&env);
+
+        for mut request in overdue.iter() {
+            let old_status = request.status;
+            let reason = if old_status == RequestStatus::Pending {
+                ExpiryReason::NotApproved
+            } else {
+                ExpiryReason::NotFulfilled
+            };
+
+            // Approved in time but never served: an SLA breach
+            if old_status == RequestStatus::Approved {
+                request.sla_breached = true;
+                storage::record_sla_breach(&env, &request.hospital_id, epoch);
+                storage::record_score_sla_breach(&env, &request.hospital_id);
+            }
+
+            request.status = RequestStatus::Expired;
+            storage::set_blood_request(&env, &request);
+            storage::update_status_index(&env, request.id, old_status, RequestStatus::Expired);
+            storage::set_expiry_reason(&env, request.id, reason);
+            storage::increment_unfulfilled_stats(&env, request.blood_type, epoch);
+            Self::release_reserved_units(&env, request.id);
+            Self::leave_waitlist(&env, request.id);
+            Self::track_orphaned_assignment(&env, &request);
+
+            events::emit_request_expired(&env, request.id, old_status);
+            expired_ids.push_back(request.id);
+        }
+
+        events::emit_expiry_sweep_completed(&env, &expired_ids, more_remaining);
+
+        Ok(overdue.len())
+    }
+
+    /// Get cancelled or expired requests that still list assigned units
+    ///
+    /// Worklist for `release_orphaned_assignments`, oldest first.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of IDs (capped at `MAX_QUERY_LIMIT`)
+    pub fn get_terminal_requests_with_units(env: Env, limit: u32) -> Vec<u64> {
+        let ids = storage::get_orphaned_assignments(&env);
+        let end = ids.len().min(limit.min(MAX_QUERY_LIMIT));
+        ids.slice(0..end)
+    }
+
+    /// Clear the units still assigned to a cancelled or expired request
+    ///
+    /// Releases whatever the request still holds in the inventory contract,
+    /// when one is configured, empties the assignment and drops the request
+    /// from the `get_terminal_requests_with_units` worklist.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of the closed request
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RequestNotFound`: Request does not exist
+    /// - `InvalidRequestState`: Request is not Cancelled or Expired, or has no
+    ///   assigned units
+    ///
+    /// # Events
+    /// Emits `orphaned_assignments_released` with the cleared unit IDs
+    pub fn release_orphaned_assignments(env: Env, request_id: u64) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        if !matches!(
+            request.status,
+            RequestStatus::Cancelled | RequestStatus::Expired
+        ) || request.assigned_units.is_empty()
+        {
+            return Err(ContractError::InvalidRequestState);
+        }
+
+        Self::release_reserved_units(&env, request_id);
+
+        let unit_ids = request.assigned_units.clone();
+        request.assigned_units = Vec::new(&env);
+        request.assignments = Vec::new(&env);
+        Self::save_request(&env, &request)?;
+        storage::remove_orphaned_assignment(&env, request_id);
+
+        events::emit_orphaned_assignments_released(&env, request_id, unit_ids, &admin);
+
+        Ok(())
+    }
+
+    /// Get the reason a request expired
+    pub fn get_expiry_reason(env: Env, request_id: u64) -> Option<ExpiryReason> {
+        storage::get_expiry_reason(&env, request_id)
+    }
+
+    /// Get how many requests of a blood type expired unfulfilled in an epoch
+    ///
+    /// Epochs are 30-day windows counted from the Unix epoch. Counters are
+    /// kept separately from the requests themselves.
+    pub fn get_unfulfilled_stats(env: Env, blood_type: BloodType, epoch: u64) -> u32 {
+        storage::get_unfulfilled_stats(&env, blood_type, epoch)
+    }
+
+    /// Get how many of a hospital's approved requests expired unfulfilled
+    /// in an epoch
+    ///
+    /// Counted by the expiry sweep in the epoch it ran; cancelled requests
+    /// never count.
+    pub fn get_sla_breaches(env: Env, hospital: Address, epoch: u64) -> u32 {
+        storage::get_sla_breaches(&env, &hospital, epoch)
+    }
+
+    /// Get how many approved requests expired unfulfilled in an epoch,
+    /// across all hospitals
+    pub fn get_total_sla_breaches(env: Env, epoch: u64) -> u32 {
+        storage::get_total_sla_breaches(&env, epoch)
+    }
+
+    /// Get the demand recorded for a blood type in an epoch
+    ///
+    /// Epochs are the same 30-day windows used by the unfulfilled stats.
+    /// Every created request counts towards the epoch it was created in. A
+    /// request cancelled before that epoch ends is taken back out; once the
+    /// epoch has closed its figures are final and later cancellations do
+    /// not change them.
+    pub fn get_demand(env: Env, blood_type: BloodType, epoch: u64) -> DemandStats {
+        storage::get_demand(&env, blood_type, epoch)
+    }
+
+    /// Get a hospital's request activity in an epoch
+    ///
+    /// Epochs are the same 30-day windows used by the demand stats. Each
+    /// figure counts in the epoch the event happened: creation adds to
+    /// `created` and `requested_ml` of the epoch the request was created
+    /// in, while completion adds to `completed` and `received_ml` (the
+    /// volume assigned to the request) of the epoch it was completed in,
+    /// even when that is a later one. Cancellations count in the epoch of
+    /// the cancellation and do not take the request back out of `created`.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is neither the hospital nor the admin
+    pub fn get_hospital_monthly_report(
+        env: Env,
+        caller: Address,
+        hospital: Address,
+        epoch: u64,
+    ) -> Result<HospitalReport, ContractError> {
+        caller.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if caller != hospital && caller != storage::get_admin(&env) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        Ok(storage::get_hospital_report(&env, &hospital, epoch))
+    }
+
+    /// Get a hospital's all-time track record: requests completed,
+    /// cancelled once approved, disputes opened and SLA breaches, with the
+    /// share of completions among them in basis points
+    ///
+    /// The derived ratio is informational only, a signal for banks choosing
+    /// whom to serve when stock is short; the contract never acts on it.
+    /// Pending requests withdrawn by the hospital are not held against it.
+    pub fn get_hospital_score(env: Env, hospital: Address) -> HospitalScore {
+        storage::get_hospital_score(&env, &hospital)
+    }
+
+    /// Assign blood units to a request
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of request
+    /// * `units` - Blood units to assign, with their blood types, volumes and
+    ///   owning banks; units may come from several banks
+    ///
+    /// With auto-completion on (see `set_auto_complete`), an InDelivery
+    /// request whose assigned volume reaches its requested quantity is
+    /// completed as if the hospital had confirmed receipt.
+    ///
+    /// # Events
+    /// Emits one `units_assigned` per source bank, with that bank's units
+    /// and their total volume, then `request_status_changed` when the
+    /// request is auto-completed
+    ///
+    /// # Errors
+    /// - `RequestNotFound`: Request does not exist
     /// - `Unauthorized`: Caller is not authorized
+    /// - `IncompatibleBloodType`: A unit cannot be matched to the request's blood type
+    /// - `PhenotypeMismatch`: A unit lacks a tag of the request's required phenotype
+    /// - `InventoryNotConfigured`: Request requires a phenotype, but no
+    ///   inventory contract is configured to check the units against
+    /// - `InvalidInput`: A unit is listed more than once
+    /// - `InvalidQuantity`: The total volume overflows
+    /// - `OverAssignment`: The total volume exceeds the quantity plus the
+    ///   assignment tolerance (see `set_assignment_tolerance`)
+    /// - `UnitsAssignedBeforeApproval`: Request is still Pending or was rejected
+    /// - `ContractPaused`: Fulfillment is paused
     pub fn assign_blood_units(
         env: Env,
         request_id: u64,
-        unit_ids: soroban_sdk::Vec<u64>,
+        units: Vec<UnitAssignment>,
     ) -> Result<(), ContractError> {
         let admin = storage::get_admin(&env);
         admin.require_auth();
 
+        if storage::get_pause_flags(&env).fulfill_paused {
+            return Err(ContractError::ContractPaused);
+        }
+
         // Get existing request
-        let mut request = storage::get_blood_request(&env, request_id)
-            .ok_or(ContractError::RequestNotFound)?;
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
 
-        // Assign units
-        request.assigned_units = unit_ids.clone();
+        // Phenotype tags live on the units, in the inventory contract
+        let inventory = if request.required_phenotype.is_empty() {
+            None
+        } else {
+            Some(
+                storage::get_inventory_contract(&env)
+                    .ok_or(ContractError::InventoryNotConfigured)?,
+            )
+        };
+
+        // Every unit must match the request's blood type and phenotype and
+        // be listed once, so per-bank totals add up to the assigned volume
+        let mut unit_ids = Vec::new(&env);
+        let mut assigned = Vec::new(&env);
+        let mut total_ml = 0u32;
+        for unit in units.iter() {
+            if !request.accepts_unit_type(unit.blood_type) {
+                return Err(ContractError::IncompatibleBloodType);
+            }
+            if unit_ids.contains(unit.unit_id) {
+                return Err(ContractError::InvalidInput);
+            }
+            if let Some(inventory) = &inventory {
+                let args = vec![
+                    &env,
+                    unit.unit_id.into_val(&env),
+                    request.required_phenotype.into_val(&env),
+                ];
+                if !env.invoke_contract::<bool>(
+                    inventory,
+                    &Symbol::new(&env, "has_phenotype_tags"),
+                    args,
+                ) {
+                    return Err(ContractError::PhenotypeMismatch);
+                }
+            }
+            total_ml = total_ml
+                .checked_add(unit.quantity_ml)
+                .ok_or(ContractError::InvalidQuantity)?;
+            unit_ids.push_back(unit.unit_id);
+            assigned.push_back(AssignedUnit {
+                unit_id: unit.unit_id,
+                quantity_ml: unit.quantity_ml,
+                bank_id: unit.bank_id,
+            });
+        }
+
+        let max_ml = storage::get_assignment_tolerance(&env).max_assignable_ml(request.quantity_ml);
+        if max_ml.is_some_and(|max_ml| total_ml > max_ml) {
+            return Err(ContractError::OverAssignment);
+        }
+
+        // Assign units, remembering which bank ships each one
+        request.assigned_units = unit_ids;
+        request.assignments = assigned.clone();
 
         // Store updated request
-        storage::set_blood_request(&env, &request);
+        Self::save_request(&env, &request)?;
+
+        // One event per source bank
+        events::emit_units_assigned(&env, request_id, &assigned);
+
+        // The closing shipment completes the request when so configured
+        if storage::is_auto_complete(&env)
+            && request.status == RequestStatus::InDelivery
+            && total_ml >= request.quantity_ml
+        {
+            Self::mark_completed(&env, &mut request)?;
+            events::emit_request_completed(&env, request_id);
+        }
 
-        // Emit event
-        events::emit_units_assigned(&env, request_id, unit_ids);
+        Ok(())
+    }
+
+    /// Put a request that cannot be served from stock on the waitlist
+    ///
+    /// The inventory contract keeps one waitlist per blood type, ordered by
+    /// priority score, and announces each new unit of that type with a
+    /// `match_available` event for the request at the top. The request
+    /// leaves the waitlist once it is fulfilled, dispatched or closed.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of the request to waitlist
+    ///
+    /// # Errors
+    /// - `RequestNotFound`: Request does not exist
+    /// - `InvalidStatusTransition`: Request is neither Pending nor Approved
+    /// - `AlreadyExists`: Request is already waitlisted
+    /// - `InventoryNotConfigured`: No inventory contract is configured
+    pub fn add_to_waitlist(env: Env, request_id: u64) -> Result<(), ContractError> {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        if !matches!(
+            request.status,
+            RequestStatus::Pending | RequestStatus::Approved
+        ) {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        if storage::is_waitlisted(&env, request_id) {
+            return Err(ContractError::AlreadyExists);
+        }
+
+        let inventory =
+            storage::get_inventory_contract(&env).ok_or(ContractError::InventoryNotConfigured)?;
+
+        Self::join_waitlist(&env, &inventory, &request);
 
         Ok(())
     }
 
+    /// Let a Critical request take over a unit reserved for a less urgent one
+    ///
+    /// The inventory contract moves the reservation to the Critical request.
+    /// The displaced request joins the waitlist for its blood type if it is
+    /// still open and not waitlisted already.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `admin` - Contract admin
+    /// * `unit_id` - Reserved blood unit
+    /// * `critical_request_id` - Critical request taking the unit
+    ///
+    /// # Returns
+    /// The request that lost the unit
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is not the admin
+    /// - `RequestNotFound`: Either request does not exist
+    /// - `InvalidUrgency`: Target request is not Critical
+    /// - `RequestAlreadyTerminal`: Target request is already closed
+    /// - `InventoryNotConfigured`: No inventory contract is configured
+    /// - `InvalidInput`: Unit is not reserved, or already reserved for the target
+    /// - `PreemptionNotAllowed`: Current holder is Critical as well
+    /// - `UnitsAlreadyAssigned`: Unit is already assigned to its holder
+    ///
+    /// # Events
+    /// Emits `reservation_preempted`; the inventory contract emits
+    /// `reservation_released` and `blood_reserved`
+    pub fn preempt_reservation(
+        env: Env,
+        admin: Address,
+        unit_id: u64,
+        critical_request_id: u64,
+    ) -> Result<u64, ContractError> {
+        admin.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if admin != storage::get_admin(&env) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let request = storage::get_blood_request(&env, critical_request_id)
+            .ok_or(ContractError::RequestNotFound)?;
+
+        if request.urgency != UrgencyLevel::Critical {
+            return Err(ContractError::InvalidUrgency);
+        }
+
+        if request.status.is_terminal() {
+            return Err(ContractError::RequestAlreadyTerminal);
+        }
+
+        let inventory =
+            storage::get_inventory_contract(&env).ok_or(ContractError::InventoryNotConfigured)?;
+
+        let holder_id = env
+            .invoke_contract::<Option<u64>>(
+                &inventory,
+                &Symbol::new(&env, "get_reserved_for"),
+                vec![&env, unit_id.into_val(&env)],
+            )
+            .ok_or(ContractError::InvalidInput)?;
+        if holder_id == critical_request_id {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let holder =
+            storage::get_blood_request(&env, holder_id).ok_or(ContractError::RequestNotFound)?;
+
+        if holder.urgency.priority_weight() >= request.urgency.priority_weight() {
+            return Err(ContractError::PreemptionNotAllowed);
+        }
+
+        if holder.assigned_units.contains(unit_id) {
+            return Err(ContractError::UnitsAlreadyAssigned);
+        }
+
+        let args = vec![
+            &env,
+            env.current_contract_address().into_val(&env),
+            unit_id.into_val(&env),
+            critical_request_id.into_val(&env),
+            request.blood_type.into_val(&env),
+            request.accept_compatible.into_val(&env),
+        ];
+        env.invoke_contract::<u64>(&inventory, &Symbol::new(&env, "transfer_reservation"), args);
+
+        // The displaced request goes back to waiting for stock
+        let waitlisted = matches!(
+            holder.status,
+            RequestStatus::Pending | RequestStatus::Approved
+        ) && !storage::is_waitlisted(&env, holder_id);
+        if waitlisted {
+            Self::join_waitlist(&env, &inventory, &holder);
+        }
+
+        events::emit_reservation_preempted(
+            &env,
+            unit_id,
+            holder_id,
+            critical_request_id,
+            waitlisted,
+        );
+
+        Ok(holder_id)
+    }
+
+    /// Check if a request is waiting for stock
+    pub fn is_waitlisted(env: Env, request_id: u64) -> bool {
+        storage::is_waitlisted(&env, request_id)
+    }
+
     /// Get all requests for a specific hospital
     ///
     /// # Arguments
@@ -406,6 +2567,105 @@ impl RequestContract {
         storage::get_requests_by_hospital(&env, &hospital)
     }
 
+    /// Get a window of a hospital's requests from one department, oldest first
+    ///
+    /// Requests created without a department are not indexed, so an empty
+    /// department matches nothing.
+    ///
+    /// # Arguments
+    /// * `hospital` - Hospital address
+    /// * `department` - Department the requests were made by
+    /// * `offset` - Number of IDs to skip
+    /// * `limit` - Maximum number of IDs (capped at `MAX_QUERY_LIMIT`)
+    pub fn get_requests_by_department(
+        env: Env,
+        hospital: Address,
+        department: String,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        storage::get_department_requests(
+            &env,
+            &hospital,
+            &department,
+            offset,
+            limit.min(MAX_QUERY_LIMIT),
+        )
+    }
+
+    /// Get a window of the requests one physician ordered at a hospital,
+    /// oldest first
+    ///
+    /// Clinically sensitive, so only the hospital itself or the admin may
+    /// read it. Requests created without a physician are not indexed.
+    ///
+    /// # Arguments
+    /// * `caller` - The hospital or the admin
+    /// * `hospital` - Hospital address
+    /// * `physician_id` - Physician the requests were ordered by
+    /// * `offset` - Number of IDs to skip
+    /// * `limit` - Maximum number of IDs (capped at `MAX_QUERY_LIMIT`)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `Unauthorized`: Caller is neither the hospital nor the admin
+    pub fn get_requests_by_physician(
+        env: Env,
+        caller: Address,
+        hospital: Address,
+        physician_id: String,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<u64>, ContractError> {
+        caller.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if caller != hospital && caller != storage::get_admin(&env) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        Ok(storage::get_physician_requests(
+            &env,
+            &hospital,
+            &physician_id,
+            offset,
+            limit.min(MAX_QUERY_LIMIT),
+        ))
+    }
+
+    /// Get summaries of all requests for a specific hospital
+    ///
+    /// Same order as `get_hospital_requests`.
+    pub fn get_hospital_requests_summary(env: Env, hospital: Address) -> Vec<RequestSummary> {
+        let ids = storage::get_requests_by_hospital(&env, &hospital);
+        Self::load_summaries_from_ids(&env, ids)
+    }
+
+    /// Get a hospital's most recent requests, newest first
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital` - Hospital address
+    /// * `n` - Number of requests wanted (capped at 25)
+    ///
+    /// # Returns
+    /// Up to `n` requests in reverse creation order
+    pub fn get_recent_hospital_requests(env: Env, hospital: Address, n: u32) -> Vec<BloodRequest> {
+        let ids = storage::get_requests_by_hospital(&env, &hospital);
+        let count = n.min(MAX_RECENT_REQUESTS).min(ids.len());
+
+        let mut requests = Vec::new(&env);
+        for i in (ids.len() - count..ids.len()).rev() {
+            if let Some(request) = storage::get_blood_request(&env, ids.get_unchecked(i)) {
+                requests.push_back(request);
+            }
+        }
+        requests
+    }
+
     /// Get all requests with a specific status
     ///
     /// # Arguments
@@ -418,6 +2678,29 @@ impl RequestContract {
         storage::get_requests_by_status(&env, status)
     }
 
+    /// Get a window of request IDs with a status
+    ///
+    /// Only the index pages covering the window are read, so deep indexes
+    /// stay cheap to page through.
+    ///
+    /// # Arguments
+    /// * `status` - Request status to filter by
+    /// * `offset` - Number of IDs to skip
+    /// * `limit` - Maximum number of IDs (capped at `MAX_QUERY_LIMIT`)
+    pub fn get_requests_by_status_page(
+        env: Env,
+        status: RequestStatus,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        storage::get_paged_index_slice(
+            &env,
+            storage::PagedIndex::Status(status),
+            offset,
+            limit.min(MAX_QUERY_LIMIT),
+        )
+    }
+
     /// Get all requests for a specific blood type
     ///
     /// # Arguments
@@ -430,28 +2713,139 @@ impl RequestContract {
         storage::get_requests_by_blood_type(&env, blood_type)
     }
 
-    /// Get all requests with a specific urgency level
-    ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `urgency` - Urgency level to filter by
+    /// Get all requests with a specific urgency level
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `urgency` - Urgency level to filter by
+    ///
+    /// # Returns
+    /// Vector of request IDs
+    pub fn get_requests_by_urgency(env: Env, urgency: UrgencyLevel) -> Vec<u64> {
+        storage::get_requests_by_urgency(&env, urgency)
+    }
+
+    /// Check if a hospital is authorized
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital` - Hospital address to check
+    ///
+    /// # Returns
+    /// true if authorized, not lapsed and not currently suspended, false
+    /// otherwise
+    pub fn is_hospital_authorized(env: Env, hospital: Address) -> bool {
+        storage::is_authorized_hospital(&env, &hospital)
+            && storage::get_active_suspension(&env, &hospital).is_none()
+    }
+
+    /// Get a page of the authorized hospitals with their current standing,
+    /// in authorization order
+    ///
+    /// Lists hospitals authorized via `initialize` or `authorize_hospital`,
+    /// including suspended and lapsed ones; the admin, which is always
+    /// authorized, appears only if it was also authorized explicitly.
+    ///
+    /// # Arguments
+    /// * `offset` - Number of hospitals to skip
+    /// * `limit` - Maximum number of entries (capped at 50)
+    pub fn list_authorized_hospitals_detail(
+        env: Env,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<HospitalEntry> {
+        let hospitals = storage::get_authorized_hospitals(&env);
+        let start = offset.min(hospitals.len());
+        let end = start
+            .saturating_add(limit.min(MAX_HOSPITAL_PAGE_LIMIT))
+            .min(hospitals.len());
+
+        let now = env.ledger().timestamp();
+        let mut entries = Vec::new(&env);
+        for hospital in hospitals.slice(start..end).iter() {
+            let valid_until = storage::get_hospital_valid_until(&env, &hospital);
+            entries.push_back(HospitalEntry {
+                suspended_until: storage::get_active_suspension(&env, &hospital),
+                requires_co_signer: !storage::get_request_signers(&env, &hospital).is_empty(),
+                valid_until,
+                remaining_validity: valid_until.map(|valid_until| valid_until.saturating_sub(now)),
+                hospital,
+            });
+        }
+        entries
+    }
+
+    /// Get the number of hospitals `list_authorized_hospitals_detail` pages
+    /// through
+    pub fn count_authorized_hospitals(env: Env) -> u32 {
+        storage::get_authorized_hospitals(&env).len()
+    }
+
+    /// Get the end of a hospital's suspension, or None if it is not suspended
+    pub fn get_hospital_suspension(env: Env, hospital: Address) -> Option<u64> {
+        storage::get_active_suspension(&env, &hospital)
+    }
+
+    /// Register a co-signer for the hospital's Critical requests
+    ///
+    /// Once a hospital has at least one signer, each of its Critical
+    /// requests must also be signed by one of them.
+    ///
+    /// # Errors
+    /// - `NotAuthorizedHospital`: Hospital is not authorized
+    /// - `InvalidInput`: Signer is the hospital itself
+    /// - `AlreadyExists`: Signer is already registered
+    pub fn add_request_signer(
+        env: Env,
+        hospital: Address,
+        signer: Address,
+    ) -> Result<(), ContractError> {
+        hospital.require_auth();
+
+        if !storage::is_authorized_hospital(&env, &hospital) {
+            return Err(ContractError::NotAuthorizedHospital);
+        }
+
+        if signer == hospital {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut signers = storage::get_request_signers(&env, &hospital);
+        if signers.contains(&signer) {
+            return Err(ContractError::AlreadyExists);
+        }
+
+        signers.push_back(signer);
+        storage::set_request_signers(&env, &hospital, &signers);
+
+        Ok(())
+    }
+
+    /// Remove a co-signer registered by the hospital
     ///
-    /// # Returns
-    /// Vector of request IDs
-    pub fn get_requests_by_urgency(env: Env, urgency: UrgencyLevel) -> Vec<u64> {
-        storage::get_requests_by_urgency(&env, urgency)
+    /// # Errors
+    /// - `NotFound`: Signer is not registered
+    pub fn remove_request_signer(
+        env: Env,
+        hospital: Address,
+        signer: Address,
+    ) -> Result<(), ContractError> {
+        hospital.require_auth();
+
+        let mut signers = storage::get_request_signers(&env, &hospital);
+        let index = signers
+            .first_index_of(&signer)
+            .ok_or(ContractError::NotFound)?;
+
+        signers.remove(index);
+        storage::set_request_signers(&env, &hospital, &signers);
+
+        Ok(())
     }
 
-    /// Check if a hospital is authorized
-    ///
-    /// # Arguments
-    /// * `env` - Contract environment
-    /// * `hospital` - Hospital address to check
-    ///
-    /// # Returns
-    /// true if authorized, false otherwise
-    pub fn is_hospital_authorized(env: Env, hospital: Address) -> bool {
-        storage::is_authorized_hospital(&env, &hospital)
+    /// Get the co-signers registered by a hospital
+    pub fn get_request_signers(env: Env, hospital: Address) -> Vec<Address> {
+        storage::get_request_signers(&env, &hospital)
     }
 
     // ========== Advanced Query Functions ==========
@@ -488,22 +2882,20 @@ impl RequestContract {
     ) -> Vec<BloodRequest> {
         // Get all request IDs for this hospital
         let request_ids = storage::get_requests_by_hospital(&env, &hospital_id);
-        
+
         // Load full request objects
         let mut requests = Self::load_requests_from_ids(&env, request_ids);
-        
+
         // Apply status filter if provided
         if let Some(status) = status_filter {
-            requests = requests.into_iter()
-                .filter(|r| r.status == status)
-                .collect();
+            requests = Self::filter_by_status(&env, requests, status);
         }
-        
+
         // Apply pagination
         Self::apply_pagination(requests, limit, offset)
     }
 
-    /// Query all pending requests across hospitals, sorted by urgency
+    /// Query all pending requests across hospitals, sorted by priority
     ///
     /// # Arguments
     /// * `env` - Contract environment
@@ -512,22 +2904,167 @@ impl RequestContract {
     ///
     /// # Returns
     /// Vector of pending requests, sorted by urgency (Critical > Urgent > Normal)
+    /// and then by earliest deadline
     pub fn query_pending_requests(
         env: Env,
         limit: Option<u32>,
         offset: Option<u32>,
     ) -> Vec<BloodRequest> {
-        // Get all pending request IDs
-        let request_ids = storage::get_requests_by_status(&env, RequestStatus::Pending);
-        
-        // Load full request objects
-        let mut requests = Self::load_requests_from_ids(&env, request_ids);
-        
-        // Sort by urgency (Critical > Urgent > Normal)
-        Self::sort_requests_by_urgency(&mut requests);
-        
-        // Apply pagination
-        Self::apply_pagination(requests, limit, offset)
+        // The pending queue is kept sorted, so only the requested page is loaded
+        let request_ids = Self::get_pending_queue_page(&env, limit, offset);
+        Self::load_requests_from_ids(&env, request_ids)
+    }
+
+    /// Get the IDs at the front of the pending queue
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `limit` - Maximum number of results (defaults to 50, max 200)
+    ///
+    /// # Returns
+    /// Pending request IDs, highest priority first
+    pub fn get_pending_queue(env: Env, limit: Option<u32>) -> Vec<u64> {
+        Self::get_pending_queue_page(&env, limit, None)
+    }
+
+    /// Propose which of a snapshot of units should go to which pending
+    /// requests, without changing anything
+    ///
+    /// Open pending requests are visited in queue order: Critical first,
+    /// then Urgent, then Normal, the earlier deadline first within an
+    /// urgency and the older request first after that. Each request takes
+    /// units that are still unallocated, compatible with it and good until
+    /// its `required_by`: units of its exact blood type first, then
+    /// compatible ones if it accepts them, soonest expiring first and lowest
+    /// ID first among those. A request is only proposed once fully covered;
+    /// otherwise its units stay free for the requests after it.
+    ///
+    /// # Arguments
+    /// * `units` - Available units; only the first `MAX_CANDIDATE_UNITS` are
+    ///   considered, and a repeated unit ID only once
+    /// * `max_requests` - How many open pending requests to visit (capped at
+    ///   200)
+    ///
+    /// # Returns
+    /// `(request_id, unit_ids)` pairs in the order the requests were visited
+    pub fn propose_allocation(
+        env: Env,
+        units: Vec<CandidateUnit>,
+        max_requests: u32,
+    ) -> Vec<(u64, Vec<u64>)> {
+        let now = env.ledger().timestamp();
+        let units = Self::sort_candidates(&env, &units);
+
+        let mut taken = Vec::new(&env);
+        for _ in 0..units.len() {
+            taken.push_back(false);
+        }
+
+        let mut proposal = Vec::new(&env);
+        let mut visited = 0u32;
+        for entry in storage::get_pending_queue(&env).iter() {
+            if visited >= max_requests.min(MAX_QUERY_LIMIT) {
+                break;
+            }
+            let Some(request) = storage::get_blood_request(&env, entry.request_id) else {
+                continue;
+            };
+            if request.is_overdue(now) {
+                continue;
+            }
+            visited += 1;
+
+            let mut picked = Vec::new(&env);
+            let mut covered_ml = 0u64;
+            for exact in [true, false] {
+                for i in 0..units.len() {
+                    if covered_ml >= request.quantity_ml as u64 {
+                        break;
+                    }
+                    let unit = units.get_unchecked(i);
+                    if taken.get_unchecked(i)
+                        || (unit.blood_type == request.blood_type) != exact
+                        || !request.accepts_unit_type(unit.blood_type)
+                        || unit.expiration_timestamp <= request.required_by
+                    {
+                        continue;
+                    }
+                    picked.push_back(i);
+                    covered_ml += unit.quantity_ml as u64;
+                }
+            }
+            if covered_ml < request.quantity_ml as u64 {
+                continue;
+            }
+
+            let mut unit_ids = Vec::new(&env);
+            for i in picked.iter() {
+                taken.set(i, true);
+                unit_ids.push_back(units.get_unchecked(i).unit_id);
+            }
+            proposal.push_back((request.id, unit_ids));
+        }
+        proposal
+    }
+
+    /// Get summaries of the requests at the front of the pending queue
+    ///
+    /// Same order as `get_pending_queue`.
+    pub fn get_pending_summaries(env: Env, limit: Option<u32>) -> Vec<RequestSummary> {
+        let ids = Self::get_pending_queue_page(&env, limit, None);
+        Self::load_summaries_from_ids(&env, ids)
+    }
+
+    /// Get the ID of the pending request to handle next
+    ///
+    /// Top of the pending queue (Critical first, earliest deadline as
+    /// tiebreak), skipping requests whose deadline has passed even if the
+    /// expiry sweep hasn't run yet.
+    ///
+    /// # Returns
+    /// The request ID, or None if no pending request is still open
+    pub fn get_next_pending_request(env: Env) -> Option<u64> {
+        Self::next_pending_request(&env).map(|request| request.id)
+    }
+
+    /// Get the full pending request to handle next
+    ///
+    /// Same selection as `get_next_pending_request`.
+    pub fn peek_next_pending_detail(env: Env) -> Option<BloodRequest> {
+        Self::next_pending_request(&env)
+    }
+
+    /// Get the pending requests that have waited longest, oldest first
+    ///
+    /// Requests whose deadline has passed are left out even if the expiry
+    /// sweep hasn't run yet.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `limit` - Maximum number of results (defaults to 50, max 200)
+    ///
+    /// # Returns
+    /// Pending request IDs ordered by creation time
+    pub fn get_pending_approvals(env: Env, limit: Option<u32>) -> Vec<u64> {
+        let max = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT);
+        let by_age = Self::open_pending_by_age(&env);
+
+        let mut ids = Vec::new(&env);
+        for (_, id) in by_age.iter().take(max as usize) {
+            ids.push_back(id);
+        }
+        ids
+    }
+
+    /// Get how long the oldest open pending request has been waiting
+    ///
+    /// # Returns
+    /// Seconds since that request was created, or None if nothing is pending
+    pub fn get_oldest_pending_age(env: Env) -> Option<u64> {
+        let now = env.ledger().timestamp();
+        Self::open_pending_by_age(&env)
+            .first()
+            .map(|(created_at, _)| now.saturating_sub(created_at))
     }
 
     /// Query requests by date range with optional status filtering
@@ -563,6 +3100,7 @@ impl RequestContract {
                 RequestStatus::Fulfilled,
                 RequestStatus::InDelivery,
                 RequestStatus::Completed,
+                RequestStatus::Rejected,
                 RequestStatus::Cancelled,
                 RequestStatus::Expired,
             ] {
@@ -573,17 +3111,78 @@ impl RequestContract {
             }
             all_ids
         };
-        
+
         // Load requests and filter by date range
-        let requests = Self::load_requests_from_ids(&env, request_ids)
-            .into_iter()
-            .filter(|r| r.created_at >= start_time && r.created_at <= end_time)
-            .collect();
-        
+        let mut requests = Vec::new(&env);
+        for request in Self::load_requests_from_ids(&env, request_ids).iter() {
+            if request.created_at >= start_time && request.created_at <= end_time {
+                requests.push_back(request);
+            }
+        }
+
         // Apply pagination
         Self::apply_pagination(requests, limit, offset)
     }
 
+    /// Get open requests whose deadline falls within a time window
+    ///
+    /// Reads the per-day deadline buckets covering the window, so only
+    /// requests due in that window are loaded. Closed requests are skipped.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `from` - Start of the window (inclusive)
+    /// * `to` - End of the window (inclusive)
+    /// * `offset` - Number of results to skip
+    /// * `limit` - Maximum number of results (defaults to 50, max 200)
+    ///
+    /// # Returns
+    /// Request IDs, grouped by deadline day
+    ///
+    /// # Errors
+    /// - `InvalidInput`: `from` is after `to`, or the window is longer than 30 days
+    pub fn get_requests_due_between(
+        env: Env,
+        from: u64,
+        to: u64,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Vec<u64>, ContractError> {
+        if from > to || to - from > storage::MAX_DUE_WINDOW_SECONDS {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut skip = offset.unwrap_or(0);
+        let max = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT);
+
+        let mut ids = Vec::new(&env);
+        for day in from / storage::SECONDS_PER_DAY..=to / storage::SECONDS_PER_DAY {
+            for id in storage::get_deadline_bucket(&env, day).iter() {
+                if ids.len() >= max {
+                    return Ok(ids);
+                }
+
+                let Some(request) = storage::get_blood_request(&env, id) else {
+                    continue;
+                };
+                if request.status.is_terminal()
+                    || request.required_by < from
+                    || request.required_by > to
+                {
+                    continue;
+                }
+
+                if skip > 0 {
+                    skip -= 1;
+                } else {
+                    ids.push_back(id);
+                }
+            }
+        }
+
+        Ok(ids)
+    }
+
     /// Query requests by urgency with optional status filtering
     ///
     /// # Arguments
@@ -604,17 +3203,15 @@ impl RequestContract {
     ) -> Vec<BloodRequest> {
         // Get request IDs by urgency
         let request_ids = storage::get_requests_by_urgency(&env, urgency);
-        
+
         // Load full request objects
         let mut requests = Self::load_requests_from_ids(&env, request_ids);
-        
+
         // Apply status filter if provided
         if let Some(status) = status_filter {
-            requests = requests.into_iter()
-                .filter(|r| r.status == status)
-                .collect();
+            requests = Self::filter_by_status(&env, requests, status);
         }
-        
+
         // Apply pagination
         Self::apply_pagination(requests, limit, offset)
     }
@@ -632,6 +3229,32 @@ impl RequestContract {
         requests
     }
 
+    /// Load summaries for a list of IDs, skipping any that don't exist
+    fn load_summaries_from_ids(env: &Env, ids: Vec<u64>) -> Vec<RequestSummary> {
+        let mut summaries = Vec::new(env);
+        for id in ids.iter() {
+            if let Some(request) = storage::get_blood_request(env, id) {
+                summaries.push_back(RequestSummary::from(request));
+            }
+        }
+        summaries
+    }
+
+    /// Keep only requests with the given status
+    fn filter_by_status(
+        env: &Env,
+        requests: Vec<BloodRequest>,
+        status: RequestStatus,
+    ) -> Vec<BloodRequest> {
+        let mut filtered = Vec::new(env);
+        for request in requests.iter() {
+            if request.status == status {
+                filtered.push_back(request);
+            }
+        }
+        filtered
+    }
+
     /// Apply pagination to a vector of requests
     fn apply_pagination(
         requests: Vec<BloodRequest>,
@@ -641,52 +3264,392 @@ impl RequestContract {
         let env = requests.env();
         let offset_val = offset.unwrap_or(0) as usize;
         let limit_val = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
-        
+
         let total = requests.len() as usize;
-        
+
         // If offset is beyond the length, return empty vector
         if offset_val >= total {
-            return Vec::new(&env);
+            return Vec::new(env);
         }
-        
+
         // Calculate end index
         let end = (offset_val + limit_val).min(total);
-        
+
         // Slice the vector
-        let mut result = Vec::new(&env);
+        let mut result = Vec::new(env);
         for i in offset_val..end {
             if let Some(request) = requests.get(i as u32) {
                 result.push_back(request);
             }
         }
-        
+
         result
     }
 
-    /// Sort requests by urgency (Critical > Urgent > Normal)
-    fn sort_requests_by_urgency(requests: &mut Vec<BloodRequest>) {
-        // Manual bubble sort since we can't use standard sort with Soroban Vec
-        let len = requests.len();
-        if len <= 1 {
+    /// Emit the totals of the previous epoch when the sweep enters a new one
+    fn roll_stats_epoch(env: &Env, epoch: u64) {
+        if let Some(last_epoch) = storage::get_stats_epoch(env) {
+            if last_epoch < epoch {
+                let mut counts = Vec::new(env);
+                let mut total = 0u32;
+                for blood_type in BloodType::ALL {
+                    let count = storage::get_unfulfilled_stats(env, blood_type, last_epoch);
+                    if count > 0 {
+                        counts.push_back(BloodTypeCount { blood_type, count });
+                        total += count;
+                    }
+                }
+                events::emit_unfulfilled_stats_rollover(env, last_epoch, counts, total);
+            }
+        }
+
+        storage::set_stats_epoch(env, epoch);
+    }
+
+    /// First non-overdue request in the pending queue
+    fn next_pending_request(env: &Env) -> Option<BloodRequest> {
+        let current_time = env.ledger().timestamp();
+
+        for entry in storage::get_pending_queue(env).iter() {
+            if let Some(request) = storage::get_blood_request(env, entry.request_id) {
+                if !request.is_overdue(current_time) {
+                    return Some(request);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The first `MAX_CANDIDATE_UNITS` distinct candidates, soonest
+    /// expiring first and lowest ID first on a tie
+    fn sort_candidates(env: &Env, units: &Vec<CandidateUnit>) -> Vec<CandidateUnit> {
+        let mut seen = Vec::new(env);
+        let mut sorted: Vec<CandidateUnit> = Vec::new(env);
+        for unit in units.iter().take(MAX_CANDIDATE_UNITS as usize) {
+            if seen.contains(unit.unit_id) {
+                continue;
+            }
+            seen.push_back(unit.unit_id);
+
+            let key = (unit.expiration_timestamp, unit.unit_id);
+            let mut low = 0u32;
+            let mut high = sorted.len();
+            while low < high {
+                let mid = low + (high - low) / 2;
+                let other = sorted.get_unchecked(mid);
+                if (other.expiration_timestamp, other.unit_id) <= key {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            sorted.insert(low, unit);
+        }
+        sorted
+    }
+
+    /// Read a page of request IDs from the pending queue
+    fn get_pending_queue_page(env: &Env, limit: Option<u32>, offset: Option<u32>) -> Vec<u64> {
+        let queue = storage::get_pending_queue(env);
+        let start = offset.unwrap_or(0).min(queue.len());
+        let limit_val = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT);
+        let end = start.saturating_add(limit_val).min(queue.len());
+
+        let mut ids = Vec::new(env);
+        for i in start..end {
+            ids.push_back(queue.get_unchecked(i).request_id);
+        }
+        ids
+    }
+
+    /// Cancel a request the caller may cancel, keeping the indexes,
+    /// inventory links and demand statistics in step
+    fn cancel(
+        env: &Env,
+        request: &mut BloodRequest,
+        caller: &Address,
+    ) -> Result<(), ContractError> {
+        let request_id = request.id;
+        let old_status = request.status;
+        request.status = RequestStatus::Cancelled;
+
+        // Save and update index
+        Self::save_request(env, request)?;
+        storage::update_status_index(env, request_id, old_status, RequestStatus::Cancelled);
+        storage::record_report_cancelled(env, &request.hospital_id, storage::current_epoch(env));
+        if old_status != RequestStatus::Pending {
+            storage::record_score_late_cancellation(env, &request.hospital_id);
+        }
+
+        // Free any units reserved for it
+        Self::release_reserved_units(env, request_id);
+        Self::leave_waitlist(env, request_id);
+        Self::track_orphaned_assignment(env, request);
+
+        // Demand withdrawn within the epoch it was raised never counted
+        let created_epoch = storage::epoch_of(request.created_at);
+        if created_epoch == storage::current_epoch(env) {
+            storage::retract_demand(env, request.blood_type, created_epoch, request.quantity_ml);
+        }
+
+        // Emit events
+        events::emit_request_cancelled(env, request_id, old_status);
+        if !request.assigned_units.is_empty() {
+            events::emit_assigned_units_released(env, request, caller);
+        }
+
+        Ok(())
+    }
+
+    /// Load a request the hospital owns that is still Pending or Approved
+    fn load_owned_open_request(
+        env: &Env,
+        hospital_id: &Address,
+        request_id: u64,
+    ) -> Result<BloodRequest, ContractError> {
+        hospital_id.require_auth();
+
+        if !storage::is_initialized(env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let request =
+            storage::get_blood_request(env, request_id).ok_or(ContractError::RequestNotFound)?;
+
+        if &request.hospital_id != hospital_id {
+            return Err(ContractError::NotRequestCreator);
+        }
+
+        if !matches!(
+            request.status,
+            RequestStatus::Pending | RequestStatus::Approved
+        ) {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        Ok(request)
+    }
+
+    /// Ask the inventory contract, if configured, to free units reserved or
+    /// donated for a request
+    fn release_reserved_units(env: &Env, request_id: u64) {
+        if let Some(inventory) = storage::get_inventory_contract(env) {
+            let args = vec![
+                env,
+                env.current_contract_address().into_val(env),
+                request_id.into_val(env),
+            ];
+            env.invoke_contract::<u32>(
+                &inventory,
+                &Symbol::new(env, "release_units_for_request"),
+                args,
+            );
+        }
+    }
+
+    /// Index a cancelled or expired request that still lists assigned units
+    fn track_orphaned_assignment(env: &Env, request: &BloodRequest) {
+        if matches!(
+            request.status,
+            RequestStatus::Cancelled | RequestStatus::Expired
+        ) && !request.assigned_units.is_empty()
+        {
+            storage::add_orphaned_assignment(env, request.id);
+        }
+    }
+
+    /// Put a request on the inventory contract's waitlist for its blood type
+    fn join_waitlist(env: &Env, inventory: &Address, request: &BloodRequest) {
+        storage::set_waitlisted(env, request.id);
+
+        let args = vec![
+            env,
+            env.current_contract_address().into_val(env),
+            request.id.into_val(env),
+            request.blood_type.into_val(env),
+            request.priority_score.into_val(env),
+        ];
+        env.invoke_contract::<()>(inventory, &Symbol::new(env, "waitlist_request"), args);
+    }
+
+    /// Take a request off the inventory contract's waitlist, if it is on it
+    fn leave_waitlist(env: &Env, request_id: u64) {
+        if !storage::is_waitlisted(env, request_id) {
             return;
         }
-        
-        for i in 0..len {
-            for j in 0..(len - i - 1) {
-                let curr = requests.get(j).unwrap();
-                let next = requests.get(j + 1).unwrap();
-                
-                // Sort by urgency (higher priority first)
-                if curr.urgency.priority_weight() < next.urgency.priority_weight() {
-                    // Swap
-                    let temp = curr.clone();
-                    requests.set(j, next);
-                    requests.set(j + 1, temp);
+        storage::clear_waitlisted(env, request_id);
+
+        if let Some(inventory) = storage::get_inventory_contract(env) {
+            let args = vec![
+                env,
+                env.current_contract_address().into_val(env),
+                request_id.into_val(env),
+            ];
+            env.invoke_contract::<bool>(
+                &inventory,
+                &Symbol::new(env, "remove_from_waitlist"),
+                args,
+            );
+        }
+    }
+
+    /// Move a request to Completed, stamping fulfillment time if still unset
+    ///
+    /// Units the request still holds in inventory but never shipped are freed.
+    fn mark_completed(env: &Env, request: &mut BloodRequest) -> Result<(), ContractError> {
+        let old_status = request.status;
+        request.status = RequestStatus::Completed;
+        request.delivery_eta = None;
+        if request.fulfilled_at.is_none() {
+            request.fulfilled_at = Some(env.ledger().timestamp());
+        }
+
+        Self::save_request(env, request)?;
+        storage::update_status_index(env, request.id, old_status, RequestStatus::Completed);
+        storage::record_report_completed(
+            env,
+            &request.hospital_id,
+            storage::current_epoch(env),
+            request.assigned_ml(),
+        );
+        storage::record_score_completed(env, &request.hospital_id);
+        Self::release_reserved_units(env, request.id);
+        Self::leave_waitlist(env, request.id);
+        Ok(())
+    }
+
+    /// Record an approval of a pending request, approving it once no
+    /// further approval is awaited
+    ///
+    /// Ordinary requests are approved straight away. Cross-region requests
+    /// collect one approval per `ApprovalAuthority`, each announced with
+    /// `approval_recorded`.
+    fn record_approval(
+        env: &Env,
+        request: &mut BloodRequest,
+        authority: ApprovalAuthority,
+        approver: &Address,
+    ) -> Result<(), ContractError> {
+        if !request.status.can_transition_to(&RequestStatus::Approved) {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        validation::validate_not_expired(env, request.required_by)?;
+
+        if request.cross_region {
+            if !request.awaits_approval(authority) {
+                return Err(ContractError::ApprovalAlreadyRecorded);
+            }
+            request.approvals |= authority.bit();
+            events::emit_approval_recorded(env, request, authority, approver);
+        }
+
+        if request.awaits_approval(ApprovalAuthority::Admin)
+            || request.awaits_approval(ApprovalAuthority::Regional)
+        {
+            return Self::save_request(env, request);
+        }
+
+        let old_status = request.status;
+        request.status = RequestStatus::Approved;
+        request.approved_at = Some(env.ledger().timestamp());
+        request.approved_by = Some(approver.clone());
+
+        Self::save_request(env, request)?;
+        storage::update_status_index(env, request.id, old_status, RequestStatus::Approved);
+        events::emit_request_status_changed(env, request.id, old_status, RequestStatus::Approved);
+        events::emit_request_approved(env, request);
+
+        Ok(())
+    }
+
+    /// Verify the co-signer of a new request
+    ///
+    /// A supplied co-signer must be registered by the hospital and sign the
+    /// transaction. Critical requests from hospitals with registered signers
+    /// must have one.
+    fn check_co_signer(
+        env: &Env,
+        hospital: &Address,
+        urgency: UrgencyLevel,
+        co_signer: &Option<Address>,
+    ) -> Result<(), ContractError> {
+        let signers = storage::get_request_signers(env, hospital);
+
+        match co_signer {
+            Some(signer) => {
+                if !signers.contains(signer) {
+                    return Err(ContractError::NotRequestSigner);
+                }
+                signer.require_auth();
+                Ok(())
+            }
+            None if urgency == UrgencyLevel::Critical && !signers.is_empty() => {
+                Err(ContractError::CoSignerRequired)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Collect `(created_at, id)` of pending requests that are not yet
+    /// overdue, oldest first
+    ///
+    /// The status index is mostly in creation order, but reverted approvals
+    /// are appended at the end, so the entries are sorted here.
+    fn open_pending_by_age(env: &Env) -> Vec<(u64, u64)> {
+        let now = env.ledger().timestamp();
+        let mut by_age: Vec<(u64, u64)> = Vec::new(env);
+
+        for id in storage::get_requests_by_status(env, RequestStatus::Pending).iter() {
+            let Some(request) = storage::get_blood_request(env, id) else {
+                continue;
+            };
+            if request.is_overdue(now) {
+                continue;
+            }
+
+            let key = (request.created_at, id);
+            let mut low = 0u32;
+            let mut high = by_age.len();
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if by_age.get_unchecked(mid) <= key {
+                    low = mid + 1;
+                } else {
+                    high = mid;
                 }
             }
+            by_age.insert(low, key);
+        }
+
+        by_age
+    }
+
+    /// Recompute the priority score, re-slot a pending request and persist it
+    fn reprioritize(env: &Env, request: &mut BloodRequest) -> Result<(), ContractError> {
+        request.priority_score =
+            BloodRequest::compute_priority_score(request.urgency, request.required_by);
+
+        if request.status == RequestStatus::Pending {
+            storage::remove_from_pending_queue(env, request.id);
+            storage::insert_into_pending_queue(env, request);
         }
+
+        Self::save_request(env, request)?;
+        events::emit_request_reprioritized(env, request);
+        Ok(())
+    }
+
+    /// Check a rewritten request is still internally consistent, then store it
+    fn save_request(env: &Env, request: &BloodRequest) -> Result<(), ContractError> {
+        request.validate(env.ledger().timestamp())?;
+        storage::set_blood_request(env, request);
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod test;
+#[cfg(test)]
+mod test_lifecycle;
@@ -1,63 +1,1143 @@
-use crate::types::{DataKey, BloodUnit};
-use soroban_sdk::{Address, Env, Vec};
+use crate::error::ContractError;
+use crate::types::{
+    AssignmentTolerance, BloodRequest, BloodType, CompletionOverride, ContractVersion, DataKey,
+    DemandStats, ExpiryReason, HospitalReport, HospitalScore, IdempotencyRecord, ImportState,
+    PauseFlags, PendingQueueEntry, RejectionReason, RequestStatus, UrgencyLevel,
+};
+use crate::validation::{DEFAULT_CANCELLATION_GRACE_SECONDS, DEFAULT_PEDIATRIC_MIN_QUANTITY_ML};
+use soroban_sdk::{Address, BytesN, Env, Map, String, Vec};
 
-// --- INSTANCE STORAGE (Admin & Authorization) ---
-// Used for high-frequency access and auth checks
+/// Maximum number of days in the future a request can be required by
+pub const MAX_REQUEST_WINDOW_DAYS: u64 = 30;
+/// Minimum lead time (seconds) between creation and required_by
+pub const MIN_REQUEST_WINDOW_SECONDS: u64 = 3600;
+pub const SECONDS_PER_DAY: u64 = 86400;
+/// Length of a statistics epoch (30 days)
+pub const SECONDS_PER_EPOCH: u64 = 30 * SECONDS_PER_DAY;
+/// Widest deadline window accepted by `get_requests_due_between`
+pub const MAX_DUE_WINDOW_SECONDS: u64 = MAX_REQUEST_WINDOW_DAYS * SECONDS_PER_DAY;
+/// How long an idempotency key keeps returning its request
+pub const IDEMPOTENCY_WINDOW_SECONDS: u64 = SECONDS_PER_DAY;
+/// Scale of `HospitalScore::reliability_bps` (100%)
+pub const RELIABILITY_SCALE_BPS: u32 = 10_000;
 
-pub fn set_admin(env: Env, admin: Address) {
-    env.storage().instance().set(&DataKey::Admin, &admin);
+/// Version of this build of the contract, stored by `initialize`
+pub const CONTRACT_VERSION: ContractVersion = ContractVersion { major: 1, minor: 0 };
+
+/// Check if the contract has been initialized
+pub fn is_initialized(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Admin)
+}
+
+/// Get the admin address
+pub fn get_admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("Admin not initialized")
+}
+
+/// Set the admin address
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+/// Get the stored contract version (None before `initialize`)
+pub fn get_version(env: &Env) -> Option<ContractVersion> {
+    env.storage().instance().get(&DataKey::Version)
+}
+
+/// Store the contract version
+pub fn set_version(env: &Env, version: &ContractVersion) {
+    env.storage().instance().set(&DataKey::Version, version);
+}
+
+/// Get the inventory contract holding reserved units, if configured
+pub fn get_inventory_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::InventoryContract)
+}
+
+/// Set the inventory contract holding reserved units
+pub fn set_inventory_contract(env: &Env, inventory: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::InventoryContract, inventory);
+}
+
+/// Get the regional authority approving cross-region requests, if configured
+pub fn get_regional_authority(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::RegionalAuthority)
+}
+
+/// Set the regional authority approving cross-region requests
+pub fn set_regional_authority(env: &Env, authority: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::RegionalAuthority, authority);
+}
+
+/// Get the current pause switches (nothing paused by default)
+pub fn get_pause_flags(env: &Env) -> PauseFlags {
+    env.storage()
+        .instance()
+        .get(&DataKey::PauseFlags)
+        .unwrap_or_default()
+}
+
+/// Set the pause switches
+pub fn set_pause_flags(env: &Env, flags: &PauseFlags) {
+    env.storage().instance().set(&DataKey::PauseFlags, flags);
+}
+
+/// Get the quantity step in ml (0 when alignment is not enforced)
+pub fn get_quantity_step(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::QuantityStep)
+        .unwrap_or(0)
+}
+
+/// Set the quantity step in ml (0 disables alignment)
+pub fn set_quantity_step(env: &Env, step_ml: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::QuantityStep, &step_ml);
+}
+
+/// Get the pediatric minimum quantity in ml
+pub fn get_pediatric_min_quantity(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::PediatricMinQuantity)
+        .unwrap_or(DEFAULT_PEDIATRIC_MIN_QUANTITY_ML)
+}
+
+/// Set the pediatric minimum quantity in ml
+pub fn set_pediatric_min_quantity(env: &Env, min_ml: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::PediatricMinQuantity, &min_ml);
+}
+
+/// Get the cancellation grace period in seconds
+pub fn get_cancellation_grace_period(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::CancellationGracePeriod)
+        .unwrap_or(DEFAULT_CANCELLATION_GRACE_SECONDS)
+}
+
+/// Set the cancellation grace period in seconds
+pub fn set_cancellation_grace_period(env: &Env, seconds: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::CancellationGracePeriod, &seconds);
+}
+
+/// Check if requests complete on their own once fully assigned
+pub fn is_auto_complete(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::AutoComplete)
+        .unwrap_or(false)
+}
+
+/// Turn auto-completion of fully assigned requests on or off
+pub fn set_auto_complete(env: &Env, enabled: bool) {
+    env.storage()
+        .instance()
+        .set(&DataKey::AutoComplete, &enabled);
+}
+
+/// Get the assignment tolerance (Unlimited unless configured)
+pub fn get_assignment_tolerance(env: &Env) -> AssignmentTolerance {
+    env.storage()
+        .instance()
+        .get(&DataKey::AssignmentTolerance)
+        .unwrap_or(AssignmentTolerance::Unlimited)
+}
+
+/// Set the assignment tolerance
+pub fn set_assignment_tolerance(env: &Env, tolerance: AssignmentTolerance) {
+    env.storage()
+        .instance()
+        .set(&DataKey::AssignmentTolerance, &tolerance);
+}
+
+/// Get the admin proposed by the current admin, if any
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::PendingAdmin)
+}
+
+/// Record the admin proposed by the current admin
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::PendingAdmin, admin);
+}
+
+/// Clear the pending admin proposal
+pub fn clear_pending_admin(env: &Env) {
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+}
+
+/// Authorize a hospital to create requests, listing it unless it already is
+pub fn authorize_hospital(env: &Env, hospital: &Address) {
+    if is_explicitly_authorized_hospital(env, hospital) {
+        return;
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::AuthorizedHospital(hospital.clone()), &true);
+
+    let mut hospitals = get_authorized_hospitals(env);
+    hospitals.push_back(hospital.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::AuthorizedHospitals, &hospitals);
+}
+
+/// Revoke a hospital's authorization and drop it from the list
+pub fn revoke_hospital(env: &Env, hospital: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::AuthorizedHospital(hospital.clone()));
+    set_hospital_valid_until(env, hospital, None);
+
+    let mut hospitals = get_authorized_hospitals(env);
+    if let Some(index) = hospitals.first_index_of(hospital) {
+        hospitals.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::AuthorizedHospitals, &hospitals);
+    }
+}
+
+/// Get the hospitals authorized via `authorize_hospital`, in authorization
+/// order
+pub fn get_authorized_hospitals(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AuthorizedHospitals)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Check if an address is authorized as a hospital
+///
+/// The admin is always authorized
+pub fn is_authorized_hospital(env: &Env, hospital: &Address) -> bool {
+    if is_initialized(env) && hospital == &get_admin(env) {
+        return true;
+    }
+
+    is_explicitly_authorized_hospital(env, hospital) && !is_hospital_lapsed(env, hospital)
+}
+
+/// Check if a hospital was authorized via `authorize_hospital`, ignoring the
+/// implicit authorization of the admin
+pub fn is_explicitly_authorized_hospital(env: &Env, hospital: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::AuthorizedHospital(hospital.clone()))
+}
+
+/// Get when a hospital's authorization lapses, if it was given a limit
+pub fn get_hospital_valid_until(env: &Env, hospital: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::HospitalValidUntil(hospital.clone()))
+}
+
+/// Set when a hospital's authorization lapses, or None for no limit
+pub fn set_hospital_valid_until(env: &Env, hospital: &Address, valid_until: Option<u64>) {
+    let key = DataKey::HospitalValidUntil(hospital.clone());
+    match valid_until {
+        Some(valid_until) => env.storage().persistent().set(&key, &valid_until),
+        None => env.storage().persistent().remove(&key),
+    }
 }
 
-pub fn get_admin(env: Env) -> Address {
-    env.storage().instance().get(&DataKey::Admin).expect("Admin not set")
+/// Check if a hospital's authorization has lapsed
+///
+/// Authorizations lapse on their own once `valid_until` is reached.
+pub fn is_hospital_lapsed(env: &Env, hospital: &Address) -> bool {
+    get_hospital_valid_until(env, hospital)
+        .is_some_and(|valid_until| valid_until <= env.ledger().timestamp())
 }
 
-pub fn is_authorized_bank(env: Env, bank: &Address) -> bool {
-    // If the Registrar key exists, the bank is authorized
-    env.storage().instance().has(&DataKey::Registrar(bank.clone()))
+/// Get the end of a hospital's suspension if it is still in effect
+///
+/// Suspensions lift themselves once their end time has passed.
+pub fn get_active_suspension(env: &Env, hospital: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::HospitalSuspension(hospital.clone()))
+        .filter(|until: &u64| *until > env.ledger().timestamp())
 }
 
-pub fn set_authorized_bank(env: Env, bank: &Address, authorized: bool) {
-    let key = DataKey::Registrar(bank.clone());
-    if authorized {
-        env.storage().instance().set(&key, &true);
+/// Suspend a hospital until the given timestamp
+pub fn set_suspension(env: &Env, hospital: &Address, until: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::HospitalSuspension(hospital.clone()), &until);
+}
+
+/// Remove a hospital's suspension
+pub fn clear_suspension(env: &Env, hospital: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::HospitalSuspension(hospital.clone()));
+}
+
+/// Get the current request counter
+pub fn get_request_counter(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::RequestCounter)
+        .unwrap_or(0)
+}
+
+/// Set the request counter; the next request gets `value + 1`
+pub fn set_request_counter(env: &Env, value: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::RequestCounter, &value);
+}
+
+/// Increment and return the next request ID
+pub fn increment_request_id(env: &Env) -> Result<u64, ContractError> {
+    let next_id = get_request_counter(env)
+        .checked_add(1)
+        .ok_or(ContractError::CounterOverflow)?;
+    set_request_counter(env, next_id);
+    Ok(next_id)
+}
+
+/// Store a blood request
+pub fn set_blood_request(env: &Env, request: &BloodRequest) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::BloodRequest(request.id), request);
+}
+
+/// Get a blood request by ID
+pub fn get_blood_request(env: &Env, id: u64) -> Option<BloodRequest> {
+    env.storage().persistent().get(&DataKey::BloodRequest(id))
+}
+
+/// Append a request ID to an index unless it is already present
+fn push_to_index(env: &Env, key: &DataKey, request_id: u64) {
+    let mut ids: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+
+    if ids.contains(request_id) {
+        return;
+    }
+    ids.push_back(request_id);
+    env.storage().persistent().set(key, &ids);
+}
+
+/// Remove a request ID from an index
+fn remove_from_index(env: &Env, key: &DataKey, request_id: u64) {
+    let mut ids: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+
+    if let Some(index) = ids.first_index_of(request_id) {
+        ids.remove(index);
+        env.storage().persistent().set(key, &ids);
+    }
+}
+
+/// Read an index, defaulting to empty
+fn get_index(env: &Env, key: &DataKey) -> Vec<u64> {
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
+
+/// Number of request IDs held by a full index page
+pub const INDEX_PAGE_SIZE: u32 = 100;
+
+/// Hot index stored as fixed-size pages instead of one growing Vec
+///
+/// Inserting touches only the last page, so its cost no longer grows with
+/// the size of the index. Each member also has a slot entry recording its
+/// page, so membership checks and removals read one page.
+#[derive(Clone, Copy)]
+pub enum PagedIndex {
+    Status(RequestStatus),
+    Urgency(UrgencyLevel),
+}
+
+impl PagedIndex {
+    fn page_key(self, page: u32) -> DataKey {
+        match self {
+            PagedIndex::Status(status) => DataKey::StatusIndexPage(status, page),
+            PagedIndex::Urgency(urgency) => DataKey::UrgencyIndexPage(urgency, page),
+        }
+    }
+
+    fn page_count_key(self) -> DataKey {
+        match self {
+            PagedIndex::Status(status) => DataKey::StatusIndexPageCount(status),
+            PagedIndex::Urgency(urgency) => DataKey::UrgencyIndexPageCount(urgency),
+        }
+    }
+
+    fn slot_key(self, request_id: u64) -> DataKey {
+        match self {
+            PagedIndex::Status(status) => DataKey::StatusIndexSlot(status, request_id),
+            PagedIndex::Urgency(urgency) => DataKey::UrgencyIndexSlot(urgency, request_id),
+        }
+    }
+
+    fn legacy_key(self) -> DataKey {
+        match self {
+            PagedIndex::Status(status) => DataKey::StatusIndex(status),
+            PagedIndex::Urgency(urgency) => DataKey::UrgencyIndex(urgency),
+        }
+    }
+}
+
+/// Split a legacy monolithic index into pages, once
+fn migrate_legacy_index(env: &Env, index: PagedIndex) {
+    let legacy_key = index.legacy_key();
+    let legacy: Option<Vec<u64>> = env.storage().persistent().get(&legacy_key);
+    let Some(ids) = legacy else {
+        return;
+    };
+
+    let mut page_count = 0u32;
+    let mut start = 0u32;
+    while start < ids.len() {
+        let end = (start + INDEX_PAGE_SIZE).min(ids.len());
+        let page = ids.slice(start..end);
+        for request_id in page.iter() {
+            env.storage()
+                .persistent()
+                .set(&index.slot_key(request_id), &page_count);
+        }
+        env.storage()
+            .persistent()
+            .set(&index.page_key(page_count), &page);
+        page_count += 1;
+        start = end;
+    }
+
+    env.storage()
+        .persistent()
+        .set(&index.page_count_key(), &page_count);
+    env.storage().persistent().remove(&legacy_key);
+}
+
+/// Number of pages of a paged index
+///
+/// Pages emptied by removals in the middle of the index still count; they
+/// read as empty.
+pub fn get_index_page_count(env: &Env, index: PagedIndex) -> u32 {
+    migrate_legacy_index(env, index);
+    env.storage()
+        .persistent()
+        .get(&index.page_count_key())
+        .unwrap_or(0)
+}
+
+/// Read one page of a paged index, defaulting to empty
+pub fn get_index_page(env: &Env, index: PagedIndex, page: u32) -> Vec<u64> {
+    get_index(env, &index.page_key(page))
+}
+
+/// Page of a paged index holding a request ID, if the index holds it
+pub fn get_index_slot(env: &Env, index: PagedIndex, request_id: u64) -> Option<u32> {
+    env.storage().persistent().get(&index.slot_key(request_id))
+}
+
+/// Append a request ID to the last page of a paged index, unless the index
+/// already holds it
+fn push_to_paged_index(env: &Env, index: PagedIndex, request_id: u64) {
+    let page_count = get_index_page_count(env, index);
+    if get_index_slot(env, index, request_id).is_some() {
+        return;
+    }
+
+    let last = page_count.saturating_sub(1);
+    let mut ids = get_index_page(env, index, last);
+    let page = if page_count > 0 && ids.len() < INDEX_PAGE_SIZE {
+        last
     } else {
-        env.storage().instance().remove(&key);
+        ids = Vec::new(env);
+        env.storage()
+            .persistent()
+            .set(&index.page_count_key(), &(page_count + 1));
+        page_count
+    };
+
+    ids.push_back(request_id);
+    env.storage().persistent().set(&index.page_key(page), &ids);
+    env.storage()
+        .persistent()
+        .set(&index.slot_key(request_id), &page);
+}
+
+/// Remove a request ID from the page of a paged index that holds it
+///
+/// Only that page is rewritten. A page left empty is deleted, and empty
+/// pages at the end of the index are dropped from its page count.
+fn remove_from_paged_index(env: &Env, index: PagedIndex, request_id: u64) {
+    let page_count = get_index_page_count(env, index);
+    let Some(page) = get_index_slot(env, index, request_id) else {
+        return;
+    };
+    env.storage()
+        .persistent()
+        .remove(&index.slot_key(request_id));
+
+    let mut ids = get_index_page(env, index, page);
+    if let Some(position) = ids.first_index_of(request_id) {
+        ids.remove(position);
+    }
+    if !ids.is_empty() {
+        env.storage().persistent().set(&index.page_key(page), &ids);
+        return;
+    }
+
+    env.storage().persistent().remove(&index.page_key(page));
+    if page + 1 == page_count {
+        let mut remaining = page;
+        while remaining > 0
+            && !env
+                .storage()
+                .persistent()
+                .has(&index.page_key(remaining - 1))
+        {
+            remaining -= 1;
+        }
+        env.storage()
+            .persistent()
+            .set(&index.page_count_key(), &remaining);
+    }
+}
+
+/// Read `limit` request IDs of a paged index after skipping `offset`,
+/// stopping at the first page that fills the result
+pub fn get_paged_index_slice(env: &Env, index: PagedIndex, offset: u32, limit: u32) -> Vec<u64> {
+    let mut result = Vec::new(env);
+    let mut skipped = 0u32;
+
+    for page in 0..get_index_page_count(env, index) {
+        if result.len() >= limit {
+            break;
+        }
+        let ids = get_index_page(env, index, page);
+        if skipped + ids.len() <= offset {
+            skipped += ids.len();
+            continue;
+        }
+        for id in ids.iter() {
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            if result.len() >= limit {
+                break;
+            }
+            result.push_back(id);
+        }
+    }
+    result
+}
+
+/// Read every request ID of a paged index, in insertion order
+fn get_paged_index(env: &Env, index: PagedIndex) -> Vec<u64> {
+    let mut result = Vec::new(env);
+    for page in 0..get_index_page_count(env, index) {
+        result.append(&get_index_page(env, index, page));
+    }
+    result
+}
+
+/// Number of request IDs held by a paged index, without reading them out
+#[cfg(test)]
+pub fn get_index_len(env: &Env, index: PagedIndex) -> u32 {
+    let mut len = 0;
+    for page in 0..get_index_page_count(env, index) {
+        len += get_index_page(env, index, page).len();
+    }
+    len
+}
+
+/// Add request to hospital index
+pub fn add_to_hospital_index(env: &Env, request: &BloodRequest) {
+    push_to_index(
+        env,
+        &DataKey::HospitalIndex(request.hospital_id.clone()),
+        request.id,
+    );
+}
+
+/// Add request to the index of its hospital and department, if it has one
+pub fn add_to_department_index(env: &Env, request: &BloodRequest) {
+    if request.department.is_empty() {
+        return;
+    }
+    push_to_index(
+        env,
+        &DataKey::DepartmentIndex(request.hospital_id.clone(), request.department.clone()),
+        request.id,
+    );
+}
+
+/// Get a window of request IDs for a hospital's department
+pub fn get_department_requests(
+    env: &Env,
+    hospital: &Address,
+    department: &String,
+    offset: u32,
+    limit: u32,
+) -> Vec<u64> {
+    let ids = get_index(
+        env,
+        &DataKey::DepartmentIndex(hospital.clone(), department.clone()),
+    );
+    let start = offset.min(ids.len());
+    let end = start.saturating_add(limit).min(ids.len());
+    ids.slice(start..end)
+}
+
+/// Add request to the index of its hospital and physician, if it has one
+pub fn add_to_physician_index(env: &Env, request: &BloodRequest) {
+    if request.physician_id.is_empty() {
+        return;
+    }
+    push_to_index(
+        env,
+        &DataKey::PhysicianIndex(request.hospital_id.clone(), request.physician_id.clone()),
+        request.id,
+    );
+}
+
+/// Get a window of request IDs ordered by one of a hospital's physicians
+pub fn get_physician_requests(
+    env: &Env,
+    hospital: &Address,
+    physician_id: &String,
+    offset: u32,
+    limit: u32,
+) -> Vec<u64> {
+    let ids = get_index(
+        env,
+        &DataKey::PhysicianIndex(hospital.clone(), physician_id.clone()),
+    );
+    let start = offset.min(ids.len());
+    let end = start.saturating_add(limit).min(ids.len());
+    ids.slice(start..end)
+}
+
+/// Add request to blood type index
+pub fn add_to_blood_type_index(env: &Env, request: &BloodRequest) {
+    push_to_index(
+        env,
+        &DataKey::BloodTypeIndex(request.blood_type),
+        request.id,
+    );
+}
+
+/// Add request to status index
+pub fn add_to_status_index(env: &Env, request: &BloodRequest) {
+    push_to_paged_index(env, PagedIndex::Status(request.status), request.id);
+}
+
+/// Add request to urgency index
+pub fn add_to_urgency_index(env: &Env, request: &BloodRequest) {
+    push_to_paged_index(env, PagedIndex::Urgency(request.urgency), request.id);
+}
+
+/// Add request to the bucket of its deadline day
+pub fn add_to_deadline_bucket(env: &Env, request: &BloodRequest) {
+    push_to_index(
+        env,
+        &DataKey::DeadlineBucket(request.required_by / SECONDS_PER_DAY),
+        request.id,
+    );
+}
+
+/// Move a request between deadline buckets after its deadline changed
+pub fn update_deadline_bucket(
+    env: &Env,
+    request_id: u64,
+    old_required_by: u64,
+    new_required_by: u64,
+) {
+    let old_day = old_required_by / SECONDS_PER_DAY;
+    let new_day = new_required_by / SECONDS_PER_DAY;
+
+    if old_day != new_day {
+        remove_from_index(env, &DataKey::DeadlineBucket(old_day), request_id);
+        push_to_index(env, &DataKey::DeadlineBucket(new_day), request_id);
+    }
+}
+
+/// Get request IDs whose deadline falls on a day
+pub fn get_deadline_bucket(env: &Env, day: u64) -> Vec<u64> {
+    get_index(env, &DataKey::DeadlineBucket(day))
+}
+
+/// Move a request between status indexes
+///
+/// A request leaving Pending is also dropped from the pending queue.
+pub fn update_status_index(
+    env: &Env,
+    request_id: u64,
+    old_status: RequestStatus,
+    new_status: RequestStatus,
+) {
+    remove_from_paged_index(env, PagedIndex::Status(old_status), request_id);
+    push_to_paged_index(env, PagedIndex::Status(new_status), request_id);
+
+    if old_status == RequestStatus::Pending && new_status != RequestStatus::Pending {
+        remove_from_pending_queue(env, request_id);
+    }
+}
+
+/// Move a request between urgency indexes
+pub fn update_urgency_index(
+    env: &Env,
+    request_id: u64,
+    old_urgency: UrgencyLevel,
+    new_urgency: UrgencyLevel,
+) {
+    remove_from_paged_index(env, PagedIndex::Urgency(old_urgency), request_id);
+    push_to_paged_index(env, PagedIndex::Urgency(new_urgency), request_id);
+}
+
+/// Read the pending queue, highest priority first
+pub fn get_pending_queue(env: &Env) -> Vec<PendingQueueEntry> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingQueue)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Insert a request into the pending queue at its priority position
+///
+/// Binary search for the first entry with a strictly lower score, so requests
+/// with equal scores keep their insertion order.
+pub fn insert_into_pending_queue(env: &Env, request: &BloodRequest) {
+    let mut queue = get_pending_queue(env);
+
+    let mut low = 0u32;
+    let mut high = queue.len();
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if queue.get_unchecked(mid).priority_score >= request.priority_score {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    queue.insert(
+        low,
+        PendingQueueEntry {
+            priority_score: request.priority_score,
+            request_id: request.id,
+        },
+    );
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingQueue, &queue);
+}
+
+/// Remove a request from the pending queue
+pub fn remove_from_pending_queue(env: &Env, request_id: u64) {
+    let mut queue = get_pending_queue(env);
+
+    for i in 0..queue.len() {
+        if queue.get_unchecked(i).request_id == request_id {
+            queue.remove(i);
+            env.storage()
+                .persistent()
+                .set(&DataKey::PendingQueue, &queue);
+            return;
+        }
+    }
+}
+
+/// Get request IDs for a hospital
+pub fn get_requests_by_hospital(env: &Env, hospital: &Address) -> Vec<u64> {
+    get_index(env, &DataKey::HospitalIndex(hospital.clone()))
+}
+
+/// Get request IDs with a status
+pub fn get_requests_by_status(env: &Env, status: RequestStatus) -> Vec<u64> {
+    get_paged_index(env, PagedIndex::Status(status))
+}
+
+/// Get request IDs for a blood type
+pub fn get_requests_by_blood_type(env: &Env, blood_type: BloodType) -> Vec<u64> {
+    get_index(env, &DataKey::BloodTypeIndex(blood_type))
+}
+
+/// Get request IDs with an urgency level
+pub fn get_requests_by_urgency(env: &Env, urgency: UrgencyLevel) -> Vec<u64> {
+    get_paged_index(env, PagedIndex::Urgency(urgency))
+}
+
+/// Epoch containing the current ledger time
+pub fn current_epoch(env: &Env) -> u64 {
+    epoch_of(env.ledger().timestamp())
+}
+
+/// Epoch containing a timestamp
+pub fn epoch_of(timestamp: u64) -> u64 {
+    timestamp / SECONDS_PER_EPOCH
+}
+
+/// Get the delivery contact of a request
+pub fn get_request_contact(env: &Env, request_id: u64) -> Option<String> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RequestContact(request_id))
+}
+
+/// Set or clear the delivery contact of a request
+pub fn set_request_contact(env: &Env, request_id: u64, contact: &Option<String>) {
+    let key = DataKey::RequestContact(request_id);
+    match contact {
+        Some(contact) => env.storage().persistent().set(&key, contact),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Record why a request expired
+pub fn set_expiry_reason(env: &Env, request_id: u64, reason: ExpiryReason) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ExpiryReason(request_id), &reason);
+}
+
+/// Get the reason a request expired
+pub fn get_expiry_reason(env: &Env, request_id: u64) -> Option<ExpiryReason> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ExpiryReason(request_id))
+}
+
+/// Record why a request was rejected
+pub fn set_rejection_reason(env: &Env, request_id: u64, reason: RejectionReason) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::RejectionReason(request_id), &reason);
+}
+
+/// Get the reason a request was rejected
+pub fn get_rejection_reason(env: &Env, request_id: u64) -> Option<RejectionReason> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RejectionReason(request_id))
+}
+
+/// Record the audit trail of a forced completion
+pub fn set_completion_override(env: &Env, record: &CompletionOverride) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::CompletionOverride(record.request_id), record);
+}
+
+/// Check if a request is on the inventory contract's waitlist
+pub fn is_waitlisted(env: &Env, request_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Waitlisted(request_id))
+}
+
+/// Record that a request joined the inventory contract's waitlist
+pub fn set_waitlisted(env: &Env, request_id: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Waitlisted(request_id), &true);
+}
+
+/// Record that a request left the inventory contract's waitlist
+pub fn clear_waitlisted(env: &Env, request_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Waitlisted(request_id));
+}
+
+/// Get the request created under a hospital's idempotency key
+pub fn get_idempotency_record(
+    env: &Env,
+    hospital_id: &Address,
+    key: &BytesN<32>,
+) -> Option<IdempotencyRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IdempotencyKey(hospital_id.clone(), key.clone()))
+}
+
+/// Remember the request created under a hospital's idempotency key
+pub fn set_idempotency_record(
+    env: &Env,
+    hospital_id: &Address,
+    key: &BytesN<32>,
+    record: &IdempotencyRecord,
+) {
+    env.storage().persistent().set(
+        &DataKey::IdempotencyKey(hospital_id.clone(), key.clone()),
+        record,
+    );
+}
+
+/// Forget a hospital's idempotency key
+pub fn remove_idempotency_record(env: &Env, hospital_id: &Address, key: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::IdempotencyKey(hospital_id.clone(), key.clone()));
+}
+
+/// Get the closed requests that still list assigned units, oldest first
+pub fn get_orphaned_assignments(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OrphanedAssignments)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add a closed request to the orphaned assignment index
+pub fn add_orphaned_assignment(env: &Env, request_id: u64) {
+    let mut ids = get_orphaned_assignments(env);
+    if !ids.contains(request_id) {
+        ids.push_back(request_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OrphanedAssignments, &ids);
     }
 }
 
-pub fn increment_blood_unit_id(env: Env) -> u64 {
-    let key = DataKey::UnitIdCounter;
-    let mut id: u64 = env.storage().instance().get(&key).unwrap_or(0);
-    id += 1;
-    env.storage().instance().set(&key, &id);
-    id
+/// Drop a request from the orphaned assignment index
+pub fn remove_orphaned_assignment(env: &Env, request_id: u64) {
+    let mut ids = get_orphaned_assignments(env);
+    if let Some(i) = ids.first_index_of(request_id) {
+        ids.remove(i);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OrphanedAssignments, &ids);
+    }
+}
+
+/// Get the audit trail of a forced completion
+pub fn get_completion_override(env: &Env, request_id: u64) -> Option<CompletionOverride> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CompletionOverride(request_id))
+}
+
+/// Get the number of unfulfilled expiries for a blood type in an epoch
+pub fn get_unfulfilled_stats(env: &Env, blood_type: BloodType, epoch: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnfulfilledStats(blood_type, epoch))
+        .unwrap_or(0)
+}
+
+/// Count an unfulfilled expiry for a blood type in an epoch
+pub fn increment_unfulfilled_stats(env: &Env, blood_type: BloodType, epoch: u64) {
+    let count = get_unfulfilled_stats(env, blood_type, epoch);
+    env.storage()
+        .persistent()
+        .set(&DataKey::UnfulfilledStats(blood_type, epoch), &(count + 1));
+}
+
+/// Get the number of SLA breaches of a hospital in an epoch
+pub fn get_sla_breaches(env: &Env, hospital: &Address, epoch: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SlaBreaches(hospital.clone(), epoch))
+        .unwrap_or(0)
+}
+
+/// Get the number of SLA breaches across all hospitals in an epoch
+pub fn get_total_sla_breaches(env: &Env, epoch: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TotalSlaBreaches(epoch))
+        .unwrap_or(0)
+}
+
+/// Count an SLA breach against a hospital and the global total
+pub fn record_sla_breach(env: &Env, hospital: &Address, epoch: u64) {
+    let count = get_sla_breaches(env, hospital, epoch);
+    env.storage()
+        .persistent()
+        .set(&DataKey::SlaBreaches(hospital.clone(), epoch), &(count + 1));
+
+    let total = get_total_sla_breaches(env, epoch);
+    env.storage()
+        .persistent()
+        .set(&DataKey::TotalSlaBreaches(epoch), &(total + 1));
+}
+
+/// Get the demand recorded for a blood type in an epoch
+pub fn get_demand(env: &Env, blood_type: BloodType, epoch: u64) -> DemandStats {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DemandStats(blood_type, epoch))
+        .unwrap_or_default()
+}
+
+/// Count a new request towards the demand of its blood type and epoch
+pub fn record_demand(env: &Env, blood_type: BloodType, epoch: u64, quantity_ml: u32) {
+    let mut stats = get_demand(env, blood_type, epoch);
+    stats.requests += 1;
+    stats.total_ml += quantity_ml as u64;
+    env.storage()
+        .persistent()
+        .set(&DataKey::DemandStats(blood_type, epoch), &stats);
+}
+
+/// Take a withdrawn request back out of the demand of its blood type and epoch
+pub fn retract_demand(env: &Env, blood_type: BloodType, epoch: u64, quantity_ml: u32) {
+    let mut stats = get_demand(env, blood_type, epoch);
+    stats.requests = stats.requests.saturating_sub(1);
+    stats.total_ml = stats.total_ml.saturating_sub(quantity_ml as u64);
+    env.storage()
+        .persistent()
+        .set(&DataKey::DemandStats(blood_type, epoch), &stats);
+}
+
+/// Get a hospital's activity in an epoch
+pub fn get_hospital_report(env: &Env, hospital: &Address, epoch: u64) -> HospitalReport {
+    env.storage()
+        .persistent()
+        .get(&DataKey::HospitalReport(hospital.clone(), epoch))
+        .unwrap_or_default()
+}
+
+/// Count a created request in its hospital's report for an epoch
+pub fn record_report_created(env: &Env, hospital: &Address, epoch: u64, quantity_ml: u32) {
+    let mut report = get_hospital_report(env, hospital, epoch);
+    report.created += 1;
+    report.requested_ml += quantity_ml as u64;
+    set_hospital_report(env, hospital, epoch, &report);
+}
+
+/// Count a completed request and the volume it received in its hospital's
+/// report for an epoch
+pub fn record_report_completed(env: &Env, hospital: &Address, epoch: u64, received_ml: u64) {
+    let mut report = get_hospital_report(env, hospital, epoch);
+    report.completed += 1;
+    report.received_ml += received_ml;
+    set_hospital_report(env, hospital, epoch, &report);
+}
+
+/// Count a cancelled request in its hospital's report for an epoch
+pub fn record_report_cancelled(env: &Env, hospital: &Address, epoch: u64) {
+    let mut report = get_hospital_report(env, hospital, epoch);
+    report.cancelled += 1;
+    set_hospital_report(env, hospital, epoch, &report);
+}
+
+fn set_hospital_report(env: &Env, hospital: &Address, epoch: u64, report: &HospitalReport) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::HospitalReport(hospital.clone(), epoch), report);
+}
+
+/// Get a hospital's all-time track record
+pub fn get_hospital_score(env: &Env, hospital: &Address) -> HospitalScore {
+    env.storage()
+        .persistent()
+        .get(&DataKey::HospitalScore(hospital.clone()))
+        .unwrap_or_default()
+}
+
+/// Count a completed request in its hospital's score
+pub fn record_score_completed(env: &Env, hospital: &Address) {
+    let mut score = get_hospital_score(env, hospital);
+    score.completed += 1;
+    set_hospital_score(env, hospital, &mut score);
 }
 
-// --- PERSISTENT STORAGE (Unit Data & Large Indexes) ---
-// Used for data that grows over time
+/// Count a request cancelled once approved in its hospital's score
+pub fn record_score_late_cancellation(env: &Env, hospital: &Address) {
+    let mut score = get_hospital_score(env, hospital);
+    score.cancelled_after_approval += 1;
+    set_hospital_score(env, hospital, &mut score);
+}
+
+/// Count an SLA breach in its hospital's score
+pub fn record_score_sla_breach(env: &Env, hospital: &Address) {
+    let mut score = get_hospital_score(env, hospital);
+    score.sla_breaches += 1;
+    set_hospital_score(env, hospital, &mut score);
+}
+
+/// Store a hospital's score, deriving `reliability_bps` from its counters
+fn set_hospital_score(env: &Env, hospital: &Address, score: &mut HospitalScore) {
+    let outcomes = score.completed as u64
+        + score.cancelled_after_approval as u64
+        + score.disputes_opened as u64
+        + score.sla_breaches as u64;
+    score.reliability_bps = (outcomes > 0)
+        .then(|| (score.completed as u64 * RELIABILITY_SCALE_BPS as u64 / outcomes) as u32);
+    env.storage()
+        .persistent()
+        .set(&DataKey::HospitalScore(hospital.clone()), score);
+}
+
+/// Get the rejection counts per reason for an epoch
+pub fn get_rejection_stats(env: &Env, epoch: u64) -> Map<RejectionReason, u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RejectionStats(epoch))
+        .unwrap_or(Map::new(env))
+}
+
+/// Count a rejection under its reason in the current epoch
+pub fn record_rejection(env: &Env, reason: RejectionReason) {
+    let epoch = current_epoch(env);
+    let mut stats = get_rejection_stats(env, epoch);
+    let count = stats.get(reason).unwrap_or(0);
+    stats.set(reason, count.saturating_add(1));
+    env.storage()
+        .persistent()
+        .set(&DataKey::RejectionStats(epoch), &stats);
+}
 
-pub fn set_blood_unit(env: Env, unit: &BloodUnit) {
-    // Standardize all blood unit data to Persistent storage
-    env.storage().persistent().set(&DataKey::BloodUnit(unit.id), unit);
+/// Get the last epoch seen by the expiry sweep
+pub fn get_stats_epoch(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::StatsEpoch)
 }
 
-pub fn get_blood_unit(env: Env, id: u64) -> Option<BloodUnit> {
-    env.storage().persistent().get(&DataKey::BloodUnit(id))
+/// Set the last epoch seen by the expiry sweep
+pub fn set_stats_epoch(env: &Env, epoch: u64) {
+    env.storage().instance().set(&DataKey::StatsEpoch, &epoch);
 }
 
-pub fn add_to_bank_index(env: Env, unit: &BloodUnit) {
-    let key = DataKey::BankUnits(unit.bank_id.clone());
-    let mut units: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
-    units.push_back(unit.id);
-    env.storage().persistent().set(&key, &units);
+/// Get the co-signers registered by a hospital
+pub fn get_request_signers(env: &Env, hospital: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RequestSigners(hospital.clone()))
+        .unwrap_or(Vec::new(env))
 }
 
-pub fn add_to_donor_index(env: Env, unit: &BloodUnit) {
-    if let Some(donor_id) = &unit.donor_id {
-        let key = DataKey::DonorUnits(donor_id.clone());
-        let mut units: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
-        units.push_back(unit.id);
-        env.storage().persistent().set(&key, &units);
+/// Replace the co-signers registered by a hospital
+pub fn set_request_signers(env: &Env, hospital: &Address, signers: &Vec<Address>) {
+    let key = DataKey::RequestSigners(hospital.clone());
+    if signers.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, signers);
     }
-}
\ No newline at end of file
+}
+
+/// Get whether state may still be imported, None once importing is closed
+pub fn get_import_state(env: &Env) -> Option<ImportState> {
+    env.storage().instance().get(&DataKey::ImportState)
+}
+
+/// Set whether state may still be imported
+pub fn set_import_state(env: &Env, state: ImportState) {
+    env.storage().instance().set(&DataKey::ImportState, &state);
+}
+
+/// Close importing for good
+pub fn close_import(env: &Env) {
+    env.storage().instance().remove(&DataKey::ImportState);
+}
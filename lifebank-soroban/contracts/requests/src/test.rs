@@ -1,68 +1,159 @@
-use crate::types::{BloodRequest, BloodType, RequestMetadata, RequestStatus, UrgencyLevel};
-use crate::{RequestContract, RequestContractClient};
-use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    vec, Address, Env, String,
-};
-
+use crate::error::ContractError;
 use crate::storage;
-use crate::types::{BloodType, RequestStatus, UrgencyLevel};
-use crate::{RequestContract, RequestContractClient};
+use crate::storage::{IDEMPOTENCY_WINDOW_SECONDS, SECONDS_PER_EPOCH};
+use crate::testutils::{authorized_hospital, sample_metadata, setup, RequestFixture};
+use crate::types::{
+    AdminTransferCancelledEvent, AdminTransferProposedEvent, AdminTransferredEvent,
+    ApprovalAuthority, ApprovalRecordedEvent, AssignedUnit, AssignedUnitsReleasedEvent,
+    AssignmentTolerance, BloodRequest, BloodType, CandidateUnit, CourierAssignedEvent, DataKey,
+    DeliveryStartedEvent, DemandStats, EtaUpdatedEvent, ExpiryReason, ExpirySweepCompletedEvent,
+    HospitalAuthorizationChangedEvent, HospitalEntry, HospitalRenewedEvent, HospitalReport,
+    HospitalScore, HospitalSuspensionChangedEvent, ImplicitAuthorizationChangedEvent,
+    OrphanedAssignmentsReleasedEvent, PauseFlags, RejectionReason, RequestApprovedEvent,
+    RequestCreatedEvent, RequestMetadata, RequestOptions, RequestRejectedEvent, RequestStatus,
+    RequestStatusChangedEvent, RequestSummary, RequiredPhenotypeSetEvent,
+    ReservationPreemptedEvent, StateExportPage, UnitAssignment, UnitsAssignedEvent, UrgencyLevel,
+    BLOOD_REQUEST_SCHEMA,
+};
+use crate::validation::{
+    MAX_CONTACT_LEN, MAX_DELIVERY_ADDRESS_LEN, MAX_DELIVERY_ETA_SECONDS, MAX_DEPARTMENT_LEN,
+    MAX_PHYSICIAN_ID_LEN,
+};
+use crate::{RequestContract, RequestContractClient, MAX_INITIAL_HOSPITALS};
+use inventory_contract::testutils::UnitFixture;
+use inventory_contract::{
+    BloodStatus, InventoryContract, InventoryContractClient, MatchAvailableEvent,
+};
+use lifebank_common::testutils::{advance_days, count_events, latest_event};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, Env, String,
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec,
 };
 
 // ========== Test Helpers ==========
 
-fn create_test_contract<'a>() -> (Env, Address, RequestContractClient<'a>, Address) {
-    let env = Env::default();
-    env.mock_all_auths();
-
-    let contract_id = env.register(RequestContract, ());
-    let client = RequestContractClient::new(&env, &contract_id);
-
-    let admin = Address::generate(&env);
-
-    client.initialize(&admin);
-
-    (env, admin, client, contract_id)
-}
-
-fn setup_authorized_hospital<'a>(
-    env: &Env,
-    client: &RequestContractClient<'a>,
-) -> Address {
-    let hospital = Address::generate(env);
-    client.authorize_hospital(&hospital);
-    hospital
+fn sample_request(env: &Env, status: RequestStatus) -> BloodRequest {
+    BloodRequest {
+        schema: BLOOD_REQUEST_SCHEMA,
+        id: 1,
+        hospital_id: Address::generate(env),
+        blood_type: BloodType::OPositive,
+        quantity_ml: 450,
+        urgency: UrgencyLevel::Urgent,
+        status,
+        created_at: 1000u64,
+        required_by: 2000u64,
+        fulfilled_at: None,
+        assigned_units: vec![env],
+        assignments: vec![env],
+        delivery_address: String::from_str(env, "Hospital"),
+        metadata: RequestMetadata {
+            patient_id: Address::generate(env),
+            procedure: String::from_str(env, "Surgery"),
+            notes: String::from_str(env, "Notes"),
+        },
+        priority_score: BloodRequest::compute_priority_score(UrgencyLevel::Urgent, 2000u64),
+        accept_compatible: false,
+        completion_override: false,
+        co_signer: None,
+        department: String::from_str(env, ""),
+        physician_id: String::from_str(env, ""),
+        pediatric: false,
+        required_phenotype: Vec::new(env),
+        cross_region: false,
+        approvals: 0,
+        approved_at: None,
+        approved_by: None,
+        courier: None,
+        delivery_eta: None,
+        sla_breached: false,
+    }
 }
 
 // ========== Initialization Tests ==========
 
 #[test]
 fn test_initialize_success() {
-    let (env, admin, _client, contract_id) = create_test_contract();
+    let (env, admin, client, contract_id) = setup();
 
     // Verify admin is set
     let stored_admin = env.as_contract(&contract_id, || storage::get_admin(&env));
     assert_eq!(stored_admin, admin);
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_version(), Some(storage::CONTRACT_VERSION));
+}
+
+#[test]
+fn test_new_requests_carry_current_schema() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .required_by(1000 + 86400)
+        .create(&client);
+    assert_eq!(client.get_request(&request_id).schema, BLOOD_REQUEST_SCHEMA);
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #0)")]
 fn test_initialize_already_initialized() {
-    let (_env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
     // Try to initialize again - should fail
-    client.initialize(&admin);
+    client.initialize(&admin, &vec![&env], &0);
+}
+
+#[test]
+fn test_initialize_seeds_hospitals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(RequestContract, ());
+    let client = RequestContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let hospital1 = Address::generate(&env);
+    let hospital2 = Address::generate(&env);
+
+    assert_eq!(client.get_version(), None);
+    client.initialize(
+        &admin,
+        &vec![&env, hospital1.clone(), hospital2.clone()],
+        &0,
+    );
+
+    assert!(client.is_hospital_authorized(&hospital1));
+    assert!(client.is_hospital_authorized(&hospital2));
+    assert!(!client.is_hospital_authorized(&Address::generate(&env)));
+}
+
+#[test]
+fn test_initialize_too_many_hospitals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(RequestContract, ());
+    let client = RequestContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let mut hospitals = vec![&env];
+    for _ in 0..=MAX_INITIAL_HOSPITALS {
+        hospitals.push_back(Address::generate(&env));
+    }
+
+    let result = client.try_initialize(&admin, &hospitals, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    // Nothing was stored, so a valid initialization still succeeds
+    client.initialize(&admin, &vec![&env], &0);
 }
 
 // ========== Hospital Authorization Tests ==========
 
 #[test]
 fn test_authorize_hospital_success() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, contract_id) = setup();
 
     let hospital = Address::generate(&env);
 
@@ -70,7 +161,24 @@ fn test_authorize_hospital_success() {
     assert!(!client.is_hospital_authorized(&hospital));
 
     // Authorize
-    client.authorize_hospital(&hospital);
+    env.ledger().set_timestamp(1000);
+    client.authorize_hospital(&hospital, &None);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "hospital_authorized"), hospital.clone()).into_val(&env),
+                HospitalAuthorizationChangedEvent {
+                    hospital: hospital.clone(),
+                    admin,
+                    changed_at: 1000,
+                }
+                .into_val(&env),
+            ),
+        ]
+    );
 
     // Now authorized
     assert!(client.is_hospital_authorized(&hospital));
@@ -78,14 +186,31 @@ fn test_authorize_hospital_success() {
 
 #[test]
 fn test_revoke_hospital_success() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, contract_id) = setup();
 
     let hospital = Address::generate(&env);
-    client.authorize_hospital(&hospital);
+    client.authorize_hospital(&hospital, &None);
     assert!(client.is_hospital_authorized(&hospital));
 
     // Revoke
+    env.ledger().set_timestamp(2000);
     client.revoke_hospital(&hospital);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "hospital_revoked"), hospital.clone()).into_val(&env),
+                HospitalAuthorizationChangedEvent {
+                    hospital: hospital.clone(),
+                    admin,
+                    changed_at: 2000,
+                }
+                .into_val(&env),
+            ),
+        ]
+    );
 
     // No longer authorized
     assert!(!client.is_hospital_authorized(&hospital));
@@ -93,551 +218,715 @@ fn test_revoke_hospital_success() {
 
 #[test]
 fn test_admin_is_always_authorized() {
-    let (_env, admin, client, _contract_id) = create_test_contract();
+    let (_env, admin, client, _contract_id) = setup();
 
     // Admin should be authorized automatically
     assert!(client.is_hospital_authorized(&admin));
 }
 
-// ========== Create Request Tests ==========
-
 #[test]
-fn test_create_request_success() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_suspended_hospital_cannot_create_requests() {
+    let (env, admin, client, contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let until = 1000 + 7 * 86400;
+    client.suspend_hospital(&hospital, &until);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "hospital_suspended"), hospital.clone()).into_val(&env),
+                HospitalSuspensionChangedEvent {
+                    hospital: hospital.clone(),
+                    admin,
+                    suspended_until: Some(until),
+                    changed_at: 1000,
+                }
+                .into_val(&env),
+            ),
+        ]
+    );
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    assert!(!client.is_hospital_authorized(&hospital));
+    assert_eq!(client.get_hospital_suspension(&hospital), Some(until));
 
-    let current_time = 1000000u64;
-    env.ledger().set_timestamp(current_time);
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::HospitalSuspended)));
+}
 
-    let blood_type = BloodType::APositive;
-    let quantity_ml = 900u32;
-    let urgency = UrgencyLevel::Normal;
-    let required_by = current_time + (7 * 86400); // 7 days
-    let delivery_address = String::from_str(&env, "123 Hospital Street, City");
+#[test]
+fn test_suspension_lifts_after_until() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let request_id = client.create_request(
-        &hospital,
-fn create_test_contract<'a>() -> (Env, Address, RequestContractClient<'a>, Address) {
-    let env = Env::default();
-    env.mock_all_auths();
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
 
-    let contract_id = env.register(RequestContract, ());
-    let client = RequestContractClient::new(&env, &contract_id);
+    let until = 1000 + 7 * 86400;
+    client.suspend_hospital(&hospital, &until);
 
-    let admin = Address::generate(&env);
-    client.initialize(&admin);
+    env.ledger().set_timestamp(until);
+    assert!(client.is_hospital_authorized(&hospital));
+    assert_eq!(client.get_hospital_suspension(&hospital), None);
 
-    (env, admin, client, contract_id)
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(until + 86400)
+        .create(&client);
 }
 
 #[test]
-fn test_initialize_success() {
-    let (env, admin, _client, contract_id) = create_test_contract();
+fn test_list_authorized_hospitals_detail() {
+    let (env, _admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let suspended = authorized_hospital(&env, &client);
+    let cosigned = authorized_hospital(&env, &client);
+    let revoked = authorized_hospital(&env, &client);
+    client.suspend_hospital(&suspended, &(1000 + 86400));
+    client.add_request_signer(&cosigned, &Address::generate(&env));
+    client.revoke_hospital(&revoked);
+
+    let page = client.list_authorized_hospitals_detail(&0, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(
+        page.get(0).unwrap(),
+        HospitalEntry {
+            hospital: suspended.clone(),
+            suspended_until: Some(1000 + 86400),
+            requires_co_signer: false,
+            valid_until: None,
+            remaining_validity: None,
+        }
+    );
+    assert_eq!(
+        page.get(1).unwrap(),
+        HospitalEntry {
+            hospital: cosigned.clone(),
+            suspended_until: None,
+            requires_co_signer: true,
+            valid_until: None,
+            remaining_validity: None,
+        }
+    );
 
-    // Verify admin is set
-    let stored_admin = env.as_contract(&contract_id, || {
-        crate::storage::get_admin(&env)
-    });
+    // Re-authorizing lists the hospital once
+    client.authorize_hospital(&revoked, &None);
+    client.authorize_hospital(&revoked, &None);
+    assert_eq!(client.count_authorized_hospitals(), 3);
 
-    assert_eq!(stored_admin, admin);
+    // Pages hold at most 50 entries
+    for _ in 0..50 {
+        authorized_hospital(&env, &client);
+    }
+    assert_eq!(client.count_authorized_hospitals(), 53);
+    let first_page = client.list_authorized_hospitals_detail(&0, &100);
+    assert_eq!(first_page.len(), 50);
+    assert_eq!(first_page.get(2).unwrap().hospital, revoked);
+    let last_page = client.list_authorized_hospitals_detail(&50, &50);
+    assert_eq!(last_page.len(), 3);
+    assert_eq!(client.list_authorized_hospitals_detail(&53, &50).len(), 0);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #0)")]
-fn test_initialize_already_initialized() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_lift_suspension_early() {
+    let (env, admin, client, contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    client.suspend_hospital(&hospital, &(1000 + 7 * 86400));
+    client.lift_suspension(&hospital);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (
+                    Symbol::new(&env, "hospital_suspension_lifted"),
+                    hospital.clone(),
+                )
+                    .into_val(&env),
+                HospitalSuspensionChangedEvent {
+                    hospital: hospital.clone(),
+                    admin,
+                    suspended_until: None,
+                    changed_at: 1000,
+                }
+                .into_val(&env),
+            ),
+        ]
+    );
 
-    // Try to initialize again
-    client.initialize(&admin);
+    assert!(client.is_hospital_authorized(&hospital));
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #32)")]
-fn test_create_request_success() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_lapsed_hospital_cannot_create_until_renewed() {
+    let (env, admin, client, contract_id) = setup();
 
+    env.ledger().set_timestamp(1000);
     let hospital = Address::generate(&env);
-    let patient = Address::generate(&env);
-    let blood_type = BloodType::OPositive;
-    let quantity_ml = 450u32;
-    let urgency = UrgencyLevel::Urgent;
-
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-    let required_by = current_time + (2 * 86400); // 2 days from now
+    let valid_until = 1000 + 365 * 86400;
+    client.authorize_hospital(&hospital, &Some(valid_until));
+    assert!(client.is_hospital_authorized(&hospital));
 
-    let delivery_address = String::from_str(&env, "Hospital Main Building");
-    let procedure = String::from_str(&env, "Emergency Surgery");
-    let notes = String::from_str(&env, "Type O+ preferred");
+    let entry = client
+        .list_authorized_hospitals_detail(&0, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(entry.valid_until, Some(valid_until));
+    assert_eq!(entry.remaining_validity, Some(365 * 86400));
 
-    // This should fail because hospital is not authorized (not the admin)
-    client.create_request(
-        &hospital,
-        &blood_type,
-        &quantity_ml,
-        &urgency,
-        &required_by,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+    // Authorization lapses on its own once valid_until is reached
+    env.ledger().set_timestamp(valid_until);
+    assert!(!client.is_hospital_authorized(&hospital));
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(valid_until + 86400)
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedHospital)));
+
+    // Lapsed hospitals stay listed with no validity left
+    let entry = client
+        .list_authorized_hospitals_detail(&0, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(entry.hospital, hospital);
+    assert_eq!(entry.remaining_validity, Some(0));
+
+    let renewed_until = valid_until + 365 * 86400;
+    client.renew_hospital(&hospital, &renewed_until);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "hospital_renewed"), hospital.clone()).into_val(&env),
+                HospitalRenewedEvent {
+                    hospital: hospital.clone(),
+                    admin,
+                    valid_until: renewed_until,
+                    renewed_at: valid_until,
+                }
+                .into_val(&env),
+            ),
+        ]
     );
+
+    assert!(client.is_hospital_authorized(&hospital));
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(valid_until + 86400)
+        .create(&client);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #32)")]
-fn test_create_request_unauthorized_hospital() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_authorize_hospital_validity_checks() {
+    let (env, _admin, client, _contract_id) = setup();
 
+    env.ledger().set_timestamp(1000);
     let hospital = Address::generate(&env);
-    let patient = Address::generate(&env);
-    let blood_type = BloodType::APositive;
-    let quantity_ml = 450u32;
-    let urgency = UrgencyLevel::Normal;
 
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-    let required_by = current_time + (2 * 86400);
+    let result = client.try_authorize_hospital(&hospital, &Some(1000));
+    assert_eq!(result, Err(Ok(ContractError::InvalidTimestamp)));
 
-    let delivery_address = String::from_str(&env, "Hospital Main");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    // Only authorized hospitals can be renewed
+    let result = client.try_renew_hospital(&hospital, &(1000 + 86400));
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedHospital)));
 
-    // This should fail because hospital is not authorized
-    client.create_request(
-        &hospital,
-        &blood_type,
-        &quantity_ml,
-        &urgency,
-        &required_by,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
-}
+    client.authorize_hospital(&hospital, &Some(1000 + 86400));
+    let result = client.try_renew_hospital(&hospital, &1000);
+    assert_eq!(result, Err(Ok(ContractError::InvalidTimestamp)));
 
-#[test]
-#[should_panic(expected = "Error(Contract, #16)")]
-fn test_create_request_invalid_quantity_too_low() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    // Re-authorizing without a limit removes it
+    client.authorize_hospital(&hospital, &None);
+    env.ledger().set_timestamp(1000 + 2 * 86400);
+    assert!(client.is_hospital_authorized(&hospital));
 
-    let patient = Address::generate(&env);
-    let blood_type = BloodType::BPositive;
-    let quantity_ml = 25u32; // Below minimum of 50ml
-    let urgency = UrgencyLevel::Critical;
+    // Revoking clears the limit along with the authorization
+    client.authorize_hospital(&hospital, &Some(1000 + 3 * 86400));
+    client.revoke_hospital(&hospital);
+    let result = client.try_renew_hospital(&hospital, &(1000 + 4 * 86400));
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedHospital)));
+    client.authorize_hospital(&hospital, &None);
+    env.ledger().set_timestamp(1000 + 5 * 86400);
+    assert!(client.is_hospital_authorized(&hospital));
+}
 
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-    let required_by = current_time + (1 * 86400);
+#[test]
+fn test_suspend_hospital_until_in_past() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
 
-    client.create_request(
-        &admin,
-        &blood_type,
-        &quantity_ml,
-        &urgency,
-        &required_by,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
+    let result = client.try_suspend_hospital(&hospital, &1000);
+    assert_eq!(result, Err(Ok(ContractError::InvalidTimestamp)));
 }
 
+// ========== Admin Transfer Tests ==========
+
 #[test]
-#[should_panic(expected = "Error(Contract, #16)")]
-fn test_create_request_invalid_quantity_too_high() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_admin_transfer_two_step() {
+    let (env, admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
-    let blood_type = BloodType::BNegative;
-    let quantity_ml = 6000u32; // Above maximum of 5000ml
-    let urgency = UrgencyLevel::Normal;
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
 
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-    let required_by = current_time + (5 * 86400);
+    // Nothing changes until the proposal is accepted
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert!(client.is_hospital_authorized(&admin));
+    assert!(!client.is_hospital_authorized(&new_admin));
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    client.accept_admin();
 
-    client.create_request(
-        &admin,
-        &blood_type,
-        &quantity_ml,
-        &urgency,
-        &required_by,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
+    assert_eq!(client.get_pending_admin(), None);
+    assert!(client.is_hospital_authorized(&new_admin));
+    assert!(!client.is_hospital_authorized(&admin));
+
+    // The new admin holds the admin powers
+    let hospital = Address::generate(&env);
+    client.authorize_hospital(&hospital, &None);
+    assert!(client.is_hospital_authorized(&hospital));
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #15)")]
-fn test_create_request_invalid_timestamp_in_past() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_get_approvers_follows_admin_and_regional_authority() {
+    let (env, admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
-    let blood_type = BloodType::ABPositive;
-    let quantity_ml = 450u32;
-    let urgency = UrgencyLevel::Urgent;
+    assert_eq!(client.get_approvers(), vec![&env, admin.clone()]);
 
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-    let required_by = current_time - 100u64; // In the past
+    let authority = Address::generate(&env);
+    client.set_regional_authority(&authority);
+    assert_eq!(
+        client.get_approvers(),
+        vec![&env, admin.clone(), authority.clone()]
+    );
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    // Replacing the authority swaps it out rather than growing the list
+    let replacement = Address::generate(&env);
+    client.set_regional_authority(&replacement);
+    assert_eq!(
+        client.get_approvers(),
+        vec![&env, admin.clone(), replacement.clone()]
+    );
 
-    client.create_request(
-        &admin,
-        &blood_type,
-        &quantity_ml,
-        &urgency,
-        &required_by,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+    // So does rotating the admin
+    let new_admin = Address::generate(&env);
+    client.propose_admin(&new_admin);
+    client.accept_admin();
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(
+        client.get_approvers(),
+        vec![&env, new_admin.clone(), replacement]
     );
+
+    // An admin doubling as the authority is listed once
+    client.set_regional_authority(&new_admin);
+    assert_eq!(client.get_approvers(), vec![&env, new_admin]);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #15)")]
-fn test_create_request_invalid_timestamp_too_far() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_accept_admin_without_proposal() {
+    let (_env, _admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
-    let blood_type = BloodType::ABNegative;
-    let quantity_ml = 450u32;
-    let urgency = UrgencyLevel::Normal;
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(ContractError::NoPendingAdmin)));
+}
 
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-    let required_by = current_time + (40 * 86400); // 40 days in future (max is 30)
+#[test]
+fn test_cancel_admin_transfer() {
+    let (env, admin, client, _contract_id) = setup();
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    let result = client.try_cancel_admin_transfer();
+    assert_eq!(result, Err(Ok(ContractError::NoPendingAdmin)));
 
-    client.create_request(
-        &admin,
-        &blood_type,
-        &quantity_ml,
-        &urgency,
-        &required_by,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
+    client.propose_admin(&Address::generate(&env));
+    client.cancel_admin_transfer();
+
+    assert_eq!(client.get_pending_admin(), None);
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(ContractError::NoPendingAdmin)));
+    assert!(client.is_hospital_authorized(&admin));
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #12)")]
-fn test_create_request_empty_delivery_address() {
-    let (env, admin, client, _contract_id) = create_test_contract();
-
-    let patient = Address::generate(&env);
-    let blood_type = BloodType::OPositive;
-    let quantity_ml = 450u32;
-    let urgency = UrgencyLevel::Critical;
+fn test_admin_rotation_events() {
+    let (env, admin, client, contract_id) = setup();
+    env.ledger().set_timestamp(100);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let successor = Address::generate(&env);
+
+    let proposed = |proposed_admin: &Address| -> (Address, Vec<Val>, Val) {
+        (
+            contract_id.clone(),
+            (Symbol::new(&env, "admin_proposed"),).into_val(&env),
+            AdminTransferProposedEvent {
+                current_admin: admin.clone(),
+                proposed_admin: proposed_admin.clone(),
+                proposed_at: 100,
+            }
+            .into_val(&env),
+        )
+    };
+    let cancelled = |cancelled_admin: &Address| -> (Address, Vec<Val>, Val) {
+        (
+            contract_id.clone(),
+            (Symbol::new(&env, "admin_transfer_cancelled"),).into_val(&env),
+            AdminTransferCancelledEvent {
+                admin: admin.clone(),
+                cancelled_admin: cancelled_admin.clone(),
+                cancelled_at: 100,
+            }
+            .into_val(&env),
+        )
+    };
+    let implicit = |account: &Address, authorized: bool| -> (Address, Vec<Val>, Val) {
+        (
+            contract_id.clone(),
+            (Symbol::new(&env, "implicit_hospital_auth_changed"),).into_val(&env),
+            ImplicitAuthorizationChangedEvent {
+                account: account.clone(),
+                authorized,
+                changed_at: 100,
+            }
+            .into_val(&env),
+        )
+    };
 
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-    let required_by = current_time + (1 * 86400);
+    client.propose_admin(&first);
+    assert_eq!(env.events().all(), vec![&env, proposed(&first)]);
 
-    let delivery_address = String::from_str(&env, ""); // Empty address
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    // A new proposal replaces the pending one
+    client.propose_admin(&second);
+    assert_eq!(
+        env.events().all(),
+        vec![&env, cancelled(&first), proposed(&second)]
+    );
 
-    client.create_request(
-        &admin,
-        &blood_type,
-        &quantity_ml,
-        &urgency,
-        &required_by,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+    client.cancel_admin_transfer();
+    assert_eq!(env.events().all(), vec![&env, cancelled(&second)]);
+
+    client.propose_admin(&successor);
+    assert_eq!(env.events().all(), vec![&env, proposed(&successor)]);
+
+    client.accept_admin();
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "admin_changed"),).into_val(&env),
+                AdminTransferredEvent {
+                    old_admin: admin.clone(),
+                    new_admin: successor.clone(),
+                    transferred_at: 100,
+                }
+                .into_val(&env),
+            ),
+            implicit(&admin, false),
+            implicit(&successor, true),
+        ]
     );
 }
 
 #[test]
-fn test_create_multiple_requests() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_old_admin_cannot_create_requests_after_rotation() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    // Implicitly authorized while admin
+    RequestFixture::new(&env)
+        .by(&admin)
+        .required_by(1000 + 86400)
+        .create(&client);
+
+    client.propose_admin(&Address::generate(&env));
+    client.accept_admin();
+
+    let result = RequestFixture::new(&env)
+        .by(&admin)
+        .required_by(1000 + 86400)
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedHospital)));
+}
 
-    let patient1 = Address::generate(&env);
-    let patient2 = Address::generate(&env);
+#[test]
+fn test_explicitly_authorized_old_admin_keeps_hospital_role() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
 
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
+    client.authorize_hospital(&admin, &None);
+    client.propose_admin(&Address::generate(&env));
+    client.accept_admin();
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    assert!(client.is_hospital_authorized(&admin));
+    RequestFixture::new(&env)
+        .by(&admin)
+        .required_by(1000 + 86400)
+        .create(&client);
+}
 
-    // Create first request
-    let request_id_1 = client.create_request(
-        &admin,
-        &BloodType::OPositive,
-        &450u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 86400),
-        &delivery_address,
-        &patient1,
-        &procedure,
-        &notes,
-    );
+// ========== Pause Tests ==========
 
-    // Create second request
-    let request_id_2 = client.create_request(
-        &admin,
-        &BloodType::ABNegative,
-        &500u32,
-        &UrgencyLevel::Critical,
-        &(current_time + 3600),
-        &delivery_address,
-        &patient2,
-        &procedure,
-        &notes,
-    );
+#[test]
+fn test_pause_flags_default_off() {
+    let (_env, _admin, client, _contract_id) = setup();
 
-    assert_eq!(request_id_1, 1);
-    assert_eq!(request_id_2, 2);
+    assert_eq!(client.get_pause_flags(), PauseFlags::default());
+}
 
-    let req1 = client.get_request(&request_id_1);
-    let req2 = client.get_request(&request_id_2);
+#[test]
+fn test_create_paused_only_blocks_creation() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let existing = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+
+    client.set_pause_flags(&true, &false, &false);
+    assert!(client.get_pause_flags().create_paused);
+
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::ContractPaused)));
+
+    // Other operations keep working
+    client.approve_request(&existing);
+    client.update_request_status(&existing, &RequestStatus::InDelivery);
+    client.complete_request(&hospital, &existing);
+}
 
-    assert_eq!(req1.blood_type, BloodType::OPositive);
-    assert_eq!(req2.blood_type, BloodType::ABNegative);
+#[test]
+fn test_approve_paused_only_blocks_approval() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let approved = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&approved);
+
+    client.set_pause_flags(&false, &true, &false);
+
+    // Hospitals keep filing requests
+    let pending = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+
+    let result = client.try_approve_request(&pending);
+    assert_eq!(result, Err(Ok(ContractError::ContractPaused)));
+    let result = client.try_update_request_status(&pending, &RequestStatus::Approved);
+    assert_eq!(result, Err(Ok(ContractError::ContractPaused)));
+
+    client.update_request_status(&approved, &RequestStatus::Fulfilled);
+    assert_eq!(
+        client.get_request(&approved).status,
+        RequestStatus::Fulfilled
+    );
 }
 
 #[test]
-fn test_update_request_status_pending_to_approved() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_fulfill_paused_only_blocks_fulfillment() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
 
-    let request_id = client.create_request(
-        &admin,
-        &BloodType::OPositive,
-        &450u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 86400),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
+    client.set_pause_flags(&false, &false, &true);
 
-    // Update status to Approved
-    client.update_request_status(&request_id, &RequestStatus::Approved);
+    client.approve_request(&request_id);
 
-    let request = client.get_request(&request_id);
-    assert_eq!(request.status, RequestStatus::Approved);
+    let units = vec![
+        &env,
+        UnitAssignment {
+            unit_id: 1,
+            blood_type: BloodType::APositive,
+            quantity_ml: 450,
+            bank_id: Address::generate(&env),
+        },
+    ];
+    let result = client.try_assign_blood_units(&request_id, &units);
+    assert_eq!(result, Err(Ok(ContractError::ContractPaused)));
+
+    let result = client.try_update_request_status(&request_id, &RequestStatus::InDelivery);
+    assert_eq!(result, Err(Ok(ContractError::ContractPaused)));
+
+    // Lifting the pause restores fulfillment
+    client.set_pause_flags(&false, &false, &false);
+    client.update_request_status(&request_id, &RequestStatus::InDelivery);
+
+    client.set_pause_flags(&false, &false, &true);
+    let result = client.try_complete_request(&hospital, &request_id);
+    assert_eq!(result, Err(Ok(ContractError::ContractPaused)));
 }
 
 #[test]
-fn test_update_request_status_approved_to_fulfilled() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_cancel_never_paused() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
 
-    let request_id = client.create_request(
-        &admin,
-        &BloodType::BPositive,
-        &500u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 86400),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
-
-    // Update to Approved
-    client.update_request_status(&request_id, &RequestStatus::Approved);
-
-    // Update to Fulfilled
-    client.update_request_status(&request_id, &RequestStatus::Fulfilled);
-
-    let request = client.get_request(&request_id);
-    assert_eq!(request.status, RequestStatus::Fulfilled);
-    assert_eq!(request.fulfilled_at, Some(current_time));
-}
-
-#[test]
-#[should_panic(expected = "Error(Contract, #41)")]
-fn test_update_request_status_invalid_transition() {
-    let (env, admin, client, _contract_id) = create_test_contract();
-
-    let patient = Address::generate(&env);
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    client.set_pause_flags(&true, &true, &true);
+    client.cancel_request(&request_id, &hospital);
 
-    let request_id = client.create_request(
-        &admin,
-        &BloodType::ABPositive,
-        &450u32,
-        &UrgencyLevel::Critical,
-        &(current_time + 3600),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Cancelled
     );
-
-    // Try invalid transition: Pending -> Fulfilled (should be Pending -> Approved -> Fulfilled)
-    client.update_request_status(&request_id, &RequestStatus::Fulfilled);
 }
 
+// ========== Create Request Tests ==========
+
 #[test]
-#[should_panic(expected = "Error(Contract, #41)")]
-fn test_update_request_status_from_terminal_state() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_create_request_success() {
+    let (env, _admin, client, _contract_id) = setup();
 
+    let hospital = authorized_hospital(&env, &client);
     let patient = Address::generate(&env);
-    let current_time = 1000u64;
+
+    let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    let blood_type = BloodType::APositive;
+    let quantity_ml = 900u32;
+    let urgency = UrgencyLevel::Normal;
+    let required_by = current_time + (7 * 86400); // 7 days
+    let delivery_address = String::from_str(&env, "123 Hospital Street, City");
+    let procedure = String::from_str(&env, "Hip replacement");
+    let notes = String::from_str(&env, "Irradiated units preferred");
 
     let request_id = client.create_request(
-        &admin,
-        &BloodType::ONegative,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 86400),
+        &hospital,
+        &blood_type,
+        &quantity_ml,
+        &urgency,
+        &required_by,
         &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+        &RequestMetadata {
+            patient_id: patient.clone(),
+            procedure: procedure.clone(),
+            notes: notes.clone(),
+        },
+        &RequestOptions::new(&env),
     );
 
-    // Transition to Rejected (terminal state)
-    client.update_request_status(&request_id, &RequestStatus::Rejected);
+    assert_eq!(request_id, 1);
 
-    // Try to transition from Rejected (should fail)
-    client.update_request_status(&request_id, &RequestStatus::Approved);
+    // Verify stored request
+    let request = client.get_request(&request_id);
+    assert_eq!(request.id, request_id);
+    assert_eq!(request.hospital_id, hospital);
+    assert_eq!(request.blood_type, blood_type);
+    assert_eq!(request.quantity_ml, quantity_ml);
+    assert_eq!(request.urgency, urgency);
+    assert_eq!(request.status, RequestStatus::Pending);
+    assert_eq!(request.created_at, current_time);
+    assert_eq!(request.required_by, required_by);
+    assert_eq!(request.fulfilled_at, None);
+    assert_eq!(request.assigned_units.len(), 0);
+    assert_eq!(request.delivery_address, delivery_address);
+    assert_eq!(request.metadata.patient_id, patient);
+    assert_eq!(request.metadata.procedure, procedure);
+    assert_eq!(request.metadata.notes, notes);
 }
 
 #[test]
-fn test_assign_blood_units() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_create_request_as_admin_success() {
+    let (env, admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
-
-    let request_id = client.create_request(
-        &admin,
-        &BloodType::OPositive,
-        &900u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 86400),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
-
-    // Assign blood units
-    let unit_ids = vec![&env, 1u64, 2u64];
-    client.assign_blood_units(&request_id, &unit_ids);
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::OPositive)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
 
     let request = client.get_request(&request_id);
-    assert_eq!(request.assigned_units.len(), 2);
-    assert_eq!(request.assigned_units.get(0).unwrap(), 1u64);
-    assert_eq!(request.assigned_units.get(1).unwrap(), 2u64);
+    assert_eq!(request.hospital_id, admin);
+    assert_eq!(request.status, RequestStatus::Pending);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #40)")]
-fn test_request_not_found() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
-
-    // Try to get a request that doesn't exist
-    client.get_request(&999u64);
-}
+fn test_create_request_increments_id() {
+    let (env, _admin, client, _contract_id) = setup();
 
-#[test]
-fn test_urgency_level_max_fulfillment_time() {
-    assert_eq!(UrgencyLevel::Critical.max_fulfillment_time(), 3600); // 1 hour
-    assert_eq!(UrgencyLevel::Urgent.max_fulfillment_time(), 21600); // 6 hours
-    assert_eq!(UrgencyLevel::Normal.max_fulfillment_time(), 86400); // 24 hours
-}
+    let hospital = authorized_hospital(&env, &client);
 
-#[test]
-fn test_request_status_transitions() {
-    // Test valid transitions
-    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Approved));
-    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Rejected));
-    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Cancelled));
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
 
-    assert!(RequestStatus::Approved.can_transition_to(&RequestStatus::Fulfilled));
-    assert!(RequestStatus::Approved.can_transition_to(&RequestStatus::Cancelled));
+    let required_by = current_time + (7 * 86400);
 
-    assert!(RequestStatus::Fulfilled.can_transition_to(&RequestStatus::Completed));
+    let id1 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+    let id2 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .required_by(required_by)
+        .create(&client);
+    let id3 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 2 * 3600) // Critical needs less time
+        .create(&client);
 
-    // Test invalid transitions
-    assert!(!RequestStatus::Pending.can_transition_to(&RequestStatus::Fulfilled));
-    assert!(!RequestStatus::Rejected.can_transition_to(&RequestStatus::Approved));
-    assert!(!RequestStatus::Completed.can_transition_to(&RequestStatus::Approved));
-    assert!(!RequestStatus::Cancelled.can_transition_to(&RequestStatus::Fulfilled));
+    assert_eq!(id1, 1);
+    assert_eq!(id2, 2);
+    assert_eq!(id3, 3);
 }
 
 #[test]
-fn test_request_status_is_terminal() {
-    assert!(!RequestStatus::Pending.is_terminal());
-    assert!(!RequestStatus::Approved.is_terminal());
-    assert!(!RequestStatus::Fulfilled.is_terminal());
-
-    assert!(RequestStatus::Completed.is_terminal());
-    assert!(RequestStatus::Rejected.is_terminal());
-    assert!(RequestStatus::Cancelled.is_terminal());
-}
+fn test_create_request_all_blood_types() {
+    let (env, _admin, client, _contract_id) = setup();
 
-#[test]
-fn test_blood_request_validate_all_blood_types() {
-    let env = Env::default();
-    env.mock_all_auths();
-    env.ledger().set_timestamp(1000u64);
+    let hospital = authorized_hospital(&env, &client);
 
-    let hospital = Address::generate(&env);
-    let patient = Address::generate(&env);
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
 
     let blood_types = [
         BloodType::APositive,
@@ -650,513 +939,907 @@ fn test_blood_request_validate_all_blood_types() {
         BloodType::ONegative,
     ];
 
-    for blood_type in blood_types.iter() {
-        let metadata = RequestMetadata {
-            patient_id: patient.clone(),
-            procedure: String::from_str(&env, "Surgery"),
-            notes: String::from_str(&env, "Notes"),
-        };
+    for (i, blood_type) in blood_types.iter().enumerate() {
+        let id = RequestFixture::new(&env)
+            .by(&hospital)
+            .with_blood_type(*blood_type)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(current_time + 7 * 86400)
+            .create(&client);
 
-        let request = BloodRequest {
-            id: 1,
-            hospital_id: hospital.clone(),
-            blood_type: *blood_type,
-            quantity_ml: 450,
-            urgency: UrgencyLevel::Normal,
-            status: RequestStatus::Pending,
-            created_at: 1000u64,
-            required_by: 2000u64,
-            fulfilled_at: None,
-            assigned_units: soroban_sdk::vec![&env],
-            delivery_address: String::from_str(&env, "Hospital"),
-            metadata,
-        };
+        assert_eq!(id, (i + 1) as u64);
 
-        assert!(request.validate(1000u64).is_ok());
+        let request = client.get_request(&id);
+        assert_eq!(request.blood_type, *blood_type);
     }
 }
 
 #[test]
-fn test_blood_request_is_overdue() {
-    let env = Env::default();
-    env.mock_all_auths();
+fn test_create_request_all_urgency_levels() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = Address::generate(&env);
-    let patient = Address::generate(&env);
+    let hospital = authorized_hospital(&env, &client);
 
-    let metadata = RequestMetadata {
-        patient_id: patient,
-        procedure: String::from_str(&env, "Surgery"),
-        notes: String::from_str(&env, "Notes"),
-    };
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
 
-    let request = BloodRequest {
-        id: 1,
-        hospital_id: hospital,
-        blood_type: BloodType::OPositive,
-        quantity_ml: 450,
-        urgency: UrgencyLevel::Urgent,
-        status: RequestStatus::Pending,
-        created_at: 1000u64,
-        required_by: 2000u64,
-        fulfilled_at: None,
-        assigned_units: soroban_sdk::vec![&env],
-        delivery_address: String::from_str(&env, "Hospital"),
-        metadata,
-    };
+    // Critical - needs at least 1 hour
+    let id1 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 2 * 3600)
+        .create(&client);
+    assert_eq!(client.get_request(&id1).urgency, UrgencyLevel::Critical);
 
-    assert!(!request.is_overdue(1500u64)); // Before deadline
-    assert!(!request.is_overdue(2000u64)); // At deadline
-    assert!(request.is_overdue(2001u64)); // After deadline
+    // Urgent - needs at least 4 hours
+    let id2 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .required_by(current_time + 6 * 3600)
+        .create(&client);
+    assert_eq!(client.get_request(&id2).urgency, UrgencyLevel::Urgent);
+
+    // Normal - needs at least 24 hours
+    let id3 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 48 * 3600)
+        .create(&client);
+    assert_eq!(client.get_request(&id3).urgency, UrgencyLevel::Normal);
 }
 
 #[test]
-fn test_blood_request_time_remaining() {
-    let env = Env::default();
-    env.mock_all_auths();
-
-    let hospital = Address::generate(&env);
-    let patient = Address::generate(&env);
+fn test_create_request_edge_case_quantities() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let metadata = RequestMetadata {
-        patient_id: patient,
-        procedure: String::from_str(&env, "Surgery"),
-        notes: String::from_str(&env, "Notes"),
-    };
+    let hospital = authorized_hospital(&env, &client);
 
-    let request = BloodRequest {
-        id: 1,
-        hospital_id: hospital,
-        blood_type: BloodType::BPositive,
-        quantity_ml: 500,
-        urgency: UrgencyLevel::Critical,
-        status: RequestStatus::Pending,
-        created_at: 1000u64,
-        required_by: 2000u64,
-        fulfilled_at: None,
-        assigned_units: soroban_sdk::vec![&env],
-        delivery_address: String::from_str(&env, "Hospital"),
-        metadata,
-    };
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
 
-    assert_eq!(request.time_remaining(1000u64), 1000i64); // 1000 seconds remaining
-    assert_eq!(request.time_remaining(1500u64), 500i64); // 500 seconds remaining
-    assert_eq!(request.time_remaining(2000u64), 0i64); // 0 seconds remaining
-    assert_eq!(request.time_remaining(2500u64), -500i64); // -500 seconds (overdue)
+    let required_by = current_time + 7 * 86400;
+
+    // Minimum quantity
+    let id1 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(100)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+    assert_eq!(client.get_request(&id1).quantity_ml, 100);
+
+    // Maximum quantity
+    let id2 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(10000)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+    assert_eq!(client.get_request(&id2).quantity_ml, 10000);
 }
 
 #[test]
-fn test_blood_request_can_fulfill() {
-    let env = Env::default();
-    env.mock_all_auths();
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_create_request_unauthorized_hospital() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = Address::generate(&env);
-    let patient = Address::generate(&env);
+    let unauthorized_hospital = Address::generate(&env);
 
-    let metadata = RequestMetadata {
-        patient_id: patient,
-        procedure: String::from_str(&env, "Surgery"),
-        notes: String::from_str(&env, "Notes"),
-    };
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
 
-    let mut request = BloodRequest {
-        id: 1,
-        hospital_id: hospital,
-        blood_type: BloodType::ABNegative,
-        quantity_ml: 450,
-        urgency: UrgencyLevel::Normal,
-        status: RequestStatus::Approved,
-        created_at: 1000u64,
-        required_by: 2000u64,
-        fulfilled_at: None,
-        assigned_units: soroban_sdk::vec![&env],
-        delivery_address: String::from_str(&env, "Hospital"),
-        metadata,
-    };
+    RequestFixture::new(&env)
+        .by(&unauthorized_hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
+}
 
-    // Can fulfill when Approved and not overdue
-    assert!(request.can_fulfill(1500u64));
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_create_request_quantity_too_low() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    // Cannot fulfill when overdue
-    assert!(!request.can_fulfill(2001u64));
+    let hospital = authorized_hospital(&env, &client);
 
-    // Cannot fulfill when not Approved
-    request.status = RequestStatus::Pending;
-    assert!(!request.can_fulfill(1500u64));
-}
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
 
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(50)
+        .with_urgency(
+            // Too low (min is 100)
+            UrgencyLevel::Normal,
+        )
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
+}
 
 #[test]
-fn test_create_request_as_admin_success() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_create_request_quantity_too_high() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
-    let blood_type = BloodType::OPositive;
-    let quantity_ml = 450u32;
-    let urgency = UrgencyLevel::Urgent;
+    let hospital = authorized_hospital(&env, &client);
 
-    let current_time = 1000u64;
+    let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
-    let required_by = current_time + (2 * 86400); // 2 days from now
 
-    let delivery_address = String::from_str(&env, "Hospital Main Building");
-    let procedure = String::from_str(&env, "Emergency Surgery");
-    let notes = String::from_str(&env, "Type O+ preferred");
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(15000)
+        .with_urgency(
+            // Too high (max is 10000)
+            UrgencyLevel::Normal,
+        )
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
+}
 
-    let request_id = client.create_request(
-        &admin,
-        &blood_type,
-        &quantity_ml,
-        &urgency,
-        &required_by,
-        &delivery_address,
-    );
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_create_request_required_by_too_soon() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    assert_eq!(request_id, 1);
+    let hospital = authorized_hospital(&env, &client);
 
-    // Verify stored request
-    let stored_request = client.get_request(&request_id);
-    assert_eq!(stored_request.id, 1);
-    assert_eq!(stored_request.hospital_id, hospital);
-    assert_eq!(stored_request.blood_type, blood_type);
-    assert_eq!(stored_request.quantity_ml, quantity_ml);
-    assert_eq!(stored_request.urgency, urgency);
-    assert_eq!(stored_request.status, RequestStatus::Pending);
-    assert_eq!(stored_request.created_at, current_time);
-    assert_eq!(stored_request.required_by, required_by);
-    assert_eq!(stored_request.fulfilled_at, None);
-    assert_eq!(stored_request.assigned_units.len(), 0);
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 1800) // Only 30 minutes (min is 1 hour)
+        .create(&client);
 }
 
 #[test]
-fn test_create_request_increments_id() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_create_request_required_by_too_far() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let required_by = current_time + (7 * 86400);
-    let delivery_address = String::from_str(&env, "123 Hospital Street");
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 31 * 86400) // 31 days (max is 30)
+        .create(&client);
+}
 
-    // Create first request
-    let id1 = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &delivery_address,
-    );
-    assert_eq!(id1, 1);
+#[test]
+fn test_create_multiple_requests() {
+    let (env, admin, client, _contract_id) = setup();
 
-    // Create second request
-    let id2 = client.create_request(
-        &hospital,
-        &BloodType::BPositive,
-        &450u32,
-        &UrgencyLevel::Urgent,
-        &required_by,
-        &delivery_address,
-    );
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let id1 = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::OPositive)
+        .required_by(current_time + 86400)
+        .create(&client);
+    let id2 = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::ABNegative)
+        .required_by(current_time + 86400)
+        .create(&client);
+
+    assert_eq!(id1, 1);
     assert_eq!(id2, 2);
-
-    // Create third request
-    let id3 = client.create_request(
-        &hospital,
-        &BloodType::ONegative,
-        &450u32,
-        &UrgencyLevel::Critical,
-        &(current_time + 2 * 3600), // Critical needs less time
-        &delivery_address,
-    );
-    assert_eq!(id3, 3);
-}
+    assert_eq!(client.get_request(&id1).blood_type, BloodType::OPositive);
+    assert_eq!(client.get_request(&id2).blood_type, BloodType::ABNegative);
+}
 
 #[test]
-fn test_create_request_all_blood_types() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_create_request_invalid_quantity_too_low() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
-    let current_time = 1000000u64;
+    let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
-    let required_by = current_time + (7 * 86400);
-    let delivery_address = String::from_str(&env, "123 Hospital Street");
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_quantity(25) // Below minimum (100ml)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 7200)
+        .create(&client);
+}
 
-    let blood_types = [
-        BloodType::APositive,
-        BloodType::ANegative,
-        BloodType::BPositive,
-        BloodType::BNegative,
-        BloodType::ABPositive,
-        BloodType::ABNegative,
-        BloodType::OPositive,
-        BloodType::ONegative,
-    ];
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_create_request_invalid_quantity_too_high() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    for (i, blood_type) in blood_types.iter().enumerate() {
-        let id = client.create_request(
-            &hospital,
-            blood_type,
-            &450u32,
-            &UrgencyLevel::Normal,
-            &required_by,
-            &delivery_address,
-        );
+    let hospital = authorized_hospital(&env, &client);
 
-        assert_eq!(id, (i + 1) as u64);
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
 
-        let request = client.get_request(&id);
-        assert_eq!(request.blood_type, *blood_type);
-    }
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_quantity(20000) // Above maximum (10000ml)
+        .required_by(current_time + 86400)
+        .create(&client);
 }
 
 #[test]
-fn test_create_request_all_urgency_levels() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_create_request_invalid_timestamp_in_past() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "123 Hospital Street");
+    // A deadline in the past fails the required_by window
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(current_time - 100)
+        .create(&client);
+}
 
-    // Critical - needs at least 1 hour
-    let id1 = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Critical,
-        &(current_time + 2 * 3600), // 2 hours
-        &delivery_address,
-    );
-    let req1 = client.get_request(&id1);
-    assert_eq!(req1.urgency, UrgencyLevel::Critical);
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_create_request_invalid_timestamp_too_far() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    // Urgent - needs at least 4 hours
-    let id2 = client.create_request(
-        &hospital,
-        &BloodType::BPositive,
-        &450u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 6 * 3600), // 6 hours
-        &delivery_address,
-    );
-    let req2 = client.get_request(&id2);
-    assert_eq!(req2.urgency, UrgencyLevel::Urgent);
+    let hospital = authorized_hospital(&env, &client);
 
-    // Normal - needs at least 24 hours
-    let id3 = client.create_request(
-        &hospital,
-        &BloodType::ONegative,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 48 * 3600), // 48 hours
-        &delivery_address,
-    );
-    let req3 = client.get_request(&id3);
-    assert_eq!(req3.urgency, UrgencyLevel::Normal);
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 40 * 86400) // 40 days (max is 30)
+        .create(&client);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #32)")]
-fn test_create_request_unauthorized_hospital() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_create_request_empty_delivery_address() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let unauthorized_hospital = Address::generate(&env);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
     client.create_request(
-        &unauthorized_hospital,
+        &hospital,
         &BloodType::APositive,
         &450u32,
         &UrgencyLevel::Normal,
         &(current_time + 7 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
+        &String::from_str(&env, ""),
+        &sample_metadata(&env),
+        &RequestOptions::new(&env),
     );
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #16)")]
-fn test_create_request_quantity_too_low() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_create_request_extreme_required_by() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(u64::MAX)
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::InvalidRequiredBy)));
+
+    // Near the end of time the validation window itself would overflow
+    env.ledger().set_timestamp(u64::MAX - 1800);
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(u64::MAX)
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::InvalidTimestamp)));
+}
 
-    let hospital = setup_authorized_hospital(&env, &client);
+// ========== Idempotency Key Tests ==========
 
-    let current_time = 1000000u64;
-    env.ledger().set_timestamp(current_time);
+#[test]
+fn test_create_request_retry_with_idempotency_key() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let key = BytesN::from_array(&env, &[7; 32]);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_idempotency_key(&key)
+        .create(&client);
+    assert_eq!(count_events(&env, "request_created"), 1);
+
+    // The retry returns the original request and emits nothing
+    let retried = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_idempotency_key(&key)
+        .create(&client);
+    assert_eq!(retried, request_id);
+    assert_eq!(count_events(&env, "request_created"), 0);
+    assert_eq!(client.get_request_count(), 1);
+    assert_eq!(client.get_hospital_requests(&hospital).len(), 1);
+    assert_eq!(
+        client.get_request_by_idempotency_key(&hospital, &key),
+        Some(request_id)
+    );
 
-    client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &50u32, // Too low (min is 100)
-        &UrgencyLevel::Normal,
-        &(current_time + 7 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
+    // Without a key every call creates a request
+    RequestFixture::new(&env).by(&hospital).create(&client);
+    assert_eq!(client.get_request_count(), 2);
+}
+
+#[test]
+fn test_idempotency_keys_namespaced_per_hospital() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let first = authorized_hospital(&env, &client);
+    let second = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let key = BytesN::from_array(&env, &[7; 32]);
+
+    let first_id = RequestFixture::new(&env)
+        .by(&first)
+        .with_idempotency_key(&key)
+        .create(&client);
+    let second_id = RequestFixture::new(&env)
+        .by(&second)
+        .with_idempotency_key(&key)
+        .create(&client);
+    assert_ne!(first_id, second_id);
+    assert_eq!(
+        client.get_request_by_idempotency_key(&second, &key),
+        Some(second_id)
     );
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #16)")]
-fn test_create_request_quantity_too_high() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_idempotency_key_lapses_and_prunes() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let key = BytesN::from_array(&env, &[7; 32]);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_idempotency_key(&key)
+        .create(&client);
+
+    // Live keys are kept
+    assert!(!client.prune_idempotency_key(&hospital, &key));
+
+    env.ledger()
+        .set_timestamp(1000 + IDEMPOTENCY_WINDOW_SECONDS);
+    assert_eq!(client.get_request_by_idempotency_key(&hospital, &key), None);
+    assert!(client.prune_idempotency_key(&hospital, &key));
+    assert!(!client.prune_idempotency_key(&hospital, &key));
+
+    // Once lapsed the key starts a new request
+    let reused = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_idempotency_key(&key)
+        .create(&client);
+    assert_ne!(reused, request_id);
+    assert_eq!(
+        client.get_request_by_idempotency_key(&hospital, &key),
+        Some(reused)
+    );
+}
 
-    let hospital = setup_authorized_hospital(&env, &client);
+// ========== Co-signer Tests ==========
 
-    let current_time = 1000000u64;
-    env.ledger().set_timestamp(current_time);
+fn create_critical_request(
+    env: &Env,
+    client: &RequestContractClient<'_>,
+    hospital: &Address,
+    co_signer: Option<Address>,
+) -> Result<u64, ContractError> {
+    let current_time = env.ledger().timestamp();
+    client
+        .try_create_request(
+            hospital,
+            &BloodType::ONegative,
+            &450u32,
+            &UrgencyLevel::Critical,
+            &(current_time + 2 * 3600),
+            &String::from_str(env, "123 Hospital Street"),
+            &sample_metadata(env),
+            &RequestOptions {
+                co_signer,
+                ..RequestOptions::new(env)
+            },
+        )
+        .map(|id| id.unwrap())
+        .map_err(|err| err.unwrap())
+}
 
-    client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &20000u32, // Too high (max is 10000)
-        &UrgencyLevel::Normal,
-        &(current_time + 7 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
-    );
+#[test]
+fn test_critical_request_requires_co_signer() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let signer = Address::generate(&env);
+    client.add_request_signer(&hospital, &signer);
+    env.ledger().set_timestamp(1000);
+
+    let result = create_critical_request(&env, &client, &hospital, None);
+    assert_eq!(result, Err(ContractError::CoSignerRequired));
+
+    let request_id =
+        create_critical_request(&env, &client, &hospital, Some(signer.clone())).unwrap();
+    assert_eq!(client.get_request(&request_id).co_signer, Some(signer));
+}
+
+#[test]
+fn test_critical_request_rejects_unregistered_co_signer() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    client.add_request_signer(&hospital, &Address::generate(&env));
+    env.ledger().set_timestamp(1000);
+
+    let result = create_critical_request(&env, &client, &hospital, Some(Address::generate(&env)));
+    assert_eq!(result, Err(ContractError::NotRequestSigner));
+}
+
+#[test]
+fn test_critical_request_without_signers_needs_single_signature() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = create_critical_request(&env, &client, &hospital, None).unwrap();
+    assert_eq!(client.get_request(&request_id).co_signer, None);
+
+    // Removing the last signer restores the single-signature behavior
+    let signer = Address::generate(&env);
+    client.add_request_signer(&hospital, &signer);
+    client.remove_request_signer(&hospital, &signer);
+    assert_eq!(client.get_request_signers(&hospital).len(), 0);
+    create_critical_request(&env, &client, &hospital, None).unwrap();
+}
+
+#[test]
+fn test_add_request_signer_validation() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let signer = Address::generate(&env);
+
+    let result = client.try_add_request_signer(&hospital, &hospital);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    client.add_request_signer(&hospital, &signer);
+    let result = client.try_add_request_signer(&hospital, &signer);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyExists)));
+
+    let result = client.try_add_request_signer(&Address::generate(&env), &signer);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedHospital)));
+
+    let result = client.try_remove_request_signer(&hospital, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(ContractError::NotFound)));
+
+    assert_eq!(client.get_request_signers(&hospital), vec![&env, signer]);
 }
 
+// ========== Urgency Time Window Tests ==========
+
 #[test]
 #[should_panic(expected = "Error(Contract, #17)")]
-fn test_create_request_required_by_too_soon() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_critical_request_insufficient_time() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    // Only 30 minutes (less than 1 hour minimum)
-    client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 1800),
-        &String::from_str(&env, "123 Hospital Street"),
-    );
+    // Critical needs at least 1 hour, but we give only 30 minutes
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 1800)
+        .create(&client);
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #17)")]
-fn test_create_request_required_by_too_far() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_urgent_request_insufficient_time() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    // 60 days (more than 30 day max)
-    client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 60 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
-    );
+    // Urgent needs at least 4 hours, but we give only 2 hours
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(current_time + 2 * 3600)
+        .create(&client);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #19)")]
-fn test_create_request_empty_delivery_address() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_normal_request_insufficient_time() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 7 * 86400),
-        &String::from_str(&env, ""), // Empty address
+    // Normal needs at least 24 hours, but we give only 12 hours
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 12 * 3600)
+        .create(&client);
+}
+
+// ========== Range Scan Tests ==========
+
+#[test]
+fn test_get_requests_range() {
+    let (env, _admin, client, contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    assert_eq!(client.get_request_count(), 0);
+    assert_eq!(client.get_requests_range(&1, &10).len(), 0);
+
+    for _ in 0..5 {
+        RequestFixture::new(&env)
+            .by(&hospital)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(1000 + 7 * 86400)
+            .create(&client);
+    }
+    assert_eq!(client.get_request_count(), 5);
+
+    // Drop request 3 to leave a hole in the ID space
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().remove(&DataKey::BloodRequest(3));
+    });
+
+    let mut ids = vec![&env];
+    for request in client.get_requests_range(&0, &10).iter() {
+        ids.push_back(request.id);
+    }
+    assert_eq!(ids, vec![&env, 1u64, 2, 4, 5]);
+
+    let page = client.get_requests_range(&4, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, 4);
+
+    assert_eq!(client.get_requests_range(&6, &10).len(), 0);
+}
+
+#[test]
+fn test_get_requests_range_caps_limit() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    for _ in 0..60 {
+        RequestFixture::new(&env)
+            .by(&hospital)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(1000 + 7 * 86400)
+            .create(&client);
+    }
+
+    assert_eq!(client.get_requests_range(&1, &100).len(), 50);
+    assert_eq!(client.get_requests_range(&51, &100).len(), 10);
+}
+
+#[test]
+fn test_get_recent_hospital_requests() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let other = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    for i in 0..15u64 {
+        RequestFixture::new(&env)
+            .by(&hospital)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(1000 + 7 * 86400 + i)
+            .create(&client);
+        // Interleave another hospital's requests
+        RequestFixture::new(&env)
+            .by(&other)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(1000 + 7 * 86400)
+            .create(&client);
+    }
+
+    let recent = client.get_recent_hospital_requests(&hospital, &10);
+    assert_eq!(recent.len(), 10);
+
+    // Hospital's requests have the odd IDs 1..=29, newest first
+    for (i, request) in recent.iter().enumerate() {
+        assert_eq!(request.hospital_id, hospital);
+        assert_eq!(request.id, 29 - 2 * i as u64);
+    }
+
+    assert_eq!(
+        client.get_recent_hospital_requests(&hospital, &100).len(),
+        15
+    );
+    assert_eq!(
+        client
+            .get_recent_hospital_requests(&Address::generate(&env), &10)
+            .len(),
+        0
     );
 }
 
+// ========== Pending Approval Worklist Tests ==========
+
 #[test]
-fn test_create_request_edge_case_quantities() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_get_pending_approvals_oldest_first() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    assert_eq!(client.get_oldest_pending_age(), None);
+
+    // Created at 1000, 2000, 3000 and 4000
+    let mut ids = vec![&env];
+    for i in 1..=4u64 {
+        env.ledger().set_timestamp(i * 1000);
+        ids.push_back(
+            RequestFixture::new(&env)
+                .by(&hospital)
+                .with_urgency(UrgencyLevel::Normal)
+                .required_by(i * 1000 + 7 * 86400)
+                .create(&client),
+        );
+    }
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    // Approving and reverting the oldest puts it at the end of the status
+    // index, but it must still come first
+    client.approve_request(&ids.get(0).unwrap());
+    client.revert_approval(
+        &admin,
+        &ids.get(0).unwrap(),
+        &String::from_str(&env, "Approved too early"),
+    );
+    client.approve_request(&ids.get(1).unwrap());
+
+    env.ledger().set_timestamp(5000);
+    assert_eq!(
+        client.get_pending_approvals(&None),
+        vec![
+            &env,
+            ids.get(0).unwrap(),
+            ids.get(2).unwrap(),
+            ids.get(3).unwrap()
+        ]
+    );
+    assert_eq!(
+        client.get_pending_approvals(&Some(1)),
+        vec![&env, ids.get(0).unwrap()]
+    );
+    assert_eq!(client.get_oldest_pending_age(), Some(4000));
+}
 
-    let current_time = 1000000u64;
-    env.ledger().set_timestamp(current_time);
+#[test]
+fn test_get_pending_approvals_skips_overdue() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    env.ledger().set_timestamp(1000);
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    env.ledger().set_timestamp(2000);
+    let open_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(2000 + 7 * 86400)
+        .create(&client);
+
+    // The first request is past its deadline but not yet swept
+    env.ledger().set_timestamp(1000 + 86400 + 1);
+    assert_eq!(client.get_pending_approvals(&None), vec![&env, open_id]);
+    assert_eq!(
+        client.get_oldest_pending_age(),
+        Some(1000 + 86400 + 1 - 2000)
+    );
+}
 
-    let required_by = current_time + (7 * 86400);
-    let delivery_address = String::from_str(&env, "123 Hospital Street");
+// ========== Deadline Window Tests ==========
 
-    // Minimum valid quantity
-    let id1 = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &100u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &delivery_address,
+#[test]
+fn test_get_requests_due_between() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let day = 86400u64;
+    env.ledger().set_timestamp(day);
+
+    let due_tomorrow = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(2 * day + 3600)
+        .create(&client);
+    let due_later = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(5 * day)
+        .create(&client);
+    let cancelled = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(2 * day + 7200)
+        .create(&client);
+    client.cancel_request(&cancelled, &hospital);
+
+    let tomorrow = client.get_requests_due_between(&(2 * day), &(3 * day - 1), &None, &None);
+    assert_eq!(tomorrow, vec![&env, due_tomorrow]);
+
+    // Extending moves the request to its new bucket only
+    client.extend_deadline(&hospital, &due_tomorrow, &(5 * day + 3600));
+    assert_eq!(
+        client
+            .get_requests_due_between(&(2 * day), &(3 * day - 1), &None, &None)
+            .len(),
+        0
+    );
+    assert_eq!(
+        client.get_requests_due_between(&(5 * day), &(6 * day - 1), &None, &None),
+        vec![&env, due_later, due_tomorrow]
+    );
+    assert_eq!(
+        client.get_requests_due_between(&(5 * day), &(6 * day - 1), &Some(1), &Some(1)),
+        vec![&env, due_tomorrow]
     );
-    let req1 = client.get_request(&id1);
-    assert_eq!(req1.quantity_ml, 100);
+}
 
-    // Maximum valid quantity
-    let id2 = client.create_request(
-        &hospital,
-        &BloodType::BPositive,
-        &10000u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &delivery_address,
+#[test]
+fn test_get_requests_due_between_rejects_bad_windows() {
+    let (_env, _admin, client, _contract_id) = setup();
+
+    let result = client.try_get_requests_due_between(&2000, &1000, &None, &None);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let result = client.try_get_requests_due_between(
+        &0,
+        &(storage::MAX_DUE_WINDOW_SECONDS + 1),
+        &None,
+        &None,
     );
-    let req2 = client.get_request(&id2);
-    assert_eq!(req2.quantity_ml, 10000);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+}
+
+// ========== Summary View Tests ==========
+
+fn assert_summary_matches(summary: &RequestSummary, request: &BloodRequest) {
+    assert_eq!(summary.id, request.id);
+    assert_eq!(summary.hospital_id, request.hospital_id);
+    assert_eq!(summary.blood_type, request.blood_type);
+    assert_eq!(summary.quantity_ml, request.quantity_ml);
+    assert_eq!(summary.urgency, request.urgency);
+    assert_eq!(summary.status, request.status);
+    assert_eq!(summary.required_by, request.required_by);
+    assert_eq!(summary.created_at, request.created_at);
+    assert_eq!(summary.sla_breached, request.sla_breached);
+}
+
+#[test]
+fn test_request_summaries_match_full_requests() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let normal = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(1000 + 7 * 86400)
+        .create(&client);
+    let urgent = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_quantity(900)
+        .required_by(1000 + 86400)
+        .create(&client);
+    let approved = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(1000 + 3 * 86400)
+        .create(&client);
+    client.approve_request(&approved);
+
+    // A summary carries eight scalar fields; the full request additionally
+    // carries the delivery address, the metadata (patient address and two
+    // strings), the assigned unit list and four more fields
+    let summaries = client.get_hospital_requests_summary(&hospital);
+    assert_eq!(summaries.len(), 3);
+    for summary in summaries.iter() {
+        assert_summary_matches(&summary, &client.get_request(&summary.id));
+    }
+
+    // Pending queue order: Urgent first, approved request excluded
+    let pending = client.get_pending_summaries(&None);
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending.get(0).unwrap().id, urgent);
+    assert_eq!(pending.get(1).unwrap().id, normal);
+    assert_eq!(client.get_pending_summaries(&Some(1)).len(), 1);
 }
 
 // ========== Approve Request Tests ==========
 
 #[test]
 fn test_approve_request_success() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let request_id = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 7 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
-    );
-
-    // Verify initial status
-    let request = client.get_request(&request_id);
-    assert_eq!(request.status, RequestStatus::Pending);
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
 
-    // Approve
+    assert_eq!(client.get_request(&request_id).approved_by, None);
     client.approve_request(&request_id);
 
-    // Verify updated status
-    let updated_request = client.get_request(&request_id);
-    assert_eq!(updated_request.status, RequestStatus::Approved);
+    let event: RequestApprovedEvent = latest_event(&env, "request_approved");
+    assert_eq!(event.request_id, request_id);
+    assert_eq!(event.hospital_id, hospital);
+    assert_eq!(event.approved_by, admin);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Approved);
+    assert_eq!(request.approved_by, Some(admin.clone()));
+
+    // Status indexes follow the request
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Pending).len(),
+        0
+    );
+    assert_eq!(
+        client
+            .get_requests_by_status(&RequestStatus::Approved)
+            .len(),
+        1
+    );
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #21)")]
 fn test_approve_request_not_found() {
-    let (_env, _admin, client, _contract_id) = create_test_contract();
+    let (_env, _admin, client, _contract_id) = setup();
 
     client.approve_request(&999);
 }
@@ -1164,107 +1847,185 @@ fn test_approve_request_not_found() {
 #[test]
 #[should_panic(expected = "Error(Contract, #41)")]
 fn test_approve_request_already_approved() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let request_id = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 7 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
-    );
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
 
-    // Approve first time
     client.approve_request(&request_id);
-
-    // Try to approve again - should fail
     client.approve_request(&request_id);
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #22)")]
 fn test_approve_request_expired() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let required_by = current_time + (2 * 86400); // 2 days
+    let required_by = current_time + 2 * 86400;
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
 
-    let request_id = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &String::from_str(&env, "123 Hospital Street"),
-    );
-
-    // Fast forward past required_by
+    // Move past the deadline
     env.ledger().set_timestamp(required_by + 1);
 
-    // Try to approve expired request
     client.approve_request(&request_id);
 }
 
+// ========== Update Status Tests ==========
+
+#[test]
+fn test_update_request_status_pending_to_approved() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::OPositive)
+        .required_by(current_time + 86400)
+        .create(&client);
+
+    client.update_request_status(&request_id, &RequestStatus::Approved);
+
+    let event: RequestApprovedEvent = latest_event(&env, "request_approved");
+    assert_eq!(event.approved_by, admin);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Approved);
+    assert_eq!(request.approved_by, Some(admin.clone()));
+
+    let approved = client.get_requests_by_status(&RequestStatus::Approved);
+    assert_eq!(approved.len(), 1);
+    assert_eq!(approved.get(0).unwrap(), request_id);
+}
+
+#[test]
+fn test_update_request_status_approved_to_fulfilled() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::BPositive)
+        .with_quantity(500)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 86400)
+        .create(&client);
+
+    client.update_request_status(&request_id, &RequestStatus::Approved);
+    client.update_request_status(&request_id, &RequestStatus::Fulfilled);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Fulfilled);
+    assert_eq!(request.fulfilled_at, Some(current_time));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn test_update_request_status_invalid_transition() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::ABPositive)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 3600)
+        .create(&client);
+
+    // Pending -> Fulfilled skips approval
+    client.update_request_status(&request_id, &RequestStatus::Fulfilled);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn test_update_request_status_from_terminal_state() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 86400)
+        .create(&client);
+
+    // Rejected is a terminal state
+    client.update_request_status(&request_id, &RequestStatus::Rejected);
+    client.update_request_status(&request_id, &RequestStatus::Approved);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn test_update_request_status_not_found() {
+    let (_env, _admin, client, _contract_id) = setup();
+
+    client.update_request_status(&999, &RequestStatus::Approved);
+}
+
 // ========== Cancel Request Tests ==========
 
 #[test]
 fn test_cancel_request_by_hospital() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let request_id = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 7 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
-    );
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
 
-    // Cancel by hospital
     client.cancel_request(&request_id, &hospital);
 
-    // Verify cancelled
     let request = client.get_request(&request_id);
     assert_eq!(request.status, RequestStatus::Cancelled);
 }
 
 #[test]
 fn test_cancel_request_by_admin() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let request_id = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 7 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
-    );
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
 
-    // Cancel by admin
     client.cancel_request(&request_id, &admin);
 
-    // Verify cancelled
     let request = client.get_request(&request_id);
     assert_eq!(request.status, RequestStatus::Cancelled);
 }
@@ -1272,778 +2033,1552 @@ fn test_cancel_request_by_admin() {
 #[test]
 #[should_panic(expected = "Error(Contract, #2)")]
 fn test_cancel_request_unauthorized() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
-    let other_hospital = Address::generate(&env);
+    let hospital = authorized_hospital(&env, &client);
+    let other = Address::generate(&env);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let request_id = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 7 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
-    );
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
 
-    // Try to cancel by unauthorized party
-    client.cancel_request(&request_id, &other_hospital);
+    client.cancel_request(&request_id, &other);
 }
 
 #[test]
 #[should_panic(expected = "Error(Contract, #42)")]
 fn test_cancel_request_already_cancelled() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let request_id = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 7 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
-    );
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
 
-    // Cancel first time
     client.cancel_request(&request_id, &hospital);
-
-    // Try to cancel again
     client.cancel_request(&request_id, &hospital);
 }
 
-// ========== Query Tests ==========
-
 #[test]
-fn test_get_hospital_requests() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_cancel_my_pending_requests_skips_approved() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital1 = setup_authorized_hospital(&env, &client);
-    let hospital2 = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
+    let other = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let required_by = current_time + (7 * 86400);
-    let delivery_address = String::from_str(&env, "123 Hospital Street");
-
-    // Create requests for hospital1
-    let id1 = client.create_request(
-        &hospital1,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &delivery_address,
-    );
-    let id2 = client.create_request(
-        &hospital1,
-        &BloodType::BPositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &delivery_address,
+    let mut ids = Vec::new(&env);
+    for _ in 0..4 {
+        ids.push_back(
+            RequestFixture::new(&env)
+                .by(&hospital)
+                .with_urgency(UrgencyLevel::Normal)
+                .required_by(current_time + 7 * 86400)
+                .create(&client),
+        );
+    }
+    let approved = ids.get(1).unwrap();
+    client.approve_request(&approved);
+    let others = RequestFixture::new(&env)
+        .by(&other)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
+
+    assert_eq!(client.cancel_my_pending_requests(&hospital, &2), 2);
+    assert_eq!(client.cancel_my_pending_requests(&hospital, &2), 1);
+    assert_eq!(client.cancel_my_pending_requests(&hospital, &2), 0);
+
+    for id in ids.iter() {
+        let expected = if id == approved {
+            RequestStatus::Approved
+        } else {
+            RequestStatus::Cancelled
+        };
+        assert_eq!(client.get_request(&id).status, expected);
+    }
+    assert_eq!(client.get_request(&others).status, RequestStatus::Pending);
+    assert_eq!(
+        client
+            .get_requests_by_status(&RequestStatus::Cancelled)
+            .len(),
+        3
     );
+    assert_eq!(client.get_pending_queue(&None), vec![&env, others]);
+}
 
-    // Create request for hospital2
-    let _id3 = client.create_request(
-        &hospital2,
-        &BloodType::ONegative,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &delivery_address,
-    );
+// ========== Completion Tests ==========
+
+#[test]
+fn test_complete_request_from_in_delivery() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+    client.update_request_status(&request_id, &RequestStatus::InDelivery);
 
-    // Query hospital1 requests
-    let hospital1_requests = client.get_hospital_requests(&hospital1);
-    assert_eq!(hospital1_requests.len(), 2);
-    assert_eq!(hospital1_requests.get(0).unwrap(), id1);
-    assert_eq!(hospital1_requests.get(1).unwrap(), id2);
+    env.ledger().set_timestamp(5000);
+    client.complete_request(&hospital, &request_id);
 
-    // Query hospital2 requests
-    let hospital2_requests = client.get_hospital_requests(&hospital2);
-    assert_eq!(hospital2_requests.len(), 1);
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Completed);
+    assert_eq!(request.fulfilled_at, Some(5000));
+    assert!(!request.completion_override);
+    assert_eq!(client.get_completion_override(&request_id), None);
 }
 
 #[test]
-fn test_get_requests_by_status() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_complete_request_requires_in_delivery() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
 
-    let current_time = 1000000u64;
-    env.ledger().set_timestamp(current_time);
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
 
-    let required_by = current_time + (7 * 86400);
-    let delivery_address = String::from_str(&env, "123 Hospital Street");
+    let result = client.try_complete_request(&hospital, &request_id);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+}
 
-    // Create requests
-    let id1 = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &delivery_address,
-    );
-    let id2 = client.create_request(
-        &hospital,
-        &BloodType::BPositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &delivery_address,
-    );
+#[test]
+fn test_complete_request_wrong_hospital() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    // Approve one request
-    client.approve_request(&id1);
+    let hospital = authorized_hospital(&env, &client);
+    let other = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
 
-    // Query pending requests
-    let pending_requests = client.get_requests_by_status(&RequestStatus::Pending);
-    assert_eq!(pending_requests.len(), 1);
-    assert_eq!(pending_requests.get(0).unwrap(), id2);
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+    client.update_request_status(&request_id, &RequestStatus::InDelivery);
 
-    // Query approved requests
-    let approved_requests = client.get_requests_by_status(&RequestStatus::Approved);
-    assert_eq!(approved_requests.len(), 1);
-    assert_eq!(approved_requests.get(0).unwrap(), id1);
+    let result = client.try_complete_request(&other, &request_id);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
 }
 
 #[test]
-fn test_get_requests_by_blood_type() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_force_complete_records_override() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(1000 + 2 * 3600)
+        .create(&client);
+    client.approve_request(&request_id);
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(2000);
+    let justification = String::from_str(&env, "Hospital key lost mid-delivery");
+    client.force_complete(&admin, &request_id, &justification);
 
-    let current_time = 1000000u64;
-    env.ledger().set_timestamp(current_time);
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Completed);
+    assert!(request.completion_override);
+    assert_eq!(request.fulfilled_at, Some(2000));
+
+    let record = client.get_completion_override(&request_id).unwrap();
+    assert_eq!(record.request_id, request_id);
+    assert_eq!(record.admin, admin);
+    assert_eq!(record.previous_status, RequestStatus::Approved);
+    assert_eq!(record.justification, justification);
+    assert_eq!(record.completed_at, 2000);
+
+    let completed = client.get_requests_by_status(&RequestStatus::Completed);
+    assert_eq!(completed.len(), 1);
+    assert!(client
+        .get_requests_by_status(&RequestStatus::Approved)
+        .is_empty());
+}
 
-    let required_by = current_time + (7 * 86400);
-    let delivery_address = String::from_str(&env, "123 Hospital Street");
+#[test]
+fn test_force_complete_requires_admin() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    // Create requests with different blood types
-    let id1 = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &delivery_address,
-    );
-    let _id2 = client.create_request(
-        &hospital,
-        &BloodType::BPositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &required_by,
-        &delivery_address,
-    );
-    let id3 = client.create_request(
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+
+    let result = client.try_force_complete(
         &hospital,
-        &BloodType::APositive,
-        &900u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 6 * 3600),
-        &delivery_address,
+        &request_id,
+        &String::from_str(&env, "Not my call"),
     );
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_force_complete_rejects_empty_justification() {
+    let (env, admin, client, _contract_id) = setup();
 
-    // Query A+ requests
-    let a_positive_requests = client.get_requests_by_blood_type(&BloodType::APositive);
-    assert_eq!(a_positive_requests.len(), 2);
-    assert_eq!(a_positive_requests.get(0).unwrap(), id1);
-    assert_eq!(a_positive_requests.get(1).unwrap(), id3);
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+
+    let result = client.try_force_complete(&admin, &request_id, &String::from_str(&env, ""));
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
 }
 
 #[test]
-fn test_get_requests_by_urgency() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_force_complete_rejects_pending_and_terminal() {
+    let (env, admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
 
-    let current_time = 1000000u64;
-    env.ledger().set_timestamp(current_time);
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    let justification = String::from_str(&env, "Override");
 
-    let delivery_address = String::from_str(&env, "123 Hospital Street");
+    let result = client.try_force_complete(&admin, &request_id, &justification);
+    assert_eq!(result, Err(Ok(ContractError::RequestNotApproved)));
 
-    // Create requests with different urgency levels
-    let id1 = client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Critical,
-        &(current_time + 2 * 3600),
-        &delivery_address,
+    client.cancel_request(&request_id, &hospital);
+    let result = client.try_force_complete(&admin, &request_id, &justification);
+    assert_eq!(result, Err(Ok(ContractError::RequestAlreadyTerminal)));
+}
+
+// ========== Approval Revert Tests ==========
+
+#[test]
+fn test_revert_approval_returns_request_to_pending() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+    assert_eq!(client.get_pending_queue(&None).len(), 0);
+
+    client.revert_approval(
+        &admin,
+        &request_id,
+        &String::from_str(&env, "Approved the wrong request"),
     );
-    let _id2 = client.create_request(
-        &hospital,
-        &BloodType::BPositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 48 * 3600),
-        &delivery_address,
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Pending);
+    assert_eq!(request.approved_by, None);
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Pending),
+        vec![&env, request_id]
     );
-    let id3 = client.create_request(
-        &hospital,
-        &BloodType::ONegative,
-        &450u32,
-        &UrgencyLevel::Critical,
-        &(current_time + 3 * 3600),
-        &delivery_address,
+    assert_eq!(
+        client
+            .get_requests_by_status(&RequestStatus::Approved)
+            .len(),
+        0
     );
-
-    // Query critical requests
-    let critical_requests = client.get_requests_by_urgency(&UrgencyLevel::Critical);
-    assert_eq!(critical_requests.len(), 2);
-    assert_eq!(critical_requests.get(0).unwrap(), id1);
-    assert_eq!(critical_requests.get(1).unwrap(), id3);
+    assert_eq!(client.get_pending_queue(&None), vec![&env, request_id]);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #21)")]
-fn test_get_request_not_found() {
-    let (_env, _admin, client, _contract_id) = create_test_contract();
+fn test_revert_approval_requires_unassigned_units() {
+    let (env, admin, client, _contract_id) = setup();
 
-    client.get_request(&999);
-}
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
 
-// ========== Urgency Time Window Tests ==========
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+    client.assign_blood_units(
+        &request_id,
+        &vec![
+            &env,
+            UnitAssignment {
+                unit_id: 1,
+                blood_type: BloodType::APositive,
+                quantity_ml: 450,
+                bank_id: admin.clone(),
+            },
+        ],
+    );
+
+    let justification = String::from_str(&env, "Approved the wrong request");
+    let result = client.try_revert_approval(&admin, &request_id, &justification);
+    assert_eq!(result, Err(Ok(ContractError::UnitsAlreadyAssigned)));
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Approved
+    );
+
+    // Unassigning makes the revert possible
+    client.assign_blood_units(&request_id, &vec![&env]);
+    client.revert_approval(&admin, &request_id, &justification);
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Pending
+    );
+}
 
 #[test]
-#[should_panic(expected = "Error(Contract, #17)")]
-fn test_critical_request_insufficient_time() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_revert_approval_only_from_approved() {
+    let (env, admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
 
-    let current_time = 1000000u64;
-    env.ledger().set_timestamp(current_time);
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
 
-    // Critical needs at least 1 hour, but we give only 30 minutes
-    client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Critical,
-        &(current_time + 1800), // 30 minutes - too short for critical
-        &String::from_str(&env, "123 Hospital Street"),
+    let result = client.try_revert_approval(
+        &admin,
+        &request_id,
+        &String::from_str(&env, "Approved the wrong request"),
     );
+    assert_eq!(result, Err(Ok(ContractError::RequestNotApproved)));
 }
 
+// ========== Delivery Address Tests ==========
+
 #[test]
-#[should_panic(expected = "Error(Contract, #17)")]
-fn test_urgent_request_insufficient_time() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_update_delivery_address_pending() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    // Urgent needs at least 4 hours, but we give only 2 hours
-    client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 2 * 3600), // 2 hours - too short for urgent
-        &String::from_str(&env, "123 Hospital Street"),
-    );
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
+
+    let new_address = String::from_str(&env, "Ward 7, North Wing");
+    client.update_delivery_address(&hospital, &request_id, &new_address);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.delivery_address, new_address);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #17)")]
-fn test_normal_request_insufficient_time() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_update_delivery_address_approved() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let hospital = authorized_hospital(&env, &client);
 
     let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    // Normal needs at least 24 hours, but we give only 12 hours
-    client.create_request(
-        &hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 12 * 3600), // 12 hours - too short for normal
-        &String::from_str(&env, "123 Hospital Street"),
-        &patient,
-        &procedure,
-        &notes,
-    );
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
+    client.approve_request(&request_id);
 
-    assert_eq!(request_id, 1);
+    let new_address = String::from_str(&env, "Emergency Department");
+    client.update_delivery_address(&hospital, &request_id, &new_address);
 
-    // Verify request was created
     let request = client.get_request(&request_id);
-    assert_eq!(request.id, request_id);
-    assert_eq!(request.hospital_id, admin);
-    assert_eq!(request.blood_type, blood_type);
-    assert_eq!(request.quantity_ml, quantity_ml);
-    assert_eq!(request.urgency, urgency);
-    assert_eq!(request.status, RequestStatus::Pending);
-    assert_eq!(request.created_at, current_time);
-    assert_eq!(request.required_by, required_by);
-    assert_eq!(request.fulfilled_at, None);
-    assert_eq!(request.delivery_address, delivery_address);
+    assert_eq!(request.delivery_address, new_address);
+    assert_eq!(request.status, RequestStatus::Approved);
 }
 
-// ========== Advanced Query Function Tests ==========
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn test_update_delivery_address_in_delivery() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+    client.update_request_status(&request_id, &RequestStatus::InDelivery);
+
+    client.update_delivery_address(&hospital, &request_id, &String::from_str(&env, "Too late"));
+}
 
 #[test]
-fn test_get_request_by_id_exists() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+#[should_panic(expected = "Error(Contract, #33)")]
+fn test_update_delivery_address_not_owner() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
-    let current_time = 1000u64;
+    let hospital = authorized_hospital(&env, &client);
+    let other_hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
 
-    let request_id = client.create_request(
-        &admin,
-        &BloodType::OPositive,
-        &450u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 86400),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+    client.update_delivery_address(
+        &other_hospital,
+        &request_id,
+        &String::from_str(&env, "Somewhere else"),
     );
-
-    // Test get_request_by_id
-    let result = client.get_request_by_id(&request_id);
-    assert!(result.is_some());
-    
-    let request = result.unwrap();
-    assert_eq!(request.id, request_id);
-    assert_eq!(request.blood_type, BloodType::OPositive);
 }
 
 #[test]
-fn test_get_request_by_id_non_existent() {
-    let (_env, _admin, client, _contract_id) = create_test_contract();
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_update_delivery_address_empty() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    // Test for non-existent request
-    let result = client.get_request_by_id(&999u64);
-    assert!(result.is_none());
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
+
+    client.update_delivery_address(&hospital, &request_id, &String::from_str(&env, ""));
 }
 
 #[test]
-fn test_query_hospital_requests_all() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_delivery_address_length_limit() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
-    let patient = Address::generate(&env);
-    
-    let current_time = 1000u64;
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    let at_limit = String::from_bytes(&env, &[b'x'; MAX_DELIVERY_ADDRESS_LEN as usize]);
+    let over_limit = String::from_bytes(&env, &[b'x'; MAX_DELIVERY_ADDRESS_LEN as usize + 1]);
 
-    // Create multiple requests for the same hospital
-    client.create_request(
+    let result = client.try_create_request(
         &hospital,
-        &BloodType::OPositive,
-        &450u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 86400),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+        &BloodType::APositive,
+        &450,
+        &UrgencyLevel::Normal,
+        &(current_time + 7 * 86400),
+        &over_limit,
+        &sample_metadata(&env),
+        &RequestOptions::new(&env),
     );
+    assert_eq!(result, Err(Ok(ContractError::DeliveryAddressTooLong)));
 
-    client.create_request(
+    let request_id = client.create_request(
         &hospital,
-        &BloodType::BPositive,
-        &500u32,
+        &BloodType::APositive,
+        &450,
         &UrgencyLevel::Normal,
-        &(current_time + 172800),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+        &(current_time + 7 * 86400),
+        &at_limit,
+        &sample_metadata(&env),
+        &RequestOptions::new(&env),
     );
+    assert_eq!(client.get_request(&request_id).delivery_address, at_limit);
 
-    client.create_request(
-        &hospital,
-        &BloodType::ABNegative,
-        &350u32,
-        &UrgencyLevel::Critical,
-        &(current_time + 7200),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
+    let result = client.try_update_delivery_address(&hospital, &request_id, &over_limit);
+    assert_eq!(result, Err(Ok(ContractError::DeliveryAddressTooLong)));
 
-    // Query all requests for this hospital (no status filter)
-    let requests = client.query_hospital_requests(&hospital, &None, &None, &None);
-    assert_eq!(requests.len(), 3);
+    let result =
+        client.try_update_delivery_address(&hospital, &request_id, &String::from_str(&env, "   "));
+    assert_eq!(result, Err(Ok(ContractError::InvalidDeliveryAddress)));
 }
 
 #[test]
-fn test_query_hospital_requests_with_status_filter() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+#[should_panic(expected = "Error(Contract, #48)")]
+fn test_update_delivery_address_not_found() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    client.update_delivery_address(&hospital, &999, &String::from_str(&env, "Anywhere"));
+}
+
+// ========== Assignment Tests ==========
+
+#[test]
+fn test_assign_blood_units() {
+    let (env, admin, client, contract_id) = setup();
 
-    let hospital = setup_authorized_hospital(&env, &client);
-    let patient = Address::generate(&env);
-    
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
-
-    // Create requests
-    let req1 = client.create_request(
-        &hospital,
-        &BloodType::OPositive,
-        &450u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 86400),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::OPositive)
+        .with_quantity(900)
+        .required_by(current_time + 86400)
+        .create(&client);
+
+    let units = vec![
+        &env,
+        UnitAssignment {
+            unit_id: 1,
+            blood_type: BloodType::OPositive,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        },
+        UnitAssignment {
+            unit_id: 2,
+            blood_type: BloodType::OPositive,
+            quantity_ml: 300,
+            bank_id: admin.clone(),
+        },
+    ];
 
-    let req2 = client.create_request(
-        &hospital,
-        &BloodType::BPositive,
-        &500u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 172800),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
+    // Units can only be assigned once the request is approved
+    let result = client.try_assign_blood_units(&request_id, &units);
+    assert_eq!(result, Err(Ok(ContractError::UnitsAssignedBeforeApproval)));
 
-    // Approve one request
-    client.approve_request(&req1);
+    client.approve_request(&request_id);
+    env.ledger().set_timestamp(current_time + 60);
+    client.assign_blood_units(&request_id, &units);
 
-    // Query pending requests only
-    let pending_requests = client.query_hospital_requests(
-        &hospital,
-        &Some(RequestStatus::Pending),
-        &None,
-        &None,
+    let assigned = vec![
+        &env,
+        AssignedUnit {
+            unit_id: 1,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        },
+        AssignedUnit {
+            unit_id: 2,
+            quantity_ml: 300,
+            bank_id: admin.clone(),
+        },
+    ];
+    let event = UnitsAssignedEvent {
+        request_id,
+        bank_id: admin.clone(),
+        assigned_units: vec![&env, 1u64, 2u64],
+        units: assigned.clone(),
+        total_ml: 750,
+        assigned_at: current_time + 60,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "units_assigned"), admin.clone()).into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
     );
-    assert_eq!(pending_requests.len(), 1);
-    assert_eq!(pending_requests.get(0).unwrap().id, req2);
 
-    // Query approved requests only
-    let approved_requests = client.query_hospital_requests(
-        &hospital,
-        &Some(RequestStatus::Approved),
-        &None,
-        &None,
-    );
-    assert_eq!(approved_requests.len(), 1);
-    assert_eq!(approved_requests.get(0).unwrap().id, req1);
+    let request = client.get_request(&request_id);
+    assert_eq!(request.assigned_units.len(), 2);
+    assert_eq!(request.assigned_units.get(0).unwrap(), 1u64);
+    assert_eq!(request.assigned_units.get(1).unwrap(), 2u64);
+    assert_eq!(request.assignments, assigned);
 }
 
 #[test]
-fn test_query_hospital_requests_pagination() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_assign_blood_units_from_two_banks() {
+    let (env, admin, client, contract_id) = setup();
+    let bank_a = Address::generate(&env);
+    let bank_b = Address::generate(&env);
 
-    let hospital = setup_authorized_hospital(&env, &client);
-    let patient = Address::generate(&env);
-    
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
-
-    // Create 5 requests
-    for i in 0..5 {
-        client.create_request(
-            &hospital,
-            &BloodType::OPositive,
-            &450u32,
-            &UrgencyLevel::Normal,
-            &(current_time + 86400 + (i * 1000)),
-            &delivery_address,
-            &patient,
-            &procedure,
-            &notes,
-        );
-    }
-
-    // Test pagination: limit=2, offset=0
-    let page1 = client.query_hospital_requests(&hospital, &None, &Some(2u32), &Some(0u32));
-    assert_eq!(page1.len(), 2);
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::APositive)
+        .with_quantity(1800)
+        .accept_compatible()
+        .required_by(current_time + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
 
-    // Test pagination: limit=2, offset=2
-    let page2 = client.query_hospital_requests(&hospital, &None, &Some(2u32), &Some(2u32));
-    assert_eq!(page2.len(), 2);
+    // Bank B's units are interleaved with bank A's and one is compatible O-
+    let unit =
+        |unit_id: u64, blood_type: BloodType, quantity_ml: u32, bank: &Address| UnitAssignment {
+            unit_id,
+            blood_type,
+            quantity_ml,
+            bank_id: bank.clone(),
+        };
+    let units = vec![
+        &env,
+        unit(10, BloodType::APositive, 450, &bank_a),
+        unit(20, BloodType::ONegative, 450, &bank_b),
+        unit(11, BloodType::APositive, 450, &bank_a),
+        unit(21, BloodType::APositive, 300, &bank_b),
+    ];
+    client.assign_blood_units(&request_id, &units);
 
-    // Test pagination: limit=2, offset=4
-    let page3 = client.query_hospital_requests(&hospital, &None, &Some(2u32), &Some(4u32));
-    assert_eq!(page3.len(), 1);
+    let assigned = |unit_id: u64, quantity_ml: u32, bank: &Address| AssignedUnit {
+        unit_id,
+        quantity_ml,
+        bank_id: bank.clone(),
+    };
+    let from_a = UnitsAssignedEvent {
+        request_id,
+        bank_id: bank_a.clone(),
+        assigned_units: vec![&env, 10u64, 11u64],
+        units: vec![&env, assigned(10, 450, &bank_a), assigned(11, 450, &bank_a)],
+        total_ml: 900,
+        assigned_at: current_time,
+    };
+    let from_b = UnitsAssignedEvent {
+        request_id,
+        bank_id: bank_b.clone(),
+        assigned_units: vec![&env, 20u64, 21u64],
+        units: vec![&env, assigned(20, 450, &bank_b), assigned(21, 300, &bank_b)],
+        total_ml: 750,
+        assigned_at: current_time,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "units_assigned"), bank_a.clone()).into_val(&env),
+                from_a.clone().into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "units_assigned"), bank_b.clone()).into_val(&env),
+                from_b.clone().into_val(&env),
+            ),
+        ]
+    );
 
-    // Verify different pages have different requests
-    assert_ne!(page1.get(0).unwrap().id, page2.get(0).unwrap().id);
+    // The request keeps every unit in call order, tagged with its bank, and
+    // the per-bank totals add up to the assigned volume
+    let request = client.get_request(&request_id);
+    assert_eq!(
+        request.assigned_units,
+        vec![&env, 10u64, 20u64, 11u64, 21u64]
+    );
+    assert_eq!(
+        request.assignments,
+        vec![
+            &env,
+            assigned(10, 450, &bank_a),
+            assigned(20, 450, &bank_b),
+            assigned(11, 450, &bank_a),
+            assigned(21, 300, &bank_b),
+        ]
+    );
+    let mut total_ml = 0;
+    for unit in request.assignments.iter() {
+        total_ml += unit.quantity_ml;
+    }
+    assert_eq!(from_a.total_ml + from_b.total_ml, total_ml);
 }
 
 #[test]
-fn test_query_hospital_requests_empty() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_assignment_reaching_quantity_auto_completes() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+    client.set_auto_complete(&true);
+    assert!(client.is_auto_complete());
+
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_quantity(900)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+    client.update_request_status(&request_id, &RequestStatus::InDelivery);
 
-    let hospital = setup_authorized_hospital(&env, &client);
+    let unit = |unit_id: u64| UnitAssignment {
+        unit_id,
+        blood_type: BloodType::APositive,
+        quantity_ml: 450,
+        bank_id: admin.clone(),
+    };
 
-    // Query requests for hospital with no requests
-    let requests = client.query_hospital_requests(&hospital, &None, &None, &None);
-    assert_eq!(requests.len(), 0);
+    // Short of the requested quantity: still waiting on another shipment
+    client.assign_blood_units(&request_id, &vec![&env, unit(1)]);
+    assert_eq!(count_events(&env, "request_status_changed"), 0);
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::InDelivery
+    );
+
+    // The shipment that closes the gap completes the request
+    env.ledger().set_timestamp(2000);
+    client.assign_blood_units(&request_id, &vec![&env, unit(1), unit(2)]);
+    assert_eq!(count_events(&env, "request_status_changed"), 1);
+    let event: RequestStatusChangedEvent = latest_event(&env, "request_status_changed");
+    assert_eq!(event.old_status, RequestStatus::InDelivery);
+    assert_eq!(event.new_status, RequestStatus::Completed);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Completed);
+    assert_eq!(request.fulfilled_at, Some(2000));
+    assert!(client
+        .get_requests_by_status(&RequestStatus::Completed)
+        .contains(request_id));
+    assert!(!client
+        .get_requests_by_status(&RequestStatus::InDelivery)
+        .contains(request_id));
+
+    // Later assignments do not complete it again
+    client.assign_blood_units(&request_id, &vec![&env, unit(1), unit(2), unit(3)]);
+    assert_eq!(count_events(&env, "request_status_changed"), 0);
 }
 
 #[test]
-fn test_query_pending_requests_sorted_by_urgency() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_assignment_tolerance_caps_assigned_volume() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+    assert_eq!(
+        client.get_assignment_tolerance(),
+        AssignmentTolerance::Unlimited
+    );
 
-    let patient = Address::generate(&env);
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
+    let unit = |unit_id: u64, quantity_ml: u32| UnitAssignment {
+        unit_id,
+        blood_type: BloodType::APositive,
+        quantity_ml,
+        bank_id: admin.clone(),
+    };
+    let approved_request = || {
+        let request_id = RequestFixture::new(&env)
+            .by(&admin)
+            .with_quantity(450)
+            .required_by(1000 + 86400)
+            .create(&client);
+        client.approve_request(&request_id);
+        request_id
+    };
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    // Assigned generously before any cap is configured
+    let earlier_id = approved_request();
+    client.assign_blood_units(&earlier_id, &vec![&env, unit(1, 450), unit(2, 450)]);
 
-    // Create requests with different urgencies (in non-sorted order)
-    client.create_request(
-        &admin,
-        &BloodType::OPositive,
-        &450u32,
-        &UrgencyLevel::Normal,  // Priority 1
-        &(current_time + 86400),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+    assert_eq!(
+        client.try_set_assignment_tolerance(&AssignmentTolerance::Percent(101)),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    client.set_assignment_tolerance(&AssignmentTolerance::Percent(10));
+    assert_eq!(
+        client.get_assignment_tolerance(),
+        AssignmentTolerance::Percent(10)
     );
 
-    client.create_request(
-        &admin,
-        &BloodType::BPositive,
-        &500u32,
-        &UrgencyLevel::Critical,  // Priority 3
-        &(current_time + 7200),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+    // 450 ml + 10% = exactly 495 ml is allowed
+    let request_id = approved_request();
+    client.assign_blood_units(&request_id, &vec![&env, unit(3, 450), unit(4, 45)]);
+    assert_eq!(client.get_request(&request_id).assigned_units.len(), 2);
+
+    // One unit more is not
+    let request_id = approved_request();
+    assert_eq!(
+        client.try_assign_blood_units(
+            &request_id,
+            &vec![&env, unit(5, 450), unit(6, 45), unit(7, 1)]
+        ),
+        Err(Ok(ContractError::OverAssignment))
+    );
+    assert!(client.get_request(&request_id).assigned_units.is_empty());
+
+    // An absolute cap works the same way
+    client.set_assignment_tolerance(&AssignmentTolerance::Ml(50));
+    client.assign_blood_units(&request_id, &vec![&env, unit(5, 450), unit(6, 50)]);
+    assert_eq!(
+        client.try_assign_blood_units(&request_id, &vec![&env, unit(5, 450), unit(6, 51)]),
+        Err(Ok(ContractError::OverAssignment))
     );
 
-    client.create_request(
-        &admin,
-        &BloodType::ABNegative,
-        &350u32,
-        &UrgencyLevel::Urgent,  // Priority 2
-        &(current_time + 21600),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+    // The earlier request keeps its units
+    assert_eq!(client.get_request(&earlier_id).assigned_units.len(), 2);
+}
+
+#[test]
+fn test_full_assignment_waits_for_confirmation_without_auto_complete() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+    assert!(!client.is_auto_complete());
+
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_quantity(450)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+    client.update_request_status(&request_id, &RequestStatus::InDelivery);
+
+    let units = vec![
+        &env,
+        UnitAssignment {
+            unit_id: 1,
+            blood_type: BloodType::APositive,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        },
+    ];
+    client.assign_blood_units(&request_id, &units);
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::InDelivery
     );
 
-    // Query pending requests (should be sorted by urgency)
-    let requests = client.query_pending_requests(&None, &None);
-    assert_eq!(requests.len(), 3);
+    client.complete_request(&admin, &request_id);
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Completed
+    );
+}
+
+#[test]
+fn test_assign_blood_units_rejects_duplicate_unit() {
+    let (env, admin, client, _contract_id) = setup();
+    let other_bank = Address::generate(&env);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_quantity(900)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
 
-    // Verify sorting: Critical > Urgent > Normal
-    assert_eq!(requests.get(0).unwrap().urgency, UrgencyLevel::Critical);
-    assert_eq!(requests.get(1).unwrap().urgency, UrgencyLevel::Urgent);
-    assert_eq!(requests.get(2).unwrap().urgency, UrgencyLevel::Normal);
+    // The same unit claimed by two banks would be counted twice
+    let units = vec![
+        &env,
+        UnitAssignment {
+            unit_id: 1,
+            blood_type: BloodType::APositive,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        },
+        UnitAssignment {
+            unit_id: 1,
+            blood_type: BloodType::APositive,
+            quantity_ml: 450,
+            bank_id: other_bank,
+        },
+    ];
+    let result = client.try_assign_blood_units(&request_id, &units);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+    assert!(client.get_request(&request_id).assignments.is_empty());
 }
 
 #[test]
-fn test_query_pending_requests_pagination() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_assign_compatible_unit_rejected_without_flag() {
+    let (env, admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::OPositive)
+        .with_quantity(900)
+        .required_by(current_time + 86400)
+        .create(&client);
+
+    let units = vec![
+        &env,
+        UnitAssignment {
+            unit_id: 1,
+            blood_type: BloodType::ONegative,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        },
+    ];
+    let result = client.try_assign_blood_units(&request_id, &units);
+    assert_eq!(result, Err(Ok(ContractError::IncompatibleBloodType)));
+    assert!(client.get_request(&request_id).assigned_units.is_empty());
+}
 
-    // Create 4 pending requests
-    for _ in 0..4 {
-        client.create_request(
-            &admin,
-            &BloodType::OPositive,
-            &450u32,
-            &UrgencyLevel::Urgent,
-            &(current_time + 86400),
-            &delivery_address,
-            &patient,
-            &procedure,
-            &notes,
-        );
-    }
+#[test]
+fn test_assign_compatible_unit_with_flag() {
+    let (env, admin, client, _contract_id) = setup();
 
-    // Test pagination
-    let page1 = client.query_pending_requests(&Some(2u32), &Some(0u32));
-    assert_eq!(page1.len(), 2);
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
 
-    let page2 = client.query_pending_requests(&Some(2u32), &Some(2u32));
-    assert_eq!(page2.len(), 2);
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_quantity(900)
+        .required_by(current_time + 86400)
+        .accept_compatible()
+        .create(&client);
+    client.approve_request(&request_id);
 
-    // Verify different IDs
-    assert_ne!(page1.get(0).unwrap().id, page2.get(0).unwrap().id);
+    let units = vec![
+        &env,
+        UnitAssignment {
+            unit_id: 1,
+            blood_type: BloodType::ONegative,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        },
+        UnitAssignment {
+            unit_id: 2,
+            blood_type: BloodType::ANegative,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        },
+    ];
+    client.assign_blood_units(&request_id, &units);
+    assert_eq!(client.get_request(&request_id).assigned_units.len(), 2);
+
+    // B+ can never be given to an A+ recipient
+    let units = vec![
+        &env,
+        UnitAssignment {
+            unit_id: 3,
+            blood_type: BloodType::BPositive,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        },
+    ];
+    let result = client.try_assign_blood_units(&request_id, &units);
+    assert_eq!(result, Err(Ok(ContractError::IncompatibleBloodType)));
 }
 
+// ========== Query Tests ==========
+
 #[test]
-fn test_query_requests_by_date_range() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+fn test_get_hospital_requests() {
+    let (env, _admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+    let hospital1 = authorized_hospital(&env, &client);
+    let hospital2 = authorized_hospital(&env, &client);
 
-    // Create requests at different times
-    env.ledger().set_timestamp(1000u64);
-    client.create_request(
-        &admin,
-        &BloodType::OPositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &2000u64,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
 
-    env.ledger().set_timestamp(5000u64);
-    client.create_request(
-        &admin,
-        &BloodType::BPositive,
-        &500u32,
-        &UrgencyLevel::Urgent,
-        &6000u64,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
+    let required_by = current_time + 7 * 86400;
+
+    let id1 = RequestFixture::new(&env)
+        .by(&hospital1)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+    let id2 = RequestFixture::new(&env)
+        .by(&hospital1)
+        .with_blood_type(BloodType::BPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+    let id3 = RequestFixture::new(&env)
+        .by(&hospital2)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+
+    let h1_requests = client.get_hospital_requests(&hospital1);
+    assert_eq!(h1_requests.len(), 2);
+    assert_eq!(h1_requests.get(0).unwrap(), id1);
+    assert_eq!(h1_requests.get(1).unwrap(), id2);
+
+    let h2_requests = client.get_hospital_requests(&hospital2);
+    assert_eq!(h2_requests.len(), 1);
+    assert_eq!(h2_requests.get(0).unwrap(), id3);
+}
 
-    env.ledger().set_timestamp(10000u64);
-    client.create_request(
-        &admin,
-        &BloodType::ABNegative,
-        &350u32,
-        &UrgencyLevel::Critical,
-        &11000u64,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+#[test]
+fn test_get_requests_by_department() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let other = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let er1 = RequestFixture::new(&env)
+        .by(&hospital)
+        .in_department("ER")
+        .create(&client);
+    let icu = RequestFixture::new(&env)
+        .by(&hospital)
+        .in_department("ICU")
+        .create(&client);
+    let er2 = RequestFixture::new(&env)
+        .by(&hospital)
+        .in_department("ER")
+        .create(&client);
+    let untagged = RequestFixture::new(&env).by(&hospital).create(&client);
+    RequestFixture::new(&env)
+        .by(&other)
+        .in_department("ER")
+        .create(&client);
+
+    let er = String::from_str(&env, "ER");
+    let event: RequestCreatedEvent = latest_event(&env, "request_created");
+    assert_eq!(event.department, er);
+
+    assert_eq!(
+        client.get_requests_by_department(&hospital, &er, &0, &10),
+        vec![&env, er1, er2]
     );
-
-    // Query requests created between 1000 and 6000
-    let requests = client.query_requests_by_date_range(
-        &1000u64,
-        &6000u64,
-        &None,
-        &None,
-        &None,
+    assert_eq!(
+        client.get_requests_by_department(&hospital, &String::from_str(&env, "ICU"), &0, &10),
+        vec![&env, icu]
     );
-    assert_eq!(requests.len(), 2);
-
-    // Query requests created between 5000 and 10000
-    let requests2 = client.query_requests_by_date_range(
-        &5000u64,
-        &10000u64,
-        &None,
-        &None,
-        &None,
+    assert_eq!(
+        client.get_requests_by_department(&hospital, &er, &1, &10),
+        vec![&env, er2]
+    );
+    assert_eq!(
+        client.get_requests_by_department(&hospital, &er, &5, &10),
+        vec![&env]
     );
-    assert_eq!(requests2.len(), 2);
 
-    // Query with narrow range
-    let requests3 = client.query_requests_by_date_range(
-        &4000u64,
-        &6000u64,
-        &None,
-        &None,
-        &None,
+    // Requests without a department are not indexed
+    assert_eq!(
+        client.get_request(&untagged).department,
+        String::from_str(&env, "")
+    );
+    assert_eq!(
+        client.get_requests_by_department(&hospital, &String::from_str(&env, ""), &0, &10),
+        vec![&env]
+    );
+    assert_eq!(client.get_hospital_requests(&hospital).len(), 4);
+    assert_eq!(
+        client.get_request(&icu).department,
+        String::from_str(&env, "ICU")
     );
-    assert_eq!(requests3.len(), 1);
 }
 
 #[test]
-fn test_query_requests_by_date_range_with_status_filter() {
-    let (env, admin, client, _contract_id) = create_test_contract();
-
-    let patient = Address::generate(&env);
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+fn test_get_requests_by_physician() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let other = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let smith1 = RequestFixture::new(&env)
+        .by(&hospital)
+        .ordered_by("MD-1001")
+        .create(&client);
+    let jones = RequestFixture::new(&env)
+        .by(&hospital)
+        .ordered_by("MD-2002")
+        .create(&client);
+    let smith2 = RequestFixture::new(&env)
+        .by(&hospital)
+        .ordered_by("MD-1001")
+        .create(&client);
+    RequestFixture::new(&env).by(&hospital).create(&client);
+    RequestFixture::new(&env)
+        .by(&other)
+        .ordered_by("MD-1001")
+        .create(&client);
+
+    let event: RequestCreatedEvent = latest_event(&env, "request_created");
+    assert_eq!(event.physician_id, String::from_str(&env, "MD-1001"));
+
+    let smith = String::from_str(&env, "MD-1001");
+    assert_eq!(
+        client.get_requests_by_physician(&hospital, &hospital, &smith, &0, &10),
+        vec![&env, smith1, smith2]
+    );
+    assert_eq!(
+        client.get_requests_by_physician(
+            &admin,
+            &hospital,
+            &String::from_str(&env, "MD-2002"),
+            &0,
+            &10
+        ),
+        vec![&env, jones]
+    );
+    assert_eq!(
+        client.get_requests_by_physician(&hospital, &hospital, &smith, &1, &10),
+        vec![&env, smith2]
+    );
+    assert_eq!(
+        client.get_request(&jones).physician_id,
+        String::from_str(&env, "MD-2002")
+    );
 
-    // Create requests
-    env.ledger().set_timestamp(1000u64);
-    let req1 = client.create_request(
-        &admin,
-        &BloodType::OPositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &2000u64,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+    // Another hospital can't read the index
+    assert_eq!(
+        client.try_get_requests_by_physician(&other, &hospital, &smith, &0, &10),
+        Err(Ok(ContractError::Unauthorized))
     );
+}
 
-    env.ledger().set_timestamp(2000u64);
-    client.create_request(
-        &admin,
-        &BloodType::BPositive,
-        &500u32,
-        &UrgencyLevel::Urgent,
-        &3000u64,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+#[test]
+fn test_create_request_physician_id_too_long() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let over_limit = "x".repeat(MAX_PHYSICIAN_ID_LEN as usize + 1);
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .ordered_by(&over_limit)
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::PhysicianIdTooLong)));
+}
+
+#[test]
+fn test_create_request_department_too_long() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let at_limit = "x".repeat(MAX_DEPARTMENT_LEN as usize);
+    let over_limit = "x".repeat(MAX_DEPARTMENT_LEN as usize + 1);
+
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .in_department(&over_limit)
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::DepartmentTooLong)));
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .in_department(&at_limit)
+        .create(&client);
+    assert_eq!(
+        client.get_request(&request_id).department,
+        String::from_str(&env, &at_limit)
+    );
+}
+
+#[test]
+fn test_get_requests_by_status() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let required_by = current_time + 7 * 86400;
+
+    let id1 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+    let id2 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+
+    client.approve_request(&id1);
+
+    let pending = client.get_requests_by_status(&RequestStatus::Pending);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap(), id2);
+
+    let approved = client.get_requests_by_status(&RequestStatus::Approved);
+    assert_eq!(approved.len(), 1);
+    assert_eq!(approved.get(0).unwrap(), id1);
+}
+
+#[test]
+fn test_get_requests_by_blood_type() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let required_by = current_time + 7 * 86400;
+
+    let id1 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+    let id3 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+
+    let a_pos = client.get_requests_by_blood_type(&BloodType::APositive);
+    assert_eq!(a_pos.len(), 2);
+    assert_eq!(a_pos.get(0).unwrap(), id1);
+    assert_eq!(a_pos.get(1).unwrap(), id3);
+}
+
+#[test]
+fn test_get_requests_by_urgency() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let id1 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 2 * 3600)
+        .create(&client);
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 7 * 86400)
+        .create(&client);
+    let id3 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 2 * 3600)
+        .create(&client);
+
+    let critical_requests = client.get_requests_by_urgency(&UrgencyLevel::Critical);
+    assert_eq!(critical_requests.len(), 2);
+    assert_eq!(critical_requests.get(0).unwrap(), id1);
+    assert_eq!(critical_requests.get(1).unwrap(), id3);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn test_get_request_not_found() {
+    let (_env, _admin, client, _contract_id) = setup();
+
+    client.get_request(&999);
+}
+
+#[test]
+fn test_request_not_found() {
+    let (_env, _admin, client, _contract_id) = setup();
+
+    assert_eq!(
+        client.try_get_request(&999),
+        Err(Ok(ContractError::RequestNotFound))
     );
+}
+
+// ========== Advanced Query Function Tests ==========
+
+#[test]
+fn test_get_request_by_id_exists() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::OPositive)
+        .required_by(current_time + 86400)
+        .create(&client);
+
+    let result = client.get_request_by_id(&request_id);
+    assert!(result.is_some());
+
+    let request = result.unwrap();
+    assert_eq!(request.id, request_id);
+    assert_eq!(request.blood_type, BloodType::OPositive);
+}
+
+#[test]
+fn test_get_request_by_id_non_existent() {
+    let (_env, _admin, client, _contract_id) = setup();
+
+    let result = client.get_request_by_id(&999u64);
+    assert!(result.is_none());
+}
+
+#[test]
+fn test_query_hospital_requests_with_status_filter() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let id1 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::OPositive)
+        .required_by(current_time + 86400)
+        .create(&client);
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_quantity(500)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ABNegative)
+        .with_quantity(350)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 3600)
+        .create(&client);
+
+    client.approve_request(&id1);
+
+    let all = client.query_hospital_requests(&hospital, &None, &None, &None);
+    assert_eq!(all.len(), 3);
+
+    let pending =
+        client.query_hospital_requests(&hospital, &Some(RequestStatus::Pending), &None, &None);
+    assert_eq!(pending.len(), 2);
+
+    let approved =
+        client.query_hospital_requests(&hospital, &Some(RequestStatus::Approved), &None, &None);
+    assert_eq!(approved.len(), 1);
+    assert_eq!(approved.get(0).unwrap().id, id1);
+}
+
+#[test]
+fn test_query_hospital_requests_pagination() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    for _ in 0..5 {
+        RequestFixture::new(&env)
+            .by(&hospital)
+            .with_blood_type(BloodType::OPositive)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(current_time + 86400)
+            .create(&client);
+    }
+
+    let page1 = client.query_hospital_requests(&hospital, &None, &Some(2u32), &Some(0u32));
+    assert_eq!(page1.len(), 2);
+    assert_eq!(page1.get(0).unwrap().id, 1);
+
+    let page2 = client.query_hospital_requests(&hospital, &None, &Some(2u32), &Some(2u32));
+    assert_eq!(page2.len(), 2);
+    assert_eq!(page2.get(0).unwrap().id, 3);
+
+    let page3 = client.query_hospital_requests(&hospital, &None, &Some(2u32), &Some(4u32));
+    assert_eq!(page3.len(), 1);
+    assert_eq!(page3.get(0).unwrap().id, 5);
+}
+
+#[test]
+fn test_query_hospital_requests_all() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    for _ in 0..3 {
+        RequestFixture::new(&env)
+            .by(&admin)
+            .required_by(current_time + 86400)
+            .create(&client);
+    }
+
+    let requests = client.query_hospital_requests(&admin, &None, &None, &None);
+    assert_eq!(requests.len(), 3);
+}
+
+#[test]
+fn test_query_hospital_requests_empty() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = Address::generate(&env);
+
+    let requests = client.query_hospital_requests(&hospital, &None, &None, &None);
+    assert_eq!(requests.len(), 0);
+}
+
+#[test]
+fn test_query_pending_requests_sorted_by_urgency() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::OPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
+    RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::BPositive)
+        .with_quantity(500)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 3600)
+        .create(&client);
+    RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::ABNegative)
+        .with_quantity(350)
+        .required_by(current_time + 86400)
+        .create(&client);
+
+    let pending = client.query_pending_requests(&None, &None);
+    assert_eq!(pending.len(), 3);
+
+    // Critical > Urgent > Normal
+    assert_eq!(pending.get(0).unwrap().urgency, UrgencyLevel::Critical);
+    assert_eq!(pending.get(1).unwrap().urgency, UrgencyLevel::Urgent);
+    assert_eq!(pending.get(2).unwrap().urgency, UrgencyLevel::Normal);
+}
+
+#[test]
+fn test_query_pending_requests_pagination() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    for _ in 0..4 {
+        RequestFixture::new(&env)
+            .by(&admin)
+            .with_blood_type(BloodType::OPositive)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(current_time + 86400)
+            .create(&client);
+    }
+
+    let page1 = client.query_pending_requests(&Some(2u32), &Some(0u32));
+    assert_eq!(page1.len(), 2);
+
+    let page2 = client.query_pending_requests(&Some(2u32), &Some(2u32));
+    assert_eq!(page2.len(), 2);
+
+    assert_ne!(page1.get(0).unwrap().id, page2.get(0).unwrap().id);
+}
+
+#[test]
+fn test_query_requests_by_date_range() {
+    let (env, admin, client, _contract_id) = setup();
+
+    // Create requests at different times
+    env.ledger().set_timestamp(1000u64);
+    RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::OPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(1000 + 86400)
+        .create(&client);
+
+    env.ledger().set_timestamp(5000u64);
+    RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::BPositive)
+        .with_quantity(500)
+        .required_by(5000 + 86400)
+        .create(&client);
+
+    env.ledger().set_timestamp(10000u64);
+    RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::ABNegative)
+        .with_quantity(350)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(10000 + 3600)
+        .create(&client);
+
+    let requests = client.query_requests_by_date_range(&1000u64, &6000u64, &None, &None, &None);
+    assert_eq!(requests.len(), 2);
+
+    let requests2 = client.query_requests_by_date_range(&5000u64, &10000u64, &None, &None, &None);
+    assert_eq!(requests2.len(), 2);
+
+    let requests3 = client.query_requests_by_date_range(&4000u64, &6000u64, &None, &None, &None);
+    assert_eq!(requests3.len(), 1);
+}
+
+#[test]
+fn test_query_requests_by_date_range_with_status_filter() {
+    let (env, admin, client, _contract_id) = setup();
+
+    env.ledger().set_timestamp(1000u64);
+    let req1 = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::OPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(1000 + 86400)
+        .create(&client);
+
+    env.ledger().set_timestamp(2000u64);
+    RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::BPositive)
+        .with_quantity(500)
+        .required_by(2000 + 86400)
+        .create(&client);
 
-    // Approve one request
     client.approve_request(&req1);
 
-    // Query approved requests in date range
-    let requests = client.query_requests_by_date_range(
+    let approved = client.query_requests_by_date_range(
         &1000u64,
         &2000u64,
         &Some(RequestStatus::Approved),
         &None,
         &None,
     );
-    assert_eq!(requests.len(), 1);
-    assert_eq!(requests.get(0).unwrap().status, RequestStatus::Approved);
+    assert_eq!(approved.len(), 1);
+    assert_eq!(approved.get(0).unwrap().status, RequestStatus::Approved);
 
-    // Query pending requests in date range
     let pending = client.query_requests_by_date_range(
         &1000u64,
         &3000u64,
@@ -2057,66 +3592,38 @@ fn test_query_requests_by_date_range_with_status_filter() {
 
 #[test]
 fn test_query_requests_by_urgency_and_status() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
-
-    // Create requests with different urgencies
-    let req1 = client.create_request(
-        &admin,
-        &BloodType::OPositive,
-        &450u32,
-        &UrgencyLevel::Critical,
-        &(current_time + 7200),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
-
-    client.create_request(
-        &admin,
-        &BloodType::BPositive,
-        &500u32,
-        &UrgencyLevel::Critical,
-        &(current_time + 7200),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
-
-    client.create_request(
-        &admin,
-        &BloodType::ABNegative,
-        &350u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 21600),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
+    let req1 = RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::OPositive)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 3600)
+        .create(&client);
+    RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::BPositive)
+        .with_quantity(500)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 3600)
+        .create(&client);
+    RequestFixture::new(&env)
+        .by(&admin)
+        .with_blood_type(BloodType::ABNegative)
+        .with_quantity(350)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 86400)
+        .create(&client);
 
-    // Approve one critical request
     client.approve_request(&req1);
 
-    // Query all critical requests (no status filter)
-    let critical_all = client.query_requests_by_urgency_and_status(
-        &UrgencyLevel::Critical,
-        &None,
-        &None,
-        &None,
-    );
-    assert_eq!(critical_all.len(), 2);
+    let critical =
+        client.query_requests_by_urgency_and_status(&UrgencyLevel::Critical, &None, &None, &None);
+    assert_eq!(critical.len(), 2);
 
-    // Query critical pending requests only
     let critical_pending = client.query_requests_by_urgency_and_status(
         &UrgencyLevel::Critical,
         &Some(RequestStatus::Pending),
@@ -2125,7 +3632,6 @@ fn test_query_requests_by_urgency_and_status() {
     );
     assert_eq!(critical_pending.len(), 1);
 
-    // Query critical approved requests only
     let critical_approved = client.query_requests_by_urgency_and_status(
         &UrgencyLevel::Critical,
         &Some(RequestStatus::Approved),
@@ -2133,78 +3639,3058 @@ fn test_query_requests_by_urgency_and_status() {
         &None,
     );
     assert_eq!(critical_approved.len(), 1);
+    assert_eq!(critical_approved.get(0).unwrap().id, req1);
 }
 
 #[test]
 fn test_pagination_edge_cases() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
-
-    // Create 3 requests
     for _ in 0..3 {
-        client.create_request(
-            &admin,
-            &BloodType::OPositive,
-            &450u32,
-            &UrgencyLevel::Normal,
-            &(current_time + 86400),
-            &delivery_address,
-            &patient,
-            &procedure,
-            &notes,
-        );
+        RequestFixture::new(&env)
+            .by(&admin)
+            .with_blood_type(BloodType::OPositive)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(current_time + 86400)
+            .create(&client);
     }
 
-    // Test: offset beyond length returns empty
-    let result = client.query_pending_requests(&Some(10u32), &Some(10u32));
-    assert_eq!(result.len(), 0);
+    // Offset beyond length
+    let empty = client.query_hospital_requests(&admin, &None, &Some(10u32), &Some(10u32));
+    assert_eq!(empty.len(), 0);
 
-    // Test: limit =0 returns no results (capped to 0)
-    let result2 = client.query_pending_requests(&Some(0u32), &Some(0u32));
-    assert_eq!(result2.len(), 0);
+    // Limit larger than available
+    let all = client.query_hospital_requests(&admin, &None, &Some(100u32), &Some(0u32));
+    assert_eq!(all.len(), 3);
 
-    // Test: large limit returns all available
-    let result3 = client.query_pending_requests(&Some(100u32), &Some(0u32));
-    assert_eq!(result3.len(), 3);
+    // Zero limit
+    let none = client.query_hospital_requests(&admin, &None, &Some(0u32), &Some(0u32));
+    assert_eq!(none.len(), 0);
 }
 
 #[test]
 fn test_max_query_limit_enforcement() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, admin, client, _contract_id) = setup();
 
-    let patient = Address::generate(&env);
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
-
-    // Create 10 requests
-    for _ in 0..10 {
-        client.create_request(
-            &admin,
-            &BloodType::OPositive,
-            &450u32,
-            &UrgencyLevel::Normal,
-            &(current_time + 86400),
-            &delivery_address,
-            &patient,
-            &procedure,
-            &notes,
-        );
+    for _ in 0..5 {
+        RequestFixture::new(&env)
+            .by(&admin)
+            .with_blood_type(BloodType::OPositive)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(current_time + 86400)
+            .create(&client);
     }
 
-    // Request with limit > MAX_QUERY_LIMIT should be capped to MAX_QUERY_LIMIT
-    // MAX_QUERY_LIMIT is 200, so requesting 300 should return at most 10 (all available)
-    let result = client.query_pending_requests(&Some(300u32), &Some(0u32));
-    assert_eq!(result.len(), 10);
+    // Limit above MAX_QUERY_LIMIT is clamped rather than rejected
+    let requests = client.query_hospital_requests(&admin, &None, &Some(1000u32), &None);
+    assert_eq!(requests.len(), 5);
+}
+
+// ========== Priority Queue Tests ==========
+
+#[test]
+fn test_pending_queue_orders_out_of_order_insertions() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let normal_late = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 5 * 86400)
+        .create(&client);
+    let critical_late = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 6 * 3600)
+        .create(&client);
+    let normal_early = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
+    let critical_early = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 2 * 3600)
+        .create(&client);
+    let urgent = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(current_time + 86400)
+        .create(&client);
+
+    let queue = client.get_pending_queue(&None);
+    assert_eq!(
+        queue,
+        vec![
+            &env,
+            critical_early,
+            critical_late,
+            urgent,
+            normal_early,
+            normal_late
+        ]
+    );
+
+    // Prefix read
+    let top = client.get_pending_queue(&Some(2u32));
+    assert_eq!(top, vec![&env, critical_early, critical_late]);
+
+    // Scores are persisted on the requests
+    let request = client.get_request(&critical_early);
+    assert_eq!(
+        request.priority_score,
+        BloodRequest::compute_priority_score(UrgencyLevel::Critical, current_time + 2 * 3600)
+    );
+    assert!(request.priority_score > client.get_request(&critical_late).priority_score);
 }
 
+#[test]
+fn test_pending_queue_escalation_reinserts() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let urgent = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(current_time + 86400)
+        .create(&client);
+    let normal = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
+    let critical = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 3 * 3600)
+        .create(&client);
+
+    assert_eq!(
+        client.get_pending_queue(&None),
+        vec![&env, critical, urgent, normal]
+    );
+
+    client.escalate_request(&hospital, &normal, &UrgencyLevel::Critical);
+
+    // Same urgency as `critical` but later deadline
+    assert_eq!(
+        client.get_pending_queue(&None),
+        vec![&env, critical, normal, urgent]
+    );
+
+    let request = client.get_request(&normal);
+    assert_eq!(request.urgency, UrgencyLevel::Critical);
+    assert_eq!(
+        client.get_requests_by_urgency(&UrgencyLevel::Normal).len(),
+        0
+    );
+    assert_eq!(
+        client
+            .get_requests_by_urgency(&UrgencyLevel::Critical)
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_pending_queue_deadline_extension_reinserts() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let first = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
+    let second = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 3 * 86400)
+        .create(&client);
+
+    assert_eq!(client.get_pending_queue(&None), vec![&env, first, second]);
+
+    client.extend_deadline(&hospital, &first, &(current_time + 4 * 86400));
+
+    assert_eq!(client.get_pending_queue(&None), vec![&env, second, first]);
+    assert_eq!(
+        client.get_request(&first).required_by,
+        current_time + 4 * 86400
+    );
+}
+
+#[test]
+fn test_pending_queue_drops_approved_and_cancelled() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let id1 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
+    let id2 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 3 * 86400)
+        .create(&client);
+    let id3 = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 4 * 86400)
+        .create(&client);
+
+    client.approve_request(&id1);
+    client.cancel_request(&id3, &hospital);
+
+    assert_eq!(client.get_pending_queue(&None), vec![&env, id2]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_escalate_request_not_higher() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(current_time + 86400)
+        .create(&client);
+
+    client.escalate_request(&hospital, &request_id, &UrgencyLevel::Normal);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_extend_deadline_earlier() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 3 * 86400)
+        .create(&client);
+
+    client.extend_deadline(&hospital, &request_id, &(current_time + 2 * 86400));
+}
+
+#[test]
+fn test_get_next_pending_request_follows_queue() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    assert_eq!(client.get_next_pending_request(), None);
+    assert_eq!(client.peek_next_pending_detail(), None);
+
+    let normal = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
+    assert_eq!(client.get_next_pending_request(), Some(normal));
+
+    let urgent = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .required_by(current_time + 86400)
+        .create(&client);
+    assert_eq!(client.get_next_pending_request(), Some(urgent));
+
+    let critical_late = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 6 * 3600)
+        .create(&client);
+    let critical_early = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 2 * 3600)
+        .create(&client);
+    assert_eq!(client.get_next_pending_request(), Some(critical_early));
+
+    let detail = client.peek_next_pending_detail().unwrap();
+    assert_eq!(detail.id, critical_early);
+    assert_eq!(detail.urgency, UrgencyLevel::Critical);
+
+    // Approving the head moves on to the next in line
+    client.approve_request(&critical_early);
+    assert_eq!(client.get_next_pending_request(), Some(critical_late));
+
+    client.approve_request(&critical_late);
+    assert_eq!(client.get_next_pending_request(), Some(urgent));
+
+    client.approve_request(&urgent);
+    assert_eq!(client.get_next_pending_request(), Some(normal));
+
+    client.approve_request(&normal);
+    assert_eq!(client.get_next_pending_request(), None);
+}
+
+#[test]
+fn test_get_next_pending_request_skips_overdue() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(current_time + 2 * 3600)
+        .create(&client);
+    let normal = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
+
+    // Critical request's deadline passes before any sweep
+    env.ledger().set_timestamp(current_time + 3 * 3600);
+    assert_eq!(client.get_next_pending_request(), Some(normal));
+    assert_eq!(client.peek_next_pending_detail().unwrap().id, normal);
+}
+
+// ========== Allocation Proposal Tests ==========
+
+fn candidate(unit_id: u64, blood_type: BloodType, expiration_timestamp: u64) -> CandidateUnit {
+    CandidateUnit {
+        unit_id,
+        blood_type,
+        quantity_ml: 450,
+        expiration_timestamp,
+    }
+}
+
+#[test]
+fn test_propose_allocation_contended_unit_goes_to_critical() {
+    let (env, _admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+    let hospital = authorized_hospital(&env, &client);
+
+    // The Normal request is older and would take O- as a compatible match
+    let normal = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::OPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(1000 + 2 * 86400)
+        .accept_compatible()
+        .create(&client);
+    let critical = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(1000 + 2 * 3600)
+        .create(&client);
+
+    let expires = 1000 + 10 * 86400;
+    let contended = vec![&env, candidate(7, BloodType::ONegative, expires)];
+    assert_eq!(
+        client.propose_allocation(&contended, &10),
+        vec![&env, (critical, vec![&env, 7u64])]
+    );
+
+    // With an O+ unit on offer as well, both are covered
+    let both = vec![
+        &env,
+        candidate(7, BloodType::ONegative, expires),
+        candidate(8, BloodType::OPositive, expires),
+    ];
+    assert_eq!(
+        client.propose_allocation(&both, &10),
+        vec![
+            &env,
+            (critical, vec![&env, 7u64]),
+            (normal, vec![&env, 8u64])
+        ]
+    );
+
+    // Nothing is reserved by a proposal
+    assert_eq!(client.get_request(&critical).status, RequestStatus::Pending);
+    assert!(client.get_request(&critical).assigned_units.is_empty());
+}
+
+#[test]
+fn test_propose_allocation_tie_breaks_and_constraints() {
+    let (env, _admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+    let hospital = authorized_hospital(&env, &client);
+    let required_by = 1000 + 86400;
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(required_by)
+        .create(&client);
+    let large = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(1350)
+        .required_by(required_by)
+        .create(&client);
+
+    let mut units = vec![
+        &env,
+        // Expires before the deadline
+        candidate(1, BloodType::APositive, required_by),
+        candidate(5, BloodType::APositive, required_by + 10 * 86400),
+        candidate(3, BloodType::APositive, required_by + 5 * 86400),
+        candidate(4, BloodType::APositive, required_by + 5 * 86400),
+        // Listed twice
+        candidate(3, BloodType::APositive, required_by + 5 * 86400),
+        // Not acceptable without accept_compatible
+        candidate(9, BloodType::ONegative, required_by + 5 * 86400),
+    ];
+
+    // Soonest expiring first, lowest ID on a tie; the large request cannot
+    // be fully covered by what is left and is left out
+    assert_eq!(
+        client.propose_allocation(&units, &10),
+        vec![&env, (request_id, vec![&env, 3u64])]
+    );
+
+    // With one more unit the large request is covered too, unless only
+    // the first request is visited
+    units.push_back(candidate(6, BloodType::APositive, required_by + 20 * 86400));
+    assert_eq!(
+        client.propose_allocation(&units, &10),
+        vec![
+            &env,
+            (request_id, vec![&env, 3u64]),
+            (large, vec![&env, 4u64, 5, 6])
+        ]
+    );
+    assert_eq!(
+        client.propose_allocation(&units, &1),
+        vec![&env, (request_id, vec![&env, 3u64])]
+    );
+}
+
+// ========== Expiry Sweep Tests ==========
+
+#[test]
+fn test_expire_overdue_requests_records_reason() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let pending = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
+    let approved = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 2 * 86400)
+        .create(&client);
+    let still_open = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(current_time + 5 * 86400)
+        .create(&client);
+    client.approve_request(&approved);
+
+    env.ledger().set_timestamp(current_time + 3 * 86400);
+    assert_eq!(client.expire_overdue_requests(&None), 2);
+
+    assert_eq!(client.get_request(&pending).status, RequestStatus::Expired);
+    assert_eq!(client.get_request(&approved).status, RequestStatus::Expired);
+    assert_eq!(
+        client.get_request(&still_open).status,
+        RequestStatus::Pending
+    );
+
+    assert_eq!(
+        client.get_expiry_reason(&pending),
+        Some(ExpiryReason::NotApproved)
+    );
+    assert_eq!(
+        client.get_expiry_reason(&approved),
+        Some(ExpiryReason::NotFulfilled)
+    );
+    assert_eq!(client.get_expiry_reason(&still_open), None);
+
+    assert_eq!(client.get_pending_queue(&None), vec![&env, still_open]);
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Expired).len(),
+        2
+    );
+
+    // Nothing left to expire
+    assert_eq!(client.expire_overdue_requests(&None), 0);
+}
+
+#[test]
+fn test_expiry_sweep_summary_flags_remaining_work() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let current_time = 1000000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let mut ids = Vec::new(&env);
+    for _ in 0..3 {
+        ids.push_back(
+            RequestFixture::new(&env)
+                .by(&hospital)
+                .with_urgency(UrgencyLevel::Normal)
+                .required_by(current_time + 86400)
+                .create(&client),
+        );
+    }
+
+    let swept_at = current_time + 2 * 86400;
+    env.ledger().set_timestamp(swept_at);
+
+    assert_eq!(client.expire_overdue_requests(&Some(2)), 2);
+    assert_eq!(
+        latest_event::<ExpirySweepCompletedEvent>(&env, "expiry_sweep_completed"),
+        ExpirySweepCompletedEvent {
+            expired_count: 2,
+            first_id: ids.get(0).unwrap(),
+            last_id: ids.get(1).unwrap(),
+            more_remaining: true,
+            swept_at,
+        }
+    );
+
+    assert_eq!(client.expire_overdue_requests(&Some(2)), 1);
+    assert_eq!(
+        latest_event::<ExpirySweepCompletedEvent>(&env, "expiry_sweep_completed"),
+        ExpirySweepCompletedEvent {
+            expired_count: 1,
+            first_id: ids.get(2).unwrap(),
+            last_id: ids.get(2).unwrap(),
+            more_remaining: false,
+            swept_at,
+        }
+    );
+}
+
+#[test]
+fn test_unfulfilled_stats_across_epoch_boundary() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    // Start a few days before the end of epoch 1
+    let epoch = 1u64;
+    let start = (epoch + 1) * SECONDS_PER_EPOCH - 5 * 86400;
+    env.ledger().set_timestamp(start);
+
+    for blood_type in [
+        BloodType::APositive,
+        BloodType::APositive,
+        BloodType::ONegative,
+    ] {
+        RequestFixture::new(&env)
+            .by(&hospital)
+            .with_blood_type(blood_type)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(start + 2 * 86400)
+            .create(&client);
+    }
+    // Deadline falls in the next epoch
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(start + 10 * 86400)
+        .create(&client);
+
+    // Sweep inside epoch 1
+    env.ledger().set_timestamp(start + 3 * 86400);
+    assert_eq!(client.expire_overdue_requests(&None), 3);
+
+    assert_eq!(
+        client.get_unfulfilled_stats(&BloodType::APositive, &epoch),
+        2
+    );
+    assert_eq!(
+        client.get_unfulfilled_stats(&BloodType::ONegative, &epoch),
+        1
+    );
+
+    // Sweep after the boundary counts against epoch 2 only
+    env.ledger().set_timestamp(start + 11 * 86400);
+    assert_eq!(client.expire_overdue_requests(&None), 1);
+
+    assert_eq!(
+        client.get_unfulfilled_stats(&BloodType::ONegative, &epoch),
+        1
+    );
+    assert_eq!(
+        client.get_unfulfilled_stats(&BloodType::ONegative, &(epoch + 1)),
+        1
+    );
+    assert_eq!(
+        client.get_unfulfilled_stats(&BloodType::APositive, &(epoch + 1)),
+        0
+    );
+}
+
+#[test]
+fn test_sla_breaches_count_expired_approvals_only() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let other = authorized_hospital(&env, &client);
+
+    let epoch = 1u64;
+    let start = epoch * SECONDS_PER_EPOCH + 86400;
+    env.ledger().set_timestamp(start);
+
+    let breached = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(start + 2 * 86400)
+        .create(&client);
+    let completed = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(start + 2 * 86400)
+        .create(&client);
+    let cancelled = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(start + 2 * 86400)
+        .create(&client);
+    let never_approved = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(start + 2 * 86400)
+        .create(&client);
+
+    client.approve_request(&breached);
+    client.approve_request(&completed);
+    client.approve_request(&cancelled);
+
+    client.update_request_status(&completed, &RequestStatus::InDelivery);
+    client.complete_request(&hospital, &completed);
+    client.cancel_request(&cancelled, &hospital);
+
+    env.ledger().set_timestamp(start + 3 * 86400);
+    assert_eq!(client.expire_overdue_requests(&None), 2);
+
+    assert!(client.get_request(&breached).sla_breached);
+    assert!(!client.get_request(&completed).sla_breached);
+    assert!(!client.get_request(&cancelled).sla_breached);
+    assert!(!client.get_request(&never_approved).sla_breached);
+
+    let summary = client
+        .get_hospital_requests_summary(&hospital)
+        .iter()
+        .find(|s| s.id == breached)
+        .unwrap();
+    assert!(summary.sla_breached);
+
+    assert_eq!(client.get_sla_breaches(&hospital, &epoch), 1);
+    assert_eq!(client.get_hospital_score(&hospital).sla_breaches, 1);
+    assert_eq!(client.get_sla_breaches(&other, &epoch), 0);
+    assert_eq!(client.get_sla_breaches(&hospital, &(epoch + 1)), 0);
+    assert_eq!(client.get_total_sla_breaches(&epoch), 1);
+}
+
+// ========== Demand Stats Tests ==========
+
+#[test]
+fn test_demand_split_across_epochs() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+
+    let first_month = 1000u64;
+    env.ledger().set_timestamp(first_month);
+    for quantity in [450u32, 900] {
+        RequestFixture::new(&env)
+            .by(&hospital)
+            .with_quantity(quantity)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(first_month + 2 * 86400)
+            .create(&client);
+    }
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_quantity(300)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(first_month + 2 * 86400)
+        .create(&client);
+
+    let second_month = SECONDS_PER_EPOCH + 1000;
+    env.ledger().set_timestamp(second_month);
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(1200)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(second_month + 2 * 86400)
+        .create(&client);
+
+    let stats = client.get_demand(&BloodType::APositive, &0);
+    assert_eq!(stats.requests, 2);
+    assert_eq!(stats.total_ml, 1350);
+
+    let stats = client.get_demand(&BloodType::APositive, &1);
+    assert_eq!(stats.requests, 1);
+    assert_eq!(stats.total_ml, 1200);
+
+    let stats = client.get_demand(&BloodType::ONegative, &0);
+    assert_eq!(stats.requests, 1);
+    assert_eq!(stats.total_ml, 300);
+
+    assert_eq!(
+        client.get_demand(&BloodType::ONegative, &1),
+        DemandStats::default()
+    );
+}
+
+#[test]
+fn test_demand_cancellation_same_epoch_decrements() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let kept = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(1000 + 2 * 86400)
+        .create(&client);
+    let cancelled = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_quantity(600)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(1000 + 2 * 86400)
+        .create(&client);
+
+    client.cancel_request(&cancelled, &hospital);
+
+    let stats = client.get_demand(&BloodType::BPositive, &0);
+    assert_eq!(stats.requests, 1);
+    assert_eq!(stats.total_ml, 450);
+    assert_eq!(client.get_request(&kept).status, RequestStatus::Pending);
+}
+
+#[test]
+fn test_demand_cancellation_after_epoch_is_final() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let created_at = SECONDS_PER_EPOCH - 86400;
+    env.ledger().set_timestamp(created_at);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(created_at + 7 * 86400)
+        .create(&client);
+
+    // Cancel once the next epoch has started
+    env.ledger().set_timestamp(SECONDS_PER_EPOCH + 10);
+    client.cancel_request(&request_id, &hospital);
+
+    let stats = client.get_demand(&BloodType::BPositive, &0);
+    assert_eq!(stats.requests, 1);
+    assert_eq!(stats.total_ml, 450);
+}
+
+#[test]
+fn test_hospital_report_completion_counts_in_completion_epoch() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let created_at = SECONDS_PER_EPOCH - 86400;
+    env.ledger().set_timestamp(created_at);
+
+    let completed = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(900)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(created_at + 2 * 86400)
+        .create(&client);
+    let cancelled = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(created_at + 2 * 86400)
+        .create(&client);
+    client.cancel_request(&cancelled, &hospital);
+
+    client.approve_request(&completed);
+    let units = vec![
+        &env,
+        UnitAssignment {
+            unit_id: 1,
+            blood_type: BloodType::APositive,
+            quantity_ml: 450,
+            bank_id: hospital.clone(),
+        },
+        UnitAssignment {
+            unit_id: 2,
+            blood_type: BloodType::APositive,
+            quantity_ml: 400,
+            bank_id: hospital.clone(),
+        },
+    ];
+    client.assign_blood_units(&completed, &units);
+    client.update_request_status(&completed, &RequestStatus::InDelivery);
+
+    // Delivered once the next month has started
+    env.ledger().set_timestamp(SECONDS_PER_EPOCH + 10);
+    client.complete_request(&hospital, &completed);
+
+    assert_eq!(
+        client.get_hospital_monthly_report(&hospital, &hospital, &0),
+        HospitalReport {
+            created: 2,
+            completed: 0,
+            cancelled: 1,
+            requested_ml: 1350,
+            received_ml: 0,
+        }
+    );
+    assert_eq!(
+        client.get_hospital_monthly_report(&hospital, &hospital, &1),
+        HospitalReport {
+            created: 0,
+            completed: 1,
+            cancelled: 0,
+            requested_ml: 0,
+            received_ml: 850,
+        }
+    );
+}
+
+#[test]
+fn test_hospital_report_readable_by_hospital_and_admin() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let other = authorized_hospital(&env, &client);
+    RequestFixture::new(&env).by(&hospital).create(&client);
+
+    let report = client.get_hospital_monthly_report(&admin, &hospital, &0);
+    assert_eq!(report.created, 1);
+    assert_eq!(report.requested_ml, 450);
+    assert_eq!(
+        client.try_get_hospital_monthly_report(&other, &hospital, &0),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_hospital_score_counts_completions_and_late_cancellations() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let hospital = authorized_hospital(&env, &client);
+    assert_eq!(
+        client.get_hospital_score(&hospital),
+        HospitalScore::default()
+    );
+
+    // A clean completion
+    let completed = RequestFixture::new(&env).by(&hospital).create(&client);
+    client.approve_request(&completed);
+    let units = vec![
+        &env,
+        UnitAssignment {
+            unit_id: 1,
+            blood_type: BloodType::APositive,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        },
+    ];
+    client.assign_blood_units(&completed, &units);
+    client.update_request_status(&completed, &RequestStatus::InDelivery);
+    client.complete_request(&hospital, &completed);
+
+    // Withdrawing a pending request is not held against the hospital
+    let withdrawn = RequestFixture::new(&env).by(&hospital).create(&client);
+    client.cancel_request(&withdrawn, &hospital);
+
+    // Cancelled after approval
+    let cancelled = RequestFixture::new(&env).by(&hospital).create(&client);
+    client.approve_request(&cancelled);
+    client.cancel_request(&cancelled, &admin);
+
+    assert_eq!(
+        client.get_hospital_score(&hospital),
+        HospitalScore {
+            completed: 1,
+            cancelled_after_approval: 1,
+            disputes_opened: 0,
+            sla_breaches: 0,
+            reliability_bps: Some(5_000),
+        }
+    );
+}
+
+// ========== Delivery ETA Tests ==========
+
+/// Create a request at t=1000 and move it to InDelivery with a courier
+fn request_in_delivery(
+    env: &Env,
+    client: &RequestContractClient<'_>,
+    courier: &Address,
+) -> (Address, u64) {
+    let hospital = authorized_hospital(env, client);
+    env.ledger().set_timestamp(1000);
+    let request_id = RequestFixture::new(env).by(&hospital).create(client);
+    client.update_request_status(&request_id, &RequestStatus::Approved);
+    client.assign_courier(&request_id, courier);
+    client.update_request_status(&request_id, &RequestStatus::InDelivery);
+    (hospital, request_id)
+}
+
+#[test]
+fn test_assign_courier() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let courier = Address::generate(&env);
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let request_id = RequestFixture::new(&env).by(&hospital).create(&client);
+
+    // Not before the request is approved
+    let result = client.try_assign_courier(&request_id, &courier);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+
+    client.update_request_status(&request_id, &RequestStatus::Approved);
+    client.assign_courier(&request_id, &courier);
+
+    let event: CourierAssignedEvent = latest_event(&env, "courier_assigned");
+    assert_eq!(event.request_id, request_id);
+    assert_eq!(event.courier, courier);
+    assert_eq!(event.assigned_at, 1000);
+    assert_eq!(client.get_request(&request_id).courier, Some(courier));
+}
+
+#[test]
+fn test_contact_readable_only_by_hospital_admin_and_courier() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let courier = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_contact("+234 801 234 5678")
+        .create(&client);
+    let contact = Some(String::from_str(&env, "+234 801 234 5678"));
+
+    let created: RequestCreatedEvent = latest_event(&env, "request_created");
+    assert_eq!(created.contact, contact);
+
+    // The courier is not assigned yet
+    assert_eq!(
+        client.try_get_contact(&courier, &request_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(client.get_contact(&hospital, &request_id), contact);
+    assert_eq!(client.get_contact(&admin, &request_id), contact);
+
+    client.approve_request(&request_id);
+    client.assign_courier(&request_id, &courier);
+    assert_eq!(client.get_contact(&courier, &request_id), contact);
+    assert_eq!(
+        client.try_get_contact(&stranger, &request_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.update_request_status(&request_id, &RequestStatus::InDelivery);
+    let started: DeliveryStartedEvent = latest_event(&env, "delivery_started");
+    assert_eq!(
+        started,
+        DeliveryStartedEvent {
+            request_id,
+            courier: Some(courier.clone()),
+            delivery_address: client.get_request(&request_id).delivery_address,
+            contact,
+            started_at: 1000,
+        }
+    );
+}
+
+#[test]
+fn test_update_contact_validation() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let request_id = RequestFixture::new(&env).by(&hospital).create(&client);
+    assert_eq!(client.get_contact(&hospital, &request_id), None);
+
+    let blank = Some(String::from_str(&env, "   "));
+    assert_eq!(
+        client.try_update_contact(&hospital, &request_id, &blank),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    let long = Some(String::from_str(
+        &env,
+        &"9".repeat(MAX_CONTACT_LEN as usize + 1),
+    ));
+    assert_eq!(
+        client.try_update_contact(&hospital, &request_id, &long),
+        Err(Ok(ContractError::ContactTooLong))
+    );
+    let other = authorized_hospital(&env, &client);
+    let phone = Some(String::from_str(&env, "555-0100"));
+    assert_eq!(
+        client.try_update_contact(&other, &request_id, &phone),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    client.update_contact(&hospital, &request_id, &phone);
+    assert_eq!(client.get_contact(&hospital, &request_id), phone);
+    client.update_contact(&hospital, &request_id, &None);
+    assert_eq!(client.get_contact(&hospital, &request_id), None);
+
+    client.cancel_request(&request_id, &hospital);
+    assert_eq!(
+        client.try_update_contact(&hospital, &request_id, &phone),
+        Err(Ok(ContractError::RequestAlreadyTerminal))
+    );
+}
+
+#[test]
+fn test_set_delivery_eta_by_courier_and_admin() {
+    let (env, admin, client, _contract_id) = setup();
+
+    let courier = Address::generate(&env);
+    let (_hospital, request_id) = request_in_delivery(&env, &client, &courier);
+
+    client.set_delivery_eta(&courier, &request_id, &2800);
+    let event: EtaUpdatedEvent = latest_event(&env, "eta_updated");
+    assert_eq!(
+        event,
+        EtaUpdatedEvent {
+            request_id,
+            old_eta: None,
+            new_eta: 2800,
+            updated_by: courier.clone(),
+            updated_at: 1000,
+        }
+    );
+    assert_eq!(client.get_request(&request_id).delivery_eta, Some(2800));
+
+    // Repeated updates carry the previous value
+    env.ledger().set_timestamp(2000);
+    client.set_delivery_eta(&admin, &request_id, &3400);
+    let event: EtaUpdatedEvent = latest_event(&env, "eta_updated");
+    assert_eq!(event.old_eta, Some(2800));
+    assert_eq!(event.new_eta, 3400);
+    assert_eq!(event.updated_by, admin);
+    assert_eq!(client.get_request(&request_id).delivery_eta, Some(3400));
+}
+
+#[test]
+fn test_set_delivery_eta_rejects_other_callers() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let courier = Address::generate(&env);
+    let (hospital, request_id) = request_in_delivery(&env, &client, &courier);
+
+    for caller in [hospital, Address::generate(&env)] {
+        let result = client.try_set_delivery_eta(&caller, &request_id, &2800);
+        assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    }
+    assert_eq!(client.get_request(&request_id).delivery_eta, None);
+}
+
+#[test]
+fn test_set_delivery_eta_validates_time() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let courier = Address::generate(&env);
+    let (_hospital, request_id) = request_in_delivery(&env, &client, &courier);
+
+    for eta in [999, 1000, 1000 + MAX_DELIVERY_ETA_SECONDS + 1] {
+        let result = client.try_set_delivery_eta(&courier, &request_id, &eta);
+        assert_eq!(result, Err(Ok(ContractError::InvalidTimestamp)));
+    }
+
+    client.set_delivery_eta(&courier, &request_id, &(1000 + MAX_DELIVERY_ETA_SECONDS));
+}
+
+#[test]
+fn test_set_delivery_eta_requires_in_delivery() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let courier = Address::generate(&env);
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let request_id = RequestFixture::new(&env).by(&hospital).create(&client);
+    client.update_request_status(&request_id, &RequestStatus::Approved);
+    client.assign_courier(&request_id, &courier);
+
+    let result = client.try_set_delivery_eta(&courier, &request_id, &2800);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+    let result = client.try_set_delivery_eta(&courier, &999, &2800);
+    assert_eq!(result, Err(Ok(ContractError::RequestNotFound)));
+}
+
+#[test]
+fn test_completion_clears_delivery_eta() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let courier = Address::generate(&env);
+    let (hospital, request_id) = request_in_delivery(&env, &client, &courier);
+    client.set_delivery_eta(&courier, &request_id, &2800);
+
+    client.complete_request(&hospital, &request_id);
+    assert_eq!(client.get_request(&request_id).delivery_eta, None);
+
+    let result = client.try_set_delivery_eta(&courier, &request_id, &2900);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+    assert_eq!(client.get_request(&request_id).delivery_eta, None);
+}
+
+// ========== Rejection Reason Tests ==========
+
+#[test]
+fn test_reject_request_records_reason() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let request_id = RequestFixture::new(&env).by(&hospital).create(&client);
+
+    client.reject_request(&request_id, &RejectionReason::InsufficientStock);
+
+    let event: RequestRejectedEvent = latest_event(&env, "request_rejected");
+    assert_eq!(event.request_id, request_id);
+    assert_eq!(event.hospital_id, hospital);
+    assert_eq!(event.reason, RejectionReason::InsufficientStock);
+    assert_eq!(event.rejected_at, 1000);
+
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Rejected
+    );
+    assert_eq!(
+        client.get_rejection_reason(&request_id),
+        Some(RejectionReason::InsufficientStock)
+    );
+}
+
+#[test]
+fn test_rejection_breakdown_counts_reasons() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    for reason in [
+        RejectionReason::DuplicateRequest,
+        RejectionReason::HospitalQuotaExceeded,
+        RejectionReason::DuplicateRequest,
+    ] {
+        let request_id = RequestFixture::new(&env).by(&hospital).create(&client);
+        client.reject_request(&request_id, &reason);
+        assert_eq!(count_events(&env, "request_rejected"), 1);
+    }
+
+    let breakdown = client.get_rejection_breakdown(&0);
+    assert_eq!(breakdown.len(), 2);
+    assert_eq!(breakdown.get(RejectionReason::DuplicateRequest), Some(2));
+    assert_eq!(
+        breakdown.get(RejectionReason::HospitalQuotaExceeded),
+        Some(1)
+    );
+    assert_eq!(breakdown.get(RejectionReason::InsufficientStock), None);
+}
+
+#[test]
+fn test_status_update_rejection_counts_as_other() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let request_id = RequestFixture::new(&env).by(&hospital).create(&client);
+
+    client.update_request_status(&request_id, &RequestStatus::Rejected);
+
+    let event: RequestRejectedEvent = latest_event(&env, "request_rejected");
+    assert_eq!(event.reason, RejectionReason::Other);
+    assert_eq!(
+        client.get_rejection_reason(&request_id),
+        Some(RejectionReason::Other)
+    );
+
+    let mut expected = Map::new(&env);
+    expected.set(RejectionReason::Other, 1u32);
+    assert_eq!(client.get_rejection_breakdown(&0), expected);
+}
+
+#[test]
+fn test_reject_request_requires_pending() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let request_id = RequestFixture::new(&env).by(&hospital).create(&client);
+    client.update_request_status(&request_id, &RequestStatus::Approved);
+
+    let result =
+        client.try_reject_request(&request_id, &RejectionReason::InvalidClinicalJustification);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+    assert_eq!(client.get_rejection_reason(&request_id), None);
+    assert_eq!(client.get_rejection_breakdown(&0).len(), 0);
+
+    let result = client.try_reject_request(&999, &RejectionReason::Other);
+    assert_eq!(result, Err(Ok(ContractError::RequestNotFound)));
+}
+
+#[test]
+fn test_rejection_breakdown_is_per_epoch() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let later = SECONDS_PER_EPOCH + 1000;
+    env.ledger().set_timestamp(later);
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(later + 86400)
+        .create(&client);
+
+    client.reject_request(&request_id, &RejectionReason::InsufficientStock);
+
+    assert_eq!(client.get_rejection_breakdown(&0).len(), 0);
+    assert_eq!(
+        client
+            .get_rejection_breakdown(&1)
+            .get(RejectionReason::InsufficientStock),
+        Some(1)
+    );
+}
+
+// ========== Inventory Integration Tests ==========
+
+/// Register an inventory contract wired to the request contract in both directions
+fn setup_inventory<'a>(
+    env: &Env,
+    admin: &Address,
+    client: &RequestContractClient<'_>,
+    request_contract_id: &Address,
+) -> InventoryContractClient<'a> {
+    let inventory_id = env.register(InventoryContract, ());
+    let inventory = InventoryContractClient::new(env, &inventory_id);
+    inventory.initialize(admin, &vec![env], &0);
+    inventory.set_request_contract(request_contract_id);
+    client.set_inventory_contract(&inventory_id);
+    inventory
+}
+
+#[test]
+fn test_cancel_request_releases_reserved_units() {
+    let (env, admin, client, contract_id) = setup();
+    let inventory = setup_inventory(&env, &admin, &client, &contract_id);
+
+    let hospital = authorized_hospital(&env, &client);
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(900)
+        .required_by(current_time + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+
+    // Admin is an authorized bank in the inventory contract
+    let expiration = current_time + 30 * 86400;
+    let mut units = vec![&env];
+    for _ in 0..2 {
+        let unit_id = UnitFixture::new(&env)
+            .expiring_at(expiration)
+            .register(&inventory, &admin);
+        inventory.reserve_unit(
+            &unit_id,
+            &request_id,
+            &BloodType::APositive,
+            &false,
+            &admin,
+            &None,
+        );
+        units.push_back(UnitAssignment {
+            unit_id,
+            blood_type: BloodType::APositive,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        });
+    }
+    client.assign_blood_units(&request_id, &units);
+
+    client.cancel_request(&request_id, &hospital);
+
+    for unit in units.iter() {
+        let blood_unit = inventory.get_blood_unit(&unit.unit_id);
+        assert_eq!(blood_unit.status, BloodStatus::Available);
+        assert_eq!(blood_unit.reserved_for, None);
+    }
+    assert!(inventory.get_reserved_units(&request_id).is_empty());
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Cancelled
+    );
+}
+
+/// Approve a new request and assign `count` unit IDs starting at `first_unit`
+fn request_with_assignment(
+    env: &Env,
+    admin: &Address,
+    client: &RequestContractClient<'_>,
+    first_unit: u64,
+    count: u64,
+) -> u64 {
+    let request_id = RequestFixture::new(env)
+        .required_by(env.ledger().timestamp() + 86400)
+        .create(client);
+    client.approve_request(&request_id);
+    let mut units = vec![env];
+    for unit_id in first_unit..first_unit + count {
+        units.push_back(UnitAssignment {
+            unit_id,
+            blood_type: BloodType::APositive,
+            quantity_ml: 450,
+            bank_id: admin.clone(),
+        });
+    }
+    client.assign_blood_units(&request_id, &units);
+    request_id
+}
+
+#[test]
+fn test_closed_requests_with_units_are_indexed() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let cancelled = request_with_assignment(&env, &admin, &client, 1, 2);
+    let expired = request_with_assignment(&env, &admin, &client, 3, 1);
+    let by_status = request_with_assignment(&env, &admin, &client, 4, 1);
+    let without_units = RequestFixture::new(&env)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&without_units);
+    assert_eq!(client.get_terminal_requests_with_units(&10).len(), 0);
+
+    client.cancel_request(&cancelled, &admin);
+    client.cancel_request(&without_units, &admin);
+    client.update_request_status(&by_status, &RequestStatus::Cancelled);
+    advance_days(&env, 2);
+    client.expire_overdue_requests(&None);
+
+    assert_eq!(
+        client.get_terminal_requests_with_units(&10),
+        vec![&env, cancelled, by_status, expired]
+    );
+    assert_eq!(
+        client.get_terminal_requests_with_units(&2),
+        vec![&env, cancelled, by_status]
+    );
+}
+
+#[test]
+fn test_release_orphaned_assignments_empties_index() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let request_id = request_with_assignment(&env, &admin, &client, 1, 2);
+    client.cancel_request(&request_id, &admin);
+    assert_eq!(
+        client.get_terminal_requests_with_units(&10),
+        vec![&env, request_id]
+    );
+
+    client.release_orphaned_assignments(&request_id);
+
+    let event: OrphanedAssignmentsReleasedEvent =
+        latest_event(&env, "orphaned_assignments_released");
+    assert_eq!(event.request_id, request_id);
+    assert_eq!(event.unit_ids, vec![&env, 1, 2]);
+    assert_eq!(event.released_by, admin);
+
+    let request = client.get_request(&request_id);
+    assert!(request.assigned_units.is_empty());
+    assert!(request.assignments.is_empty());
+    assert_eq!(client.get_terminal_requests_with_units(&10).len(), 0);
+
+    // Nothing left to release
+    assert_eq!(
+        client.try_release_orphaned_assignments(&request_id),
+        Err(Ok(ContractError::InvalidRequestState))
+    );
+}
+
+#[test]
+fn test_release_orphaned_assignments_requires_closed_request() {
+    let (env, admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let request_id = request_with_assignment(&env, &admin, &client, 1, 1);
+    assert_eq!(
+        client.try_release_orphaned_assignments(&request_id),
+        Err(Ok(ContractError::InvalidRequestState))
+    );
+    assert_eq!(
+        client.try_release_orphaned_assignments(&99),
+        Err(Ok(ContractError::RequestNotFound))
+    );
+    assert_eq!(client.get_request(&request_id).assigned_units.len(), 1);
+}
+
+#[test]
+fn test_release_orphaned_assignments_frees_inventory_holds() {
+    let (env, admin, client, contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    // Cancelled before the request contract was pointed at the inventory
+    let unit_request = RequestFixture::new(&env)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&unit_request);
+    let inventory_id = env.register(InventoryContract, ());
+    let inventory = InventoryContractClient::new(&env, &inventory_id);
+    inventory.initialize(&admin, &vec![&env], &0);
+    inventory.set_request_contract(&contract_id);
+    let unit_id = UnitFixture::new(&env).register(&inventory, &admin);
+    inventory.reserve_unit(
+        &unit_id,
+        &unit_request,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+    client.assign_blood_units(
+        &unit_request,
+        &vec![
+            &env,
+            UnitAssignment {
+                unit_id,
+                blood_type: BloodType::APositive,
+                quantity_ml: 450,
+                bank_id: admin.clone(),
+            },
+        ],
+    );
+    client.cancel_request(&unit_request, &admin);
+    assert_eq!(
+        inventory.get_blood_unit(&unit_id).status,
+        BloodStatus::Reserved
+    );
+
+    client.set_inventory_contract(&inventory_id);
+    client.release_orphaned_assignments(&unit_request);
+
+    let unit = inventory.get_blood_unit(&unit_id);
+    assert_eq!(unit.status, BloodStatus::Available);
+    assert_eq!(unit.reserved_for, None);
+    assert_eq!(client.get_terminal_requests_with_units(&10).len(), 0);
+}
+
+#[test]
+fn test_assign_checks_required_phenotype() {
+    let (env, admin, client, contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env).by(&hospital).create(&client);
+    client.approve_request(&request_id);
+    let required = vec![
+        &env,
+        Symbol::new(&env, "kell_neg"),
+        Symbol::new(&env, "c_neg"),
+    ];
+    client.set_required_phenotype(&hospital, &request_id, &required);
+    assert_eq!(client.get_request(&request_id).required_phenotype, required);
+
+    let assignment = |unit_id: u64| {
+        vec![
+            &env,
+            UnitAssignment {
+                unit_id,
+                blood_type: BloodType::APositive,
+                quantity_ml: 450,
+                bank_id: admin.clone(),
+            },
+        ]
+    };
+
+    // Tags can only be checked against a configured inventory
+    let result = client.try_assign_blood_units(&request_id, &assignment(1));
+    assert_eq!(result, Err(Ok(ContractError::InventoryNotConfigured)));
+
+    let inventory = setup_inventory(&env, &admin, &client, &contract_id);
+    let matching = UnitFixture::new(&env)
+        .with_phenotype(&["kell_neg", "c_neg", "e_neg"])
+        .register(&inventory, &admin);
+    let missing_one = UnitFixture::new(&env)
+        .with_phenotype(&["kell_neg"])
+        .register(&inventory, &admin);
+
+    let result = client.try_assign_blood_units(&request_id, &assignment(missing_one));
+    assert_eq!(result, Err(Ok(ContractError::PhenotypeMismatch)));
+
+    client.assign_blood_units(&request_id, &assignment(matching));
+    assert_eq!(
+        client.get_request(&request_id).assigned_units,
+        vec![&env, matching]
+    );
+}
+
+#[test]
+fn test_set_required_phenotype_validation() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    let other = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env).by(&hospital).create(&client);
+    let kell_neg = Symbol::new(&env, "kell_neg");
+
+    let result =
+        client.try_set_required_phenotype(&other, &request_id, &vec![&env, kell_neg.clone()]);
+    assert_eq!(result, Err(Ok(ContractError::NotRequestCreator)));
+
+    let duplicated = vec![&env, kell_neg.clone(), kell_neg.clone()];
+    let result = client.try_set_required_phenotype(&hospital, &request_id, &duplicated);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    client.set_required_phenotype(&hospital, &request_id, &vec![&env, kell_neg.clone()]);
+    let event: RequiredPhenotypeSetEvent = latest_event(&env, "required_phenotype_set");
+    assert_eq!(event.request_id, request_id);
+    assert_eq!(event.required_phenotype, vec![&env, kell_neg]);
+
+    client.cancel_request(&request_id, &hospital);
+    let result = client.try_set_required_phenotype(&hospital, &request_id, &vec![&env]);
+    assert_eq!(result, Err(Ok(ContractError::InvalidStatusTransition)));
+}
+
+#[test]
+fn test_expiry_sweep_releases_reserved_units() {
+    let (env, admin, client, contract_id) = setup();
+    let inventory = setup_inventory(&env, &admin, &client, &contract_id);
+
+    let hospital = authorized_hospital(&env, &client);
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::OPositive)
+        .required_by(current_time + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+
+    let unit_id = UnitFixture::new(&env)
+        .with_blood_type(BloodType::OPositive)
+        .expiring_at(current_time + 30 * 86400)
+        .register(&inventory, &admin);
+    inventory.reserve_unit(
+        &unit_id,
+        &request_id,
+        &BloodType::OPositive,
+        &false,
+        &admin,
+        &None,
+    );
+
+    env.ledger().set_timestamp(current_time + 2 * 86400);
+    assert_eq!(client.expire_overdue_requests(&None), 1);
+
+    assert_eq!(
+        inventory.get_blood_unit(&unit_id).status,
+        BloodStatus::Available
+    );
+}
+
+#[test]
+fn test_rejected_request_releases_directed_units() {
+    let (env, admin, client, contract_id) = setup();
+    let inventory = setup_inventory(&env, &admin, &client, &contract_id);
+
+    env.ledger().set_timestamp(1000);
+    let request_id = RequestFixture::new(&env)
+        .required_by(1000 + 86400)
+        .create(&client);
+
+    // A relative donated for this patient
+    let unit_id = UnitFixture::new(&env)
+        .directed_to(request_id)
+        .register(&inventory, &admin);
+    assert_eq!(inventory.get_next_unit_fifo(&BloodType::APositive), None);
+
+    client.update_request_status(&request_id, &RequestStatus::Rejected);
+
+    let blood_unit = inventory.get_blood_unit(&unit_id);
+    assert_eq!(blood_unit.status, BloodStatus::Available);
+    assert_eq!(blood_unit.directed_to, None);
+    assert!(inventory.get_directed_units(&request_id).is_empty());
+    assert_eq!(
+        inventory.get_next_unit_fifo(&BloodType::APositive),
+        Some(unit_id)
+    );
+}
+
+#[test]
+fn test_waitlist_matches_top_priority_request() {
+    let (env, admin, client, contract_id) = setup();
+    let inventory = setup_inventory(&env, &admin, &client, &contract_id);
+    env.ledger().set_timestamp(1000);
+
+    let normal = RequestFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(1000 + 2 * 86400)
+        .create(&client);
+    let critical = RequestFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(1000 + 3600)
+        .create(&client);
+    client.approve_request(&critical);
+
+    client.add_to_waitlist(&normal);
+    client.add_to_waitlist(&critical);
+    assert!(client.is_waitlisted(&critical));
+    assert_eq!(
+        client.try_add_to_waitlist(&critical),
+        Err(Ok(ContractError::AlreadyExists))
+    );
+
+    // New stock is offered to the critical request only
+    let unit_id = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .register(&inventory, &admin);
+    assert_eq!(count_events(&env, "match_available"), 1);
+    let event: MatchAvailableEvent = latest_event(&env, "match_available");
+    assert_eq!(event.request_id, critical);
+    assert_eq!(event.blood_unit_id, unit_id);
+
+    // Once it is served the next request moves up
+    client.update_request_status(&critical, &RequestStatus::Fulfilled);
+    assert!(!client.is_waitlisted(&critical));
+    UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .register(&inventory, &admin);
+    let event: MatchAvailableEvent = latest_event(&env, "match_available");
+    assert_eq!(event.request_id, normal);
+
+    // A cancelled request drops off and the waitlist is empty
+    client.cancel_request(&normal, &admin);
+    assert!(inventory.get_waitlist(&BloodType::ONegative).is_empty());
+    UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .register(&inventory, &admin);
+    assert_eq!(count_events(&env, "match_available"), 0);
+}
+
+#[test]
+fn test_add_to_waitlist_errors() {
+    let (env, _admin, client, _contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env)
+        .required_by(1000 + 86400)
+        .create(&client);
+
+    assert_eq!(
+        client.try_add_to_waitlist(&request_id),
+        Err(Ok(ContractError::InventoryNotConfigured))
+    );
+    assert!(!client.is_waitlisted(&request_id));
+
+    client.update_request_status(&request_id, &RequestStatus::Rejected);
+    assert_eq!(
+        client.try_add_to_waitlist(&request_id),
+        Err(Ok(ContractError::InvalidStatusTransition))
+    );
+    assert_eq!(
+        client.try_add_to_waitlist(&99),
+        Err(Ok(ContractError::RequestNotFound))
+    );
+}
+
+/// Create an approved O- request of the given urgency and reserve one new
+/// O- unit for it
+fn reserved_request(
+    env: &Env,
+    admin: &Address,
+    client: &RequestContractClient<'_>,
+    inventory: &InventoryContractClient<'_>,
+    urgency: UrgencyLevel,
+) -> (u64, u64) {
+    let request_id = RequestFixture::new(env)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(urgency)
+        .required_by(env.ledger().timestamp() + 86400)
+        .create(client);
+    client.approve_request(&request_id);
+    let unit_id = UnitFixture::new(env)
+        .with_blood_type(BloodType::ONegative)
+        .register(inventory, admin);
+    inventory.reserve_unit(
+        &unit_id,
+        &request_id,
+        &BloodType::ONegative,
+        &false,
+        admin,
+        &None,
+    );
+    (request_id, unit_id)
+}
+
+#[test]
+fn test_preempt_reservation_moves_unit_to_critical_request() {
+    let (env, admin, client, contract_id) = setup();
+    let inventory = setup_inventory(&env, &admin, &client, &contract_id);
+    env.ledger().set_timestamp(1000);
+
+    let (normal, unit_id) =
+        reserved_request(&env, &admin, &client, &inventory, UrgencyLevel::Normal);
+    let critical = RequestFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(1000 + 3600)
+        .create(&client);
+
+    assert_eq!(
+        client.preempt_reservation(&admin, &unit_id, &critical),
+        normal
+    );
+
+    let event: ReservationPreemptedEvent = latest_event(&env, "reservation_preempted");
+    assert_eq!(
+        event,
+        ReservationPreemptedEvent {
+            unit_id,
+            from_request_id: normal,
+            to_request_id: critical,
+            waitlisted: true,
+            preempted_at: 1000,
+        }
+    );
+    assert_eq!(count_events(&env, "reservation_released"), 1);
+    assert_eq!(count_events(&env, "blood_reserved"), 1);
+
+    let unit = inventory.get_blood_unit(&unit_id);
+    assert_eq!(unit.status, BloodStatus::Reserved);
+    assert_eq!(unit.reserved_for, Some(critical));
+    assert_eq!(inventory.get_reserved_units(&normal).len(), 0);
+
+    // The displaced request waits for the next O- unit
+    assert!(client.is_waitlisted(&normal));
+    let waitlist = inventory.get_waitlist(&BloodType::ONegative);
+    assert_eq!(waitlist.len(), 1);
+    assert_eq!(waitlist.get(0).unwrap().request_id, normal);
+}
+
+#[test]
+fn test_preempt_reservation_rejected_when_holder_is_critical() {
+    let (env, admin, client, contract_id) = setup();
+    let inventory = setup_inventory(&env, &admin, &client, &contract_id);
+    env.ledger().set_timestamp(1000);
+
+    let (holder, unit_id) =
+        reserved_request(&env, &admin, &client, &inventory, UrgencyLevel::Critical);
+    let critical = RequestFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(1000 + 3600)
+        .create(&client);
+
+    assert_eq!(
+        client.try_preempt_reservation(&admin, &unit_id, &critical),
+        Err(Ok(ContractError::PreemptionNotAllowed))
+    );
+    assert_eq!(inventory.get_reserved_for(&unit_id), Some(holder));
+    assert!(!client.is_waitlisted(&holder));
+}
+
+#[test]
+fn test_preempt_reservation_errors() {
+    let (env, admin, client, contract_id) = setup();
+    env.ledger().set_timestamp(1000);
+
+    let urgent = RequestFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Urgent)
+        .required_by(1000 + 4 * 3600)
+        .create(&client);
+    let critical = RequestFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(1000 + 3600)
+        .create(&client);
+
+    assert_eq!(
+        client.try_preempt_reservation(&admin, &1, &critical),
+        Err(Ok(ContractError::InventoryNotConfigured))
+    );
+    let inventory = setup_inventory(&env, &admin, &client, &contract_id);
+
+    let (holder, unit_id) =
+        reserved_request(&env, &admin, &client, &inventory, UrgencyLevel::Normal);
+
+    // Only Critical, open requests may preempt
+    assert_eq!(
+        client.try_preempt_reservation(&admin, &unit_id, &urgent),
+        Err(Ok(ContractError::InvalidUrgency))
+    );
+    client.cancel_request(&critical, &admin);
+    assert_eq!(
+        client.try_preempt_reservation(&admin, &unit_id, &critical),
+        Err(Ok(ContractError::RequestAlreadyTerminal))
+    );
+
+    let critical = RequestFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .with_urgency(UrgencyLevel::Critical)
+        .required_by(1000 + 3600)
+        .create(&client);
+    assert_eq!(
+        client.try_preempt_reservation(&Address::generate(&env), &unit_id, &critical),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_preempt_reservation(&admin, &unit_id, &99),
+        Err(Ok(ContractError::RequestNotFound))
+    );
+
+    // Unreserved units have nothing to preempt
+    let free_unit = UnitFixture::new(&env)
+        .with_blood_type(BloodType::ONegative)
+        .register(&inventory, &admin);
+    assert_eq!(
+        client.try_preempt_reservation(&admin, &free_unit, &critical),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(inventory.get_reserved_for(&unit_id), Some(holder));
+}
+
+#[test]
+fn test_hospital_cancels_within_grace_period_releasing_units() {
+    let (env, admin, client, contract_id) = setup();
+    let inventory = setup_inventory(&env, &admin, &client, &contract_id);
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+    assert_eq!(client.get_request(&request_id).approved_at, Some(1000));
+
+    let unit_id = UnitFixture::new(&env).register(&inventory, &admin);
+    inventory.reserve_unit(
+        &unit_id,
+        &request_id,
+        &BloodType::APositive,
+        &false,
+        &admin,
+        &None,
+    );
+    client.assign_blood_units(
+        &request_id,
+        &vec![
+            &env,
+            UnitAssignment {
+                unit_id,
+                blood_type: BloodType::APositive,
+                quantity_ml: 450,
+                bank_id: admin.clone(),
+            },
+        ],
+    );
+
+    // The last second of the default one-hour window still counts
+    env.ledger().set_timestamp(1000 + 3600);
+    client.cancel_request(&request_id, &hospital);
+
+    let event: AssignedUnitsReleasedEvent = latest_event(&env, "assigned_units_released");
+    assert_eq!(event.request_id, request_id);
+    assert_eq!(event.unit_ids, vec![&env, unit_id]);
+    assert_eq!(event.cancelled_by, hospital);
+    assert_eq!(count_events(&env, "reservation_released"), 1);
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Cancelled
+    );
+
+    let blood_unit = inventory.get_blood_unit(&unit_id);
+    assert_eq!(blood_unit.status, BloodStatus::Available);
+    assert_eq!(blood_unit.reserved_for, None);
+}
+
+#[test]
+fn test_hospital_cannot_cancel_after_grace_period() {
+    let (env, admin, client, _contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let approved = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    let pending = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&approved);
+
+    env.ledger().set_timestamp(1000 + 3601);
+    let result = client.try_cancel_request(&approved, &hospital);
+    assert_eq!(result, Err(Ok(ContractError::CancellationWindowClosed)));
+    assert_eq!(
+        client.get_request(&approved).status,
+        RequestStatus::Approved
+    );
+
+    // Pending requests are not affected, and the admin can still cancel
+    client.cancel_request(&pending, &hospital);
+    client.cancel_request(&approved, &admin);
+    assert_eq!(
+        client.get_request(&approved).status,
+        RequestStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_cancellation_grace_period_restarts_on_reapproval() {
+    let (env, admin, client, _contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    assert_eq!(client.get_cancellation_grace_period(), 3600);
+    client.set_cancellation_grace_period(&600);
+    assert_eq!(client.get_cancellation_grace_period(), 600);
+    let result = client.try_set_cancellation_grace_period(&(86400 + 1));
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .required_by(1000 + 86400)
+        .create(&client);
+    client.approve_request(&request_id);
+
+    env.ledger().set_timestamp(1000 + 601);
+    let result = client.try_cancel_request(&request_id, &hospital);
+    assert_eq!(result, Err(Ok(ContractError::CancellationWindowClosed)));
+
+    client.revert_approval(
+        &admin,
+        &request_id,
+        &String::from_str(&env, "Approved in error"),
+    );
+    assert_eq!(client.get_request(&request_id).approved_at, None);
+    client.approve_request(&request_id);
+
+    client.cancel_request(&request_id, &hospital);
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_cancel_without_inventory_configured() {
+    let (env, _admin, client, _contract_id) = setup();
+
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::OPositive)
+        .required_by(1000 + 86400)
+        .create(&client);
+
+    assert_eq!(client.get_inventory_contract(), None);
+    client.cancel_request(&request_id, &hospital);
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Cancelled
+    );
+}
+
+// ========== Index Invariant Tests ==========
+
+fn assert_no_duplicates(ids: &Vec<u64>) {
+    for (i, id) in ids.iter().enumerate() {
+        assert_eq!(
+            ids.first_index_of(id),
+            Some(i as u32),
+            "duplicate id {}",
+            id
+        );
+    }
+}
+
+#[test]
+fn test_index_insertion_is_idempotent() {
+    let (env, _admin, client, contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(env.ledger().timestamp() + 86400)
+        .create(&client);
+
+    // Re-running the index writes must not add a second entry
+    env.as_contract(&contract_id, || {
+        let request = storage::get_blood_request(&env, request_id).unwrap();
+        storage::add_to_hospital_index(&env, &request);
+        storage::add_to_blood_type_index(&env, &request);
+        storage::add_to_status_index(&env, &request);
+        storage::add_to_urgency_index(&env, &request);
+        storage::add_to_deadline_bucket(&env, &request);
+    });
+
+    assert_eq!(
+        client.get_hospital_requests(&hospital),
+        vec![&env, request_id]
+    );
+    assert_eq!(
+        client.get_requests_by_blood_type(&BloodType::APositive),
+        vec![&env, request_id]
+    );
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Pending),
+        vec![&env, request_id]
+    );
+    assert_eq!(
+        client.get_requests_by_urgency(&UrgencyLevel::Normal),
+        vec![&env, request_id]
+    );
+}
+
+#[test]
+fn test_indexes_stay_duplicate_free_after_random_operations() {
+    let (env, admin, client, _contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    let base_time = env.ledger().timestamp();
+
+    let blood_types = [
+        BloodType::APositive,
+        BloodType::ONegative,
+        BloodType::BPositive,
+    ];
+    let urgencies = [
+        UrgencyLevel::Normal,
+        UrgencyLevel::Urgent,
+        UrgencyLevel::Critical,
+    ];
+
+    // Deterministic pseudo-random sequence (LCG)
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |bound: u64| {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) % bound
+    };
+
+    let mut created: u64 = 0;
+    for _ in 0..60 {
+        let op = next(6);
+        let target = if created == 0 { 0 } else { next(created) + 1 };
+        match op {
+            0 | 1 => {
+                let blood_type = blood_types[next(3) as usize];
+                let urgency = urgencies[next(3) as usize];
+                RequestFixture::new(&env)
+                    .by(&hospital)
+                    .with_blood_type(blood_type)
+                    .with_urgency(urgency)
+                    .required_by(base_time + 2 * 86400 + next(86400))
+                    .create(&client);
+                created += 1;
+            }
+            2 => {
+                let _ = client.try_approve_request(&target);
+            }
+            3 => {
+                let _ = client.try_cancel_request(&target, &hospital);
+            }
+            4 => {
+                let _ = client.try_escalate_request(&hospital, &target, &UrgencyLevel::Critical);
+            }
+            _ => {
+                let _ = client.try_revert_approval(
+                    &admin,
+                    &target,
+                    &String::from_str(&env, "Re-review"),
+                );
+            }
+        }
+    }
+
+    assert_no_duplicates(&client.get_hospital_requests(&hospital));
+    for blood_type in blood_types {
+        assert_no_duplicates(&client.get_requests_by_blood_type(&blood_type));
+    }
+    for urgency in urgencies {
+        assert_no_duplicates(&client.get_requests_by_urgency(&urgency));
+    }
+    for status in [
+        RequestStatus::Pending,
+        RequestStatus::Approved,
+        RequestStatus::Cancelled,
+    ] {
+        let ids = client.get_requests_by_status(&status);
+        assert_no_duplicates(&ids);
+        for id in ids.iter() {
+            assert_eq!(client.get_request(&id).status, status);
+        }
+    }
+}
+
+// ========== Event Tests ==========
+
+#[test]
+fn test_request_created_event_topics() {
+    let (env, _admin, client, contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+
+    env.ledger().set_timestamp(5000);
+    let required_by = 5000 + 4 * 3600;
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .required_by(required_by)
+        .create(&client);
+
+    let event = RequestCreatedEvent {
+        request_id,
+        hospital_id: hospital.clone(),
+        blood_type: BloodType::ONegative,
+        quantity_ml: 450,
+        urgency: UrgencyLevel::Urgent,
+        required_by,
+        co_signer: None,
+        department: String::from_str(&env, ""),
+        physician_id: String::from_str(&env, ""),
+        pediatric: false,
+        cross_region: false,
+        contact: None,
+        created_at: 5000,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (
+                    Symbol::new(&env, "request_created"),
+                    hospital.clone(),
+                    BloodType::ONegative,
+                )
+                    .into_val(&env),
+                event.into_val(&env),
+            ),
+        ]
+    );
+}
+
+// ========== Paged Index Tests ==========
+
+/// Sequential IDs `from..to`
+fn id_range(env: &Env, from: u64, to: u64) -> soroban_sdk::Vec<u64> {
+    let mut ids = vec![env];
+    for id in from..to {
+        ids.push_back(id);
+    }
+    ids
+}
+
+#[test]
+fn test_status_index_rolls_over_pages() {
+    let (env, _admin, client, contract_id) = setup();
+    let index = storage::PagedIndex::Status(RequestStatus::Pending);
+
+    env.as_contract(&contract_id, || {
+        let mut request = sample_request(&env, RequestStatus::Pending);
+        for id in 1..=250u64 {
+            request.id = id;
+            storage::add_to_status_index(&env, &request);
+        }
+        // A repeated insert of the newest ID is a no-op
+        storage::add_to_status_index(&env, &request);
+
+        assert_eq!(storage::get_index_page_count(&env, index), 3);
+        assert_eq!(storage::get_index_page(&env, index, 0).len(), 100);
+        assert_eq!(storage::get_index_page(&env, index, 1).len(), 100);
+        assert_eq!(storage::get_index_page(&env, index, 2).len(), 50);
+    });
+
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Pending),
+        id_range(&env, 1, 251)
+    );
+    assert_eq!(
+        client.get_requests_by_status_page(&RequestStatus::Pending, &150, &60),
+        id_range(&env, 151, 211)
+    );
+    assert_eq!(
+        client
+            .get_requests_by_status_page(&RequestStatus::Pending, &240, &60)
+            .len(),
+        10
+    );
+
+    // Moving a request out of an earlier page leaves the rest in order
+    env.as_contract(&contract_id, || {
+        storage::update_status_index(&env, 5, RequestStatus::Pending, RequestStatus::Approved);
+        assert_eq!(storage::get_index_page(&env, index, 0).len(), 99);
+    });
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Approved),
+        vec![&env, 5]
+    );
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Pending).len(),
+        249
+    );
+}
+
+#[test]
+fn test_status_index_removes_from_one_page() {
+    let (env, _admin, client, contract_id) = setup();
+    let index = storage::PagedIndex::Status(RequestStatus::Pending);
+
+    env.as_contract(&contract_id, || {
+        // Hundreds of index writes in one frame
+        env.cost_estimate().budget().reset_unlimited();
+        let mut request = sample_request(&env, RequestStatus::Pending);
+        for id in 1..=250u64 {
+            request.id = id;
+            storage::add_to_status_index(&env, &request);
+        }
+        let approve = |id: u64| {
+            storage::update_status_index(&env, id, RequestStatus::Pending, RequestStatus::Approved)
+        };
+
+        // Only the page holding the ID changes
+        approve(150);
+        assert_eq!(storage::get_index_slot(&env, index, 150), None);
+        assert_eq!(storage::get_index_page(&env, index, 0).len(), 100);
+        assert_eq!(storage::get_index_page(&env, index, 1).len(), 99);
+        assert_eq!(storage::get_index_page(&env, index, 2).len(), 50);
+        assert_eq!(storage::get_index_slot(&env, index, 201), Some(2));
+
+        // An emptied middle page reads as empty; later pages keep their place
+        (101..=200u64).filter(|id| *id != 150).for_each(approve);
+        assert_eq!(storage::get_index_page_count(&env, index), 3);
+        assert!(storage::get_index_page(&env, index, 1).is_empty());
+        assert_eq!(storage::get_index_slot(&env, index, 250), Some(2));
+
+        // Emptying the last page drops it and the empty page before it
+        (201..=250u64).for_each(approve);
+        assert_eq!(storage::get_index_page_count(&env, index), 1);
+
+        // A repeated insert is skipped wherever the ID sits
+        for id in [300, 300, 50] {
+            request.id = id;
+            storage::add_to_status_index(&env, &request);
+        }
+        assert_eq!(storage::get_index_page(&env, index, 1), vec![&env, 300]);
+    });
+
+    let mut expected = id_range(&env, 1, 101);
+    expected.push_back(300);
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Pending),
+        expected
+    );
+}
+
+#[test]
+fn test_legacy_status_index_migrates_lazily() {
+    let (env, _admin, client, contract_id) = setup();
+    let legacy_key = DataKey::StatusIndex(RequestStatus::Approved);
+
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&legacy_key, &id_range(&env, 1, 151));
+    });
+
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Approved),
+        id_range(&env, 1, 151)
+    );
+
+    env.as_contract(&contract_id, || {
+        let index = storage::PagedIndex::Status(RequestStatus::Approved);
+        assert!(!env.storage().persistent().has(&legacy_key));
+        assert_eq!(storage::get_index_page_count(&env, index), 2);
+
+        let mut request = sample_request(&env, RequestStatus::Approved);
+        request.id = 151;
+        storage::add_to_status_index(&env, &request);
+        assert_eq!(storage::get_index_page(&env, index, 1).len(), 51);
+    });
+}
+
+#[test]
+fn test_status_index_insert_cost_independent_of_size() {
+    let (env, _admin, _client, contract_id) = setup();
+
+    env.as_contract(&contract_id, || {
+        // Small index: one page of 50; large index: 20 full pages plus 50
+        env.storage().persistent().set(
+            &DataKey::StatusIndex(RequestStatus::Pending),
+            &id_range(&env, 1, 51),
+        );
+        env.storage().persistent().set(
+            &DataKey::StatusIndex(RequestStatus::Approved),
+            &id_range(&env, 1, 2051),
+        );
+
+        let insert_cost = |status: RequestStatus, id: u64| {
+            let mut request = sample_request(&env, status);
+            request.id = id;
+            let mut budget = env.cost_estimate().budget();
+            budget.reset_unlimited();
+            storage::add_to_status_index(&env, &request);
+            budget.cpu_instruction_cost()
+        };
+
+        // The first insert pays for the one-off migration
+        insert_cost(RequestStatus::Pending, 100_000);
+        insert_cost(RequestStatus::Approved, 100_000);
+
+        let small = insert_cost(RequestStatus::Pending, 100_001);
+        let large = insert_cost(RequestStatus::Approved, 100_001);
+        assert!(large < small * 2, "small: {}, large: {}", small, large);
+    });
+}
+
+// ========== Quantity Step Tests ==========
+
+#[test]
+fn test_quantity_step_enforced_on_create() {
+    let (env, _admin, client, _contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    let required_by = env.ledger().timestamp() + 86400;
+
+    // Disabled by default
+    assert_eq!(client.get_quantity_step(), 0);
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(437)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+
+    client.set_quantity_step(&450);
+    assert_eq!(client.get_quantity_step(), 450);
+
+    for quantity in [449u32, 451, 899] {
+        let result = RequestFixture::new(&env)
+            .by(&hospital)
+            .with_blood_type(BloodType::BPositive)
+            .with_quantity(quantity)
+            .with_urgency(UrgencyLevel::Normal)
+            .required_by(required_by)
+            .try_create(&client);
+        assert_eq!(result, Err(Ok(ContractError::QuantityNotAligned)));
+    }
+
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::BPositive)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::OPositive)
+        .with_quantity(900)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+
+    client.set_quantity_step(&0);
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_blood_type(BloodType::ONegative)
+        .with_quantity(437)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+}
+
+// ========== Pediatric Request Tests ==========
+
+#[test]
+fn test_pediatric_request_allows_small_volume() {
+    let (env, _admin, client, _contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(25)
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::InvalidQuantity)));
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(25)
+        .pediatric()
+        .create(&client);
+
+    let event: RequestCreatedEvent = latest_event(&env, "request_created");
+    assert!(event.pediatric);
+    assert_eq!(event.quantity_ml, 25);
+    let request = client.get_request(&request_id);
+    assert!(request.pediatric);
+    assert_eq!(request.quantity_ml, 25);
+
+    // Still bounded by the pediatric minimum
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(5)
+        .pediatric()
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::InvalidQuantity)));
+
+    // Aliquots are not held to the bag-sized quantity step
+    client.set_quantity_step(&450);
+    RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(25)
+        .pediatric()
+        .create(&client);
+}
+
+#[test]
+fn test_pediatric_flag_keeps_deadline_rules() {
+    let (env, _admin, client, _contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let adult = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(1000 + 7200)
+        .try_create(&client);
+    let pediatric = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(25)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(1000 + 7200)
+        .pediatric()
+        .try_create(&client);
+    assert_eq!(adult, Err(Ok(ContractError::InvalidRequiredBy)));
+    assert_eq!(pediatric, adult);
+
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(25)
+        .required_by(500)
+        .pediatric()
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::InvalidRequiredBy)));
+}
+
+#[test]
+fn test_set_pediatric_min_quantity() {
+    let (env, _admin, client, _contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    assert_eq!(client.get_pediatric_min_quantity(), 10);
+
+    client.set_pediatric_min_quantity(&20);
+    assert_eq!(client.get_pediatric_min_quantity(), 20);
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(15)
+        .pediatric()
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::InvalidQuantity)));
+
+    for min_ml in [0u32, 101] {
+        let result = client.try_set_pediatric_min_quantity(&min_ml);
+        assert_eq!(result, Err(Ok(ContractError::InvalidQuantity)));
+    }
+    assert_eq!(client.get_pediatric_min_quantity(), 20);
+}
+
+// ========== Cross-Region Approval Tests ==========
+
+#[test]
+fn test_cross_region_admin_approval_first() {
+    let (env, admin, client, _contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let regional = Address::generate(&env);
+    client.set_regional_authority(&regional);
+    assert_eq!(client.get_regional_authority(), Some(regional.clone()));
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .cross_region()
+        .create(&client);
+    let event: RequestCreatedEvent = latest_event(&env, "request_created");
+    assert!(event.cross_region);
+
+    // The admin alone is not enough
+    client.approve_request(&request_id);
+    let event: ApprovalRecordedEvent = latest_event(&env, "approval_recorded");
+    assert_eq!(event.authority, ApprovalAuthority::Admin);
+    assert_eq!(event.approver, admin);
+    assert_eq!(event.approvals, ApprovalAuthority::Admin.bit());
+    assert_eq!(count_events(&env, "request_status_changed"), 0);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Pending);
+    assert!(!request.awaits_approval(ApprovalAuthority::Admin));
+    assert!(request.awaits_approval(ApprovalAuthority::Regional));
+    assert_eq!(request.approved_by, None);
+
+    let result = client.try_approve_request(&request_id);
+    assert_eq!(result, Err(Ok(ContractError::ApprovalAlreadyRecorded)));
+
+    client.approve_cross_region(&request_id);
+    let event: ApprovalRecordedEvent = latest_event(&env, "approval_recorded");
+    assert_eq!(event.authority, ApprovalAuthority::Regional);
+    assert_eq!(event.approver, regional);
+    assert_eq!(event.approvals, 3);
+    assert_eq!(count_events(&env, "request_status_changed"), 1);
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Approved);
+    assert_eq!(request.approved_by, Some(regional));
+}
+
+#[test]
+fn test_cross_region_regional_approval_first() {
+    let (env, _admin, client, _contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let regional = Address::generate(&env);
+    client.set_regional_authority(&regional);
+
+    let request_id = RequestFixture::new(&env)
+        .by(&hospital)
+        .cross_region()
+        .create(&client);
+
+    client.approve_cross_region(&request_id);
+    let event: ApprovalRecordedEvent = latest_event(&env, "approval_recorded");
+    assert_eq!(event.authority, ApprovalAuthority::Regional);
+    assert_eq!(event.approvals, ApprovalAuthority::Regional.bit());
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Pending
+    );
+
+    let result = client.try_approve_cross_region(&request_id);
+    assert_eq!(result, Err(Ok(ContractError::ApprovalAlreadyRecorded)));
+
+    client.approve_request(&request_id);
+    let event: ApprovalRecordedEvent = latest_event(&env, "approval_recorded");
+    assert_eq!(event.authority, ApprovalAuthority::Admin);
+    assert_eq!(event.approvals, 3);
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Approved
+    );
+}
+
+#[test]
+fn test_cross_region_approval_errors() {
+    let (env, admin, client, _contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+
+    let cross_region = RequestFixture::new(&env)
+        .by(&hospital)
+        .cross_region()
+        .create(&client);
+    let local = RequestFixture::new(&env).by(&hospital).create(&client);
+
+    let result = client.try_approve_cross_region(&cross_region);
+    assert_eq!(
+        result,
+        Err(Ok(ContractError::RegionalAuthorityNotConfigured))
+    );
+
+    client.set_regional_authority(&Address::generate(&env));
+    let result = client.try_approve_cross_region(&local);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    // Ordinary requests are approved by the admin alone, without a record
+    client.approve_request(&local);
+    assert_eq!(client.get_request(&local).status, RequestStatus::Approved);
+    assert_eq!(count_events(&env, "approval_recorded"), 0);
+
+    // The generic status update cannot skip the regional approval
+    let result = client.try_update_request_status(&cross_region, &RequestStatus::Approved);
+    assert_eq!(result, Err(Ok(ContractError::SecondApprovalRequired)));
+
+    // Reverting an approval needs both approvals again
+    client.approve_cross_region(&cross_region);
+    client.approve_request(&cross_region);
+    client.revert_approval(
+        &admin,
+        &cross_region,
+        &String::from_str(&env, "Wrong region"),
+    );
+    let request = client.get_request(&cross_region);
+    assert_eq!(request.status, RequestStatus::Pending);
+    assert_eq!(request.approvals, 0);
+}
+
+// ========== ID Counter Tests ==========
+
+#[test]
+fn test_initialize_with_id_offset() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(RequestContract, ());
+    let client = RequestContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &vec![&env], &500);
+
+    let hospital = authorized_hospital(&env, &client);
+    let required_by = env.ledger().timestamp() + 86400;
+    let first = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(500)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(required_by)
+        .create(&client);
+    assert_eq!(first, 501);
+    assert_eq!(client.get_request_count(), 501);
+    assert_eq!(client.get_request(&501).id, 501);
+}
+
+#[test]
+fn test_create_request_counter_overflow() {
+    let (env, _admin, client, contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .set(&DataKey::RequestCounter, &u64::MAX);
+    });
+
+    let result = RequestFixture::new(&env)
+        .by(&hospital)
+        .with_quantity(500)
+        .with_urgency(UrgencyLevel::Normal)
+        .required_by(env.ledger().timestamp() + 86400)
+        .try_create(&client);
+    assert_eq!(result, Err(Ok(ContractError::CounterOverflow)));
+    assert_eq!(client.get_request_count(), u64::MAX);
+}
+
+// ========== State Export Tests ==========
+
+/// Walk an export from the first page to the final one
+fn export_all(env: &Env, client: &RequestContractClient<'_>, limit: u32) -> Vec<StateExportPage> {
+    let mut pages = Vec::new(env);
+    let mut cursor = 0;
+    loop {
+        let page = client.export_state_page(&cursor, &limit);
+        cursor = page.next_cursor;
+        pages.push_back(page);
+        if cursor == 0 {
+            return pages;
+        }
+    }
+}
+
+#[test]
+fn test_export_import_round_trip() {
+    let (env, admin, client, _contract_id) = setup();
+    let now = env.ledger().timestamp();
+
+    let hospital = authorized_hospital(&env, &client);
+    let signer = Address::generate(&env);
+    client.add_request_signer(&hospital, &signer);
+    let lapsing = Address::generate(&env);
+    client.authorize_hospital(&lapsing, &Some(now + 30 * 86400));
+    client.suspend_hospital(&lapsing, &(now + 86400));
+    client.set_quantity_step(&50);
+    client.set_auto_complete(&true);
+    client.set_assignment_tolerance(&AssignmentTolerance::Percent(10));
+
+    let mut ids = Vec::new(&env);
+    for urgency in [
+        UrgencyLevel::Normal,
+        UrgencyLevel::Urgent,
+        UrgencyLevel::Urgent,
+        UrgencyLevel::Normal,
+        UrgencyLevel::Urgent,
+    ] {
+        ids.push_back(
+            RequestFixture::new(&env)
+                .by(&hospital)
+                .with_urgency(urgency)
+                .with_contact("Ward 3")
+                .in_department("Surgery")
+                .create(&client),
+        );
+    }
+    client.approve_request(&ids.get_unchecked(1));
+    client.reject_request(&ids.get_unchecked(2), &RejectionReason::InsufficientStock);
+
+    let pages = export_all(&env, &client, 2);
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages.last().unwrap().requests.len(), 1);
+    let rejections = &pages.get_unchecked(1).rejection_reasons;
+    assert_eq!(
+        rejections.get(ids.get_unchecked(2)),
+        Some(RejectionReason::InsufficientStock)
+    );
+    let config = client.export_state_config();
+    assert_eq!(config.request_counter, 5);
+
+    // Import into a fresh contract
+    let imported_id = env.register(RequestContract, ());
+    let imported = RequestContractClient::new(&env, &imported_id);
+    imported.initialize(&admin, &vec![&env], &0);
+    for page in pages.iter() {
+        imported.import_state_page(&page);
+    }
+    imported.import_state_config(&config);
+
+    assert_eq!(export_all(&env, &imported, 2), pages);
+    assert_eq!(imported.export_state_config(), config);
+    assert_eq!(
+        imported.get_rejection_reason(&ids.get_unchecked(2)),
+        Some(RejectionReason::InsufficientStock)
+    );
+    assert_eq!(
+        imported.get_requests_range(&1, &50),
+        client.get_requests_range(&1, &50)
+    );
+    assert_eq!(
+        imported.get_pending_queue(&None),
+        client.get_pending_queue(&None)
+    );
+    assert_eq!(
+        imported.get_requests_by_status(&RequestStatus::Approved),
+        vec![&env, ids.get_unchecked(1)]
+    );
+    let surgery = String::from_str(&env, "Surgery");
+    assert_eq!(
+        imported.get_requests_by_department(&hospital, &surgery, &0, &50),
+        ids
+    );
+    assert_eq!(
+        imported.list_authorized_hospitals_detail(&0, &50),
+        client.list_authorized_hospitals_detail(&0, &50)
+    );
+    assert_eq!(imported.get_request_signers(&hospital), vec![&env, signer]);
+    assert_eq!(imported.get_quantity_step(), 50);
+    assert!(imported.is_auto_complete());
+    assert_eq!(
+        imported.get_assignment_tolerance(),
+        AssignmentTolerance::Percent(10)
+    );
+
+    // The next request continues the ID sequence, and importing is closed
+    let next = RequestFixture::new(&env).by(&hospital).create(&imported);
+    assert_eq!(next, 6);
+    assert_eq!(
+        imported.try_import_state_page(&pages.get_unchecked(0)),
+        Err(Ok(ContractError::ImportNotAllowed))
+    );
+    assert_eq!(
+        imported.try_import_state_config(&config),
+        Err(Ok(ContractError::ImportNotAllowed))
+    );
+}
+
+#[test]
+fn test_import_only_before_organic_activity() {
+    let (env, admin, client, _contract_id) = setup();
+    RequestFixture::new(&env).by(&admin).create(&client);
+    let page = client.export_state_page(&0, &50);
+
+    // A request created before importing closes it
+    let (env2, admin2, organic, _) = setup();
+    RequestFixture::new(&env2).by(&admin2).create(&organic);
+    let empty = StateExportPage {
+        requests: Vec::new(&env2),
+        expiry_reasons: Map::new(&env2),
+        rejection_reasons: Map::new(&env2),
+        completion_overrides: Vec::new(&env2),
+        next_cursor: 0,
+    };
+    assert_eq!(
+        organic.try_import_state_page(&empty),
+        Err(Ok(ContractError::ImportNotAllowed))
+    );
+
+    // Creating requests is refused while an import is in progress
+    let importing_id = env.register(RequestContract, ());
+    let importing = RequestContractClient::new(&env, &importing_id);
+    importing.initialize(&admin, &vec![&env], &0);
+    importing.import_state_page(&page);
+    assert_eq!(
+        RequestFixture::new(&env).by(&admin).try_create(&importing),
+        Err(Ok(ContractError::ImportInProgress))
+    );
+
+    // The same request cannot be imported twice
+    assert_eq!(
+        importing.try_import_state_page(&page),
+        Err(Ok(ContractError::AlreadyExists))
+    );
+}
+
+// ========== Type Tests ==========
+
+#[test]
+fn test_urgency_level_max_fulfillment_time() {
+    assert_eq!(UrgencyLevel::Critical.max_fulfillment_time(), 3600); // 1 hour
+    assert_eq!(UrgencyLevel::Urgent.max_fulfillment_time(), 21600); // 6 hours
+    assert_eq!(UrgencyLevel::Normal.max_fulfillment_time(), 86400); // 24 hours
+}
+
+#[test]
+fn test_request_status_transitions() {
+    // Valid transitions
+    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Approved));
+    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Rejected));
+    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Cancelled));
+
+    assert!(RequestStatus::Approved.can_transition_to(&RequestStatus::Fulfilled));
+    assert!(RequestStatus::Approved.can_transition_to(&RequestStatus::Cancelled));
+
+    assert!(RequestStatus::Fulfilled.can_transition_to(&RequestStatus::Completed));
+
+    // Invalid transitions
+    assert!(!RequestStatus::Pending.can_transition_to(&RequestStatus::Fulfilled));
+    assert!(!RequestStatus::Rejected.can_transition_to(&RequestStatus::Approved));
+    assert!(!RequestStatus::Completed.can_transition_to(&RequestStatus::Approved));
+    assert!(!RequestStatus::Cancelled.can_transition_to(&RequestStatus::Fulfilled));
+}
+
+#[test]
+fn test_request_status_is_terminal() {
+    assert!(!RequestStatus::Pending.is_terminal());
+    assert!(!RequestStatus::Approved.is_terminal());
+    assert!(!RequestStatus::Fulfilled.is_terminal());
+
+    assert!(RequestStatus::Completed.is_terminal());
+    assert!(RequestStatus::Rejected.is_terminal());
+    assert!(RequestStatus::Cancelled.is_terminal());
+}
+
+#[test]
+fn test_blood_request_validate() {
+    let env = Env::default();
+
+    let request = sample_request(&env, RequestStatus::Pending);
+    assert!(request.validate(1000u64).is_ok());
+}
+
+#[test]
+fn test_blood_request_validate_quantity() {
+    let env = Env::default();
+
+    let mut request = sample_request(&env, RequestStatus::Pending);
+    request.quantity_ml = 50;
+    assert_eq!(request.validate(1000), Err(ContractError::InvalidQuantity));
+}
+
+#[test]
+fn test_blood_request_validate_required_by_ordering() {
+    let env = Env::default();
+
+    let mut request = sample_request(&env, RequestStatus::Pending);
+    request.required_by = request.created_at;
+    assert_eq!(
+        request.validate(1000),
+        Err(ContractError::InvalidRequiredBy)
+    );
+}
+
+#[test]
+fn test_blood_request_validate_delivery_address() {
+    let env = Env::default();
+
+    let mut request = sample_request(&env, RequestStatus::Pending);
+    request.delivery_address = String::from_str(&env, "  ");
+    assert_eq!(
+        request.validate(1000),
+        Err(ContractError::InvalidDeliveryAddress)
+    );
+
+    request.delivery_address =
+        String::from_bytes(&env, &[b'x'; MAX_DELIVERY_ADDRESS_LEN as usize + 1]);
+    assert_eq!(
+        request.validate(1000),
+        Err(ContractError::DeliveryAddressTooLong)
+    );
+}
+
+#[test]
+fn test_blood_request_validate_assigned_units_status() {
+    let env = Env::default();
+
+    for status in [RequestStatus::Pending, RequestStatus::Rejected] {
+        let mut request = sample_request(&env, status);
+        request.assigned_units = vec![&env, 1];
+        assert_eq!(
+            request.validate(1000),
+            Err(ContractError::UnitsAssignedBeforeApproval)
+        );
+    }
+
+    let mut request = sample_request(&env, RequestStatus::Approved);
+    request.assigned_units = vec![&env, 1];
+    assert!(request.validate(1000).is_ok());
+}
+
+#[test]
+fn test_blood_request_validate_fulfilled_at() {
+    let env = Env::default();
+
+    for status in [
+        RequestStatus::Pending,
+        RequestStatus::Approved,
+        RequestStatus::Cancelled,
+        RequestStatus::Expired,
+    ] {
+        let mut request = sample_request(&env, status);
+        request.fulfilled_at = Some(1500);
+        assert_eq!(
+            request.validate(1500),
+            Err(ContractError::InvalidFulfilledAt)
+        );
+    }
+
+    let mut request = sample_request(&env, RequestStatus::Completed);
+    request.fulfilled_at = Some(1500);
+    assert!(request.validate(1500).is_ok());
+
+    // Cannot be fulfilled before it was created
+    request.fulfilled_at = Some(request.created_at - 1);
+    assert_eq!(
+        request.validate(1500),
+        Err(ContractError::InvalidFulfilledAt)
+    );
+}
+
+#[test]
+fn test_blood_request_validate_all_blood_types() {
+    let env = Env::default();
+
+    for blood_type in [
+        BloodType::APositive,
+        BloodType::ANegative,
+        BloodType::BPositive,
+        BloodType::BNegative,
+        BloodType::ABPositive,
+        BloodType::ABNegative,
+        BloodType::OPositive,
+        BloodType::ONegative,
+    ] {
+        let mut request = sample_request(&env, RequestStatus::Pending);
+        request.blood_type = blood_type;
+        assert!(request.validate(1000).is_ok());
+    }
+}
+
+#[test]
+fn test_blood_request_is_overdue() {
+    let env = Env::default();
+
+    let request = sample_request(&env, RequestStatus::Pending);
+
+    assert!(!request.is_overdue(1500u64)); // Before deadline
+    assert!(!request.is_overdue(2000u64)); // At deadline
+    assert!(request.is_overdue(2001u64)); // After deadline
+}
+
+#[test]
+fn test_blood_request_time_remaining() {
+    let env = Env::default();
+
+    let request = sample_request(&env, RequestStatus::Pending);
+
+    assert_eq!(request.time_remaining(1000u64), 1000i64);
+    assert_eq!(request.time_remaining(1500u64), 500i64);
+    assert_eq!(request.time_remaining(2000u64), 0i64);
+    assert_eq!(request.time_remaining(2500u64), -500i64); // Overdue
+}
+
+#[test]
+fn test_blood_request_can_fulfill() {
+    let env = Env::default();
+
+    let mut request = sample_request(&env, RequestStatus::Approved);
+
+    // Can fulfill when Approved and not overdue
+    assert!(request.can_fulfill(1500u64));
+
+    // Cannot fulfill when overdue
+    assert!(!request.can_fulfill(2001u64));
+
+    // Cannot fulfill when not Approved
+    request.status = RequestStatus::Pending;
+    assert!(!request.can_fulfill(1500u64));
+}
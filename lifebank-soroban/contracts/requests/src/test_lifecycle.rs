@@ -0,0 +1,293 @@
+//! Exhaustive and randomized checks of the request lifecycle
+//!
+//! The transition table is pinned against `TRANSITIONS`, then the contract
+//! is driven through pseudo-random operation sequences and the storage
+//! invariants are checked after every step.
+
+use crate::storage::{self, PagedIndex};
+use crate::testutils::{authorized_hospital, setup, RequestFixture};
+use crate::types::{BloodType, RequestStatus, UnitAssignment, UrgencyLevel};
+use crate::RequestContractClient;
+use lifebank_common::testutils::advance_hours;
+use soroban_sdk::{testutils::Ledger, vec, Address, Env, Map, String, Vec};
+
+const STATUSES: [RequestStatus; 8] = [
+    RequestStatus::Pending,
+    RequestStatus::Approved,
+    RequestStatus::Fulfilled,
+    RequestStatus::InDelivery,
+    RequestStatus::Completed,
+    RequestStatus::Rejected,
+    RequestStatus::Cancelled,
+    RequestStatus::Expired,
+];
+
+const URGENCIES: [UrgencyLevel; 3] = [
+    UrgencyLevel::Critical,
+    UrgencyLevel::Urgent,
+    UrgencyLevel::Normal,
+];
+
+/// Allowed `update_request_status` transitions, rows are the current status
+/// and columns the new one, both in `STATUSES` order
+///
+/// ```text
+///              Pend Appr Fulf InDl Comp Rejc Canc Expd
+/// Pending       .    x    .    .    .    x    x    x
+/// Approved      .    .    x    x    .    .    x    x
+/// Fulfilled     .    .    .    x    x    .    .    .
+/// InDelivery    .    .    .    .    x    .    .    .
+/// Completed     .    .    .    .    .    .    .    .
+/// Rejected      .    .    .    .    .    .    .    .
+/// Cancelled     .    .    .    .    .    .    .    .
+/// Expired       .    .    .    .    .    .    .    .
+/// ```
+const TRANSITIONS: [[bool; 8]; 8] = {
+    const X: bool = true;
+    const O: bool = false;
+    [
+        [O, X, O, O, O, X, X, X],
+        [O, O, X, X, O, O, X, X],
+        [O, O, O, X, X, O, O, O],
+        [O, O, O, O, X, O, O, O],
+        [O, O, O, O, O, O, O, O],
+        [O, O, O, O, O, O, O, O],
+        [O, O, O, O, O, O, O, O],
+        [O, O, O, O, O, O, O, O],
+    ]
+};
+
+/// Whether a status change observed on a stored request is legal
+///
+/// Besides the table, `revert_approval` returns Approved requests to
+/// Pending and `force_complete` closes any approved, non-terminal request.
+fn is_legal_change(from: RequestStatus, to: RequestStatus) -> bool {
+    from.can_transition_to(&to)
+        || (from == RequestStatus::Approved && to == RequestStatus::Pending)
+        || (to == RequestStatus::Completed
+            && matches!(
+                from,
+                RequestStatus::Approved | RequestStatus::Fulfilled | RequestStatus::InDelivery
+            ))
+}
+
+#[test]
+fn test_transition_table_matches_matrix() {
+    for (i, from) in STATUSES.iter().enumerate() {
+        for (j, to) in STATUSES.iter().enumerate() {
+            assert_eq!(
+                from.can_transition_to(to),
+                TRANSITIONS[i][j],
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+        }
+    }
+}
+
+#[test]
+fn test_terminal_and_cancel_match_matrix() {
+    for (i, status) in STATUSES.iter().enumerate() {
+        let has_exit = TRANSITIONS[i].iter().any(|allowed| *allowed);
+        assert_eq!(status.is_terminal(), !has_exit, "{:?}", status);
+        assert_eq!(
+            status.can_cancel(),
+            TRANSITIONS[i][6],
+            "{:?} can_cancel",
+            status
+        );
+        // Nothing transitions back into the initial state or to itself
+        assert!(!status.can_transition_to(&RequestStatus::Pending));
+        assert!(!status.can_transition_to(status));
+    }
+}
+
+/// Check every storage invariant of the request contract
+fn assert_invariants(env: &Env, client: &RequestContractClient<'_>, contract_id: &Address) {
+    let count = client.get_request_count();
+
+    // Status index membership, keyed by request ID
+    let mut indexed = Map::new(env);
+    let mut indexed_len = 0u64;
+    for status in STATUSES {
+        let ids = client.get_requests_by_status(&status);
+        let len = env.as_contract(contract_id, || {
+            storage::get_index_len(env, PagedIndex::Status(status))
+        });
+        assert_eq!(ids.len(), len);
+        indexed_len += len as u64;
+        for id in ids.iter() {
+            indexed.set(id, status);
+        }
+    }
+    assert_eq!(indexed_len, count, "status indexes must hold every request");
+    assert_eq!(
+        indexed.len() as u64,
+        count,
+        "a request is in two status indexes"
+    );
+
+    let mut by_urgency = 0u64;
+    for urgency in URGENCIES {
+        by_urgency += env.as_contract(contract_id, || {
+            storage::get_index_len(env, PagedIndex::Urgency(urgency))
+        }) as u64;
+    }
+    assert_eq!(
+        by_urgency, count,
+        "urgency indexes must hold every request once"
+    );
+
+    let pending = client.get_requests_by_status(&RequestStatus::Pending);
+    let queue = client.get_pending_queue(&None);
+    assert_eq!(queue.len(), pending.len());
+    for id in queue.iter() {
+        assert!(pending.contains(id), "queued request {} is not Pending", id);
+    }
+
+    for id in 1..=count {
+        let request = client.get_request(&id);
+        assert_eq!(indexed.get(id), Some(request.status), "request {}", id);
+
+        match request.status {
+            RequestStatus::Fulfilled | RequestStatus::Completed => {
+                assert!(
+                    request.fulfilled_at.is_some(),
+                    "request {} has no fulfilled_at",
+                    id
+                )
+            }
+            RequestStatus::InDelivery => {}
+            _ => assert!(
+                request.fulfilled_at.is_none(),
+                "request {} has fulfilled_at",
+                id
+            ),
+        }
+        if request.status == RequestStatus::InDelivery {
+            assert!(
+                !request.assigned_units.is_empty(),
+                "request {} has no units",
+                id
+            );
+        }
+        assert!(request.validate(env.ledger().timestamp()).is_ok());
+    }
+}
+
+/// Drive the contract through `steps` pseudo-random operations
+fn run_random_lifecycle(seed: u64, steps: u32) {
+    let (env, admin, client, contract_id) = setup();
+    let hospital = authorized_hospital(&env, &client);
+    env.ledger().set_timestamp(1000);
+    let reason = String::from_str(&env, "Lifecycle check");
+
+    // Deterministic pseudo-random sequence (LCG)
+    let mut seed = seed;
+    let mut next = |bound: u64| {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) % bound
+    };
+
+    let mut statuses = Vec::new(&env);
+    let mut next_unit = 1u64;
+    for _ in 0..steps {
+        let created = client.get_request_count();
+        let target = if created == 0 { 1 } else { next(created) + 1 };
+
+        match next(10) {
+            0 | 1 => {
+                let now = env.ledger().timestamp();
+                RequestFixture::new(&env)
+                    .by(&hospital)
+                    .with_urgency(URGENCIES[next(3) as usize])
+                    .required_by(now + 86400 + next(2 * 86400))
+                    .create(&client);
+            }
+            2 => {
+                let _ = client.try_approve_request(&target);
+            }
+            3 => {
+                let _ = client.try_update_request_status(&target, &RequestStatus::Rejected);
+            }
+            4 => {
+                let _ = client.try_cancel_request(&target, &hospital);
+            }
+            5 => {
+                let _ = client.try_update_request_status(&target, &RequestStatus::Fulfilled);
+            }
+            6 => {
+                // Dispatch: units are assigned before the request ships
+                let units = vec![
+                    &env,
+                    UnitAssignment {
+                        unit_id: next_unit,
+                        blood_type: BloodType::APositive,
+                        quantity_ml: 450,
+                        bank_id: admin.clone(),
+                    },
+                ];
+                next_unit += 1;
+                let status = client
+                    .try_get_request(&target)
+                    .ok()
+                    .map(|r| r.unwrap().status);
+                if matches!(
+                    status,
+                    Some(RequestStatus::Approved | RequestStatus::Fulfilled)
+                ) && client.try_assign_blood_units(&target, &units).is_ok()
+                {
+                    let _ = client.try_update_request_status(&target, &RequestStatus::InDelivery);
+                }
+            }
+            7 => {
+                let _ = client.try_complete_request(&hospital, &target);
+            }
+            8 => {
+                if next(2) == 0 {
+                    let _ = client.try_revert_approval(&admin, &target, &reason);
+                } else {
+                    let _ = client.try_force_complete(&admin, &target, &reason);
+                }
+            }
+            _ => {
+                advance_hours(&env, 1 + next(24));
+                let _ = client.try_expire_overdue_requests(&None);
+            }
+        }
+
+        // Every observed status change must be a legal one
+        for id in 1..=client.get_request_count() {
+            let status = client.get_request(&id).status;
+            let index = (id - 1) as u32;
+            if index < statuses.len() {
+                let before = statuses.get_unchecked(index);
+                if before != status {
+                    assert!(
+                        is_legal_change(before, status),
+                        "request {}: {:?} -> {:?}",
+                        id,
+                        before,
+                        status
+                    );
+                    statuses.set(index, status);
+                }
+            } else {
+                assert_eq!(status, RequestStatus::Pending);
+                statuses.push_back(status);
+            }
+        }
+
+        assert_invariants(&env, &client, &contract_id);
+    }
+}
+
+#[test]
+fn test_random_lifecycles_preserve_invariants() {
+    for seed in [0x2545_f491_4f6c_dd1d, 0xdead_beef, 42] {
+        run_random_lifecycle(seed, 50);
+    }
+}
@@ -0,0 +1,222 @@
+//! Fixtures for tests that need a live request contract
+//!
+//! Compiled for this crate's tests and, through the `testutils` feature, for
+//! the integration tests.
+//!
+//! ```ignore
+//! let (env, admin, client, _contract_id) = testutils::setup();
+//! let request_id = RequestFixture::new(&env)
+//!     .with_urgency(UrgencyLevel::Critical)
+//!     .required_by(env.ledger().timestamp() + 2 * SECONDS_PER_HOUR)
+//!     .create(&client);
+//! ```
+
+use crate::error::ContractError;
+use crate::types::{BloodType, RequestMetadata, RequestOptions, UrgencyLevel};
+use crate::{RequestContract, RequestContractClient};
+use lifebank_common::testutils::SECONDS_PER_DAY;
+use soroban_sdk::{
+    testutils::Address as _, vec, Address, BytesN, ConversionError, Env, InvokeError, String,
+};
+
+/// Register and initialize a request contract with auth mocked
+///
+/// Returns `(env, admin, client, contract_id)`. No hospitals are seeded;
+/// the admin may create requests on its own behalf.
+pub fn setup<'a>() -> (Env, Address, RequestContractClient<'a>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(RequestContract, ());
+    let client = RequestContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin, &vec![&env], &0);
+
+    (env, admin, client, contract_id)
+}
+
+/// Generate a hospital address and authorize it
+pub fn authorized_hospital(env: &Env, client: &RequestContractClient<'_>) -> Address {
+    let hospital = Address::generate(env);
+    client.authorize_hospital(&hospital, &None);
+    hospital
+}
+
+/// Metadata with a fresh patient id and placeholder procedure and notes
+pub fn sample_metadata(env: &Env) -> RequestMetadata {
+    RequestMetadata {
+        patient_id: Address::generate(env),
+        procedure: String::from_str(env, "Surgery"),
+        notes: String::from_str(env, "Notes"),
+    }
+}
+
+/// Builder for a `create_request` call
+///
+/// Defaults to an urgent 450 ml A+ request, exact match only, due one day
+/// after the ledger time at which it is created, delivered to
+/// "123 Hospital Street" with `sample_metadata`. Without `by` the request
+/// is created by a freshly authorized hospital.
+pub struct RequestFixture {
+    env: Env,
+    hospital: Option<Address>,
+    blood_type: BloodType,
+    quantity_ml: u32,
+    urgency: UrgencyLevel,
+    required_by: Option<u64>,
+    delivery_address: String,
+    metadata: Option<RequestMetadata>,
+    options: RequestOptions,
+}
+
+impl RequestFixture {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            env: env.clone(),
+            hospital: None,
+            blood_type: BloodType::APositive,
+            quantity_ml: 450,
+            urgency: UrgencyLevel::Urgent,
+            required_by: None,
+            delivery_address: String::from_str(env, "123 Hospital Street"),
+            metadata: None,
+            options: RequestOptions::new(env),
+        }
+    }
+
+    /// Create the request as `hospital`, which must already be authorized
+    pub fn by(mut self, hospital: &Address) -> Self {
+        self.hospital = Some(hospital.clone());
+        self
+    }
+
+    pub fn with_blood_type(mut self, blood_type: BloodType) -> Self {
+        self.blood_type = blood_type;
+        self
+    }
+
+    pub fn with_quantity(mut self, quantity_ml: u32) -> Self {
+        self.quantity_ml = quantity_ml;
+        self
+    }
+
+    pub fn with_urgency(mut self, urgency: UrgencyLevel) -> Self {
+        self.urgency = urgency;
+        self
+    }
+
+    /// Absolute deadline
+    pub fn required_by(mut self, required_by: u64) -> Self {
+        self.required_by = Some(required_by);
+        self
+    }
+
+    pub fn with_delivery_address(mut self, delivery_address: &str) -> Self {
+        self.delivery_address = String::from_str(&self.env, delivery_address);
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: RequestMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Allow compatible (not only identical) blood types to be assigned
+    pub fn accept_compatible(mut self) -> Self {
+        self.options.accept_compatible = true;
+        self
+    }
+
+    /// Second signer for critical requests
+    pub fn with_co_signer(mut self, co_signer: &Address) -> Self {
+        self.options.co_signer = Some(co_signer.clone());
+        self
+    }
+
+    /// Requesting department or ward
+    pub fn in_department(mut self, department: &str) -> Self {
+        self.options.department = String::from_str(&self.env, department);
+        self
+    }
+
+    /// Physician who ordered the blood
+    pub fn ordered_by(mut self, physician_id: &str) -> Self {
+        self.options.physician_id = String::from_str(&self.env, physician_id);
+        self
+    }
+
+    /// Small-volume pediatric request
+    pub fn pediatric(mut self) -> Self {
+        self.options.pediatric = true;
+        self
+    }
+
+    /// Shipped across a regional boundary (needs a second approval)
+    pub fn cross_region(mut self) -> Self {
+        self.options.cross_region = true;
+        self
+    }
+
+    /// Deduplicate retries under `key`
+    pub fn with_idempotency_key(mut self, key: &BytesN<32>) -> Self {
+        self.options.idempotency_key = Some(key.clone());
+        self
+    }
+
+    /// Contact to call at the delivery point
+    pub fn with_contact(mut self, contact: &str) -> Self {
+        self.options.contact = Some(String::from_str(&self.env, contact));
+        self
+    }
+
+    /// Create the request and return its id, panicking on contract errors
+    pub fn create(self, client: &RequestContractClient<'_>) -> u64 {
+        let (hospital, required_by, metadata) = self.resolve(client);
+        client.create_request(
+            &hospital,
+            &self.blood_type,
+            &self.quantity_ml,
+            &self.urgency,
+            &required_by,
+            &self.delivery_address,
+            &metadata,
+            &self.options,
+        )
+    }
+
+    /// Create the request and return the raw `try_create_request` result
+    #[allow(clippy::type_complexity)]
+    pub fn try_create(
+        self,
+        client: &RequestContractClient<'_>,
+    ) -> Result<Result<u64, ConversionError>, Result<ContractError, InvokeError>> {
+        let (hospital, required_by, metadata) = self.resolve(client);
+        client.try_create_request(
+            &hospital,
+            &self.blood_type,
+            &self.quantity_ml,
+            &self.urgency,
+            &required_by,
+            &self.delivery_address,
+            &metadata,
+            &self.options,
+        )
+    }
+
+    fn resolve(&self, client: &RequestContractClient<'_>) -> (Address, u64, RequestMetadata) {
+        let hospital = self
+            .hospital
+            .clone()
+            .unwrap_or_else(|| authorized_hospital(&self.env, client));
+        let required_by = self
+            .required_by
+            .unwrap_or_else(|| self.env.ledger().timestamp() + SECONDS_PER_DAY);
+        let metadata = self
+            .metadata
+            .clone()
+            .unwrap_or_else(|| sample_metadata(&self.env));
+        (hospital, required_by, metadata)
+    }
+}
@@ -1,28 +1,1157 @@
-use soroban_sdk::{contracttype, Address, Map, String};
+use crate::error::ContractError;
+use crate::validation;
+use soroban_sdk::{contracttype, Address, BytesN, Env, Map, String, Symbol, Vec};
 
-#[derive(Clone, Debug, PartialEq)]
+/// Contract version type shared with the inventory contract
+pub use lifebank_common::ContractVersion;
+
+/// Blood type shared with the inventory contract
+use lifebank_common::compatibility;
+pub use lifebank_common::BloodType;
+
+/// Layout version of newly written `BloodRequest` records
+pub const BLOOD_REQUEST_SCHEMA: u32 = 1;
+
+/// Width of the deadline component of a priority score
+///
+/// Larger than any realistic Unix timestamp, so urgency always dominates and
+/// the deadline only orders requests of the same urgency.
+pub const PRIORITY_DEADLINE_SPAN: u64 = 1 << 40;
+
+/// Urgency level of a blood request
 #[contracttype]
-pub enum DataKey {
-    BloodUnit(u64),      // unit_id
-    BankUnits(Address),  // bank_id -> Vec<u64>
-    DonorUnits(Address), // donor_id -> Vec<u64>
-    Registrar(Address),  // Authorization status
-    Admin,               // The Admin Address
-    UnitIdCounter,       // Internal counter for IDs
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum UrgencyLevel {
+    /// Life-threatening, 1 hour max fulfillment
+    Critical,
+    /// High priority, 6 hours max fulfillment
+    Urgent,
+    /// Standard priority, 24 hours max fulfillment
+    Normal,
+}
+
+impl UrgencyLevel {
+    /// Relative priority used for sorting (higher is more urgent)
+    pub fn priority_weight(&self) -> u32 {
+        match self {
+            UrgencyLevel::Critical => 3,
+            UrgencyLevel::Urgent => 2,
+            UrgencyLevel::Normal => 1,
+        }
+    }
+
+    /// Maximum fulfillment time in seconds for SLA tracking
+    pub fn max_fulfillment_time(&self) -> u64 {
+        match self {
+            UrgencyLevel::Critical => 3600,
+            UrgencyLevel::Urgent => 6 * 3600,
+            UrgencyLevel::Normal => 24 * 3600,
+        }
+    }
+}
+
+/// Lifecycle status of a blood request
+///
+/// Status transitions follow this flow:
+/// Pending -> Approved -> Fulfilled -> InDelivery -> Completed
+///         \-> Rejected
+/// Pending/Approved -> Cancelled, Expired
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum RequestStatus {
+    /// Initial state, awaiting approval
+    Pending,
+    /// Approved by the blood bank admin, awaiting fulfillment
+    Approved,
+    /// Blood units assigned and being prepared
+    Fulfilled,
+    /// Blood units are on their way to the hospital
+    InDelivery,
+    /// Request completed successfully
+    Completed,
+    /// Request rejected by the blood bank admin
+    Rejected,
+    /// Request cancelled by the hospital or admin
+    Cancelled,
+    /// Deadline passed before the request was fulfilled
+    Expired,
+}
+
+impl RequestStatus {
+    /// Check if transition from current status to new status is valid
+    ///
+    /// Valid transitions:
+    /// - Pending -> Approved, Rejected, Cancelled, Expired
+    /// - Approved -> Fulfilled, InDelivery, Cancelled, Expired
+    /// - Fulfilled -> InDelivery, Completed
+    /// - InDelivery -> Completed
+    /// - Completed, Rejected, Cancelled, Expired -> (terminal states)
+    pub fn can_transition_to(&self, new_status: &RequestStatus) -> bool {
+        use RequestStatus::*;
+
+        matches!(
+            (self, new_status),
+            (Pending, Approved)
+                | (Pending, Rejected)
+                | (Pending, Cancelled)
+                | (Pending, Expired)
+                | (Approved, Fulfilled)
+                | (Approved, InDelivery)
+                | (Approved, Cancelled)
+                | (Approved, Expired)
+                | (Fulfilled, InDelivery)
+                | (Fulfilled, Completed)
+                | (InDelivery, Completed)
+        )
+    }
+
+    /// Check if this status is a terminal state
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            RequestStatus::Completed
+                | RequestStatus::Rejected
+                | RequestStatus::Cancelled
+                | RequestStatus::Expired
+        )
+    }
+
+    /// Check if a request in this status can still be cancelled
+    pub fn can_cancel(&self) -> bool {
+        matches!(self, RequestStatus::Pending | RequestStatus::Approved)
+    }
+}
+
+/// Authority whose sign-off a cross-region request needs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum ApprovalAuthority {
+    /// Contract admin, the approver of every request
+    Admin,
+    /// Regional authority set with `set_regional_authority`
+    Regional,
+}
+
+impl ApprovalAuthority {
+    /// Bit recorded in `BloodRequest::approvals`
+    pub fn bit(&self) -> u32 {
+        match self {
+            ApprovalAuthority::Admin => 1,
+            ApprovalAuthority::Regional => 2,
+        }
+    }
+}
+
+/// Why the blood bank rejected a request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum RejectionReason {
+    /// Not enough matching stock to serve the request
+    InsufficientStock,
+    /// Clinical justification missing or not accepted
+    InvalidClinicalJustification,
+    /// Same need is already covered by another request
+    DuplicateRequest,
+    /// Hospital is over its allocation
+    HospitalQuotaExceeded,
+    /// Anything else, including rejections recorded without a reason
+    Other,
+}
+
+/// Why a request expired without being fulfilled
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum ExpiryReason {
+    /// Deadline passed while the request was still awaiting approval
+    NotApproved,
+    /// Deadline passed after approval but before fulfillment
+    NotFulfilled,
+}
+
+/// Additional clinical context attached to a request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestMetadata {
+    /// Patient identifier
+    pub patient_id: Address,
+
+    /// Medical procedure requiring blood
+    pub procedure: String,
+
+    /// Special requirements or notes
+    pub notes: String,
+}
+
+/// Optional settings of a new request, passed to `create_request`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestOptions {
+    /// Allow assignment of compatible, non-identical blood types
+    pub accept_compatible: bool,
+
+    /// Signer registered by the hospital who co-signs the request; required
+    /// for Critical requests once the hospital has any
+    pub co_signer: Option<Address>,
+
+    /// Requesting department or ward (at most `MAX_DEPARTMENT_LEN` bytes);
+    /// empty if not recorded
+    pub department: String,
+
+    /// Identifier of the ordering physician, such as a license number (at
+    /// most `MAX_PHYSICIAN_ID_LEN` bytes); empty if not recorded
+    pub physician_id: String,
+
+    /// Small-volume (neonatal/pediatric) request; the quantity floor drops
+    /// to the configured pediatric minimum
+    pub pediatric: bool,
+
+    /// Shipped across a regional boundary; approval also needs the
+    /// configured regional authority
+    pub cross_region: bool,
+
+    /// Client-chosen key that makes retries safe: while the key is live, a
+    /// repeat create from the same hospital with the same key returns the
+    /// original request ID
+    pub idempotency_key: Option<BytesN<32>>,
+
+    /// Phone number or other contact at the delivery point (at most
+    /// `MAX_CONTACT_LEN` bytes); readable only through `get_contact`
+    pub contact: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+impl RequestOptions {
+    /// Exact-match, unsigned, adult request without a department
+    pub fn new(env: &Env) -> Self {
+        Self {
+            accept_compatible: false,
+            co_signer: None,
+            department: String::from_str(env, ""),
+            physician_id: String::from_str(env, ""),
+            pediatric: false,
+            cross_region: false,
+            idempotency_key: None,
+            contact: None,
+        }
+    }
+}
+
+/// Complete blood request record stored in the request contract
+///
+/// # Storage Keys
+/// - Primary key: `id` (u64)
+/// - Secondary indexes: `hospital_id`, `blood_type`, `status`, `urgency`
 #[contracttype]
-pub struct BloodUnit {
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BloodRequest {
+    /// Layout version the record was written with (`BLOOD_REQUEST_SCHEMA`
+    /// for new records)
+    pub schema: u32,
+
+    /// Unique identifier for this request
     pub id: u64,
+
+    /// Hospital requesting blood
+    pub hospital_id: Address,
+
+    /// Type of blood requested
+    pub blood_type: BloodType,
+
+    /// Quantity in milliliters
+    pub quantity_ml: u32,
+
+    /// Priority level
+    pub urgency: UrgencyLevel,
+
+    /// Current lifecycle status
+    pub status: RequestStatus,
+
+    /// Unix timestamp (seconds) when the request was created
+    pub created_at: u64,
+
+    /// Unix timestamp (seconds) by which blood is required
+    pub required_by: u64,
+
+    /// Unix timestamp (seconds) when the request was fulfilled
+    pub fulfilled_at: Option<u64>,
+
+    /// Blood unit IDs (inventory contract) assigned to this request
+    pub assigned_units: Vec<u64>,
+
+    /// Volume and shipping bank of each assigned unit, in `assigned_units` order
+    pub assignments: Vec<AssignedUnit>,
+
+    /// Where the blood should be delivered
+    pub delivery_address: String,
+
+    /// Patient info, procedure and notes
+    pub metadata: RequestMetadata,
+
+    /// Queue priority computed from urgency and deadline (higher is served first)
+    pub priority_score: u64,
+
+    /// Whether compatible (non-identical) blood types may be assigned
+    pub accept_compatible: bool,
+
+    /// Set when the admin closed the request out with `force_complete`
+    pub completion_override: bool,
+
+    /// Hospital-registered signer who co-signed the request
+    pub co_signer: Option<Address>,
+
+    /// Requesting department or ward (e.g. "ER"); empty when not given
+    pub department: String,
+
+    /// Ordering physician (e.g. license number); empty when not given
+    pub physician_id: String,
+
+    /// Small-volume (neonatal/pediatric) request, prepared as aliquots
+    pub pediatric: bool,
+
+    /// Extended phenotype tags every assigned unit must carry
+    /// (e.g. `kell_neg`); empty when any unit of a matching type will do
+    pub required_phenotype: Vec<Symbol>,
+
+    /// Needs the regional authority's approval as well as the admin's
+    pub cross_region: bool,
+
+    /// `ApprovalAuthority` bits collected while Pending (cross-region
+    /// requests only); cleared when an approval is reverted
+    pub approvals: u32,
+
+    /// Unix timestamp (seconds) when the request was last approved
+    pub approved_at: Option<u64>,
+
+    /// Address whose approval made the request Approved; None until then
+    /// and again once the approval is reverted
+    pub approved_by: Option<Address>,
+
+    /// Courier the admin assigned to deliver the request
+    pub courier: Option<Address>,
+
+    /// Expected arrival (Unix seconds) while InDelivery; cleared on completion
+    pub delivery_eta: Option<u64>,
+
+    /// Set when the request was approved but expired before it was fulfilled
+    pub sla_breached: bool,
+}
+
+/// An authorized hospital with its current standing, for admin screens
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HospitalEntry {
+    pub hospital: Address,
+
+    /// End of the suspension in effect, if any
+    pub suspended_until: Option<u64>,
+
+    /// Critical requests need one of the hospital's registered co-signers
+    pub requires_co_signer: bool,
+
+    /// When the authorization lapses, or None if it does not
+    pub valid_until: Option<u64>,
+
+    /// Seconds left before the authorization lapses (0 once it has)
+    pub remaining_validity: Option<u64>,
+}
+
+/// Compact view of a request for list screens
+///
+/// Leaves out the delivery address, metadata and assigned units.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestSummary {
+    pub id: u64,
+    pub hospital_id: Address,
+    pub blood_type: BloodType,
+    pub quantity_ml: u32,
+    pub urgency: UrgencyLevel,
+    pub status: RequestStatus,
+    pub required_by: u64,
+    pub created_at: u64,
+    pub sla_breached: bool,
+}
+
+impl From<BloodRequest> for RequestSummary {
+    fn from(request: BloodRequest) -> Self {
+        RequestSummary {
+            id: request.id,
+            hospital_id: request.hospital_id,
+            blood_type: request.blood_type,
+            quantity_ml: request.quantity_ml,
+            urgency: request.urgency,
+            status: request.status,
+            required_by: request.required_by,
+            created_at: request.created_at,
+            sla_breached: request.sla_breached,
+        }
+    }
+}
+
+impl BloodRequest {
+    /// Compute the queue priority for an urgency level and deadline
+    ///
+    /// Urgency weight is the major component; among requests of the same
+    /// urgency, the one with the earlier `required_by` scores higher.
+    pub fn compute_priority_score(urgency: UrgencyLevel, required_by: u64) -> u64 {
+        let deadline = required_by.min(PRIORITY_DEADLINE_SPAN - 1);
+        urgency.priority_weight() as u64 * PRIORITY_DEADLINE_SPAN
+            + (PRIORITY_DEADLINE_SPAN - 1 - deadline)
+    }
+
+    /// Check whether approval still waits on `authority`
+    ///
+    /// Only cross-region requests collect approvals; for any other request
+    /// this is always false.
+    pub fn awaits_approval(&self, authority: ApprovalAuthority) -> bool {
+        self.cross_region && self.approvals & authority.bit() == 0
+    }
+
+    /// Validate that the request data is consistent and valid
+    ///
+    /// Each check fails with an error naming the offending field:
+    /// - Quantity is within acceptable range, down to
+    ///   `MIN_PEDIATRIC_QUANTITY_ML` for pediatric requests (`InvalidQuantity`)
+    /// - created_at is before required_by (`InvalidRequiredBy`)
+    /// - Delivery address is valid (`InvalidDeliveryAddress`,
+    ///   `DeliveryAddressTooLong`)
+    /// - No units are assigned while Pending or Rejected
+    ///   (`UnitsAssignedBeforeApproval`)
+    /// - fulfilled_at is only set once Fulfilled, InDelivery or Completed, and
+    ///   not before created_at (`InvalidFulfilledAt`)
+    pub fn validate(&self, _current_time: u64) -> Result<(), ContractError> {
+        let min_quantity_ml = if self.pediatric {
+            validation::MIN_PEDIATRIC_QUANTITY_ML
+        } else {
+            validation::MIN_REQUEST_QUANTITY_ML
+        };
+        validation::validate_quantity_from(self.quantity_ml, min_quantity_ml)?;
+
+        if self.required_by <= self.created_at {
+            return Err(ContractError::InvalidRequiredBy);
+        }
+
+        validation::validate_delivery_address(&self.delivery_address)?;
+
+        if !self.assigned_units.is_empty()
+            && matches!(
+                self.status,
+                RequestStatus::Pending | RequestStatus::Rejected
+            )
+        {
+            return Err(ContractError::UnitsAssignedBeforeApproval);
+        }
+
+        if let Some(fulfilled_at) = self.fulfilled_at {
+            let fulfilled = matches!(
+                self.status,
+                RequestStatus::Fulfilled | RequestStatus::InDelivery | RequestStatus::Completed
+            );
+            if !fulfilled || fulfilled_at < self.created_at {
+                return Err(ContractError::InvalidFulfilledAt);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check if the request has exceeded its deadline
+    pub fn is_overdue(&self, current_time: u64) -> bool {
+        validation::is_request_overdue(self.required_by, current_time)
+    }
+
+    /// Seconds until the deadline (negative if overdue)
+    pub fn time_remaining(&self, current_time: u64) -> i64 {
+        validation::time_until_deadline(self.required_by, current_time)
+    }
+
+    /// Check if the request can be fulfilled (Approved and not overdue)
+    pub fn can_fulfill(&self, current_time: u64) -> bool {
+        self.status == RequestStatus::Approved && !self.is_overdue(current_time)
+    }
+
+    /// Check if a unit of the given blood type may be assigned to this request
+    pub fn accepts_unit_type(&self, unit_type: BloodType) -> bool {
+        compatibility::is_acceptable_match(unit_type, self.blood_type, self.accept_compatible)
+    }
+
+    /// Total volume of the units assigned to this request
+    pub fn assigned_ml(&self) -> u64 {
+        self.assignments
+            .iter()
+            .map(|unit| unit.quantity_ml as u64)
+            .sum()
+    }
+}
+
+/// Storage key types for efficient querying
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum DataKey {
+    /// Individual blood request by ID
+    BloodRequest(u64),
+
+    /// Counter for generating new request IDs
+    RequestCounter,
+
+    /// Index: Hospital -> Vec<u64> (request IDs)
+    HospitalIndex(Address),
+
+    /// Index: (Hospital, department) -> Vec<u64> (request IDs)
+    DepartmentIndex(Address, String),
+
+    /// Index: (Hospital, physician) -> Vec<u64> (request IDs)
+    PhysicianIndex(Address, String),
+
+    /// Index: Blood type -> Vec<u64> (request IDs)
+    BloodTypeIndex(BloodType),
+
+    /// Legacy monolithic status index -> Vec<u64>, moved into
+    /// `StatusIndexPage` on first access
+    StatusIndex(RequestStatus),
+
+    /// Legacy monolithic urgency index -> Vec<u64>, moved into
+    /// `UrgencyIndexPage` on first access
+    UrgencyIndex(UrgencyLevel),
+
+    /// Index: (Status, page) -> Vec<u64> (at most `INDEX_PAGE_SIZE` request IDs)
+    StatusIndexPage(RequestStatus, u32),
+
+    /// Number of pages of a status index
+    StatusIndexPageCount(RequestStatus),
+
+    /// Page of a status index holding a request: (Status, request ID) -> u32
+    StatusIndexSlot(RequestStatus, u64),
+
+    /// Index: (Urgency, page) -> Vec<u64> (at most `INDEX_PAGE_SIZE` request IDs)
+    UrgencyIndexPage(UrgencyLevel, u32),
+
+    /// Number of pages of an urgency index
+    UrgencyIndexPageCount(UrgencyLevel),
+
+    /// Page of an urgency index holding a request: (Urgency, request ID) -> u32
+    UrgencyIndexSlot(UrgencyLevel, u64),
+
+    /// Index: Day of the deadline (required_by / 86400) -> Vec<u64> (request IDs)
+    DeadlineBucket(u64),
+
+    /// Admin address
+    Admin,
+
+    /// Version of the code that initialized the contract -> ContractVersion
+    Version,
+
+    /// Authorization flag for a hospital
+    AuthorizedHospital(Address),
+
+    /// Hospitals authorized via `authorize_hospital`, in authorization
+    /// order -> Vec<Address>
+    AuthorizedHospitals,
+
+    /// Pending requests ordered by priority score (highest first)
+    PendingQueue,
+
+    /// Reason a request expired
+    ExpiryReason(u64),
+
+    /// Reason a request was rejected
+    RejectionReason(u64),
+
+    /// Contact at the delivery point of a request, kept off the request
+    /// record so only authorized readers see it
+    RequestContact(u64),
+
+    /// Count of requests of a blood type that expired unfulfilled in an epoch
+    UnfulfilledStats(BloodType, u64),
+
+    /// Count of a hospital's approved requests that expired unfulfilled in
+    /// an epoch
+    SlaBreaches(Address, u64),
+
+    /// Count of approved requests that expired unfulfilled in an epoch
+    TotalSlaBreaches(u64),
+
+    /// Last epoch seen by the expiry sweep
+    StatsEpoch,
+
+    /// Audit record of an admin forced completion
+    CompletionOverride(u64),
+
+    /// Inventory contract holding the units reserved for requests
+    InventoryContract,
+
+    /// Demand counters for a blood type in an epoch
+    DemandStats(BloodType, u64),
+
+    /// Admin proposed by the current admin, awaiting acceptance
+    PendingAdmin,
+
+    /// Which groups of operations are paused
+    PauseFlags,
+
+    /// Timestamp until which a hospital is suspended
+    HospitalSuspension(Address),
+
+    /// Timestamp at which a hospital's authorization lapses
+    HospitalValidUntil(Address),
+
+    /// Co-signers registered by a hospital -> Vec<Address>
+    RequestSigners(Address),
+
+    /// Quantities must be a multiple of this many ml (0 = disabled)
+    QuantityStep,
+
+    /// Smallest quantity in ml a pediatric request may ask for
+    PediatricMinQuantity,
+
+    /// Set while a request is on the inventory contract's waitlist
+    Waitlisted(u64),
+
+    /// Second approver of cross-region requests
+    RegionalAuthority,
+
+    /// Seconds after approval during which a hospital may still cancel
+    CancellationGracePeriod,
+
+    /// Complete InDelivery requests once their assignments cover the
+    /// requested quantity -> bool
+    AutoComplete,
+
+    /// Cap on assigned volume above the requested quantity -> AssignmentTolerance
+    AssignmentTolerance,
+
+    /// Rejections per `RejectionReason` in an epoch -> Map<RejectionReason, u32>
+    RejectionStats(u64),
+
+    /// Cancelled or expired requests still listing assigned units, oldest
+    /// first -> Vec<u64>
+    OrphanedAssignments,
+
+    /// (Hospital, idempotency key) -> IdempotencyRecord
+    IdempotencyKey(Address, BytesN<32>),
+
+    /// Activity of a hospital in an epoch -> HospitalReport
+    HospitalReport(Address, u64),
+
+    /// All-time track record of a hospital -> HospitalScore
+    HospitalScore(Address),
+
+    /// Whether state may still be imported -> ImportState (absent once
+    /// closed)
+    ImportState,
+}
+
+/// A unit offered to `propose_allocation`, as seen by the caller
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CandidateUnit {
+    pub unit_id: u64,
     pub blood_type: BloodType,
     pub quantity_ml: u32,
-    pub bank_id: Address,
-    pub donor_id: Option<Address>,
-    pub donation_timestamp: u64,
     pub expiration_timestamp: u64,
-    pub status: BloodStatus,
-    pub metadata: Map<String, String>,
 }
 
-// Ensure BloodType and BloodStatus are also defined here as #[contracttype]
+/// Entry of the priority-ordered pending queue
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingQueueEntry {
+    pub priority_score: u64,
+    pub request_id: u64,
+}
+
+/// Event emitted when a blood request is created
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestCreatedEvent {
+    pub request_id: u64,
+    pub hospital_id: Address,
+    pub blood_type: BloodType,
+    pub quantity_ml: u32,
+    pub urgency: UrgencyLevel,
+    pub required_by: u64,
+    pub co_signer: Option<Address>,
+    pub department: String,
+    pub physician_id: String,
+    pub pediatric: bool,
+    pub cross_region: bool,
+    pub contact: Option<String>,
+    pub created_at: u64,
+}
+
+/// Event emitted when a request status changes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestStatusChangedEvent {
+    pub request_id: u64,
+    pub old_status: RequestStatus,
+    pub new_status: RequestStatus,
+    pub changed_at: u64,
+}
+
+/// A blood unit offered for assignment, with its type, volume and owning
+/// bank as recorded in inventory
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitAssignment {
+    pub unit_id: u64,
+    pub blood_type: BloodType,
+    pub quantity_ml: u32,
+    pub bank_id: Address,
+}
+
+/// A single bag assigned to a request, with the bank shipping it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssignedUnit {
+    pub unit_id: u64,
+    pub quantity_ml: u32,
+    pub bank_id: Address,
+}
+
+/// Event emitted when blood units are assigned to a request
+///
+/// Published once per source bank, with the bank as a topic, so each bank
+/// sees only its own shipments. `units` lists every bag from that bank with
+/// its volume so hospitals can reconcile against individual units, and
+/// `total_ml` is their sum.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitsAssignedEvent {
+    pub request_id: u64,
+    pub bank_id: Address,
+    pub assigned_units: Vec<u64>,
+    pub units: Vec<AssignedUnit>,
+    pub total_ml: u32,
+    pub assigned_at: u64,
+}
+
+/// Event emitted when a hospital changes the delivery address of a request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeliveryAddressUpdatedEvent {
+    pub request_id: u64,
+    pub hospital_id: Address,
+    pub old_address: String,
+    pub new_address: String,
+    pub updated_at: u64,
+}
+
+/// Event emitted when a request goes out for delivery, with who to call at
+/// the delivery point
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeliveryStartedEvent {
+    pub request_id: u64,
+    pub courier: Option<Address>,
+    pub delivery_address: String,
+    pub contact: Option<String>,
+    pub started_at: u64,
+}
+
+/// Event emitted when a hospital changes the contact of a request; the
+/// contact itself is left out
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContactUpdatedEvent {
+    pub request_id: u64,
+    pub hospital_id: Address,
+    pub updated_at: u64,
+}
+
+/// Event emitted when the admin assigns a courier to a request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CourierAssignedEvent {
+    pub request_id: u64,
+    pub courier: Address,
+    pub assigned_at: u64,
+}
+
+/// Event emitted when the expected arrival of a delivery changes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EtaUpdatedEvent {
+    pub request_id: u64,
+    pub old_eta: Option<u64>,
+    pub new_eta: u64,
+    pub updated_by: Address,
+    pub updated_at: u64,
+}
+
+/// Event emitted when a hospital sets the phenotype a request requires
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequiredPhenotypeSetEvent {
+    pub request_id: u64,
+    pub hospital_id: Address,
+    pub required_phenotype: Vec<Symbol>,
+    pub updated_at: u64,
+}
+
+/// Event emitted when a request's urgency or deadline changes its priority
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestReprioritizedEvent {
+    pub request_id: u64,
+    pub urgency: UrgencyLevel,
+    pub required_by: u64,
+    pub priority_score: u64,
+    pub updated_at: u64,
+}
+
+/// Independent pause switches for groups of operations
+///
+/// Cancellation is never pausable.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PauseFlags {
+    /// Blocks `create_request`
+    pub create_paused: bool,
+    /// Blocks `approve_request`
+    pub approve_paused: bool,
+    /// Blocks unit assignment and moves towards delivery and completion
+    pub fulfill_paused: bool,
+}
+
+/// How far the volume assigned to a request may exceed its `quantity_ml`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum AssignmentTolerance {
+    /// No cap on the assigned volume
+    Unlimited,
+    /// At most this percentage of `quantity_ml` on top of it
+    Percent(u32),
+    /// At most this many ml on top of `quantity_ml`
+    Ml(u32),
+}
+
+impl AssignmentTolerance {
+    /// Largest total volume that may be assigned to a request of
+    /// `quantity_ml`, or None when unlimited
+    pub fn max_assignable_ml(&self, quantity_ml: u32) -> Option<u32> {
+        match self {
+            AssignmentTolerance::Unlimited => None,
+            AssignmentTolerance::Percent(percent) => {
+                let extra = quantity_ml as u64 * *percent as u64 / 100;
+                Some(quantity_ml.saturating_add(extra.min(u32::MAX as u64) as u32))
+            }
+            AssignmentTolerance::Ml(extra_ml) => Some(quantity_ml.saturating_add(*extra_ml)),
+        }
+    }
+}
+
+/// A request with its contact and waitlist flag, as carried by
+/// `export_state_page`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportedRequest {
+    pub request: BloodRequest,
+    pub contact: Option<String>,
+    pub waitlisted: bool,
+}
+
+/// An explicitly authorized hospital and its standing, as carried by
+/// `export_state_config`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportedHospital {
+    pub hospital: Address,
+    pub valid_until: Option<u64>,
+    pub suspended_until: Option<u64>,
+    pub signers: Vec<Address>,
+}
+
+/// Counters and configuration, as carried by `export_state_config`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportedConfig {
+    pub request_counter: u64,
+    pub hospitals: Vec<ExportedHospital>,
+    pub pause_flags: PauseFlags,
+    pub inventory_contract: Option<Address>,
+    pub regional_authority: Option<Address>,
+    pub quantity_step: u32,
+    pub pediatric_min_quantity: u32,
+    pub cancellation_grace_period: u64,
+    pub auto_complete: bool,
+    pub assignment_tolerance: AssignmentTolerance,
+    pub orphaned_assignments: Vec<u64>,
+    pub stats_epoch: Option<u64>,
+}
+
+/// One page of a state export
+///
+/// The reason and override records of the page's requests are keyed by
+/// request ID, and only present for requests that have one.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateExportPage {
+    pub requests: Vec<ExportedRequest>,
+    pub expiry_reasons: Map<u64, ExpiryReason>,
+    pub rejection_reasons: Map<u64, RejectionReason>,
+    pub completion_overrides: Vec<CompletionOverride>,
+    /// Cursor of the next page, 0 once the last request is exported
+    pub next_cursor: u64,
+}
+
+/// Whether `import_state_page` and `import_state_config` may still be used
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum ImportState {
+    /// Freshly initialized: nothing imported and no organic activity yet
+    Open,
+    /// Some pages imported; closed by `import_state_config`
+    InProgress,
+}
+
+/// Demand recorded for one blood type in one epoch
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DemandStats {
+    /// Number of requests created
+    pub requests: u32,
+    /// Total quantity requested in milliliters
+    pub total_ml: u64,
+}
+
+/// A hospital's request activity in one epoch (30-day month), for
+/// reconciliation by its finance team
+///
+/// Each figure counts in the epoch the event happened: a request created
+/// in one month and completed in the next adds to `created` and
+/// `requested_ml` of the first and to `completed` and `received_ml` of the
+/// second.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HospitalReport {
+    /// Requests created
+    pub created: u32,
+    /// Requests completed
+    pub completed: u32,
+    /// Requests cancelled
+    pub cancelled: u32,
+    /// Total quantity of the requests created, in milliliters
+    pub requested_ml: u64,
+    /// Total volume assigned to the requests completed, in milliliters
+    pub received_ml: u64,
+}
+
+/// A hospital's track record across all time, for banks weighing who to
+/// serve first when stock is short
+///
+/// `reliability_bps` is informational only: completions as a share of all
+/// closed outcomes counted here, in basis points, or None before the
+/// hospital has any. Nothing in the contract acts on it.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HospitalScore {
+    /// Requests completed
+    pub completed: u32,
+    /// Requests cancelled once approved or later
+    pub cancelled_after_approval: u32,
+    /// Disputes opened by the hospital; no dispute flow exists yet, so
+    /// this stays zero
+    pub disputes_opened: u32,
+    /// Approved requests that expired unserved (see `get_sla_breaches`)
+    pub sla_breaches: u32,
+    /// `completed` over the sum of all four counters, in basis points
+    pub reliability_bps: Option<u32>,
+}
+
+/// Count of unfulfilled requests for one blood type
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BloodTypeCount {
+    pub blood_type: BloodType,
+    pub count: u32,
+}
+
+/// Event emitted when the expiry sweep enters a new epoch
+///
+/// Carries the final unfulfilled totals of the epoch that just closed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnfulfilledStatsRolloverEvent {
+    pub epoch: u64,
+    pub counts: Vec<BloodTypeCount>,
+    pub total: u32,
+}
+
+/// Summary published once per `expire_overdue_requests` call
+///
+/// `first_id` and `last_id` bound the IDs expired by the call (both 0 when
+/// nothing expired). `more_remaining` is set when the limit cut the sweep
+/// short and another call would find more overdue requests.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpirySweepCompletedEvent {
+    pub expired_count: u32,
+    pub first_id: u64,
+    pub last_id: u64,
+    pub more_remaining: bool,
+    pub swept_at: u64,
+}
+
+/// Audit record of a request completed by admin override
+///
+/// Stored per request and published as the `forced_completion` event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompletionOverride {
+    pub request_id: u64,
+    pub admin: Address,
+    pub previous_status: RequestStatus,
+    pub justification: String,
+    pub completed_at: u64,
+}
+
+/// Request created under an idempotency key
+///
+/// Repeat creates with the key return `request_id` until `expires_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IdempotencyRecord {
+    pub request_id: u64,
+    pub expires_at: u64,
+}
+
+/// Event emitted when the admin sends an approved request back to Pending
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalRevertedEvent {
+    pub request_id: u64,
+    pub admin: Address,
+    pub justification: String,
+    pub reverted_at: u64,
+}
+
+/// Event emitted for each approval a cross-region request collects
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalRecordedEvent {
+    pub request_id: u64,
+    pub authority: ApprovalAuthority,
+    pub approver: Address,
+    pub approvals: u32,
+    pub recorded_at: u64,
+}
+
+/// Event emitted when a request becomes Approved, naming the approver
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestApprovedEvent {
+    pub request_id: u64,
+    pub hospital_id: Address,
+    pub approved_by: Address,
+    pub approved_at: u64,
+}
+
+/// Event emitted when the admin rejects a request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestRejectedEvent {
+    pub request_id: u64,
+    pub hospital_id: Address,
+    pub reason: RejectionReason,
+    pub rejected_at: u64,
+}
+
+/// Event emitted when a Critical request takes over a unit reserved for a
+/// less urgent request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReservationPreemptedEvent {
+    pub unit_id: u64,
+    pub from_request_id: u64,
+    pub to_request_id: u64,
+    /// Whether the displaced request was put on the waitlist
+    pub waitlisted: bool,
+    pub preempted_at: u64,
+}
+
+/// Event emitted when the admin clears the assignment of a closed request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrphanedAssignmentsReleasedEvent {
+    pub request_id: u64,
+    pub unit_ids: Vec<u64>,
+    pub released_by: Address,
+    pub released_at: u64,
+}
+
+/// Event emitted when a cancelled request gives up its assigned units
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssignedUnitsReleasedEvent {
+    pub request_id: u64,
+    pub unit_ids: Vec<u64>,
+    pub cancelled_by: Address,
+    pub released_at: u64,
+}
+
+/// Event emitted when the admin proposes a successor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferProposedEvent {
+    pub current_admin: Address,
+    pub proposed_admin: Address,
+    pub proposed_at: u64,
+}
+
+/// Event emitted when the proposed admin accepts the role
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferredEvent {
+    pub old_admin: Address,
+    pub new_admin: Address,
+    pub transferred_at: u64,
+}
+
+/// Event emitted when a pending admin proposal is withdrawn or replaced
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferCancelledEvent {
+    pub admin: Address,
+    pub cancelled_admin: Address,
+    pub cancelled_at: u64,
+}
+
+/// Event emitted when the admin authorizes or revokes a hospital
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HospitalAuthorizationChangedEvent {
+    pub hospital: Address,
+    pub admin: Address,
+    pub changed_at: u64,
+}
+
+/// Event emitted when the admin extends a hospital's authorization
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HospitalRenewedEvent {
+    pub hospital: Address,
+    pub admin: Address,
+    pub valid_until: u64,
+    pub renewed_at: u64,
+}
+
+/// Event emitted when the admin suspends a hospital or lifts its suspension
+///
+/// `suspended_until` is None when the suspension is lifted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HospitalSuspensionChangedEvent {
+    pub hospital: Address,
+    pub admin: Address,
+    pub suspended_until: Option<u64>,
+    pub changed_at: u64,
+}
+
+/// Event emitted when a hospital authorization held only through the admin
+/// role is gained or lost
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImplicitAuthorizationChangedEvent {
+    pub account: Address,
+    pub authorized: bool,
+    pub changed_at: u64,
+}
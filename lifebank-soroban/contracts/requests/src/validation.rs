@@ -1,46 +1,61 @@
 use crate::error::ContractError;
 use crate::storage::{MAX_REQUEST_WINDOW_DAYS, MIN_REQUEST_WINDOW_SECONDS, SECONDS_PER_DAY};
-use soroban_sdk::{Env, String};
+use crate::types::BloodType;
+use soroban_sdk::{Env, String, Symbol, Vec};
 
 /// Minimum quantity for a blood request (100ml = partial unit)
 pub const MIN_REQUEST_QUANTITY_ML: u32 = 100;
 
+/// Lowest pediatric minimum the admin can configure
+pub const MIN_PEDIATRIC_QUANTITY_ML: u32 = 1;
+
+/// Default minimum quantity for a pediatric request (neonatal aliquot)
+pub const DEFAULT_PEDIATRIC_MIN_QUANTITY_ML: u32 = 10;
+
 /// Maximum quantity for a blood request (10000ml = ~22 units)
 /// Large enough for major surgeries or emergency situations
 pub const MAX_REQUEST_QUANTITY_ML: u32 = 10000;
 
+/// Maximum length of a delivery address in bytes
+pub const MAX_DELIVERY_ADDRESS_LEN: u32 = 256;
+
+/// Maximum length of an ordering physician's identifier, in bytes
+pub const MAX_PHYSICIAN_ID_LEN: u32 = 32;
+
+/// Maximum length of a delivery contact, in bytes
+pub const MAX_CONTACT_LEN: u32 = 32;
+
+/// Maximum length of a requesting department, in bytes
+pub const MAX_DEPARTMENT_LEN: u32 = 64;
+
+/// Maximum number of phenotype tags a request can require
+pub const MAX_REQUIRED_PHENOTYPE_TAGS: u32 = 16;
+
+/// Default time after approval during which the hospital may still cancel
+pub const DEFAULT_CANCELLATION_GRACE_SECONDS: u64 = 3600;
+
+/// Longest cancellation grace period the admin can configure (1 day)
+pub const MAX_CANCELLATION_GRACE_SECONDS: u64 = 86400;
+
+/// Furthest ahead a delivery ETA may be set (2 days)
+pub const MAX_DELIVERY_ETA_SECONDS: u64 = 2 * 86400;
+
 /// Validate blood request creation parameters
 ///
 /// Checks:
-/// - Quantity is within acceptable range (100-10000ml)
+/// - Quantity is within acceptable range (`min_quantity_ml`-10000ml)
 /// - required_by is in the future
 /// - required_by is not too far in the future (max 30 days)
-/// - Delivery address is not empty
-use crate::types::BloodType;
-use soroban_sdk::Env;
-
-/// Maximum request quantity (5000ml)
-pub const MAX_REQUEST_QUANTITY_ML: u32 = 5000;
-/// Minimum request quantity (50ml)
-pub const MIN_REQUEST_QUANTITY_ML: u32 = 50;
-/// Maximum days in future for required_by timestamp
-pub const MAX_DAYS_IN_FUTURE: u64 = 30;
-pub const SECONDS_PER_DAY: u64 = 86400;
-
-/// Validate blood request parameters
-///
-/// Checks:
-/// - Quantity is within acceptable range (50-5000ml)
-/// - Required_by is in the future
-/// - Required_by is not too far in the future (max 30 days)
+/// - Delivery address is not blank and not too long
 pub fn validate_request_creation(
     env: &Env,
     quantity_ml: u32,
+    min_quantity_ml: u32,
     required_by: u64,
     delivery_address: &String,
 ) -> Result<(), ContractError> {
     // Validate quantity
-    validate_quantity(quantity_ml)?;
+    validate_quantity_from(quantity_ml, min_quantity_ml)?;
 
     // Validate required_by timestamp
     validate_required_by(env, required_by)?;
@@ -58,13 +73,41 @@ pub fn validate_request_creation(
 ///
 /// # Returns
 /// Ok(()) if valid, Err(InvalidQuantity) if out of range
+#[cfg(test)]
 pub fn validate_quantity(quantity_ml: u32) -> Result<(), ContractError> {
-    if quantity_ml < MIN_REQUEST_QUANTITY_ML || quantity_ml > MAX_REQUEST_QUANTITY_ML {
+    validate_quantity_from(quantity_ml, MIN_REQUEST_QUANTITY_ML)
+}
+
+/// Validate quantity is between a custom floor and the maximum
+///
+/// # Arguments
+/// * `quantity_ml` - Quantity in milliliters
+/// * `min_quantity_ml` - Smallest allowed quantity (lower for pediatric requests)
+///
+/// # Returns
+/// Ok(()) if valid, Err(InvalidQuantity) if out of range
+pub fn validate_quantity_from(quantity_ml: u32, min_quantity_ml: u32) -> Result<(), ContractError> {
+    if !(min_quantity_ml..=MAX_REQUEST_QUANTITY_ML).contains(&quantity_ml) {
         return Err(ContractError::InvalidQuantity);
     }
     Ok(())
 }
 
+/// Validate quantity is a multiple of the configured step
+///
+/// # Arguments
+/// * `quantity_ml` - Quantity in milliliters
+/// * `step_ml` - Required step in milliliters (0 disables the check)
+///
+/// # Returns
+/// Ok(()) if aligned or disabled, Err(QuantityNotAligned) otherwise
+pub fn validate_quantity_step(quantity_ml: u32, step_ml: u32) -> Result<(), ContractError> {
+    if step_ml != 0 && !quantity_ml.is_multiple_of(step_ml) {
+        return Err(ContractError::QuantityNotAligned);
+    }
+    Ok(())
+}
+
 /// Validate required_by timestamp
 ///
 /// Checks:
@@ -76,18 +119,23 @@ pub fn validate_quantity(quantity_ml: u32) -> Result<(), ContractError> {
 /// * `required_by` - Unix timestamp when blood is required
 ///
 /// # Returns
-/// Ok(()) if valid, Err(InvalidRequiredBy) if invalid
+/// Ok(()) if valid, Err(InvalidRequiredBy) if invalid, Err(InvalidTimestamp)
+/// if the window bounds overflow
 pub fn validate_required_by(env: &Env, required_by: u64) -> Result<(), ContractError> {
     let current_time = env.ledger().timestamp();
 
     // required_by must be in the future (at least 1 hour for processing)
-    let min_required_by = current_time + MIN_REQUEST_WINDOW_SECONDS;
+    let min_required_by = current_time
+        .checked_add(MIN_REQUEST_WINDOW_SECONDS)
+        .ok_or(ContractError::InvalidTimestamp)?;
     if required_by < min_required_by {
         return Err(ContractError::InvalidRequiredBy);
     }
 
     // required_by shouldn't be too far in the future (max 30 days)
-    let max_required_by = current_time + (MAX_REQUEST_WINDOW_DAYS * SECONDS_PER_DAY);
+    let max_required_by = current_time
+        .checked_add(MAX_REQUEST_WINDOW_DAYS * SECONDS_PER_DAY)
+        .ok_or(ContractError::InvalidTimestamp)?;
     if required_by > max_required_by {
         return Err(ContractError::InvalidRequiredBy);
     }
@@ -110,46 +158,123 @@ pub fn validate_not_expired(env: &Env, required_by: u64) -> Result<(), ContractE
 
     if current_time >= required_by {
         return Err(ContractError::RequestExpired);
-) -> Result<(), ContractError> {
-    // Validate quantity
-    if quantity_ml < MIN_REQUEST_QUANTITY_ML || quantity_ml > MAX_REQUEST_QUANTITY_ML {
-        return Err(ContractError::InvalidQuantity);
     }
 
-    let current_time = env.ledger().timestamp();
+    Ok(())
+}
 
-    // Required_by must be in the future
-    if required_by <= current_time {
-        return Err(ContractError::InvalidTimestamp);
-    }
+/// Validate a delivery ETA
+///
+/// Checks:
+/// - Must be in the future
+/// - Must not be more than `MAX_DELIVERY_ETA_SECONDS` ahead
+///
+/// # Returns
+/// Ok(()) if valid, Err(InvalidTimestamp) otherwise
+pub fn validate_delivery_eta(env: &Env, eta: u64) -> Result<(), ContractError> {
+    let current_time = env.ledger().timestamp();
 
-    // Required_by shouldn't be too far in the future
-    let max_future = current_time + (MAX_DAYS_IN_FUTURE * SECONDS_PER_DAY);
-    if required_by > max_future {
+    if eta <= current_time || eta - current_time > MAX_DELIVERY_ETA_SECONDS {
         return Err(ContractError::InvalidTimestamp);
     }
 
     Ok(())
 }
 
-/// Validate delivery address is not empty
+/// Validate delivery address is not blank and within the length limit
 ///
 /// # Arguments
 /// * `delivery_address` - The delivery address string
 ///
 /// # Returns
-/// Ok(()) if valid, Err(InvalidDeliveryAddress) if empty
+/// Ok(()) if valid, Err(DeliveryAddressTooLong) if longer than
+/// `MAX_DELIVERY_ADDRESS_LEN` bytes, Err(InvalidDeliveryAddress) if empty or
+/// only whitespace
 pub fn validate_delivery_address(delivery_address: &String) -> Result<(), ContractError> {
-    if delivery_address.len() == 0 {
+    let len = delivery_address.len();
+    if len > MAX_DELIVERY_ADDRESS_LEN {
+        return Err(ContractError::DeliveryAddressTooLong);
+    }
+
+    let mut buf = [0u8; MAX_DELIVERY_ADDRESS_LEN as usize];
+    let bytes = &mut buf[..len as usize];
+    delivery_address.copy_into_slice(bytes);
+    if bytes.iter().all(|b| b.is_ascii_whitespace()) {
         return Err(ContractError::InvalidDeliveryAddress);
-pub fn validate_delivery_address(address: &soroban_sdk::String) -> Result<(), ContractError> {
-    if address.len() == 0 {
+    }
+    Ok(())
+}
+
+/// Validate the requesting department is within the length limit
+///
+/// An empty department means none was given.
+///
+/// # Returns
+/// Ok(()) if valid, Err(DepartmentTooLong) if longer than
+/// `MAX_DEPARTMENT_LEN` bytes
+pub fn validate_department(department: &String) -> Result<(), ContractError> {
+    if department.len() > MAX_DEPARTMENT_LEN {
+        return Err(ContractError::DepartmentTooLong);
+    }
+    Ok(())
+}
+
+/// Validate a delivery contact, when one is given
+///
+/// # Returns
+/// Ok(()) if valid or None, Err(ContactTooLong) if longer than
+/// `MAX_CONTACT_LEN` bytes, Err(InvalidInput) if empty or only whitespace
+pub fn validate_contact(contact: &Option<String>) -> Result<(), ContractError> {
+    let Some(contact) = contact else {
+        return Ok(());
+    };
+
+    let len = contact.len();
+    if len > MAX_CONTACT_LEN {
+        return Err(ContractError::ContactTooLong);
+    }
+
+    let mut buf = [0u8; MAX_CONTACT_LEN as usize];
+    let bytes = &mut buf[..len as usize];
+    contact.copy_into_slice(bytes);
+    if bytes.iter().all(|b| b.is_ascii_whitespace()) {
+        return Err(ContractError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Validate the ordering physician's identifier is within the length limit
+///
+/// An empty identifier means none was given.
+///
+/// # Returns
+/// Ok(()) if valid, Err(PhysicianIdTooLong) if longer than
+/// `MAX_PHYSICIAN_ID_LEN` bytes
+pub fn validate_physician_id(physician_id: &String) -> Result<(), ContractError> {
+    if physician_id.len() > MAX_PHYSICIAN_ID_LEN {
+        return Err(ContractError::PhysicianIdTooLong);
+    }
+    Ok(())
+}
+
+/// Validate the phenotype tags a request requires
+///
+/// # Returns
+/// Ok(()) if valid, Err(InvalidInput) if there are more than
+/// `MAX_REQUIRED_PHENOTYPE_TAGS` tags or a tag is listed twice
+pub fn validate_required_phenotype(tags: &Vec<Symbol>) -> Result<(), ContractError> {
+    if tags.len() > MAX_REQUIRED_PHENOTYPE_TAGS {
         return Err(ContractError::InvalidInput);
     }
+    for (i, tag) in tags.iter().enumerate() {
+        if tags.first_index_of(&tag) != Some(i as u32) {
+            return Err(ContractError::InvalidInput);
+        }
+    }
     Ok(())
 }
 
-/// Validate that required_by allows minimum time for critical requests
+/// Validate that required_by allows minimum time for the urgency level
 ///
 /// Critical requests need at least 1 hour for processing
 /// Urgent requests need at least 4 hours
@@ -158,7 +283,7 @@ pub fn validate_delivery_address(address: &soroban_sdk::String) -> Result<(), Co
 /// # Arguments
 /// * `env` - Contract environment
 /// * `required_by` - Unix timestamp when blood is required
-/// * `is_critical` - Whether this is a critical urgency request
+/// * `urgency_weight` - Priority weight of the request's urgency level
 ///
 /// # Returns
 /// Ok(()) if valid time window, Err if too short
@@ -175,9 +300,9 @@ pub fn validate_urgency_time_window(
     // Urgent (weight 2): 4 hours minimum
     // Normal (weight 1): 24 hours minimum
     let min_time = match urgency_weight {
-        3 => 3600,       // 1 hour for critical
-        2 => 4 * 3600,   // 4 hours for urgent
-        _ => 24 * 3600,  // 24 hours for normal
+        3 => 3600,      // 1 hour for critical
+        2 => 4 * 3600,  // 4 hours for urgent
+        _ => 24 * 3600, // 24 hours for normal
     };
 
     if time_available < min_time {
@@ -187,6 +312,24 @@ pub fn validate_urgency_time_window(
     Ok(())
 }
 
+/// Validate blood type is valid
+pub fn validate_blood_type(_blood_type: &BloodType) -> Result<(), ContractError> {
+    // All BloodType variants are valid by construction
+    Ok(())
+}
+
+/// Check if request has exceeded its deadline
+pub fn is_request_overdue(required_by: u64, current_time: u64) -> bool {
+    current_time > required_by
+}
+
+/// Calculate time remaining until deadline in seconds
+///
+/// Clamped to the `i64` range instead of wrapping.
+pub fn time_until_deadline(required_by: u64, current_time: u64) -> i64 {
+    (required_by as i128 - current_time as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,6 +430,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_required_by_near_max() {
+        let env = setup_env_with_time(1000000);
+        assert_eq!(
+            validate_required_by(&env, u64::MAX),
+            Err(ContractError::InvalidRequiredBy)
+        );
+
+        // Window bounds would overflow at the end of time
+        let env = setup_env_with_time(u64::MAX - 10);
+        assert_eq!(
+            validate_required_by(&env, u64::MAX),
+            Err(ContractError::InvalidTimestamp)
+        );
+    }
+
+    #[test]
+    fn test_time_until_deadline_extremes() {
+        assert_eq!(time_until_deadline(u64::MAX, 0), i64::MAX);
+        assert_eq!(time_until_deadline(0, u64::MAX), i64::MIN);
+        assert_eq!(time_until_deadline(1000, 400), 600);
+        assert_eq!(time_until_deadline(400, 1000), -600);
+    }
+
+    #[test]
+    fn test_validate_urgency_time_window_past_deadline() {
+        let env = setup_env_with_time(u64::MAX);
+        assert_eq!(
+            validate_urgency_time_window(&env, 0, 3),
+            Err(ContractError::InvalidRequiredBy)
+        );
+    }
+
     #[test]
     fn test_validate_not_expired_valid() {
         let current_time = 1000000u64;
@@ -317,6 +493,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_quantity_step() {
+        assert!(validate_quantity_step(437, 0).is_ok());
+        assert!(validate_quantity_step(450, 450).is_ok());
+        assert!(validate_quantity_step(900, 450).is_ok());
+        assert_eq!(
+            validate_quantity_step(449, 450),
+            Err(ContractError::QuantityNotAligned)
+        );
+        assert_eq!(
+            validate_quantity_step(451, 450),
+            Err(ContractError::QuantityNotAligned)
+        );
+    }
+
     #[test]
     fn test_validate_delivery_address_valid() {
         let env = Env::default();
@@ -337,6 +528,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_delivery_address_whitespace() {
+        let env = Env::default();
+        let address = String::from_str(&env, " \t\n  ");
+        assert_eq!(
+            validate_delivery_address(&address),
+            Err(ContractError::InvalidDeliveryAddress)
+        );
+
+        let address = String::from_str(&env, "  Ward 3  ");
+        assert!(validate_delivery_address(&address).is_ok());
+    }
+
+    #[test]
+    fn test_validate_delivery_address_length_boundary() {
+        let env = Env::default();
+        let at_limit = [b'a'; MAX_DELIVERY_ADDRESS_LEN as usize];
+        let address = String::from_bytes(&env, &at_limit);
+        assert!(validate_delivery_address(&address).is_ok());
+
+        let over_limit = [b'a'; MAX_DELIVERY_ADDRESS_LEN as usize + 1];
+        let address = String::from_bytes(&env, &over_limit);
+        assert_eq!(
+            validate_delivery_address(&address),
+            Err(ContractError::DeliveryAddressTooLong)
+        );
+    }
+
     #[test]
     fn test_validate_urgency_time_window_critical() {
         let current_time = 1000000u64;
@@ -394,7 +613,14 @@ mod tests {
         let required_by = current_time + 7 * SECONDS_PER_DAY;
         let delivery_address = String::from_str(&env, "123 Hospital Street");
 
-        assert!(validate_request_creation(&env, quantity_ml, required_by, &delivery_address).is_ok());
+        assert!(validate_request_creation(
+            &env,
+            quantity_ml,
+            MIN_REQUEST_QUANTITY_ML,
+            required_by,
+            &delivery_address
+        )
+        .is_ok());
     }
 
     #[test]
@@ -406,7 +632,13 @@ mod tests {
         let delivery_address = String::from_str(&env, "123 Hospital Street");
 
         assert_eq!(
-            validate_request_creation(&env, 50, required_by, &delivery_address),
+            validate_request_creation(
+                &env,
+                50,
+                MIN_REQUEST_QUANTITY_ML,
+                required_by,
+                &delivery_address
+            ),
             Err(ContractError::InvalidQuantity)
         );
     }
@@ -421,7 +653,13 @@ mod tests {
 
         // Too soon
         assert_eq!(
-            validate_request_creation(&env, quantity_ml, current_time + 100, &delivery_address),
+            validate_request_creation(
+                &env,
+                quantity_ml,
+                MIN_REQUEST_QUANTITY_ML,
+                current_time + 100,
+                &delivery_address
+            ),
             Err(ContractError::InvalidRequiredBy)
         );
     }
@@ -436,22 +674,14 @@ mod tests {
         let delivery_address = String::from_str(&env, "");
 
         assert_eq!(
-            validate_request_creation(&env, quantity_ml, required_by, &delivery_address),
+            validate_request_creation(
+                &env,
+                quantity_ml,
+                MIN_REQUEST_QUANTITY_ML,
+                required_by,
+                &delivery_address
+            ),
             Err(ContractError::InvalidDeliveryAddress)
         );
     }
-/// Validate blood type is valid
-pub fn validate_blood_type(_blood_type: &BloodType) -> Result<(), ContractError> {
-    // All BloodType variants are valid by construction
-    Ok(())
-}
-
-/// Check if request has exceeded its deadline
-pub fn is_request_overdue(required_by: u64, current_time: u64) -> bool {
-    current_time > required_by
-}
-
-/// Calculate time remaining until deadline in seconds
-pub fn time_until_deadline(required_by: u64, current_time: u64) -> i64 {
-    required_by as i64 - current_time as i64
 }